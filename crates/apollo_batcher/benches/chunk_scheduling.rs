@@ -0,0 +1,146 @@
+//! Compares the fixed and adaptive chunk scheduling strategies of the block builder.
+//!
+//! The block builder loop is simulated in virtual time against an executor with a fixed number of
+//! workers, for several transaction cost profiles. For each strategy, the benchmark reports the
+//! number of transactions included in the block and how long after the deadline the block was
+//! closed (the executor waits for the transactions that are running when the block is closed).
+//!
+//! Run with `cargo bench -p apollo_batcher --bench chunk_scheduling`.
+
+use std::collections::VecDeque;
+use std::time::Duration;
+
+use apollo_batcher::chunk_scheduler::{ChunkDecision, ChunkScheduler, ChunkSchedulerConfig};
+use tokio::time::Instant;
+
+const N_WORKERS: usize = 4;
+const N_CONCURRENT_TXS: usize = 100;
+const BLOCK_TIME: Duration = Duration::from_secs(2);
+const TX_POLLING_INTERVAL: Duration = Duration::from_millis(10);
+/// The time it takes the block builder to add a chunk to the executor.
+const ADD_CHUNK_TIME: Duration = Duration::from_millis(1);
+
+struct Workload {
+    name: &'static str,
+    /// The execution time of the i-th transaction.
+    tx_duration: fn(usize) -> Duration,
+}
+
+const WORKLOADS: [Workload; 3] = [
+    Workload { name: "cheap", tx_duration: |_| Duration::from_millis(2) },
+    Workload { name: "expensive", tx_duration: |_| Duration::from_millis(150) },
+    Workload {
+        name: "mixed",
+        tx_duration: |i| {
+            if i % 20 == 19 { Duration::from_millis(400) } else { Duration::from_millis(3) }
+        },
+    },
+];
+
+struct Outcome {
+    n_included_txs: usize,
+    /// The time the block was closed at, relative to the deadline (in ms, negative if early).
+    close_time_millis: i128,
+}
+
+/// An executor whose workers run the transactions in order, each on the first free worker.
+struct SimulatedExecutor {
+    workers_free_at: Vec<Instant>,
+    /// The start and completion times of the transactions in progress, in order.
+    in_progress: VecDeque<(Instant, Instant)>,
+}
+
+impl SimulatedExecutor {
+    fn new(start: Instant) -> Self {
+        Self { workers_free_at: vec![start; N_WORKERS], in_progress: VecDeque::new() }
+    }
+
+    fn add_tx(&mut self, now: Instant, duration: Duration) {
+        let worker = self.workers_free_at.iter_mut().min().expect("There is at least one worker.");
+        let tx_start = (*worker).max(now);
+        *worker = tx_start + duration;
+        self.in_progress.push_back((tx_start, *worker));
+    }
+
+    /// Returns the number of transactions whose results became available, in order.
+    fn collect_results(&mut self, now: Instant) -> usize {
+        let mut n_results = 0;
+        while self.in_progress.front().is_some_and(|(_, completion)| *completion <= now) {
+            self.in_progress.pop_front();
+            n_results += 1;
+        }
+        n_results
+    }
+
+    /// Returns when the block closed at `now` is ready: the transactions running at `now` are
+    /// completed, and the rest are dropped.
+    fn close_time(&self, now: Instant) -> Instant {
+        self.in_progress
+            .iter()
+            .filter(|(tx_start, completion)| *tx_start <= now && *completion > now)
+            .map(|(_, completion)| *completion)
+            .max()
+            .unwrap_or(now)
+    }
+}
+
+fn simulate(config: ChunkSchedulerConfig, workload: &Workload) -> Outcome {
+    let start = Instant::now();
+    let deadline = start + BLOCK_TIME;
+    let mut scheduler = ChunkScheduler::new(config, N_CONCURRENT_TXS);
+    let mut executor = SimulatedExecutor::new(start);
+    let mut now = start;
+    let mut n_started_txs = 0;
+    let mut n_executed_txs = 0;
+
+    while now < deadline {
+        let n_results = executor.collect_results(now);
+        if n_results > 0 {
+            n_executed_txs += n_results;
+            scheduler.on_txs_executed(now, n_results, n_started_txs - n_executed_txs);
+        }
+
+        let n_txs_in_progress = n_started_txs - n_executed_txs;
+        match scheduler.next_chunk(n_txs_in_progress, deadline.saturating_duration_since(now)) {
+            ChunkDecision::Fetch(n_txs) => {
+                scheduler.on_txs_started(now, n_txs_in_progress);
+                for tx_index in n_started_txs..n_started_txs + n_txs {
+                    executor.add_tx(now, (workload.tx_duration)(tx_index));
+                }
+                n_started_txs += n_txs;
+                now += ADD_CHUNK_TIME;
+            }
+            ChunkDecision::Wait => now += TX_POLLING_INTERVAL,
+            ChunkDecision::Stop => break,
+        }
+    }
+
+    let close_time = executor.close_time(now);
+    let close_time_millis = if close_time >= deadline {
+        i128::try_from((close_time - deadline).as_millis()).expect("Duration should fit in i128.")
+    } else {
+        -i128::try_from((deadline - close_time).as_millis()).expect("Duration should fit in i128.")
+    };
+    Outcome { n_included_txs: n_executed_txs, close_time_millis }
+}
+
+fn main() {
+    let strategies = [
+        ("fixed", ChunkSchedulerConfig::default()),
+        ("adaptive", ChunkSchedulerConfig { adaptive: true, ..Default::default() }),
+    ];
+
+    println!(
+        "{:<10} {:<10} {:>12} {:>22}",
+        "workload", "strategy", "included_txs", "close_vs_deadline_ms"
+    );
+    for workload in &WORKLOADS {
+        for (strategy_name, config) in strategies {
+            let outcome = simulate(config, workload);
+            println!(
+                "{:<10} {:<10} {:>12} {:>22}",
+                workload.name, strategy_name, outcome.n_included_txs, outcome.close_time_millis
+            );
+        }
+    }
+}
@@ -1,15 +1,19 @@
 use std::collections::HashMap;
 use std::sync::Arc;
+use std::time::Duration;
 
 use apollo_batcher_types::batcher_types::{
     BatcherResult,
     CentralObjects,
     DecisionReachedInput,
     DecisionReachedResponse,
+    ExecutionProfile,
+    ExecutionProfilesFilter,
     GetHeightResponse,
     GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
+    PreconfirmedTransactionStatus,
     ProposalCommitment,
     ProposalId,
     ProposalStatus,
@@ -21,6 +25,7 @@ use apollo_batcher_types::batcher_types::{
     StartHeightInput,
     ValidateBlockInput,
 };
+use apollo_batcher_types::communication::SharedPreconfirmedBlockSource;
 use apollo_batcher_types::errors::BatcherError;
 use apollo_class_manager_types::transaction_converter::TransactionConverter;
 use apollo_class_manager_types::SharedClassManagerClient;
@@ -28,9 +33,14 @@ use apollo_infra::component_definitions::{default_component_start_fn, ComponentS
 use apollo_l1_provider_types::errors::{L1ProviderClientError, L1ProviderError};
 use apollo_l1_provider_types::{SessionState, SharedL1ProviderClient};
 use apollo_mempool_types::communication::SharedMempoolClient;
-use apollo_mempool_types::mempool_types::CommitBlockArgs;
+use apollo_mempool_types::mempool_types::{CommitBlockArgs, RevertBlockArgs};
 use apollo_reverts::revert_block;
 use apollo_state_sync_types::state_sync_types::SyncBlock;
+use apollo_storage::committed_transactions::{
+    CommittedBlockTransactions,
+    CommittedTransactionsStorageReader,
+    CommittedTransactionsStorageWriter,
+};
 use apollo_storage::state::{StateStorageReader, StateStorageWriter};
 use async_trait::async_trait;
 use blockifier::concurrency::worker_pool::WorkerPool;
@@ -39,10 +49,12 @@ use futures::FutureExt;
 use indexmap::IndexSet;
 #[cfg(test)]
 use mockall::automock;
-use starknet_api::block::{BlockHeaderWithoutHash, BlockNumber};
+use serde_json::Value;
+use starknet_api::block::{BlockHeaderWithoutHash, BlockInfo, BlockNumber};
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
 use starknet_api::core::{ContractAddress, Nonce};
-use starknet_api::state::ThinStateDiff;
+use starknet_api::rpc_transaction::InternalRpcTransaction;
+use starknet_api::state::{StateNumber, ThinStateDiff};
 use starknet_api::transaction::TransactionHash;
 use tokio::sync::Mutex;
 use tracing::{debug, error, info, instrument, trace, Instrument};
@@ -58,6 +70,11 @@ use crate::block_builder::{
 };
 use crate::cende_client_types::CendeBlockMetadata;
 use crate::config::BatcherConfig;
+use crate::execution_profile::{
+    execution_profile,
+    log_execution_profile_summary,
+    ExecutionProfileLog,
+};
 use crate::metrics::{
     register_metrics,
     ProposalMetricsHandle,
@@ -77,7 +94,22 @@ use crate::pre_confirmed_block_writer::{
     PreconfirmedBlockWriterTrait,
 };
 use crate::pre_confirmed_cende_client::PreconfirmedCendeClientTrait;
-use crate::transaction_provider::{ProposeTransactionProvider, ValidateTransactionProvider};
+use crate::proposal_recorder::{ProposalRecorder, RecordedProposalInput};
+use crate::speculative_execution::{
+    executed_txs_channel,
+    state_maps_from,
+    SpeculatedTransactionProvider,
+    Speculation,
+    SpeculationHit,
+    SpeculationResult,
+    SpeculativeTransactionProvider,
+    StateLayer,
+};
+use crate::transaction_provider::{
+    ProposeTransactionProvider,
+    TransactionProvider,
+    ValidateTransactionProvider,
+};
 use crate::utils::{
     deadline_as_instant,
     proposal_status_from,
@@ -104,6 +136,22 @@ pub struct Batcher {
     /// Used to create pre-confirmed block writers.
     pre_confirmed_block_writer_factory: Box<dyn PreconfirmedBlockWriterFactoryTrait>,
 
+    /// Serves the pre-confirmed blocks, if they are published in-process.
+    pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
+
+    /// Records the proposals for offline replay, if enabled.
+    proposal_recorder: Option<ProposalRecorder>,
+
+    /// The block info of the proposals built in the current height, used to speculatively execute
+    /// the next height once they are built. Only kept if speculative execution is enabled.
+    speculation_candidates: HashMap<ProposalId, BlockInfo>,
+    /// The speculative execution of the next height, if any.
+    speculation: Option<Speculation>,
+    /// What is kept from a speculative execution that matched the decision. Its state layer is
+    /// used by the block builders of the height it was built for, and its executed transactions
+    /// start the first proposal of that height.
+    speculation_hit: Option<SpeculationHit>,
+
     /// The height that the batcher is currently working on.
     /// All proposals are considered to be at this height.
     active_height: Option<BlockNumber>,
@@ -117,6 +165,10 @@ pub struct Batcher {
     /// Holds all the proposals that completed execution in the current height.
     executed_proposals: Arc<Mutex<HashMap<ProposalId, ProposalResult<BlockExecutionArtifacts>>>>,
 
+    /// The execution profiles of the most recent proposals that completed execution, across
+    /// heights.
+    execution_profiles: Arc<Mutex<ExecutionProfileLog>>,
+
     /// The propose blocks transaction streams, used to stream out the proposal transactions.
     /// Each stream is kept until all the transactions are streamed out, or a new height is
     /// started.
@@ -139,7 +191,17 @@ impl Batcher {
         transaction_converter: TransactionConverter,
         block_builder_factory: Box<dyn BlockBuilderFactoryTrait>,
         pre_confirmed_block_writer_factory: Box<dyn PreconfirmedBlockWriterFactoryTrait>,
+        pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
     ) -> Self {
+        let proposal_recorder = config.proposal_recorder_config.enabled.then(|| {
+            ProposalRecorder::new(
+                config.proposal_recorder_config.clone(),
+                config.block_builder_config.clone(),
+            )
+        });
+        let execution_profiles = Arc::new(Mutex::new(ExecutionProfileLog::new(
+            config.execution_profile_config.n_retained_profiles,
+        )));
         Self {
             config,
             storage_reader,
@@ -149,10 +211,16 @@ impl Batcher {
             transaction_converter,
             block_builder_factory,
             pre_confirmed_block_writer_factory,
+            pre_confirmed_block_source,
+            proposal_recorder,
+            speculation_candidates: HashMap::new(),
+            speculation: None,
+            speculation_hit: None,
             active_height: None,
             active_proposal: Arc::new(Mutex::new(None)),
             active_proposal_task: None,
             executed_proposals: Arc::new(Mutex::new(HashMap::new())),
+            execution_profiles,
             propose_tx_streams: HashMap::new(),
             validate_tx_streams: HashMap::new(),
         }
@@ -193,6 +261,7 @@ impl Batcher {
             block_number,
             propose_block_input.retrospective_block_hash,
         )?;
+        self.stop_speculation(None).await;
 
         // TODO(yair): extract function for the following calls, use join_all.
         info!(
@@ -230,12 +299,28 @@ impl Batcher {
                 BatcherError::NotReady
             })?;
 
-        let tx_provider = ProposeTransactionProvider::new(
-            self.mempool_client.clone(),
-            self.l1_provider_client.clone(),
-            self.config.max_l1_handler_txs_per_block_proposal,
-            propose_block_input.block_info.block_number,
-        );
+        let recording = self.proposal_recorder.as_ref().map(|proposal_recorder| {
+            proposal_recorder.start(RecordedProposalInput::Propose(propose_block_input.clone()))
+        });
+        let mut tx_provider: Box<dyn TransactionProvider> =
+            Box::new(ProposeTransactionProvider::new(
+                self.mempool_client.clone(),
+                self.l1_provider_client.clone(),
+                self.config.max_l1_handler_txs_per_block_proposal,
+                propose_block_input.block_info.block_number,
+            ));
+        let speculated_txs = self.take_speculated_txs(block_number);
+        if !speculated_txs.is_empty() {
+            info!(
+                "Starting proposal {} with {} transactions executed speculatively.",
+                propose_block_input.proposal_id,
+                speculated_txs.len()
+            );
+            tx_provider = Box::new(SpeculatedTransactionProvider::new(speculated_txs, tx_provider));
+        }
+        if let Some(recording) = &recording {
+            tx_provider = recording.record_tx_provider(tx_provider);
+        }
 
         // A channel to receive the transactions included in the proposed block.
         let (output_tx_sender, output_tx_receiver) = tokio::sync::mpsc::unbounded_channel();
@@ -248,18 +333,23 @@ impl Batcher {
                 cende_block_metadata,
             );
 
-        let (block_builder, abort_signal_sender) = self
+        if self.config.speculative_execution_config.enabled {
+            self.speculation_candidates
+                .insert(propose_block_input.proposal_id, propose_block_input.block_info.clone());
+        }
+        let (mut block_builder, abort_signal_sender) = self
             .block_builder_factory
             .create_block_builder(
                 BlockMetadata {
                     block_info: propose_block_input.block_info,
                     retrospective_block_hash: propose_block_input.retrospective_block_hash,
                 },
+                self.state_layer_for(block_number),
                 BlockBuilderExecutionParams {
                     deadline: deadline_as_instant(propose_block_input.deadline)?,
                     is_validator: false,
                 },
-                Box::new(tx_provider),
+                tx_provider,
                 Some(output_tx_sender),
                 Some(candidate_tx_sender),
                 Some(pre_confirmed_tx_sender),
@@ -269,6 +359,9 @@ impl Batcher {
                 error!("Failed to get block builder: {}", err);
                 BatcherError::InternalError
             })?;
+        if let Some(recording) = recording {
+            block_builder = recording.record_block_builder(block_builder);
+        }
 
         self.spawn_proposal(
             propose_block_input.proposal_id,
@@ -303,6 +396,7 @@ impl Batcher {
             validate_block_input.block_info.block_number,
             validate_block_input.retrospective_block_hash,
         )?;
+        self.stop_speculation(None).await;
 
         self.l1_provider_client
             .start_block(SessionState::Validate, validate_block_input.block_info.block_number)
@@ -321,24 +415,33 @@ impl Batcher {
         let (final_n_executed_txs_sender, final_n_executed_txs_receiver) =
             tokio::sync::oneshot::channel();
 
-        let tx_provider = ValidateTransactionProvider::new(
-            input_tx_receiver,
-            final_n_executed_txs_receiver,
-            self.l1_provider_client.clone(),
-            validate_block_input.block_info.block_number,
-        );
-        let (block_builder, abort_signal_sender) = self
+        let recording = self.proposal_recorder.as_ref().map(|proposal_recorder| {
+            proposal_recorder.start(RecordedProposalInput::Validate(validate_block_input.clone()))
+        });
+        let mut tx_provider: Box<dyn TransactionProvider> =
+            Box::new(ValidateTransactionProvider::new(
+                input_tx_receiver,
+                final_n_executed_txs_receiver,
+                self.l1_provider_client.clone(),
+                validate_block_input.block_info.block_number,
+            ));
+        if let Some(recording) = &recording {
+            tx_provider = recording.record_tx_provider(tx_provider);
+        }
+        let state_layer = self.state_layer_for(validate_block_input.block_info.block_number);
+        let (mut block_builder, abort_signal_sender) = self
             .block_builder_factory
             .create_block_builder(
                 BlockMetadata {
                     block_info: validate_block_input.block_info,
                     retrospective_block_hash: validate_block_input.retrospective_block_hash,
                 },
+                state_layer,
                 BlockBuilderExecutionParams {
                     deadline: deadline_as_instant(validate_block_input.deadline)?,
                     is_validator: true,
                 },
-                Box::new(tx_provider),
+                tx_provider,
                 None,
                 None,
                 None,
@@ -348,6 +451,9 @@ impl Batcher {
                 error!("Failed to get block builder: {}", err);
                 BatcherError::InternalError
             })?;
+        if let Some(recording) = recording {
+            block_builder = recording.record_block_builder(block_builder);
+        }
 
         self.spawn_proposal(
             validate_block_input.proposal_id,
@@ -394,6 +500,8 @@ impl Batcher {
     /// Clear all the proposals from the previous height.
     async fn abort_active_height(&mut self) {
         self.abort_active_proposal().await;
+        self.stop_speculation(None).await;
+        self.speculation_candidates.clear();
         self.executed_proposals.lock().await.clear();
         self.propose_tx_streams.clear();
         self.validate_tx_streams.clear();
@@ -478,6 +586,37 @@ impl Batcher {
         })
     }
 
+    /// Returns the execution profiles of the most recent proposals that match the given filter,
+    /// oldest first.
+    #[instrument(skip(self))]
+    pub async fn get_execution_profiles(
+        &self,
+        filter: &ExecutionProfilesFilter,
+    ) -> Vec<ExecutionProfile> {
+        self.execution_profiles.lock().await.query(filter)
+    }
+
+    /// Returns the latest pre-confirmed block, if the pre-confirmed blocks are published
+    /// in-process.
+    #[instrument(skip(self), err)]
+    pub fn get_latest_preconfirmed_block(&self) -> BatcherResult<Option<Value>> {
+        Ok(self.pre_confirmed_block_source()?.latest_block())
+    }
+
+    /// Returns the status of the transaction in the latest pre-confirmed block, if the
+    /// pre-confirmed blocks are published in-process.
+    #[instrument(skip(self), err)]
+    pub fn get_preconfirmed_transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> BatcherResult<Option<PreconfirmedTransactionStatus>> {
+        Ok(self.pre_confirmed_block_source()?.transaction_status(tx_hash))
+    }
+
+    fn pre_confirmed_block_source(&self) -> BatcherResult<&SharedPreconfirmedBlockSource> {
+        self.pre_confirmed_block_source.as_ref().ok_or(BatcherError::PreconfirmedBlocksNotInProcess)
+    }
+
     #[instrument(skip(self), err)]
     pub async fn get_height(&self) -> BatcherResult<GetHeightResponse> {
         let height = self.get_height_from_storage()?;
@@ -520,6 +659,7 @@ impl Batcher {
             "BATCHER_FIN_PROPOSER: Finished building proposal {proposal_id} with \
              {final_n_executed_txs} transactions."
         );
+        self.start_speculation(proposal_id).await;
         Ok(GetProposalContentResponse {
             content: GetProposalContent::Finished { id: commitment, final_n_executed_txs },
         })
@@ -548,12 +688,16 @@ impl Batcher {
             info!("Aborting all work on height {} due to state sync.", height);
             self.abort_active_height().await;
         }
+        self.speculation_hit = None;
 
         let address_to_nonce = state_diff.nonces.iter().map(|(k, v)| (*k, *v)).collect();
+        // Sync blocks carry only the hashes of their account transactions, so these cannot be
+        // returned to the mempool if the block is reverted.
         self.commit_proposal_and_block(
             height,
             state_diff,
             address_to_nonce,
+            Vec::new(),
             l1_transaction_hashes.iter().copied().collect(),
             Default::default(),
         )
@@ -580,6 +724,11 @@ impl Batcher {
                 error!("Failed to get block execution artifacts: {}", err);
                 BatcherError::InternalError
             })?;
+        let speculation_hit = self.stop_speculation(Some(proposal_id)).await;
+        self.speculation_hit = None;
+        if self.config.execution_profile_config.log_on_decision {
+            log_execution_profile_summary(proposal_id, &block_execution_artifacts);
+        }
         let state_diff = block_execution_artifacts.thin_state_diff();
         let n_txs = u64::try_from(block_execution_artifacts.tx_hashes().len())
             .expect("Number of transactions should fit in u64");
@@ -599,10 +748,16 @@ impl Batcher {
             height,
             state_diff.clone(),
             block_execution_artifacts.address_to_nonce(),
+            block_execution_artifacts.account_transactions,
             block_execution_artifacts.execution_data.consumed_l1_handler_tx_hashes,
             block_execution_artifacts.execution_data.rejected_tx_hashes,
         )
         .await?;
+        // The writes of the state layer are now in the storage.
+        self.speculation_hit = speculation_hit.map(|hit| SpeculationHit {
+            state_layer: hit.state_layer.on_storage_height(height.unchecked_next()),
+            ..hit
+        });
         let execution_infos = block_execution_artifacts.execution_data.execution_infos;
 
         LAST_BATCHED_BLOCK.set_lossy(height.0);
@@ -632,6 +787,7 @@ impl Batcher {
         height: BlockNumber,
         state_diff: ThinStateDiff,
         address_to_nonce: HashMap<ContractAddress, Nonce>,
+        account_transactions: Vec<InternalRpcTransaction>,
         consumed_l1_handler_tx_hashes: IndexSet<TransactionHash>,
         rejected_tx_hashes: IndexSet<TransactionHash>,
     ) -> BatcherResult<()> {
@@ -641,11 +797,25 @@ impl Batcher {
         );
         trace!("Rejected transactions: {:#?}, State diff: {:#?}.", rejected_tx_hashes, state_diff);
 
-        // Commit the proposal to the storage.
-        self.storage_writer.commit_proposal(height, state_diff).map_err(|err| {
+        // Commit the proposal to the storage, along with its transactions in case it is reverted.
+        let transactions = CommittedBlockTransactions {
+            account_transactions,
+            l1_handler_transaction_hashes: consumed_l1_handler_tx_hashes.iter().copied().collect(),
+        };
+        self.storage_writer.commit_proposal(height, state_diff, &transactions).map_err(|err| {
             error!("Failed to commit proposal to storage: {}", err);
             BatcherError::InternalError
         })?;
+        let retention_block_count = u64::try_from(self.config.committed_txs_retention_block_count)
+            .expect("The retention block count should fit in u64");
+        if let Some(pruned_height) = height.0.checked_sub(retention_block_count) {
+            if let Err(err) =
+                self.storage_writer.delete_committed_transactions(BlockNumber(pruned_height))
+            {
+                // Recoverable error, the transactions are kept in the storage.
+                error!("Failed to delete the transactions of block {pruned_height}: {err}");
+            }
+        }
 
         // Notify the L1 provider of the new block.
         let rejected_l1_handler_tx_hashes = rejected_tx_hashes
@@ -699,6 +869,92 @@ impl Batcher {
         Ok(())
     }
 
+    /// Returns the state layer to build blocks of the given height on, if one was kept.
+    fn state_layer_for(&self, height: BlockNumber) -> Option<StateLayer> {
+        self.speculation_hit
+            .as_ref()
+            .map(|hit| &hit.state_layer)
+            .filter(|state_layer| state_layer.storage_height == height)
+            .cloned()
+    }
+
+    /// Takes the transactions executed speculatively for the given height, if any were kept.
+    fn take_speculated_txs(&mut self, height: BlockNumber) -> Vec<InternalConsensusTransaction> {
+        self.speculation_hit
+            .as_mut()
+            .filter(|hit| hit.state_layer.storage_height == height)
+            .map(|hit| std::mem::take(&mut hit.executed_txs))
+            .unwrap_or_default()
+    }
+
+    /// Starts executing the next height on top of the given proposal, if it is a speculation
+    /// candidate.
+    async fn start_speculation(&mut self, proposal_id: ProposalId) {
+        let Some(block_info) = self.speculation_candidates.remove(&proposal_id) else {
+            return;
+        };
+        if self.active_proposal.lock().await.is_some() {
+            debug!("Not speculating on proposal {proposal_id}, another proposal is in progress.");
+            return;
+        }
+        let writes = match self.executed_proposals.lock().await.get(&proposal_id) {
+            Some(Ok(artifacts)) => state_maps_from(&artifacts.commitment_state_diff),
+            _ => return,
+        };
+        self.stop_speculation(None).await;
+
+        // The proposal is executed on top of the storage, so its writes are layered on top of the
+        // storage at its height.
+        let state_layer = StateLayer::new(block_info.block_number, writes);
+        let block_metadata = BlockMetadata {
+            block_info: BlockInfo {
+                block_number: block_info.block_number.unchecked_next(),
+                ..block_info
+            },
+            retrospective_block_hash: None,
+        };
+        let deadline = tokio::time::Instant::now()
+            + Duration::from_millis(self.config.speculative_execution_config.max_duration_millis);
+        let (executed_txs_sender, executed_txs_receiver) = executed_txs_channel();
+        let block_builder = self.block_builder_factory.create_block_builder(
+            block_metadata,
+            Some(state_layer.clone()),
+            BlockBuilderExecutionParams { deadline, is_validator: false },
+            Box::new(SpeculativeTransactionProvider::new(self.mempool_client.clone())),
+            None,
+            None,
+            Some(executed_txs_sender),
+            tokio::runtime::Handle::current(),
+        );
+        match block_builder {
+            Ok((block_builder, abort_signal_sender)) => {
+                self.speculation = Some(Speculation::start(
+                    proposal_id,
+                    state_layer,
+                    block_builder,
+                    abort_signal_sender,
+                    executed_txs_receiver,
+                ));
+            }
+            Err(err) => error!("Failed to start speculative execution: {}", err),
+        }
+    }
+
+    /// Stops the speculative execution, if any. Returns what is kept from it if
+    /// `decided_proposal_id` is the proposal it was built on.
+    async fn stop_speculation(
+        &mut self,
+        decided_proposal_id: Option<ProposalId>,
+    ) -> Option<SpeculationHit> {
+        let speculation = self.speculation.take()?;
+        let result = match decided_proposal_id {
+            Some(proposal_id) if proposal_id == speculation.proposal_id() => SpeculationResult::Hit,
+            Some(_) => SpeculationResult::Miss,
+            None => SpeculationResult::Interrupted,
+        };
+        speculation.stop(result).await
+    }
+
     async fn is_active(&self, proposal_id: ProposalId) -> bool {
         *self.active_proposal.lock().await == Some(proposal_id)
     }
@@ -735,17 +991,32 @@ impl Batcher {
         pre_confirmed_block_writer: Option<Box<dyn PreconfirmedBlockWriterTrait>>,
         mut proposal_metrics_handle: ProposalMetricsHandle,
     ) -> BatcherResult<()> {
+        let height = self.active_height.ok_or(BatcherError::NoActiveHeight)?;
         self.set_active_proposal(proposal_id).await?;
         info!("Starting generation of a new proposal with id {}.", proposal_id);
 
         let active_proposal = self.active_proposal.clone();
         let executed_proposals = self.executed_proposals.clone();
+        let execution_profiles = self.execution_profiles.clone();
+        // Only validated proposals get the final number of transactions from the proposer.
+        let is_validator = final_n_executed_txs_sender.is_some();
+        let block_capacity = self.config.block_builder_config.bouncer_config.block_max_capacity;
 
         let execution_join_handle = tokio::spawn(
             async move {
                 let result = match block_builder.build_block().await {
                     Ok(artifacts) => {
                         proposal_metrics_handle.set_succeeded();
+                        let mut execution_profiles = execution_profiles.lock().await;
+                        if execution_profiles.is_enabled() {
+                            execution_profiles.record(execution_profile(
+                                proposal_id,
+                                height,
+                                is_validator,
+                                &artifacts,
+                                block_capacity,
+                            ));
+                        }
                         Ok(artifacts)
                     }
                     Err(BlockBuilderError::Aborted) => {
@@ -866,14 +1137,42 @@ impl Batcher {
             });
         }
 
+        let address_to_nonce = self.storage_reader.nonces_before_block(height).map_err(|err| {
+            error!("Failed to get the nonces preceding block {height} from storage: {err}");
+            BatcherError::InternalError
+        })?;
+        let CommittedBlockTransactions { account_transactions, l1_handler_transaction_hashes } =
+            self.storage_reader.committed_block_transactions(height).map_err(|err| {
+                error!("Failed to get the transactions of block {height} from storage: {err}");
+                BatcherError::InternalError
+            })?;
+
         if let Some(height) = self.active_height {
             info!("Aborting all work on height {} due to a revert request.", height);
             self.abort_active_height().await;
         }
+        self.speculation_hit = None;
 
         self.storage_writer.revert_block(height);
         STORAGE_HEIGHT.decrement(1);
         REVERTED_BLOCKS.increment(1);
+
+        // Return the block's transactions to the mempool and the L1 provider, so that they are
+        // included in a later block.
+        let mempool_result = self
+            .mempool_client
+            .revert_block(RevertBlockArgs { address_to_nonce, reverted_txs: account_transactions })
+            .await;
+        if let Err(mempool_err) = mempool_result {
+            // Recoverable error, mempool will reject the reverted nonces until they are retained.
+            error!("Failed to revert block in mempool: {}", mempool_err);
+        }
+        if let Err(err) =
+            self.l1_provider_client.revert_block(height, l1_handler_transaction_hashes).await
+        {
+            // Recoverable error, the L1 handler transactions of the block remain committed.
+            error!("Failed to revert block in L1 provider: {:?}", err);
+        }
         Ok(())
     }
 }
@@ -884,6 +1183,7 @@ pub fn create_batcher(
     l1_provider_client: SharedL1ProviderClient,
     class_manager_client: SharedClassManagerClient,
     pre_confirmed_cende_client: Arc<dyn PreconfirmedCendeClientTrait>,
+    pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
 ) -> Batcher {
     let (storage_reader, storage_writer) = apollo_storage::open_storage(config.storage.clone())
         .expect("Failed to open batcher's storage");
@@ -917,6 +1217,7 @@ pub fn create_batcher(
         transaction_converter,
         block_builder_factory,
         pre_confirmed_block_writer_factory,
+        pre_confirmed_block_source,
     )
 }
 
@@ -924,12 +1225,54 @@ pub fn create_batcher(
 pub trait BatcherStorageReaderTrait: Send + Sync {
     /// Returns the next height that the batcher should work on.
     fn height(&self) -> apollo_storage::StorageResult<BlockNumber>;
+
+    /// Returns the nonces, right before the given block, of the accounts whose nonce was updated
+    /// in it.
+    fn nonces_before_block(
+        &self,
+        height: BlockNumber,
+    ) -> apollo_storage::StorageResult<HashMap<ContractAddress, Nonce>>;
+
+    /// Returns the transactions of the given committed block. Returns no transactions if they
+    /// were not stored, e.g., for blocks committed before they were kept.
+    fn committed_block_transactions(
+        &self,
+        height: BlockNumber,
+    ) -> apollo_storage::StorageResult<CommittedBlockTransactions>;
 }
 
 impl BatcherStorageReaderTrait for apollo_storage::StorageReader {
     fn height(&self) -> apollo_storage::StorageResult<BlockNumber> {
         self.begin_ro_txn()?.get_state_marker()
     }
+
+    fn nonces_before_block(
+        &self,
+        height: BlockNumber,
+    ) -> apollo_storage::StorageResult<HashMap<ContractAddress, Nonce>> {
+        let txn = self.begin_ro_txn()?;
+        let Some(state_diff) = txn.get_state_diff(height)? else {
+            return Ok(HashMap::new());
+        };
+
+        let state_reader = txn.get_state_reader()?;
+        let state_number = StateNumber::right_before_block(height);
+        state_diff
+            .nonces
+            .keys()
+            .map(|address| {
+                let nonce = state_reader.get_nonce_at(state_number, address)?.unwrap_or_default();
+                Ok((*address, nonce))
+            })
+            .collect()
+    }
+
+    fn committed_block_transactions(
+        &self,
+        height: BlockNumber,
+    ) -> apollo_storage::StorageResult<CommittedBlockTransactions> {
+        Ok(self.begin_ro_txn()?.get_committed_block_transactions(height)?.unwrap_or_default())
+    }
 }
 
 #[cfg_attr(test, automock)]
@@ -938,6 +1281,14 @@ pub trait BatcherStorageWriterTrait: Send + Sync {
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        transactions: &CommittedBlockTransactions,
+    ) -> apollo_storage::StorageResult<()>;
+
+    /// Deletes the stored transactions of the given committed block; it can no longer be reverted
+    /// with its transactions returned.
+    fn delete_committed_transactions(
+        &mut self,
+        height: BlockNumber,
     ) -> apollo_storage::StorageResult<()>;
 
     fn revert_block(&mut self, height: BlockNumber);
@@ -948,14 +1299,28 @@ impl BatcherStorageWriterTrait for apollo_storage::StorageWriter {
         &mut self,
         height: BlockNumber,
         state_diff: ThinStateDiff,
+        transactions: &CommittedBlockTransactions,
     ) -> apollo_storage::StorageResult<()> {
         // TODO(AlonH): write casms.
-        self.begin_rw_txn()?.append_state_diff(height, state_diff)?.commit()
+        self.begin_rw_txn()?
+            .append_state_diff(height, state_diff)?
+            .set_committed_block_transactions(height, transactions)?
+            .commit()
+    }
+
+    fn delete_committed_transactions(
+        &mut self,
+        height: BlockNumber,
+    ) -> apollo_storage::StorageResult<()> {
+        self.begin_rw_txn()?.delete_committed_block_transactions(height)?.commit()
     }
 
     // This function will panic if there is a storage failure to revert the block.
     fn revert_block(&mut self, height: BlockNumber) {
         revert_block(self, height);
+        self.delete_committed_transactions(height).unwrap_or_else(|err| {
+            panic!("Failed to delete the transactions of reverted block {height}: {err}")
+        });
     }
 }
 
@@ -4,10 +4,12 @@ use std::sync::Arc;
 use apollo_batcher_types::batcher_types::{
     DecisionReachedInput,
     DecisionReachedResponse,
+    ExecutionProfilesFilter,
     GetHeightResponse,
     GetProposalContent,
     GetProposalContentInput,
     GetProposalContentResponse,
+    PreconfirmedTransactionStatus,
     ProposalCommitment,
     ProposalId,
     ProposalStatus,
@@ -19,6 +21,10 @@ use apollo_batcher_types::batcher_types::{
     StartHeightInput,
     ValidateBlockInput,
 };
+use apollo_batcher_types::communication::{
+    MockPreconfirmedBlockSource,
+    SharedPreconfirmedBlockSource,
+};
 use apollo_batcher_types::errors::BatcherError;
 use apollo_class_manager_types::transaction_converter::TransactionConverter;
 use apollo_class_manager_types::{EmptyClassManagerClient, SharedClassManagerClient};
@@ -27,8 +33,9 @@ use apollo_infra::component_definitions::ComponentStarter;
 use apollo_l1_provider_types::errors::{L1ProviderClientError, L1ProviderError};
 use apollo_l1_provider_types::{MockL1ProviderClient, SessionState};
 use apollo_mempool_types::communication::{MempoolClientError, MockMempoolClient};
-use apollo_mempool_types::mempool_types::CommitBlockArgs;
+use apollo_mempool_types::mempool_types::{CommitBlockArgs, RevertBlockArgs};
 use apollo_state_sync_types::state_sync_types::SyncBlock;
+use apollo_storage::committed_transactions::CommittedBlockTransactions;
 use assert_matches::assert_matches;
 use blockifier::abi::constants;
 use indexmap::{indexmap, IndexSet};
@@ -53,10 +60,12 @@ use crate::block_builder::{
     BlockExecutionArtifacts,
     FailOnErrorCause,
     MockBlockBuilderFactoryTrait,
+    MockBlockBuilderTrait,
 };
 use crate::config::BatcherConfig;
 use crate::metrics::{
     BATCHED_TRANSACTIONS,
+    LABEL_NAME_SPECULATION_RESULT,
     LAST_SYNCED_BLOCK,
     PROPOSAL_ABORTED,
     PROPOSAL_FAILED,
@@ -65,6 +74,7 @@ use crate::metrics::{
     REJECTED_TRANSACTIONS,
     REVERTED_BLOCKS,
     REVERTED_TRANSACTIONS,
+    SPECULATIONS,
     STORAGE_HEIGHT,
     SYNCED_TRANSACTIONS,
 };
@@ -72,6 +82,7 @@ use crate::pre_confirmed_block_writer::{
     MockPreconfirmedBlockWriterFactoryTrait,
     MockPreconfirmedBlockWriterTrait,
 };
+use crate::speculative_execution::{SpeculationResult, SpeculativeExecutionConfig};
 use crate::test_utils::{
     test_txs,
     verify_indexed_execution_infos,
@@ -118,6 +129,7 @@ struct MockDependencies {
     l1_provider_client: MockL1ProviderClient,
     block_builder_factory: MockBlockBuilderFactoryTrait,
     pre_confirmed_block_writer_factory: MockPreconfirmedBlockWriterFactoryTrait,
+    pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
     class_manager_client: SharedClassManagerClient,
 }
 
@@ -154,6 +166,7 @@ impl Default for MockDependencies {
             mempool_client,
             block_builder_factory,
             pre_confirmed_block_writer_factory,
+            pre_confirmed_block_source: None,
             // TODO(noamsp): use MockClassManagerClient
             class_manager_client: Arc::new(EmptyClassManagerClient),
         }
@@ -161,8 +174,19 @@ impl Default for MockDependencies {
 }
 
 async fn create_batcher(mock_dependencies: MockDependencies) -> Batcher {
-    let mut batcher = Batcher::new(
+    create_batcher_with_config(
         BatcherConfig { outstream_content_buffer_size: STREAMING_CHUNK_SIZE, ..Default::default() },
+        mock_dependencies,
+    )
+    .await
+}
+
+async fn create_batcher_with_config(
+    config: BatcherConfig,
+    mock_dependencies: MockDependencies,
+) -> Batcher {
+    let mut batcher = Batcher::new(
+        config,
         Arc::new(mock_dependencies.storage_reader),
         Box::new(mock_dependencies.storage_writer),
         Arc::new(mock_dependencies.l1_provider_client),
@@ -173,6 +197,7 @@ async fn create_batcher(mock_dependencies: MockDependencies) -> Batcher {
         ),
         Box::new(mock_dependencies.block_builder_factory),
         Box::new(mock_dependencies.pre_confirmed_block_writer_factory),
+        mock_dependencies.pre_confirmed_block_source,
     );
     // Call post-creation functionality (e.g., metrics registration).
     batcher.start().await;
@@ -198,7 +223,7 @@ fn mock_create_builder_for_validate_block(
     build_block_result: BlockBuilderResult<BlockExecutionArtifacts>,
 ) {
     block_builder_factory.expect_create_block_builder().times(1).return_once(
-        |_, _, tx_provider, _, _, _, _| {
+        |_, _, _, tx_provider, _, _, _, _| {
             let block_builder = FakeValidateBlockBuilder {
                 tx_provider,
                 build_block_result: Some(build_block_result),
@@ -214,7 +239,7 @@ fn mock_create_builder_for_propose_block(
     build_block_result: BlockBuilderResult<BlockExecutionArtifacts>,
 ) {
     block_builder_factory.expect_create_block_builder().times(1).return_once(
-        move |_, _, _, output_content_sender, _, _, _| {
+        move |_, _, _, _, output_content_sender, _, _, _| {
             let block_builder = FakeProposeBlockBuilder {
                 output_content_sender: output_content_sender.unwrap(),
                 output_txs,
@@ -688,6 +713,84 @@ async fn get_height() {
     assert_eq!(result, GetHeightResponse { height: INITIAL_HEIGHT });
 }
 
+#[tokio::test]
+async fn execution_profile_of_proposal() {
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.l1_provider_client.expect_start_block().returning(|_, _| Ok(()));
+    mock_create_builder_for_propose_block(
+        &mut mock_dependencies.block_builder_factory,
+        vec![],
+        Ok(BlockExecutionArtifacts::create_for_testing()),
+    );
+    let mut batcher = create_batcher(mock_dependencies).await;
+
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher.propose_block(propose_block_input(PROPOSAL_ID)).await.unwrap();
+    batcher.await_active_proposal(DUMMY_FINAL_N_EXECUTED_TXS).await.unwrap();
+
+    let profiles = batcher
+        .get_execution_profiles(&ExecutionProfilesFilter {
+            height: Some(INITIAL_HEIGHT),
+            ..Default::default()
+        })
+        .await;
+    assert_eq!(profiles.len(), 1);
+    assert_eq!(profiles[0].proposal_id, PROPOSAL_ID);
+    assert!(!profiles[0].is_validator);
+    assert_eq!(
+        profiles[0].txs.len(),
+        BlockExecutionArtifacts::create_for_testing().execution_data.execution_infos.len()
+    );
+
+    let other_height_profiles = batcher
+        .get_execution_profiles(&ExecutionProfilesFilter {
+            height: Some(INITIAL_HEIGHT.unchecked_next()),
+            ..Default::default()
+        })
+        .await;
+    assert!(other_height_profiles.is_empty());
+}
+
+#[tokio::test]
+async fn pre_confirmed_blocks_from_the_in_process_source() {
+    let mut pre_confirmed_block_source = MockPreconfirmedBlockSource::new();
+    pre_confirmed_block_source
+        .expect_latest_block()
+        .returning(|| Some(serde_json::json!({"block_number": 2})));
+    pre_confirmed_block_source
+        .expect_transaction_status()
+        .with(eq(tx_hash!(1)))
+        .returning(|_| Some(PreconfirmedTransactionStatus::Candidate));
+    let batcher = create_batcher(MockDependencies {
+        pre_confirmed_block_source: Some(Arc::new(pre_confirmed_block_source)),
+        ..Default::default()
+    })
+    .await;
+
+    assert_eq!(
+        batcher.get_latest_preconfirmed_block().unwrap(),
+        Some(serde_json::json!({"block_number": 2}))
+    );
+    assert_eq!(
+        batcher.get_preconfirmed_transaction_status(tx_hash!(1)).unwrap(),
+        Some(PreconfirmedTransactionStatus::Candidate)
+    );
+}
+
+#[tokio::test]
+async fn pre_confirmed_blocks_without_an_in_process_source() {
+    let batcher = create_batcher(MockDependencies::default()).await;
+
+    assert_eq!(
+        batcher.get_latest_preconfirmed_block(),
+        Err(BatcherError::PreconfirmedBlocksNotInProcess)
+    );
+    assert_eq!(
+        batcher.get_preconfirmed_transaction_status(tx_hash!(1)),
+        Err(BatcherError::PreconfirmedBlocksNotInProcess)
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn propose_block_without_retrospective_block_hash() {
@@ -855,8 +958,15 @@ async fn add_sync_block() {
         .storage_writer
         .expect_commit_proposal()
         .times(1)
-        .with(eq(INITIAL_HEIGHT), eq(test_state_diff()))
-        .returning(|_, _| Ok(()));
+        .with(
+            eq(INITIAL_HEIGHT),
+            eq(test_state_diff()),
+            eq(CommittedBlockTransactions {
+                account_transactions: vec![],
+                l1_handler_transaction_hashes: l1_transaction_hashes.iter().copied().collect(),
+            }),
+        )
+        .returning(|_, _, _| Ok(()));
 
     mock_dependencies
         .mempool_client
@@ -902,6 +1012,34 @@ async fn add_sync_block() {
     );
 }
 
+#[tokio::test]
+async fn commit_deletes_transactions_beyond_retention() {
+    let mut mock_dependencies = MockDependencies::default();
+    mock_dependencies.storage_writer.expect_commit_proposal().returning(|_, _, _| Ok(()));
+    mock_dependencies
+        .storage_writer
+        .expect_delete_committed_transactions()
+        .times(1)
+        .with(eq(LATEST_BLOCK_IN_STORAGE))
+        .returning(|_| Ok(()));
+    mock_dependencies.l1_provider_client.expect_commit_block().returning(|_, _, _| Ok(()));
+
+    let mut batcher = create_batcher_with_config(
+        BatcherConfig { committed_txs_retention_block_count: 1, ..Default::default() },
+        mock_dependencies,
+    )
+    .await;
+
+    let sync_block = SyncBlock {
+        block_header_without_hash: BlockHeaderWithoutHash {
+            block_number: INITIAL_HEIGHT,
+            ..Default::default()
+        },
+        ..Default::default()
+    };
+    batcher.add_sync_block(sync_block).await.unwrap();
+}
+
 #[rstest]
 #[tokio::test]
 async fn add_sync_block_mismatch_block_number() {
@@ -929,13 +1067,53 @@ async fn revert_block() {
     let recorder = PrometheusBuilder::new().build_recorder();
     let _recorder_guard = metrics::set_default_local_recorder(&recorder);
     let mut mock_dependencies = MockDependencies::default();
+    let nonces_before_block = HashMap::from([(contract_address!("0x1"), nonce!(7_u8))]);
 
+    let nonces = nonces_before_block.clone();
+    mock_dependencies
+        .storage_reader
+        .expect_nonces_before_block()
+        .times(1)
+        .with(eq(LATEST_BLOCK_IN_STORAGE))
+        .returning(move |_| Ok(nonces.clone()));
+    let transactions = CommittedBlockTransactions {
+        account_transactions: test_txs(0..2)
+            .into_iter()
+            .map(|tx| match tx {
+                InternalConsensusTransaction::RpcTransaction(tx) => tx,
+                InternalConsensusTransaction::L1Handler(_) => unreachable!(),
+            })
+            .collect(),
+        l1_handler_transaction_hashes: test_tx_hashes().into_iter().collect(),
+    };
+    let stored_transactions = transactions.clone();
+    mock_dependencies
+        .storage_reader
+        .expect_committed_block_transactions()
+        .times(1)
+        .with(eq(LATEST_BLOCK_IN_STORAGE))
+        .returning(move |_| Ok(stored_transactions.clone()));
     mock_dependencies
         .storage_writer
         .expect_revert_block()
         .times(1)
         .with(eq(LATEST_BLOCK_IN_STORAGE))
         .returning(|_| ());
+    mock_dependencies
+        .mempool_client
+        .expect_revert_block()
+        .times(1)
+        .with(eq(RevertBlockArgs {
+            address_to_nonce: nonces_before_block,
+            reverted_txs: transactions.account_transactions,
+        }))
+        .returning(|_| Ok(()));
+    mock_dependencies
+        .l1_provider_client
+        .expect_revert_block()
+        .times(1)
+        .with(eq(LATEST_BLOCK_IN_STORAGE), eq(transactions.l1_handler_transaction_hashes))
+        .returning(|_, _| Ok(()));
 
     let mut batcher = create_batcher(mock_dependencies).await;
 
@@ -1020,8 +1198,15 @@ async fn decision_reached() {
         .storage_writer
         .expect_commit_proposal()
         .times(1)
-        .with(eq(INITIAL_HEIGHT), eq(expected_artifacts.thin_state_diff()))
-        .returning(|_, _| Ok(()));
+        .with(
+            eq(INITIAL_HEIGHT),
+            eq(expected_artifacts.thin_state_diff()),
+            eq(CommittedBlockTransactions {
+                account_transactions: expected_artifacts.account_transactions.clone(),
+                l1_handler_transaction_hashes: vec![],
+            }),
+        )
+        .returning(|_, _, _| Ok(()));
 
     mock_create_builder_for_propose_block(
         &mut mock_dependencies.block_builder_factory,
@@ -1060,6 +1245,84 @@ async fn decision_reached() {
     );
 }
 
+#[tokio::test]
+async fn speculative_execution_on_decided_proposal() {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let _recorder_guard = metrics::set_default_local_recorder(&recorder);
+    let mut mock_dependencies = MockDependencies::default();
+    let expected_artifacts = BlockExecutionArtifacts::create_for_testing();
+
+    mock_dependencies.mempool_client.expect_commit_block().times(1).returning(|_| Ok(()));
+    mock_dependencies.l1_provider_client.expect_start_block().times(1).returning(|_, _| Ok(()));
+    mock_dependencies.l1_provider_client.expect_commit_block().times(1).returning(|_, _, _| Ok(()));
+    mock_dependencies.storage_writer.expect_commit_proposal().times(1).returning(|_, _, _| Ok(()));
+
+    mock_create_builder_for_propose_block(
+        &mut mock_dependencies.block_builder_factory,
+        vec![],
+        Ok(expected_artifacts),
+    );
+    // The next height is executed on top of the built proposal.
+    mock_dependencies
+        .block_builder_factory
+        .expect_create_block_builder()
+        .times(1)
+        .withf(
+            |block_metadata,
+             state_layer,
+             _,
+             _,
+             output_content_sender,
+             _,
+             pre_confirmed_tx_sender,
+             _| {
+                block_metadata.block_info.block_number == INITIAL_HEIGHT.unchecked_next()
+                    && state_layer
+                        .as_ref()
+                        .is_some_and(|state_layer| state_layer.storage_height == INITIAL_HEIGHT)
+                    && output_content_sender.is_none()
+                    && pre_confirmed_tx_sender.is_some()
+            },
+        )
+        .return_once(|_, _, _, _, _, _, _, _| {
+            let mut block_builder = MockBlockBuilderTrait::new();
+            block_builder.expect_build_block().return_once(|| Err(BlockBuilderError::Aborted));
+            Ok((Box::new(block_builder), abort_signal_sender()))
+        });
+
+    let mut batcher = create_batcher_with_config(
+        BatcherConfig {
+            outstream_content_buffer_size: STREAMING_CHUNK_SIZE,
+            speculative_execution_config: SpeculativeExecutionConfig {
+                enabled: true,
+                ..Default::default()
+            },
+            ..Default::default()
+        },
+        mock_dependencies,
+    )
+    .await;
+    batcher.start_height(StartHeightInput { height: INITIAL_HEIGHT }).await.unwrap();
+    batcher.propose_block(propose_block_input(PROPOSAL_ID)).await.unwrap();
+    let content = batcher
+        .get_proposal_content(GetProposalContentInput { proposal_id: PROPOSAL_ID })
+        .await
+        .unwrap()
+        .content;
+    assert_matches!(content, GetProposalContent::Finished { .. });
+
+    batcher.decision_reached(DecisionReachedInput { proposal_id: PROPOSAL_ID }).await.unwrap();
+
+    let metrics = recorder.handle().render();
+    assert_eq!(
+        SPECULATIONS.parse_numeric_metric::<u64>(
+            &metrics,
+            &[(LABEL_NAME_SPECULATION_RESULT, SpeculationResult::Hit.into())]
+        ),
+        Some(1)
+    );
+}
+
 #[rstest]
 #[tokio::test]
 async fn decision_reached_no_executed_proposal() {
@@ -1083,7 +1346,7 @@ async fn test_execution_info_order_is_kept() {
     mock_dependencies.l1_provider_client.expect_start_block().returning(|_, _| Ok(()));
     mock_dependencies.mempool_client.expect_commit_block().returning(|_| Ok(()));
     mock_dependencies.l1_provider_client.expect_commit_block().returning(|_, _, _| Ok(()));
-    mock_dependencies.storage_writer.expect_commit_proposal().returning(|_, _| Ok(()));
+    mock_dependencies.storage_writer.expect_commit_proposal().returning(|_, _, _| Ok(()));
 
     let block_builder_result = BlockExecutionArtifacts::create_for_testing();
     // Check that the execution_infos were initiated properly for this test.
@@ -1163,7 +1426,7 @@ async fn decision_reached_return_error_when_l1_commit_block_fails(
         .times(1)
         .returning(move |_, _, _| Err(l1_error.clone()));
 
-    mock_dependencies.storage_writer.expect_commit_proposal().returning(|_, _| Ok(()));
+    mock_dependencies.storage_writer.expect_commit_proposal().returning(|_, _, _| Ok(()));
 
     mock_dependencies.storage_writer.expect_revert_block().returning(|_| ());
 
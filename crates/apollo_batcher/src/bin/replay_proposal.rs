@@ -0,0 +1,197 @@
+//! Replays a proposal recorded by the batcher against a storage snapshot and compares the
+//! resulting commitment with the recorded one.
+//!
+//! The snapshot must contain the state up to (at least) the height of the recorded block, and the
+//! classes used by its transactions.
+
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::sync::Arc;
+use std::time::Duration;
+
+use apollo_batcher::block_builder::BlockBuilderFactory;
+use apollo_batcher::proposal_recorder::ProposalRecording;
+use apollo_batcher::proposal_replay::replay_proposal;
+use apollo_class_manager::class_storage::{ClassStorage, FsClassStorage};
+use apollo_class_manager::config::{
+    ClassHashDbConfig,
+    ClassHashStorageConfig,
+    FsClassStorageConfig,
+};
+use apollo_class_manager_types::{
+    Class,
+    ClassHashes,
+    ClassId,
+    ClassManagerClient,
+    ClassManagerClientResult,
+    ClassManagerError,
+    ExecutableClass,
+    ExecutableClassHash,
+};
+use apollo_infra::trace_util::configure_tracing;
+use apollo_storage::db::DbConfig;
+use apollo_storage::state::StateStorageReader;
+use apollo_storage::{StorageConfig, StorageScope};
+use async_trait::async_trait;
+use blockifier::blockifier::config::ContractClassManagerConfig;
+use blockifier::concurrency::worker_pool::WorkerPool;
+use blockifier::state::contract_class_manager::ContractClassManager;
+use clap::Parser;
+use starknet_api::deprecated_contract_class::ContractClass as DeprecatedClass;
+use tracing::info;
+
+#[derive(Parser, Debug)]
+#[command(name = "replay_proposal", about = "Replay a recorded batcher proposal.")]
+struct Args {
+    /// Path to the recording file.
+    #[arg(long)]
+    recording: PathBuf,
+
+    /// Path prefix of the batcher storage snapshot.
+    #[arg(long)]
+    storage_path: PathBuf,
+
+    /// Path to the class manager's class storage directory.
+    #[arg(long)]
+    class_storage_path: PathBuf,
+
+    /// Path prefix of the class manager's class hash storage.
+    #[arg(long)]
+    class_hash_storage_path: PathBuf,
+
+    /// The time (in seconds) the replay may take.
+    #[arg(long, default_value_t = 600)]
+    timeout_secs: u64,
+}
+
+#[tokio::main]
+async fn main() -> ExitCode {
+    configure_tracing().await;
+    let args = Args::parse();
+
+    let recording =
+        ProposalRecording::read_from_file(&args.recording).expect("Failed to read the recording.");
+    let block_builder_config = recording.block_builder_config.clone();
+    let block_number = recording.block_metadata.block_info.block_number;
+    info!("Replaying proposal {} of block {block_number}.", recording.input.proposal_id());
+
+    let (storage_reader, _storage_writer) = apollo_storage::open_storage(StorageConfig {
+        db_config: DbConfig {
+            path_prefix: args.storage_path,
+            chain_id: block_builder_config.chain_info.chain_id.clone(),
+            enforce_file_exists: true,
+            ..Default::default()
+        },
+        scope: StorageScope::StateOnly,
+        ..Default::default()
+    })
+    .expect("Failed to open the storage snapshot.");
+    let storage_height = storage_reader
+        .begin_ro_txn()
+        .and_then(|txn| txn.get_state_marker())
+        .expect("Failed to read the storage height.");
+    assert!(
+        storage_height >= block_number,
+        "The storage snapshot (height {storage_height}) does not contain the state before block \
+         {block_number}."
+    );
+
+    let class_storage = FsClassStorage::new(FsClassStorageConfig {
+        persistent_root: args.class_storage_path,
+        class_hash_storage_config: ClassHashStorageConfig {
+            class_hash_db_config: ClassHashDbConfig {
+                path_prefix: args.class_hash_storage_path,
+                enforce_file_exists: true,
+                ..ClassHashStorageConfig::default().class_hash_db_config
+            },
+            ..Default::default()
+        },
+    })
+    .expect("Failed to open the class storage snapshot.");
+
+    let block_builder_factory = BlockBuilderFactory {
+        worker_pool: Arc::new(WorkerPool::start(&block_builder_config.execute_config)),
+        block_builder_config,
+        storage_reader,
+        contract_class_manager: ContractClassManager::start(ContractClassManagerConfig::default()),
+        class_manager_client: Arc::new(SnapshotClassManagerClient(class_storage)),
+    };
+
+    let report =
+        replay_proposal(recording, &block_builder_factory, Duration::from_secs(args.timeout_secs))
+            .await
+            .expect("Failed to replay the proposal.");
+
+    info!("Replayed the proposal in {:?}.", report.build_duration);
+    println!("Replayed commitment: {:?}", report.replayed.commitment);
+    let diff = report.diff();
+    if report.commitment_matches() && diff.is_empty() {
+        println!("The replay matches the recording.");
+        return ExitCode::SUCCESS;
+    }
+    println!("The replay differs from the recording:");
+    for line in diff {
+        println!("  {line}");
+    }
+    ExitCode::FAILURE
+}
+
+/// A read-only class manager client over a class storage snapshot.
+struct SnapshotClassManagerClient(FsClassStorage);
+
+fn storage_error(err: impl std::error::Error) -> ClassManagerError {
+    ClassManagerError::ClassStorage(err.to_string())
+}
+
+fn read_only_error() -> ClassManagerError {
+    ClassManagerError::Client("The snapshot class manager is read-only.".to_string())
+}
+
+#[async_trait]
+impl ClassManagerClient for SnapshotClassManagerClient {
+    async fn add_class(&self, _class: Class) -> ClassManagerClientResult<ClassHashes> {
+        Err(read_only_error().into())
+    }
+
+    async fn get_executable(
+        &self,
+        class_id: ClassId,
+    ) -> ClassManagerClientResult<Option<ExecutableClass>> {
+        let class = self.0.get_executable(class_id).map_err(storage_error)?;
+        let class = class.map(ExecutableClass::try_from).transpose().map_err(storage_error)?;
+        Ok(class)
+    }
+
+    async fn get_sierra(&self, class_id: ClassId) -> ClassManagerClientResult<Option<Class>> {
+        let class = self.0.get_sierra(class_id).map_err(storage_error)?;
+        let class = class.map(Class::try_from).transpose().map_err(storage_error)?;
+        Ok(class)
+    }
+
+    async fn get_executable_class_hash_v2(
+        &self,
+        class_id: ClassId,
+    ) -> ClassManagerClientResult<Option<ExecutableClassHash>> {
+        let executable_class_hash_v2 =
+            self.0.get_executable_class_hash_v2(class_id).map_err(storage_error)?;
+        Ok(executable_class_hash_v2)
+    }
+
+    async fn add_deprecated_class(
+        &self,
+        _class_id: ClassId,
+        _class: DeprecatedClass,
+    ) -> ClassManagerClientResult<()> {
+        Err(read_only_error().into())
+    }
+
+    async fn add_class_and_executable_unsafe(
+        &self,
+        _class_id: ClassId,
+        _class: Class,
+        _executable_class_hash_v2: ExecutableClassHash,
+        _executable_class: ExecutableClass,
+    ) -> ClassManagerClientResult<()> {
+        Err(read_only_error().into())
+    }
+}
@@ -1,8 +1,8 @@
-use std::cmp::min;
 use std::collections::{BTreeMap, HashMap, HashSet};
 use std::sync::Arc;
+use std::time::Duration;
 
-use apollo_batcher_types::batcher_types::ProposalCommitment;
+use apollo_batcher_types::batcher_types::{BlockCloseReason, BouncerDimension, ProposalCommitment};
 use apollo_class_manager_types::transaction_converter::{
     TransactionConverter,
     TransactionConverterError,
@@ -44,6 +44,7 @@ use starknet_api::block_hash::state_diff_hash::calculate_state_diff_hash;
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::execution_resources::GasAmount;
+use starknet_api::rpc_transaction::InternalRpcTransaction;
 use starknet_api::state::ThinStateDiff;
 use starknet_api::transaction::{TransactionHash, TransactionOffsetInBlock};
 use thiserror::Error;
@@ -53,8 +54,10 @@ use tracing::{debug, error, info, trace, warn};
 
 use crate::block_builder::FailOnErrorCause::L1HandlerTransactionValidationFailed;
 use crate::cende_client_types::{StarknetClientStateDiff, StarknetClientTransactionReceipt};
+use crate::chunk_scheduler::{ChunkDecision, ChunkScheduler, ChunkSchedulerConfig};
 use crate::metrics::FULL_BLOCKS;
 use crate::pre_confirmed_block_writer::{CandidateTxSender, PreconfirmedTxSender};
+use crate::speculative_execution::{LayeredStateReader, StateLayer};
 use crate::transaction_executor::TransactionExecutorTrait;
 use crate::transaction_provider::{TransactionProvider, TransactionProviderError};
 
@@ -106,6 +109,12 @@ pub struct BlockExecutionArtifacts {
     pub commitment_state_diff: CommitmentStateDiff,
     pub compressed_state_diff: Option<CommitmentStateDiff>,
     pub bouncer_weights: BouncerWeights,
+    // The bouncer weights and the chunk latency of each transaction in `execution_infos`, in the
+    // same order. The chunk latency of a transaction is the wall time from when its chunk was
+    // handed to the executor until its result was collected.
+    pub tx_bouncer_weights: Vec<BouncerWeights>,
+    pub chunk_latencies: Vec<Duration>,
+    pub close_reason: BlockCloseReason,
     pub l2_gas_used: GasAmount,
     pub casm_hash_computation_data_sierra_gas: CasmHashComputationData,
     pub casm_hash_computation_data_proving_gas: CasmHashComputationData,
@@ -113,6 +122,9 @@ pub struct BlockExecutionArtifacts {
     // The number of transactions executed by the proposer out of the transactions that were sent.
     // This value includes rejected transactions.
     pub final_n_executed_txs: usize,
+    // The account transactions included in the block, in order. Kept so that they can be returned
+    // to the mempool if the block is reverted.
+    pub account_transactions: Vec<InternalRpcTransaction>,
 }
 
 impl BlockExecutionArtifacts {
@@ -177,12 +189,18 @@ pub struct BlockBuilder {
     n_executed_txs: usize,
     /// The transactions whose execution started.
     block_txs: Vec<InternalConsensusTransaction>,
+    /// The time each transaction in `block_txs` was handed to the executor.
+    tx_start_times: Vec<tokio::time::Instant>,
     execution_data: BlockTransactionExecutionData,
+    /// The chunk latency of each transaction in `execution_data.execution_infos`, in the same
+    /// order.
+    chunk_latencies: Vec<Duration>,
 
     /// Parameters to configure the block builder behavior.
-    n_concurrent_txs: usize,
     tx_polling_interval_millis: u64,
     execution_params: BlockBuilderExecutionParams,
+    chunk_scheduler: ChunkScheduler,
+    block_capacity: BouncerWeights,
 }
 
 impl BlockBuilder {
@@ -199,9 +217,17 @@ impl BlockBuilder {
         transaction_converter: TransactionConverter,
         n_concurrent_txs: usize,
         tx_polling_interval_millis: u64,
+        chunk_scheduler_config: ChunkSchedulerConfig,
+        block_capacity: BouncerWeights,
         execution_params: BlockBuilderExecutionParams,
     ) -> Self {
         let executor = Arc::new(Mutex::new(executor));
+        // A validator executes the transactions chosen by the proposer, so the adaptive scheduling
+        // only applies to proposals.
+        let chunk_scheduler_config = ChunkSchedulerConfig {
+            adaptive: chunk_scheduler_config.adaptive && !execution_params.is_validator,
+            ..chunk_scheduler_config
+        };
         Self {
             executor,
             tx_provider,
@@ -212,10 +238,13 @@ impl BlockBuilder {
             transaction_converter,
             n_executed_txs: 0,
             block_txs: Vec::new(),
+            tx_start_times: Vec::new(),
             execution_data: BlockTransactionExecutionData::default(),
-            n_concurrent_txs,
+            chunk_latencies: Vec::new(),
             tx_polling_interval_millis,
             execution_params,
+            chunk_scheduler: ChunkScheduler::new(chunk_scheduler_config, n_concurrent_txs),
+            block_capacity,
         }
     }
 }
@@ -234,12 +263,16 @@ impl BlockBuilderTrait for BlockBuilder {
 impl BlockBuilder {
     async fn build_block_inner(&mut self) -> BlockBuilderResult<BlockExecutionArtifacts> {
         let mut final_n_executed_txs: Option<usize> = None;
+        // Only a validator leaves the loop without setting the reason, once all the transactions
+        // sent by the proposer were executed.
+        let mut close_reason = BlockCloseReason::ProposerFinished;
         while !self.finished_block_txs(final_n_executed_txs) {
             if tokio::time::Instant::now() >= self.execution_params.deadline {
                 info!("Block builder deadline reached.");
                 if self.execution_params.is_validator {
                     return Err(BlockBuilderError::FailOnError(FailOnErrorCause::DeadlineReached));
                 }
+                close_reason = BlockCloseReason::DeadlineReached;
                 break;
             }
             if final_n_executed_txs.is_none() {
@@ -263,12 +296,32 @@ impl BlockBuilder {
                 self.handle_executed_txs().await?;
                 info!("Block is full.");
                 FULL_BLOCKS.increment(1);
+                // The exceeded dimensions are known once the block is closed.
+                close_reason = BlockCloseReason::BlockFull { exceeded_dimensions: Vec::new() };
                 break;
             }
 
-            match self.add_txs_to_executor().await? {
-                AddTxsToExecutorResult::NoNewTxs => self.sleep().await,
-                AddTxsToExecutorResult::NewTxs => {}
+            let time_to_deadline = self
+                .execution_params
+                .deadline
+                .saturating_duration_since(tokio::time::Instant::now());
+            match self.chunk_scheduler.next_chunk(self.n_txs_in_progress(), time_to_deadline) {
+                ChunkDecision::Fetch(n_txs_to_fetch) => {
+                    match self.add_txs_to_executor(n_txs_to_fetch).await? {
+                        AddTxsToExecutorResult::NoNewTxs => self.sleep().await,
+                        AddTxsToExecutorResult::NewTxs => {}
+                    }
+                }
+                ChunkDecision::Wait => self.sleep().await,
+                ChunkDecision::Stop => {
+                    info!(
+                        "Closing the block ahead of the deadline. {} transactions in progress are \
+                         not included.",
+                        self.n_txs_in_progress()
+                    );
+                    close_reason = BlockCloseReason::ClosedAheadOfDeadline;
+                    break;
+                }
             }
         }
 
@@ -305,6 +358,8 @@ impl BlockBuilder {
             state_diff,
             compressed_state_diff,
             bouncer_weights,
+            mut tx_bouncer_weights,
+            rejected_tx_bouncer_weights,
             casm_hash_computation_data_sierra_gas,
             casm_hash_computation_data_proving_gas,
             compiled_class_hashes_for_migration,
@@ -318,17 +373,47 @@ impl BlockBuilder {
                 self.block_txs[final_n_executed_txs..].iter().map(|tx| tx.tx_hash()).collect();
             execution_data.remove_last_txs(&remove_tx_hashes);
         }
+        let n_included_txs = execution_data.execution_infos.len();
+        let account_transactions = self
+            .block_txs
+            .iter()
+            .take(final_n_executed_txs_nonopt)
+            .filter_map(|tx| match tx {
+                InternalConsensusTransaction::RpcTransaction(tx)
+                    if execution_data.execution_infos.contains_key(&tx.tx_hash) =>
+                {
+                    Some(tx.clone())
+                }
+                _ => None,
+            })
+            .collect();
+        tx_bouncer_weights.truncate(n_included_txs);
+        let mut chunk_latencies = std::mem::take(&mut self.chunk_latencies);
+        chunk_latencies.truncate(n_included_txs);
+        if let (BlockCloseReason::BlockFull { exceeded_dimensions }, Some(rejected_tx_weights)) =
+            (&mut close_reason, rejected_tx_bouncer_weights)
+        {
+            let weights_with_rejected_tx = bouncer_weights
+                .checked_add(rejected_tx_weights)
+                .expect("Bouncer weights addition overflow.");
+            *exceeded_dimensions =
+                BouncerDimension::exceeded(&weights_with_rejected_tx, &self.block_capacity);
+        }
         let l2_gas_used = execution_data.l2_gas_used();
         Ok(BlockExecutionArtifacts {
             execution_data,
             commitment_state_diff: state_diff,
             compressed_state_diff,
             bouncer_weights,
+            tx_bouncer_weights,
+            chunk_latencies,
+            close_reason,
             l2_gas_used,
             casm_hash_computation_data_sierra_gas,
             casm_hash_computation_data_proving_gas,
             final_n_executed_txs: final_n_executed_txs_nonopt,
             compiled_class_hashes_for_migration,
+            account_transactions,
         })
     }
 
@@ -348,20 +433,15 @@ impl BlockBuilder {
         }
     }
 
-    /// Adds new transactions (if there are any) from `tx_provider` to the executor.
+    /// Adds up to `n_txs_to_fetch` new transactions (if there are any) from `tx_provider` to the
+    /// executor.
     ///
     /// Returns whether new transactions were added and whether the transaction stream is exhausted
     /// (this can only happen in validator mode).
-    async fn add_txs_to_executor(&mut self) -> BlockBuilderResult<AddTxsToExecutorResult> {
-        // Restrict the number of transactions to fetch such that the number of transactions in
-        // progress is at most `n_concurrent_txs`.
-        let n_txs_to_fetch =
-            self.n_concurrent_txs - min(self.n_txs_in_progress(), self.n_concurrent_txs);
-
-        if n_txs_to_fetch == 0 {
-            return Ok(AddTxsToExecutorResult::NoNewTxs);
-        }
-
+    async fn add_txs_to_executor(
+        &mut self,
+        n_txs_to_fetch: usize,
+    ) -> BlockBuilderResult<AddTxsToExecutorResult> {
         let next_txs = match self.tx_provider.get_txs(n_txs_to_fetch).await {
             Err(e @ TransactionProviderError::L1HandlerTransactionValidationFailed { .. })
                 if self.execution_params.is_validator =>
@@ -387,7 +467,10 @@ impl BlockBuilder {
 
         self.send_candidate_txs(&next_txs);
 
+        let now = tokio::time::Instant::now();
+        self.chunk_scheduler.on_txs_started(now, self.n_txs_in_progress());
         self.block_txs.extend(next_txs.iter().cloned());
+        self.tx_start_times.extend(std::iter::repeat_n(now, n_txs));
 
         let tx_convert_futures = next_txs.iter().map(|tx| async {
             convert_to_executable_blockifier_tx(&self.transaction_converter, tx.clone()).await
@@ -421,14 +504,27 @@ impl BlockBuilder {
 
         let old_n_executed_txs = self.n_executed_txs;
         self.n_executed_txs += results.len();
+        let now = tokio::time::Instant::now();
+        self.chunk_scheduler.on_txs_executed(now, results.len(), self.n_txs_in_progress());
 
+        let executed_txs = old_n_executed_txs..self.n_executed_txs;
         collect_execution_results_and_stream_txs(
-            &self.block_txs[old_n_executed_txs..self.n_executed_txs],
+            &self.block_txs[executed_txs.clone()],
             results,
             &mut self.execution_data,
             &mut self.pre_confirmed_tx_sender,
         )
-        .await
+        .await?;
+
+        // Rejected transactions are not in the block, so they are not timed.
+        for (tx, start_time) in
+            self.block_txs[executed_txs.clone()].iter().zip(&self.tx_start_times[executed_txs])
+        {
+            if self.execution_data.execution_infos.contains_key(&tx.tx_hash()) {
+                self.chunk_latencies.push(now.duration_since(*start_time));
+            }
+        }
+        Ok(())
     }
 
     fn send_candidate_txs(&mut self, next_tx_chunk: &[InternalConsensusTransaction]) {
@@ -585,6 +681,7 @@ async fn collect_execution_results_and_stream_txs(
     Ok(())
 }
 
+#[derive(Clone, Debug, Deserialize, Serialize)]
 pub struct BlockMetadata {
     pub block_info: BlockInfo,
     pub retrospective_block_hash: Option<BlockHashAndNumber>,
@@ -593,16 +690,18 @@ pub struct BlockMetadata {
 // Type definitions for the abort channel required to abort the block builder.
 pub type AbortSignalSender = tokio::sync::oneshot::Sender<()>;
 pub type BatcherWorkerPool =
-    Arc<WorkerPool<CachedState<StateReaderAndContractManager<PapyrusReader>>>>;
+    Arc<WorkerPool<CachedState<StateReaderAndContractManager<LayeredStateReader<PapyrusReader>>>>>;
 
 /// The BlockBuilderFactoryTrait is responsible for creating a new block builder.
 #[cfg_attr(test, automock)]
 pub trait BlockBuilderFactoryTrait: Send + Sync {
+    /// The block is executed on top of the storage state, or on top of `state_layer` if given.
     // TODO(noamsp): Investigate and remove this clippy warning.
     #[allow(clippy::too_many_arguments)]
     fn create_block_builder(
         &self,
         block_metadata: BlockMetadata,
+        state_layer: Option<StateLayer>,
         execution_params: BlockBuilderExecutionParams,
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<
@@ -621,6 +720,7 @@ pub struct BlockBuilderConfig {
     pub bouncer_config: BouncerConfig,
     pub n_concurrent_txs: usize,
     pub tx_polling_interval_millis: u64,
+    pub chunk_scheduler_config: ChunkSchedulerConfig,
     pub versioned_constants_overrides: VersionedConstantsOverrides,
 }
 
@@ -633,6 +733,7 @@ impl Default for BlockBuilderConfig {
             bouncer_config: BouncerConfig::default(),
             n_concurrent_txs: 100,
             tx_polling_interval_millis: 10,
+            chunk_scheduler_config: ChunkSchedulerConfig::default(),
             versioned_constants_overrides: VersionedConstantsOverrides::default(),
         }
     }
@@ -656,6 +757,10 @@ impl SerializeConfig for BlockBuilderConfig {
              request returned no transactions.",
             ParamPrivacyInput::Public,
         )]));
+        dump.append(&mut prepend_sub_config_name(
+            self.chunk_scheduler_config.dump(),
+            "chunk_scheduler_config",
+        ));
         dump.append(&mut prepend_sub_config_name(
             self.versioned_constants_overrides.dump(),
             "versioned_constants_overrides",
@@ -677,9 +782,12 @@ impl BlockBuilderFactory {
     fn preprocess_and_create_transaction_executor(
         &self,
         block_metadata: BlockMetadata,
+        state_layer: Option<StateLayer>,
         runtime: tokio::runtime::Handle,
     ) -> BlockBuilderResult<
-        ConcurrentTransactionExecutor<StateReaderAndContractManager<PapyrusReader>>,
+        ConcurrentTransactionExecutor<
+            StateReaderAndContractManager<LayeredStateReader<PapyrusReader>>,
+        >,
     > {
         info!(
             "preprocess and create transaction executor for block {}",
@@ -697,11 +805,16 @@ impl BlockBuilderFactory {
             block_builder_config.bouncer_config,
         );
 
+        // A state layer holds the state that is not in the storage yet.
+        let storage_height = state_layer.as_ref().map_or(height, |layer| layer.storage_height);
         let class_reader = Some(ClassReader { reader: self.class_manager_client.clone(), runtime });
-        let papyrus_reader =
-            PapyrusReader::new_with_class_reader(self.storage_reader.clone(), height, class_reader);
+        let papyrus_reader = PapyrusReader::new_with_class_reader(
+            self.storage_reader.clone(),
+            storage_height,
+            class_reader,
+        );
         let state_reader = StateReaderAndContractManager {
-            state_reader: papyrus_reader,
+            state_reader: LayeredStateReader { state_reader: papyrus_reader, state_layer },
             contract_class_manager: self.contract_class_manager.clone(),
         };
 
@@ -721,6 +834,7 @@ impl BlockBuilderFactoryTrait for BlockBuilderFactory {
     fn create_block_builder(
         &self,
         block_metadata: BlockMetadata,
+        state_layer: Option<StateLayer>,
         execution_params: BlockBuilderExecutionParams,
         tx_provider: Box<dyn TransactionProvider>,
         output_content_sender: Option<
@@ -730,7 +844,8 @@ impl BlockBuilderFactoryTrait for BlockBuilderFactory {
         pre_confirmed_tx_sender: Option<PreconfirmedTxSender>,
         runtime: tokio::runtime::Handle,
     ) -> BlockBuilderResult<(Box<dyn BlockBuilderTrait>, AbortSignalSender)> {
-        let executor = self.preprocess_and_create_transaction_executor(block_metadata, runtime)?;
+        let executor =
+            self.preprocess_and_create_transaction_executor(block_metadata, state_layer, runtime)?;
         let (abort_signal_sender, abort_signal_receiver) = tokio::sync::oneshot::channel();
         let transaction_converter = TransactionConverter::new(
             self.class_manager_client.clone(),
@@ -746,6 +861,8 @@ impl BlockBuilderFactoryTrait for BlockBuilderFactory {
             transaction_converter,
             self.block_builder_config.n_concurrent_txs,
             self.block_builder_config.tx_polling_interval_millis,
+            self.block_builder_config.chunk_scheduler_config,
+            self.block_builder_config.bouncer_config.block_max_capacity,
             execution_params,
         ));
         Ok((block_builder, abort_signal_sender))
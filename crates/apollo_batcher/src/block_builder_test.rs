@@ -1,5 +1,6 @@
 use std::sync::Arc;
 
+use apollo_batcher_types::batcher_types::{BlockCloseReason, BouncerDimension};
 use apollo_class_manager_types::transaction_converter::TransactionConverter;
 use apollo_class_manager_types::MockClassManagerClient;
 use apollo_l1_provider_types::InvalidValidationStatus;
@@ -32,6 +33,7 @@ use pretty_assertions::assert_eq;
 use rstest::rstest;
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
 use starknet_api::execution_resources::{GasAmount, GasVector};
+use starknet_api::test_utils::invoke::{internal_invoke_tx, InvokeTxArgs};
 use starknet_api::test_utils::CHAIN_ID_FOR_TESTS;
 use starknet_api::transaction::fields::Fee;
 use starknet_api::transaction::TransactionHash;
@@ -48,6 +50,7 @@ use crate::block_builder::{
     BlockTransactionExecutionData,
     FailOnErrorCause,
 };
+use crate::chunk_scheduler::ChunkSchedulerConfig;
 use crate::metrics::FULL_BLOCKS;
 use crate::test_utils::{test_l1_handler_txs, test_txs};
 use crate::transaction_executor::MockTransactionExecutorTrait;
@@ -68,6 +71,10 @@ struct TestExpectations {
     expected_full_blocks_metric: u64,
 }
 
+fn block_capacity() -> BouncerWeights {
+    BouncerWeights { l1_gas: 150, ..BouncerWeights::max() }
+}
+
 fn output_channel()
 -> (UnboundedSender<InternalConsensusTransaction>, UnboundedReceiver<InternalConsensusTransaction>)
 {
@@ -81,6 +88,14 @@ fn block_execution_artifacts(
     final_n_executed_txs: usize,
 ) -> BlockExecutionArtifacts {
     let l2_gas_used = GasAmount(execution_infos.len().try_into().unwrap());
+    let tx_bouncer_weights =
+        vec![BouncerWeights { n_txs: 1, ..BouncerWeights::empty() }; execution_infos.len()];
+    // The account transactions are the ones created by `test_txs`.
+    let account_transactions = execution_infos
+        .keys()
+        .filter(|tx_hash| !consumed_l1_handler_tx_hashes.contains(*tx_hash))
+        .map(|tx_hash| internal_invoke_tx(InvokeTxArgs { tx_hash: *tx_hash, ..Default::default() }))
+        .collect();
     BlockExecutionArtifacts {
         execution_data: BlockTransactionExecutionData {
             execution_infos,
@@ -90,12 +105,17 @@ fn block_execution_artifacts(
         commitment_state_diff: Default::default(),
         compressed_state_diff: Default::default(),
         bouncer_weights: BouncerWeights { l1_gas: 100, ..BouncerWeights::empty() },
+        tx_bouncer_weights,
+        // The chunk latencies are not deterministic, see `without_chunk_latencies`.
+        chunk_latencies: vec![],
+        close_reason: BlockCloseReason::DeadlineReached,
         // Each mock transaction uses 1 L2 gas so the total amount should be the number of txs.
         l2_gas_used,
         casm_hash_computation_data_sierra_gas: CasmHashComputationData::default(),
         casm_hash_computation_data_proving_gas: CasmHashComputationData::default(),
         compiled_class_hashes_for_migration: vec![],
         final_n_executed_txs,
+        account_transactions,
     }
 }
 
@@ -295,7 +315,16 @@ fn block_full_test_expectations(before_is_done: bool) -> TestExpectations {
     helper.expect_successful_get_new_results(if before_is_done { 0 } else { 1 });
 
     let mut mock_transaction_executor = helper.mock_transaction_executor;
-    let expected_block_artifacts = set_close_block_expectations(&mut mock_transaction_executor, 1);
+    // The rejected transaction exceeds the L1 gas capacity of the block.
+    let rejected_tx_bouncer_weights =
+        BouncerWeights { l1_gas: 60, n_txs: 1, ..BouncerWeights::empty() };
+    let mut expected_block_artifacts = set_close_block_expectations_ex(
+        &mut mock_transaction_executor,
+        1,
+        Some(rejected_tx_bouncer_weights),
+    );
+    expected_block_artifacts.close_reason =
+        BlockCloseReason::BlockFull { exceeded_dimensions: vec![BouncerDimension::L1Gas] };
 
     let mock_tx_provider = mock_tx_provider_limited_calls(vec![input_txs.clone()]);
 
@@ -411,6 +440,8 @@ fn transaction_failed_test_expectations() -> TestExpectations {
             state_diff: expected_block_artifacts_copy.commitment_state_diff,
             compressed_state_diff: None,
             bouncer_weights: expected_block_artifacts_copy.bouncer_weights,
+            tx_bouncer_weights: expected_block_artifacts_copy.tx_bouncer_weights,
+            rejected_tx_bouncer_weights: None,
             casm_hash_computation_data_sierra_gas: expected_block_artifacts_copy
                 .casm_hash_computation_data_sierra_gas,
             casm_hash_computation_data_proving_gas: expected_block_artifacts_copy
@@ -452,6 +483,14 @@ fn block_builder_expected_output(
 fn set_close_block_expectations(
     mock_transaction_executor: &mut MockTransactionExecutorTrait,
     block_size: usize,
+) -> BlockExecutionArtifacts {
+    set_close_block_expectations_ex(mock_transaction_executor, block_size, None)
+}
+
+fn set_close_block_expectations_ex(
+    mock_transaction_executor: &mut MockTransactionExecutorTrait,
+    block_size: usize,
+    rejected_tx_bouncer_weights: Option<BouncerWeights>,
 ) -> BlockExecutionArtifacts {
     let output_block_artifacts = block_builder_expected_output(block_size, block_size);
     let output_block_artifacts_copy = output_block_artifacts.clone();
@@ -460,6 +499,8 @@ fn set_close_block_expectations(
             state_diff: output_block_artifacts.commitment_state_diff,
             compressed_state_diff: None,
             bouncer_weights: output_block_artifacts.bouncer_weights,
+            tx_bouncer_weights: output_block_artifacts.tx_bouncer_weights,
+            rejected_tx_bouncer_weights,
             casm_hash_computation_data_sierra_gas: output_block_artifacts
                 .casm_hash_computation_data_sierra_gas,
             casm_hash_computation_data_proving_gas: output_block_artifacts
@@ -599,11 +640,19 @@ async fn verify_build_block_output(
     assert_eq!(output_txs, expected_output_txs);
 
     // Verify the block artifacts.
-    assert_eq!(result_block_artifacts, expected_block_artifacts);
+    assert_eq!(without_chunk_latencies(result_block_artifacts), expected_block_artifacts);
 
     FULL_BLOCKS.assert_eq::<u64>(metrics, expected_full_blocks_metric);
 }
 
+/// Clears the chunk latencies of the given artifacts, since they depend on the wall clock, after
+/// verifying there is one per included transaction.
+fn without_chunk_latencies(mut artifacts: BlockExecutionArtifacts) -> BlockExecutionArtifacts {
+    assert_eq!(artifacts.chunk_latencies.len(), artifacts.execution_data.execution_infos.len());
+    artifacts.chunk_latencies.clear();
+    artifacts
+}
+
 async fn run_build_block(
     mock_transaction_executor: MockTransactionExecutorTrait,
     tx_provider: MockTransactionProvider,
@@ -627,6 +676,8 @@ async fn run_build_block(
         transaction_converter,
         N_CONCURRENT_TXS,
         TX_POLLING_INTERVAL,
+        ChunkSchedulerConfig::default(),
+        block_capacity(),
         BlockBuilderExecutionParams { deadline, is_validator },
     );
 
@@ -677,8 +728,9 @@ async fn test_build_block(#[case] test_expectations: TestExpectations) {
 #[tokio::test]
 async fn test_validate_block() {
     let input_txs = test_txs(0..3);
-    let (mock_transaction_executor, expected_block_artifacts) =
+    let (mock_transaction_executor, mut expected_block_artifacts) =
         one_chunk_mock_executor(&input_txs, input_txs.len(), true);
+    expected_block_artifacts.close_reason = BlockCloseReason::ProposerFinished;
     let mock_tx_provider = mock_tx_provider_stream_done(input_txs);
 
     let (_abort_sender, abort_receiver) = tokio::sync::oneshot::channel();
@@ -693,7 +745,7 @@ async fn test_validate_block() {
     .await
     .unwrap();
 
-    assert_eq!(result_block_artifacts, expected_block_artifacts);
+    assert_eq!(without_chunk_latencies(result_block_artifacts), expected_block_artifacts);
 }
 
 /// Tests the case where the final number of transactions in the block is smaller than the number
@@ -704,8 +756,9 @@ async fn test_validate_block_excluded_txs() {
     let n_executed_txs = first_chunk.len() + second_chunk.len();
     let final_n_executed_txs = n_executed_txs - 1;
 
-    let expected_block_artifacts =
+    let mut expected_block_artifacts =
         set_close_block_expectations(&mut mock_transaction_executor, final_n_executed_txs);
+    expected_block_artifacts.close_reason = BlockCloseReason::ProposerFinished;
 
     let mut mock_tx_provider = mock_tx_provider_limited_calls_ex(
         vec![first_chunk, second_chunk],
@@ -726,7 +779,7 @@ async fn test_validate_block_excluded_txs() {
     .await
     .unwrap();
 
-    assert_eq!(result_block_artifacts, expected_block_artifacts);
+    assert_eq!(without_chunk_latencies(result_block_artifacts), expected_block_artifacts);
 }
 
 #[rstest]
@@ -956,6 +1009,8 @@ async fn failed_l1_handler_transaction_consumed() {
             state_diff: Default::default(),
             compressed_state_diff: None,
             bouncer_weights: BouncerWeights::empty(),
+            tx_bouncer_weights: vec![BouncerWeights::empty(); 2],
+            rejected_tx_bouncer_weights: None,
             casm_hash_computation_data_sierra_gas: CasmHashComputationData::default(),
             casm_hash_computation_data_proving_gas: CasmHashComputationData::default(),
             compiled_class_hashes_for_migration: vec![],
@@ -999,12 +1054,14 @@ async fn partial_chunk_execution_proposer() {
     helper.expect_is_done(true);
     helper.expect_successful_get_new_results(0);
 
-    let expected_block_artifacts = block_execution_artifacts(
+    let mut expected_block_artifacts = block_execution_artifacts(
         expected_execution_infos,
         Default::default(),
         Default::default(),
         executed_txs.len(),
     );
+    expected_block_artifacts.close_reason =
+        BlockCloseReason::BlockFull { exceeded_dimensions: vec![] };
 
     let expected_block_artifacts_copy = expected_block_artifacts.clone();
     helper.mock_transaction_executor.expect_close_block().times(1).return_once(move |_| {
@@ -1012,6 +1069,8 @@ async fn partial_chunk_execution_proposer() {
             state_diff: expected_block_artifacts.commitment_state_diff,
             compressed_state_diff: None,
             bouncer_weights: expected_block_artifacts.bouncer_weights,
+            tx_bouncer_weights: expected_block_artifacts.tx_bouncer_weights,
+            rejected_tx_bouncer_weights: None,
             casm_hash_computation_data_sierra_gas: expected_block_artifacts
                 .casm_hash_computation_data_sierra_gas,
             casm_hash_computation_data_proving_gas: expected_block_artifacts
@@ -1037,7 +1096,7 @@ async fn partial_chunk_execution_proposer() {
     .await
     .unwrap();
 
-    assert_eq!(result_block_artifacts, expected_block_artifacts_copy);
+    assert_eq!(without_chunk_latencies(result_block_artifacts), expected_block_artifacts_copy);
 }
 
 #[rstest]
@@ -1055,7 +1114,10 @@ async fn partial_chunk_execution_validator(#[case] successful: bool) {
 
     let expected_block_artifacts = if successful {
         helper.mock_transaction_executor.expect_abort_block().times(0);
-        Some(set_close_block_expectations(&mut helper.mock_transaction_executor, 2))
+        let mut expected_block_artifacts =
+            set_close_block_expectations(&mut helper.mock_transaction_executor, 2);
+        expected_block_artifacts.close_reason = BlockCloseReason::ProposerFinished;
+        Some(expected_block_artifacts)
     } else {
         // Validator continues the loop even after the scheduler is done.
         helper.mock_transaction_executor.expect_get_new_results().times(1..).returning(Vec::new);
@@ -1087,7 +1149,10 @@ async fn partial_chunk_execution_validator(#[case] successful: bool) {
     .await;
 
     if successful {
-        assert_eq!(result_block_artifacts.unwrap(), expected_block_artifacts.unwrap());
+        assert_eq!(
+            without_chunk_latencies(result_block_artifacts.unwrap()),
+            expected_block_artifacts.unwrap()
+        );
     } else {
         // Deadline is reached since the validator never completes 3 transactions.
         assert!(matches!(
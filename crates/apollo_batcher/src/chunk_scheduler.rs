@@ -0,0 +1,191 @@
+use std::collections::BTreeMap;
+use std::time::Duration;
+
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use strum_macros::{EnumVariantNames, IntoStaticStr};
+use tokio::time::Instant;
+
+use crate::metrics::{
+    CHUNK_SCHEDULER_DECISIONS,
+    CHUNK_SCHEDULER_TX_EXECUTION_TIME,
+    CHUNK_SIZE,
+    LABEL_NAME_CHUNK_DECISION,
+};
+
+/// The weight of the latest observation in the moving average of the execution time per
+/// transaction.
+const EXECUTION_TIME_SMOOTHING_FACTOR: f64 = 0.3;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ChunkSchedulerConfig {
+    pub adaptive: bool,
+    pub initial_chunk_size: usize,
+    pub deadline_margin_millis: u64,
+}
+
+impl Default for ChunkSchedulerConfig {
+    fn default() -> Self {
+        Self { adaptive: false, initial_chunk_size: 10, deadline_margin_millis: 100 }
+    }
+}
+
+impl SerializeConfig for ChunkSchedulerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "adaptive",
+                &self.adaptive,
+                "If true, the number of transactions requested in each chunk is derived from the \
+                 observed execution time per transaction and the time left until the deadline. \
+                 Otherwise, chunks keep n_concurrent_txs transactions in progress.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "initial_chunk_size",
+                &self.initial_chunk_size,
+                "Adaptive scheduling: the number of transactions requested before any execution \
+                 time was observed.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "deadline_margin_millis",
+                &self.deadline_margin_millis,
+                "Adaptive scheduling: time (ms) before the deadline at which the block is closed. \
+                 Transactions are only requested if they are expected to finish executing before \
+                 this margin.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// What the block builder should do in the current iteration.
+#[derive(Clone, Copy, Debug, PartialEq, IntoStaticStr, EnumVariantNames)]
+#[strum(serialize_all = "snake_case")]
+pub enum ChunkDecision {
+    /// Request up to the given number of transactions.
+    Fetch(usize),
+    /// Request no transactions, and wait for the transactions in progress.
+    Wait,
+    /// Close the block.
+    Stop,
+}
+
+impl ChunkDecision {
+    fn record_metrics(&self) {
+        CHUNK_SCHEDULER_DECISIONS.increment(1, &[(LABEL_NAME_CHUNK_DECISION, self.into())]);
+        if let ChunkDecision::Fetch(chunk_size) = self {
+            CHUNK_SIZE.record_lossy(*chunk_size);
+        }
+    }
+}
+
+/// Decides how many transactions the block builder requests in each chunk.
+///
+/// The fixed strategy keeps `max_txs_in_progress` transactions in progress. The adaptive strategy
+/// additionally only requests transactions that are expected to finish executing before the
+/// deadline (minus a margin), based on a moving average of the execution time per transaction, and
+/// closes the block once the margin is reached.
+pub struct ChunkScheduler {
+    config: ChunkSchedulerConfig,
+    max_txs_in_progress: usize,
+    /// Moving average of the wall-clock time it takes the executor to complete a transaction.
+    time_per_tx: Option<Duration>,
+    /// The start of the current execution time observation, set while transactions are in
+    /// progress.
+    observation_start: Option<Instant>,
+}
+
+impl ChunkScheduler {
+    pub fn new(config: ChunkSchedulerConfig, max_txs_in_progress: usize) -> Self {
+        Self { config, max_txs_in_progress, time_per_tx: None, observation_start: None }
+    }
+
+    /// Returns the decision for the current iteration, and records it in the metrics.
+    pub fn next_chunk(
+        &self,
+        n_txs_in_progress: usize,
+        time_to_deadline: Duration,
+    ) -> ChunkDecision {
+        let decision = self.decide(n_txs_in_progress, time_to_deadline);
+        decision.record_metrics();
+        decision
+    }
+
+    fn decide(&self, n_txs_in_progress: usize, time_to_deadline: Duration) -> ChunkDecision {
+        let n_free_slots = self.max_txs_in_progress.saturating_sub(n_txs_in_progress);
+        if !self.config.adaptive {
+            return fetch_or_wait(n_free_slots);
+        }
+
+        let Some(time_budget) =
+            time_to_deadline.checked_sub(Duration::from_millis(self.config.deadline_margin_millis))
+        else {
+            return ChunkDecision::Stop;
+        };
+        if time_budget.is_zero() {
+            return ChunkDecision::Stop;
+        }
+
+        let Some(time_per_tx) = self.time_per_tx else {
+            // Nothing was observed yet; request a small chunk to measure the execution time.
+            if n_txs_in_progress > 0 {
+                return ChunkDecision::Wait;
+            }
+            return fetch_or_wait(n_free_slots.min(self.config.initial_chunk_size));
+        };
+
+        let n_affordable_txs = if time_per_tx.is_zero() {
+            usize::MAX
+        } else {
+            usize::try_from(time_budget.as_nanos() / time_per_tx.as_nanos()).unwrap_or(usize::MAX)
+        };
+        let n_txs_to_fetch = n_affordable_txs.saturating_sub(n_txs_in_progress).min(n_free_slots);
+        if n_txs_to_fetch == 0 && n_txs_in_progress == 0 && n_free_slots > 0 {
+            // Not even a single transaction is expected to finish in time.
+            return ChunkDecision::Stop;
+        }
+        fetch_or_wait(n_txs_to_fetch)
+    }
+
+    /// Records that transactions were added to the executor. `n_txs_in_progress` is the number of
+    /// transactions in progress before they were added.
+    pub fn on_txs_started(&mut self, now: Instant, n_txs_in_progress: usize) {
+        if n_txs_in_progress == 0 {
+            self.observation_start = Some(now);
+        }
+    }
+
+    /// Records that the executor completed `n_executed_txs` transactions. `n_txs_in_progress` is
+    /// the number of transactions still in progress.
+    pub fn on_txs_executed(
+        &mut self,
+        now: Instant,
+        n_executed_txs: usize,
+        n_txs_in_progress: usize,
+    ) {
+        let Some(observation_start) = self.observation_start else {
+            return;
+        };
+        if n_executed_txs > 0 {
+            let n_executed_txs = u32::try_from(n_executed_txs).unwrap_or(u32::MAX);
+            let sample = now.saturating_duration_since(observation_start) / n_executed_txs;
+            let time_per_tx = match self.time_per_tx {
+                Some(time_per_tx) => {
+                    time_per_tx.mul_f64(1.0 - EXECUTION_TIME_SMOOTHING_FACTOR)
+                        + sample.mul_f64(EXECUTION_TIME_SMOOTHING_FACTOR)
+                }
+                None => sample,
+            };
+            CHUNK_SCHEDULER_TX_EXECUTION_TIME.set(time_per_tx.as_secs_f64());
+            self.time_per_tx = Some(time_per_tx);
+        }
+        self.observation_start = (n_txs_in_progress > 0).then_some(now);
+    }
+}
+
+fn fetch_or_wait(n_txs_to_fetch: usize) -> ChunkDecision {
+    if n_txs_to_fetch == 0 { ChunkDecision::Wait } else { ChunkDecision::Fetch(n_txs_to_fetch) }
+}
@@ -0,0 +1,95 @@
+use std::time::Duration;
+
+use rstest::rstest;
+use tokio::time::Instant;
+
+use crate::chunk_scheduler::{ChunkDecision, ChunkScheduler, ChunkSchedulerConfig};
+
+const MAX_TXS_IN_PROGRESS: usize = 100;
+const DEADLINE_MARGIN: Duration = Duration::from_millis(100);
+
+fn adaptive_scheduler() -> ChunkScheduler {
+    let config = ChunkSchedulerConfig {
+        adaptive: true,
+        initial_chunk_size: 10,
+        deadline_margin_millis: 100,
+    };
+    ChunkScheduler::new(config, MAX_TXS_IN_PROGRESS)
+}
+
+/// Returns an adaptive scheduler that observed the given execution time per transaction.
+fn adaptive_scheduler_with_observation(time_per_tx: Duration) -> ChunkScheduler {
+    let mut scheduler = adaptive_scheduler();
+    let start = Instant::now();
+    scheduler.on_txs_started(start, 0);
+    scheduler.on_txs_executed(start + time_per_tx * 10, 10, 0);
+    scheduler
+}
+
+#[rstest]
+#[case::no_txs_in_progress(0, ChunkDecision::Fetch(MAX_TXS_IN_PROGRESS))]
+#[case::some_txs_in_progress(30, ChunkDecision::Fetch(70))]
+#[case::full(MAX_TXS_IN_PROGRESS, ChunkDecision::Wait)]
+fn fixed_scheduler_ignores_the_deadline(
+    #[case] n_txs_in_progress: usize,
+    #[case] expected_decision: ChunkDecision,
+    #[values(Duration::ZERO, Duration::from_secs(1))] time_to_deadline: Duration,
+) {
+    let scheduler = ChunkScheduler::new(ChunkSchedulerConfig::default(), MAX_TXS_IN_PROGRESS);
+
+    assert_eq!(scheduler.next_chunk(n_txs_in_progress, time_to_deadline), expected_decision);
+}
+
+#[test]
+fn adaptive_scheduler_probes_before_observing() {
+    let scheduler = adaptive_scheduler();
+
+    assert_eq!(scheduler.next_chunk(0, Duration::from_secs(1)), ChunkDecision::Fetch(10));
+    assert_eq!(scheduler.next_chunk(10, Duration::from_secs(1)), ChunkDecision::Wait);
+}
+
+#[rstest]
+// 900ms of budget at 10ms per transaction.
+#[case::limited_by_capacity(Duration::from_millis(10), 0, ChunkDecision::Fetch(90))]
+#[case::limited_by_capacity_with_txs_in_progress(
+    Duration::from_millis(10),
+    50,
+    ChunkDecision::Fetch(40)
+)]
+#[case::limited_by_max_txs_in_progress(Duration::from_millis(1), 50, ChunkDecision::Fetch(50))]
+#[case::txs_in_progress_exhaust_the_budget(Duration::from_millis(10), 90, ChunkDecision::Wait)]
+#[case::no_tx_fits_in_the_budget(Duration::from_secs(1), 0, ChunkDecision::Stop)]
+fn adaptive_scheduler_sizes_chunks_by_execution_time(
+    #[case] time_per_tx: Duration,
+    #[case] n_txs_in_progress: usize,
+    #[case] expected_decision: ChunkDecision,
+) {
+    let scheduler = adaptive_scheduler_with_observation(time_per_tx);
+
+    assert_eq!(scheduler.next_chunk(n_txs_in_progress, Duration::from_secs(1)), expected_decision);
+}
+
+#[rstest]
+fn adaptive_scheduler_stops_within_the_deadline_margin(
+    #[values(0, 10)] n_txs_in_progress: usize,
+    #[values(Duration::ZERO, DEADLINE_MARGIN)] time_to_deadline: Duration,
+) {
+    let scheduler = adaptive_scheduler_with_observation(Duration::from_millis(1));
+
+    assert_eq!(scheduler.next_chunk(n_txs_in_progress, time_to_deadline), ChunkDecision::Stop);
+}
+
+#[test]
+fn adaptive_scheduler_averages_observations() {
+    let mut scheduler = adaptive_scheduler_with_observation(Duration::from_millis(10));
+    let start = Instant::now();
+    scheduler.on_txs_started(start, 0);
+    // The first 10 transactions take 20ms each, while the remaining 10 are still in progress.
+    scheduler.on_txs_executed(start + Duration::from_millis(200), 10, 10);
+    // Then 10 transactions take 10ms each.
+    scheduler.on_txs_executed(start + Duration::from_millis(300), 10, 0);
+
+    // The moving average is 0.7 * (0.7 * 10 + 0.3 * 20) + 0.3 * 10 = 12.1ms, so 74 transactions
+    // fit in the 900ms budget.
+    assert_eq!(scheduler.next_chunk(0, Duration::from_secs(1)), ChunkDecision::Fetch(74));
+}
@@ -39,6 +39,19 @@ impl ComponentRequestHandler<BatcherRequest, BatcherResponse> for Batcher {
             BatcherRequest::RevertBlock(input) => {
                 BatcherResponse::RevertBlock(self.revert_block(input).await)
             }
+            BatcherRequest::GetExecutionProfiles(filter) => {
+                BatcherResponse::GetExecutionProfiles(Ok(self
+                    .get_execution_profiles(&filter)
+                    .await))
+            }
+            BatcherRequest::GetLatestPreconfirmedBlock => {
+                BatcherResponse::GetLatestPreconfirmedBlock(self.get_latest_preconfirmed_block())
+            }
+            BatcherRequest::GetPreconfirmedTransactionStatus(tx_hash) => {
+                BatcherResponse::GetPreconfirmedTransactionStatus(
+                    self.get_preconfirmed_transaction_status(tx_hash),
+                )
+            }
         }
     }
 }
@@ -7,8 +7,11 @@ use serde::{Deserialize, Serialize};
 use validator::{Validate, ValidationError};
 
 use crate::block_builder::BlockBuilderConfig;
+use crate::execution_profile::ExecutionProfileConfig;
 use crate::pre_confirmed_block_writer::PreconfirmedBlockWriterConfig;
 use crate::pre_confirmed_cende_client::PreconfirmedCendeConfig;
+use crate::proposal_recorder::ProposalRecorderConfig;
+use crate::speculative_execution::SpeculativeExecutionConfig;
 
 /// The batcher related configuration.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
@@ -21,7 +24,13 @@ pub struct BatcherConfig {
     pub pre_confirmed_block_writer_config: PreconfirmedBlockWriterConfig,
     pub contract_class_manager_config: ContractClassManagerConfig,
     pub max_l1_handler_txs_per_block_proposal: usize,
+    // Number of latest committed blocks whose transactions are kept in the storage, to be returned
+    // to the mempool and the L1 provider if their block is reverted.
+    pub committed_txs_retention_block_count: usize,
     pub pre_confirmed_cende_config: PreconfirmedCendeConfig,
+    pub proposal_recorder_config: ProposalRecorderConfig,
+    pub speculative_execution_config: SpeculativeExecutionConfig,
+    pub execution_profile_config: ExecutionProfileConfig,
 }
 
 impl SerializeConfig for BatcherConfig {
@@ -47,6 +56,13 @@ impl SerializeConfig for BatcherConfig {
                 "The maximum number of L1 handler transactions to include in a block proposal.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "committed_txs_retention_block_count",
+                &self.committed_txs_retention_block_count,
+                "Number of latest committed blocks whose transactions are kept in the storage, to \
+                 be returned to the mempool and the L1 provider if their block is reverted.",
+                ParamPrivacyInput::Public,
+            ),
         ]);
         dump.append(&mut prepend_sub_config_name(self.storage.dump(), "storage"));
         dump.append(&mut prepend_sub_config_name(
@@ -65,6 +81,18 @@ impl SerializeConfig for BatcherConfig {
             self.pre_confirmed_cende_config.dump(),
             "pre_confirmed_cende_config",
         ));
+        dump.append(&mut prepend_sub_config_name(
+            self.proposal_recorder_config.dump(),
+            "proposal_recorder_config",
+        ));
+        dump.append(&mut prepend_sub_config_name(
+            self.speculative_execution_config.dump(),
+            "speculative_execution_config",
+        ));
+        dump.append(&mut prepend_sub_config_name(
+            self.execution_profile_config.dump(),
+            "execution_profile_config",
+        ));
         dump
     }
 }
@@ -88,7 +116,11 @@ impl Default for BatcherConfig {
             pre_confirmed_block_writer_config: PreconfirmedBlockWriterConfig::default(),
             contract_class_manager_config: ContractClassManagerConfig::default(),
             max_l1_handler_txs_per_block_proposal: 3,
+            committed_txs_retention_block_count: 100,
             pre_confirmed_cende_config: PreconfirmedCendeConfig::default(),
+            proposal_recorder_config: ProposalRecorderConfig::default(),
+            speculative_execution_config: SpeculativeExecutionConfig::default(),
+            execution_profile_config: ExecutionProfileConfig::default(),
         }
     }
 }
@@ -0,0 +1,142 @@
+use std::collections::{BTreeMap, VecDeque};
+
+use apollo_batcher_types::batcher_types::{
+    ExecutionProfile,
+    ExecutionProfilesFilter,
+    ProposalId,
+    TransactionExecutionProfile,
+};
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use blockifier::bouncer::BouncerWeights;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use tracing::info;
+
+use crate::block_builder::BlockExecutionArtifacts;
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ExecutionProfileConfig {
+    pub n_retained_profiles: usize,
+    pub log_on_decision: bool,
+}
+
+impl Default for ExecutionProfileConfig {
+    fn default() -> Self {
+        Self { n_retained_profiles: 64, log_on_decision: false }
+    }
+}
+
+impl SerializeConfig for ExecutionProfileConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "n_retained_profiles",
+                &self.n_retained_profiles,
+                "The number of most recent proposal execution profiles to keep for queries. 0 \
+                 disables the execution profiles.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "log_on_decision",
+                &self.log_on_decision,
+                "If true, a summary of the execution profile of the decided proposal is logged.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Returns the execution profile of a proposal that finished its execution.
+pub fn execution_profile(
+    proposal_id: ProposalId,
+    height: BlockNumber,
+    is_validator: bool,
+    artifacts: &BlockExecutionArtifacts,
+    block_capacity: BouncerWeights,
+) -> ExecutionProfile {
+    let txs = artifacts
+        .execution_data
+        .execution_infos
+        .iter()
+        .zip(&artifacts.tx_bouncer_weights)
+        .zip(&artifacts.chunk_latencies)
+        .map(|(((tx_hash, execution_info), bouncer_weights), chunk_latency)| {
+            TransactionExecutionProfile {
+                tx_hash: *tx_hash,
+                bouncer_weights: *bouncer_weights,
+                l2_gas: execution_info.receipt.gas.l2_gas,
+                chunk_latency_micros: chunk_latency
+                    .as_micros()
+                    .try_into()
+                    .expect("Chunk latency should fit in u64."),
+                reverted: execution_info.revert_error.is_some(),
+            }
+        })
+        .collect();
+
+    ExecutionProfile {
+        proposal_id,
+        height,
+        is_validator,
+        close_reason: artifacts.close_reason.clone(),
+        bouncer_weights: artifacts.bouncer_weights,
+        block_capacity,
+        n_rejected_txs: artifacts.execution_data.rejected_tx_hashes.len(),
+        txs,
+    }
+}
+
+/// Logs what filled the given proposal.
+pub fn log_execution_profile_summary(proposal_id: ProposalId, artifacts: &BlockExecutionArtifacts) {
+    let execution_infos = &artifacts.execution_data.execution_infos;
+    let n_reverted_txs =
+        execution_infos.values().filter(|info| info.revert_error.is_some()).count();
+    let highest_latency_tx = artifacts
+        .chunk_latencies
+        .iter()
+        .zip(execution_infos.keys())
+        .max_by_key(|(chunk_latency, _)| **chunk_latency);
+    info!(
+        "Execution profile of proposal {proposal_id}: close reason: {:?}, {} transactions ({} \
+         reverted, {} rejected), bouncer weights: {:?}, highest chunk latency: {:?}.",
+        artifacts.close_reason,
+        execution_infos.len(),
+        n_reverted_txs,
+        artifacts.execution_data.rejected_tx_hashes.len(),
+        artifacts.bouncer_weights,
+        highest_latency_tx.map(|(chunk_latency, tx_hash)| (tx_hash, chunk_latency)),
+    );
+}
+
+/// A bounded log of the execution profiles of the most recent proposals; once full, the oldest
+/// profiles are discarded.
+pub struct ExecutionProfileLog {
+    profiles: VecDeque<ExecutionProfile>,
+    capacity: usize,
+}
+
+impl ExecutionProfileLog {
+    pub fn new(capacity: usize) -> Self {
+        ExecutionProfileLog { profiles: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.capacity > 0
+    }
+
+    pub fn record(&mut self, profile: ExecutionProfile) {
+        if !self.is_enabled() {
+            return;
+        }
+        if self.profiles.len() == self.capacity {
+            self.profiles.pop_front();
+        }
+        self.profiles.push_back(profile);
+    }
+
+    /// Returns the profiles that match the given filter, oldest first.
+    pub fn query(&self, filter: &ExecutionProfilesFilter) -> Vec<ExecutionProfile> {
+        self.profiles.iter().filter(|profile| filter.matches(profile)).cloned().collect()
+    }
+}
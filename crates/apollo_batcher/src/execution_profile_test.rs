@@ -0,0 +1,82 @@
+use apollo_batcher_types::batcher_types::{
+    BlockCloseReason,
+    ExecutionProfile,
+    ExecutionProfilesFilter,
+    ProposalId,
+    TransactionExecutionProfile,
+};
+use blockifier::bouncer::BouncerWeights;
+use rstest::rstest;
+use starknet_api::block::BlockNumber;
+use starknet_api::execution_resources::GasAmount;
+
+use crate::block_builder::BlockExecutionArtifacts;
+use crate::execution_profile::{execution_profile, ExecutionProfileLog};
+
+const HEIGHT: BlockNumber = BlockNumber(3);
+
+fn profile(proposal_id: u64, height: BlockNumber) -> ExecutionProfile {
+    execution_profile(
+        ProposalId(proposal_id),
+        height,
+        false,
+        &BlockExecutionArtifacts::create_for_testing(),
+        BouncerWeights::max(),
+    )
+}
+
+#[test]
+fn execution_profile_from_artifacts() {
+    let artifacts = BlockExecutionArtifacts::create_for_testing();
+
+    let profile = execution_profile(ProposalId(1), HEIGHT, true, &artifacts, BouncerWeights::max());
+
+    assert_eq!(profile.close_reason, BlockCloseReason::DeadlineReached);
+    assert_eq!(profile.n_rejected_txs, artifacts.execution_data.rejected_tx_hashes.len());
+    assert_eq!(profile.txs.len(), artifacts.execution_data.execution_infos.len());
+    for (tx_profile, tx_hash) in
+        profile.txs.iter().zip(artifacts.execution_data.execution_infos.keys())
+    {
+        assert_eq!(
+            tx_profile,
+            &TransactionExecutionProfile {
+                tx_hash: *tx_hash,
+                bouncer_weights: BouncerWeights { n_txs: 1, ..BouncerWeights::empty() },
+                l2_gas: GasAmount::default(),
+                chunk_latency_micros: 100,
+                reverted: false,
+            }
+        );
+    }
+}
+
+#[rstest]
+#[case::all(ExecutionProfilesFilter::default(), vec![1, 2, 3])]
+#[case::height(ExecutionProfilesFilter { height: Some(HEIGHT), ..Default::default() }, vec![1, 2])]
+#[case::proposal_id(
+    ExecutionProfilesFilter { proposal_id: Some(ProposalId(2)), ..Default::default() },
+    vec![2]
+)]
+fn query_execution_profiles(
+    #[case] filter: ExecutionProfilesFilter,
+    #[case] expected_proposal_ids: Vec<u64>,
+) {
+    let mut log = ExecutionProfileLog::new(3);
+    log.record(profile(0, HEIGHT));
+    log.record(profile(1, HEIGHT));
+    log.record(profile(2, HEIGHT));
+    log.record(profile(3, HEIGHT.unchecked_next()));
+
+    let proposal_ids: Vec<u64> =
+        log.query(&filter).iter().map(|profile| profile.proposal_id.0).collect();
+
+    assert_eq!(proposal_ids, expected_proposal_ids);
+}
+
+#[test]
+fn disabled_log_records_nothing() {
+    let mut log = ExecutionProfileLog::new(0);
+    log.record(profile(0, HEIGHT));
+
+    assert!(log.query(&ExecutionProfilesFilter::default()).is_empty());
+}
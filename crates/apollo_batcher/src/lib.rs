@@ -5,13 +5,28 @@ pub mod block_builder;
 #[cfg(test)]
 mod block_builder_test;
 pub mod cende_client_types;
+pub mod chunk_scheduler;
+#[cfg(test)]
+mod chunk_scheduler_test;
 pub mod communication;
 pub mod config;
+pub mod execution_profile;
+#[cfg(test)]
+mod execution_profile_test;
 pub mod metrics;
 pub mod pre_confirmed_block_writer;
 pub mod pre_confirmed_cende_client;
 #[cfg(test)]
 mod pre_confirmed_cende_client_test;
+pub mod proposal_recorder;
+#[cfg(test)]
+mod proposal_recorder_test;
+pub mod proposal_replay;
+#[cfg(test)]
+mod proposal_replay_test;
+pub mod speculative_execution;
+#[cfg(test)]
+mod speculative_execution_test;
 #[cfg(test)]
 mod test_utils;
 mod transaction_executor;
@@ -1,5 +1,5 @@
 use apollo_batcher_types::communication::BATCHER_REQUEST_LABELS;
-use apollo_metrics::define_metrics;
+use apollo_metrics::{define_metrics, generate_permutation_labels};
 use blockifier::metrics::{
     CALLS_RUNNING_NATIVE,
     CLASS_CACHE_HITS,
@@ -9,6 +9,10 @@ use blockifier::metrics::{
     TOTAL_CALLS,
 };
 use starknet_api::block::BlockNumber;
+use strum::VariantNames;
+
+use crate::chunk_scheduler::ChunkDecision;
+use crate::speculative_execution::SpeculationResult;
 
 define_metrics!(
     Batcher => {
@@ -31,6 +35,12 @@ define_metrics!(
 
         MetricCounter { FULL_BLOCKS, "batcher_full_blocks", "Counter of blocks closed on full capacity", init = 0 },
         MetricCounter { PRECONFIRMED_BLOCK_WRITTEN, "batcher_preconfirmed_block_written", "Counter of preconfirmed blocks written to storage", init = 0 },
+        // Chunk scheduling
+        LabeledMetricCounter { CHUNK_SCHEDULER_DECISIONS, "batcher_chunk_scheduler_decisions", "Counter of block builder chunk scheduling decisions, per decision", init = 0, labels = CHUNK_DECISION_LABELS },
+        MetricHistogram { CHUNK_SIZE, "batcher_chunk_size", "The number of transactions requested in each chunk" },
+        MetricGauge { CHUNK_SCHEDULER_TX_EXECUTION_TIME, "batcher_chunk_scheduler_tx_execution_time_secs", "The moving average of the executor's wall-clock time per transaction, as estimated by the chunk scheduler (secs)" },
+        // Speculative execution
+        LabeledMetricCounter { SPECULATIONS, "batcher_speculations", "Counter of speculative executions of the next height, per result", init = 0, labels = SPECULATION_RESULT_LABELS },
     },
     Infra => {
         // Batcher request labels
@@ -42,6 +52,20 @@ define_metrics!(
     },
 );
 
+pub const LABEL_NAME_CHUNK_DECISION: &str = "decision";
+
+generate_permutation_labels! {
+    CHUNK_DECISION_LABELS,
+    (LABEL_NAME_CHUNK_DECISION, ChunkDecision),
+}
+
+pub const LABEL_NAME_SPECULATION_RESULT: &str = "result";
+
+generate_permutation_labels! {
+    SPECULATION_RESULT_LABELS,
+    (LABEL_NAME_SPECULATION_RESULT, SpeculationResult),
+}
+
 pub fn register_metrics(storage_height: BlockNumber) {
     STORAGE_HEIGHT.register();
     STORAGE_HEIGHT.set_lossy(storage_height.0);
@@ -63,6 +87,12 @@ pub fn register_metrics(storage_height: BlockNumber) {
     FULL_BLOCKS.register();
     PRECONFIRMED_BLOCK_WRITTEN.register();
 
+    CHUNK_SCHEDULER_DECISIONS.register();
+    CHUNK_SIZE.register();
+    CHUNK_SCHEDULER_TX_EXECUTION_TIME.register();
+
+    SPECULATIONS.register();
+
     // Blockifier's metrics
     CALLS_RUNNING_NATIVE.register();
     CLASS_CACHE_HITS.register();
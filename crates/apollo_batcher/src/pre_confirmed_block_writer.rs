@@ -245,6 +245,16 @@ fn is_round_mismatch_error(
     error: &PreconfirmedCendeClientError,
     next_write_iteration: u64,
 ) -> bool {
+    // Local sinks detect a round mismatch directly.
+    if let PreconfirmedCendeClientError::RoundMismatch { block_number, round, .. } = error {
+        error!(
+            "A higher round was detected for block_number: {}. rejected round: {}. Stopping \
+             pre-confirmed block writer.",
+            block_number, round,
+        );
+        return true;
+    }
+
     let PreconfirmedCendeClientError::CendeRecorderError {
         block_number,
         round,
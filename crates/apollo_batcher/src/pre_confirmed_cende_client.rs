@@ -1,13 +1,21 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
 
-use apollo_batcher_types::batcher_types::Round;
+use apollo_batcher_types::batcher_types::{PreconfirmedTransactionStatus, Round};
+use apollo_batcher_types::communication::{PreconfirmedBlockSource, SharedPreconfirmedBlockSource};
 use apollo_config::dumping::{ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use async_trait::async_trait;
+use futures::stream::{self, BoxStream};
+use futures::StreamExt;
 use reqwest::{Client, StatusCode};
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use starknet_api::block::BlockNumber;
+use starknet_api::transaction::TransactionHash;
 use thiserror::Error;
+use tokio::sync::watch;
 use tracing::{debug, error, trace, warn};
 use url::Url;
 
@@ -28,6 +36,20 @@ pub enum PreconfirmedCendeClientError {
         write_iteration: u64,
         status_code: StatusCode,
     },
+    #[error(
+        "A higher round was already written. block_number: {block_number}, round: {round}, \
+         latest_block_number: {latest_block_number}, latest_round: {latest_round}."
+    )]
+    RoundMismatch {
+        block_number: BlockNumber,
+        round: Round,
+        latest_block_number: BlockNumber,
+        latest_round: Round,
+    },
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
 }
 
 pub type PreconfirmedCendeClientResult<T> = Result<T, PreconfirmedCendeClientError>;
@@ -65,29 +87,81 @@ impl PreconfirmedCendeClient {
     }
 }
 
+/// Where the pre-confirmed blocks are written to.
+#[derive(Clone, Copy, Debug, Default, Deserialize, PartialEq, Serialize)]
+pub enum PreconfirmedBlockSink {
+    /// Sends the pre-confirmed blocks to the Cende recorder.
+    #[default]
+    CendeRecorder,
+    /// Writes the latest pre-confirmed block to a file in a local directory.
+    LocalStorage,
+    /// Publishes the pre-confirmed blocks to in-process subscribers, served by the monitoring
+    /// endpoint.
+    InProcess,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct PreconfirmedCendeConfig {
+    pub sink: PreconfirmedBlockSink,
     pub recorder_url: Url,
+    pub local_storage_dir: PathBuf,
 }
 
 impl Default for PreconfirmedCendeConfig {
     fn default() -> Self {
         Self {
+            sink: PreconfirmedBlockSink::default(),
             recorder_url: "https://recorder_url"
                 .parse()
                 .expect("recorder_url must be a valid Recorder URL"),
+            local_storage_dir: "/data/batcher/pre_confirmed".into(),
         }
     }
 }
 
 impl SerializeConfig for PreconfirmedCendeConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from([ser_param(
-            "recorder_url",
-            &self.recorder_url,
-            "The URL of the Pythonic cende_recorder",
-            ParamPrivacyInput::Private,
-        )])
+        BTreeMap::from_iter([
+            ser_param(
+                "sink",
+                &self.sink,
+                "Where the pre-confirmed blocks are written to. One of CendeRecorder, \
+                 LocalStorage or InProcess.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "recorder_url",
+                &self.recorder_url,
+                "The URL of the Pythonic cende_recorder",
+                ParamPrivacyInput::Private,
+            ),
+            ser_param(
+                "local_storage_dir",
+                &self.local_storage_dir,
+                "The directory the latest pre-confirmed block is written to when the sink is \
+                 LocalStorage.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// The pre-confirmed block client selected in the config, and the source of the pre-confirmed
+/// blocks if the sink is [PreconfirmedBlockSink::InProcess].
+pub fn create_pre_confirmed_cende_client(
+    config: PreconfirmedCendeConfig,
+) -> (Arc<dyn PreconfirmedCendeClientTrait>, Option<SharedPreconfirmedBlockSource>) {
+    match config.sink {
+        PreconfirmedBlockSink::CendeRecorder => {
+            (Arc::new(PreconfirmedCendeClient::new(config)), None)
+        }
+        PreconfirmedBlockSink::LocalStorage => {
+            (Arc::new(LocalStoragePreconfirmedClient::new(config.local_storage_dir)), None)
+        }
+        PreconfirmedBlockSink::InProcess => {
+            let feed = PreconfirmedBlockFeed::default();
+            (Arc::new(feed.clone()), Some(Arc::new(feed)))
+        }
     }
 }
 
@@ -152,3 +226,182 @@ impl PreconfirmedCendeClientTrait for PreconfirmedCendeClient {
         }
     }
 }
+
+/// The block number and round of the latest write. Writes of an earlier block or of a lower round
+/// are rejected, as the Cende recorder does.
+#[derive(Default)]
+struct LatestWrite(Option<(BlockNumber, Round)>);
+
+impl LatestWrite {
+    fn update(
+        &mut self,
+        block_number: BlockNumber,
+        round: Round,
+    ) -> PreconfirmedCendeClientResult<()> {
+        if let Some((latest_block_number, latest_round)) = self.0 {
+            if (block_number, round) < (latest_block_number, latest_round) {
+                return Err(PreconfirmedCendeClientError::RoundMismatch {
+                    block_number,
+                    round,
+                    latest_block_number,
+                    latest_round,
+                });
+            }
+        }
+        self.0 = Some((block_number, round));
+        Ok(())
+    }
+}
+
+/// The name of the file the latest pre-confirmed block is written to.
+pub const LOCAL_PRE_CONFIRMED_BLOCK_FILE_NAME: &str = "pre_confirmed_block.json";
+
+/// Writes the latest pre-confirmed block to a file, for deployments without a Cende recorder.
+/// The file is replaced atomically, so readers always see a complete block.
+pub struct LocalStoragePreconfirmedClient {
+    dir: PathBuf,
+    latest_write: Mutex<LatestWrite>,
+}
+
+impl LocalStoragePreconfirmedClient {
+    pub fn new(dir: PathBuf) -> Self {
+        Self { dir, latest_write: Mutex::default() }
+    }
+
+    pub fn file_path(&self) -> PathBuf {
+        self.dir.join(LOCAL_PRE_CONFIRMED_BLOCK_FILE_NAME)
+    }
+}
+
+#[async_trait]
+impl PreconfirmedCendeClientTrait for LocalStoragePreconfirmedClient {
+    async fn write_pre_confirmed_block(
+        &self,
+        pre_confirmed_block: CendeWritePreconfirmedBlock,
+    ) -> PreconfirmedCendeClientResult<()> {
+        let block_number = pre_confirmed_block.block_number;
+        let round = pre_confirmed_block.round;
+        let write_iteration = pre_confirmed_block.write_iteration;
+        self.latest_write
+            .lock()
+            .expect("Latest write lock is poisoned.")
+            .update(block_number, round)?;
+
+        let serialized_block = serde_json::to_vec(&pre_confirmed_block)?;
+        let dir = self.dir.clone();
+        let path = self.file_path();
+        tokio::task::spawn_blocking(move || {
+            std::fs::create_dir_all(&dir)?;
+            let tmp_path = path.with_extension("json.tmp");
+            std::fs::write(&tmp_path, serialized_block)?;
+            std::fs::rename(tmp_path, path)
+        })
+        .await
+        .expect("Writing the pre-confirmed block panicked.")?;
+
+        debug!(
+            "Wrote pre-confirmed block to local storage. block_number={block_number}, \
+             round={round}, write_iteration={write_iteration}",
+        );
+        PRECONFIRMED_BLOCK_WRITTEN.increment(1);
+        Ok(())
+    }
+}
+
+/// Publishes the pre-confirmed blocks to in-process subscribers, e.g. the monitoring endpoint,
+/// which serves the pre-confirmed blocks and transaction statuses directly from the batcher.
+///
+/// Subscribers see the latest pre-confirmed block; intermediate writes may be skipped by slow
+/// subscribers.
+#[derive(Clone)]
+pub struct PreconfirmedBlockFeed {
+    sender: Arc<watch::Sender<Option<Arc<CendeWritePreconfirmedBlock>>>>,
+    latest_write: Arc<Mutex<LatestWrite>>,
+}
+
+impl Default for PreconfirmedBlockFeed {
+    fn default() -> Self {
+        Self { sender: Arc::new(watch::channel(None).0), latest_write: Arc::default() }
+    }
+}
+
+impl PreconfirmedBlockFeed {
+    /// Returns the latest pre-confirmed block, if any was written.
+    pub fn latest(&self) -> Option<Arc<CendeWritePreconfirmedBlock>> {
+        self.sender.borrow().clone()
+    }
+}
+
+impl PreconfirmedBlockSource for PreconfirmedBlockFeed {
+    fn subscribe(&self) -> BoxStream<'static, Value> {
+        let mut receiver = self.sender.subscribe();
+        // The latest block, if any, is streamed first.
+        receiver.mark_changed();
+        stream::unfold(receiver, |mut receiver| async move {
+            loop {
+                // Stops once the feed is dropped.
+                receiver.changed().await.ok()?;
+                let Some(pre_confirmed_block) = receiver.borrow_and_update().clone() else {
+                    continue;
+                };
+                if let Some(value) = pre_confirmed_block_to_json(&pre_confirmed_block) {
+                    return Some((value, receiver));
+                }
+            }
+        })
+        .boxed()
+    }
+
+    fn latest_block(&self) -> Option<Value> {
+        pre_confirmed_block_to_json(&*self.latest()?)
+    }
+
+    fn transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> Option<PreconfirmedTransactionStatus> {
+        let latest = self.latest()?;
+        let block = &latest.pre_confirmed_block;
+        let index = block.transactions.iter().position(|tx| tx.transaction_hash() == tx_hash)?;
+        match block.transaction_receipts.get(index) {
+            Some(Some(_)) => Some(PreconfirmedTransactionStatus::PreConfirmed),
+            _ => Some(PreconfirmedTransactionStatus::Candidate),
+        }
+    }
+}
+
+fn pre_confirmed_block_to_json(pre_confirmed_block: &CendeWritePreconfirmedBlock) -> Option<Value> {
+    serde_json::to_value(pre_confirmed_block)
+        .inspect_err(|err| {
+            error!(
+                "Failed to serialize pre-confirmed block. block_number={}, round={}: {err}",
+                pre_confirmed_block.block_number, pre_confirmed_block.round
+            )
+        })
+        .ok()
+}
+
+#[async_trait]
+impl PreconfirmedCendeClientTrait for PreconfirmedBlockFeed {
+    async fn write_pre_confirmed_block(
+        &self,
+        pre_confirmed_block: CendeWritePreconfirmedBlock,
+    ) -> PreconfirmedCendeClientResult<()> {
+        let block_number = pre_confirmed_block.block_number;
+        let round = pre_confirmed_block.round;
+        let write_iteration = pre_confirmed_block.write_iteration;
+        self.latest_write
+            .lock()
+            .expect("Latest write lock is poisoned.")
+            .update(block_number, round)?;
+
+        // Having no subscribers is not an error; the block is kept for future subscribers.
+        self.sender.send_replace(Some(Arc::new(pre_confirmed_block)));
+        trace!(
+            "Published pre-confirmed block. block_number={block_number}, round={round}, \
+             write_iteration={write_iteration}",
+        );
+        PRECONFIRMED_BLOCK_WRITTEN.increment(1);
+        Ok(())
+    }
+}
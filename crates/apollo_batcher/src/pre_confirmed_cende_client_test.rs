@@ -1,5 +1,7 @@
-use apollo_batcher_types::batcher_types::Round;
+use apollo_batcher_types::batcher_types::{PreconfirmedTransactionStatus, Round};
+use apollo_batcher_types::communication::PreconfirmedBlockSource;
 use assert_matches::assert_matches;
+use futures::StreamExt;
 use mockito::{Server, ServerGuard};
 use starknet_api::block::{
     BlockNumber,
@@ -11,16 +13,25 @@ use starknet_api::block::{
 use starknet_api::core::ContractAddress;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::felt;
+use starknet_api::transaction::TransactionHash;
 
 use super::pre_confirmed_cende_client::{
     CendeWritePreconfirmedBlock,
+    LocalStoragePreconfirmedClient,
+    PreconfirmedBlockFeed,
     PreconfirmedCendeClient,
     PreconfirmedCendeClientError,
     PreconfirmedCendeClientTrait,
     PreconfirmedCendeConfig,
     RECORDER_WRITE_PRE_CONFIRMED_BLOCK_PATH,
 };
-use crate::cende_client_types::{CendeBlockMetadata, CendePreconfirmedBlock};
+use crate::cende_client_types::{
+    CendeBlockMetadata,
+    CendePreconfirmedBlock,
+    CendePreconfirmedTransaction,
+    L1HandlerTransaction,
+    StarknetClientTransactionReceipt,
+};
 
 const TEST_BLOCK_NUMBER: BlockNumber = BlockNumber(123);
 const TEST_ROUND: Round = 1;
@@ -30,7 +41,10 @@ const TEST_GAS_PRICE: GasPrice = GasPrice(1);
 const TEST_SEQUENCER_ADDRESS: &str = "0x111";
 
 fn test_cende_client(server: &mut ServerGuard) -> PreconfirmedCendeClient {
-    let config = PreconfirmedCendeConfig { recorder_url: server.url().parse().unwrap() };
+    let config = PreconfirmedCendeConfig {
+        recorder_url: server.url().parse().unwrap(),
+        ..Default::default()
+    };
     PreconfirmedCendeClient::new(config)
 }
 
@@ -143,6 +157,7 @@ async fn test_write_pre_confirmed_block_server_error() {
 async fn test_write_pre_confirmed_block_network_error() {
     let config = PreconfirmedCendeConfig {
         recorder_url: "http://invalid-url-that-should-not-exist.pmrewpohg".parse().unwrap(),
+        ..Default::default()
     };
     let client = PreconfirmedCendeClient::new(config);
     let test_data = test_preconfirmed_block_data();
@@ -152,3 +167,118 @@ async fn test_write_pre_confirmed_block_network_error() {
     };
     assert!(e.is_connect());
 }
+
+fn test_preconfirmed_block_data_at(
+    block_number: BlockNumber,
+    round: Round,
+    write_iteration: u64,
+) -> CendeWritePreconfirmedBlock {
+    CendeWritePreconfirmedBlock {
+        block_number,
+        round,
+        write_iteration,
+        ..test_preconfirmed_block_data()
+    }
+}
+
+#[tokio::test]
+async fn test_local_storage_writes_latest_pre_confirmed_block() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = LocalStoragePreconfirmedClient::new(dir.path().join("pre_confirmed"));
+
+    client.write_pre_confirmed_block(test_preconfirmed_block_data()).await.unwrap();
+    client
+        .write_pre_confirmed_block(test_preconfirmed_block_data_at(
+            TEST_BLOCK_NUMBER,
+            TEST_ROUND,
+            TEST_WRITE_ITERATION + 1,
+        ))
+        .await
+        .unwrap();
+
+    let written: serde_json::Value =
+        serde_json::from_slice(&std::fs::read(client.file_path()).unwrap()).unwrap();
+    assert_eq!(written["block_number"], TEST_BLOCK_NUMBER.0);
+    assert_eq!(written["round"], TEST_ROUND);
+    assert_eq!(written["write_iteration"], TEST_WRITE_ITERATION + 1);
+}
+
+#[tokio::test]
+async fn test_local_storage_rejects_lower_round() {
+    let dir = tempfile::tempdir().unwrap();
+    let client = LocalStoragePreconfirmedClient::new(dir.path().to_path_buf());
+
+    client.write_pre_confirmed_block(test_preconfirmed_block_data()).await.unwrap();
+    let result = client
+        .write_pre_confirmed_block(test_preconfirmed_block_data_at(
+            TEST_BLOCK_NUMBER,
+            TEST_ROUND - 1,
+            0,
+        ))
+        .await;
+
+    assert_matches!(
+        result,
+        Err(PreconfirmedCendeClientError::RoundMismatch { round, latest_round, .. })
+            if round == TEST_ROUND - 1 && latest_round == TEST_ROUND
+    );
+}
+
+#[tokio::test]
+async fn test_feed_publishes_to_subscribers() {
+    let feed = PreconfirmedBlockFeed::default();
+    let mut subscriber = feed.subscribe();
+    assert!(feed.latest().is_none());
+    assert!(feed.latest_block().is_none());
+
+    feed.write_pre_confirmed_block(test_preconfirmed_block_data()).await.unwrap();
+
+    let published = subscriber.next().await.unwrap();
+    assert_eq!(published["block_number"], TEST_BLOCK_NUMBER.0);
+    assert_eq!(published["round"], TEST_ROUND);
+    assert_eq!(published["write_iteration"], TEST_WRITE_ITERATION);
+
+    // A new round of the next block is accepted, an older block is rejected.
+    let next_block = TEST_BLOCK_NUMBER.unchecked_next();
+    feed.write_pre_confirmed_block(test_preconfirmed_block_data_at(next_block, 0, 0))
+        .await
+        .unwrap();
+    let result = feed.write_pre_confirmed_block(test_preconfirmed_block_data()).await;
+    assert_matches!(result, Err(PreconfirmedCendeClientError::RoundMismatch { .. }));
+    assert_eq!(feed.latest().unwrap().block_number, next_block);
+    assert_eq!(feed.latest_block().unwrap()["block_number"], next_block.0);
+
+    // A late subscriber starts with the latest block.
+    let published = feed.subscribe().next().await.unwrap();
+    assert_eq!(published["block_number"], next_block.0);
+}
+
+#[tokio::test]
+async fn test_feed_transaction_status() {
+    let feed = PreconfirmedBlockFeed::default();
+    let executed_tx = CendePreconfirmedTransaction::L1Handler(L1HandlerTransaction {
+        transaction_hash: TransactionHash(felt!("0x1")),
+        ..Default::default()
+    });
+    let candidate_tx = CendePreconfirmedTransaction::L1Handler(L1HandlerTransaction {
+        transaction_hash: TransactionHash(felt!("0x2")),
+        ..Default::default()
+    });
+    let mut pre_confirmed_block = test_preconfirmed_block_data();
+    pre_confirmed_block.pre_confirmed_block.transactions = vec![executed_tx, candidate_tx];
+    pre_confirmed_block.pre_confirmed_block.transaction_receipts =
+        vec![Some(StarknetClientTransactionReceipt::default()), None];
+    pre_confirmed_block.pre_confirmed_block.transaction_state_diffs = vec![None, None];
+
+    feed.write_pre_confirmed_block(pre_confirmed_block).await.unwrap();
+
+    assert_eq!(
+        feed.transaction_status(TransactionHash(felt!("0x1"))),
+        Some(PreconfirmedTransactionStatus::PreConfirmed)
+    );
+    assert_eq!(
+        feed.transaction_status(TransactionHash(felt!("0x2"))),
+        Some(PreconfirmedTransactionStatus::Candidate)
+    );
+    assert_eq!(feed.transaction_status(TransactionHash(felt!("0x3"))), None);
+}
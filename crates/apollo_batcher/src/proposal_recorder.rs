@@ -0,0 +1,382 @@
+use std::collections::BTreeMap;
+use std::fs::File;
+use std::io::{BufReader, BufWriter};
+use std::path::{Path, PathBuf};
+use std::sync::{Arc, Mutex};
+
+use apollo_batcher_types::batcher_types::{
+    ProposalCommitment,
+    ProposalId,
+    ProposeBlockInput,
+    ValidateBlockInput,
+};
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use async_trait::async_trait;
+use blockifier::bouncer::BouncerWeights;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::execution_resources::GasAmount;
+use starknet_api::transaction::TransactionHash;
+use thiserror::Error;
+use tokio::time::Instant;
+use tracing::{error, info};
+
+use crate::block_builder::{
+    BlockBuilderConfig,
+    BlockBuilderResult,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    BlockMetadata,
+};
+use crate::transaction_provider::{NextTxs, TransactionProvider, TransactionProviderResult};
+
+/// The version of the recording format. Bump it on any incompatible change to
+/// [ProposalRecording].
+pub const PROPOSAL_RECORDING_FORMAT_VERSION: u32 = 1;
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ProposalRecorderConfig {
+    pub enabled: bool,
+    pub recordings_dir: PathBuf,
+    pub max_recordings: usize,
+}
+
+impl Default for ProposalRecorderConfig {
+    fn default() -> Self {
+        Self {
+            enabled: false,
+            recordings_dir: "/data/batcher/proposal_recordings".into(),
+            max_recordings: 1000,
+        }
+    }
+}
+
+impl SerializeConfig for ProposalRecorderConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, every proposed and validated block is recorded for offline replay.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "recordings_dir",
+                &self.recordings_dir,
+                "The directory the proposal recordings are written to.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_recordings",
+                &self.max_recordings,
+                "The maximal number of recordings kept in the recordings directory. The \
+                 recordings of the oldest blocks are removed first.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+#[derive(Debug, Error)]
+pub enum ProposalRecordingError {
+    #[error(transparent)]
+    Io(#[from] std::io::Error),
+    #[error(transparent)]
+    Serde(#[from] serde_json::Error),
+}
+
+pub type ProposalRecordingResult<T> = Result<T, ProposalRecordingError>;
+
+/// The request that started the recorded proposal.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub enum RecordedProposalInput {
+    Propose(ProposeBlockInput),
+    Validate(ValidateBlockInput),
+}
+
+impl RecordedProposalInput {
+    pub fn proposal_id(&self) -> ProposalId {
+        match self {
+            Self::Propose(input) => input.proposal_id,
+            Self::Validate(input) => input.proposal_id,
+        }
+    }
+
+    pub fn block_metadata(&self) -> BlockMetadata {
+        let (block_info, retrospective_block_hash) = match self {
+            Self::Propose(input) => (&input.block_info, input.retrospective_block_hash),
+            Self::Validate(input) => (&input.block_info, input.retrospective_block_hash),
+        };
+        BlockMetadata { block_info: block_info.clone(), retrospective_block_hash }
+    }
+}
+
+/// A summary of the [BlockExecutionArtifacts] of a proposal.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ArtifactsSummary {
+    pub commitment: ProposalCommitment,
+    pub final_n_executed_txs: usize,
+    pub n_execution_infos: usize,
+    pub rejected_tx_hashes: Vec<TransactionHash>,
+    pub consumed_l1_handler_tx_hashes: Vec<TransactionHash>,
+    pub l2_gas_used: GasAmount,
+    pub bouncer_weights: BouncerWeights,
+}
+
+impl From<&BlockExecutionArtifacts> for ArtifactsSummary {
+    fn from(artifacts: &BlockExecutionArtifacts) -> Self {
+        Self {
+            commitment: artifacts.commitment(),
+            final_n_executed_txs: artifacts.final_n_executed_txs,
+            n_execution_infos: artifacts.execution_data.execution_infos.len(),
+            rejected_tx_hashes: artifacts
+                .execution_data
+                .rejected_tx_hashes
+                .iter()
+                .copied()
+                .collect(),
+            consumed_l1_handler_tx_hashes: artifacts
+                .execution_data
+                .consumed_l1_handler_tx_hashes
+                .iter()
+                .copied()
+                .collect(),
+            l2_gas_used: artifacts.l2_gas_used,
+            bouncer_weights: artifacts.bouncer_weights,
+        }
+    }
+}
+
+/// A chunk of transactions returned by the transaction provider.
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct RecordedChunk {
+    /// The time (in milliseconds) since the start of the proposal at which the chunk was received.
+    pub received_after_millis: u64,
+    pub n_txs: usize,
+}
+
+#[derive(Clone, Debug, Deserialize, PartialEq, Serialize)]
+pub struct ProposalTimings {
+    pub started_at: chrono::DateTime<Utc>,
+    pub build_duration_millis: u64,
+    pub chunks: Vec<RecordedChunk>,
+}
+
+/// Everything needed to replay a proposal on top of the state it was built on.
+#[derive(Clone, Debug, Deserialize, Serialize)]
+pub struct ProposalRecording {
+    pub format_version: u32,
+    pub input: RecordedProposalInput,
+    pub block_metadata: BlockMetadata,
+    pub block_builder_config: BlockBuilderConfig,
+    /// The transactions returned by the transaction provider, in order. Only the first
+    /// `final_n_executed_txs` of them are included in the block.
+    pub transactions: Vec<InternalConsensusTransaction>,
+    /// The artifacts summary, or the error the proposal failed with.
+    pub result: Result<ArtifactsSummary, String>,
+    pub timings: ProposalTimings,
+}
+
+impl ProposalRecording {
+    pub fn file_name(block_number: BlockNumber, proposal_id: ProposalId) -> String {
+        format!("block_{block_number}_proposal_{proposal_id}.json")
+    }
+
+    /// The inverse of [Self::file_name]. Returns `None` for any other file.
+    fn parse_file_name(file_name: &str) -> Option<(BlockNumber, ProposalId)> {
+        let (block_number, proposal_id) =
+            file_name.strip_prefix("block_")?.strip_suffix(".json")?.split_once("_proposal_")?;
+        Some((BlockNumber(block_number.parse().ok()?), ProposalId(proposal_id.parse().ok()?)))
+    }
+
+    pub fn read_from_file(path: &Path) -> ProposalRecordingResult<Self> {
+        Ok(serde_json::from_reader(BufReader::new(File::open(path)?))?)
+    }
+
+    /// Writes the recording to a temporary file that then replaces the file at `path`, so readers
+    /// always see a complete recording.
+    pub fn write_to_file(&self, path: &Path) -> ProposalRecordingResult<()> {
+        if let Some(parent) = path.parent() {
+            std::fs::create_dir_all(parent)?;
+        }
+        let tmp_path = path.with_extension("json.tmp");
+        let mut writer = BufWriter::new(File::create(&tmp_path)?);
+        serde_json::to_writer(&mut writer, self)?;
+        writer.into_inner().map_err(|err| err.into_error())?.sync_all()?;
+        std::fs::rename(tmp_path, path)?;
+        Ok(())
+    }
+}
+
+/// Removes the recordings of the oldest blocks in `dir`, so that at most `max_recordings` remain.
+pub(crate) fn prune_recordings(dir: &Path, max_recordings: usize) -> ProposalRecordingResult<()> {
+    let mut recordings = Vec::new();
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        let key = path
+            .file_name()
+            .and_then(|file_name| file_name.to_str())
+            .and_then(ProposalRecording::parse_file_name);
+        if let Some(key) = key {
+            recordings.push((key, path));
+        }
+    }
+    let Some(n_to_remove) = recordings.len().checked_sub(max_recordings) else {
+        return Ok(());
+    };
+    recordings.sort_unstable_by_key(|(key, _)| *key);
+    for (_, path) in recordings.into_iter().take(n_to_remove) {
+        // Recordings finishing together may prune the same files.
+        match std::fs::remove_file(path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => return Err(err.into()),
+            _ => {}
+        }
+    }
+    Ok(())
+}
+
+/// Records the proposals of the batcher to the configured directory.
+pub(crate) struct ProposalRecorder {
+    config: ProposalRecorderConfig,
+    block_builder_config: BlockBuilderConfig,
+}
+
+impl ProposalRecorder {
+    pub fn new(config: ProposalRecorderConfig, block_builder_config: BlockBuilderConfig) -> Self {
+        Self { config, block_builder_config }
+    }
+
+    pub fn start(&self, input: RecordedProposalInput) -> ActiveRecording {
+        let block_metadata = input.block_metadata();
+        let path = self.config.recordings_dir.join(ProposalRecording::file_name(
+            block_metadata.block_info.block_number,
+            input.proposal_id(),
+        ));
+        ActiveRecording {
+            path,
+            max_recordings: self.config.max_recordings,
+            input,
+            block_metadata,
+            block_builder_config: self.block_builder_config.clone(),
+            start: Instant::now(),
+            started_at: Utc::now(),
+            recorded_txs: Arc::default(),
+        }
+    }
+}
+
+#[derive(Default)]
+struct RecordedTxs {
+    transactions: Vec<InternalConsensusTransaction>,
+    chunks: Vec<RecordedChunk>,
+}
+
+/// The recording of a single proposal. The transaction provider and the block builder of the
+/// proposal are wrapped, and the recording is written once the block builder is done.
+pub(crate) struct ActiveRecording {
+    path: PathBuf,
+    max_recordings: usize,
+    input: RecordedProposalInput,
+    block_metadata: BlockMetadata,
+    block_builder_config: BlockBuilderConfig,
+    start: Instant,
+    started_at: chrono::DateTime<Utc>,
+    recorded_txs: Arc<Mutex<RecordedTxs>>,
+}
+
+impl ActiveRecording {
+    pub fn record_tx_provider(
+        &self,
+        tx_provider: Box<dyn TransactionProvider>,
+    ) -> Box<dyn TransactionProvider> {
+        Box::new(RecordingTransactionProvider {
+            tx_provider,
+            start: self.start,
+            recorded_txs: self.recorded_txs.clone(),
+        })
+    }
+
+    pub fn record_block_builder(
+        self,
+        block_builder: Box<dyn BlockBuilderTrait>,
+    ) -> Box<dyn BlockBuilderTrait> {
+        Box::new(RecordingBlockBuilder { block_builder, recording: Some(self) })
+    }
+
+    fn finish(self, result: &BlockBuilderResult<BlockExecutionArtifacts>) {
+        let build_duration_millis =
+            u64::try_from(self.start.elapsed().as_millis()).unwrap_or(u64::MAX);
+        let RecordedTxs { transactions, chunks } = std::mem::take(
+            &mut *self.recorded_txs.lock().expect("Recorded transactions lock is poisoned."),
+        );
+        let recording = ProposalRecording {
+            format_version: PROPOSAL_RECORDING_FORMAT_VERSION,
+            input: self.input,
+            block_metadata: self.block_metadata,
+            block_builder_config: self.block_builder_config,
+            transactions,
+            result: result.as_ref().map(ArtifactsSummary::from).map_err(|err| err.to_string()),
+            timings: ProposalTimings { started_at: self.started_at, build_duration_millis, chunks },
+        };
+
+        // Writing the recording does not delay the proposal.
+        let path = self.path;
+        let max_recordings = self.max_recordings;
+        tokio::task::spawn_blocking(move || {
+            match recording.write_to_file(&path) {
+                Ok(()) => info!("Recorded proposal to {}.", path.display()),
+                Err(err) => error!("Failed to record proposal to {}: {err}", path.display()),
+            }
+            if let Some(dir) = path.parent() {
+                if let Err(err) = prune_recordings(dir, max_recordings) {
+                    error!("Failed to prune the proposal recordings in {}: {err}", dir.display());
+                }
+            }
+        });
+    }
+}
+
+struct RecordingTransactionProvider {
+    tx_provider: Box<dyn TransactionProvider>,
+    start: Instant,
+    recorded_txs: Arc<Mutex<RecordedTxs>>,
+}
+
+#[async_trait]
+impl TransactionProvider for RecordingTransactionProvider {
+    async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs> {
+        let txs = self.tx_provider.get_txs(n_txs).await?;
+        if !txs.is_empty() {
+            let received_after_millis =
+                u64::try_from(self.start.elapsed().as_millis()).unwrap_or(u64::MAX);
+            let mut recorded_txs =
+                self.recorded_txs.lock().expect("Recorded transactions lock is poisoned.");
+            recorded_txs.chunks.push(RecordedChunk { received_after_millis, n_txs: txs.len() });
+            recorded_txs.transactions.extend(txs.iter().cloned());
+        }
+        Ok(txs)
+    }
+
+    async fn get_final_n_executed_txs(&mut self) -> Option<usize> {
+        self.tx_provider.get_final_n_executed_txs().await
+    }
+}
+
+struct RecordingBlockBuilder {
+    block_builder: Box<dyn BlockBuilderTrait>,
+    recording: Option<ActiveRecording>,
+}
+
+#[async_trait]
+impl BlockBuilderTrait for RecordingBlockBuilder {
+    async fn build_block(&mut self) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let result = self.block_builder.build_block().await;
+        if let Some(recording) = self.recording.take() {
+            recording.finish(&result);
+        }
+        result
+    }
+}
@@ -0,0 +1,146 @@
+use std::path::Path;
+use std::time::Duration;
+
+use apollo_batcher_types::batcher_types::{ProposalId, ProposeBlockInput};
+use starknet_api::block::{BlockInfo, BlockNumber};
+use tempfile::TempDir;
+
+use crate::block_builder::{BlockBuilderConfig, BlockBuilderError, BlockExecutionArtifacts};
+use crate::proposal_recorder::{
+    prune_recordings,
+    ArtifactsSummary,
+    ProposalRecorder,
+    ProposalRecorderConfig,
+    ProposalRecording,
+    RecordedProposalInput,
+    PROPOSAL_RECORDING_FORMAT_VERSION,
+};
+use crate::proposal_replay::ReplayTransactionProvider;
+use crate::test_utils::{test_txs, FakeValidateBlockBuilder};
+
+const BLOCK_NUMBER: BlockNumber = BlockNumber(5);
+const PROPOSAL_ID: ProposalId = ProposalId(1);
+
+fn propose_block_input() -> ProposeBlockInput {
+    ProposeBlockInput {
+        proposal_id: PROPOSAL_ID,
+        deadline: chrono::Utc::now(),
+        retrospective_block_hash: None,
+        block_info: BlockInfo { block_number: BLOCK_NUMBER, ..Default::default() },
+        proposal_round: 0,
+    }
+}
+
+fn proposal_recorder(recordings_dir: &TempDir) -> ProposalRecorder {
+    ProposalRecorder::new(
+        ProposalRecorderConfig {
+            enabled: true,
+            recordings_dir: recordings_dir.path().to_path_buf(),
+            max_recordings: 10,
+        },
+        BlockBuilderConfig::default(),
+    )
+}
+
+// The recording is written in the background, so wait until it is complete.
+async fn wait_for_recording(path: &Path) -> ProposalRecording {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        loop {
+            if let Ok(recording) = ProposalRecording::read_from_file(path) {
+                return recording;
+            }
+            tokio::time::sleep(Duration::from_millis(10)).await;
+        }
+    })
+    .await
+    .expect("The proposal should be recorded.")
+}
+
+#[tokio::test]
+async fn records_the_built_proposal() {
+    let recordings_dir = TempDir::new().unwrap();
+    let input = propose_block_input();
+    let recording =
+        proposal_recorder(&recordings_dir).start(RecordedProposalInput::Propose(input.clone()));
+
+    let txs = test_txs(0..3);
+    let tx_provider =
+        recording.record_tx_provider(Box::new(ReplayTransactionProvider::new(txs.clone())));
+    let artifacts = BlockExecutionArtifacts::create_for_testing();
+    let mut block_builder = recording.record_block_builder(Box::new(FakeValidateBlockBuilder {
+        tx_provider,
+        build_block_result: Some(Ok(artifacts.clone())),
+    }));
+    assert_eq!(block_builder.build_block().await.unwrap(), artifacts);
+
+    let recorded = wait_for_recording(
+        &recordings_dir.path().join(ProposalRecording::file_name(BLOCK_NUMBER, PROPOSAL_ID)),
+    )
+    .await;
+    assert_eq!(recorded.format_version, PROPOSAL_RECORDING_FORMAT_VERSION);
+    assert_eq!(recorded.input.proposal_id(), PROPOSAL_ID);
+    assert_eq!(recorded.block_metadata.block_info, input.block_info);
+    assert_eq!(recorded.transactions, txs);
+    // The fake block builder requests a single transaction at a time.
+    assert_eq!(
+        recorded.timings.chunks.iter().map(|chunk| chunk.n_txs).collect::<Vec<_>>(),
+        vec![1, 1, 1]
+    );
+    assert_eq!(recorded.result.unwrap(), ArtifactsSummary::from(&artifacts));
+}
+
+#[tokio::test]
+async fn records_the_failed_proposal() {
+    let recordings_dir = TempDir::new().unwrap();
+    let recording = proposal_recorder(&recordings_dir)
+        .start(RecordedProposalInput::Propose(propose_block_input()));
+
+    let tx_provider =
+        recording.record_tx_provider(Box::new(ReplayTransactionProvider::new(test_txs(0..3))));
+    let mut block_builder = recording.record_block_builder(Box::new(FakeValidateBlockBuilder {
+        tx_provider,
+        build_block_result: Some(Err(BlockBuilderError::Aborted)),
+    }));
+    block_builder.build_block().await.unwrap_err();
+
+    let recorded = wait_for_recording(
+        &recordings_dir.path().join(ProposalRecording::file_name(BLOCK_NUMBER, PROPOSAL_ID)),
+    )
+    .await;
+    assert!(recorded.transactions.is_empty());
+    assert_eq!(recorded.result.unwrap_err(), BlockBuilderError::Aborted.to_string());
+}
+
+#[test]
+fn pruning_keeps_the_latest_recordings() {
+    let recordings_dir = TempDir::new().unwrap();
+    let write_file = |file_name: &str| {
+        std::fs::write(recordings_dir.path().join(file_name), "{}").unwrap();
+    };
+    for (block_number, proposal_id) in [(9, 1), (10, 0), (10, 1), (2, 7)] {
+        write_file(&ProposalRecording::file_name(
+            BlockNumber(block_number),
+            ProposalId(proposal_id),
+        ));
+    }
+    // Files that are not complete recordings are left alone.
+    write_file("block_1_proposal_0.json.tmp");
+    write_file("notes.txt");
+
+    prune_recordings(recordings_dir.path(), 2).unwrap();
+
+    let mut file_names: Vec<_> = std::fs::read_dir(recordings_dir.path())
+        .unwrap()
+        .map(|entry| entry.unwrap().file_name().into_string().unwrap())
+        .collect();
+    file_names.sort();
+    assert_eq!(
+        file_names,
+        vec![
+            "block_10_proposal_0.json",
+            "block_10_proposal_1.json",
+            "block_1_proposal_0.json.tmp",
+            "notes.txt",
+        ]
+    );
+}
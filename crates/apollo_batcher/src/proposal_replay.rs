@@ -0,0 +1,159 @@
+use std::time::Duration;
+
+use async_trait::async_trait;
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use thiserror::Error;
+use tokio::time::Instant;
+
+use crate::block_builder::{
+    BlockBuilderError,
+    BlockBuilderExecutionParams,
+    BlockBuilderFactoryTrait,
+};
+use crate::proposal_recorder::{
+    ArtifactsSummary,
+    ProposalRecording,
+    PROPOSAL_RECORDING_FORMAT_VERSION,
+};
+use crate::transaction_provider::{NextTxs, TransactionProvider, TransactionProviderResult};
+
+#[derive(Debug, Error)]
+pub enum ProposalReplayError {
+    #[error(
+        "Unsupported recording format version {format_version} (expected \
+         {PROPOSAL_RECORDING_FORMAT_VERSION})."
+    )]
+    UnsupportedFormatVersion { format_version: u32 },
+    #[error(transparent)]
+    BlockBuilder(#[from] BlockBuilderError),
+}
+
+pub type ProposalReplayResult<T> = Result<T, ProposalReplayError>;
+
+#[derive(Debug)]
+pub struct ProposalReplayReport {
+    pub recorded: Result<ArtifactsSummary, String>,
+    pub replayed: ArtifactsSummary,
+    pub build_duration: Duration,
+}
+
+impl ProposalReplayReport {
+    pub fn commitment_matches(&self) -> bool {
+        self.recorded.as_ref().is_ok_and(|recorded| recorded.commitment == self.replayed.commitment)
+    }
+
+    /// Returns a description of every field that differs between the recorded and the replayed
+    /// artifacts.
+    pub fn diff(&self) -> Vec<String> {
+        let recorded = match &self.recorded {
+            Ok(recorded) => recorded,
+            Err(err) => return vec![format!("The recorded proposal failed: {err}")],
+        };
+        let replayed = &self.replayed;
+
+        let mut diff = Vec::new();
+        let mut compare = |field: &str, recorded: String, replayed: String| {
+            if recorded != replayed {
+                diff.push(format!("{field}: recorded {recorded}, replayed {replayed}"));
+            }
+        };
+        compare(
+            "commitment",
+            format!("{:?}", recorded.commitment),
+            format!("{:?}", replayed.commitment),
+        );
+        compare(
+            "final_n_executed_txs",
+            recorded.final_n_executed_txs.to_string(),
+            replayed.final_n_executed_txs.to_string(),
+        );
+        compare(
+            "n_execution_infos",
+            recorded.n_execution_infos.to_string(),
+            replayed.n_execution_infos.to_string(),
+        );
+        compare(
+            "rejected_tx_hashes",
+            format!("{:?}", recorded.rejected_tx_hashes),
+            format!("{:?}", replayed.rejected_tx_hashes),
+        );
+        compare(
+            "consumed_l1_handler_tx_hashes",
+            format!("{:?}", recorded.consumed_l1_handler_tx_hashes),
+            format!("{:?}", replayed.consumed_l1_handler_tx_hashes),
+        );
+        compare("l2_gas_used", recorded.l2_gas_used.to_string(), replayed.l2_gas_used.to_string());
+        compare(
+            "bouncer_weights",
+            format!("{:?}", recorded.bouncer_weights),
+            format!("{:?}", replayed.bouncer_weights),
+        );
+        diff
+    }
+}
+
+/// Re-executes a recorded proposal with a block builder of the given factory.
+///
+/// The proposal is replayed in validate mode: the transactions that were included in the recorded
+/// block are fed in their recorded order, followed by the final number of transactions. If the
+/// recorded proposal failed, all the recorded transactions are fed.
+pub async fn replay_proposal(
+    recording: ProposalRecording,
+    block_builder_factory: &dyn BlockBuilderFactoryTrait,
+    timeout: Duration,
+) -> ProposalReplayResult<ProposalReplayReport> {
+    if recording.format_version != PROPOSAL_RECORDING_FORMAT_VERSION {
+        return Err(ProposalReplayError::UnsupportedFormatVersion {
+            format_version: recording.format_version,
+        });
+    }
+
+    let mut transactions = recording.transactions;
+    if let Ok(recorded) = &recording.result {
+        transactions.truncate(recorded.final_n_executed_txs);
+    }
+    let tx_provider = ReplayTransactionProvider::new(transactions);
+
+    let start = Instant::now();
+    let (mut block_builder, _abort_signal_sender) = block_builder_factory.create_block_builder(
+        recording.block_metadata,
+        None,
+        BlockBuilderExecutionParams { deadline: start + timeout, is_validator: true },
+        Box::new(tx_provider),
+        None,
+        None,
+        None,
+        tokio::runtime::Handle::current(),
+    )?;
+    let artifacts = block_builder.build_block().await?;
+
+    Ok(ProposalReplayReport {
+        recorded: recording.result,
+        replayed: ArtifactsSummary::from(&artifacts),
+        build_duration: start.elapsed(),
+    })
+}
+
+/// Returns the recorded transactions in order, and the final number of transactions once all of
+/// them were returned.
+pub(crate) struct ReplayTransactionProvider {
+    txs: std::vec::IntoIter<InternalConsensusTransaction>,
+    n_txs: usize,
+}
+
+impl ReplayTransactionProvider {
+    pub fn new(txs: Vec<InternalConsensusTransaction>) -> Self {
+        Self { n_txs: txs.len(), txs: txs.into_iter() }
+    }
+}
+
+#[async_trait]
+impl TransactionProvider for ReplayTransactionProvider {
+    async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs> {
+        Ok(self.txs.by_ref().take(n_txs).collect())
+    }
+
+    async fn get_final_n_executed_txs(&mut self) -> Option<usize> {
+        self.txs.as_slice().is_empty().then_some(self.n_txs)
+    }
+}
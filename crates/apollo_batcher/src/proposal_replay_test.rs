@@ -0,0 +1,164 @@
+use std::time::Duration;
+
+use apollo_batcher_types::batcher_types::{ProposalId, ProposeBlockInput};
+use assert_matches::assert_matches;
+use async_trait::async_trait;
+use blockifier::state::cached_state::CommitmentStateDiff;
+use chrono::Utc;
+use rstest::rstest;
+use starknet_api::block::{BlockInfo, BlockNumber};
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+
+use crate::block_builder::{
+    BlockBuilderConfig,
+    BlockBuilderResult,
+    BlockBuilderTrait,
+    BlockExecutionArtifacts,
+    MockBlockBuilderFactoryTrait,
+};
+use crate::proposal_recorder::{
+    ArtifactsSummary,
+    ProposalRecording,
+    ProposalTimings,
+    RecordedProposalInput,
+    PROPOSAL_RECORDING_FORMAT_VERSION,
+};
+use crate::proposal_replay::{replay_proposal, ProposalReplayError};
+use crate::test_utils::{test_txs, DUMMY_FINAL_N_EXECUTED_TXS};
+use crate::transaction_provider::TransactionProvider;
+
+const REPLAY_TIMEOUT: Duration = Duration::from_secs(5);
+
+fn recording(
+    transactions: Vec<InternalConsensusTransaction>,
+    result: Result<ArtifactsSummary, String>,
+) -> ProposalRecording {
+    let input = RecordedProposalInput::Propose(ProposeBlockInput {
+        proposal_id: ProposalId(1),
+        deadline: Utc::now(),
+        retrospective_block_hash: None,
+        block_info: BlockInfo { block_number: BlockNumber(5), ..Default::default() },
+        proposal_round: 0,
+    });
+    ProposalRecording {
+        format_version: PROPOSAL_RECORDING_FORMAT_VERSION,
+        block_metadata: input.block_metadata(),
+        input,
+        block_builder_config: BlockBuilderConfig::default(),
+        transactions,
+        result,
+        timings: ProposalTimings {
+            started_at: Utc::now(),
+            build_duration_millis: 0,
+            chunks: vec![],
+        },
+    }
+}
+
+/// A block builder that verifies it is fed the expected transactions.
+struct ExpectingBlockBuilder {
+    tx_provider: Box<dyn TransactionProvider>,
+    expected_txs: Vec<InternalConsensusTransaction>,
+    artifacts: BlockExecutionArtifacts,
+}
+
+#[async_trait]
+impl BlockBuilderTrait for ExpectingBlockBuilder {
+    async fn build_block(&mut self) -> BlockBuilderResult<BlockExecutionArtifacts> {
+        let mut txs = Vec::new();
+        loop {
+            if let Some(final_n_executed_txs) = self.tx_provider.get_final_n_executed_txs().await {
+                assert_eq!(final_n_executed_txs, self.expected_txs.len());
+                break;
+            }
+            txs.extend(self.tx_provider.get_txs(5).await.unwrap());
+        }
+        assert_eq!(txs, self.expected_txs);
+        Ok(self.artifacts.clone())
+    }
+}
+
+fn block_builder_factory(
+    expected_txs: Vec<InternalConsensusTransaction>,
+    artifacts: BlockExecutionArtifacts,
+) -> MockBlockBuilderFactoryTrait {
+    let mut block_builder_factory = MockBlockBuilderFactoryTrait::new();
+    block_builder_factory
+        .expect_create_block_builder()
+        .times(1)
+        .withf(|_, state_layer, execution_params, _, output_content_sender, _, _, _| {
+            state_layer.is_none()
+                && execution_params.is_validator
+                && output_content_sender.is_none()
+        })
+        .return_once(move |_, _, _, tx_provider, _, _, _, _| {
+            let block_builder = ExpectingBlockBuilder { tx_provider, expected_txs, artifacts };
+            Ok((Box::new(block_builder), tokio::sync::oneshot::channel().0))
+        });
+    block_builder_factory
+}
+
+#[rstest]
+#[case::succeeded_proposal(
+    Ok(ArtifactsSummary::from(&BlockExecutionArtifacts::create_for_testing())),
+    DUMMY_FINAL_N_EXECUTED_TXS
+)]
+#[case::failed_proposal(Err("Deadline has been reached".to_string()), 15)]
+#[tokio::test]
+async fn replay_feeds_the_included_transactions(
+    #[case] recorded_result: Result<ArtifactsSummary, String>,
+    #[case] n_expected_txs: usize,
+) {
+    let recorded_txs = test_txs(0..15);
+    let expected_txs = recorded_txs[..n_expected_txs].to_vec();
+    let block_builder_factory =
+        block_builder_factory(expected_txs, BlockExecutionArtifacts::create_for_testing());
+
+    let report = replay_proposal(
+        recording(recorded_txs, recorded_result.clone()),
+        &block_builder_factory,
+        REPLAY_TIMEOUT,
+    )
+    .await
+    .unwrap();
+
+    assert_eq!(report.commitment_matches(), recorded_result.is_ok());
+    assert_eq!(report.diff().is_empty(), recorded_result.is_ok());
+}
+
+#[tokio::test]
+async fn replay_reports_a_different_commitment() {
+    let recorded_artifacts = BlockExecutionArtifacts::create_for_testing();
+    let replayed_artifacts = BlockExecutionArtifacts {
+        commitment_state_diff: CommitmentStateDiff::default(),
+        ..recorded_artifacts.clone()
+    };
+    let recorded_txs = test_txs(0..DUMMY_FINAL_N_EXECUTED_TXS);
+    let block_builder_factory = block_builder_factory(recorded_txs.clone(), replayed_artifacts);
+
+    let report = replay_proposal(
+        recording(recorded_txs, Ok(ArtifactsSummary::from(&recorded_artifacts))),
+        &block_builder_factory,
+        REPLAY_TIMEOUT,
+    )
+    .await
+    .unwrap();
+
+    assert!(!report.commitment_matches());
+    let diff = report.diff();
+    assert_eq!(diff.len(), 1);
+    assert!(diff[0].starts_with("commitment:"));
+}
+
+#[tokio::test]
+async fn replay_rejects_an_unknown_format_version() {
+    let recording = ProposalRecording {
+        format_version: PROPOSAL_RECORDING_FORMAT_VERSION + 1,
+        ..recording(test_txs(0..1), Err("Aborted".to_string()))
+    };
+
+    let result =
+        replay_proposal(recording, &MockBlockBuilderFactoryTrait::new(), REPLAY_TIMEOUT).await;
+
+    assert_matches!(result, Err(ProposalReplayError::UnsupportedFormatVersion { .. }));
+}
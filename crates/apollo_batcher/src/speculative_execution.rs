@@ -0,0 +1,408 @@
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
+use std::hash::Hash;
+use std::sync::{Arc, RwLock};
+
+use apollo_batcher_types::batcher_types::ProposalId;
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use apollo_mempool_types::communication::SharedMempoolClient;
+use async_trait::async_trait;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::cached_state::{CommitmentStateDiff, StateMaps};
+use blockifier::state::global_cache::CompiledClasses;
+use blockifier::state::state_api::{StateReader, StateResult};
+use blockifier::state::state_reader_and_contract_manager::FetchCompiledClasses;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_api::transaction::TransactionHash;
+use starknet_types_core::felt::Felt;
+use strum_macros::{EnumVariantNames, IntoStaticStr};
+use tracing::{debug, error, info, warn, Instrument};
+
+use crate::block_builder::{AbortSignalSender, BlockBuilderError, BlockBuilderTrait};
+use crate::metrics::{LABEL_NAME_SPECULATION_RESULT, SPECULATIONS};
+use crate::pre_confirmed_block_writer::{PreconfirmedTxReceiver, PreconfirmedTxSender};
+use crate::transaction_provider::{NextTxs, TransactionProvider, TransactionProviderResult};
+
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Serialize)]
+pub struct SpeculativeExecutionConfig {
+    pub enabled: bool,
+    pub max_duration_millis: u64,
+}
+
+impl Default for SpeculativeExecutionConfig {
+    fn default() -> Self {
+        Self { enabled: false, max_duration_millis: 2000 }
+    }
+}
+
+impl SerializeConfig for SpeculativeExecutionConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, once a proposal is built the batcher executes the candidate \
+                 transactions of the next height on top of it until the decision is reached. If \
+                 the decision matches the proposal, the state read during the speculative \
+                 execution is reused by the next height.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_duration_millis",
+                &self.max_duration_millis,
+                "The maximal time (ms) to execute transactions speculatively.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// State values layered over the storage state at `storage_height`: the writes of blocks that
+/// are not in the storage yet, and the values read from the storage below them.
+#[derive(Clone, Debug)]
+pub struct StateLayer {
+    pub storage_height: BlockNumber,
+    writes: Arc<StateMaps>,
+    reads: Arc<RwLock<StateMaps>>,
+}
+
+impl StateLayer {
+    pub fn new(storage_height: BlockNumber, writes: StateMaps) -> Self {
+        Self { storage_height, writes: Arc::new(writes), reads: Arc::default() }
+    }
+
+    /// Returns this layer over a later storage height, once the writes of the layer were
+    /// committed to the storage.
+    pub fn on_storage_height(&self, storage_height: BlockNumber) -> Self {
+        Self { storage_height, ..self.clone() }
+    }
+
+    /// Returns the number of values read from the storage through this layer.
+    pub fn n_reads(&self) -> usize {
+        let reads = self.reads.read().expect("State layer reads lock is poisoned.");
+        reads.storage.len()
+            + reads.nonces.len()
+            + reads.class_hashes.len()
+            + reads.compiled_class_hashes.len()
+            + reads.declared_contracts.len()
+    }
+}
+
+/// Returns the state writes of the given state diff.
+pub fn state_maps_from(state_diff: &CommitmentStateDiff) -> StateMaps {
+    StateMaps {
+        nonces: state_diff.address_to_nonce.iter().map(|(k, v)| (*k, *v)).collect(),
+        class_hashes: state_diff.address_to_class_hash.iter().map(|(k, v)| (*k, *v)).collect(),
+        storage: state_diff
+            .storage_updates
+            .iter()
+            .flat_map(|(address, updates)| {
+                updates.iter().map(|(key, value)| ((*address, *key), *value))
+            })
+            .collect(),
+        compiled_class_hashes: state_diff
+            .class_hash_to_compiled_class_hash
+            .iter()
+            .map(|(k, v)| (*k, *v))
+            .collect(),
+        declared_contracts: state_diff
+            .class_hash_to_compiled_class_hash
+            .keys()
+            .map(|class_hash| (*class_hash, true))
+            .collect(),
+    }
+}
+
+/// Reads the state through an optional [StateLayer]. Values that are not in the layer are read
+/// from the underlying state reader, and are recorded in the layer.
+///
+/// Note: the compiled classes are always read from the underlying state reader, so classes
+/// declared in the layer's writes are only available once they are in the storage.
+pub struct LayeredStateReader<S: StateReader> {
+    pub state_reader: S,
+    pub state_layer: Option<StateLayer>,
+}
+
+impl<S: StateReader> LayeredStateReader<S> {
+    fn read_through<K: Copy + Eq + Hash, V: Copy>(
+        &self,
+        key: K,
+        values: fn(&StateMaps) -> &HashMap<K, V>,
+        values_mut: fn(&mut StateMaps) -> &mut HashMap<K, V>,
+        read: impl FnOnce() -> StateResult<V>,
+    ) -> StateResult<V> {
+        let Some(state_layer) = &self.state_layer else {
+            return read();
+        };
+        if let Some(value) = values(&state_layer.writes).get(&key) {
+            return Ok(*value);
+        }
+        if let Some(value) =
+            values(&state_layer.reads.read().expect("State layer reads lock is poisoned."))
+                .get(&key)
+        {
+            return Ok(*value);
+        }
+
+        let value = read()?;
+        values_mut(&mut state_layer.reads.write().expect("State layer reads lock is poisoned."))
+            .insert(key, value);
+        Ok(value)
+    }
+}
+
+impl<S: StateReader> StateReader for LayeredStateReader<S> {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        self.read_through(
+            (contract_address, key),
+            |maps| &maps.storage,
+            |maps| &mut maps.storage,
+            || self.state_reader.get_storage_at(contract_address, key),
+        )
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        self.read_through(
+            contract_address,
+            |maps| &maps.nonces,
+            |maps| &mut maps.nonces,
+            || self.state_reader.get_nonce_at(contract_address),
+        )
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        self.read_through(
+            contract_address,
+            |maps| &maps.class_hashes,
+            |maps| &mut maps.class_hashes,
+            || self.state_reader.get_class_hash_at(contract_address),
+        )
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        self.state_reader.get_compiled_class(class_hash)
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.read_through(
+            class_hash,
+            |maps| &maps.compiled_class_hashes,
+            |maps| &mut maps.compiled_class_hashes,
+            || self.state_reader.get_compiled_class_hash(class_hash),
+        )
+    }
+
+    fn get_compiled_class_hash_v2(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        self.state_reader.get_compiled_class_hash_v2(class_hash)
+    }
+}
+
+impl<S: FetchCompiledClasses> FetchCompiledClasses for LayeredStateReader<S> {
+    fn get_compiled_classes(&self, class_hash: ClassHash) -> StateResult<CompiledClasses> {
+        self.state_reader.get_compiled_classes(class_hash)
+    }
+
+    fn is_declared(&self, class_hash: ClassHash) -> StateResult<bool> {
+        self.read_through(
+            class_hash,
+            |maps| &maps.declared_contracts,
+            |maps| &mut maps.declared_contracts,
+            || self.state_reader.is_declared(class_hash),
+        )
+    }
+}
+
+// The capacity of the channel of the speculatively executed transactions. The channel is drained
+// while the execution runs.
+const EXECUTED_TXS_CHANNEL_CAPACITY: usize = 10000;
+
+/// Provides the candidate transactions of the next height from the mempool. The transactions are
+/// peeked, so the mempool still serves them to the proposals of the current height. L1 handler
+/// transactions are not provided, since the L1 provider only serves the current height.
+pub(crate) struct SpeculativeTransactionProvider {
+    mempool_client: SharedMempoolClient,
+    provided_tx_hashes: HashSet<TransactionHash>,
+}
+
+impl SpeculativeTransactionProvider {
+    pub fn new(mempool_client: SharedMempoolClient) -> Self {
+        Self { mempool_client, provided_tx_hashes: HashSet::new() }
+    }
+}
+
+#[async_trait]
+impl TransactionProvider for SpeculativeTransactionProvider {
+    async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs> {
+        // Peeking does not remove the transactions from the mempool, so the ones provided so far
+        // are peeked again.
+        let n_txs_to_peek = self.provided_tx_hashes.len() + n_txs;
+        Ok(self
+            .mempool_client
+            .peek_txs(n_txs_to_peek)
+            .await?
+            .into_iter()
+            .filter(|tx| self.provided_tx_hashes.insert(tx.tx_hash))
+            .take(n_txs)
+            .map(InternalConsensusTransaction::RpcTransaction)
+            .collect())
+    }
+
+    async fn get_final_n_executed_txs(&mut self) -> Option<usize> {
+        None
+    }
+}
+
+/// Provides the transactions executed speculatively for the height first, then the transactions
+/// of the given provider that were not provided yet.
+pub(crate) struct SpeculatedTransactionProvider {
+    speculated_txs: VecDeque<InternalConsensusTransaction>,
+    speculated_tx_hashes: HashSet<TransactionHash>,
+    tx_provider: Box<dyn TransactionProvider>,
+}
+
+impl SpeculatedTransactionProvider {
+    pub fn new(
+        speculated_txs: Vec<InternalConsensusTransaction>,
+        tx_provider: Box<dyn TransactionProvider>,
+    ) -> Self {
+        let speculated_tx_hashes = speculated_txs.iter().map(|tx| tx.tx_hash()).collect();
+        Self { speculated_txs: speculated_txs.into(), speculated_tx_hashes, tx_provider }
+    }
+}
+
+#[async_trait]
+impl TransactionProvider for SpeculatedTransactionProvider {
+    async fn get_txs(&mut self, n_txs: usize) -> TransactionProviderResult<NextTxs> {
+        let n_speculated_txs = n_txs.min(self.speculated_txs.len());
+        let mut txs: NextTxs = self.speculated_txs.drain(..n_speculated_txs).collect();
+        while txs.len() < n_txs {
+            let next_txs = self.tx_provider.get_txs(n_txs - txs.len()).await?;
+            if next_txs.is_empty() {
+                break;
+            }
+            txs.extend(
+                next_txs
+                    .into_iter()
+                    .filter(|tx| !self.speculated_tx_hashes.contains(&tx.tx_hash())),
+            );
+        }
+        Ok(txs)
+    }
+
+    async fn get_final_n_executed_txs(&mut self) -> Option<usize> {
+        self.tx_provider.get_final_n_executed_txs().await
+    }
+}
+
+/// How a speculative execution ended.
+#[derive(Clone, Copy, Debug, PartialEq, IntoStaticStr, EnumVariantNames)]
+#[strum(serialize_all = "snake_case")]
+pub enum SpeculationResult {
+    /// The decision matched the proposal the execution was built on.
+    Hit,
+    /// Another proposal was decided.
+    Miss,
+    /// The execution was stopped before a decision was reached.
+    Interrupted,
+}
+
+/// What a speculative execution that matched the decision leaves to the height it was built for.
+#[derive(Clone, Debug)]
+pub(crate) struct SpeculationHit {
+    pub state_layer: StateLayer,
+    /// The transactions executed speculatively, in order, to start the height's proposal with.
+    pub executed_txs: Vec<InternalConsensusTransaction>,
+}
+
+/// Returns a channel for the block builder of a speculative execution to send the transactions
+/// it executed through.
+pub(crate) fn executed_txs_channel() -> (PreconfirmedTxSender, PreconfirmedTxReceiver) {
+    tokio::sync::mpsc::channel(EXECUTED_TXS_CHANNEL_CAPACITY)
+}
+
+/// A speculative execution of the height following a proposal.
+pub(crate) struct Speculation {
+    proposal_id: ProposalId,
+    state_layer: StateLayer,
+    abort_signal_sender: AbortSignalSender,
+    join_handle: tokio::task::JoinHandle<()>,
+    executed_txs_handle: tokio::task::JoinHandle<Vec<InternalConsensusTransaction>>,
+}
+
+impl Speculation {
+    pub fn start(
+        proposal_id: ProposalId,
+        state_layer: StateLayer,
+        mut block_builder: Box<dyn BlockBuilderTrait>,
+        abort_signal_sender: AbortSignalSender,
+        mut executed_txs_receiver: PreconfirmedTxReceiver,
+    ) -> Self {
+        info!("Starting speculative execution on top of proposal {proposal_id}.");
+        // Collects the executed transactions until the block builder is dropped.
+        let executed_txs_handle = tokio::spawn(async move {
+            let mut executed_txs = Vec::new();
+            while let Some((tx, _receipt, _state_diff)) = executed_txs_receiver.recv().await {
+                executed_txs.push(tx);
+            }
+            executed_txs
+        });
+        let join_handle = tokio::spawn(
+            async move {
+                match block_builder.build_block().await {
+                    Ok(artifacts) => debug!(
+                        "Speculative execution on top of proposal {proposal_id} finished with {} \
+                         transactions.",
+                        artifacts.final_n_executed_txs
+                    ),
+                    Err(BlockBuilderError::Aborted) => {}
+                    Err(err) => {
+                        warn!(
+                            "Speculative execution on top of proposal {proposal_id} failed: {err}"
+                        )
+                    }
+                }
+            }
+            .in_current_span(),
+        );
+        Self { proposal_id, state_layer, abort_signal_sender, join_handle, executed_txs_handle }
+    }
+
+    pub fn proposal_id(&self) -> ProposalId {
+        self.proposal_id
+    }
+
+    /// Stops the execution and waits for it to release the executor. Returns the state layer and
+    /// the executed transactions of the execution on a hit.
+    pub async fn stop(self, result: SpeculationResult) -> Option<SpeculationHit> {
+        self.abort_signal_sender.send(()).ok();
+        if let Err(err) = self.join_handle.await {
+            error!("Speculative execution on top of proposal {} panicked: {err}", self.proposal_id);
+        }
+        let executed_txs = self.executed_txs_handle.await.unwrap_or_else(|err| {
+            error!(
+                "Collecting the speculatively executed transactions on top of proposal {} \
+                 panicked: {err}",
+                self.proposal_id
+            );
+            Vec::new()
+        });
+        info!(
+            "Stopped speculative execution on top of proposal {}: {result:?}. {} transactions \
+             were executed and {} state values were read.",
+            self.proposal_id,
+            executed_txs.len(),
+            self.state_layer.n_reads()
+        );
+        SPECULATIONS.increment(1, &[(LABEL_NAME_SPECULATION_RESULT, result.into())]);
+        (result == SpeculationResult::Hit)
+            .then_some(SpeculationHit { state_layer: self.state_layer, executed_txs })
+    }
+}
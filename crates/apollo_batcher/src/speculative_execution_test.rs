@@ -0,0 +1,202 @@
+use std::sync::Arc;
+
+use apollo_batcher_types::batcher_types::ProposalId;
+use apollo_mempool_types::communication::MockMempoolClient;
+use blockifier::state::cached_state::{CommitmentStateDiff, StateMaps};
+use blockifier::state::state_api::StateReader;
+use blockifier::state::state_reader_and_contract_manager::FetchCompiledClasses;
+use blockifier::test_utils::dict_state_reader::DictStateReader;
+use indexmap::indexmap;
+use mockall::predicate::eq;
+use mockall::Sequence;
+use rstest::rstest;
+use starknet_api::block::BlockNumber;
+use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::core::ContractAddress;
+use starknet_api::rpc_transaction::InternalRpcTransaction;
+use starknet_api::state::StorageKey;
+use starknet_api::{class_hash, compiled_class_hash, contract_address, felt, nonce, storage_key};
+
+use crate::block_builder::{BlockBuilderError, MockBlockBuilderTrait};
+use crate::speculative_execution::{
+    executed_txs_channel,
+    state_maps_from,
+    LayeredStateReader,
+    SpeculatedTransactionProvider,
+    Speculation,
+    SpeculationResult,
+    SpeculativeTransactionProvider,
+    StateLayer,
+};
+use crate::test_utils::test_txs;
+use crate::transaction_provider::{MockTransactionProvider, TransactionProvider};
+
+const STORAGE_HEIGHT: BlockNumber = BlockNumber(3);
+
+fn address() -> ContractAddress {
+    contract_address!("0x100")
+}
+
+fn key() -> StorageKey {
+    storage_key!("0x10")
+}
+
+fn base_state_reader() -> DictStateReader {
+    DictStateReader {
+        storage_view: [((address(), key()), felt!(1_u8))].into(),
+        address_to_nonce: [(address(), nonce!(1_u8))].into(),
+        ..Default::default()
+    }
+}
+
+#[test]
+fn layer_writes_take_precedence() {
+    let writes =
+        StateMaps { storage: [((address(), key()), felt!(2_u8))].into(), ..Default::default() };
+    let state_layer = StateLayer::new(STORAGE_HEIGHT, writes);
+    let reader =
+        LayeredStateReader { state_reader: base_state_reader(), state_layer: Some(state_layer) };
+
+    assert_eq!(reader.get_storage_at(address(), key()).unwrap(), felt!(2_u8));
+    assert_eq!(reader.get_nonce_at(address()).unwrap(), nonce!(1_u8));
+}
+
+#[test]
+fn base_reads_are_recorded_in_layer() {
+    let state_layer = StateLayer::new(STORAGE_HEIGHT, StateMaps::default());
+    let reader = LayeredStateReader {
+        state_reader: base_state_reader(),
+        state_layer: Some(state_layer.clone()),
+    };
+    assert_eq!(reader.get_nonce_at(address()).unwrap(), nonce!(1_u8));
+    assert_eq!(reader.get_storage_at(address(), key()).unwrap(), felt!(1_u8));
+    assert!(!reader.is_declared(class_hash!("0x1")).unwrap());
+    assert_eq!(state_layer.n_reads(), 3);
+
+    // The recorded values are served by a reader over the same layer, even if the underlying
+    // state reader is missing them.
+    let next_height_layer = state_layer.on_storage_height(STORAGE_HEIGHT.unchecked_next());
+    let reader = LayeredStateReader {
+        state_reader: DictStateReader::default(),
+        state_layer: Some(next_height_layer.clone()),
+    };
+    assert_eq!(reader.get_nonce_at(address()).unwrap(), nonce!(1_u8));
+    assert_eq!(reader.get_storage_at(address(), key()).unwrap(), felt!(1_u8));
+    assert_eq!(next_height_layer.n_reads(), 3);
+}
+
+#[test]
+fn no_layer_reads_from_state_reader() {
+    let reader = LayeredStateReader { state_reader: base_state_reader(), state_layer: None };
+    assert_eq!(reader.get_storage_at(address(), key()).unwrap(), felt!(1_u8));
+    assert_eq!(reader.get_nonce_at(address()).unwrap(), nonce!(1_u8));
+}
+
+#[test]
+fn state_maps_from_state_diff() {
+    let state_diff = CommitmentStateDiff {
+        address_to_class_hash: indexmap! { address() => class_hash!("0x2") },
+        address_to_nonce: indexmap! { address() => nonce!(3_u8) },
+        storage_updates: indexmap! { address() => indexmap! { key() => felt!(4_u8) } },
+        class_hash_to_compiled_class_hash: indexmap! {
+            class_hash!("0x2") => compiled_class_hash!(5_u8)
+        },
+    };
+
+    let state_maps = state_maps_from(&state_diff);
+
+    assert_eq!(
+        state_maps,
+        StateMaps {
+            nonces: [(address(), nonce!(3_u8))].into(),
+            class_hashes: [(address(), class_hash!("0x2"))].into(),
+            storage: [((address(), key()), felt!(4_u8))].into(),
+            compiled_class_hashes: [(class_hash!("0x2"), compiled_class_hash!(5_u8))].into(),
+            declared_contracts: [(class_hash!("0x2"), true)].into(),
+        }
+    );
+}
+
+fn rpc_txs(txs: Vec<InternalConsensusTransaction>) -> Vec<InternalRpcTransaction> {
+    txs.into_iter()
+        .map(|tx| match tx {
+            InternalConsensusTransaction::RpcTransaction(tx) => tx,
+            InternalConsensusTransaction::L1Handler(_) => panic!("Expected an RPC transaction."),
+        })
+        .collect()
+}
+
+#[tokio::test]
+async fn speculative_provider_peeks_txs_not_provided_yet() {
+    let mut mempool_client = MockMempoolClient::new();
+    mempool_client.expect_peek_txs().with(eq(2)).returning(|_| Ok(rpc_txs(test_txs(0..2))));
+    mempool_client.expect_peek_txs().with(eq(5)).returning(|_| Ok(rpc_txs(test_txs(0..4))));
+    let mut tx_provider = SpeculativeTransactionProvider::new(Arc::new(mempool_client));
+
+    assert_eq!(tx_provider.get_txs(2).await.unwrap(), test_txs(0..2));
+    assert_eq!(tx_provider.get_txs(3).await.unwrap(), test_txs(2..4));
+}
+
+#[tokio::test]
+async fn speculated_txs_are_provided_first() {
+    let mut inner_tx_provider = MockTransactionProvider::new();
+    let mut seq = Sequence::new();
+    // The speculated transactions are returned by the inner provider as well, and are skipped.
+    inner_tx_provider
+        .expect_get_txs()
+        .times(1)
+        .in_sequence(&mut seq)
+        .with(eq(2))
+        .returning(|_| Ok(test_txs(1..3)));
+    inner_tx_provider
+        .expect_get_txs()
+        .times(1)
+        .in_sequence(&mut seq)
+        .with(eq(1))
+        .returning(|_| Ok(test_txs(3..4)));
+    inner_tx_provider
+        .expect_get_txs()
+        .times(1)
+        .in_sequence(&mut seq)
+        .with(eq(3))
+        .returning(|_| Ok(vec![]));
+    let mut tx_provider =
+        SpeculatedTransactionProvider::new(test_txs(0..2), Box::new(inner_tx_provider));
+
+    assert_eq!(tx_provider.get_txs(1).await.unwrap(), test_txs(0..1));
+    assert_eq!(tx_provider.get_txs(3).await.unwrap(), test_txs(1..4));
+    assert_eq!(tx_provider.get_txs(3).await.unwrap(), vec![]);
+}
+
+#[rstest]
+#[case::hit(SpeculationResult::Hit)]
+#[case::miss(SpeculationResult::Miss)]
+#[tokio::test]
+async fn speculation_keeps_executed_txs_on_hit(#[case] result: SpeculationResult) {
+    let (executed_txs_sender, executed_txs_receiver) = executed_txs_channel();
+    let mut block_builder = MockBlockBuilderTrait::new();
+    block_builder.expect_build_block().return_once(move || {
+        for tx in test_txs(0..2) {
+            executed_txs_sender.try_send((tx, Default::default(), Default::default())).unwrap();
+        }
+        Err(BlockBuilderError::Aborted)
+    });
+    let speculation = Speculation::start(
+        ProposalId(0),
+        StateLayer::new(STORAGE_HEIGHT, StateMaps::default()),
+        Box::new(block_builder),
+        tokio::sync::oneshot::channel().0,
+        executed_txs_receiver,
+    );
+
+    let speculation_hit = speculation.stop(result).await;
+
+    match result {
+        SpeculationResult::Hit => {
+            let speculation_hit = speculation_hit.unwrap();
+            assert_eq!(speculation_hit.state_layer.storage_height, STORAGE_HEIGHT);
+            assert_eq!(speculation_hit.executed_txs, test_txs(0..2));
+        }
+        _ => assert!(speculation_hit.is_none()),
+    }
+}
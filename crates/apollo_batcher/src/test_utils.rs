@@ -1,5 +1,7 @@
 use std::ops::Range;
+use std::time::Duration;
 
+use apollo_batcher_types::batcher_types::BlockCloseReason;
 use async_trait::async_trait;
 use blockifier::bouncer::{BouncerWeights, CasmHashComputationData};
 use blockifier::fee::receipt::TransactionReceipt;
@@ -125,10 +127,12 @@ pub fn verify_indexed_execution_infos(
 
 impl BlockExecutionArtifacts {
     pub fn create_for_testing() -> Self {
+        let execution_infos = indexed_execution_infos();
+        let n_txs = execution_infos.len();
         // Use a non-empty commitment_state_diff to get a valuable test verification of the result.
         Self {
             execution_data: BlockTransactionExecutionData {
-                execution_infos: indexed_execution_infos(),
+                execution_infos,
                 rejected_tx_hashes: test_txs(10..15).iter().map(|tx| tx.tx_hash()).collect(),
                 consumed_l1_handler_tx_hashes: Default::default(),
             },
@@ -143,11 +147,21 @@ impl BlockExecutionArtifacts {
             },
             compressed_state_diff: Default::default(),
             bouncer_weights: BouncerWeights::empty(),
+            tx_bouncer_weights: vec![BouncerWeights { n_txs: 1, ..BouncerWeights::empty() }; n_txs],
+            chunk_latencies: vec![Duration::from_micros(100); n_txs],
+            close_reason: BlockCloseReason::DeadlineReached,
             l2_gas_used: GasAmount::default(),
             casm_hash_computation_data_sierra_gas: CasmHashComputationData::empty(),
             casm_hash_computation_data_proving_gas: CasmHashComputationData::empty(),
             compiled_class_hashes_for_migration: vec![],
             final_n_executed_txs: DUMMY_FINAL_N_EXECUTED_TXS,
+            account_transactions: test_txs(0..n_txs)
+                .into_iter()
+                .map(|tx| match tx {
+                    InternalConsensusTransaction::RpcTransaction(tx) => tx,
+                    InternalConsensusTransaction::L1Handler(_) => unreachable!(),
+                })
+                .collect(),
         }
     }
 }
@@ -16,7 +16,7 @@ use starknet_api::consensus_transaction::InternalConsensusTransaction;
 use starknet_api::transaction::TransactionHash;
 use thiserror::Error;
 
-type TransactionProviderResult<T> = Result<T, TransactionProviderError>;
+pub type TransactionProviderResult<T> = Result<T, TransactionProviderError>;
 
 #[derive(Clone, Debug, Error)]
 pub enum TransactionProviderError {
@@ -148,4 +148,106 @@ pub struct RevertBlockInput {
     pub height: BlockNumber,
 }
 
+/// A dimension of the block capacity enforced by the bouncer.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BouncerDimension {
+    L1Gas,
+    MessageSegmentLength,
+    NEvents,
+    StateDiffSize,
+    SierraGas,
+    NTxs,
+    ProvingGas,
+}
+
+impl BouncerDimension {
+    /// Returns the dimensions in which `weights` exceed `capacity`.
+    pub fn exceeded(weights: &BouncerWeights, capacity: &BouncerWeights) -> Vec<Self> {
+        [
+            (Self::L1Gas, weights.l1_gas > capacity.l1_gas),
+            (
+                Self::MessageSegmentLength,
+                weights.message_segment_length > capacity.message_segment_length,
+            ),
+            (Self::NEvents, weights.n_events > capacity.n_events),
+            (Self::StateDiffSize, weights.state_diff_size > capacity.state_diff_size),
+            (Self::SierraGas, weights.sierra_gas > capacity.sierra_gas),
+            (Self::NTxs, weights.n_txs > capacity.n_txs),
+            (Self::ProvingGas, weights.proving_gas > capacity.proving_gas),
+        ]
+        .into_iter()
+        .filter_map(|(dimension, is_exceeded)| is_exceeded.then_some(dimension))
+        .collect()
+    }
+}
+
+/// The reason the block builder stopped adding transactions to a block.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum BlockCloseReason {
+    /// A transaction did not fit in the block. Holds the dimensions the block would have exceeded
+    /// with it; empty if the executor closed the block for another reason.
+    BlockFull {
+        exceeded_dimensions: Vec<BouncerDimension>,
+    },
+    DeadlineReached,
+    /// The block was closed ahead of the deadline, since the transactions in progress were not
+    /// expected to finish in time.
+    ClosedAheadOfDeadline,
+    /// All the transactions sent by the proposer were executed (validate flow only).
+    ProposerFinished,
+}
+
+/// The execution profile of a transaction included in a proposal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct TransactionExecutionProfile {
+    pub tx_hash: TransactionHash,
+    /// The weights the transaction added to the block, including its Sierra and proving gas.
+    pub bouncer_weights: BouncerWeights,
+    /// The L2 gas the transaction was charged for.
+    pub l2_gas: GasAmount,
+    /// The wall time from when the transaction's chunk was handed to the executor until the
+    /// block builder collected the transaction's result. This includes the time the transaction
+    /// waited for the rest of its chunk, so it bounds its execution time from above.
+    pub chunk_latency_micros: u64,
+    pub reverted: bool,
+}
+
+/// Summarizes what filled a proposal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ExecutionProfile {
+    pub proposal_id: ProposalId,
+    pub height: BlockNumber,
+    /// Whether the proposal was validated rather than built by this node.
+    pub is_validator: bool,
+    pub close_reason: BlockCloseReason,
+    pub bouncer_weights: BouncerWeights,
+    pub block_capacity: BouncerWeights,
+    pub n_rejected_txs: usize,
+    /// The transactions included in the proposal, in order.
+    pub txs: Vec<TransactionExecutionProfile>,
+}
+
+/// Selects execution profiles; unset fields match all profiles.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ExecutionProfilesFilter {
+    pub height: Option<BlockNumber>,
+    pub proposal_id: Option<ProposalId>,
+}
+
+impl ExecutionProfilesFilter {
+    pub fn matches(&self, profile: &ExecutionProfile) -> bool {
+        self.height.is_none_or(|height| height == profile.height)
+            && self.proposal_id.is_none_or(|proposal_id| proposal_id == profile.proposal_id)
+    }
+}
+
+/// The status of a transaction in the pre-confirmed block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum PreconfirmedTransactionStatus {
+    /// The transaction was sent to execution, and was not executed yet.
+    Candidate,
+    /// The transaction was executed, and its receipt is available.
+    PreConfirmed,
+}
+
 pub type BatcherResult<T> = Result<T, BatcherError>;
@@ -8,9 +8,12 @@ use apollo_metrics::generate_permutation_labels;
 use apollo_proc_macros::handle_all_response_variants;
 use apollo_state_sync_types::state_sync_types::SyncBlock;
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 #[cfg(any(feature = "testing", test))]
 use mockall::automock;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use starknet_api::transaction::TransactionHash;
 use strum::{EnumVariantNames, VariantNames};
 use strum_macros::{AsRefStr, EnumDiscriminants, EnumIter, IntoStaticStr};
 use thiserror::Error;
@@ -19,9 +22,12 @@ use crate::batcher_types::{
     BatcherResult,
     DecisionReachedInput,
     DecisionReachedResponse,
+    ExecutionProfile,
+    ExecutionProfilesFilter,
     GetHeightResponse,
     GetProposalContentInput,
     GetProposalContentResponse,
+    PreconfirmedTransactionStatus,
     ProposeBlockInput,
     RevertBlockInput,
     SendProposalContentInput,
@@ -79,6 +85,37 @@ pub trait BatcherClient: Send + Sync {
     ) -> BatcherClientResult<DecisionReachedResponse>;
     /// Reverts the block with the given block number, only if it is the last in the storage.
     async fn revert_block(&self, input: RevertBlockInput) -> BatcherClientResult<()>;
+    /// Returns the retained execution profiles of recent proposals that match the filter, oldest
+    /// first.
+    async fn get_execution_profiles(
+        &self,
+        filter: ExecutionProfilesFilter,
+    ) -> BatcherClientResult<Vec<ExecutionProfile>>;
+    /// Returns the latest pre-confirmed block as JSON, in the format sent to the Cende recorder.
+    /// Fails unless the batcher publishes its pre-confirmed blocks in-process.
+    async fn get_latest_preconfirmed_block(&self) -> BatcherClientResult<Option<Value>>;
+    /// Returns the status of the transaction in the latest pre-confirmed block, if it is there.
+    /// Fails unless the batcher publishes its pre-confirmed blocks in-process.
+    async fn get_preconfirmed_transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> BatcherClientResult<Option<PreconfirmedTransactionStatus>>;
+}
+
+pub type SharedPreconfirmedBlockSource = Arc<dyn PreconfirmedBlockSource>;
+
+/// Serves the pre-confirmed blocks the batcher publishes in-process, e.g. to the monitoring
+/// endpoint.
+#[cfg_attr(any(test, feature = "testing"), automock)]
+pub trait PreconfirmedBlockSource: Send + Sync {
+    /// Streams the pre-confirmed blocks as JSON, in the format sent to the Cende recorder,
+    /// starting with the latest one. Slow subscribers may skip intermediate blocks.
+    fn subscribe(&self) -> BoxStream<'static, Value>;
+    /// Returns the latest pre-confirmed block as JSON, if any was published.
+    fn latest_block(&self) -> Option<Value>;
+    /// Returns the status of the transaction in the latest pre-confirmed block, if it is there.
+    fn transaction_status(&self, tx_hash: TransactionHash)
+        -> Option<PreconfirmedTransactionStatus>;
 }
 
 #[derive(Serialize, Deserialize, Clone, AsRefStr, EnumDiscriminants)]
@@ -97,6 +134,9 @@ pub enum BatcherRequest {
     DecisionReached(DecisionReachedInput),
     AddSyncBlock(SyncBlock),
     RevertBlock(RevertBlockInput),
+    GetExecutionProfiles(ExecutionProfilesFilter),
+    GetLatestPreconfirmedBlock,
+    GetPreconfirmedTransactionStatus(TransactionHash),
 }
 impl_debug_for_infra_requests_and_responses!(BatcherRequest);
 impl_labeled_request!(BatcherRequest, BatcherRequestLabelValue);
@@ -118,6 +158,9 @@ pub enum BatcherResponse {
     DecisionReached(BatcherResult<Box<DecisionReachedResponse>>),
     AddSyncBlock(BatcherResult<()>),
     RevertBlock(BatcherResult<()>),
+    GetExecutionProfiles(BatcherResult<Vec<ExecutionProfile>>),
+    GetLatestPreconfirmedBlock(BatcherResult<Option<Value>>),
+    GetPreconfirmedTransactionStatus(BatcherResult<Option<PreconfirmedTransactionStatus>>),
 }
 impl_debug_for_infra_requests_and_responses!(BatcherResponse);
 
@@ -241,4 +284,43 @@ where
             Direct
         )
     }
+
+    async fn get_execution_profiles(
+        &self,
+        filter: ExecutionProfilesFilter,
+    ) -> BatcherClientResult<Vec<ExecutionProfile>> {
+        let request = BatcherRequest::GetExecutionProfiles(filter);
+        handle_all_response_variants!(
+            BatcherResponse,
+            GetExecutionProfiles,
+            BatcherClientError,
+            BatcherError,
+            Direct
+        )
+    }
+
+    async fn get_latest_preconfirmed_block(&self) -> BatcherClientResult<Option<Value>> {
+        let request = BatcherRequest::GetLatestPreconfirmedBlock;
+        handle_all_response_variants!(
+            BatcherResponse,
+            GetLatestPreconfirmedBlock,
+            BatcherClientError,
+            BatcherError,
+            Direct
+        )
+    }
+
+    async fn get_preconfirmed_transaction_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> BatcherClientResult<Option<PreconfirmedTransactionStatus>> {
+        let request = BatcherRequest::GetPreconfirmedTransactionStatus(tx_hash);
+        handle_all_response_variants!(
+            BatcherResponse,
+            GetPreconfirmedTransactionStatus,
+            BatcherClientError,
+            BatcherError,
+            Direct
+        )
+    }
 }
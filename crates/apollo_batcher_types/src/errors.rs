@@ -30,6 +30,8 @@ pub enum BatcherError {
     NoActiveHeight,
     #[error("Not ready to begin work on proposal.")]
     NotReady,
+    #[error("The batcher does not publish its pre-confirmed blocks in-process.")]
+    PreconfirmedBlocksNotInProcess,
     #[error("Proposal aborted.")]
     ProposalAborted,
     #[error("Proposal with ID {proposal_id} already exists.")]
@@ -3,6 +3,7 @@
 //! such as the validator ID, the network topic of the consensus, and the starting block height.
 
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use apollo_config::converters::{
@@ -32,6 +33,10 @@ pub struct ConsensusConfig {
     pub sync_retry_interval: Duration,
     /// Future message limits configuration.
     pub future_msg_limit: FutureMsgLimitsConfig,
+    /// Write-ahead log configuration.
+    pub write_ahead_log: WriteAheadLogConfig,
+    /// Equivocation evidence configuration.
+    pub evidence: EvidenceConfig,
 }
 
 impl SerializeConfig for ConsensusConfig {
@@ -58,6 +63,8 @@ impl SerializeConfig for ConsensusConfig {
         ]);
         config.extend(prepend_sub_config_name(self.timeouts.dump(), "timeouts"));
         config.extend(prepend_sub_config_name(self.future_msg_limit.dump(), "future_msg_limit"));
+        config.extend(prepend_sub_config_name(self.write_ahead_log.dump(), "write_ahead_log"));
+        config.extend(prepend_sub_config_name(self.evidence.dump(), "evidence"));
         config
     }
 }
@@ -70,6 +77,8 @@ impl Default for ConsensusConfig {
             timeouts: TimeoutsConfig::default(),
             sync_retry_interval: Duration::from_secs_f64(1.0),
             future_msg_limit: FutureMsgLimitsConfig::default(),
+            write_ahead_log: WriteAheadLogConfig::default(),
+            evidence: EvidenceConfig::default(),
         }
     }
 }
@@ -165,6 +174,87 @@ impl Default for FutureMsgLimitsConfig {
     }
 }
 
+/// Configuration for the consensus write-ahead log.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct WriteAheadLogConfig {
+    /// Whether to persist the votes, proposals and locks of this node before they take effect, so
+    /// that a restarted node doesn't contradict them.
+    pub enabled: bool,
+    /// The directory of the write-ahead log, which holds a file per height.
+    pub path: PathBuf,
+}
+
+impl SerializeConfig for WriteAheadLogConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "Whether to persist the votes, proposals and locks of this node before they take \
+                 effect, so that a restarted node doesn't contradict them.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "The directory of the write-ahead log, which holds a file per height.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for WriteAheadLogConfig {
+    fn default() -> Self {
+        Self { enabled: false, path: PathBuf::from("/data/consensus_wal") }
+    }
+}
+
+/// Configuration for the evidence of equivocations detected by consensus.
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
+pub struct EvidenceConfig {
+    /// Whether to persist the evidence, so that it survives restarts. Otherwise, it is only kept
+    /// in memory.
+    pub persist: bool,
+    /// The file in which the evidence is persisted.
+    pub path: PathBuf,
+    /// The maximum number of evidence records kept in memory. The oldest are dropped first.
+    pub max_records: usize,
+}
+
+impl SerializeConfig for EvidenceConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "persist",
+                &self.persist,
+                "Whether to persist the evidence of equivocations, so that it survives restarts. \
+                 Otherwise, it is only kept in memory.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "The file in which the evidence of equivocations is persisted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_records",
+                &self.max_records,
+                "The maximum number of evidence records of equivocations kept in memory. The \
+                 oldest are dropped first.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+impl Default for EvidenceConfig {
+    fn default() -> Self {
+        Self { persist: true, path: PathBuf::from("/data/consensus_evidence"), max_records: 1000 }
+    }
+}
+
 /// Configuration for the `StreamHandler`.
 #[derive(Debug, Deserialize, Serialize, Clone, PartialEq)]
 pub struct StreamHandlerConfig {
@@ -0,0 +1,254 @@
+//! Evidence of equivocation: a validator signing two conflicting messages for the same height and
+//! round.
+//!
+//! [`EquivocationEvidence`] - the two conflicting messages, including their signatures, which
+//! prove the equivocation to anyone who knows the public key of the validator.
+//!
+//! [`EvidenceStore`] - the recent evidence detected by this node, which can be queried while
+//! consensus is running and optionally persisted across restarts.
+
+#[cfg(test)]
+#[path = "evidence_test.rs"]
+mod evidence_test;
+
+use std::collections::{HashSet, VecDeque};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::sync::{Arc, Mutex, MutexGuard};
+
+use apollo_protobuf::consensus::{EvidenceFilter, EvidenceSource, ProposalInit, Vote, VoteType};
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use starknet_api::block::BlockNumber;
+use tracing::{info, warn};
+
+use crate::config::EvidenceConfig;
+use crate::metrics::EquivocationType;
+use crate::types::{ConsensusError, Round, ValidatorId};
+
+/// Two conflicting messages signed by the same validator. The first is the one received first.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum EquivocationEvidence {
+    /// Two votes of the same type, height and round for different values.
+    ConflictingVotes {
+        /// The vote received first.
+        first: Vote,
+        /// The conflicting vote.
+        second: Vote,
+    },
+    /// Two proposals for the same height and round whose signed inits differ.
+    ConflictingProposals {
+        /// The proposal init received first.
+        first: ProposalInit,
+        /// The conflicting proposal init.
+        second: ProposalInit,
+    },
+}
+
+impl EquivocationEvidence {
+    /// The validator which signed both messages.
+    pub fn offender(&self) -> ValidatorId {
+        match self {
+            Self::ConflictingVotes { first, .. } => first.voter,
+            Self::ConflictingProposals { first, .. } => first.proposer,
+        }
+    }
+
+    /// The height of both messages.
+    pub fn height(&self) -> BlockNumber {
+        match self {
+            Self::ConflictingVotes { first, .. } => BlockNumber(first.height),
+            Self::ConflictingProposals { first, .. } => first.height,
+        }
+    }
+
+    /// The round of both messages.
+    pub fn round(&self) -> Round {
+        match self {
+            Self::ConflictingVotes { first, .. } => first.round,
+            Self::ConflictingProposals { first, .. } => first.round,
+        }
+    }
+
+    /// The type of both messages.
+    pub fn equivocation_type(&self) -> EquivocationType {
+        match self {
+            Self::ConflictingVotes { first, .. } => match first.vote_type {
+                VoteType::Prevote => EquivocationType::Prevote,
+                VoteType::Precommit => EquivocationType::Precommit,
+            },
+            Self::ConflictingProposals { .. } => EquivocationType::Proposal,
+        }
+    }
+
+    // A validator can equivocate in many ways in the same step, but a single proof is enough.
+    fn key(&self) -> EvidenceKey {
+        (self.offender(), self.height(), self.round(), self.equivocation_type())
+    }
+}
+
+type EvidenceKey = (ValidatorId, BlockNumber, Round, EquivocationType);
+
+/// The evidence of equivocations detected by this node, in the order it was detected. Only the
+/// first evidence of each validator, height, round and message type is kept, and only the most
+/// recent `max_records` are kept in memory. Persisted evidence is never removed from the file.
+///
+/// Clones share the same evidence, so a clone can be used to query the evidence detected by a
+/// running consensus.
+#[derive(Clone, Debug, Default)]
+pub struct EvidenceStore {
+    inner: Arc<Mutex<EvidenceStoreInner>>,
+}
+
+#[derive(Debug)]
+struct EvidenceStoreInner {
+    evidence: VecDeque<EquivocationEvidence>,
+    keys: HashSet<EvidenceKey>,
+    max_records: usize,
+    // Evidence is appended to the file as JSON lines, if it is persisted.
+    file: Option<File>,
+}
+
+impl Default for EvidenceStoreInner {
+    fn default() -> Self {
+        Self {
+            evidence: VecDeque::new(),
+            keys: HashSet::new(),
+            max_records: EvidenceConfig::default().max_records,
+            file: None,
+        }
+    }
+}
+
+impl EvidenceStore {
+    /// Opens the store according to the config, loading the evidence persisted before a restart.
+    pub fn open(config: &EvidenceConfig) -> Result<Self, ConsensusError> {
+        let mut inner =
+            EvidenceStoreInner { max_records: config.max_records, ..Default::default() };
+        if !config.persist {
+            return Ok(Self { inner: Arc::new(Mutex::new(inner)) });
+        }
+        if let Some(dir) = config.path.parent() {
+            fs::create_dir_all(dir).map_err(evidence_error)?;
+        }
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&config.path)
+            .map_err(evidence_error)?;
+
+        for evidence in read_evidence(&mut file)? {
+            if !inner.keys.contains(&evidence.key()) {
+                inner.push(evidence);
+            }
+        }
+        if !inner.evidence.is_empty() {
+            info!("Loaded {} equivocation evidence records.", inner.evidence.len());
+        }
+        inner.file = Some(file);
+        Ok(Self { inner: Arc::new(Mutex::new(inner)) })
+    }
+
+    /// Adds the evidence, persisting it if configured to. Returns false if evidence of the same
+    /// equivocation was already stored.
+    pub(crate) fn add(&self, evidence: EquivocationEvidence) -> Result<bool, ConsensusError> {
+        let mut inner = self.lock();
+        if inner.keys.contains(&evidence.key()) {
+            return Ok(false);
+        }
+        if let Some(file) = &mut inner.file {
+            let mut line = serde_json::to_vec(&evidence)
+                .map_err(|err| ConsensusError::EvidenceStoreError(err.to_string()))?;
+            line.push(b'\n');
+            file.write_all(&line).and_then(|()| file.sync_data()).map_err(evidence_error)?;
+        }
+        inner.push(evidence);
+        Ok(true)
+    }
+
+    /// Returns all the stored evidence.
+    pub fn evidence(&self) -> Vec<EquivocationEvidence> {
+        self.lock().evidence.iter().cloned().collect()
+    }
+
+    /// Returns the evidence which matches the filter.
+    pub fn evidence_matching(&self, filter: &EvidenceFilter) -> Vec<EquivocationEvidence> {
+        self.filter(|evidence| filter.matches(evidence.height(), evidence.offender()))
+    }
+
+    /// Returns the evidence of equivocations at `height`.
+    pub fn evidence_at_height(&self, height: BlockNumber) -> Vec<EquivocationEvidence> {
+        self.filter(|evidence| evidence.height() == height)
+    }
+
+    /// Returns the evidence of equivocations by `validator`.
+    pub fn evidence_against(&self, validator: ValidatorId) -> Vec<EquivocationEvidence> {
+        self.filter(|evidence| evidence.offender() == validator)
+    }
+
+    fn filter(
+        &self,
+        predicate: impl Fn(&EquivocationEvidence) -> bool,
+    ) -> Vec<EquivocationEvidence> {
+        self.lock().evidence.iter().filter(|evidence| predicate(evidence)).cloned().collect()
+    }
+
+    fn lock(&self) -> MutexGuard<'_, EvidenceStoreInner> {
+        self.inner.lock().expect("Evidence store lock should not be poisoned.")
+    }
+}
+
+impl EvidenceSource for EvidenceStore {
+    fn get_evidence(&self, filter: &EvidenceFilter) -> Vec<Value> {
+        self.evidence_matching(filter)
+            .iter()
+            .map(|evidence| {
+                serde_json::to_value(evidence).expect("Evidence should be serializable to JSON.")
+            })
+            .collect()
+    }
+}
+
+impl EvidenceStoreInner {
+    // Stores new evidence, dropping the oldest evidence if there are too many records. Consensus
+    // doesn't return to past heights, so the dropped equivocations aren't detected again.
+    fn push(&mut self, evidence: EquivocationEvidence) {
+        self.keys.insert(evidence.key());
+        self.evidence.push_back(evidence);
+        if self.evidence.len() > self.max_records {
+            let oldest = self.evidence.pop_front().expect("The evidence should not be empty.");
+            self.keys.remove(&oldest.key());
+        }
+    }
+}
+
+// Reads all the evidence in the file. A trailing partial record, left by a crash mid-write, is
+// discarded.
+fn read_evidence(file: &mut File) -> Result<Vec<EquivocationEvidence>, ConsensusError> {
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).map_err(evidence_error)?;
+    let complete_len = content.iter().rposition(|byte| *byte == b'\n').map_or(0, |i| i + 1);
+    if complete_len < content.len() {
+        warn!("Discarding a partially written equivocation evidence record.");
+        file.set_len(u64::try_from(complete_len).expect("File length should fit in u64."))
+            .and_then(|()| file.sync_data())
+            .map_err(evidence_error)?;
+    }
+
+    content[..complete_len]
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            serde_json::from_slice(line).map_err(|err| {
+                ConsensusError::EvidenceStoreError(format!(
+                    "Corrupted equivocation evidence record: {err}"
+                ))
+            })
+        })
+        .collect()
+}
+
+fn evidence_error(err: io::Error) -> ConsensusError {
+    ConsensusError::EvidenceStoreError(err.to_string())
+}
@@ -0,0 +1,181 @@
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+use apollo_protobuf::consensus::{
+    EvidenceFilter,
+    EvidenceSource,
+    ProposalInit,
+    DEFAULT_VALIDATOR_ID,
+};
+use lazy_static::lazy_static;
+use starknet_api::block::BlockNumber;
+use starknet_types_core::felt::Felt;
+use tempfile::tempdir;
+
+use crate::config::EvidenceConfig;
+use crate::evidence::{EquivocationEvidence, EvidenceStore};
+use crate::metrics::EquivocationType;
+use crate::test_utils::{precommit, prevote, proposal_init};
+use crate::types::ValidatorId;
+
+lazy_static! {
+    static ref VALIDATOR_ID_1: ValidatorId = DEFAULT_VALIDATOR_ID.into();
+    static ref VALIDATOR_ID_2: ValidatorId = (DEFAULT_VALIDATOR_ID + 1).into();
+}
+
+fn conflicting_prevotes(height: u64, round: u32, voter: ValidatorId) -> EquivocationEvidence {
+    EquivocationEvidence::ConflictingVotes {
+        first: prevote(Some(Felt::ONE), height, round, voter),
+        second: prevote(Some(Felt::TWO), height, round, voter),
+    }
+}
+
+fn persisted(path: &Path) -> EvidenceConfig {
+    EvidenceConfig { persist: true, path: path.to_path_buf(), ..Default::default() }
+}
+
+#[test]
+fn evidence_describes_the_equivocation() {
+    let votes = EquivocationEvidence::ConflictingVotes {
+        first: precommit(Some(Felt::ONE), 3, 2, *VALIDATOR_ID_1),
+        second: precommit(None, 3, 2, *VALIDATOR_ID_1),
+    };
+    assert_eq!(votes.offender(), *VALIDATOR_ID_1);
+    assert_eq!(votes.height(), BlockNumber(3));
+    assert_eq!(votes.round(), 2);
+    assert_eq!(votes.equivocation_type(), EquivocationType::Precommit);
+
+    let first = proposal_init(4, 1, *VALIDATOR_ID_2);
+    let proposals = EquivocationEvidence::ConflictingProposals {
+        first,
+        second: ProposalInit { valid_round: Some(0), ..first },
+    };
+    assert_eq!(proposals.offender(), *VALIDATOR_ID_2);
+    assert_eq!(proposals.height(), BlockNumber(4));
+    assert_eq!(proposals.round(), 1);
+    assert_eq!(proposals.equivocation_type(), EquivocationType::Proposal);
+}
+
+#[test]
+fn store_keeps_first_evidence_of_each_equivocation() {
+    let store = EvidenceStore::default();
+    let evidence = conflicting_prevotes(1, 0, *VALIDATOR_ID_1);
+    // Another conflicting prevote of the same validator, height and round.
+    let same_equivocation = EquivocationEvidence::ConflictingVotes {
+        first: prevote(Some(Felt::ONE), 1, 0, *VALIDATOR_ID_1),
+        second: prevote(None, 1, 0, *VALIDATOR_ID_1),
+    };
+    let other_round = conflicting_prevotes(1, 1, *VALIDATOR_ID_1);
+
+    assert!(store.add(evidence.clone()).unwrap());
+    assert!(!store.add(same_equivocation).unwrap());
+    assert!(!store.add(evidence.clone()).unwrap());
+    assert!(store.add(other_round.clone()).unwrap());
+
+    assert_eq!(store.evidence(), vec![evidence, other_round]);
+}
+
+#[test]
+fn query_evidence() {
+    let store = EvidenceStore::default();
+    let evidence = vec![
+        conflicting_prevotes(1, 0, *VALIDATOR_ID_1),
+        conflicting_prevotes(1, 0, *VALIDATOR_ID_2),
+        conflicting_prevotes(2, 0, *VALIDATOR_ID_1),
+    ];
+    for evidence in &evidence {
+        store.add(evidence.clone()).unwrap();
+    }
+    // Clones share the evidence.
+    let store = store.clone();
+
+    assert_eq!(store.evidence_at_height(BlockNumber(1)), evidence[..2]);
+    assert_eq!(store.evidence_at_height(BlockNumber(3)), Vec::new());
+    assert_eq!(
+        store.evidence_against(*VALIDATOR_ID_1),
+        vec![evidence[0].clone(), evidence[2].clone()]
+    );
+    let filter = EvidenceFilter { height: Some(BlockNumber(1)), offender: Some(*VALIDATOR_ID_2) };
+    assert_eq!(store.evidence_matching(&filter), evidence[1..2]);
+    assert_eq!(store.get_evidence(&filter), vec![serde_json::to_value(&evidence[1]).unwrap()]);
+    assert_eq!(store.evidence_matching(&EvidenceFilter::default()), evidence);
+}
+
+#[test]
+fn only_recent_evidence_is_kept_in_memory() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("equivocations");
+    let config = EvidenceConfig { max_records: 2, ..persisted(&path) };
+    let evidence: Vec<_> =
+        (1..=3).map(|height| conflicting_prevotes(height, 0, *VALIDATOR_ID_1)).collect();
+
+    let store = EvidenceStore::open(&config).unwrap();
+    for evidence in &evidence {
+        assert!(store.add(evidence.clone()).unwrap());
+    }
+    assert_eq!(store.evidence(), evidence[1..]);
+    drop(store);
+
+    // All the evidence is persisted, but only the most recent is loaded.
+    assert_eq!(EvidenceStore::open(&persisted(&path)).unwrap().evidence(), evidence);
+    assert_eq!(EvidenceStore::open(&config).unwrap().evidence(), evidence[1..]);
+}
+
+#[test]
+fn evidence_survives_restart() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("evidence").join("equivocations");
+    let evidence = vec![
+        conflicting_prevotes(1, 0, *VALIDATOR_ID_1),
+        EquivocationEvidence::ConflictingProposals {
+            first: proposal_init(1, 0, *VALIDATOR_ID_2),
+            second: ProposalInit { valid_round: Some(0), ..proposal_init(1, 0, *VALIDATOR_ID_2) },
+        },
+    ];
+    {
+        let store = EvidenceStore::open(&persisted(&path)).unwrap();
+        for evidence in &evidence {
+            store.add(evidence.clone()).unwrap();
+        }
+    }
+
+    let store = EvidenceStore::open(&persisted(&path)).unwrap();
+    assert_eq!(store.evidence(), evidence);
+    // The loaded evidence is still deduplicated.
+    assert!(!store.add(evidence[0].clone()).unwrap());
+}
+
+#[test]
+fn not_persisted_when_disabled() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("equivocations");
+    let config = EvidenceConfig { persist: false, path: path.clone(), ..Default::default() };
+
+    EvidenceStore::open(&config).unwrap().add(conflicting_prevotes(1, 0, *VALIDATOR_ID_1)).unwrap();
+
+    assert!(!path.exists());
+    assert_eq!(EvidenceStore::open(&config).unwrap().evidence(), Vec::new());
+}
+
+#[test]
+fn partially_written_evidence_is_discarded() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("equivocations");
+    let evidence = conflicting_prevotes(1, 0, *VALIDATOR_ID_1);
+    EvidenceStore::open(&persisted(&path)).unwrap().add(evidence.clone()).unwrap();
+    // Simulate a crash in the middle of writing a record.
+    OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"ConflictingVo").unwrap();
+
+    let store = EvidenceStore::open(&persisted(&path)).unwrap();
+    assert_eq!(store.evidence(), vec![evidence.clone()]);
+
+    // New evidence is appended after the complete records.
+    let new_evidence = conflicting_prevotes(2, 0, *VALIDATOR_ID_1);
+    store.add(new_evidence.clone()).unwrap();
+    drop(store);
+    assert_eq!(
+        EvidenceStore::open(&persisted(&path)).unwrap().evidence(),
+        vec![evidence, new_evidence]
+    );
+}
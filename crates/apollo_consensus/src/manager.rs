@@ -16,6 +16,10 @@ use apollo_network::network_manager::BroadcastTopicClientTrait;
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use apollo_protobuf::consensus::{ProposalInit, Vote};
 use apollo_protobuf::converters::ProtobufConversionError;
+use apollo_signature_manager::signature_manager::{
+    verify_proposal_init_signature,
+    verify_vote_signature,
+};
 use apollo_time::time::{sleep_until, Clock, DefaultClock};
 use futures::channel::mpsc;
 use futures::stream::FuturesUnordered;
@@ -23,19 +27,31 @@ use futures::{FutureExt, StreamExt};
 use starknet_api::block::BlockNumber;
 use tracing::{debug, error, info, instrument, trace, warn};
 
-use crate::config::{FutureMsgLimitsConfig, TimeoutsConfig};
+use crate::config::{FutureMsgLimitsConfig, TimeoutsConfig, WriteAheadLogConfig};
+use crate::evidence::EvidenceStore;
 use crate::metrics::{
     register_metrics,
     CONSENSUS_BLOCK_NUMBER,
     CONSENSUS_CACHED_VOTES,
     CONSENSUS_DECISIONS_REACHED_BY_CONSENSUS,
     CONSENSUS_DECISIONS_REACHED_BY_SYNC,
+    CONSENSUS_EQUIVOCATIONS,
     CONSENSUS_MAX_CACHED_BLOCK_NUMBER,
     CONSENSUS_PROPOSALS_RECEIVED,
+    LABEL_NAME_EQUIVOCATION_TYPE,
 };
 use crate::single_height_consensus::{ShcReturn, SingleHeightConsensus};
-use crate::types::{BroadcastVoteChannel, ConsensusContext, ConsensusError, Decision, ValidatorId};
+use crate::stream_handler::InboundStream;
+use crate::types::{
+    BroadcastVoteChannel,
+    ConsensusContext,
+    ConsensusError,
+    Decision,
+    ValidatorId,
+    ValidatorSet,
+};
 use crate::votes_threshold::QuorumType;
+use crate::write_ahead_log::WriteAheadLog;
 
 /// Arguments for running consensus.
 #[derive(Clone, Debug)]
@@ -56,6 +72,11 @@ pub struct RunConsensusArguments {
     pub quorum_type: QuorumType,
     /// Future message limits configuration.
     pub future_msg_limit: FutureMsgLimitsConfig,
+    /// Write-ahead log configuration.
+    pub write_ahead_log: WriteAheadLogConfig,
+    /// Stores the evidence of the equivocations detected by consensus. Clones of the store can be
+    /// used to query the evidence while consensus is running.
+    pub evidence_store: EvidenceStore,
 }
 
 /// Run consensus indefinitely.
@@ -70,14 +91,15 @@ pub struct RunConsensusArguments {
 /// - `vote_receiver`: The channels to receive votes from the network. These are self contained
 ///   messages.
 /// - `proposals_receiver`: The channel to receive proposals from the network. Proposals are
-///   represented as streams (ProposalInit, Content.*, ProposalFin).
+///   represented as streams (ProposalInit, Content.*, ProposalFin), with the metadata of their
+///   sender.
 // Always print the validator ID since some tests collate multiple consensus logs in a single file.
 #[instrument(skip_all, fields(validator_id=%run_consensus_args.validator_id), level = "error")]
 pub async fn run_consensus<ContextT>(
     run_consensus_args: RunConsensusArguments,
     mut context: ContextT,
     mut vote_receiver: BroadcastVoteChannel,
-    mut proposals_receiver: mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
+    mut proposals_receiver: mpsc::Receiver<InboundStream<ContextT::ProposalPart>>,
 ) -> Result<(), ConsensusError>
 where
     ContextT: ConsensusContext,
@@ -88,12 +110,19 @@ where
     tokio::time::sleep(run_consensus_args.consensus_delay).await;
     assert!(run_consensus_args.start_observe_height <= run_consensus_args.start_active_height);
     let mut current_height = run_consensus_args.start_observe_height;
+    let write_ahead_log = if run_consensus_args.write_ahead_log.enabled {
+        Some(WriteAheadLog::open(&run_consensus_args.write_ahead_log.path)?)
+    } else {
+        None
+    };
     let mut manager = MultiHeightManager::new(
         run_consensus_args.validator_id,
         run_consensus_args.sync_retry_interval,
         run_consensus_args.quorum_type,
         run_consensus_args.timeouts,
         run_consensus_args.future_msg_limit,
+        write_ahead_log,
+        run_consensus_args.evidence_store,
     );
     loop {
         let must_observer = current_height < run_consensus_args.start_active_height;
@@ -113,7 +142,7 @@ where
                 let round = decision.precommits[0].round;
                 let proposer = context.proposer(current_height, round);
                 info!(
-                    "DECISION_REACHED: Decision reached for round {} with proposer {}. {:?}",
+                    "DECISION_REACHED: Decision reached for round {} with proposer {:?}. {:?}",
                     round, proposer, decision
                 );
                 CONSENSUS_DECISIONS_REACHED_BY_CONSENSUS.increment(1);
@@ -138,20 +167,24 @@ pub enum RunHeightRes {
     Sync,
 }
 
-type ProposalReceiverTuple<T> = (ProposalInit, mpsc::Receiver<T>);
+type ProposalReceiverTuple<T> = (ProposalInit, BroadcastedMessageMetadata, mpsc::Receiver<T>);
 
 /// Runs Tendermint repeatedly across different heights. Handles issues which are not explicitly
 /// part of the single height consensus algorithm (e.g. messages from future heights).
 #[derive(Debug)]
 struct MultiHeightManager<ContextT: ConsensusContext> {
     validator_id: ValidatorId,
-    future_votes: BTreeMap<u64, Vec<Vote>>,
+    // Votes are cached with their metadata, so that their senders can be reported if the votes
+    // turn out to be invalid once the validators of their height are known.
+    future_votes: BTreeMap<u64, Vec<(Vote, BroadcastedMessageMetadata)>>,
     sync_retry_interval: Duration,
     quorum_type: QuorumType,
-    // Mapping: { Height : { Round : (Init, Receiver)}}
+    // Mapping: { Height : { Round : (Init, Metadata, Receiver)}}
     cached_proposals: BTreeMap<u64, BTreeMap<u32, ProposalReceiverTuple<ContextT::ProposalPart>>>,
     timeouts: TimeoutsConfig,
     future_msg_limit: FutureMsgLimitsConfig,
+    write_ahead_log: Option<WriteAheadLog>,
+    evidence_store: EvidenceStore,
 }
 
 impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
@@ -162,6 +195,8 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         quorum_type: QuorumType,
         timeouts: TimeoutsConfig,
         future_msg_limit: FutureMsgLimitsConfig,
+        write_ahead_log: Option<WriteAheadLog>,
+        evidence_store: EvidenceStore,
     ) -> Self {
         Self {
             validator_id,
@@ -171,6 +206,8 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             cached_proposals: BTreeMap::new(),
             timeouts,
             future_msg_limit,
+            write_ahead_log,
+            evidence_store,
         }
     }
 
@@ -195,7 +232,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         height: BlockNumber,
         must_observer: bool,
         broadcast_channels: &mut BroadcastVoteChannel,
-        proposals_receiver: &mut mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
+        proposals_receiver: &mut mpsc::Receiver<InboundStream<ContextT::ProposalPart>>,
     ) -> Result<RunHeightRes, ConsensusError> {
         info!("Running consensus for height {}.", height);
         let res = self
@@ -229,7 +266,8 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         // care about the returned proposals).
         self.get_current_height_proposals(height);
         while let Ok(content_receiver) = proposals_receiver.try_next() {
-            self.handle_proposal(context, height, None, content_receiver).await?;
+            self.handle_proposal(context, height, None, content_receiver, broadcast_channels)
+                .await?;
         }
 
         Ok(res)
@@ -241,16 +279,20 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         height: BlockNumber,
         must_observer: bool,
         broadcast_channels: &mut BroadcastVoteChannel,
-        proposals_receiver: &mut mpsc::Receiver<mpsc::Receiver<ContextT::ProposalPart>>,
+        proposals_receiver: &mut mpsc::Receiver<InboundStream<ContextT::ProposalPart>>,
     ) -> Result<RunHeightRes, ConsensusError> {
         CONSENSUS_BLOCK_NUMBER.set_lossy(height.0);
         self.report_max_cached_block_number_metric(height);
+        if let Some(write_ahead_log) = &self.write_ahead_log {
+            // Lower heights are decided, so our messages for them can no longer conflict.
+            write_ahead_log.prune(height)?;
+        }
         if context.try_sync(height).await {
             return Ok(RunHeightRes::Sync);
         }
 
-        let validators = context.validators(height).await;
-        let is_observer = must_observer || !validators.contains(&self.validator_id);
+        let validators = context.validators(height).await?;
+        let is_observer = must_observer || !validators.contains_key(&self.validator_id);
         info!(
             "START_HEIGHT: running consensus for height {:?}. is_observer: {}, validators: {:?}",
             height, is_observer, validators,
@@ -264,9 +306,15 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             self.quorum_type,
             self.timeouts.clone(),
         );
+        if let Some(write_ahead_log) = &self.write_ahead_log {
+            if !is_observer {
+                let (height_log, recovered_height) = write_ahead_log.open_height(height)?;
+                shc.set_write_ahead_log(height_log, recovered_height);
+            }
+        }
         let mut shc_events = FuturesUnordered::new();
 
-        match self.start_height(context, height, &mut shc).await? {
+        match self.start_height(context, height, &mut shc, broadcast_channels).await? {
             ShcReturn::Decision(decision) => {
                 return Ok(RunHeightRes::Decision(decision));
             }
@@ -288,7 +336,9 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
                         context, height, Some(&mut shc), message, broadcast_channels).await?
                 },
                 content_receiver = proposals_receiver.next() => {
-                    self.handle_proposal(context, height, Some(&mut shc), content_receiver).await?
+                    self.handle_proposal(
+                        context, height, Some(&mut shc), content_receiver, broadcast_channels
+                    ).await?
                 },
                 Some(shc_event) = shc_events.next() => {
                     shc.handle_event(context, shc_event).await?
@@ -320,6 +370,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         context: &mut ContextT,
         height: BlockNumber,
         shc: &mut SingleHeightConsensus,
+        broadcast_channels: &mut BroadcastVoteChannel,
     ) -> Result<ShcReturn, ConsensusError> {
         CONSENSUS_CACHED_VOTES.set_lossy(self.future_votes.entry(height.0).or_default().len());
         let mut tasks = match shc.start(context).await? {
@@ -335,8 +386,15 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
 
         let cached_proposals = self.get_current_height_proposals(height);
         trace!("Cached proposals for height {}: {:?}", height, cached_proposals);
-        for (init, content_receiver) in cached_proposals {
-            match shc.handle_proposal(context, init, content_receiver).await? {
+        for (init, metadata, content_receiver) in cached_proposals {
+            if !is_signed_by_proposer(shc.validators(), &init, &metadata, broadcast_channels) {
+                continue;
+            }
+            let shc_return = shc.handle_proposal(context, init, content_receiver).await?;
+            if self.record_equivocations(shc)? {
+                report_peer(broadcast_channels, &metadata);
+            }
+            match shc_return {
                 decision @ ShcReturn::Decision(_) => return Ok(decision),
                 ShcReturn::Tasks(new_tasks) => tasks.extend(new_tasks),
             }
@@ -344,8 +402,15 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
 
         let cached_votes = self.get_current_height_votes(height);
         trace!("Cached votes for height {}: {:?}", height, cached_votes);
-        for msg in cached_votes {
-            match shc.handle_vote(context, msg).await? {
+        for (msg, metadata) in cached_votes {
+            if !is_signed_by_voter(shc.validators(), &msg, &metadata, broadcast_channels) {
+                continue;
+            }
+            let shc_return = shc.handle_vote(context, msg).await?;
+            if self.record_equivocations(shc)? {
+                report_peer(broadcast_channels, &metadata);
+            }
+            match shc_return {
                 decision @ ShcReturn::Decision(_) => return Ok(decision),
                 ShcReturn::Tasks(new_tasks) => tasks.extend(new_tasks),
             }
@@ -361,11 +426,12 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         context: &mut ContextT,
         height: BlockNumber,
         shc: Option<&mut SingleHeightConsensus>,
-        content_receiver: Option<mpsc::Receiver<ContextT::ProposalPart>>,
+        content_receiver: Option<InboundStream<ContextT::ProposalPart>>,
+        broadcast_channels: &mut BroadcastVoteChannel,
     ) -> Result<ShcReturn, ConsensusError> {
         CONSENSUS_PROPOSALS_RECEIVED.increment(1);
         // Get the first message to verify the init was sent.
-        let Some(mut content_receiver) = content_receiver else {
+        let Some((metadata, mut content_receiver)) = content_receiver else {
             return Err(ConsensusError::InternalNetworkError(
                 "proposal receiver should never be closed".to_string(),
             ));
@@ -398,7 +464,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
                         .entry(proposal_init.height.0)
                         .or_default()
                         .entry(proposal_init.round)
-                        .or_insert((proposal_init, content_receiver));
+                        .or_insert((proposal_init, metadata, content_receiver));
                 }
                 Ok(ShcReturn::Tasks(Vec::new()))
             }
@@ -408,8 +474,20 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             }
             std::cmp::Ordering::Equal => match shc {
                 Some(shc) => {
-                    if self.should_cache_proposal(&height, shc.current_round(), &proposal_init) {
-                        shc.handle_proposal(context, proposal_init, content_receiver).await
+                    if self.should_cache_proposal(&height, shc.current_round(), &proposal_init)
+                        && is_signed_by_proposer(
+                            shc.validators(),
+                            &proposal_init,
+                            &metadata,
+                            broadcast_channels,
+                        )
+                    {
+                        let shc_return =
+                            shc.handle_proposal(context, proposal_init, content_receiver).await?;
+                        if self.record_equivocations(shc)? {
+                            report_peer(broadcast_channels, &metadata);
+                        }
+                        Ok(shc_return)
                     } else {
                         Ok(ShcReturn::Tasks(Vec::new()))
                     }
@@ -432,7 +510,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         vote: Option<(Result<Vote, ProtobufConversionError>, BroadcastedMessageMetadata)>,
         broadcast_channels: &mut BroadcastVoteChannel,
     ) -> Result<ShcReturn, ConsensusError> {
-        let message = match vote {
+        let (message, metadata) = match vote {
             None => Err(ConsensusError::InternalNetworkError(
                 "NetworkReceiver should never be closed".to_string(),
             )),
@@ -446,18 +524,11 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
                 {
                     error!("Unable to send continue_propagation. {:?}", metadata);
                 }
-                Ok(msg)
+                Ok((msg, metadata))
             }
             Some((Err(e), metadata)) => {
                 // Failed to parse consensus message
-                if broadcast_channels
-                    .broadcast_topic_client
-                    .report_peer(metadata.clone())
-                    .now_or_never()
-                    .is_none()
-                {
-                    error!("Unable to send report_peer. {:?}", metadata)
-                }
+                report_peer(broadcast_channels, &metadata);
                 Err(e.into())
             }
         }?;
@@ -469,7 +540,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             std::cmp::Ordering::Greater => {
                 if self.should_cache_vote(&height, 0, &message) {
                     trace!("Cache message for a future height. {:?}", message);
-                    self.future_votes.entry(message.height).or_default().push(message);
+                    self.future_votes.entry(message.height).or_default().push((message, metadata));
                 }
                 Ok(ShcReturn::Tasks(Vec::new()))
             }
@@ -479,8 +550,19 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
             }
             std::cmp::Ordering::Equal => match shc {
                 Some(shc) => {
-                    if self.should_cache_vote(&height, shc.current_round(), &message) {
-                        shc.handle_vote(context, message).await
+                    if self.should_cache_vote(&height, shc.current_round(), &message)
+                        && is_signed_by_voter(
+                            shc.validators(),
+                            &message,
+                            &metadata,
+                            broadcast_channels,
+                        )
+                    {
+                        let shc_return = shc.handle_vote(context, message).await?;
+                        if self.record_equivocations(shc)? {
+                            report_peer(broadcast_channels, &metadata);
+                        }
+                        Ok(shc_return)
                     } else {
                         Ok(ShcReturn::Tasks(Vec::new()))
                     }
@@ -499,7 +581,7 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     fn get_current_height_proposals(
         &mut self,
         height: BlockNumber,
-    ) -> Vec<(ProposalInit, mpsc::Receiver<ContextT::ProposalPart>)> {
+    ) -> Vec<ProposalReceiverTuple<ContextT::ProposalPart>> {
         loop {
             let Some(entry) = self.cached_proposals.first_entry() else {
                 return Vec::new();
@@ -521,7 +603,10 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
     /// - returns (and removes from stored votes) all of the current height votes.
     /// - drops votes from earlier heights.
     /// - retains future votes in the cache.
-    fn get_current_height_votes(&mut self, height: BlockNumber) -> Vec<Vote> {
+    fn get_current_height_votes(
+        &mut self,
+        height: BlockNumber,
+    ) -> Vec<(Vote, BroadcastedMessageMetadata)> {
         // Depends on `future_votes` being sorted by height.
         loop {
             let Some(entry) = self.future_votes.first_entry() else {
@@ -537,6 +622,26 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         }
     }
 
+    // Stores the equivocations detected by the SHC. Returns whether any were detected, in which
+    // case the sender of the message which was just handled should be reported, even if the
+    // evidence was already stored.
+    fn record_equivocations(
+        &self,
+        shc: &mut SingleHeightConsensus,
+    ) -> Result<bool, ConsensusError> {
+        let equivocations = shc.take_equivocations();
+        let detected = !equivocations.is_empty();
+        for evidence in equivocations {
+            let equivocation_type = evidence.equivocation_type();
+            if self.evidence_store.add(evidence.clone())? {
+                warn!("Equivocation detected. {:?}", evidence);
+                CONSENSUS_EQUIVOCATIONS
+                    .increment(1, &[(LABEL_NAME_EQUIVOCATION_TYPE, equivocation_type.into())]);
+            }
+        }
+        Ok(detected)
+    }
+
     fn report_max_cached_block_number_metric(&self, height: BlockNumber) {
         // If nothing is cached use current height as "max".
         let max_cached_block_number = self.cached_proposals.keys().max().unwrap_or(&height.0);
@@ -602,3 +707,54 @@ impl<ContextT: ConsensusContext> MultiHeightManager<ContextT> {
         self.should_cache_msg(current_height, current_round, vote.height, vote.round, "vote")
     }
 }
+
+// Checks that the vote is signed by its voter, who must be a validator of the height. The sender of
+// a vote which isn't is reported to the network.
+fn is_signed_by_voter(
+    validators: &ValidatorSet,
+    vote: &Vote,
+    metadata: &BroadcastedMessageMetadata,
+    broadcast_channels: &mut BroadcastVoteChannel,
+) -> bool {
+    let is_signed = validators.get(&vote.voter).is_some_and(|validator| {
+        verify_vote_signature(vote, validator.public_key).unwrap_or(false)
+    });
+    if !is_signed {
+        warn!("Dropping a vote which isn't signed by its voter. {:?}", vote);
+        report_peer(broadcast_channels, metadata);
+    }
+    is_signed
+}
+
+// Reports the sender of a message to the network, which scores it as misconduct by the peer.
+fn report_peer(
+    broadcast_channels: &mut BroadcastVoteChannel,
+    metadata: &BroadcastedMessageMetadata,
+) {
+    if broadcast_channels
+        .broadcast_topic_client
+        .report_peer(metadata.clone())
+        .now_or_never()
+        .is_none()
+    {
+        error!("Unable to send report_peer. {:?}", metadata)
+    }
+}
+
+// Checks that the proposal is signed by its proposer, who must be a validator of the height. The
+// sender of a proposal which isn't is reported to the network.
+fn is_signed_by_proposer(
+    validators: &ValidatorSet,
+    init: &ProposalInit,
+    metadata: &BroadcastedMessageMetadata,
+    broadcast_channels: &mut BroadcastVoteChannel,
+) -> bool {
+    let is_signed = validators.get(&init.proposer).is_some_and(|validator| {
+        verify_proposal_init_signature(init, validator.public_key).unwrap_or(false)
+    });
+    if !is_signed {
+        warn!("Dropping a proposal which isn't signed by its proposer. {:?}", init);
+        report_peer(broadcast_channels, metadata);
+    }
+    is_signed
+}
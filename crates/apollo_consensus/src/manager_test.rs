@@ -1,3 +1,4 @@
+use std::collections::HashSet;
 use std::time::Duration;
 use std::vec;
 
@@ -7,17 +8,29 @@ use apollo_network::network_manager::test_utils::{
     TestSubscriberChannels,
 };
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
-use apollo_protobuf::consensus::{Vote, DEFAULT_VALIDATOR_ID};
+use apollo_protobuf::consensus::{ProposalInit, Vote, DEFAULT_VALIDATOR_ID};
 use apollo_test_utils::{get_rng, GetTestInstance};
 use futures::channel::{mpsc, oneshot};
-use futures::{FutureExt, SinkExt};
+use futures::{FutureExt, SinkExt, StreamExt};
 use lazy_static::lazy_static;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::crypto::utils::Signature;
 use starknet_types_core::felt::Felt;
 
 use super::{run_consensus, MultiHeightManager, RunHeightRes};
-use crate::config::{FutureMsgLimitsConfig, TimeoutsConfig};
-use crate::test_utils::{precommit, prevote, proposal_init, MockTestContext, TestProposalPart};
+use crate::config::{FutureMsgLimitsConfig, TimeoutsConfig, WriteAheadLogConfig};
+use crate::evidence::{EquivocationEvidence, EvidenceStore};
+use crate::stream_handler::InboundStream;
+use crate::test_utils::{
+    equally_weighted,
+    precommit,
+    prevote,
+    proposal_init,
+    sign_proposal_init,
+    sign_vote,
+    MockTestContext,
+    TestProposalPart,
+};
 use crate::types::ValidatorId;
 use crate::votes_threshold::QuorumType;
 use crate::RunConsensusArguments;
@@ -45,17 +58,19 @@ const FUTURE_MSG_LIMIT: FutureMsgLimitsConfig = FutureMsgLimitsConfig {
 async fn send(sender: &mut MockBroadcastedMessagesSender<Vote>, msg: Vote) {
     let broadcasted_message_metadata =
         BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
-    sender.send((msg, broadcasted_message_metadata)).await.unwrap();
+    sender.send((sign_vote(msg).await, broadcasted_message_metadata)).await.unwrap();
 }
 
 async fn send_proposal(
-    proposal_receiver_sender: &mut mpsc::Sender<mpsc::Receiver<TestProposalPart>>,
+    proposal_receiver_sender: &mut mpsc::Sender<InboundStream<TestProposalPart>>,
     content: Vec<TestProposalPart>,
 ) {
     let (mut proposal_sender, proposal_receiver) = mpsc::channel(CHANNEL_SIZE);
-    proposal_receiver_sender.send(proposal_receiver).await.unwrap();
+    let metadata = BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
+    proposal_receiver_sender.send((metadata, proposal_receiver)).await.unwrap();
     for item in content {
-        proposal_sender.send(item).await.unwrap();
+        let TestProposalPart::Init(init) = item;
+        proposal_sender.send(TestProposalPart::Init(sign_proposal_init(init).await)).await.unwrap();
     }
 }
 
@@ -110,8 +125,10 @@ async fn manager_multiple_heights_unordered() {
     // Run the manager for height 1.
     context.expect_try_sync().returning(|_| false);
     expect_validate_proposal(&mut context, Felt::ONE, 1);
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validators()
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
 
@@ -121,6 +138,8 @@ async fn manager_multiple_heights_unordered() {
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
         FUTURE_MSG_LIMIT,
+        None,
+        EvidenceStore::default(),
     );
     let mut subscriber_channels = subscriber_channels.into();
     let decision = manager
@@ -159,8 +178,10 @@ async fn run_consensus_sync() {
     let (mut proposal_receiver_sender, proposal_receiver_receiver) = mpsc::channel(CHANNEL_SIZE);
 
     expect_validate_proposal(&mut context, Felt::TWO, 1);
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validators()
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_set_height_and_round().returning(move |_, _| ());
     context.expect_broadcast().returning(move |_| Ok(()));
     context
@@ -197,6 +218,8 @@ async fn run_consensus_sync() {
         sync_retry_interval: SYNC_RETRY_INTERVAL,
         quorum_type: QuorumType::Byzantine,
         future_msg_limit: FUTURE_MSG_LIMIT,
+        write_ahead_log: WriteAheadLogConfig::default(),
+        evidence_store: EvidenceStore::default(),
     };
     // Start at height 1.
     tokio::spawn(async move {
@@ -235,10 +258,10 @@ async fn test_timeouts() {
     let mut context = MockTestContext::new();
     context.expect_set_height_and_round().returning(move |_, _| ());
     expect_validate_proposal(&mut context, Felt::ONE, 2);
-    context
-        .expect_validators()
-        .returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_validators().returning(move |_| {
+        Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2, *VALIDATOR_ID_3]))
+    });
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_try_sync().returning(|_| false);
 
     let (timeout_send, timeout_receive) = oneshot::channel();
@@ -259,6 +282,8 @@ async fn test_timeouts() {
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
         FUTURE_MSG_LIMIT,
+        None,
+        EvidenceStore::default(),
     );
     let manager_handle = tokio::spawn(async move {
         let decision = manager
@@ -303,7 +328,8 @@ async fn timely_message_handling() {
     let (mut proposal_receiver_sender, mut proposal_receiver_receiver) = mpsc::channel(0);
     let (mut content_sender, content_receiver) = mpsc::channel(0);
     content_sender.try_send(TestProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID))).unwrap();
-    proposal_receiver_sender.try_send(content_receiver).unwrap();
+    let proposal_metadata = BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
+    proposal_receiver_sender.try_send((proposal_metadata, content_receiver)).unwrap();
 
     // Fill up the sender.
     let TestSubscriberChannels { mock_network, subscriber_channels } =
@@ -321,6 +347,8 @@ async fn timely_message_handling() {
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
         FUTURE_MSG_LIMIT,
+        None,
+        EvidenceStore::default(),
     );
     let res = manager
         .run_height(
@@ -335,7 +363,7 @@ async fn timely_message_handling() {
 
     // Try sending another proposal, to check that, even though sync was known at the beginning of
     // the height and so consensus was not actually run, the inbound channels are cleared.
-    proposal_receiver_sender.try_send(mpsc::channel(1).1).unwrap();
+    proposal_receiver_sender.try_send((metadata.clone(), mpsc::channel(1).1)).unwrap();
     assert!(vote_sender.send((vote.clone(), metadata.clone())).now_or_never().is_some());
 }
 
@@ -384,8 +412,10 @@ async fn future_height_limit_caching_and_dropping() {
     context.expect_try_sync().returning(|_| false);
     expect_validate_proposal(&mut context, Felt::ZERO, 1); // Height 0 validation
     expect_validate_proposal(&mut context, Felt::ONE, 1); // Height 1 validation
-    context.expect_validators().returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID]);
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context
+        .expect_validators()
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_set_height_and_round().returning(move |_, _| ());
     // Set up coordination to detect when node votes Nil for height 2 (indicating proposal was
     // dropped, so the node didn't received the proposal and votes Nil).
@@ -411,6 +441,8 @@ async fn future_height_limit_caching_and_dropping() {
             future_round_limit: LOW_ROUND_LIMIT,
             future_height_round_limit: LOW_HEIGHT_ROUND_LIMIT,
         },
+        None,
+        EvidenceStore::default(),
     );
     let mut subscriber_channels = subscriber_channels.into();
 
@@ -509,8 +541,8 @@ async fn current_height_round_limit_caching_and_dropping() {
     expect_validate_proposal(&mut context, Felt::ONE, 2);
     context
         .expect_validators()
-        .returning(move |_| vec![*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2]);
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID, *VALIDATOR_ID_2])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_broadcast().returning(move |_| Ok(()));
 
     // Set up coordination for round advancement.
@@ -544,6 +576,8 @@ async fn current_height_round_limit_caching_and_dropping() {
             future_round_limit: LOW_ROUND_LIMIT,
             future_height_round_limit: HEIGHT_ROUND_LIMIT,
         },
+        None,
+        EvidenceStore::default(),
     );
     let mut subscriber_channels = subscriber_channels.into();
 
@@ -593,3 +627,241 @@ async fn current_height_round_limit_caching_and_dropping() {
         .unwrap();
     assert_decision(decision, Felt::ONE, 2);
 }
+
+#[tokio::test]
+async fn drop_messages_not_signed_by_sender() {
+    let TestSubscriberChannels { mut mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut sender = mock_network.broadcasted_messages_sender;
+
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    // A proposal and a vote which claim to be from the proposer, but aren't signed by it. Had they
+    // been accepted, the proposer's real messages would have been ignored.
+    let mut rng = get_rng();
+    let (mut proposal_sender, proposal_receiver) = mpsc::channel(CHANNEL_SIZE);
+    let forged_proposal_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    proposal_receiver_sender
+        .send((forged_proposal_metadata.clone(), proposal_receiver))
+        .await
+        .unwrap();
+    proposal_sender.send(TestProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID))).await.unwrap();
+    let forged_precommit = Vote {
+        voter: *PROPOSER_ID,
+        ..sign_vote(precommit(Some(Felt::TWO), 1, 0, *VALIDATOR_ID_2)).await
+    };
+    let forged_vote_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    sender.send((forged_precommit, forged_vote_metadata.clone())).await.unwrap();
+
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![TestProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID))],
+    )
+    .await;
+    send(&mut sender, prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+    send(&mut sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+
+    let mut context = MockTestContext::new();
+    context.expect_try_sync().returning(|_| false);
+    context
+        .expect_validate_proposal()
+        .withf(|init, _, _| init.signature != Signature::default())
+        .times(1)
+        .returning(move |_, _, _| {
+            let (block_sender, block_receiver) = oneshot::channel();
+            block_sender.send(BlockHash(Felt::ONE)).unwrap();
+            block_receiver
+        });
+    context
+        .expect_validators()
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        SYNC_RETRY_INTERVAL,
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+        FUTURE_MSG_LIMIT,
+        None,
+        EvidenceStore::default(),
+    );
+    let mut subscriber_channels = subscriber_channels.into();
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await
+        .unwrap();
+    assert_decision(decision, Felt::ONE, 0);
+
+    // The senders of both the forged proposal and the forged vote are reported.
+    let mut reported_peers = HashSet::new();
+    while let Some(Some(peer_id)) = mock_network.reported_messages_receiver.next().now_or_never() {
+        reported_peers.insert(peer_id);
+    }
+    assert_eq!(
+        reported_peers,
+        HashSet::from([
+            forged_proposal_metadata.originator_id.private_get_peer_id(),
+            forged_vote_metadata.originator_id.private_get_peer_id(),
+        ])
+    );
+}
+
+#[tokio::test]
+async fn equivocating_voter_is_recorded_and_reported() {
+    let TestSubscriberChannels { mut mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let mut sender = mock_network.broadcasted_messages_sender;
+
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    send_proposal(
+        &mut proposal_receiver_sender,
+        vec![TestProposalPart::Init(proposal_init(1, 0, *PROPOSER_ID))],
+    )
+    .await;
+    let first = sign_vote(prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+    let second = sign_vote(prevote(Some(Felt::TWO), 1, 0, *PROPOSER_ID)).await;
+    let second_metadata = BroadcastedMessageMetadata::get_test_instance(&mut get_rng());
+    sender
+        .send((first.clone(), BroadcastedMessageMetadata::get_test_instance(&mut get_rng())))
+        .await
+        .unwrap();
+    sender.send((second.clone(), second_metadata.clone())).await.unwrap();
+    send(&mut sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+
+    let mut context = MockTestContext::new();
+    context.expect_try_sync().returning(|_| false);
+    expect_validate_proposal(&mut context, Felt::ONE, 1);
+    context
+        .expect_validators()
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let evidence_store = EvidenceStore::default();
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        SYNC_RETRY_INTERVAL,
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+        FUTURE_MSG_LIMIT,
+        None,
+        evidence_store.clone(),
+    );
+    let mut subscriber_channels = subscriber_channels.into();
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await
+        .unwrap();
+    // The conflicting vote is ignored.
+    assert_decision(decision, Felt::ONE, 0);
+
+    assert_eq!(
+        evidence_store.evidence(),
+        vec![EquivocationEvidence::ConflictingVotes { first, second }]
+    );
+    assert_eq!(
+        mock_network.reported_messages_receiver.next().now_or_never(),
+        Some(Some(second_metadata.originator_id.private_get_peer_id()))
+    );
+    assert!(mock_network.reported_messages_receiver.next().now_or_never().is_none());
+}
+
+#[tokio::test]
+async fn equivocating_proposer_is_recorded_and_reported() {
+    let TestSubscriberChannels { mock_network, subscriber_channels } =
+        mock_register_broadcast_topic().unwrap();
+    let sender = mock_network.broadcasted_messages_sender;
+
+    let (mut proposal_receiver_sender, mut proposal_receiver_receiver) =
+        mpsc::channel(CHANNEL_SIZE);
+
+    let first = sign_proposal_init(proposal_init(1, 0, *PROPOSER_ID)).await;
+    let second = sign_proposal_init(ProposalInit { valid_round: Some(0), ..first }).await;
+    let mut rng = get_rng();
+    let second_metadata = BroadcastedMessageMetadata::get_test_instance(&mut rng);
+    for (init, metadata) in [
+        (first, BroadcastedMessageMetadata::get_test_instance(&mut rng)),
+        (second, second_metadata.clone()),
+    ] {
+        let (mut proposal_sender, proposal_receiver) = mpsc::channel(CHANNEL_SIZE);
+        proposal_receiver_sender.send((metadata, proposal_receiver)).await.unwrap();
+        proposal_sender.send(TestProposalPart::Init(init)).await.unwrap();
+    }
+
+    // The height can't be decided before the proposer's votes are sent, which is only once the
+    // sender of the conflicting proposal is reported, or the report timed out.
+    let mut reported_messages_receiver = mock_network.reported_messages_receiver;
+    let mut votes_sender = sender.clone();
+    let votes_task = tokio::spawn(async move {
+        let reported_peer =
+            tokio::time::timeout(Duration::from_secs(1), reported_messages_receiver.next())
+                .await
+                .ok()
+                .flatten();
+        send(&mut votes_sender, prevote(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+        send(&mut votes_sender, precommit(Some(Felt::ONE), 1, 0, *PROPOSER_ID)).await;
+        reported_peer
+    });
+
+    let mut context = MockTestContext::new();
+    context.expect_try_sync().returning(|_| false);
+    expect_validate_proposal(&mut context, Felt::ONE, 1);
+    context
+        .expect_validators()
+        .returning(move |_| Ok(equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID])));
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_broadcast().returning(move |_| Ok(()));
+
+    let evidence_store = EvidenceStore::default();
+    let mut manager = MultiHeightManager::new(
+        *VALIDATOR_ID,
+        SYNC_RETRY_INTERVAL,
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+        FUTURE_MSG_LIMIT,
+        None,
+        evidence_store.clone(),
+    );
+    let mut subscriber_channels = subscriber_channels.into();
+    let decision = manager
+        .run_height(
+            &mut context,
+            BlockNumber(1),
+            false,
+            &mut subscriber_channels,
+            &mut proposal_receiver_receiver,
+        )
+        .await
+        .unwrap();
+    // The conflicting proposal is ignored.
+    assert_decision(decision, Felt::ONE, 0);
+
+    assert_eq!(
+        evidence_store.evidence(),
+        vec![EquivocationEvidence::ConflictingProposals { first, second }]
+    );
+    assert_eq!(
+        votes_task.await.unwrap(),
+        Some(second_metadata.originator_id.private_get_peer_id())
+    );
+}
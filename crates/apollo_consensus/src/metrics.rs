@@ -29,6 +29,7 @@ define_metrics!(
         // TODO(Matan): remove this metric.
         MetricCounter { CONSENSUS_ROUND_ABOVE_ZERO, "consensus_round_above_zero", "The number of times the consensus round has increased above zero", init=0 },
         MetricCounter { CONSENSUS_CONFLICTING_VOTES, "consensus_conflicting_votes", "The number of times consensus has received conflicting votes", init=0 },
+        LabeledMetricCounter { CONSENSUS_EQUIVOCATIONS, "consensus_equivocations", "The number of equivocations detected, by the type of the conflicting messages", init=0, labels = CONSENSUS_EQUIVOCATION_LABELS },
         LabeledMetricCounter { CONSENSUS_TIMEOUTS, "consensus_timeouts", "The number of times consensus has timed out", init=0, labels = CONSENSUS_TIMEOUT_LABELS },
     },
 );
@@ -48,6 +49,21 @@ generate_permutation_labels! {
     (LABEL_NAME_TIMEOUT_REASON, TimeoutReason),
 }
 
+pub const LABEL_NAME_EQUIVOCATION_TYPE: &str = "equivocation_type";
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, IntoStaticStr, EnumIter, EnumVariantNames)]
+#[strum(serialize_all = "snake_case")]
+pub enum EquivocationType {
+    Prevote,
+    Precommit,
+    Proposal,
+}
+
+generate_permutation_labels! {
+    CONSENSUS_EQUIVOCATION_LABELS,
+    (LABEL_NAME_EQUIVOCATION_TYPE, EquivocationType),
+}
+
 pub(crate) fn register_metrics() {
     CONSENSUS_BLOCK_NUMBER.register();
     CONSENSUS_ROUND.register();
@@ -71,5 +87,6 @@ pub(crate) fn register_metrics() {
     CONSENSUS_OUTBOUND_STREAM_FINISHED.register();
     CONSENSUS_ROUND_ABOVE_ZERO.register();
     CONSENSUS_CONFLICTING_VOTES.register();
+    CONSENSUS_EQUIVOCATIONS.register();
     CONSENSUS_TIMEOUTS.register();
 }
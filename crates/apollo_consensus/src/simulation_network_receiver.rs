@@ -13,7 +13,7 @@ use apollo_protobuf::consensus::Vote;
 use apollo_protobuf::converters::ProtobufConversionError;
 use futures::{Stream, StreamExt};
 use lru::LruCache;
-use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_types_core::felt::Felt;
 use tracing::{debug, instrument};
 
 /// Receiver which can simulate network issues in a repeatable manner. Simulates drops and network
@@ -107,8 +107,7 @@ impl NetworkReceiver {
             return msg;
         }
         debug!("Invalidating message");
-        // TODO(matan): Allow for invalid votes based on signature.
-        msg.voter = ContractAddress(PatriciaKey::from(msg_hash));
+        msg.signature.r = Felt::from(msg_hash);
         msg
     }
 }
@@ -21,6 +21,7 @@ use starknet_api::block::BlockNumber;
 use tracing::{debug, info, instrument, trace, warn};
 
 use crate::config::TimeoutsConfig;
+use crate::evidence::EquivocationEvidence;
 use crate::metrics::{
     CONSENSUS_BUILD_PROPOSAL_FAILED,
     CONSENSUS_BUILD_PROPOSAL_TOTAL,
@@ -38,8 +39,10 @@ use crate::types::{
     ProposalCommitment,
     Round,
     ValidatorId,
+    ValidatorSet,
 };
 use crate::votes_threshold::QuorumType;
+use crate::write_ahead_log::{HeightWriteAheadLog, RecoveredHeight, WalEntry};
 
 /// The SHC can either update the manager of a decision or return tasks that should be run without
 /// blocking further calls to itself.
@@ -128,20 +131,31 @@ impl ShcTask {
 ///
 /// Each height is begun with a call to `start`, with no further calls to it.
 ///
+/// If a write-ahead log is set, the votes, proposals and lock of this node are logged before they
+/// are sent, and the progress recovered from the log is resumed by `start`.
+///
 /// SHC is not a top level task, it is called directly and returns values (doesn't directly run sub
 /// tasks). SHC does have side effects, such as sending messages to the network via the context.
 #[derive(Serialize, Deserialize)]
 pub(crate) struct SingleHeightConsensus {
     height: BlockNumber,
-    validators: Vec<ValidatorId>,
+    validators: ValidatorSet,
     id: ValidatorId,
     timeouts: TimeoutsConfig,
     state_machine: StateMachine,
     proposals: HashMap<Round, Option<ProposalCommitment>>,
+    // The proposal inits received from other validators, kept to detect conflicting proposals.
+    proposal_inits: HashMap<Round, ProposalInit>,
     prevotes: HashMap<(Round, ValidatorId), Vote>,
     precommits: HashMap<(Round, ValidatorId), Vote>,
     last_prevote: Option<Vote>,
     last_precommit: Option<Vote>,
+    // Equivocations detected since they were last taken by the manager.
+    equivocations: Vec<EquivocationEvidence>,
+    #[serde(skip)]
+    write_ahead_log: Option<HeightWriteAheadLog>,
+    #[serde(skip)]
+    recovered_height: Option<RecoveredHeight>,
 }
 
 impl SingleHeightConsensus {
@@ -149,14 +163,16 @@ impl SingleHeightConsensus {
         height: BlockNumber,
         is_observer: bool,
         id: ValidatorId,
-        validators: Vec<ValidatorId>,
+        validators: ValidatorSet,
         quorum_type: QuorumType,
         timeouts: TimeoutsConfig,
     ) -> Self {
-        // TODO(matan): Use actual weights, not just `len`.
-        let n_validators =
-            u64::try_from(validators.len()).expect("Should have way less than u64::MAX validators");
-        let state_machine = StateMachine::new(id, n_validators, is_observer, quorum_type);
+        let total_weight = validators
+            .values()
+            .try_fold(0_u128, |total, validator| total.checked_add(validator.weight.0))
+            .expect("Total voting weight should fit in u128.");
+        let weight = validators.get(&id).map_or(0, |validator| validator.weight.0);
+        let state_machine = StateMachine::new(id, total_weight, weight, is_observer, quorum_type);
         Self {
             height,
             validators,
@@ -164,26 +180,65 @@ impl SingleHeightConsensus {
             timeouts,
             state_machine,
             proposals: HashMap::new(),
+            proposal_inits: HashMap::new(),
             prevotes: HashMap::new(),
             precommits: HashMap::new(),
             last_prevote: None,
             last_precommit: None,
+            equivocations: Vec::new(),
+            write_ahead_log: None,
+            recovered_height: None,
         }
     }
 
+    /// Must be called before `start`. `recovered_height` is the progress logged for this height
+    /// before a restart, if any.
+    pub(crate) fn set_write_ahead_log(
+        &mut self,
+        write_ahead_log: HeightWriteAheadLog,
+        recovered_height: Option<RecoveredHeight>,
+    ) {
+        self.write_ahead_log = Some(write_ahead_log);
+        self.recovered_height = recovered_height;
+    }
+
     pub(crate) fn current_round(&self) -> Round {
         self.state_machine.round()
     }
 
+    pub(crate) fn validators(&self) -> &ValidatorSet {
+        &self.validators
+    }
+
+    /// Returns the equivocations detected since the last call.
+    pub(crate) fn take_equivocations(&mut self) -> Vec<EquivocationEvidence> {
+        std::mem::take(&mut self.equivocations)
+    }
+
     #[instrument(skip_all)]
     pub(crate) async fn start<ContextT: ConsensusContext>(
         &mut self,
         context: &mut ContextT,
     ) -> Result<ShcReturn, ConsensusError> {
         context.set_height_and_round(self.height, self.state_machine.round()).await;
-        let leader_fn = |round: Round| -> ValidatorId { context.proposer(self.height, round) };
-        let events = self.state_machine.start(&leader_fn);
-        let ret = self.handle_state_machine_events(context, events).await;
+        let leader_fn = |round: Round| context.proposer(self.height, round);
+        let ret = match self.recovered_height.take() {
+            Some(recovered_height) => {
+                let events = self.state_machine.resume(&recovered_height, &leader_fn);
+                let rebroadcast_tasks = self.restore_own_messages(recovered_height);
+                match self.handle_state_machine_events(context, events).await {
+                    Ok(ShcReturn::Tasks(mut tasks)) => {
+                        tasks.extend(rebroadcast_tasks);
+                        Ok(ShcReturn::Tasks(tasks))
+                    }
+                    ret => ret,
+                }
+            }
+            None => {
+                let events = self.state_machine.start(&leader_fn);
+                self.handle_state_machine_events(context, events).await
+            }
+        };
         // Defensive programming. We don't expect the height and round to have changed from the
         // start of this method.
         context.set_height_and_round(self.height, self.state_machine.round()).await;
@@ -200,15 +255,33 @@ impl SingleHeightConsensus {
         p2p_messages_receiver: mpsc::Receiver<ContextT::ProposalPart>,
     ) -> Result<ShcReturn, ConsensusError> {
         debug!("Received {init:?}");
-        let proposer_id = context.proposer(self.height, init.round);
         if init.height != self.height {
             warn!("Invalid proposal height: expected {:?}, got {:?}", self.height, init.height);
             return Ok(ShcReturn::Tasks(Vec::new()));
         }
+        let proposer_id = match context.proposer(self.height, init.round) {
+            Ok(proposer_id) => proposer_id,
+            Err(e) => {
+                warn!("Dropping a proposal whose proposer can't be determined: {e}. {init:?}");
+                return Ok(ShcReturn::Tasks(Vec::new()));
+            }
+        };
         if init.proposer != proposer_id {
             warn!("Invalid proposer: expected {:?}, got {:?}", proposer_id, init.proposer);
             return Ok(ShcReturn::Tasks(Vec::new()));
         }
+        if let Some(first) = self.proposal_inits.get(&init.round) {
+            // Only the init of a proposal is signed by its proposer, so a proposal provably
+            // conflicts with another only if their inits differ.
+            let is_repeated = ProposalInit { signature: init.signature, ..*first } == init;
+            if !is_repeated {
+                warn!("Conflicting proposals: first={first:?}, second={init:?}");
+                self.equivocations.push(EquivocationEvidence::ConflictingProposals {
+                    first: *first,
+                    second: init,
+                });
+            }
+        }
         let Entry::Vacant(proposal_entry) = self.proposals.entry(init.round) else {
             warn!("Round {} already has a proposal, ignoring", init.round);
             return Ok(ShcReturn::Tasks(Vec::new()));
@@ -223,6 +296,7 @@ impl SingleHeightConsensus {
         // Since validating the proposal is non-blocking, we want to avoid validating the same round
         // twice in parallel. This could be caused by a network repeat or a malicious spam attack.
         proposal_entry.insert(None);
+        self.proposal_inits.insert(init.round, init);
         let block_receiver = context.validate_proposal(init, timeout, p2p_messages_receiver).await;
         context.set_height_and_round(self.height, self.state_machine.round()).await;
         Ok(ShcReturn::Tasks(vec![ShcTask::ValidateProposal(init, block_receiver)]))
@@ -276,10 +350,9 @@ impl SingleHeightConsensus {
                 )]))
             }
             StateMachineEvent::Proposal(proposal_id, round, valid_round) => {
-                let leader_fn =
-                    |round: Round| -> ValidatorId { context.proposer(self.height, round) };
+                let leader_fn = |round: Round| context.proposer(self.height, round);
                 debug!(
-                    proposer = %leader_fn(round),
+                    proposer = ?leader_fn(round),
                     %round,
                     ?valid_round,
                     proposal_commitment = ?proposal_id,
@@ -318,8 +391,7 @@ impl SingleHeightConsensus {
                     "State machine should not progress while awaiting proposal"
                 );
                 debug!(%round, proposal_commitment = ?proposal_id, "Built proposal.");
-                let leader_fn =
-                    |round: Round| -> ValidatorId { context.proposer(self.height, round) };
+                let leader_fn = |round: Round| context.proposer(self.height, round);
                 let sm_events = self
                     .state_machine
                     .handle_event(StateMachineEvent::GetProposal(proposal_id, round), &leader_fn);
@@ -336,7 +408,7 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
         event: StateMachineEvent,
     ) -> Result<ShcReturn, ConsensusError> {
-        let leader_fn = |round: Round| -> ValidatorId { context.proposer(self.height, round) };
+        let leader_fn = |round: Round| context.proposer(self.height, round);
         let sm_events = self.state_machine.handle_event(event, &leader_fn);
         self.handle_state_machine_events(context, sm_events).await
     }
@@ -349,10 +421,10 @@ impl SingleHeightConsensus {
         vote: Vote,
     ) -> Result<ShcReturn, ConsensusError> {
         trace!("Received {:?}", vote);
-        if !self.validators.contains(&vote.voter) {
+        let Some(validator) = self.validators.get(&vote.voter).copied() else {
             debug!("Ignoring vote from non validator: vote={:?}", vote);
             return Ok(ShcReturn::Tasks(Vec::new()));
-        }
+        };
 
         let (votes, sm_vote) = match vote.vote_type {
             VoteType::Prevote => {
@@ -372,6 +444,10 @@ impl SingleHeightConsensus {
                 if old.block_hash != vote.block_hash {
                     warn!("Conflicting votes: old={:?}, new={:?}", old, vote);
                     CONSENSUS_CONFLICTING_VOTES.increment(1);
+                    self.equivocations.push(EquivocationEvidence::ConflictingVotes {
+                        first: old.clone(),
+                        second: vote,
+                    });
                     return Ok(ShcReturn::Tasks(Vec::new()));
                 } else {
                     // Replay, ignore.
@@ -380,31 +456,81 @@ impl SingleHeightConsensus {
             }
         }
         info!("Accepting {:?}", vote);
-        let leader_fn = |round: Round| -> ValidatorId { context.proposer(self.height, round) };
-        let sm_events = self.state_machine.handle_event(sm_vote, &leader_fn);
+        let leader_fn = |round: Round| context.proposer(self.height, round);
+        let sm_events = self.state_machine.handle_vote(sm_vote, validator.weight.0, &leader_fn);
         let ret = self.handle_state_machine_events(context, sm_events).await;
         context.set_height_and_round(self.height, self.state_machine.round()).await;
         ret
     }
 
+    // Restores the votes and proposals this node sent before a restart. Returns the tasks which
+    // rebroadcast the latest votes, so that peers which missed them can still count them.
+    fn restore_own_messages(&mut self, recovered_height: RecoveredHeight) -> Vec<ShcTask> {
+        for vote in recovered_height.votes {
+            let (votes, last_vote) = match vote.vote_type {
+                VoteType::Prevote => (&mut self.prevotes, &mut self.last_prevote),
+                VoteType::Precommit => (&mut self.precommits, &mut self.last_precommit),
+            };
+            if last_vote.as_ref().is_none_or(|last_vote| last_vote.round < vote.round) {
+                *last_vote = Some(vote.clone());
+            }
+            votes.insert((vote.round, vote.voter), vote);
+        }
+        // The content of these proposals was lost, so the rounds can't be decided locally. The
+        // entries keep the rounds from accepting another proposal.
+        for round in recovered_height.proposal_rounds {
+            self.proposals.insert(round, None);
+        }
+
+        let mut tasks = Vec::new();
+        if let Some(vote) = &self.last_prevote {
+            tasks.push(ShcTask::Prevote(
+                Duration::ZERO,
+                StateMachineEvent::Prevote(vote.block_hash, vote.round),
+            ));
+        }
+        if let Some(vote) = &self.last_precommit {
+            tasks.push(ShcTask::Precommit(
+                Duration::ZERO,
+                StateMachineEvent::Precommit(vote.block_hash, vote.round),
+            ));
+        }
+        tasks
+    }
+
+    fn write_ahead(&mut self, entry: WalEntry) -> Result<(), ConsensusError> {
+        match &mut self.write_ahead_log {
+            Some(write_ahead_log) => write_ahead_log.append(&entry),
+            None => Ok(()),
+        }
+    }
+
     // Handle events output by the state machine.
     async fn handle_state_machine_events<ContextT: ConsensusContext>(
         &mut self,
         context: &mut ContextT,
         mut events: VecDeque<StateMachineEvent>,
     ) -> Result<ShcReturn, ConsensusError> {
+        // The state machine already advanced its round and lock, log them before sending anything
+        // which depends on them.
+        if let Some(write_ahead_log) = &mut self.write_ahead_log {
+            write_ahead_log.log_progress(
+                self.state_machine.round(),
+                self.state_machine.locked_value_round(),
+            )?;
+        }
         let mut ret_val = Vec::new();
         while let Some(event) = events.pop_front() {
             trace!("Handling sm event: {:?}", event);
             match event {
                 StateMachineEvent::GetProposal(proposal_id, round) => {
                     ret_val.extend(
-                        self.handle_state_machine_get_proposal(context, proposal_id, round).await,
+                        self.handle_state_machine_get_proposal(context, proposal_id, round).await?,
                     );
                 }
                 StateMachineEvent::Proposal(proposal_id, round, valid_round) => {
                     self.handle_state_machine_proposal(context, proposal_id, round, valid_round)
-                        .await;
+                        .await?;
                 }
                 StateMachineEvent::Decision(proposal_id, round) => {
                     return self.handle_state_machine_decision(proposal_id, round).await;
@@ -452,7 +578,7 @@ impl SingleHeightConsensus {
         context: &mut ContextT,
         proposal_id: Option<ProposalCommitment>,
         round: Round,
-    ) -> Vec<ShcTask> {
+    ) -> Result<Vec<ShcTask>, ConsensusError> {
         assert!(
             proposal_id.is_none(),
             "StateMachine is requesting a new proposal, but provided a content id."
@@ -460,11 +586,22 @@ impl SingleHeightConsensus {
 
         // TODO(Matan): Figure out how to handle failed proposal building. I believe this should be
         // handled by applying timeoutPropose when we are the leader.
-        let init =
-            ProposalInit { height: self.height, round, proposer: self.id, valid_round: None };
+        self.write_ahead(WalEntry::Proposal {
+            round,
+            proposal_commitment: None,
+            valid_round: None,
+        })?;
+        // The context signs the init when it streams the proposal.
+        let init = ProposalInit {
+            height: self.height,
+            round,
+            proposer: self.id,
+            valid_round: None,
+            signature: Default::default(),
+        };
         CONSENSUS_BUILD_PROPOSAL_TOTAL.increment(1);
         let fin_receiver = context.build_proposal(init, self.timeouts.proposal_timeout).await;
-        vec![ShcTask::BuildProposal(round, fin_receiver)]
+        Ok(vec![ShcTask::BuildProposal(round, fin_receiver)])
     }
 
     async fn handle_state_machine_proposal<ContextT: ConsensusContext>(
@@ -473,10 +610,15 @@ impl SingleHeightConsensus {
         proposal_id: Option<ProposalCommitment>,
         round: Round,
         valid_round: Option<Round>,
-    ) {
+    ) -> Result<(), ConsensusError> {
+        self.write_ahead(WalEntry::Proposal {
+            round,
+            proposal_commitment: proposal_id,
+            valid_round,
+        })?;
         let Some(valid_round) = valid_round else {
             // Newly built proposals are handled by the BuildProposal flow.
-            return;
+            return Ok(());
         };
         let proposal_id = proposal_id.expect("Reproposal must have a valid ID");
 
@@ -495,9 +637,11 @@ impl SingleHeightConsensus {
             round,
             proposer: self.id,
             valid_round: Some(valid_round),
+            signature: Default::default(),
         };
         CONSENSUS_REPROPOSALS.increment(1);
         context.repropose(id, init).await;
+        Ok(())
     }
 
     async fn handle_state_machine_vote<ContextT: ConsensusContext>(
@@ -531,6 +675,8 @@ impl SingleHeightConsensus {
             round,
             block_hash: proposal_id,
             voter: self.id,
+            // The context signs the vote when it broadcasts it.
+            signature: Default::default(),
         };
         if let Some(old) = votes.insert((round, self.id), vote.clone()) {
             return Err(ConsensusError::InternalInconsistency(format!(
@@ -551,6 +697,7 @@ impl SingleHeightConsensus {
             }
         };
 
+        self.write_ahead(WalEntry::Vote(vote.clone()))?;
         info!("Broadcasting {vote:?}");
         context.broadcast(vote).await?;
         Ok(vec![task])
@@ -582,16 +729,15 @@ impl SingleHeightConsensus {
         }
         let supporting_precommits: Vec<Vote> = self
             .validators
-            .iter()
+            .keys()
             .filter_map(|v| {
                 let vote = self.precommits.get(&(round, *v))?;
                 if vote.block_hash == Some(proposal_id) { Some(vote.clone()) } else { None }
             })
             .collect();
 
-        // TODO(matan): Check actual weights.
-        let vote_weight = u64::try_from(supporting_precommits.len())
-            .expect("Should have way less than u64::MAX supporting votes");
+        let vote_weight: u128 =
+            supporting_precommits.iter().map(|vote| self.validators[&vote.voter].weight.0).sum();
         let total_weight = self.state_machine.total_weight();
 
         if !self.state_machine.quorum().is_met(vote_weight, total_weight) {
@@ -1,26 +1,39 @@
+use std::time::Duration;
+
 use apollo_protobuf::consensus::{ProposalFin, ProposalInit, Vote, DEFAULT_VALIDATOR_ID};
 use futures::channel::{mpsc, oneshot};
 use futures::SinkExt;
 use lazy_static::lazy_static;
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_types_core::felt::Felt;
+use tempfile::tempdir;
 use test_case::test_case;
 
 use super::SingleHeightConsensus;
 use crate::config::TimeoutsConfig;
+use crate::evidence::EquivocationEvidence;
 use crate::single_height_consensus::{ShcReturn, ShcTask};
 use crate::state_machine::StateMachineEvent;
-use crate::test_utils::{precommit, prevote, MockTestContext, TestBlock, TestProposalPart};
-use crate::types::ValidatorId;
+use crate::test_utils::{
+    equally_weighted,
+    precommit,
+    prevote,
+    weighted,
+    MockTestContext,
+    TestBlock,
+    TestProposalPart,
+};
+use crate::types::{ValidatorId, ValidatorSet};
 use crate::votes_threshold::QuorumType;
+use crate::write_ahead_log::WriteAheadLog;
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
     static ref VALIDATOR_ID_1: ValidatorId = (DEFAULT_VALIDATOR_ID + 1).into();
     static ref VALIDATOR_ID_2: ValidatorId = (DEFAULT_VALIDATOR_ID + 2).into();
     static ref VALIDATOR_ID_3: ValidatorId = (DEFAULT_VALIDATOR_ID + 3).into();
-    static ref VALIDATORS: Vec<ValidatorId> =
-        vec![*PROPOSER_ID, *VALIDATOR_ID_1, *VALIDATOR_ID_2, *VALIDATOR_ID_3];
+    static ref VALIDATORS: ValidatorSet =
+        equally_weighted(&[*PROPOSER_ID, *VALIDATOR_ID_1, *VALIDATOR_ID_2, *VALIDATOR_ID_3]);
     static ref BLOCK: TestBlock = TestBlock { content: vec![1, 2, 3], id: BlockHash(Felt::ONE) };
     static ref PROPOSAL_INIT: ProposalInit =
         ProposalInit { proposer: *PROPOSER_ID, ..Default::default() };
@@ -30,7 +43,8 @@ lazy_static! {
         PROPOSAL_INIT.round,
         PROPOSAL_INIT.valid_round,
     );
-    static ref PROPOSAL_FIN: ProposalFin = ProposalFin { proposal_commitment: BLOCK.id };
+    static ref PROPOSAL_FIN: ProposalFin =
+        ProposalFin { proposal_commitment: BLOCK.id, signature: Default::default() };
 }
 
 const CHANNEL_SIZE: usize = 1;
@@ -79,12 +93,12 @@ async fn proposer() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
     );
 
-    context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
+    context.expect_proposer().times(1).returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_build_proposal().times(1).returning(move |_, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send(BLOCK.id).unwrap();
@@ -155,12 +169,12 @@ async fn validator(repeat_proposal: bool) {
         BlockNumber(0),
         false,
         *VALIDATOR_ID_1,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
     );
 
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_validate_proposal().times(1).returning(move |_, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send(BLOCK.id).unwrap();
@@ -227,12 +241,12 @@ async fn vote_twice(same_vote: bool) {
         BlockNumber(0),
         false,
         *VALIDATOR_ID_1,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
     );
 
-    context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
+    context.expect_proposer().times(1).returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_validate_proposal().times(1).returning(move |_, _, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send(BLOCK.id).unwrap();
@@ -293,12 +307,12 @@ async fn rebroadcast_votes() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
     );
 
-    context.expect_proposer().times(1).returning(move |_, _| *PROPOSER_ID);
+    context.expect_proposer().times(1).returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_build_proposal().times(1).returning(move |_, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send(BLOCK.id).unwrap();
@@ -349,12 +363,12 @@ async fn repropose() {
         BlockNumber(0),
         false,
         *PROPOSER_ID,
-        VALIDATORS.to_vec(),
+        VALIDATORS.clone(),
         QuorumType::Byzantine,
         TIMEOUTS.clone(),
     );
 
-    context.expect_proposer().returning(move |_, _| *PROPOSER_ID);
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
     context.expect_build_proposal().times(1).returning(move |_, _| {
         let (block_sender, block_receiver) = oneshot::channel();
         block_sender.send(BLOCK.id).unwrap();
@@ -418,3 +432,208 @@ async fn repropose() {
     assert_eq!(decision.block, BLOCK.id);
     assert!(decision.precommits.into_iter().all(|item| precommits.contains(&item)));
 }
+
+#[tokio::test]
+async fn resume_after_restart() {
+    let dir = tempdir().unwrap();
+    let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+    let new_shc = || {
+        let mut shc = SingleHeightConsensus::new(
+            BlockNumber(0),
+            false,
+            *VALIDATOR_ID_1,
+            VALIDATORS.clone(),
+            QuorumType::Byzantine,
+            TIMEOUTS.clone(),
+        );
+        let (height_log, recovered_height) = write_ahead_log.open_height(BlockNumber(0)).unwrap();
+        shc.set_write_ahead_log(height_log, recovered_height);
+        shc
+    };
+    let mut context = MockTestContext::new();
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_validate_proposal().times(1).returning(move |_, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send(BLOCK.id).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &Vote| msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+
+    let mut shc = new_shc();
+    assert_eq!(
+        shc.start(&mut context).await,
+        Ok(ShcReturn::Tasks(vec![ShcTask::TimeoutPropose(
+            TIMEOUTS.proposal_timeout,
+            StateMachineEvent::TimeoutPropose(0)
+        )]))
+    );
+    handle_proposal(&mut shc, &mut context).await;
+    assert_eq!(
+        shc.handle_event(&mut context, VALIDATE_PROPOSAL_EVENT.clone()).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    // Crash and restart.
+    drop(shc);
+    let mut shc = new_shc();
+    // The prevote is rebroadcast right away, instead of waiting for a new proposal.
+    assert_eq!(
+        shc.start(&mut context).await,
+        Ok(ShcReturn::Tasks(vec![ShcTask::Prevote(
+            Duration::ZERO,
+            StateMachineEvent::Prevote(Some(BLOCK.id), 0)
+        )]))
+    );
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &Vote| msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+    assert_eq!(
+        shc.handle_event(&mut context, StateMachineEvent::Prevote(Some(BLOCK.id), 0)).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
+    );
+    // The node doesn't vote again in the same round and step.
+    assert_eq!(
+        shc.handle_event(&mut context, StateMachineEvent::TimeoutPropose(0)).await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+}
+
+#[tokio::test]
+async fn stake_weighted_decision() {
+    let mut context = MockTestContext::new();
+    // The proposer holds 5 out of 8, which together with any other validator is a quorum.
+    let validators = weighted(&[
+        (*PROPOSER_ID, 5),
+        (*VALIDATOR_ID_1, 1),
+        (*VALIDATOR_ID_2, 1),
+        (*VALIDATOR_ID_3, 1),
+    ]);
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        false,
+        *VALIDATOR_ID_1,
+        validators,
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+    );
+
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_validate_proposal().times(1).returning(move |_, _, _| {
+        let (block_sender, block_receiver) = oneshot::channel();
+        block_sender.send(BLOCK.id).unwrap();
+        block_receiver
+    });
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &Vote| msg == &prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+    handle_proposal(&mut shc, &mut context).await;
+    assert_eq!(
+        shc.handle_event(&mut context, VALIDATE_PROPOSAL_EVENT.clone()).await,
+        Ok(ShcReturn::Tasks(vec![prevote_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    // The light validators alone don't reach a quorum.
+    assert_eq!(
+        shc.handle_vote(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2)).await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+    context
+        .expect_broadcast()
+        .times(1)
+        .withf(move |msg: &Vote| msg == &precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1))
+        .returning(move |_| Ok(()));
+    assert_eq!(
+        shc.handle_vote(&mut context, prevote(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID)).await,
+        Ok(ShcReturn::Tasks(vec![timeout_prevote_task(0), precommit_task(Some(BLOCK.id.0), 0)]))
+    );
+
+    let proposer_precommit = precommit(Some(BLOCK.id.0), 0, 0, *PROPOSER_ID);
+    let ShcReturn::Decision(decision) =
+        shc.handle_vote(&mut context, proposer_precommit.clone()).await.unwrap()
+    else {
+        panic!("Expected decision");
+    };
+    assert_eq!(decision.block, BLOCK.id);
+    assert_eq!(
+        decision.precommits,
+        vec![proposer_precommit, precommit(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_1)]
+    );
+}
+
+#[test_case(prevote; "prevotes")]
+#[test_case(precommit; "precommits")]
+#[tokio::test]
+async fn conflicting_votes_are_evidence(vote: fn(Option<Felt>, u64, u32, ValidatorId) -> Vote) {
+    let mut context = MockTestContext::new();
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        true,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+    );
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    shc.start(&mut context).await.unwrap();
+
+    let first = vote(Some(BLOCK.id.0), 0, 0, *VALIDATOR_ID_2);
+    let second = vote(Some(Felt::TWO), 0, 0, *VALIDATOR_ID_2);
+    shc.handle_vote(&mut context, first.clone()).await.unwrap();
+    // A repeated vote isn't an equivocation.
+    shc.handle_vote(&mut context, first.clone()).await.unwrap();
+    assert_eq!(shc.take_equivocations(), Vec::new());
+
+    assert_eq!(
+        shc.handle_vote(&mut context, second.clone()).await,
+        Ok(ShcReturn::Tasks(Vec::new()))
+    );
+    assert_eq!(
+        shc.take_equivocations(),
+        vec![EquivocationEvidence::ConflictingVotes { first, second }]
+    );
+    assert_eq!(shc.take_equivocations(), Vec::new());
+}
+
+#[tokio::test]
+async fn conflicting_proposals_are_evidence() {
+    let mut context = MockTestContext::new();
+    let mut shc = SingleHeightConsensus::new(
+        BlockNumber(0),
+        true,
+        *VALIDATOR_ID_1,
+        VALIDATORS.clone(),
+        QuorumType::Byzantine,
+        TIMEOUTS.clone(),
+    );
+    context.expect_proposer().returning(move |_, _| Ok(*PROPOSER_ID));
+    context.expect_set_height_and_round().returning(move |_, _| ());
+    context.expect_validate_proposal().times(1).returning(move |_, _, _| {
+        let (_block_sender, block_receiver) = oneshot::channel();
+        block_receiver
+    });
+    shc.start(&mut context).await.unwrap();
+
+    let first = ProposalInit { round: 1, ..*PROPOSAL_INIT };
+    let second = ProposalInit { valid_round: Some(0), ..first };
+    for init in [first, first, second] {
+        let (_content_sender, content_receiver) = mpsc::channel(CHANNEL_SIZE);
+        shc.handle_proposal(&mut context, init, content_receiver).await.unwrap();
+    }
+
+    // Only the conflicting proposal is evidence, not the repeated one, and it isn't validated.
+    assert_eq!(
+        shc.take_equivocations(),
+        vec![EquivocationEvidence::ConflictingProposals { first, second }]
+    );
+}
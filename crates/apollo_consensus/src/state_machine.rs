@@ -9,8 +9,9 @@ mod state_machine_test;
 
 use std::collections::{HashMap, HashSet, VecDeque};
 
+use apollo_protobuf::consensus::VoteType;
 use serde::{Deserialize, Serialize};
-use tracing::{debug, info, trace, warn};
+use tracing::{debug, error, info, trace, warn};
 
 use crate::metrics::{
     TimeoutReason,
@@ -21,8 +22,9 @@ use crate::metrics::{
     CONSENSUS_TIMEOUTS,
     LABEL_NAME_TIMEOUT_REASON,
 };
-use crate::types::{ProposalCommitment, Round, ValidatorId};
+use crate::types::{ConsensusError, ProposalCommitment, Round, ValidatorId};
 use crate::votes_threshold::{QuorumType, VotesThreshold, ROUND_SKIP_THRESHOLD};
+use crate::write_ahead_log::RecoveredHeight;
 
 /// Events which the state machine sends/receives.
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -62,7 +64,10 @@ pub enum Step {
 /// 1. SHC handles: authentication, replays, and conflicts.
 /// 2. SM must handle "out of order" messages (E.g. vote arrives before proposal).
 ///
-/// Each height is begun with a call to `start`, with no further calls to it.
+/// Votes are counted by the voting weight (stake) of their senders, and quorums are thresholds of
+/// the total weight of the validators.
+///
+/// Each height is begun with a call to `start` (or `resume`), with no further calls to it.
 #[derive(Serialize, Deserialize)]
 pub struct StateMachine {
     id: ValidatorId,
@@ -70,17 +75,20 @@ pub struct StateMachine {
     step: Step,
     quorum: VotesThreshold,
     round_skip_threshold: VotesThreshold,
-    total_weight: u64,
+    total_weight: u128,
+    // The voting weight of this node.
+    weight: u128,
     is_observer: bool,
     // {round: (proposal_id, valid_round)}
     proposals: HashMap<Round, (Option<ProposalCommitment>, Option<Round>)>,
-    // {round: {proposal_id: vote_count}
-    prevotes: HashMap<Round, HashMap<Option<ProposalCommitment>, u32>>,
-    precommits: HashMap<Round, HashMap<Option<ProposalCommitment>, u32>>,
+    // {round: {proposal_id: vote_weight}
+    prevotes: HashMap<Round, HashMap<Option<ProposalCommitment>, u128>>,
+    precommits: HashMap<Round, HashMap<Option<ProposalCommitment>, u128>>,
     // When true, the state machine will wait for a GetProposal event, buffering all other input
     // events in `events_queue`.
     awaiting_get_proposal: bool,
-    events_queue: VecDeque<StateMachineEvent>,
+    // Events to handle, each with the voting weight of its sender (only used for votes).
+    events_queue: VecDeque<(StateMachineEvent, u128)>,
     locked_value_round: Option<(ProposalCommitment, Round)>,
     valid_value_round: Option<(ProposalCommitment, Round)>,
     prevote_quorum: HashSet<Round>,
@@ -90,9 +98,11 @@ pub struct StateMachine {
 
 impl StateMachine {
     /// total_weight - the total voting weight of all validators for this height.
+    /// weight - the voting weight of this node.
     pub fn new(
         id: ValidatorId,
-        total_weight: u64,
+        total_weight: u128,
+        weight: u128,
         is_observer: bool,
         quorum_type: QuorumType,
     ) -> Self {
@@ -105,6 +115,7 @@ impl StateMachine {
             // Skip round threshold is 1/3 of the total weight.
             round_skip_threshold: ROUND_SKIP_THRESHOLD,
             total_weight,
+            weight,
             is_observer,
             proposals: HashMap::new(),
             prevotes: HashMap::new(),
@@ -123,7 +134,7 @@ impl StateMachine {
         self.round
     }
 
-    pub fn total_weight(&self) -> u64 {
+    pub fn total_weight(&self) -> u128 {
         self.total_weight
     }
 
@@ -131,17 +142,67 @@ impl StateMachine {
         &self.quorum
     }
 
+    pub fn locked_value_round(&self) -> Option<(ProposalCommitment, Round)> {
+        self.locked_value_round
+    }
+
     /// Starts the state machine, effectively calling `StartRound(0)` from the paper. This is
     /// needed to trigger the first leader to propose.
     /// See [`GetProposal`](StateMachineEvent::GetProposal)
     pub fn start<LeaderFn>(&mut self, leader_fn: &LeaderFn) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         self.advance_to_round(0, leader_fn)
     }
 
-    /// Process the incoming event.
+    /// Resumes a height after a restart, instead of `start`. The state machine continues from the
+    /// round and step it reached, holding the same lock and counting its own votes, so that it
+    /// never votes twice in the same round and step.
+    pub fn resume<LeaderFn>(
+        &mut self,
+        recovered: &RecoveredHeight,
+        leader_fn: &LeaderFn,
+    ) -> VecDeque<StateMachineEvent>
+    where
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
+    {
+        // The valid value isn't recovered, as its content was lost and it can't be reproposed.
+        self.locked_value_round = recovered.locked_value_round;
+        let mut step = Step::Propose;
+        for vote in &recovered.votes {
+            let (votes, vote_step) = match vote.vote_type {
+                VoteType::Prevote => (&mut self.prevotes, Step::Prevote),
+                VoteType::Precommit => (&mut self.precommits, Step::Precommit),
+            };
+            *votes.entry(vote.round).or_default().entry(vote.block_hash).or_insert(0) +=
+                self.weight;
+            if vote.round == recovered.round && step != Step::Precommit {
+                step = vote_step;
+            }
+        }
+        let round = recovered.round;
+        if step == Step::Propose && !recovered.proposal_rounds.contains(&round) {
+            // Nothing was sent in this round, so it can be started from scratch.
+            return self.advance_to_round(round, leader_fn);
+        }
+
+        info!("RESUME_ROUND: Resuming round {round} at step {step:?}");
+        CONSENSUS_ROUND.set(round);
+        self.round = round;
+        self.step = step;
+        // The proposal started before the restart isn't rebuilt; as with a failed proposal, the
+        // round proceeds via TimeoutPropose.
+        let mut output = if self.step == Step::Propose {
+            VecDeque::from([StateMachineEvent::TimeoutPropose(round)])
+        } else {
+            VecDeque::new()
+        };
+        output.append(&mut self.current_round_upons());
+        output
+    }
+
+    /// Process the incoming event. Votes are handled by [`handle_vote`](Self::handle_vote).
     ///
     /// If we are waiting for a response to [`GetProposal`](`StateMachineEvent::GetProposal`) all
     /// other incoming events are buffered until that response arrives.
@@ -156,22 +217,57 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
+    {
+        assert!(
+            !matches!(event, StateMachineEvent::Prevote(_, _) | StateMachineEvent::Precommit(_, _)),
+            "Votes must be handled with the weight of their sender: {event:?}"
+        );
+        self.enqueue_and_handle(event, 0, leader_fn)
+    }
+
+    /// Process a vote of a peer, which is counted with the voting weight of its sender.
+    ///
+    /// See [`handle_event`](Self::handle_event).
+    pub fn handle_vote<LeaderFn>(
+        &mut self,
+        vote: StateMachineEvent,
+        weight: u128,
+        leader_fn: &LeaderFn,
+    ) -> VecDeque<StateMachineEvent>
+    where
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
+    {
+        assert!(
+            matches!(vote, StateMachineEvent::Prevote(_, _) | StateMachineEvent::Precommit(_, _)),
+            "Expected a vote: {vote:?}"
+        );
+        self.enqueue_and_handle(vote, weight, leader_fn)
+    }
+
+    fn enqueue_and_handle<LeaderFn>(
+        &mut self,
+        event: StateMachineEvent,
+        weight: u128,
+        leader_fn: &LeaderFn,
+    ) -> VecDeque<StateMachineEvent>
+    where
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         // Mimic LOC 18 in the paper; the state machine doesn't
         // handle any events until `getValue` completes.
         if self.awaiting_get_proposal {
             match event {
                 StateMachineEvent::GetProposal(_, round) if round == self.round => {
-                    self.events_queue.push_front(event);
+                    self.events_queue.push_front((event, weight));
                 }
                 _ => {
-                    self.events_queue.push_back(event);
+                    self.events_queue.push_back((event, weight));
                     return VecDeque::new();
                 }
             }
         } else {
-            self.events_queue.push_back(event);
+            self.events_queue.push_back((event, weight));
         }
 
         self.handle_enqueued_events(leader_fn)
@@ -182,13 +278,13 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         let mut output_events = VecDeque::new();
-        while let Some(event) = self.events_queue.pop_front() {
+        while let Some((event, weight)) = self.events_queue.pop_front() {
             // Handle a specific event and then decide which of the output events should also be
             // sent to self.
-            let mut resultant_events = self.handle_event_internal(event, leader_fn);
+            let mut resultant_events = self.handle_event_internal(event, weight, leader_fn);
             while let Some(e) = resultant_events.pop_front() {
                 match e {
                     StateMachineEvent::Proposal(_, _, _)
@@ -197,7 +293,7 @@ impl StateMachine {
                         if self.is_observer {
                             continue;
                         }
-                        self.events_queue.push_back(e.clone());
+                        self.events_queue.push_back((e.clone(), self.weight));
                     }
                     StateMachineEvent::Decision(_, _) => {
                         output_events.push_back(e);
@@ -221,10 +317,11 @@ impl StateMachine {
     fn handle_event_internal<LeaderFn>(
         &mut self,
         event: StateMachineEvent,
+        weight: u128,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         trace!("Processing event: {:?}", event);
         if self.awaiting_get_proposal {
@@ -239,10 +336,10 @@ impl StateMachine {
                 self.handle_proposal(proposal_id, round, valid_round, leader_fn)
             }
             StateMachineEvent::Prevote(proposal_id, round) => {
-                self.handle_prevote(proposal_id, round, leader_fn)
+                self.handle_prevote(proposal_id, round, weight, leader_fn)
             }
             StateMachineEvent::Precommit(proposal_id, round) => {
-                self.handle_precommit(proposal_id, round, leader_fn)
+                self.handle_precommit(proposal_id, round, weight, leader_fn)
             }
             StateMachineEvent::Decision(_, _) => {
                 unimplemented!(
@@ -278,7 +375,7 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         let old = self.proposals.insert(round, (proposal_id, valid_round));
         assert!(old.is_none(), "SHC should handle conflicts & replays");
@@ -305,14 +402,15 @@ impl StateMachine {
         &mut self,
         proposal_id: Option<ProposalCommitment>,
         round: u32,
+        weight: u128,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
-        let prevote_count = self.prevotes.entry(round).or_default().entry(proposal_id).or_insert(0);
-        // TODO(matan): Use variable weight.
-        *prevote_count += 1;
+        let prevote_weight =
+            self.prevotes.entry(round).or_default().entry(proposal_id).or_insert(0);
+        *prevote_weight += weight;
         self.map_round_to_upons(round, leader_fn)
     }
 
@@ -333,15 +431,15 @@ impl StateMachine {
         &mut self,
         proposal_id: Option<ProposalCommitment>,
         round: u32,
+        weight: u128,
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
-        let precommit_count =
+        let precommit_weight =
             self.precommits.entry(round).or_default().entry(proposal_id).or_insert(0);
-        // TODO(matan): Use variable weight.
-        *precommit_count += 1;
+        *precommit_weight += weight;
         self.map_round_to_upons(round, leader_fn)
     }
 
@@ -351,7 +449,7 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         if round != self.round {
             return VecDeque::new();
@@ -362,6 +460,21 @@ impl StateMachine {
         self.advance_to_round(round + 1, leader_fn)
     }
 
+    // A node which can't determine the proposer of the round doesn't propose in it, and waits for
+    // the round to time out.
+    fn is_leader<LeaderFn>(&self, round: Round, leader_fn: &LeaderFn) -> bool
+    where
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
+    {
+        match leader_fn(round) {
+            Ok(leader) => leader == self.id,
+            Err(e) => {
+                error!("Failed to get the proposer of round {round}: {e}");
+                false
+            }
+        }
+    }
+
     // LOC 11 in the paper.
     fn advance_to_round<LeaderFn>(
         &mut self,
@@ -369,7 +482,7 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         CONSENSUS_ROUND.set(round);
         // Count how many times consensus advanced above round 0.
@@ -381,7 +494,7 @@ impl StateMachine {
         }
         self.round = round;
         self.step = Step::Propose;
-        let mut output = if !self.is_observer && self.id == leader_fn(self.round) {
+        let mut output = if !self.is_observer && self.is_leader(round, leader_fn) {
             info!("START_ROUND_PROPOSER: Starting round {round} as Proposer");
             // Leader.
             match self.valid_value_round {
@@ -417,7 +530,7 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         match round.cmp(&self.round) {
             std::cmp::Ordering::Less => self.past_round_upons(round),
@@ -593,7 +706,7 @@ impl StateMachine {
         leader_fn: &LeaderFn,
     ) -> VecDeque<StateMachineEvent>
     where
-        LeaderFn: Fn(Round) -> ValidatorId,
+        LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>,
     {
         if self.round_has_enough_votes(&self.prevotes, round, &self.round_skip_threshold)
             || self.round_has_enough_votes(&self.precommits, round, &self.round_skip_threshold)
@@ -606,24 +719,21 @@ impl StateMachine {
 
     fn round_has_enough_votes(
         &self,
-        votes: &HashMap<u32, HashMap<Option<ProposalCommitment>, u32>>,
+        votes: &HashMap<u32, HashMap<Option<ProposalCommitment>, u128>>,
         round: u32,
         threshold: &VotesThreshold,
     ) -> bool {
-        threshold
-            .is_met(votes.get(&round).map_or(0, |v| v.values().sum()).into(), self.total_weight)
+        threshold.is_met(votes.get(&round).map_or(0, |v| v.values().sum()), self.total_weight)
     }
 
     fn value_has_enough_votes(
         &self,
-        votes: &HashMap<u32, HashMap<Option<ProposalCommitment>, u32>>,
+        votes: &HashMap<u32, HashMap<Option<ProposalCommitment>, u128>>,
         round: u32,
         value: &Option<ProposalCommitment>,
         threshold: &VotesThreshold,
     ) -> bool {
-        threshold.is_met(
-            votes.get(&round).map_or(0, |v| *v.get(value).unwrap_or(&0)).into(),
-            self.total_weight,
-        )
+        threshold
+            .is_met(votes.get(&round).map_or(0, |v| *v.get(value).unwrap_or(&0)), self.total_weight)
     }
 }
@@ -1,4 +1,4 @@
-use std::collections::VecDeque;
+use std::collections::{BTreeSet, VecDeque};
 
 use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 use lazy_static::lazy_static;
@@ -8,8 +8,10 @@ use test_case::test_case;
 
 use super::Round;
 use crate::state_machine::{StateMachine, StateMachineEvent};
-use crate::types::{ProposalCommitment, ValidatorId};
+use crate::test_utils::{precommit, prevote};
+use crate::types::{ConsensusError, ProposalCommitment, ValidatorId};
 use crate::votes_threshold::QuorumType;
+use crate::write_ahead_log::RecoveredHeight;
 
 lazy_static! {
     static ref PROPOSER_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
@@ -19,22 +21,34 @@ lazy_static! {
 const PROPOSAL_ID: Option<ProposalCommitment> = Some(BlockHash(Felt::ONE));
 const ROUND: Round = 0;
 
-struct TestWrapper<LeaderFn: Fn(Round) -> ValidatorId> {
+struct TestWrapper<LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>> {
     state_machine: StateMachine,
     leader_fn: LeaderFn,
     events: VecDeque<StateMachineEvent>,
 }
 
-impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
+impl<LeaderFn: Fn(Round) -> Result<ValidatorId, ConsensusError>> TestWrapper<LeaderFn> {
+    /// Every validator has a voting weight of 1.
     pub fn new(
         id: ValidatorId,
-        total_weight: u64,
+        total_weight: u128,
+        leader_fn: LeaderFn,
+        is_observer: bool,
+        quorum_type: QuorumType,
+    ) -> Self {
+        Self::new_weighted(id, 1, total_weight, leader_fn, is_observer, quorum_type)
+    }
+
+    pub fn new_weighted(
+        id: ValidatorId,
+        weight: u128,
+        total_weight: u128,
         leader_fn: LeaderFn,
         is_observer: bool,
         quorum_type: QuorumType,
     ) -> Self {
         Self {
-            state_machine: StateMachine::new(id, total_weight, is_observer, quorum_type),
+            state_machine: StateMachine::new(id, total_weight, weight, is_observer, quorum_type),
             leader_fn,
             events: VecDeque::new(),
         }
@@ -48,6 +62,10 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
         self.events.append(&mut self.state_machine.start(&self.leader_fn))
     }
 
+    pub fn resume(&mut self, recovered_height: &RecoveredHeight) {
+        self.events.append(&mut self.state_machine.resume(recovered_height, &self.leader_fn))
+    }
+
     pub fn send_get_proposal(&mut self, proposal_id: Option<ProposalCommitment>, round: Round) {
         self.send_event(StateMachineEvent::GetProposal(proposal_id, round))
     }
@@ -57,11 +75,29 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
     }
 
     pub fn send_prevote(&mut self, proposal_id: Option<ProposalCommitment>, round: Round) {
-        self.send_event(StateMachineEvent::Prevote(proposal_id, round))
+        self.send_weighted_prevote(proposal_id, round, 1)
     }
 
     pub fn send_precommit(&mut self, proposal_id: Option<ProposalCommitment>, round: Round) {
-        self.send_event(StateMachineEvent::Precommit(proposal_id, round))
+        self.send_weighted_precommit(proposal_id, round, 1)
+    }
+
+    pub fn send_weighted_prevote(
+        &mut self,
+        proposal_id: Option<ProposalCommitment>,
+        round: Round,
+        weight: u128,
+    ) {
+        self.send_vote(StateMachineEvent::Prevote(proposal_id, round), weight)
+    }
+
+    pub fn send_weighted_precommit(
+        &mut self,
+        proposal_id: Option<ProposalCommitment>,
+        round: Round,
+        weight: u128,
+    ) {
+        self.send_vote(StateMachineEvent::Precommit(proposal_id, round), weight)
     }
 
     pub fn send_timeout_propose(&mut self, round: Round) {
@@ -79,6 +115,10 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
     fn send_event(&mut self, event: StateMachineEvent) {
         self.events.append(&mut self.state_machine.handle_event(event, &self.leader_fn));
     }
+
+    fn send_vote(&mut self, vote: StateMachineEvent, weight: u128) {
+        self.events.append(&mut self.state_machine.handle_vote(vote, weight, &self.leader_fn));
+    }
 }
 
 #[test_case(true; "proposer")]
@@ -86,7 +126,7 @@ impl<LeaderFn: Fn(Round) -> ValidatorId> TestWrapper<LeaderFn> {
 fn events_arrive_in_ideal_order(is_proposer: bool) {
     let id = if is_proposer { *PROPOSER_ID } else { *VALIDATOR_ID };
     let mut wrapper =
-        TestWrapper::new(id, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+        TestWrapper::new(id, 4, |_: Round| Ok(*PROPOSER_ID), false, QuorumType::Byzantine);
 
     wrapper.start();
     if is_proposer {
@@ -129,8 +169,13 @@ fn events_arrive_in_ideal_order(is_proposer: bool) {
 
 #[test]
 fn validator_receives_votes_first() {
-    let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     // Waiting for the proposal.
@@ -164,8 +209,13 @@ fn validator_receives_votes_first() {
 #[test_case(PROPOSAL_ID ; "valid_proposal")]
 #[test_case(None ; "invalid_proposal")]
 fn buffer_events_during_get_proposal(vote: Option<ProposalCommitment>) {
-    let mut wrapper =
-        TestWrapper::new(*PROPOSER_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *PROPOSER_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::GetProposal(None, 0));
@@ -190,8 +240,13 @@ fn buffer_events_during_get_proposal(vote: Option<ProposalCommitment>) {
 
 #[test]
 fn only_send_precommit_with_prevote_quorum_and_proposal() {
-    let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     // Waiting for the proposal.
@@ -214,8 +269,13 @@ fn only_send_precommit_with_prevote_quorum_and_proposal() {
 
 #[test]
 fn only_decide_with_prcommit_quorum_and_proposal() {
-    let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     // Waiting for the proposal.
@@ -245,8 +305,13 @@ fn only_decide_with_prcommit_quorum_and_proposal() {
 
 #[test]
 fn advance_to_the_next_round() {
-    let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     // Waiting for the proposal.
@@ -272,8 +337,13 @@ fn advance_to_the_next_round() {
 
 #[test]
 fn prevote_when_receiving_proposal_in_current_round() {
-    let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
@@ -298,8 +368,13 @@ fn prevote_when_receiving_proposal_in_current_round() {
 #[test_case(true ; "send_proposal")]
 #[test_case(false ; "send_timeout_propose")]
 fn mixed_quorum(send_proposal: bool) {
-    let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     // Waiting for the proposal.
@@ -329,8 +404,13 @@ fn mixed_quorum(send_proposal: bool) {
 
 #[test]
 fn dont_handle_enqueued_while_awaiting_get_proposal() {
-    let mut wrapper =
-        TestWrapper::new(*PROPOSER_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *PROPOSER_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::GetProposal(None, ROUND));
@@ -375,8 +455,13 @@ fn dont_handle_enqueued_while_awaiting_get_proposal() {
 
 #[test]
 fn return_proposal_if_locked_value_is_set() {
-    let mut wrapper =
-        TestWrapper::new(*PROPOSER_ID, 4, |_: Round| *PROPOSER_ID, false, QuorumType::Byzantine);
+    let mut wrapper = TestWrapper::new(
+        *PROPOSER_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
 
     wrapper.start();
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::GetProposal(None, ROUND));
@@ -408,10 +493,24 @@ fn return_proposal_if_locked_value_is_set() {
     assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND + 1));
 }
 
+#[test]
+fn unknown_proposer_waits_for_the_proposal() {
+    let leader_fn =
+        |_: Round| Err(ConsensusError::CommitteeError("Missing committee.".to_string()));
+    let mut wrapper = TestWrapper::new(*PROPOSER_ID, 4, leader_fn, false, QuorumType::Byzantine);
+
+    wrapper.start();
+
+    // The node doesn't propose, even if it is the proposer.
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    assert!(wrapper.next_event().is_none());
+}
+
 #[test]
 fn observer_node_reaches_decision() {
     let id = *VALIDATOR_ID;
-    let mut wrapper = TestWrapper::new(id, 4, |_: Round| *PROPOSER_ID, true, QuorumType::Byzantine);
+    let mut wrapper =
+        TestWrapper::new(id, 4, |_: Round| Ok(*PROPOSER_ID), true, QuorumType::Byzantine);
 
     wrapper.start();
 
@@ -438,7 +537,7 @@ fn observer_node_reaches_decision() {
 #[test_case(QuorumType::Honest; "honest")]
 fn number_of_required_votes(quorum_type: QuorumType) {
     let mut wrapper =
-        TestWrapper::new(*VALIDATOR_ID, 3, |_: Round| *PROPOSER_ID, false, quorum_type);
+        TestWrapper::new(*VALIDATOR_ID, 3, |_: Round| Ok(*PROPOSER_ID), false, quorum_type);
 
     wrapper.start();
     // Waiting for the proposal.
@@ -491,3 +590,177 @@ fn number_of_required_votes(quorum_type: QuorumType) {
     );
     assert!(wrapper.next_event().is_none());
 }
+
+#[test]
+fn resume_after_prevote() {
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
+
+    wrapper.resume(&RecoveredHeight {
+        round: ROUND,
+        votes: vec![prevote(Some(Felt::ONE), 0, ROUND, *VALIDATOR_ID)],
+        ..Default::default()
+    });
+    assert!(wrapper.next_event().is_none());
+
+    // Already prevoted in this round, so the timeout has no effect.
+    wrapper.send_timeout_propose(ROUND);
+    assert!(wrapper.next_event().is_none());
+
+    // The node's own prevote counts towards the quorum.
+    wrapper.send_prevote(PROPOSAL_ID, ROUND);
+    wrapper.send_prevote(PROPOSAL_ID, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // The proposal was lost in the restart, so the node can't lock on it.
+    wrapper.send_timeout_prevote(ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(None, ROUND));
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn resume_after_starting_proposal() {
+    let mut wrapper = TestWrapper::new(
+        *PROPOSER_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
+
+    wrapper.resume(&RecoveredHeight {
+        round: ROUND,
+        proposal_rounds: BTreeSet::from([ROUND]),
+        ..Default::default()
+    });
+    // The proposal isn't built again.
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    wrapper.send_timeout_propose(ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(None, ROUND));
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn resume_keeps_lock() {
+    let mut wrapper = TestWrapper::new(
+        *VALIDATOR_ID,
+        4,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
+
+    wrapper.resume(&RecoveredHeight {
+        round: ROUND + 1,
+        votes: vec![
+            prevote(Some(Felt::ONE), 0, ROUND, *VALIDATOR_ID),
+            precommit(Some(Felt::ONE), 0, ROUND, *VALIDATOR_ID),
+        ],
+        locked_value_round: Some((PROPOSAL_ID.unwrap(), ROUND)),
+        ..Default::default()
+    });
+    // Nothing was sent in the new round, so it starts from scratch.
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND + 1));
+    assert!(wrapper.next_event().is_none());
+
+    // A proposal which conflicts with the lock is rejected.
+    wrapper.send_proposal(Some(BlockHash(Felt::TWO)), ROUND + 1);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(None, ROUND + 1));
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn heavy_validator_completes_quorum() {
+    // The proposer holds most of the stake: 6 out of 10.
+    let mut wrapper = TestWrapper::new_weighted(
+        *VALIDATOR_ID,
+        1,
+        10,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
+
+    wrapper.start();
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    wrapper.send_proposal(PROPOSAL_ID, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // Two votes carry 7 out of 10, which is more than 2/3 of the stake.
+    wrapper.send_weighted_prevote(PROPOSAL_ID, ROUND, 6);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    wrapper.send_weighted_precommit(PROPOSAL_ID, ROUND, 6);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrecommit(ROUND));
+    assert_eq!(
+        wrapper.next_event().unwrap(),
+        StateMachineEvent::Decision(PROPOSAL_ID.unwrap(), ROUND)
+    );
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn light_validators_do_not_reach_quorum() {
+    // The proposer holds 4 out of 10, the node and 5 other validators hold 1 each.
+    let mut wrapper = TestWrapper::new_weighted(
+        *VALIDATOR_ID,
+        1,
+        10,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
+
+    wrapper.start();
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    wrapper.send_proposal(PROPOSAL_ID, ROUND);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Prevote(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // 6 of the 7 validators prevote, but they only hold 6 out of 10.
+    for _ in 0..5 {
+        wrapper.send_weighted_prevote(PROPOSAL_ID, ROUND, 1);
+    }
+    assert!(wrapper.next_event().is_none());
+
+    wrapper.send_weighted_prevote(PROPOSAL_ID, ROUND, 4);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPrevote(ROUND));
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::Precommit(PROPOSAL_ID, ROUND));
+    assert!(wrapper.next_event().is_none());
+}
+
+#[test]
+fn round_skip_by_stake() {
+    let mut wrapper = TestWrapper::new_weighted(
+        *VALIDATOR_ID,
+        1,
+        10,
+        |_: Round| Ok(*PROPOSER_ID),
+        false,
+        QuorumType::Byzantine,
+    );
+
+    wrapper.start();
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND));
+    assert!(wrapper.next_event().is_none());
+
+    // 3 out of 10 is not more than 1/3 of the stake.
+    wrapper.send_weighted_prevote(None, ROUND + 1, 3);
+    assert!(wrapper.next_event().is_none());
+
+    // A single vote with more than 1/3 of the stake is enough to skip to its round.
+    wrapper.send_weighted_precommit(None, ROUND + 2, 4);
+    assert_eq!(wrapper.next_event().unwrap(), StateMachineEvent::TimeoutPropose(ROUND + 2));
+    assert!(wrapper.next_event().is_none());
+}
@@ -34,6 +34,10 @@ mod stream_handler_test;
 type PeerId = OpaquePeerId;
 type MessageId = u64;
 
+/// An inbound stream handed to the application, with the metadata of the message which started it,
+/// so that its sender can be reported.
+pub type InboundStream<StreamContent> = (BroadcastedMessageMetadata, mpsc::Receiver<StreamContent>);
+
 /// Errors which cause the stream handler to stop functioning.
 #[derive(thiserror::Error, PartialEq, Debug)]
 pub enum StreamHandlerError {
@@ -98,6 +102,8 @@ struct StreamData<StreamContent: StreamContentTrait, StreamId: StreamIdTrait> {
     // Keep the receiver until it is time to send it to the application.
     receiver: Option<mpsc::Receiver<StreamContent>>,
     sender: mpsc::Sender<StreamContent>,
+    // The metadata of the message which started the stream.
+    metadata: BroadcastedMessageMetadata,
     // A buffer for messages that were received out of order.
     message_buffer: HashMap<MessageId, StreamMessage<StreamContent, StreamId>>,
 }
@@ -105,7 +111,7 @@ struct StreamData<StreamContent: StreamContentTrait, StreamId: StreamIdTrait> {
 impl<StreamContent: StreamContentTrait, StreamId: StreamIdTrait>
     StreamData<StreamContent, StreamId>
 {
-    fn new(channel_buffer_capacity: usize) -> Self {
+    fn new(channel_buffer_capacity: usize, metadata: BroadcastedMessageMetadata) -> Self {
         let (sender, receiver) = mpsc::channel(channel_buffer_capacity);
         StreamData {
             next_message_id: 0,
@@ -113,6 +119,7 @@ impl<StreamContent: StreamContentTrait, StreamId: StreamIdTrait>
             max_message_id_received: 0,
             sender,
             receiver: Some(receiver),
+            metadata,
             message_buffer: HashMap::new(),
         }
     }
@@ -132,7 +139,7 @@ where
     config: StreamHandlerConfig,
     // For each stream ID from the network, send the application a Receiver
     // that will receive the messages in order. This allows sending such Receivers.
-    inbound_channel_sender: mpsc::Sender<mpsc::Receiver<StreamContent>>,
+    inbound_channel_sender: mpsc::Sender<InboundStream<StreamContent>>,
     // This receives messages from the network.
     inbound_receiver: InboundReceiverT,
     // An LRU cache mapping (peer_id, stream_id) to a struct that contains all the information
@@ -163,7 +170,7 @@ where
     /// Create a new StreamHandler.
     pub fn new(
         config: StreamHandlerConfig,
-        inbound_channel_sender: mpsc::Sender<mpsc::Receiver<StreamContent>>,
+        inbound_channel_sender: mpsc::Sender<InboundStream<StreamContent>>,
         inbound_receiver: InboundReceiverT,
         outbound_channel_receiver: mpsc::Receiver<(StreamId, mpsc::Receiver<StreamContent>)>,
         outbound_sender: OutboundSenderT,
@@ -281,7 +288,9 @@ where
                 // If this is the first message, send the receiver to the application.
                 let receiver = data.receiver.take().expect("Receiver should exist");
                 // Send the receiver to the application.
-                self.inbound_channel_sender.try_send(receiver).expect("Send should succeed");
+                self.inbound_channel_sender
+                    .try_send((data.metadata.clone(), receiver))
+                    .expect("Send should succeed");
             }
             data.next_message_id += 1;
             return false;
@@ -351,7 +360,7 @@ where
             None => {
                 info!(?peer_id, ?stream_id, "Inbound stream started");
                 CONSENSUS_INBOUND_STREAM_STARTED.increment(1);
-                StreamData::new(self.config.channel_buffer_capacity)
+                StreamData::new(self.config.channel_buffer_capacity, metadata.clone())
             }
         };
         if let Some(data) = self.handle_message_inner(message, metadata, data) {
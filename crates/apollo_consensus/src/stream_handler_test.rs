@@ -11,7 +11,7 @@ use futures::{FutureExt, SinkExt, StreamExt};
 use prost::DecodeError;
 
 use crate::config::StreamHandlerConfig;
-use crate::stream_handler::StreamHandler;
+use crate::stream_handler::{InboundStream, StreamHandler};
 const CHANNEL_CAPACITY: usize = 100;
 const MAX_STREAMS: usize = 10;
 
@@ -87,7 +87,7 @@ fn setup() -> (
         FakeBroadcastClient,
     >,
     Sender<ReceivedBroadcastedMessage<StreamMessage>>,
-    Receiver<Receiver<ProposalPart>>,
+    Receiver<InboundStream<ProposalPart>>,
     Sender<(TestStreamId, Receiver<ProposalPart>)>,
     Receiver<StreamMessage>,
 ) {
@@ -264,7 +264,10 @@ async fn inbound_in_order() {
     network_to_streamhandler_sender.send((Ok(message), metadata.clone())).await.unwrap();
     stream_handler.handle_next_msg().await.unwrap();
     // Fin is communicated by dropping the sender, hence `..num_message` not `..=num_messages`
-    let mut receiver = streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
+    let (stream_metadata, mut receiver) =
+        streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
+    // The stream identifies its sender.
+    assert_eq!(stream_metadata, metadata);
     for i in 0..num_messages {
         let message = receiver.next().await.unwrap();
         assert_eq!(message, ProposalPart::Init(ProposalInit { round: i, ..Default::default() }));
@@ -298,7 +301,8 @@ async fn lru_cache_for_inbound_streams() {
     }
 
     for i in (0..num_streams).rev() {
-        let mut receiver = streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
+        let (_, mut receiver) =
+            streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
         let message = receiver.next().await.unwrap();
         assert_eq!(
             message,
@@ -343,7 +347,8 @@ async fn inbound_multiple() {
     let mut expected_msgs = (0..num_streams).map(|_| Vec::new()).collect::<Vec<_>>();
     let mut actual_msgs = expected_msgs.clone();
     for sid in 0..num_streams {
-        let mut receiver = streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
+        let (_, mut receiver) =
+            streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
         // Fin is communicated by dropping the sender, hence `..num_message` not `..=num_messages`
         for i in 0..num_messages {
             let message = receiver.next().await.unwrap();
@@ -392,7 +397,8 @@ async fn inbound_delayed_first() {
     stream_handler.handle_next_msg().await.unwrap();
 
     // Now first message and all cached messages should be received.
-    let mut receiver = streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
+    let (_, mut receiver) =
+        streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
     // Fin is communicated by dropping the sender, hence `..num_message` not `..=num_messages`
     for i in 0..num_messages {
         let message = receiver.next().await.unwrap();
@@ -430,7 +436,8 @@ async fn inbound_delayed_middle() {
     stream_handler.handle_next_msg().await.unwrap();
 
     // Should receive a few messages, until we reach the missing one.
-    let mut receiver = streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
+    let (_, mut receiver) =
+        streamhandler_to_client_receiver.next().now_or_never().unwrap().unwrap();
     for i in 0..missing_message_id {
         let message = receiver.next().await.unwrap();
         assert_eq!(message, ProposalPart::Init(ProposalInit { round: i, ..Default::default() }));
@@ -2,13 +2,24 @@ use std::time::Duration;
 
 use apollo_protobuf::consensus::{ProposalInit, Vote, VoteType};
 use apollo_protobuf::converters::ProtobufConversionError;
+use apollo_signature_manager::signature_manager::{LocalKeyStore, SignatureManager};
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
 use mockall::mock;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::crypto::utils::PrivateKey;
+use starknet_api::staking::StakingWeight;
 use starknet_types_core::felt::Felt;
 
-use crate::types::{ConsensusContext, ConsensusError, ProposalCommitment, Round, ValidatorId};
+use crate::types::{
+    ConsensusContext,
+    ConsensusError,
+    ProposalCommitment,
+    Round,
+    Validator,
+    ValidatorId,
+    ValidatorSet,
+};
 
 /// Define a consensus block which can be used to enable auto mocking Context.
 #[derive(Debug, PartialEq, Clone)]
@@ -78,9 +89,13 @@ mock! {
             init: ProposalInit,
         );
 
-        async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+        async fn validators(&self, height: BlockNumber) -> Result<ValidatorSet, ConsensusError>;
 
-        fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
+        fn proposer(
+            &self,
+            height: BlockNumber,
+            round: Round,
+        ) -> Result<ValidatorId, ConsensusError>;
 
         async fn broadcast(&mut self, message: Vote) -> Result<(), ConsensusError>;
 
@@ -98,13 +113,46 @@ mock! {
 
 pub fn prevote(block_felt: Option<Felt>, height: u64, round: u32, voter: ValidatorId) -> Vote {
     let block_hash = block_felt.map(BlockHash);
-    Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter }
+    Vote { vote_type: VoteType::Prevote, height, round, block_hash, voter, ..Default::default() }
 }
 
 pub fn precommit(block_felt: Option<Felt>, height: u64, round: u32, voter: ValidatorId) -> Vote {
     let block_hash = block_felt.map(BlockHash);
-    Vote { vote_type: VoteType::Precommit, height, round, block_hash, voter }
+    Vote { vote_type: VoteType::Precommit, height, round, block_hash, voter, ..Default::default() }
 }
+
+pub fn weighted(validators: &[(ValidatorId, u128)]) -> ValidatorSet {
+    validators
+        .iter()
+        .map(|(id, weight)| {
+            let validator =
+                Validator { weight: StakingWeight(*weight), public_key: key_store(*id).public_key };
+            (*id, validator)
+        })
+        .collect()
+}
+
+pub fn equally_weighted(validators: &[ValidatorId]) -> ValidatorSet {
+    weighted(&validators.iter().map(|validator| (*validator, 1)).collect::<Vec<_>>())
+}
+
+// Each validator signs with a key derived from its ID.
+fn key_store(validator: ValidatorId) -> LocalKeyStore {
+    LocalKeyStore::new(PrivateKey(validator.into()))
+}
+
+pub async fn sign_vote(mut vote: Vote) -> Vote {
+    let signature_manager = SignatureManager::new(key_store(vote.voter));
+    vote.signature = signature_manager.sign_vote(&vote).await.unwrap().try_into().unwrap();
+    vote
+}
+
+pub async fn sign_proposal_init(mut init: ProposalInit) -> ProposalInit {
+    let signature_manager = SignatureManager::new(key_store(init.proposer));
+    init.signature = signature_manager.sign_proposal_init(&init).await.unwrap().try_into().unwrap();
+    init
+}
+
 pub fn proposal_init(height: u64, round: u32, proposer: ValidatorId) -> ProposalInit {
     ProposalInit { height: BlockNumber(height), round, proposer, ..Default::default() }
 }
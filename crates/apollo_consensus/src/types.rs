@@ -1,4 +1,5 @@
 //! Types for interfacing between consensus and the node.
+use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::time::Duration;
 
@@ -12,8 +13,11 @@ use apollo_protobuf::consensus::{ProposalInit, Vote};
 use apollo_protobuf::converters::ProtobufConversionError;
 use async_trait::async_trait;
 use futures::channel::{mpsc, oneshot};
+use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::PublicKey;
+use starknet_api::staking::StakingWeight;
 
 /// Used to identify the node by consensus.
 /// 1. This ID is derived from the id registered with Starknet's L2 staking contract.
@@ -23,6 +27,17 @@ use starknet_api::core::ContractAddress;
 pub type ValidatorId = ContractAddress;
 pub type Round = u32;
 pub type ProposalCommitment = BlockHash;
+/// The validators of a height. Ordered, so that proposer selection over the set is deterministic.
+pub type ValidatorSet = BTreeMap<ValidatorId, Validator>;
+
+/// A member of the validator set of a height.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Validator {
+    /// The voting weight (stake) of the validator.
+    pub weight: StakingWeight,
+    /// The key which the messages of the validator are signed with.
+    pub public_key: PublicKey,
+}
 
 /// Interface for consensus to call out to the node.
 ///
@@ -47,7 +62,8 @@ pub trait ConsensusContext {
     /// parallel to the block being built.
     ///
     /// Params:
-    /// - `init`: The `ProposalInit` that is broadcast to the network.
+    /// - `init`: The `ProposalInit` that is broadcast to the network. The context signs it, as well
+    ///   as the `ProposalFin`.
     /// - `timeout`: The maximum time to wait for the block to be built.
     ///
     /// Returns:
@@ -86,20 +102,20 @@ pub trait ConsensusContext {
     ///
     /// Params:
     /// - `id`: The `ProposalCommitment` associated with the block's content.
-    /// - `init`: The `ProposalInit` that is broadcast to the network.
+    /// - `init`: The `ProposalInit` that is broadcast to the network. The context signs it, as well
+    ///   as the `ProposalFin`.
     async fn repropose(&mut self, id: ProposalCommitment, init: ProposalInit);
 
-    /// Get the set of validators for a given height. These are the nodes that can propose and vote
-    /// on blocks.
-    // TODO(matan): We expect this to change in the future to BTreeMap. Why?
-    // 1. Map - The nodes will have associated information (e.g. voting weight).
-    // 2. BTreeMap - We want a stable ordering of the nodes for deterministic leader selection.
-    async fn validators(&self, height: BlockNumber) -> Vec<ValidatorId>;
+    /// Get the set of validators for a given height, with their voting weights and public keys.
+    /// These are the nodes that can propose and vote on blocks; quorums are reached by the sum of
+    /// their weights.
+    async fn validators(&self, height: BlockNumber) -> Result<ValidatorSet, ConsensusError>;
 
     /// Calculates the ID of the Proposer based on the inputs.
     // TODO(matan): Consider passing the validator set in order to keep this sync.
-    fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId;
+    fn proposer(&self, height: BlockNumber, round: Round) -> Result<ValidatorId, ConsensusError>;
 
+    /// Signs the vote of this node and broadcasts it to the network.
     async fn broadcast(&mut self, message: Vote) -> Result<(), ConsensusError>;
 
     /// Update the context that a decision has been reached for a given height.
@@ -158,6 +174,16 @@ pub enum ConsensusError {
     InternalNetworkError(String),
     #[error("{0}")]
     SyncError(String),
+    // Failing to persist our own messages means we can't safely continue, as a restart could lead
+    // us to contradict them.
+    #[error("Write-ahead log error: {0}")]
+    WriteAheadLogError(String),
+    #[error("Evidence store error: {0}")]
+    EvidenceStoreError(String),
+    #[error("Failed to sign a message: {0}")]
+    SigningError(String),
+    #[error("Failed to get the committee: {0}")]
+    CommitteeError(String),
     // For example the state machine and SHC are out of sync.
     #[error("{0}")]
     InternalInconsistency(String),
@@ -0,0 +1,224 @@
+//! A write-ahead log (WAL) of the messages this node committed to during a height.
+//!
+//! Before the node broadcasts a vote, starts a proposal or locks on a value, the change is
+//! appended to the log of the height and synced to disk. After a restart, the log is replayed so
+//! that the node resumes the round and step it reached and never sends messages which contradict
+//! the ones it sent before the restart (equivocation).
+//!
+//! [`WriteAheadLog`] - the directory of the logs, holding a file per height.
+//!
+//! [`HeightWriteAheadLog`] - the log of a single height.
+
+#[cfg(test)]
+#[path = "write_ahead_log_test.rs"]
+mod write_ahead_log_test;
+
+use std::collections::BTreeSet;
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, Read, Write};
+use std::path::{Path, PathBuf};
+
+use apollo_protobuf::consensus::Vote;
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use tracing::{info, warn};
+
+use crate::types::{ConsensusError, ProposalCommitment, Round};
+
+const FILE_EXTENSION: &str = "wal";
+
+/// An entry of the log. Each entry is written before its effect is visible to other nodes.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub(crate) enum WalEntry {
+    /// The node advanced to this round.
+    Round(Round),
+    /// A vote of this node.
+    Vote(Vote),
+    /// A proposal of this node. A new proposal is logged before it is built, when its commitment
+    /// is still unknown, and again once it is built.
+    Proposal {
+        round: Round,
+        proposal_commitment: Option<ProposalCommitment>,
+        valid_round: Option<Round>,
+    },
+    /// The node locked on a value in a round.
+    Lock(ProposalCommitment, Round),
+}
+
+/// The progress of the node in a height, as recovered from its log.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub(crate) struct RecoveredHeight {
+    /// The latest round the node reached.
+    pub round: Round,
+    /// The votes of the node, in the order they were sent.
+    pub votes: Vec<Vote>,
+    /// The rounds in which the node started a proposal.
+    pub proposal_rounds: BTreeSet<Round>,
+    pub locked_value_round: Option<(ProposalCommitment, Round)>,
+}
+
+impl RecoveredHeight {
+    /// Returns `None` if nothing was logged for the height.
+    fn from_entries(entries: Vec<WalEntry>) -> Option<Self> {
+        if entries.is_empty() {
+            return None;
+        }
+        let mut recovered = RecoveredHeight::default();
+        for entry in entries {
+            match entry {
+                WalEntry::Round(round) => {
+                    recovered.round = recovered.round.max(round);
+                }
+                WalEntry::Vote(vote) => {
+                    recovered.round = recovered.round.max(vote.round);
+                    recovered.votes.push(vote);
+                }
+                WalEntry::Proposal { round, .. } => {
+                    recovered.round = recovered.round.max(round);
+                    recovered.proposal_rounds.insert(round);
+                }
+                WalEntry::Lock(value, round) => {
+                    recovered.locked_value_round = Some((value, round));
+                }
+            }
+        }
+        Some(recovered)
+    }
+}
+
+/// The directory of the write-ahead logs, holding a file per height.
+#[derive(Debug)]
+pub(crate) struct WriteAheadLog {
+    dir: PathBuf,
+}
+
+impl WriteAheadLog {
+    pub(crate) fn open(dir: &Path) -> Result<Self, ConsensusError> {
+        fs::create_dir_all(dir).map_err(wal_error)?;
+        Ok(WriteAheadLog { dir: dir.to_path_buf() })
+    }
+
+    /// Opens the log of `height` for appending. Returns the progress which was logged for the
+    /// height before a restart, if any.
+    pub(crate) fn open_height(
+        &self,
+        height: BlockNumber,
+    ) -> Result<(HeightWriteAheadLog, Option<RecoveredHeight>), ConsensusError> {
+        let path = self.height_path(height);
+        let is_new = !path.exists();
+        let mut file = OpenOptions::new()
+            .read(true)
+            .append(true)
+            .create(true)
+            .open(&path)
+            .map_err(wal_error)?;
+        if is_new {
+            // Make sure the file itself survives a crash, not just its content.
+            File::open(&self.dir).and_then(|dir| dir.sync_all()).map_err(wal_error)?;
+        }
+        let entries = read_entries(&mut file)?;
+        let recovered = RecoveredHeight::from_entries(entries);
+        if let Some(recovered) = &recovered {
+            info!(
+                "Recovered the progress of height {height} from the write-ahead log: {recovered:?}"
+            );
+        }
+
+        let (round, locked_value_round) = recovered
+            .as_ref()
+            .map_or((0, None), |recovered| (recovered.round, recovered.locked_value_round));
+        Ok((HeightWriteAheadLog { file, round, locked_value_round }, recovered))
+    }
+
+    /// Removes the logs of all heights below `height`, which are no longer needed once the node
+    /// moves on from them.
+    pub(crate) fn prune(&self, height: BlockNumber) -> Result<(), ConsensusError> {
+        for dir_entry in fs::read_dir(&self.dir).map_err(wal_error)? {
+            let path = dir_entry.map_err(wal_error)?.path();
+            if path.extension().is_none_or(|extension| extension != FILE_EXTENSION) {
+                continue;
+            }
+            let Some(log_height) =
+                path.file_stem().and_then(|stem| stem.to_str()?.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if log_height < height.0 {
+                fs::remove_file(&path).map_err(wal_error)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn height_path(&self, height: BlockNumber) -> PathBuf {
+        self.dir.join(format!("{}.{FILE_EXTENSION}", height.0))
+    }
+}
+
+/// The log of a single height. Entries are appended as JSON lines, each synced to disk before
+/// `append` returns.
+#[derive(Debug)]
+pub(crate) struct HeightWriteAheadLog {
+    file: File,
+    // The latest logged round and lock, so that only changes to them are logged.
+    round: Round,
+    locked_value_round: Option<(ProposalCommitment, Round)>,
+}
+
+impl HeightWriteAheadLog {
+    pub(crate) fn append(&mut self, entry: &WalEntry) -> Result<(), ConsensusError> {
+        let mut line = serde_json::to_vec(entry)
+            .map_err(|err| ConsensusError::WriteAheadLogError(err.to_string()))?;
+        line.push(b'\n');
+        self.file.write_all(&line).and_then(|()| self.file.sync_data()).map_err(wal_error)
+    }
+
+    /// Logs the round and lock of the node, if they changed since they were last logged.
+    pub(crate) fn log_progress(
+        &mut self,
+        round: Round,
+        locked_value_round: Option<(ProposalCommitment, Round)>,
+    ) -> Result<(), ConsensusError> {
+        if round > self.round {
+            self.append(&WalEntry::Round(round))?;
+            self.round = round;
+        }
+        if let Some((value, lock_round)) = locked_value_round {
+            if locked_value_round != self.locked_value_round {
+                self.append(&WalEntry::Lock(value, lock_round))?;
+                self.locked_value_round = locked_value_round;
+            }
+        }
+        Ok(())
+    }
+}
+
+// Reads all the entries in the file. A trailing partial entry, left by a crash mid-write, is
+// discarded; it was never synced, so its effect was never visible to other nodes.
+fn read_entries(file: &mut File) -> Result<Vec<WalEntry>, ConsensusError> {
+    let mut content = Vec::new();
+    file.read_to_end(&mut content).map_err(wal_error)?;
+    let complete_len = content.iter().rposition(|byte| *byte == b'\n').map_or(0, |i| i + 1);
+    if complete_len < content.len() {
+        warn!("Discarding a partially written entry of the write-ahead log.");
+        file.set_len(u64::try_from(complete_len).expect("File length should fit in u64."))
+            .and_then(|()| file.sync_data())
+            .map_err(wal_error)?;
+    }
+
+    content[..complete_len]
+        .split(|byte| *byte == b'\n')
+        .filter(|line| !line.is_empty())
+        .map(|line| {
+            serde_json::from_slice(line).map_err(|err| {
+                ConsensusError::WriteAheadLogError(format!(
+                    "Corrupted write-ahead log entry: {err}"
+                ))
+            })
+        })
+        .collect()
+}
+
+fn wal_error(err: io::Error) -> ConsensusError {
+    ConsensusError::WriteAheadLogError(err.to_string())
+}
@@ -0,0 +1,125 @@
+use std::collections::BTreeSet;
+use std::fs::OpenOptions;
+use std::io::Write;
+
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
+use lazy_static::lazy_static;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+use tempfile::tempdir;
+
+use crate::test_utils::{precommit, prevote};
+use crate::types::ValidatorId;
+use crate::write_ahead_log::{RecoveredHeight, WalEntry, WriteAheadLog};
+
+lazy_static! {
+    static ref VALIDATOR_ID: ValidatorId = DEFAULT_VALIDATOR_ID.into();
+}
+
+const HEIGHT: BlockNumber = BlockNumber(3);
+
+#[test]
+fn new_height_has_nothing_to_recover() {
+    let dir = tempdir().unwrap();
+    let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+
+    let (_, recovered_height) = write_ahead_log.open_height(HEIGHT).unwrap();
+
+    assert_eq!(recovered_height, None);
+}
+
+#[test]
+fn recover_after_restart() {
+    let dir = tempdir().unwrap();
+    let locked_value_round = Some((BlockHash(Felt::ONE), 1));
+    let votes = vec![
+        prevote(Some(Felt::ONE), HEIGHT.0, 0, *VALIDATOR_ID),
+        precommit(None, HEIGHT.0, 0, *VALIDATOR_ID),
+        prevote(Some(Felt::ONE), HEIGHT.0, 1, *VALIDATOR_ID),
+        precommit(Some(Felt::ONE), HEIGHT.0, 1, *VALIDATOR_ID),
+    ];
+    {
+        let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+        let (mut height_log, _) = write_ahead_log.open_height(HEIGHT).unwrap();
+        height_log
+            .append(&WalEntry::Proposal { round: 0, proposal_commitment: None, valid_round: None })
+            .unwrap();
+        height_log.append(&WalEntry::Vote(votes[0].clone())).unwrap();
+        height_log.append(&WalEntry::Vote(votes[1].clone())).unwrap();
+        height_log.log_progress(1, None).unwrap();
+        height_log.append(&WalEntry::Vote(votes[2].clone())).unwrap();
+        height_log.log_progress(1, locked_value_round).unwrap();
+        height_log.append(&WalEntry::Vote(votes[3].clone())).unwrap();
+        height_log.log_progress(2, locked_value_round).unwrap();
+    }
+
+    let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+    let (_, recovered_height) = write_ahead_log.open_height(HEIGHT).unwrap();
+
+    assert_eq!(
+        recovered_height,
+        Some(RecoveredHeight {
+            round: 2,
+            votes,
+            proposal_rounds: BTreeSet::from([0]),
+            locked_value_round
+        })
+    );
+}
+
+#[test]
+fn unchanged_progress_is_not_logged() {
+    let dir = tempdir().unwrap();
+    let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+    let (mut height_log, _) = write_ahead_log.open_height(HEIGHT).unwrap();
+
+    height_log.log_progress(0, None).unwrap();
+
+    let (_, recovered_height) = write_ahead_log.open_height(HEIGHT).unwrap();
+    assert_eq!(recovered_height, None);
+}
+
+#[test]
+fn partially_written_entry_is_discarded() {
+    let dir = tempdir().unwrap();
+    let vote = prevote(Some(Felt::ONE), HEIGHT.0, 0, *VALIDATOR_ID);
+    let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+    {
+        let (mut height_log, _) = write_ahead_log.open_height(HEIGHT).unwrap();
+        height_log.append(&WalEntry::Vote(vote.clone())).unwrap();
+    }
+    // Simulate a crash in the middle of writing the next entry.
+    let mut file =
+        OpenOptions::new().append(true).open(dir.path().join(format!("{}.wal", HEIGHT.0))).unwrap();
+    file.write_all(b"{\"Vote\":{\"vote_ty").unwrap();
+
+    let (mut height_log, recovered_height) = write_ahead_log.open_height(HEIGHT).unwrap();
+    assert_eq!(recovered_height.unwrap().votes, vec![vote.clone()]);
+
+    // The log remains usable after discarding the partial entry.
+    height_log.log_progress(1, None).unwrap();
+    let (_, recovered_height) = write_ahead_log.open_height(HEIGHT).unwrap();
+    assert_eq!(
+        recovered_height,
+        Some(RecoveredHeight { round: 1, votes: vec![vote], ..Default::default() })
+    );
+}
+
+#[test]
+fn prune_lower_heights() {
+    let dir = tempdir().unwrap();
+    let write_ahead_log = WriteAheadLog::open(dir.path()).unwrap();
+    for height in [HEIGHT.prev().unwrap(), HEIGHT, HEIGHT.unchecked_next()] {
+        let (mut height_log, _) = write_ahead_log.open_height(height).unwrap();
+        height_log.log_progress(1, None).unwrap();
+    }
+
+    write_ahead_log.prune(HEIGHT).unwrap();
+
+    let (_, recovered_height) = write_ahead_log.open_height(HEIGHT.prev().unwrap()).unwrap();
+    assert_eq!(recovered_height, None);
+    for height in [HEIGHT, HEIGHT.unchecked_next()] {
+        let (_, recovered_height) = write_ahead_log.open_height(height).unwrap();
+        assert_eq!(recovered_height.unwrap().round, 1);
+    }
+}
@@ -7,12 +7,15 @@ use apollo_consensus_orchestrator::cende::CendeConfig;
 use apollo_consensus_orchestrator::config::ContextConfig;
 use apollo_network::NetworkConfig;
 use apollo_reverts::RevertConfig;
+use apollo_staking::staking_manager::StakingManagerConfig;
 use serde::{Deserialize, Serialize};
 use starknet_api::block::BlockNumber;
-use validator::Validate;
+use starknet_api::core::ContractAddress;
+use validator::{Validate, ValidationError};
 
 /// The consensus manager related configuration.
 #[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+#[validate(schema(function = "validate_consensus_manager_config"))]
 pub struct ConsensusManagerConfig {
     pub consensus_manager_config: ConsensusConfig,
     pub context_config: ContextConfig,
@@ -21,6 +24,8 @@ pub struct ConsensusManagerConfig {
     pub network_config: NetworkConfig,
     pub cende_config: CendeConfig,
     pub revert_config: RevertConfig,
+    #[validate]
+    pub staking_manager_config: StakingManagerConfig,
     pub votes_topic: String,
     pub proposals_topic: String,
     pub broadcast_buffer_size: usize,
@@ -76,6 +81,10 @@ impl SerializeConfig for ConsensusManagerConfig {
         config.extend(prepend_sub_config_name(self.cende_config.dump(), "cende_config"));
         config.extend(prepend_sub_config_name(self.network_config.dump(), "network_config"));
         config.extend(prepend_sub_config_name(self.revert_config.dump(), "revert_config"));
+        config.extend(prepend_sub_config_name(
+            self.staking_manager_config.dump(),
+            "staking_manager_config",
+        ));
         config
     }
 }
@@ -89,6 +98,7 @@ impl Default for ConsensusManagerConfig {
             cende_config: CendeConfig::default(),
             network_config: NetworkConfig::default(),
             revert_config: RevertConfig::default(),
+            staking_manager_config: StakingManagerConfig::default(),
             votes_topic: "consensus_votes".to_string(),
             proposals_topic: "consensus_proposals".to_string(),
             broadcast_buffer_size: 10000,
@@ -97,3 +107,19 @@ impl Default for ConsensusManagerConfig {
         }
     }
 }
+
+// The stakers are read from the staking contract unless they are set by config, so one of them must
+// be set.
+fn validate_consensus_manager_config(
+    config: &ConsensusManagerConfig,
+) -> Result<(), ValidationError> {
+    if config.context_config.stakers.is_empty()
+        && config.staking_manager_config.staking_contract_address == ContractAddress::default()
+    {
+        return Err(ValidationError::new(
+            "either context_config.stakers or staking_manager_config.staking_contract_address \
+             must be set",
+        ));
+    }
+    Ok(())
+}
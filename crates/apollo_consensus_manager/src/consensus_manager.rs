@@ -9,6 +9,7 @@ use apollo_batcher_types::batcher_types::RevertBlockInput;
 use apollo_batcher_types::communication::SharedBatcherClient;
 use apollo_class_manager_types::transaction_converter::TransactionConverter;
 use apollo_class_manager_types::SharedClassManagerClient;
+use apollo_consensus::evidence::EvidenceStore;
 use apollo_consensus::stream_handler::StreamHandler;
 use apollo_consensus::types::ConsensusError;
 use apollo_consensus::votes_threshold::QuorumType;
@@ -26,9 +27,21 @@ use apollo_network::network_manager::{BroadcastTopicChannels, NetworkManager};
 use apollo_protobuf::consensus::{HeightAndRound, ProposalPart, StreamMessage, Vote};
 use apollo_reverts::revert_blocks_and_eternal_pending;
 use apollo_signature_manager_types::SharedSignatureManagerClient;
+use apollo_staking::committee_provider::{CommitteeProviderResult, ExecutionContext};
+use apollo_staking::staking_contract::{
+    ExecutionStakingContract,
+    StakingContract,
+    StaticStakingContract,
+};
+use apollo_staking::staking_manager::StakingManager;
+use apollo_staking::sync_state_reader::SyncStateReader;
+use apollo_staking::utils::BlockPseudorandomGenerator;
 use apollo_state_sync_types::communication::SharedStateSyncClient;
 use apollo_time::time::DefaultClock;
 use async_trait::async_trait;
+use blockifier::blockifier_versioned_constants::VersionedConstants;
+use blockifier::bouncer::BouncerConfig;
+use blockifier::context::BlockContext;
 use futures::channel::mpsc;
 use starknet_api::block::BlockNumber;
 use tracing::{info, info_span, Instrument};
@@ -51,6 +64,7 @@ pub struct ConsensusManager {
     pub class_manager_client: SharedClassManagerClient,
     pub signature_manager_client: SharedSignatureManagerClient,
     l1_gas_price_provider: Arc<dyn L1GasPriceProviderClient>,
+    evidence_store: EvidenceStore,
 }
 
 impl ConsensusManager {
@@ -62,6 +76,8 @@ impl ConsensusManager {
         signature_manager_client: SharedSignatureManagerClient,
         l1_gas_price_provider: Arc<dyn L1GasPriceProviderClient>,
     ) -> Self {
+        let evidence_store = EvidenceStore::open(&config.consensus_manager_config.evidence)
+            .expect("Failed to open the equivocation evidence store");
         Self {
             config,
             batcher_client,
@@ -69,9 +85,15 @@ impl ConsensusManager {
             class_manager_client,
             signature_manager_client,
             l1_gas_price_provider,
+            evidence_store,
         }
     }
 
+    /// The evidence of the equivocations detected by consensus, which is updated while it runs.
+    pub fn evidence_store(&self) -> &EvidenceStore {
+        &self.evidence_store
+    }
+
     pub async fn run(&self) -> Result<(), ConsensusError> {
         if self.config.revert_config.should_revert {
             self.revert_batcher_blocks(self.config.revert_config.revert_up_to_and_including).await;
@@ -165,6 +187,13 @@ impl ConsensusManager {
                 clock: Arc::new(DefaultClock),
                 outbound_proposal_sender: outbound_internal_sender,
                 vote_broadcast_client: votes_broadcast_channels.broadcast_topic_client.clone(),
+                signature_manager_client: Arc::clone(&self.signature_manager_client),
+                committee_provider: Arc::new(StakingManager::new(
+                    self.staking_contract(),
+                    Box::new(BlockPseudorandomGenerator),
+                    Arc::clone(&self.state_sync_client),
+                    self.config.staking_manager_config.clone(),
+                )),
             },
         );
 
@@ -187,6 +216,8 @@ impl ConsensusManager {
             sync_retry_interval: self.config.consensus_manager_config.sync_retry_interval,
             quorum_type,
             future_msg_limit: self.config.consensus_manager_config.future_msg_limit,
+            write_ahead_log: self.config.consensus_manager_config.write_ahead_log.clone(),
+            evidence_store: self.evidence_store.clone(),
         };
         let consensus_fut = apollo_consensus::run_consensus(
             run_consensus_args,
@@ -211,6 +242,45 @@ impl ConsensusManager {
         }
     }
 
+    // Returns the source of the stakers: the staking contract, read over the state of the latest
+    // synced block, unless the stakers are set by config.
+    fn staking_contract(&self) -> Arc<dyn StakingContract> {
+        let context_config = &self.config.context_config;
+        if !context_config.stakers.is_empty() {
+            return Arc::new(StaticStakingContract::new(context_config.stakers.clone()));
+        }
+
+        let staking_manager_config = &self.config.staking_manager_config;
+        let state_sync_client = Arc::clone(&self.state_sync_client);
+        let class_manager_client = Arc::clone(&self.class_manager_client);
+        let runtime = tokio::runtime::Handle::current();
+        let chain_info = staking_manager_config.chain_info.clone();
+        let versioned_constants = VersionedConstants::get_versioned_constants(
+            staking_manager_config.versioned_constants_overrides.clone(),
+        );
+        let execution_context_fn = move || {
+            let state_reader = SyncStateReader::from_latest_block(
+                Arc::clone(&state_sync_client),
+                Arc::clone(&class_manager_client),
+                runtime.clone(),
+            )?;
+            let block_context = BlockContext::new(
+                state_reader.get_block_info()?,
+                chain_info.clone(),
+                versioned_constants.clone(),
+                BouncerConfig::max(),
+            );
+            CommitteeProviderResult::Ok(ExecutionContext {
+                state_reader,
+                block_context: Arc::new(block_context),
+            })
+        };
+        Arc::new(ExecutionStakingContract::new(
+            staking_manager_config.staking_contract_address,
+            execution_context_fn,
+        ))
+    }
+
     // Performs reverts to the batcher.
     async fn revert_batcher_blocks(&self, revert_up_to_and_including: BlockNumber) {
         // If we revert all blocks up to height X (including), the new height marker will be X.
@@ -9,13 +9,22 @@ use apollo_signature_manager_types::MockSignatureManagerClient;
 use apollo_state_sync_types::communication::MockStateSyncClient;
 use mockall::predicate::eq;
 use starknet_api::block::BlockNumber;
+use starknet_api::core::ContractAddress;
 use tokio::time::{timeout, Duration};
+use validator::Validate;
 
 use crate::config::ConsensusManagerConfig;
 use crate::consensus_manager::ConsensusManager;
 
 const BATCHER_HEIGHT: BlockNumber = BlockNumber(10);
 
+// The tests don't persist the evidence of equivocations.
+fn config_without_evidence_persistence() -> ConsensusManagerConfig {
+    let mut config = ConsensusManagerConfig::default();
+    config.consensus_manager_config.evidence.persist = false;
+    config
+}
+
 #[tokio::test]
 async fn revert_batcher_blocks() {
     const REVERT_UP_TO_AND_INCLUDING_HEIGHT: BlockNumber = BlockNumber(7);
@@ -40,7 +49,7 @@ async fn revert_batcher_blocks() {
             revert_up_to_and_including: REVERT_UP_TO_AND_INCLUDING_HEIGHT,
             should_revert: true,
         },
-        ..Default::default()
+        ..config_without_evidence_persistence()
     };
 
     let consensus_manager = ConsensusManager::new(
@@ -63,7 +72,7 @@ async fn no_reverts_without_config() {
     mock_batcher.expect_get_height().returning(|| Ok(GetHeightResponse { height: BlockNumber(0) }));
 
     let consensus_manager = ConsensusManager::new(
-        ConsensusManagerConfig::default(),
+        config_without_evidence_persistence(),
         Arc::new(mock_batcher),
         Arc::new(MockStateSyncClient::new()),
         Arc::new(EmptyClassManagerClient),
@@ -74,3 +83,15 @@ async fn no_reverts_without_config() {
     // TODO(Shahak, dvir): try to solve this better (the test will take 100 milliseconds to run).
     timeout(Duration::from_millis(100), consensus_manager.run()).await.unwrap_err();
 }
+
+#[test]
+fn config_requires_stakers_or_staking_contract() {
+    let mut config = ConsensusManagerConfig::default();
+    assert!(config.validate().is_ok());
+
+    config.context_config.stakers.clear();
+    assert!(config.validate().is_err());
+
+    config.staking_manager_config.staking_contract_address = ContractAddress::from(1_u64);
+    assert!(config.validate().is_ok());
+}
@@ -20,11 +20,11 @@ use apollo_consensus::types::{ProposalCommitment, Round};
 use apollo_l1_gas_price_types::errors::{EthToStrkOracleClientError, L1GasPriceClientError};
 use apollo_protobuf::consensus::{
     ConsensusBlockInfo,
-    ProposalFin,
     ProposalInit,
     ProposalPart,
     TransactionBatch,
 };
+use apollo_signature_manager_types::{SignatureManagerClient, SignatureManagerClientError};
 use apollo_time::time::{Clock, DateTime};
 use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
@@ -41,6 +41,8 @@ use crate::utils::{
     convert_to_sn_api_block_info,
     get_oracle_rate_and_prices,
     retrospective_block_hash,
+    sign_proposal_init,
+    signed_proposal_fin,
     truncate_to_executed_txs,
     GasPriceParams,
     StreamSender,
@@ -93,6 +95,8 @@ pub(crate) enum BuildProposalError {
     TransactionConverterError(#[from] TransactionConverterError),
     #[error("Block info conversion error: {0}")]
     BlockInfoConversion(#[from] StarknetApiError),
+    #[error("Failed to sign the proposal: {0}")]
+    SignatureManager(#[from] SignatureManagerClientError),
 }
 
 // Handles building a new proposal without blocking consensus:
@@ -101,8 +105,10 @@ pub(crate) async fn build_proposal(
 ) -> BuildProposalResult<ProposalCommitment> {
     let batcher_deadline = args.deps.clock.now() + args.batcher_timeout;
     let block_info = initiate_build(&args).await?;
+    let signed_init =
+        sign_proposal_init(args.deps.signature_manager_client.as_ref(), args.proposal_init).await?;
     args.stream_sender
-        .send(ProposalPart::Init(args.proposal_init))
+        .send(ProposalPart::Init(signed_init))
         .await
         .expect("Failed to send proposal init");
     args.stream_sender
@@ -111,8 +117,10 @@ pub(crate) async fn build_proposal(
         .expect("Failed to send block info");
 
     let (proposal_commitment, content) = get_proposal_content(
+        args.proposal_init,
         args.proposal_id,
         args.deps.batcher.as_ref(),
+        args.deps.signature_manager_client.as_ref(),
         args.stream_sender,
         args.cende_write_success,
         args.deps.transaction_converter,
@@ -185,8 +193,10 @@ async fn initiate_build(args: &ProposalBuildArguments) -> BuildProposalResult<Co
 // separately.
 #[allow(clippy::too_many_arguments)]
 async fn get_proposal_content(
+    proposal_init: ProposalInit,
     proposal_id: ProposalId,
     batcher: &dyn BatcherClient,
+    signature_manager_client: &dyn SignatureManagerClient,
     mut stream_sender: StreamSender,
     cende_write_success: AbortOnDropHandle<bool>,
     transaction_converter: Arc<dyn TransactionConverterTrait>,
@@ -280,7 +290,12 @@ async fn get_proposal_content(
                     .send(ProposalPart::ExecutedTransactionCount(final_n_executed_txs_u64))
                     .await
                     .expect("Failed to broadcast executed transaction count");
-                let fin = ProposalFin { proposal_commitment };
+                let fin = signed_proposal_fin(
+                    signature_manager_client,
+                    proposal_init,
+                    proposal_commitment,
+                )
+                .await?;
                 info!("Sending fin={fin:?}");
                 stream_sender
                     .send(ProposalPart::Fin(fin))
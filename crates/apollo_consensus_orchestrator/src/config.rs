@@ -2,11 +2,16 @@ use std::collections::BTreeMap;
 use std::fmt::Debug;
 use std::time::Duration;
 
-use apollo_config::converters::deserialize_milliseconds_to_duration;
+use apollo_config::converters::{deserialize_milliseconds_to_duration, deserialize_vec};
 use apollo_config::dumping::{ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
+use apollo_signature_manager::signature_manager::LocalKeyStore;
+use apollo_staking::committee_provider::Staker;
+use apollo_staking::staking_contract::ConfiguredStaker;
 use serde::{Deserialize, Serialize};
 use starknet_api::core::{ChainId, ContractAddress};
+use starknet_api::staking::StakingWeight;
 use validator::Validate;
 
 const GWEI_FACTOR: u128 = u128::pow(10, 9);
@@ -19,6 +24,10 @@ pub struct ContextConfig {
     pub proposal_buffer_size: usize,
     /// The number of validators.
     pub num_validators: u64,
+    /// The stakers of each epoch, from which the committees are selected. If empty, the stakers
+    /// are read from the staking contract.
+    #[serde(deserialize_with = "deserialize_vec")]
+    pub stakers: Vec<ConfiguredStaker>,
     /// The chain id of the Starknet chain.
     pub chain_id: ChainId,
     /// Maximum allowed deviation (seconds) of a proposed block's timestamp from the current time.
@@ -71,6 +80,14 @@ impl SerializeConfig for ContextConfig {
                 "The number of validators.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "stakers",
+                &self.stakers.iter().map(ToString::to_string).collect::<Vec<_>>().join(" "),
+                "Space separated stakers, each as 'start_epoch:address:weight:public_key'. The \
+                 stakers of an epoch are the ones with the latest start epoch which isn't after \
+                 it. If empty, the stakers are read from the staking contract.",
+                ParamPrivacyInput::Public,
+            ),
             ser_param(
                 "chain_id",
                 &self.chain_id,
@@ -162,6 +179,15 @@ impl Default for ContextConfig {
         Self {
             proposal_buffer_size: 100,
             num_validators: 1,
+            // The default validator, signing with the testing key.
+            stakers: vec![ConfiguredStaker {
+                start_epoch: 0,
+                staker: Staker {
+                    address: ContractAddress::from(DEFAULT_VALIDATOR_ID),
+                    weight: StakingWeight(1),
+                    public_key: LocalKeyStore::new_for_testing().public_key.0,
+                },
+            }],
             chain_id: ChainId::Mainnet,
             block_timestamp_window_seconds: 1,
             l1_da_mode: true,
@@ -23,20 +23,22 @@ use apollo_consensus::types::{
     ConsensusError,
     ProposalCommitment,
     Round,
+    Validator,
     ValidatorId,
+    ValidatorSet,
 };
 use apollo_l1_gas_price_types::{L1GasPriceProviderClient, DEFAULT_ETH_TO_FRI_RATE};
 use apollo_network::network_manager::{BroadcastTopicClient, BroadcastTopicClientTrait};
 use apollo_protobuf::consensus::{
     ConsensusBlockInfo,
     HeightAndRound,
-    ProposalFin,
     ProposalInit,
     ProposalPart,
     TransactionBatch,
     Vote,
-    DEFAULT_VALIDATOR_ID,
 };
+use apollo_signature_manager_types::SharedSignatureManagerClient;
+use apollo_staking::committee_provider::SharedCommitteeProvider;
 use apollo_state_sync_types::communication::{StateSyncClient, StateSyncClientError};
 use apollo_state_sync_types::errors::StateSyncError;
 use apollo_state_sync_types::state_sync_types::SyncBlock;
@@ -55,6 +57,7 @@ use starknet_api::block::{
 };
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
 use starknet_api::core::SequencerContractAddress;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::transaction::TransactionHash;
 use tokio::task::JoinHandle;
@@ -68,7 +71,14 @@ use crate::config::ContextConfig;
 use crate::fee_market::{calculate_next_base_gas_price, FeeMarketInfo};
 use crate::metrics::{register_metrics, CONSENSUS_L2_GAS_PRICE};
 use crate::orchestrator_versioned_constants::VersionedConstants;
-use crate::utils::{convert_to_sn_api_block_info, GasPriceParams, StreamSender};
+use crate::utils::{
+    convert_to_sn_api_block_info,
+    sign_proposal_init,
+    sign_vote,
+    signed_proposal_fin,
+    GasPriceParams,
+    StreamSender,
+};
 use crate::validate_proposal::{
     validate_proposal,
     BlockInfoValidation,
@@ -76,7 +86,12 @@ use crate::validate_proposal::{
     ValidateProposalError,
 };
 
-type ValidationParams = (BlockNumber, ValidatorId, Duration, mpsc::Receiver<ProposalPart>);
+type ValidationParams = (ProposalInit, Duration, mpsc::Receiver<ProposalPart>);
+
+// The interval between attempts to fetch the committee of a height.
+const COMMITTEE_RETRY_INTERVAL: Duration = Duration::from_millis(100);
+// The number of attempts to fetch the committee of a height before giving up on it.
+const COMMITTEE_FETCH_ATTEMPTS: usize = 50;
 
 type HeightToIdToContent = BTreeMap<
     BlockNumber,
@@ -136,7 +151,6 @@ impl BuiltProposals {
 pub struct SequencerConsensusContext {
     config: ContextConfig,
     deps: SequencerConsensusContextDeps,
-    validators: Vec<ValidatorId>,
     // Proposal building/validating returns immediately, leaving the actual processing to a spawned
     // task. The spawned task processes the proposal asynchronously and updates the
     // valid_proposals map upon completion, ensuring consistency across tasks.
@@ -172,12 +186,15 @@ pub struct SequencerConsensusContextDeps {
     pub outbound_proposal_sender: mpsc::Sender<(HeightAndRound, mpsc::Receiver<ProposalPart>)>,
     // Used to broadcast votes to other consensus nodes.
     pub vote_broadcast_client: BroadcastTopicClient<Vote>,
+    // Used to sign the votes and proposals of this node.
+    pub signature_manager_client: SharedSignatureManagerClient,
+    // Provides the committee and proposers of each height, according to the staking state.
+    pub committee_provider: SharedCommitteeProvider,
 }
 
 impl SequencerConsensusContext {
     pub fn new(config: ContextConfig, deps: SequencerConsensusContextDeps) -> Self {
         register_metrics();
-        let num_validators = config.num_validators;
         let l1_da_mode = if config.l1_da_mode {
             L1DataAvailabilityMode::Blob
         } else {
@@ -186,10 +203,6 @@ impl SequencerConsensusContext {
         Self {
             config,
             deps,
-            // TODO(Matan): Set the actual validator IDs (contract addresses).
-            validators: (0..num_validators)
-                .map(|i| ValidatorId::from(DEFAULT_VALIDATOR_ID + i))
-                .collect(),
             valid_proposals: Arc::new(Mutex::new(BuiltProposals::new())),
             proposal_id: 0,
             current_height: None,
@@ -319,10 +332,7 @@ impl ConsensusContext for SequencerConsensusContext {
                 trace!("Queueing proposal for future round.");
                 self.queued_proposals.insert(
                     proposal_init.round,
-                    (
-                        (proposal_init.height, proposal_init.proposer, timeout, content_receiver),
-                        fin_sender,
-                    ),
+                    ((proposal_init, timeout, content_receiver), fin_sender),
                 );
                 fin_receiver
             }
@@ -336,7 +346,7 @@ impl ConsensusContext for SequencerConsensusContext {
                 };
                 self.validate_current_round_proposal(
                     block_info_validation,
-                    proposal_init.proposer,
+                    proposal_init,
                     timeout,
                     self.config.validate_proposal_margin_millis,
                     content_receiver,
@@ -359,11 +369,20 @@ impl ConsensusContext for SequencerConsensusContext {
             .clone();
 
         let transaction_converter = self.deps.transaction_converter.clone();
+        let signature_manager_client = Arc::clone(&self.deps.signature_manager_client);
         let mut stream_sender = self.start_stream(HeightAndRound(height.0, init.round)).await;
         tokio::spawn(
             async move {
+                let signed_init =
+                    match sign_proposal_init(signature_manager_client.as_ref(), init).await {
+                        Ok(signed_init) => signed_init,
+                        Err(e) => {
+                            error!("Failed to sign the proposal init during repropose: {e:?}");
+                            return;
+                        }
+                    };
                 stream_sender
-                    .send(ProposalPart::Init(init))
+                    .send(ProposalPart::Init(signed_init))
                     .await
                     .expect("Failed to send proposal init");
                 stream_sender
@@ -400,8 +419,16 @@ impl ConsensusContext for SequencerConsensusContext {
                     ))
                     .await
                     .expect("Failed to broadcast executed transaction count");
+                let fin =
+                    match signed_proposal_fin(signature_manager_client.as_ref(), init, id).await {
+                        Ok(fin) => fin,
+                        Err(e) => {
+                            error!("Failed to sign the proposal fin during repropose: {e:?}");
+                            return;
+                        }
+                    };
                 stream_sender
-                    .send(ProposalPart::Fin(ProposalFin { proposal_commitment: id }))
+                    .send(ProposalPart::Fin(fin))
                     .await
                     .expect("Failed to broadcast proposal fin");
             }
@@ -409,20 +436,51 @@ impl ConsensusContext for SequencerConsensusContext {
         );
     }
 
-    async fn validators(&self, _height: BlockNumber) -> Vec<ValidatorId> {
-        self.validators.clone()
+    async fn validators(&self, height: BlockNumber) -> Result<ValidatorSet, ConsensusError> {
+        // The committee may not be available yet (e.g. the state is still syncing), so retry a
+        // bounded number of times before failing.
+        let mut attempt = 1;
+        let committee = loop {
+            match self.deps.committee_provider.get_committee(height).await {
+                Ok(committee) => break committee,
+                Err(e) if attempt >= COMMITTEE_FETCH_ATTEMPTS => {
+                    return Err(ConsensusError::CommitteeError(format!(
+                        "Failed to get the committee of height {height} after {attempt} attempts: \
+                         {e:?}"
+                    )));
+                }
+                Err(e) => {
+                    error!("Failed to get the committee of height {height}: {e:?}");
+                }
+            }
+            attempt += 1;
+            tokio::time::sleep(COMMITTEE_RETRY_INTERVAL).await;
+        };
+        Ok(committee
+            .iter()
+            .map(|staker| {
+                let validator =
+                    Validator { weight: staker.weight, public_key: PublicKey(staker.public_key) };
+                (staker.address, validator)
+            })
+            .collect())
     }
 
-    fn proposer(&self, height: BlockNumber, round: Round) -> ValidatorId {
-        let height: usize = height.0.try_into().expect("Cannot convert to usize");
-        let round: usize = round.try_into().expect("Cannot convert to usize");
-        *self
-            .validators
-            .get((height + round) % self.validators.len())
-            .expect("There should be at least one validator")
+    fn proposer(&self, height: BlockNumber, round: Round) -> Result<ValidatorId, ConsensusError> {
+        // Consensus gets the validators of a height before selecting its proposers, which fetches
+        // the committee. The committee provider only keeps it for recent heights, so this may still
+        // fail, for example for a height which consensus is long past.
+        self.deps.committee_provider.get_proposer(height, round).map_err(|e| {
+            ConsensusError::CommitteeError(format!(
+                "Failed to get the proposer of height {height}, round {round}: {e:?}"
+            ))
+        })
     }
 
     async fn broadcast(&mut self, message: Vote) -> Result<(), ConsensusError> {
+        let message = sign_vote(self.deps.signature_manager_client.as_ref(), message)
+            .await
+            .map_err(|e| ConsensusError::SigningError(e.to_string()))?;
         trace!("Broadcasting message: {message:?}");
         self.deps.vote_broadcast_client.broadcast_message(message).await?;
         Ok(())
@@ -677,11 +735,11 @@ impl ConsensusContext for SequencerConsensusContext {
             }
         }
         // Validate the proposal for the current round if exists.
-        let Some(((height, validator, timeout, content), fin_sender)) = to_process else {
+        let Some(((proposal_init, timeout, content), fin_sender)) = to_process else {
             return;
         };
         let block_info_validation = BlockInfoValidation {
-            height,
+            height: proposal_init.height,
             block_timestamp_window_seconds: self.config.block_timestamp_window_seconds,
             previous_block_info: self.previous_block_info.clone(),
             l1_da_mode: self.l1_da_mode,
@@ -689,7 +747,7 @@ impl ConsensusContext for SequencerConsensusContext {
         };
         self.validate_current_round_proposal(
             block_info_validation,
-            validator,
+            proposal_init,
             timeout,
             self.config.validate_proposal_margin_millis,
             content,
@@ -703,12 +761,28 @@ impl SequencerConsensusContext {
     async fn validate_current_round_proposal(
         &mut self,
         block_info_validation: BlockInfoValidation,
-        proposer: ValidatorId,
+        proposal_init: ProposalInit,
         timeout: Duration,
         batcher_timeout_margin: Duration,
         content_receiver: mpsc::Receiver<ProposalPart>,
         fin_sender: oneshot::Sender<ProposalCommitment>,
     ) {
+        let proposer = proposal_init.proposer;
+        let validators = match self.validators(proposal_init.height).await {
+            Ok(validators) => validators,
+            Err(e) => {
+                // Dropping `fin_sender` marks the proposal as invalid.
+                warn!(%proposer, "Dropping a proposal whose validators can't be determined: {e}");
+                return;
+            }
+        };
+        let Some(proposer_public_key) =
+            validators.get(&proposer).map(|validator| validator.public_key)
+        else {
+            // Dropping `fin_sender` marks the proposal as invalid.
+            warn!(%proposer, "Dropping a proposal from a proposer which is not a validator.");
+            return;
+        };
         let proposal_id = ProposalId(self.proposal_id);
         self.proposal_id += 1;
         info!(?timeout, %proposal_id, %proposer, round=self.current_round, "Validating proposal.");
@@ -728,6 +802,8 @@ impl SequencerConsensusContext {
         };
         let args = ProposalValidateArguments {
             deps: self.deps.clone(),
+            proposal_init,
+            proposer_public_key,
             block_info_validation,
             proposal_id,
             timeout,
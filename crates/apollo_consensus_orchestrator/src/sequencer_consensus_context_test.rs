@@ -5,15 +5,17 @@ use std::vec;
 use apollo_batcher_types::batcher_types::{CentralObjects, DecisionReachedResponse};
 use apollo_batcher_types::communication::BatcherClientError;
 use apollo_batcher_types::errors::BatcherError;
-use apollo_consensus::types::{ConsensusContext, Round};
+use apollo_consensus::types::{ConsensusContext, ConsensusError, Round};
 use apollo_l1_gas_price_types::errors::{
     EthToStrkOracleClientError,
     L1GasPriceClientError,
     L1GasPriceProviderError,
 };
 use apollo_l1_gas_price_types::{MockL1GasPriceProviderClient, PriceInfo, DEFAULT_ETH_TO_FRI_RATE};
-use apollo_protobuf::consensus::{ProposalFin, ProposalInit, ProposalPart, TransactionBatch, Vote};
+use apollo_protobuf::consensus::{ProposalInit, ProposalPart, TransactionBatch, Vote};
+use apollo_staking::committee_provider::{CommitteeProviderError, MockCommitteeProvider};
 use apollo_time::time::MockClock;
+use assert_matches::assert_matches;
 use chrono::{TimeZone, Utc};
 use futures::channel::mpsc;
 use futures::channel::oneshot::Canceled;
@@ -35,9 +37,12 @@ use crate::cende::MockCendeContext;
 use crate::config::ContextConfig;
 use crate::metrics::CONSENSUS_L2_GAS_PRICE;
 use crate::orchestrator_versioned_constants::VersionedConstants;
+use crate::sequencer_consensus_context::COMMITTEE_FETCH_ATTEMPTS;
 use crate::test_utils::{
     block_info,
     create_test_and_network_deps,
+    signed_proposal_fin,
+    signed_proposal_init,
     ETH_TO_FRI_RATE,
     INTERNAL_TX_BATCH,
     STATE_DIFF_COMMITMENT,
@@ -82,9 +87,10 @@ async fn validate_proposal_success() {
         .await
         .unwrap();
     content_sender
-        .send(ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        }))
+        .send(ProposalPart::Fin(signed_proposal_fin(
+            ProposalInit::default(),
+            BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        )))
         .await
         .unwrap();
     let fin_receiver =
@@ -144,9 +150,10 @@ async fn validate_then_repropose(#[case] execute_all_txs: bool) {
         .send(ProposalPart::ExecutedTransactionCount(final_n_executed_txs.try_into().unwrap()))
         .await
         .unwrap();
-    let fin = ProposalPart::Fin(ProposalFin {
-        proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-    });
+    let fin = ProposalPart::Fin(signed_proposal_fin(
+        ProposalInit::default(),
+        BlockHash(STATE_DIFF_COMMITMENT.0.0),
+    ));
     content_sender.send(fin.clone()).await.unwrap();
     let fin_receiver =
         context.validate_proposal(ProposalInit::default(), TIMEOUT, content_receiver).await;
@@ -156,7 +163,7 @@ async fn validate_then_repropose(#[case] execute_all_txs: bool) {
     let init = ProposalInit { round: 1, ..Default::default() };
     context.repropose(BlockHash(STATE_DIFF_COMMITMENT.0.0), init).await;
     let (_, mut receiver) = network.outbound_proposal_receiver.next().await.unwrap();
-    assert_eq!(receiver.next().await.unwrap(), ProposalPart::Init(init));
+    assert_eq!(receiver.next().await.unwrap(), ProposalPart::Init(signed_proposal_init(init)));
     assert_eq!(receiver.next().await.unwrap(), block_info);
     assert_eq!(
         receiver.next().await.unwrap(),
@@ -166,7 +173,10 @@ async fn validate_then_repropose(#[case] execute_all_txs: bool) {
         receiver.next().await.unwrap(),
         ProposalPart::ExecutedTransactionCount(final_n_executed_txs.try_into().unwrap())
     );
-    assert_eq!(receiver.next().await.unwrap(), fin);
+    assert_eq!(
+        receiver.next().await.unwrap(),
+        ProposalPart::Fin(signed_proposal_fin(init, BlockHash(STATE_DIFF_COMMITMENT.0.0)))
+    );
     assert!(receiver.next().await.is_none());
 }
 
@@ -184,9 +194,10 @@ async fn proposals_from_different_rounds() {
         ProposalPart::Transactions(TransactionBatch { transactions: TX_BATCH.to_vec() });
     let prop_part_executed_count =
         ProposalPart::ExecutedTransactionCount(INTERNAL_TX_BATCH.len().try_into().unwrap());
-    let prop_part_fin = ProposalPart::Fin(ProposalFin {
-        proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-    });
+    let prop_part_fin = ProposalPart::Fin(signed_proposal_fin(
+        ProposalInit { round: 1, ..Default::default() },
+        BlockHash(STATE_DIFF_COMMITMENT.0.0),
+    ));
 
     // The proposal from the past round is ignored.
     let (mut content_sender, content_receiver) = mpsc::channel(context.config.proposal_buffer_size);
@@ -254,9 +265,10 @@ async fn interrupt_active_proposal() {
         .await
         .unwrap();
     content_sender_1
-        .send(ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        }))
+        .send(ProposalPart::Fin(signed_proposal_fin(
+            ProposalInit { round: 1, ..Default::default() },
+            BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        )))
         .await
         .unwrap();
     let fin_receiver_1 = context
@@ -284,7 +296,10 @@ async fn build_proposal() {
     let fin_receiver = context.build_proposal(ProposalInit::default(), TIMEOUT).await;
     // Test proposal parts.
     let (_, mut receiver) = network.outbound_proposal_receiver.next().await.unwrap();
-    assert_eq!(receiver.next().await.unwrap(), ProposalPart::Init(ProposalInit::default()));
+    assert_eq!(
+        receiver.next().await.unwrap(),
+        ProposalPart::Init(signed_proposal_init(ProposalInit::default()))
+    );
     let block_info = receiver.next().await.unwrap();
     let after: u64 =
         chrono::Utc::now().timestamp().try_into().expect("Timestamp conversion failed");
@@ -303,9 +318,10 @@ async fn build_proposal() {
     );
     assert_eq!(
         receiver.next().await.unwrap(),
-        ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        })
+        ProposalPart::Fin(signed_proposal_fin(
+            ProposalInit::default(),
+            BlockHash(STATE_DIFF_COMMITMENT.0.0)
+        ))
     );
     assert!(receiver.next().await.is_none());
     assert_eq!(fin_receiver.await.unwrap().0, STATE_DIFF_COMMITMENT.0.0);
@@ -399,26 +415,25 @@ async fn propose_then_repropose(#[case] execute_all_txs: bool) {
     let _txs = receiver.next().await.unwrap();
     let final_n_executed_txs = receiver.next().await.unwrap();
     assert!(matches!(final_n_executed_txs, ProposalPart::ExecutedTransactionCount(_)));
-    let fin = receiver.next().await.unwrap();
+    let _fin = receiver.next().await.unwrap();
     assert_eq!(fin_receiver.await.unwrap().0, STATE_DIFF_COMMITMENT.0.0);
 
     // Re-propose.
-    context
-        .repropose(
-            BlockHash(STATE_DIFF_COMMITMENT.0.0),
-            ProposalInit { round: 1, ..Default::default() },
-        )
-        .await;
-    // Re-propose sends the same proposal.
+    let init = ProposalInit { round: 1, ..Default::default() };
+    context.repropose(BlockHash(STATE_DIFF_COMMITMENT.0.0), init).await;
+    // Re-propose sends the same proposal, signed for the new round.
     let (_, mut receiver) = network.outbound_proposal_receiver.next().await.unwrap();
-    let _init = receiver.next().await.unwrap();
+    assert_eq!(receiver.next().await.unwrap(), ProposalPart::Init(signed_proposal_init(init)));
     assert_eq!(receiver.next().await.unwrap(), block_info);
 
     let reproposed_txs = ProposalPart::Transactions(TransactionBatch { transactions });
     assert_eq!(receiver.next().await.unwrap(), reproposed_txs);
 
     assert_eq!(receiver.next().await.unwrap(), final_n_executed_txs);
-    assert_eq!(receiver.next().await.unwrap(), fin);
+    assert_eq!(
+        receiver.next().await.unwrap(),
+        ProposalPart::Fin(signed_proposal_fin(init, BlockHash(STATE_DIFF_COMMITMENT.0.0)))
+    );
     assert!(receiver.next().await.is_none());
 }
 
@@ -503,9 +518,10 @@ async fn gas_price_limits(#[case] maximum: bool) {
         .await
         .unwrap();
     content_sender
-        .send(ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        }))
+        .send(ProposalPart::Fin(signed_proposal_fin(
+            ProposalInit::default(),
+            BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        )))
         .await
         .unwrap();
 
@@ -614,7 +630,10 @@ async fn oracle_fails_on_startup(#[case] l1_oracle_failure: bool) {
 
     let (_, mut receiver) = network.outbound_proposal_receiver.next().await.unwrap();
 
-    assert_eq!(receiver.next().await.unwrap(), ProposalPart::Init(ProposalInit::default()));
+    assert_eq!(
+        receiver.next().await.unwrap(),
+        ProposalPart::Init(signed_proposal_init(ProposalInit::default()))
+    );
     let block_info = receiver.next().await.unwrap();
     let ProposalPart::BlockInfo(info) = block_info else {
         panic!("Expected ProposalPart::BlockInfo");
@@ -637,9 +656,10 @@ async fn oracle_fails_on_startup(#[case] l1_oracle_failure: bool) {
     );
     assert_eq!(
         receiver.next().await.unwrap(),
-        ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        })
+        ProposalPart::Fin(signed_proposal_fin(
+            ProposalInit::default(),
+            BlockHash(STATE_DIFF_COMMITMENT.0.0)
+        ))
     );
     assert!(receiver.next().await.is_none());
     assert_eq!(fin_receiver.await.unwrap().0, STATE_DIFF_COMMITMENT.0.0);
@@ -725,9 +745,10 @@ async fn oracle_fails_on_second_block(#[case] l1_oracle_failure: bool) {
         .await
         .unwrap();
     content_sender
-        .send(ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        }))
+        .send(ProposalPart::Fin(signed_proposal_fin(
+            ProposalInit::default(),
+            BlockHash(STATE_DIFF_COMMITMENT.0.0),
+        )))
         .await
         .unwrap();
     let fin_receiver =
@@ -753,10 +774,7 @@ async fn oracle_fails_on_second_block(#[case] l1_oracle_failure: bool) {
 
     let (_, mut receiver) = network.outbound_proposal_receiver.next().await.unwrap();
 
-    assert_eq!(
-        receiver.next().await.unwrap(),
-        ProposalPart::Init(ProposalInit { height: BlockNumber(1), ..Default::default() })
-    );
+    assert_eq!(receiver.next().await.unwrap(), ProposalPart::Init(signed_proposal_init(init)));
     let info = receiver.next().await.unwrap();
     let ProposalPart::BlockInfo(info) = info else {
         panic!("Expected ProposalPart::BlockInfo");
@@ -778,9 +796,7 @@ async fn oracle_fails_on_second_block(#[case] l1_oracle_failure: bool) {
     );
     assert_eq!(
         receiver.next().await.unwrap(),
-        ProposalPart::Fin(ProposalFin {
-            proposal_commitment: BlockHash(STATE_DIFF_COMMITMENT.0.0),
-        })
+        ProposalPart::Fin(signed_proposal_fin(init, BlockHash(STATE_DIFF_COMMITMENT.0.0)))
     );
     assert!(receiver.next().await.is_none());
     assert_eq!(fin_receiver.await.unwrap().0, STATE_DIFF_COMMITMENT.0.0);
@@ -840,3 +856,31 @@ async fn constant_l2_gas_price_behavior(
         );
     }
 }
+
+#[tokio::test(start_paused = true)]
+async fn validators_fail_after_bounded_committee_fetch_attempts() {
+    let (mut deps, _network) = create_test_and_network_deps();
+    deps.committee_provider = MockCommitteeProvider::new();
+    deps.committee_provider
+        .expect_get_committee()
+        .times(COMMITTEE_FETCH_ATTEMPTS)
+        .returning(|height| Err(CommitteeProviderError::MissingCommittee(height)));
+    let context = deps.build_context();
+
+    assert_matches!(
+        context.validators(BlockNumber(0)).await,
+        Err(ConsensusError::CommitteeError(_))
+    );
+}
+
+#[tokio::test]
+async fn proposer_of_an_uncached_height_is_an_error() {
+    let (mut deps, _network) = create_test_and_network_deps();
+    deps.committee_provider = MockCommitteeProvider::new();
+    deps.committee_provider
+        .expect_get_proposer()
+        .returning(|height, _| Err(CommitteeProviderError::MissingCommittee(height)));
+    let context = deps.build_context();
+
+    assert_matches!(context.proposer(BlockNumber(0), 0), Err(ConsensusError::CommitteeError(_)));
+}
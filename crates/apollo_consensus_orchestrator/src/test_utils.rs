@@ -27,22 +27,36 @@ use apollo_network::network_manager::test_utils::{
     TestSubscriberChannels,
 };
 use apollo_network::network_manager::{BroadcastTopicChannels, BroadcastTopicClient};
-use apollo_protobuf::consensus::{ConsensusBlockInfo, HeightAndRound, ProposalPart, Vote};
+use apollo_protobuf::consensus::{
+    ConsensusBlockInfo,
+    HeightAndRound,
+    ProposalFin,
+    ProposalInit,
+    ProposalPart,
+    Vote,
+    DEFAULT_VALIDATOR_ID,
+};
+use apollo_signature_manager::signature_manager::{LocalKeyStore, SignatureManager};
+use apollo_signature_manager_types::MockSignatureManagerClient;
+use apollo_staking::committee_provider::{MockCommitteeProvider, Staker};
 use apollo_state_sync_types::communication::MockStateSyncClient;
 use apollo_time::time::{Clock, DefaultClock};
 use futures::channel::mpsc;
 use futures::executor::block_on;
 use starknet_api::block::{
+    BlockHash,
     BlockNumber,
     GasPrice,
     TEMP_ETH_BLOB_GAS_FEE_IN_WEI,
     TEMP_ETH_GAS_FEE_IN_WEI,
 };
 use starknet_api::consensus_transaction::{ConsensusTransaction, InternalConsensusTransaction};
-use starknet_api::core::{ChainId, Nonce, StateDiffCommitment};
+use starknet_api::core::{ChainId, ContractAddress, Nonce, StateDiffCommitment};
+use starknet_api::crypto::utils::Signature;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::felt;
 use starknet_api::hash::PoseidonHash;
+use starknet_api::staking::StakingWeight;
 use starknet_api::test_utils::invoke::{rpc_invoke_tx, InvokeTxArgs};
 use starknet_types_core::felt::Felt;
 
@@ -93,6 +107,8 @@ pub(crate) struct TestDeps {
     pub clock: Arc<dyn Clock>,
     pub outbound_proposal_sender: mpsc::Sender<(HeightAndRound, mpsc::Receiver<ProposalPart>)>,
     pub vote_broadcast_client: BroadcastTopicClient<Vote>,
+    pub signature_manager_client: MockSignatureManagerClient,
+    pub committee_provider: MockCommitteeProvider,
 }
 
 impl From<TestDeps> for SequencerConsensusContextDeps {
@@ -106,6 +122,8 @@ impl From<TestDeps> for SequencerConsensusContextDeps {
             clock: deps.clock,
             outbound_proposal_sender: deps.outbound_proposal_sender,
             vote_broadcast_client: deps.vote_broadcast_client,
+            signature_manager_client: Arc::new(deps.signature_manager_client),
+            committee_provider: Arc::new(deps.committee_provider),
         }
     }
 }
@@ -253,6 +271,8 @@ pub(crate) fn create_test_and_network_deps() -> (TestDeps, NetworkDependencies)
     let cende_ambassador = MockCendeContext::new();
     let l1_gas_price_provider = MockL1GasPriceProviderClient::new();
     let clock = Arc::new(DefaultClock);
+    let signature_manager_client = signature_manager_client();
+    let committee_provider = committee_provider();
 
     let test_deps = TestDeps {
         transaction_converter,
@@ -263,6 +283,8 @@ pub(crate) fn create_test_and_network_deps() -> (TestDeps, NetworkDependencies)
         clock,
         outbound_proposal_sender,
         vote_broadcast_client: votes_topic_client,
+        signature_manager_client,
+        committee_provider,
     };
 
     let network_deps =
@@ -271,6 +293,61 @@ pub(crate) fn create_test_and_network_deps() -> (TestDeps, NetworkDependencies)
     (test_deps, network_deps)
 }
 
+const TEST_SIGNATURE_MANAGER: SignatureManager<LocalKeyStore> =
+    SignatureManager { keystore: LocalKeyStore::new_for_testing() };
+
+// Signs with the key all validators currently share, so that the signatures are verifiable.
+fn signature_manager_client() -> MockSignatureManagerClient {
+    let mut signature_manager_client = MockSignatureManagerClient::new();
+    signature_manager_client
+        .expect_sign_vote()
+        .returning(|vote| Ok(block_on(TEST_SIGNATURE_MANAGER.sign_vote(&vote))?));
+    signature_manager_client
+        .expect_sign_proposal_init()
+        .returning(|init| Ok(block_on(TEST_SIGNATURE_MANAGER.sign_proposal_init(&init))?));
+    signature_manager_client.expect_sign_proposal_fin().returning(|init, fin| {
+        Ok(block_on(TEST_SIGNATURE_MANAGER.sign_proposal_fin(&init, &fin))?)
+    });
+    signature_manager_client
+}
+
+// A committee of `NUM_VALIDATORS` equally weighted validators, all signing with the testing key, in
+// which the proposers rotate by height and round.
+fn committee_provider() -> MockCommitteeProvider {
+    let committee: Vec<Staker> = (0..NUM_VALIDATORS)
+        .map(|i| Staker {
+            address: ContractAddress::from(DEFAULT_VALIDATOR_ID + i),
+            weight: StakingWeight(1),
+            public_key: TEST_SIGNATURE_MANAGER.keystore.public_key.0,
+        })
+        .collect();
+    let committee = Arc::new(committee);
+
+    let mut committee_provider = MockCommitteeProvider::new();
+    committee_provider.expect_get_committee().returning(move |_| Ok(Arc::clone(&committee)));
+    committee_provider.expect_get_proposer().returning(|height, round| {
+        Ok(ContractAddress::from(
+            DEFAULT_VALIDATOR_ID + (height.0 + u64::from(round)) % NUM_VALIDATORS,
+        ))
+    });
+    committee_provider
+}
+
+pub(crate) fn signed_proposal_init(init: ProposalInit) -> ProposalInit {
+    let signature = block_on(TEST_SIGNATURE_MANAGER.sign_proposal_init(&init)).unwrap();
+    ProposalInit { signature: Signature::try_from(signature).unwrap(), ..init }
+}
+
+/// Returns the fin of the proposal started by `init`, signed as the proposer.
+pub(crate) fn signed_proposal_fin(
+    init: ProposalInit,
+    proposal_commitment: BlockHash,
+) -> ProposalFin {
+    let fin = ProposalFin { proposal_commitment, signature: Signature::default() };
+    let signature = block_on(TEST_SIGNATURE_MANAGER.sign_proposal_fin(&init, &fin)).unwrap();
+    ProposalFin { signature: Signature::try_from(signature).unwrap(), ..fin }
+}
+
 pub(crate) fn generate_invoke_tx(nonce: u8) -> ConsensusTransaction {
     ConsensusTransaction::RpcTransaction(rpc_invoke_tx(InvokeTxArgs {
         nonce: Nonce(felt!(nonce)),
@@ -1,7 +1,19 @@
 use std::sync::Arc;
 
+use apollo_consensus::types::ProposalCommitment;
 use apollo_l1_gas_price_types::{L1GasPriceProviderClient, PriceInfo, DEFAULT_ETH_TO_FRI_RATE};
-use apollo_protobuf::consensus::{ConsensusBlockInfo, ProposalPart};
+use apollo_protobuf::consensus::{
+    ConsensusBlockInfo,
+    ProposalFin,
+    ProposalInit,
+    ProposalPart,
+    Vote,
+};
+use apollo_signature_manager_types::{
+    SignatureManagerClient,
+    SignatureManagerClientResult,
+    SignatureManagerError,
+};
 use apollo_state_sync_types::communication::{
     StateSyncClient,
     StateSyncClientError,
@@ -23,6 +35,7 @@ use starknet_api::block::{
     NonzeroGasPrice,
 };
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::crypto::utils::{RawSignature, Signature};
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::StarknetApiError;
 use tracing::{info, warn};
@@ -212,3 +225,34 @@ pub(crate) fn truncate_to_executed_txs(
 
     executed_content
 }
+
+pub(crate) async fn sign_vote(
+    signature_manager_client: &dyn SignatureManagerClient,
+    vote: Vote,
+) -> SignatureManagerClientResult<Vote> {
+    let signature = signature_manager_client.sign_vote(vote.clone()).await?;
+    Ok(Vote { signature: to_signature(signature)?, ..vote })
+}
+
+pub(crate) async fn sign_proposal_init(
+    signature_manager_client: &dyn SignatureManagerClient,
+    init: ProposalInit,
+) -> SignatureManagerClientResult<ProposalInit> {
+    let signature = signature_manager_client.sign_proposal_init(init).await?;
+    Ok(ProposalInit { signature: to_signature(signature)?, ..init })
+}
+
+/// Creates the signed fin of the proposal started by `init`.
+pub(crate) async fn signed_proposal_fin(
+    signature_manager_client: &dyn SignatureManagerClient,
+    init: ProposalInit,
+    proposal_commitment: ProposalCommitment,
+) -> SignatureManagerClientResult<ProposalFin> {
+    let fin = ProposalFin { proposal_commitment, signature: Signature::default() };
+    let signature = signature_manager_client.sign_proposal_fin(init, fin.clone()).await?;
+    Ok(ProposalFin { signature: to_signature(signature)?, ..fin })
+}
+
+fn to_signature(signature: RawSignature) -> Result<Signature, SignatureManagerError> {
+    Signature::try_from(signature).map_err(SignatureManagerError::from)
+}
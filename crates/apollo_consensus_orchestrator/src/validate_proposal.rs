@@ -18,13 +18,21 @@ use apollo_class_manager_types::transaction_converter::TransactionConverterTrait
 use apollo_consensus::types::ProposalCommitment;
 use apollo_l1_gas_price_types::errors::{EthToStrkOracleClientError, L1GasPriceClientError};
 use apollo_l1_gas_price_types::L1GasPriceProviderClient;
-use apollo_protobuf::consensus::{ConsensusBlockInfo, ProposalFin, ProposalPart, TransactionBatch};
+use apollo_protobuf::consensus::{
+    ConsensusBlockInfo,
+    ProposalFin,
+    ProposalInit,
+    ProposalPart,
+    TransactionBatch,
+};
+use apollo_signature_manager::signature_manager::verify_proposal_fin_signature;
 use apollo_state_sync_types::communication::StateSyncClient;
 use apollo_time::time::{sleep_until, Clock, DateTime};
 use futures::channel::mpsc;
 use futures::StreamExt;
 use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::consensus_transaction::InternalConsensusTransaction;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::transaction::TransactionHash;
 use starknet_api::StarknetApiError;
@@ -52,6 +60,9 @@ const GAS_PRICE_ABS_DIFF_MARGIN: u128 = 1;
 
 pub(crate) struct ProposalValidateArguments {
     pub deps: SequencerConsensusContextDeps,
+    pub proposal_init: ProposalInit,
+    // Used to verify that the fin was signed by the proposer.
+    pub proposer_public_key: PublicKey,
     pub block_info_validation: BlockInfoValidation,
     pub proposal_id: ProposalId,
     pub timeout: Duration,
@@ -117,6 +128,8 @@ pub(crate) enum ValidateProposalError {
     ProposalPartFailed(String, Option<ProposalPart>),
     #[error("proposal_commitment built by the batcher does not match the proposal fin.")]
     ProposalFinMismatch,
+    #[error("The proposal fin is not signed by the proposer: {0:?}.")]
+    InvalidFinSignature(ProposalFin),
     #[error("Cannot calculate deadline. timeout: {timeout:?}, now: {now:?}")]
     CannotCalculateDeadline { timeout: Duration, now: DateTime },
 }
@@ -142,22 +155,25 @@ pub(crate) async fn validate_proposal(
     .await?
     {
         SecondProposalPart::BlockInfo(block_info) => block_info,
-        SecondProposalPart::Fin(ProposalFin { proposal_commitment }) => {
-            return Ok(proposal_commitment);
+        SecondProposalPart::Fin(fin) => {
+            if !is_fin_signed_by_proposer(&args, &fin) {
+                return Err(ValidateProposalError::InvalidFinSignature(fin));
+            }
+            return Ok(fin.proposal_commitment);
         }
     };
     is_block_info_valid(
         args.block_info_validation.clone(),
         block_info.clone(),
         args.deps.clock.as_ref(),
-        args.deps.l1_gas_price_provider,
+        Arc::clone(&args.deps.l1_gas_price_provider),
         &args.gas_price_params,
     )
     .await?;
 
     initiate_validation(
         args.deps.batcher.as_ref(),
-        args.deps.state_sync_client,
+        Arc::clone(&args.deps.state_sync_client),
         block_info.clone(),
         args.proposal_id,
         args.timeout + args.batcher_timeout_margin,
@@ -221,7 +237,9 @@ pub(crate) async fn validate_proposal(
         &args.proposal_id,
     );
 
-    // TODO(matan): Switch to signature validation.
+    if !is_fin_signed_by_proposer(&args, &received_fin) {
+        return Err(ValidateProposalError::InvalidFinSignature(received_fin));
+    }
     if built_block != received_fin.proposal_commitment {
         CONSENSUS_PROPOSAL_FIN_MISMATCH.increment(1);
         return Err(ValidateProposalError::ProposalFinMismatch);
@@ -230,6 +248,13 @@ pub(crate) async fn validate_proposal(
     Ok(built_block)
 }
 
+// Only the proposer may decide on the commitment of its proposal, so that a peer relaying the
+// stream cannot swap it.
+fn is_fin_signed_by_proposer(args: &ProposalValidateArguments, fin: &ProposalFin) -> bool {
+    verify_proposal_fin_signature(&args.proposal_init, fin, args.proposer_public_key)
+        .unwrap_or(false)
+}
+
 #[instrument(level = "warn", skip_all, fields(?block_info_validation, ?block_info_proposed))]
 async fn is_block_info_valid(
     block_info_validation: BlockInfoValidation,
@@ -358,9 +383,9 @@ async fn await_second_proposal_part(
                 Some(ProposalPart::BlockInfo(block_info)) => {
                     Ok(SecondProposalPart::BlockInfo(block_info))
                 }
-                Some(ProposalPart::Fin(ProposalFin { proposal_commitment })) => {
+                Some(ProposalPart::Fin(fin)) => {
                     warn!("Received an empty proposal.");
-                    Ok(SecondProposalPart::Fin(ProposalFin { proposal_commitment }))
+                    Ok(SecondProposalPart::Fin(fin))
                 }
                 x => {
                     Err(ValidateProposalError::InvalidSecondProposalPart(x
@@ -11,7 +11,8 @@ use apollo_batcher_types::batcher_types::{
 };
 use apollo_batcher_types::communication::BatcherClientError;
 use apollo_infra::component_client::ClientError;
-use apollo_protobuf::consensus::{ProposalFin, ProposalPart, TransactionBatch};
+use apollo_protobuf::consensus::{ProposalInit, ProposalPart, TransactionBatch};
+use apollo_signature_manager::signature_manager::LocalKeyStore;
 use assert_matches::assert_matches;
 use futures::channel::mpsc;
 use futures::SinkExt;
@@ -19,6 +20,7 @@ use num_rational::Ratio;
 use rstest::rstest;
 use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::core::StateDiffCommitment;
+use starknet_api::crypto::utils::PublicKey;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::hash::PoseidonHash;
 use starknet_types_core::felt::Felt;
@@ -30,6 +32,7 @@ use crate::sequencer_consensus_context::BuiltProposals;
 use crate::test_utils::{
     block_info,
     create_test_and_network_deps,
+    signed_proposal_fin,
     TestDeps,
     CHANNEL_SIZE,
     TIMEOUT,
@@ -46,6 +49,8 @@ use crate::validate_proposal::{
 
 struct TestProposalValidateArguments {
     pub deps: TestDeps,
+    pub proposal_init: ProposalInit,
+    pub proposer_public_key: PublicKey,
     pub block_info_validation: BlockInfoValidation,
     pub proposal_id: ProposalId,
     pub timeout: Duration,
@@ -60,6 +65,8 @@ impl From<TestProposalValidateArguments> for ProposalValidateArguments {
     fn from(args: TestProposalValidateArguments) -> Self {
         ProposalValidateArguments {
             deps: args.deps.into(),
+            proposal_init: args.proposal_init,
+            proposer_public_key: args.proposer_public_key,
             block_info_validation: args.block_info_validation,
             proposal_id: args.proposal_id,
             timeout: args.timeout,
@@ -76,6 +83,8 @@ fn create_proposal_validate_arguments()
 -> (TestProposalValidateArguments, mpsc::Sender<ProposalPart>) {
     let (mut deps, _) = create_test_and_network_deps();
     deps.setup_default_expectations();
+    let proposal_init = ProposalInit::default();
+    let proposer_public_key = LocalKeyStore::new_for_testing().public_key;
     let block_info_validation = BlockInfoValidation {
         height: BlockNumber(0),
         block_timestamp_window_seconds: 60,
@@ -105,6 +114,8 @@ fn create_proposal_validate_arguments()
     (
         TestProposalValidateArguments {
             deps,
+            proposal_init,
+            proposer_public_key,
             block_info_validation,
             proposal_id,
             timeout,
@@ -123,7 +134,7 @@ async fn validate_empty_proposal() {
     let (proposal_args, mut content_sender) = create_proposal_validate_arguments();
     // Send an empty proposal.
     content_sender
-        .send(ProposalPart::Fin(ProposalFin { proposal_commitment: BlockHash::default() }))
+        .send(ProposalPart::Fin(signed_proposal_fin(ProposalInit::default(), BlockHash::default())))
         .await
         .unwrap();
 
@@ -131,6 +142,20 @@ async fn validate_empty_proposal() {
     assert_matches!(res, Ok(val) if val == BlockHash::default());
 }
 
+#[tokio::test]
+async fn fin_not_signed_by_proposer() {
+    let (proposal_args, mut content_sender) = create_proposal_validate_arguments();
+    // Send an empty proposal, ending with a fin signed for another round.
+    let other_init = ProposalInit { round: 1, ..Default::default() };
+    content_sender
+        .send(ProposalPart::Fin(signed_proposal_fin(other_init, BlockHash::default())))
+        .await
+        .unwrap();
+
+    let res = validate_proposal(proposal_args.into()).await;
+    assert_matches!(res, Err(ValidateProposalError::InvalidFinSignature(_)));
+}
+
 #[tokio::test]
 async fn validate_proposal_success() {
     let (mut proposal_args, mut content_sender) = create_proposal_validate_arguments();
@@ -150,7 +175,7 @@ async fn validate_proposal_success() {
         .await
         .unwrap();
     content_sender
-        .send(ProposalPart::Fin(ProposalFin { proposal_commitment: BlockHash::default() }))
+        .send(ProposalPart::Fin(signed_proposal_fin(ProposalInit::default(), BlockHash::default())))
         .await
         .unwrap();
 
@@ -264,7 +289,7 @@ async fn receive_fin_without_executed_transaction_count() {
     content_sender.send(ProposalPart::BlockInfo(block_info)).await.unwrap();
     // Send Fin part without sending executed transaction count.
     content_sender
-        .send(ProposalPart::Fin(ProposalFin { proposal_commitment: BlockHash::default() }))
+        .send(ProposalPart::Fin(signed_proposal_fin(ProposalInit::default(), BlockHash::default())))
         .await
         .unwrap();
 
@@ -336,7 +361,7 @@ async fn proposal_fin_mismatch() {
     // Send Fin part.
     let received_fin = BlockHash::default();
     content_sender
-        .send(ProposalPart::Fin(ProposalFin { proposal_commitment: received_fin }))
+        .send(ProposalPart::Fin(signed_proposal_fin(ProposalInit::default(), received_fin)))
         .await
         .unwrap();
 
@@ -372,7 +397,7 @@ async fn batcher_returns_invalid_proposal() {
         .await
         .unwrap();
     content_sender
-        .send(ProposalPart::Fin(ProposalFin { proposal_commitment: BlockHash::default() }))
+        .send(ProposalPart::Fin(signed_proposal_fin(ProposalInit::default(), BlockHash::default())))
         .await
         .unwrap();
 
@@ -9,6 +9,9 @@ use apollo_gateway::metrics::{
     GATEWAY_TRANSACTIONS_RECEIVED,
     GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL,
     GATEWAY_VALIDATE_TX_LATENCY,
+    GATEWAY_VALIDATION_ACTIVE_WORKERS,
+    GATEWAY_VALIDATION_QUEUE_WAIT_TIME,
+    GATEWAY_VALIDATION_REJECTIONS,
     LABEL_NAME_SOURCE,
     LABEL_NAME_TX_TYPE as GATEWAY_LABEL_NAME_TX_TYPE,
 };
@@ -152,6 +155,18 @@ fn get_panel_gateway_transactions_sent_to_mempool() -> Panel {
     )
 }
 
+fn get_panel_gateway_validation_queue_wait_time() -> Panel {
+    Panel::from_hist(&GATEWAY_VALIDATION_QUEUE_WAIT_TIME, PanelType::TimeSeries)
+}
+
+fn get_panel_gateway_validation_active_workers() -> Panel {
+    Panel::from_gauge(&GATEWAY_VALIDATION_ACTIVE_WORKERS, PanelType::TimeSeries)
+}
+
+fn get_panel_gateway_validation_rejections() -> Panel {
+    Panel::from_counter(&GATEWAY_VALIDATION_REJECTIONS, PanelType::TimeSeries)
+}
+
 pub(crate) fn get_gateway_row() -> Row {
     Row::new(
         "Gateway",
@@ -163,6 +178,9 @@ pub(crate) fn get_gateway_row() -> Row {
             get_panel_gateway_validate_tx_latency(),
             get_panel_gateway_transactions_failed(),
             get_panel_gateway_transactions_sent_to_mempool(),
+            get_panel_gateway_validation_queue_wait_time(),
+            get_panel_gateway_validation_active_workers(),
+            get_panel_gateway_validation_rejections(),
         ],
     )
 }
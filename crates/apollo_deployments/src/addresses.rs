@@ -15,6 +15,23 @@ pub(crate) fn get_peer_id(node_id: usize) -> String {
     PEER_IDS[node_id].to_string()
 }
 
+// The public keys of the consensus signing keys, where the signing key of node with index `id` is
+// `private_key_for_testing(id)` of `apollo_signature_manager`, i.e.,
+// 0x608bf2cdb1ad4138e72d2f82b8c5db9fa182d1883868ae582ed373429b7a133 + id.
+const STAKER_PUBLIC_KEYS: [&str; 6] = [
+    "0x125d56b1fbba593f1dd215b7c55e384acd838cad549c4a2b9c6d32d264f4e2a",
+    "0x67886f20cec8a7641667ea274dc7d32fa75b6bfeb2956db0d724935a26d5229",
+    "0x268357f03214cdc94a662890148c41b3d646f81a2eca827303243b64597a25d",
+    "0x7cd7a8fdab4df72b635884a9cf609c39ce2de1c95e510bf7969423a174f7065",
+    "0x460a05f92d331437b7ef4a5f841b48ebf8efc8b343df5b74381236cc6e18da7",
+    "0xa93fb5ff4d20f2fa82fb3d156b586db07e57bc80a7d48de566327739e9f60f",
+];
+
+pub(crate) fn get_staker_public_key(node_id: usize) -> String {
+    assert!(node_id < STAKER_PUBLIC_KEYS.len(), "Node index out of bounds: {node_id}");
+    STAKER_PUBLIC_KEYS[node_id].to_string()
+}
+
 pub(crate) fn get_p2p_address(dns: &str, port: u16, peer_id: &str) -> String {
     format!("/dns/{dns}/tcp/{port}/p2p/{peer_id}")
 }
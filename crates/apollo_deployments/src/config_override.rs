@@ -12,6 +12,7 @@ use url::Url;
 use crate::deployment_definitions::{StateSyncConfig, StateSyncType};
 #[cfg(test)]
 use crate::test_utils::FIX_BINARY_NAME;
+use crate::utils::get_stakers;
 
 const DEPLOYMENT_FILE_NAME: &str = "deployment_config_override.json";
 
@@ -126,6 +127,8 @@ pub struct DeploymentConfigOverride {
     l1_provider_config_provider_startup_height_override_is_none: bool,
     #[serde(rename = "consensus_manager_config.context_config.num_validators")]
     consensus_manager_config_context_config_num_validators: usize,
+    #[serde(rename = "consensus_manager_config.context_config.stakers")]
+    consensus_manager_config_context_config_stakers: String,
     #[serde(flatten)]
     state_sync_config: StateSyncConfig,
 }
@@ -159,6 +162,9 @@ impl DeploymentConfigOverride {
             l1_provider_config_provider_startup_height_override,
             l1_provider_config_provider_startup_height_override_is_none,
             consensus_manager_config_context_config_num_validators,
+            consensus_manager_config_context_config_stakers: get_stakers(
+                consensus_manager_config_context_config_num_validators,
+            ),
             state_sync_config: state_sync_type.get_state_sync_config(),
         }
     }
@@ -307,8 +307,8 @@ impl ComponentConfigInService {
                 vec!["monitoring_endpoint_config".to_string()]
             }
             ComponentConfigInService::SierraCompiler => vec!["sierra_compiler_config".to_string()],
-            // Signature manager does not have a separate config sub-struct in
-            // `SequencerNodeConfig`. Keep this empty to avoid generating
+            // The signature manager config is not optional in `SequencerNodeConfig`, and its only
+            // param is a secret. Keep this empty to avoid generating
             // `signature_manager_config.#is_none` flags.
             // TODO(Nadin): TAL add refactor this temp fix.
             ComponentConfigInService::SignatureManager => vec![],
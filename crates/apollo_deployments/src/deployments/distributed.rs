@@ -150,6 +150,7 @@ impl GetComponentConfigs for DistributedNodeServiceName {
                     mempool.local(),
                     class_manager.remote(),
                     gateway.remote(),
+                    state_sync.remote(),
                 ),
                 DistributedNodeServiceName::SierraCompiler => {
                     get_sierra_compiler_component_config(sierra_compiler.local())
@@ -994,12 +995,14 @@ fn get_mempool_component_config(
     mempool_local_config: ReactiveComponentExecutionConfig,
     class_manager_remote_config: ReactiveComponentExecutionConfig,
     gateway_remote_config: ReactiveComponentExecutionConfig,
+    state_sync_remote_config: ReactiveComponentExecutionConfig,
 ) -> ComponentConfig {
     let mut config = ComponentConfig::disabled();
     config.mempool = mempool_local_config;
     config.mempool_p2p = ReactiveComponentExecutionConfig::local_with_remote_disabled();
     config.class_manager = class_manager_remote_config;
     config.gateway = gateway_remote_config;
+    config.state_sync = state_sync_remote_config;
     config.monitoring_endpoint = ActiveComponentExecutionConfig::enabled();
     config
 }
@@ -5,6 +5,8 @@ use apollo_config::converters::{
     UrlAndHeaders,
 };
 use serde::{Serialize, Serializer};
+use starknet_api::crypto::utils::PrivateKey;
+use starknet_api::hash::StarkHash;
 use url::Url;
 
 pub(crate) const FIX_BINARY_NAME: &str = "deployment_generator";
@@ -35,6 +37,8 @@ pub struct SecretsConfigOverride {
     )]
     mempool_p2p_config_network_config_secret_key: Option<Vec<u8>>,
     recorder_url: Url,
+    #[serde(rename = "signature_manager_config.private_key")]
+    signature_manager_config_private_key: PrivateKey,
     #[serde(
         rename = "state_sync_config.central_sync_client_config.central_source_config.http_headers"
     )]
@@ -63,6 +67,7 @@ impl Default for SecretsConfigOverride {
             ),
             mempool_p2p_config_network_config_secret_key: None,
             recorder_url: Url::parse("https://arbitrary.recorder.url").unwrap(),
+            signature_manager_config_private_key: PrivateKey(StarkHash::ONE),
             state_sync_config_central_sync_client_config_central_source_config_http_headers: ""
                 .to_string(),
             state_sync_config_network_config_secret_key: None,
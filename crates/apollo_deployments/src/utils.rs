@@ -2,10 +2,21 @@ use std::collections::HashSet;
 
 use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 
+use crate::addresses::get_staker_public_key;
+
 pub(crate) fn get_validator_id(id: usize) -> String {
     format!("0x{:x}", id + usize::try_from(DEFAULT_VALIDATOR_ID).unwrap())
 }
 
+/// Returns the configured stakers of the first `num_validators` nodes, equally weighted from the
+/// first epoch, as space separated 'start_epoch:address:weight:public_key'.
+pub(crate) fn get_stakers(num_validators: usize) -> String {
+    (0..num_validators)
+        .map(|id| format!("0:{}:1:{}", get_validator_id(id), get_staker_public_key(id)))
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
 // TODO(Nadin): Update this function to validate that the ports are unique and have the correct
 // length.
 /// Returns a validated or generated vector of port numbers of length `n`.
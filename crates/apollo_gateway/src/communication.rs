@@ -32,6 +32,12 @@ impl ComponentRequestHandler<GatewayRequest, GatewayResponse> for Gateway {
                         }),
                 )
             }
+            GatewayRequest::ValidateTransaction(rpc_tx) => GatewayResponse::ValidateTransaction(
+                self.validate_tx(rpc_tx).await.map_err(|source| {
+                    GatewayError::DeprecatedGatewayError { source, p2p_message_metadata: None }
+                }),
+            ),
+            GatewayRequest::GetPolicy => GatewayResponse::GetPolicy(Ok(self.get_policy())),
         }
     }
 }
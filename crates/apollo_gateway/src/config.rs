@@ -1,6 +1,9 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::str::FromStr;
+use std::time::Duration;
 
+use apollo_config::converters::deserialize_seconds_to_duration;
 use apollo_config::dumping::{
     prepend_sub_config_name,
     ser_optional_param,
@@ -8,6 +11,7 @@ use apollo_config::dumping::{
     SerializeConfig,
 };
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use apollo_gateway_types::gateway_types::GatewayPolicy;
 use blockifier::blockifier_versioned_constants::VersionedConstantsOverrides;
 use blockifier::context::ChainInfo;
 use serde::{de, Deserialize, Deserializer, Serialize};
@@ -27,6 +31,12 @@ pub struct GatewayConfig {
     pub block_declare: bool,
     #[serde(default, deserialize_with = "deserialize_optional_contract_addresses")]
     pub authorized_declarer_accounts: Option<Vec<ContractAddress>>,
+    #[validate]
+    pub duplicate_submission_cache_config: DuplicateSubmissionCacheConfig,
+    pub state_cache_config: StateCacheConfig,
+    #[validate]
+    pub validation_worker_pool_config: ValidationWorkerPoolConfig,
+    pub policy_file_config: GatewayPolicyFileConfig,
 }
 
 impl SerializeConfig for GatewayConfig {
@@ -46,6 +56,16 @@ impl SerializeConfig for GatewayConfig {
             "stateful_tx_validator_config",
         ));
         dump.extend(prepend_sub_config_name(self.chain_info.dump(), "chain_info"));
+        dump.extend(prepend_sub_config_name(
+            self.duplicate_submission_cache_config.dump(),
+            "duplicate_submission_cache_config",
+        ));
+        dump.extend(prepend_sub_config_name(self.state_cache_config.dump(), "state_cache_config"));
+        dump.extend(prepend_sub_config_name(
+            self.validation_worker_pool_config.dump(),
+            "validation_worker_pool_config",
+        ));
+        dump.extend(prepend_sub_config_name(self.policy_file_config.dump(), "policy_file_config"));
         dump.extend(ser_optional_param(
             &self.authorized_declarer_accounts.as_ref().map(|accounts| {
                 accounts.iter().map(|addr| addr.0.to_string()).collect::<Vec<_>>().join(",")
@@ -61,10 +81,13 @@ impl SerializeConfig for GatewayConfig {
 }
 
 impl GatewayConfig {
-    pub fn is_authorized_declarer(&self, declarer_address: &ContractAddress) -> bool {
-        match &self.authorized_declarer_accounts {
-            Some(allowed_accounts) => allowed_accounts.contains(declarer_address),
-            None => true,
+    /// Returns the policy applied by the gateway until it is replaced through the policy file.
+    pub fn initial_policy(&self) -> GatewayPolicy {
+        GatewayPolicy {
+            block_declare: self.block_declare,
+            authorized_declarer_accounts: self.authorized_declarer_accounts.clone(),
+            denied_sender_accounts: Vec::new(),
+            min_gas_price_percentage: self.stateful_tx_validator_config.min_gas_price_percentage,
         }
     }
 }
@@ -294,3 +317,158 @@ impl SerializeConfig for StatefulTransactionValidatorConfig {
         dump
     }
 }
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct DuplicateSubmissionCacheConfig {
+    pub enabled: bool,
+    // The maximum number of cached verdicts.
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+    // Time-to-live for cached verdicts, in seconds.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub ttl: Duration,
+}
+
+impl Default for DuplicateSubmissionCacheConfig {
+    fn default() -> Self {
+        DuplicateSubmissionCacheConfig {
+            enabled: false,
+            capacity: 10000,
+            ttl: Duration::from_secs(10),
+        }
+    }
+}
+
+impl SerializeConfig for DuplicateSubmissionCacheConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, the gateway caches its verdicts on recently submitted transactions, and \
+                 answers resubmissions of them without processing them again.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity",
+                &self.capacity,
+                "The maximum number of cached verdicts on recently submitted transactions.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "ttl",
+                &self.ttl.as_secs(),
+                "Time-to-live for cached verdicts, in seconds. Verdicts are also discarded once a \
+                 new block is committed.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct StateCacheConfig {
+    // The maximum estimated size of the values cached per block, in bytes.
+    pub max_size_in_bytes: usize,
+}
+
+impl Default for StateCacheConfig {
+    fn default() -> Self {
+        StateCacheConfig { max_size_in_bytes: 64 * 1024 * 1024 }
+    }
+}
+
+impl SerializeConfig for StateCacheConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "max_size_in_bytes",
+            &self.max_size_in_bytes,
+            "The maximum estimated size, in bytes, of the state of the latest block cached for \
+             stateful validation; 0 disables the cache.",
+            ParamPrivacyInput::Public,
+        )])
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct ValidationWorkerPoolConfig {
+    // The maximum number of transactions processed concurrently.
+    #[validate(range(min = 1))]
+    pub n_workers: usize,
+    // The maximum number of transactions waiting for a free worker; further transactions are
+    // rejected.
+    pub max_queue_depth: usize,
+}
+
+impl Default for ValidationWorkerPoolConfig {
+    fn default() -> Self {
+        ValidationWorkerPoolConfig { n_workers: 64, max_queue_depth: 1000 }
+    }
+}
+
+impl SerializeConfig for ValidationWorkerPoolConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "n_workers",
+                &self.n_workers,
+                "The maximum number of transactions processed concurrently by the gateway.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_queue_depth",
+                &self.max_queue_depth,
+                "The maximum number of transactions waiting to be processed by the gateway; \
+                 further transactions are rejected as the node is overloaded.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+#[derive(Clone, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct GatewayPolicyFileConfig {
+    // If true, the gateway policy is replaced whenever the policy file changes.
+    pub enabled: bool,
+    // Path of the policy file, a JSON-serialized `GatewayPolicy`.
+    pub path: PathBuf,
+    // How often the policy file is checked for changes, in seconds.
+    #[serde(deserialize_with = "deserialize_seconds_to_duration")]
+    pub poll_interval: Duration,
+}
+
+impl Default for GatewayPolicyFileConfig {
+    fn default() -> Self {
+        GatewayPolicyFileConfig {
+            enabled: false,
+            path: "/config/gateway/policy.json".into(),
+            poll_interval: Duration::from_secs(5),
+        }
+    }
+}
+
+impl SerializeConfig for GatewayPolicyFileConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, the gateway policy (declare gating, sender denylist and minimal gas \
+                 price percentage) is replaced at runtime whenever the policy file changes.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "Path of the gateway policy file.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "poll_interval",
+                &self.poll_interval.as_secs(),
+                "How often the gateway policy file is checked for changes, in seconds.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
@@ -0,0 +1,161 @@
+use std::collections::{HashMap, VecDeque};
+use std::sync::{Mutex, MutexGuard};
+use std::time::Instant;
+
+use apollo_gateway_types::gateway_types::GatewayOutput;
+use starknet_api::block::BlockNumber;
+use starknet_api::transaction::fields::TransactionSignature;
+use starknet_api::transaction::TransactionHash;
+
+use crate::config::DuplicateSubmissionCacheConfig;
+use crate::errors::GatewayResult;
+use crate::metrics::{
+    GATEWAY_DUPLICATE_SUBMISSION_CACHE_HITS,
+    GATEWAY_DUPLICATE_SUBMISSION_CACHE_MISSES,
+};
+
+#[cfg(test)]
+#[path = "duplicate_submission_cache_test.rs"]
+mod duplicate_submission_cache_test;
+
+/// A submission of a transaction, made when the given block was the latest one.
+#[derive(Clone, Debug)]
+pub(crate) struct Submission {
+    pub tx_hash: TransactionHash,
+    // The signature is not part of the transaction hash, and a retry may fix it.
+    pub signature: TransactionSignature,
+    pub latest_block_number: BlockNumber,
+}
+
+/// Remembers the recent verdicts of the gateway on submitted transactions, so that retries of a
+/// transaction are answered without processing it again.
+/// A verdict is kept for a bounded time, and until a block other than the one it was given against
+/// becomes the latest block.
+pub(crate) struct DuplicateSubmissionCache {
+    config: DuplicateSubmissionCacheConfig,
+    state: Mutex<CacheState>,
+}
+
+#[derive(Default)]
+struct CacheState {
+    // The block the cached verdicts were given against.
+    latest_block_number: Option<BlockNumber>,
+    verdicts: HashMap<TransactionHash, CachedVerdict>,
+    // The cached transaction hashes and their insertion times, oldest first. A hash that was
+    // cached again also appears with its previous insertion times.
+    insertion_order: VecDeque<(TransactionHash, Instant)>,
+}
+
+struct CachedVerdict {
+    signature: TransactionSignature,
+    verdict: GatewayResult<GatewayOutput>,
+    inserted_at: Instant,
+}
+
+impl DuplicateSubmissionCache {
+    pub fn new(config: DuplicateSubmissionCacheConfig) -> Self {
+        Self { config, state: Mutex::new(CacheState::default()) }
+    }
+
+    pub fn is_enabled(&self) -> bool {
+        self.config.enabled
+    }
+
+    /// Returns the cached verdict on the submitted transaction, if any.
+    pub fn get(
+        &self,
+        submission: &Submission,
+        now: Instant,
+    ) -> Option<GatewayResult<GatewayOutput>> {
+        if !self.is_enabled() {
+            return None;
+        }
+
+        let mut state = self.lock();
+        if state.latest_block_number != Some(submission.latest_block_number) {
+            // The verdicts may differ against the new latest block.
+            state.clear(submission.latest_block_number);
+        }
+
+        let verdict = state
+            .verdicts
+            .get(&submission.tx_hash)
+            .filter(|cached| {
+                cached.signature == submission.signature
+                    && now.saturating_duration_since(cached.inserted_at) < self.config.ttl
+            })
+            .map(|cached| cached.verdict.clone());
+        match verdict {
+            Some(_) => GATEWAY_DUPLICATE_SUBMISSION_CACHE_HITS.increment(1),
+            None => GATEWAY_DUPLICATE_SUBMISSION_CACHE_MISSES.increment(1),
+        }
+        verdict
+    }
+
+    /// Caches the verdict on the submitted transaction. Only definitive verdicts should be given,
+    /// i.e., ones that do not depend on the content of the mempool.
+    pub fn insert(
+        &self,
+        submission: &Submission,
+        verdict: &GatewayResult<GatewayOutput>,
+        now: Instant,
+    ) {
+        if !self.is_enabled() {
+            return;
+        }
+        // Internal and overload errors are transient; a retry should be processed again.
+        if verdict.as_ref().is_err_and(|error| error.is_internal() || error.is_node_overloaded()) {
+            return;
+        }
+
+        let mut state = self.lock();
+        // The latest block changed while the transaction was processed.
+        if state.latest_block_number != Some(submission.latest_block_number) {
+            return;
+        }
+
+        state.evict_expired(&self.config, now);
+        while state.insertion_order.len() >= self.config.capacity {
+            state.evict_oldest();
+        }
+        state.insertion_order.push_back((submission.tx_hash, now));
+        state.verdicts.insert(
+            submission.tx_hash,
+            CachedVerdict {
+                signature: submission.signature.clone(),
+                verdict: verdict.clone(),
+                inserted_at: now,
+            },
+        );
+    }
+
+    fn lock(&self) -> MutexGuard<'_, CacheState> {
+        self.state.lock().expect("Duplicate submission cache lock should not be poisoned.")
+    }
+}
+
+impl CacheState {
+    fn clear(&mut self, latest_block_number: BlockNumber) {
+        self.latest_block_number = Some(latest_block_number);
+        self.verdicts.clear();
+        self.insertion_order.clear();
+    }
+
+    fn evict_expired(&mut self, config: &DuplicateSubmissionCacheConfig, now: Instant) {
+        while self.insertion_order.front().is_some_and(|(_, inserted_at)| {
+            now.saturating_duration_since(*inserted_at) >= config.ttl
+        }) {
+            self.evict_oldest();
+        }
+    }
+
+    fn evict_oldest(&mut self) {
+        let Some((tx_hash, inserted_at)) = self.insertion_order.pop_front() else {
+            return;
+        };
+        // Keep the verdict if it was cached again since.
+        if self.verdicts.get(&tx_hash).is_some_and(|cached| cached.inserted_at == inserted_at) {
+            self.verdicts.remove(&tx_hash);
+        }
+    }
+}
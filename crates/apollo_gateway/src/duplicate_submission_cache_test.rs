@@ -0,0 +1,115 @@
+use std::time::{Duration, Instant};
+
+use apollo_gateway_types::deprecated_gateway_error::{
+    KnownStarknetErrorCode,
+    StarknetError,
+    StarknetErrorCode,
+};
+use apollo_gateway_types::gateway_types::{GatewayOutput, InvokeGatewayOutput};
+use starknet_api::block::BlockNumber;
+use starknet_api::transaction::fields::TransactionSignature;
+use starknet_api::tx_hash;
+use starknet_types_core::felt::Felt;
+
+use crate::config::DuplicateSubmissionCacheConfig;
+use crate::duplicate_submission_cache::{DuplicateSubmissionCache, Submission};
+use crate::errors::GatewayResult;
+
+const TTL: Duration = Duration::from_secs(10);
+
+fn cache(capacity: usize) -> DuplicateSubmissionCache {
+    DuplicateSubmissionCache::new(DuplicateSubmissionCacheConfig {
+        enabled: true,
+        capacity,
+        ttl: TTL,
+    })
+}
+
+fn submission(tx_hash: u64, latest_block_number: u64) -> Submission {
+    Submission {
+        tx_hash: tx_hash!(tx_hash),
+        signature: TransactionSignature::default(),
+        latest_block_number: BlockNumber(latest_block_number),
+    }
+}
+
+fn accepted(tx_hash: u64) -> GatewayResult<GatewayOutput> {
+    Ok(GatewayOutput::Invoke(InvokeGatewayOutput::new(tx_hash!(tx_hash))))
+}
+
+/// Caches the verdict on the submission after a miss, as the gateway does.
+fn get_and_insert(
+    cache: &DuplicateSubmissionCache,
+    submission: Submission,
+    verdict: &GatewayResult<GatewayOutput>,
+    now: Instant,
+) {
+    assert_eq!(cache.get(&submission, now), None);
+    cache.insert(&submission, verdict, now);
+}
+
+#[test]
+fn returns_verdicts_of_resubmitted_txs() {
+    let cache = cache(10);
+    let now = Instant::now();
+    let rejected = Err(StarknetError {
+        code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+        message: "Validation failed.".to_string(),
+    });
+    get_and_insert(&cache, submission(1, 0), &accepted(1), now);
+    get_and_insert(&cache, submission(2, 0), &rejected, now);
+
+    assert_eq!(cache.get(&submission(1, 0), now), Some(accepted(1)));
+    assert_eq!(cache.get(&submission(2, 0), now), Some(rejected));
+
+    // A resubmission with a different signature is processed again.
+    let resigned_submission =
+        Submission { signature: TransactionSignature(vec![Felt::ONE].into()), ..submission(2, 0) };
+    assert_eq!(cache.get(&resigned_submission, now), None);
+}
+
+#[test]
+fn verdicts_expire() {
+    let cache = cache(10);
+    let now = Instant::now();
+    get_and_insert(&cache, submission(1, 0), &accepted(1), now);
+
+    assert_eq!(cache.get(&submission(1, 0), now + TTL - Duration::from_secs(1)), Some(accepted(1)));
+    assert_eq!(cache.get(&submission(1, 0), now + TTL), None);
+}
+
+#[test]
+fn verdicts_are_invalidated_by_a_new_block() {
+    let cache = cache(10);
+    let now = Instant::now();
+    get_and_insert(&cache, submission(1, 0), &accepted(1), now);
+
+    assert_eq!(cache.get(&submission(1, 1), now), None);
+
+    // A verdict given against a block that is no longer the latest one is not cached.
+    get_and_insert(&cache, submission(2, 1), &accepted(2), now);
+    cache.insert(&submission(3, 0), &accepted(3), now);
+    assert_eq!(cache.get(&submission(3, 0), now), None);
+}
+
+#[test]
+fn internal_errors_are_not_cached() {
+    let cache = cache(10);
+    let now = Instant::now();
+    get_and_insert(&cache, submission(1, 0), &Err(StarknetError::internal("Error")), now);
+
+    assert_eq!(cache.get(&submission(1, 0), now), None);
+}
+
+#[test]
+fn oldest_verdicts_are_evicted_at_capacity() {
+    let cache = cache(2);
+    let now = Instant::now();
+    for tx_hash in 1..=3 {
+        get_and_insert(&cache, submission(tx_hash, 0), &accepted(tx_hash), now);
+    }
+
+    assert_eq!(cache.get(&submission(1, 0), now), None);
+    assert_eq!(cache.get(&submission(2, 0), now), Some(accepted(2)));
+    assert_eq!(cache.get(&submission(3, 0), now), Some(accepted(3)));
+}
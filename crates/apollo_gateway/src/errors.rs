@@ -196,7 +196,8 @@ pub fn mempool_client_result_to_gw_spec_result(
             match mempool_error {
                 MempoolError::DuplicateNonce { .. }
                 | MempoolError::NonceTooLarge { .. }
-                | MempoolError::NonceTooOld { .. } => {
+                | MempoolError::NonceTooOld { .. }
+                | MempoolError::NonceDistanceExceeded { .. } => {
                     Err(GatewaySpecError::InvalidTransactionNonce)
                 }
                 MempoolError::DuplicateTransaction { .. } => Err(GatewaySpecError::DuplicateTx),
@@ -204,6 +205,10 @@ pub fn mempool_client_result_to_gw_spec_result(
                 MempoolError::MempoolFull => {
                     Err(GatewaySpecError::UnexpectedError { data: "Mempool full".to_owned() })
                 }
+                MempoolError::AccountTxLimitExceeded { .. }
+                | MempoolError::AccountCapacityExceeded { .. } => {
+                    Err(GatewaySpecError::UnexpectedError { data: mempool_error.to_string() })
+                }
                 MempoolError::P2pPropagatorClientError { .. } => {
                     // Not an error from the gateway's perspective.
                     warn!("P2p propagator client error: {}", mempool_error);
@@ -239,15 +244,21 @@ pub fn mempool_client_err_to_deprecated_gw_err(err: MempoolClientError) -> Stark
                         "StarknetErrorCode.NONCE_TOO_LARGE".to_string(),
                     )
                 }
-                MempoolError::NonceTooOld { .. } => StarknetErrorCode::KnownErrorCode(
-                    KnownStarknetErrorCode::InvalidTransactionNonce,
-                ),
+                MempoolError::NonceTooOld { .. } | MempoolError::NonceDistanceExceeded { .. } => {
+                    StarknetErrorCode::KnownErrorCode(
+                        KnownStarknetErrorCode::InvalidTransactionNonce,
+                    )
+                }
                 MempoolError::DuplicateTransaction { .. } => {
                     StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::DuplicatedTransaction)
                 }
-                MempoolError::MempoolFull => StarknetErrorCode::KnownErrorCode(
-                    KnownStarknetErrorCode::TransactionLimitExceeded,
-                ),
+                MempoolError::MempoolFull
+                | MempoolError::AccountTxLimitExceeded { .. }
+                | MempoolError::AccountCapacityExceeded { .. } => {
+                    StarknetErrorCode::KnownErrorCode(
+                        KnownStarknetErrorCode::TransactionLimitExceeded,
+                    )
+                }
                 MempoolError::P2pPropagatorClientError { .. } => {
                     // Not an error from the gateway's perspective.
                     return StarknetError::internal(&message);
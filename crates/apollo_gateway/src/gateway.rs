@@ -1,5 +1,6 @@
 use std::clone::Clone;
 use std::sync::Arc;
+use std::time::Instant;
 
 use apollo_class_manager_types::transaction_converter::{
     TransactionConverter,
@@ -15,7 +16,10 @@ use apollo_gateway_types::gateway_types::{
     DeclareGatewayOutput,
     DeployAccountGatewayOutput,
     GatewayOutput,
+    GatewayPolicy,
     InvokeGatewayOutput,
+    ValidateTransactionOutput,
+    ValidationResources,
 };
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_mempool_types::communication::{AddTransactionArgsWrapper, SharedMempoolClient};
@@ -31,22 +35,28 @@ use starknet_api::rpc_transaction::{
     RpcDeclareTransaction,
     RpcTransaction,
 };
-use tracing::{debug, error, info, instrument, warn, Span};
+use starknet_api::transaction::Transaction;
+use tracing::{debug, info, instrument, warn, Span};
 
 use crate::config::GatewayConfig;
+use crate::duplicate_submission_cache::{DuplicateSubmissionCache, Submission};
 use crate::errors::{
     mempool_client_result_to_deprecated_gw_result,
     transaction_converter_err_to_deprecated_gw_err,
     GatewayResult,
 };
+use crate::gateway_policy::{watch_policy_file, ActiveGatewayPolicy};
 use crate::metrics::{register_metrics, GatewayMetricHandle, GATEWAY_ADD_TX_LATENCY};
+use crate::state_cache::LatestBlockStateCache;
 use crate::state_reader::StateReaderFactory;
 use crate::stateful_transaction_validator::{
+    get_latest_block_info,
     StatefulTransactionValidatorFactory,
     StatefulTransactionValidatorFactoryTrait,
 };
 use crate::stateless_transaction_validator::StatelessTransactionValidator;
 use crate::sync_state_reader::SyncStateReaderFactory;
+use crate::validation_worker_pool::ValidationWorkerPool;
 
 #[cfg(test)]
 #[path = "gateway_test.rs"]
@@ -61,6 +71,9 @@ pub struct Gateway {
     pub mempool_client: SharedMempoolClient,
     pub transaction_converter: Arc<TransactionConverter>,
     pub chain_info: Arc<ChainInfo>,
+    duplicate_submission_cache: Arc<DuplicateSubmissionCache>,
+    validation_worker_pool: Arc<ValidationWorkerPool>,
+    policy: Arc<ActiveGatewayPolicy>,
 }
 
 impl Gateway {
@@ -70,18 +83,28 @@ impl Gateway {
         mempool_client: SharedMempoolClient,
         transaction_converter: TransactionConverter,
     ) -> Self {
+        let policy = Arc::new(ActiveGatewayPolicy::new(config.initial_policy()));
         Self {
             config: Arc::new(config.clone()),
             stateless_tx_validator: Arc::new(StatelessTransactionValidator {
                 config: config.stateless_tx_validator_config.clone(),
+                is_paymaster_enabled: config.chain_info.is_paymaster_enabled,
             }),
             stateful_tx_validator_factory: Arc::new(StatefulTransactionValidatorFactory {
                 config: config.stateful_tx_validator_config.clone(),
+                policy: policy.clone(),
             }),
             state_reader_factory,
             mempool_client,
             chain_info: Arc::new(config.chain_info.clone()),
             transaction_converter: Arc::new(transaction_converter),
+            duplicate_submission_cache: Arc::new(DuplicateSubmissionCache::new(
+                config.duplicate_submission_cache_config.clone(),
+            )),
+            validation_worker_pool: Arc::new(ValidationWorkerPool::new(
+                &config.validation_worker_pool_config,
+            )),
+            policy,
         }
     }
 
@@ -94,33 +117,38 @@ impl Gateway {
     ) -> GatewayResult<GatewayOutput> {
         debug!("Processing tx: {:?}", tx);
 
-        if let RpcTransaction::Declare(ref declare_tx) = tx {
-            self.check_declare_permissions(declare_tx)?;
+        self.check_policy(&tx)?;
+
+        let submission = self.identify_submission(&tx).await;
+        if let Some(verdict) = submission
+            .as_ref()
+            .and_then(|submission| self.duplicate_submission_cache.get(submission, Instant::now()))
+        {
+            debug!("Returning the cached verdict on a resubmitted tx.");
+            return verdict;
         }
 
+        self.process_and_add_tx(tx, p2p_message_metadata, submission.as_ref()).await
+    }
+
+    async fn process_and_add_tx(
+        &self,
+        tx: RpcTransaction,
+        p2p_message_metadata: Option<BroadcastedMessageMetadata>,
+        submission: Option<&Submission>,
+    ) -> GatewayResult<GatewayOutput> {
         let mut metric_counters = GatewayMetricHandle::new(&tx, &p2p_message_metadata);
         metric_counters.count_transaction_received();
 
-        let blocking_task =
-            ProcessTxBlockingTask::new(self, tx.clone(), tokio::runtime::Handle::current());
-        // Run the blocking task in the current span.
-        let curr_span = Span::current();
-        let add_tx_args =
-            tokio::task::spawn_blocking(move || curr_span.in_scope(|| blocking_task.process_tx()))
-                .await
-                .map_err(|join_err| {
-                    error!("Failed to process tx: {}", join_err);
-                    StarknetError::internal(&join_err.to_string())
-                })?
-                .inspect_err(|starknet_error| {
-                    info!(
-                        "Gateway validation failed for tx: {:?} with error: {}",
-                        tx, starknet_error
-                    );
-                })?;
+        let (add_tx_args, _validation_resources) = self
+            .process_tx(tx)
+            .await
+            .inspect_err(|error| self.cache_verdict(submission, &Err(error.clone())))?;
 
         let gateway_output = create_gateway_output(&add_tx_args.tx);
 
+        // Mempool errors are not cached, as they depend on the mempool's content, which may change
+        // before the transaction is resubmitted.
         let add_tx_args = AddTransactionArgsWrapper { args: add_tx_args, p2p_message_metadata };
         mempool_client_result_to_deprecated_gw_result(
             self.mempool_client.add_tx(add_tx_args).await,
@@ -128,38 +156,138 @@ impl Gateway {
 
         metric_counters.transaction_sent_to_mempool();
 
-        Ok(gateway_output)
+        let result = Ok(gateway_output);
+        self.cache_verdict(submission, &result);
+        result
+    }
+
+    fn cache_verdict(
+        &self,
+        submission: Option<&Submission>,
+        verdict: &GatewayResult<GatewayOutput>,
+    ) {
+        if let Some(submission) = submission {
+            self.duplicate_submission_cache.insert(submission, verdict, Instant::now());
+        }
+    }
+
+    /// Identifies the submission of the transaction for the duplicate submission cache. Returns
+    /// `None` if the cache is disabled or the submission cannot be identified, in which case the
+    /// transaction is processed as usual.
+    async fn identify_submission(&self, tx: &RpcTransaction) -> Option<Submission> {
+        if !self.duplicate_submission_cache.is_enabled() {
+            return None;
+        }
+
+        let tx = tx.clone();
+        let state_reader_factory = self.state_reader_factory.clone();
+        let chain_info = self.chain_info.clone();
+        // Reading the latest block blocks the thread, and hashing a declared class is
+        // CPU-intensive.
+        tokio::task::spawn_blocking(move || {
+            let latest_block_number =
+                get_latest_block_info(state_reader_factory.as_ref()).ok()?.block_number;
+            let signature = tx.signature().clone();
+            let tx_hash =
+                Transaction::from(tx).calculate_transaction_hash(&chain_info.chain_id).ok()?;
+            Some(Submission { tx_hash, signature, latest_block_number })
+        })
+        .await
+        .ok()
+        .flatten()
+    }
+
+    /// Runs all the validations of `add_tx`, without adding the transaction to the mempool or
+    /// propagating it to other nodes.
+    #[instrument(skip_all, ret)]
+    pub async fn validate_tx(
+        &self,
+        tx: RpcTransaction,
+    ) -> GatewayResult<ValidateTransactionOutput> {
+        debug!("Validating tx: {:?}", tx);
+
+        self.check_policy(&tx)?;
+
+        let (add_tx_args, validation_resources) = self.process_tx(tx).await?;
+
+        Ok(ValidateTransactionOutput {
+            transaction_hash: add_tx_args.tx.tx_hash,
+            validation_resources,
+        })
     }
 
-    fn check_declare_permissions(
+    async fn process_tx(
         &self,
-        declare_tx: &RpcDeclareTransaction,
-    ) -> Result<(), StarknetError> {
-        // TODO(noamsp): Return same error as in Python gateway.
-        if self.config.block_declare {
-            return Err(StarknetError {
-                code: StarknetErrorCode::UnknownErrorCode(
-                    "StarknetErrorCode.BLOCKED_TRANSACTION_TYPE".to_string(),
-                ),
-                message: "Transaction type is temporarily blocked.".to_string(),
-            });
+        tx: RpcTransaction,
+    ) -> GatewayResult<(AddTransactionArgs, Option<ValidationResources>)> {
+        let blocking_task =
+            ProcessTxBlockingTask::new(self, tx.clone(), tokio::runtime::Handle::current());
+        // Run the blocking task in the current span.
+        let curr_span = Span::current();
+        self.validation_worker_pool
+            .run(move || curr_span.in_scope(|| blocking_task.process_tx()))
+            .await
+            .inspect_err(|starknet_error| {
+                info!("Gateway validation failed for tx: {:?} with error: {}", tx, starknet_error);
+            })
+    }
+
+    /// Returns the admission policy currently applied by the gateway.
+    pub fn get_policy(&self) -> GatewayPolicy {
+        self.policy.current().as_ref().clone()
+    }
+
+    fn check_policy(&self, tx: &RpcTransaction) -> Result<(), StarknetError> {
+        let policy = self.policy.current();
+        if let RpcTransaction::Declare(declare_tx) = tx {
+            check_declare_permissions(&policy, declare_tx)?;
         }
-        let RpcDeclareTransaction::V3(declare_v3_tx) = declare_tx;
-        if !self.config.is_authorized_declarer(&declare_v3_tx.sender_address) {
-            return Err(StarknetError {
-                code: StarknetErrorCode::KnownErrorCode(
-                    KnownStarknetErrorCode::UnauthorizedDeclare,
-                ),
-                message: format!(
-                    "Account address {} is not allowed to declare contracts.",
-                    &declare_v3_tx.sender_address
-                ),
-            });
+        // Transactions whose sender address cannot be calculated are rejected by the later
+        // validations.
+        if !policy.denied_sender_accounts.is_empty() {
+            if let Ok(sender_address) = tx.calculate_sender_address() {
+                if policy.is_denied_sender(&sender_address) {
+                    return Err(StarknetError {
+                        code: StarknetErrorCode::UnknownErrorCode(
+                            "StarknetErrorCode.BLOCKED_SENDER_ADDRESS".to_string(),
+                        ),
+                        message: format!(
+                            "Account address {sender_address} is temporarily blocked."
+                        ),
+                    });
+                }
+            }
         }
         Ok(())
     }
 }
 
+fn check_declare_permissions(
+    policy: &GatewayPolicy,
+    declare_tx: &RpcDeclareTransaction,
+) -> Result<(), StarknetError> {
+    // TODO(noamsp): Return same error as in Python gateway.
+    if policy.block_declare {
+        return Err(StarknetError {
+            code: StarknetErrorCode::UnknownErrorCode(
+                "StarknetErrorCode.BLOCKED_TRANSACTION_TYPE".to_string(),
+            ),
+            message: "Transaction type is temporarily blocked.".to_string(),
+        });
+    }
+    let RpcDeclareTransaction::V3(declare_v3_tx) = declare_tx;
+    if !policy.is_authorized_declarer(&declare_v3_tx.sender_address) {
+        return Err(StarknetError {
+            code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::UnauthorizedDeclare),
+            message: format!(
+                "Account address {} is not allowed to declare contracts.",
+                &declare_v3_tx.sender_address
+            ),
+        });
+    }
+    Ok(())
+}
+
 /// CPU-intensive transaction processing, spawned in a blocking thread to avoid blocking other tasks
 /// from running.
 struct ProcessTxBlockingTask {
@@ -189,7 +317,7 @@ impl ProcessTxBlockingTask {
 
     // TODO(Arni): Make into async function and remove all block_on calls once we manage removing
     // the spawn_blocking call.
-    fn process_tx(self) -> GatewayResult<AddTransactionArgs> {
+    fn process_tx(self) -> GatewayResult<(AddTransactionArgs, Option<ValidationResources>)> {
         // TODO(Arni, 1/5/2024): Perform congestion control.
 
         // Perform stateless validations.
@@ -221,17 +349,19 @@ impl ProcessTxBlockingTask {
             .stateful_tx_validator_factory
             .instantiate_validator(self.state_reader_factory.as_ref(), &self.chain_info)?;
 
-        let nonce = stateful_transaction_validator.extract_state_nonce_and_run_validations(
-            &executable_tx,
-            self.mempool_client,
-            self.runtime,
-        )?;
+        let (nonce, validation_resources) = stateful_transaction_validator
+            .extract_state_nonce_and_run_validations(
+                &executable_tx,
+                self.mempool_client,
+                self.runtime,
+            )?;
 
         // TODO(Arni): Add the Sierra and the Casm to the mempool input.
-        Ok(AddTransactionArgs {
+        let add_tx_args = AddTransactionArgs {
             tx: internal_tx,
             account_state: AccountState { address: executable_tx.contract_address(), nonce },
-        })
+        };
+        Ok((add_tx_args, validation_resources))
     }
 }
 
@@ -246,6 +376,7 @@ pub fn create_gateway(
         shared_state_sync_client,
         class_manager_client: class_manager_client.clone(),
         runtime,
+        state_cache: LatestBlockStateCache::new(config.state_cache_config.clone()),
     });
     let transaction_converter =
         TransactionConverter::new(class_manager_client, config.chain_info.chain_id.clone());
@@ -257,6 +388,12 @@ pub fn create_gateway(
 impl ComponentStarter for Gateway {
     async fn start(&mut self) {
         register_metrics();
+        if self.config.policy_file_config.enabled {
+            tokio::spawn(watch_policy_file(
+                self.policy.clone(),
+                self.config.policy_file_config.clone(),
+            ));
+        }
     }
 }
 
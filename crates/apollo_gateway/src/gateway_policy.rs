@@ -0,0 +1,71 @@
+use std::sync::{Arc, RwLock};
+
+use apollo_gateway_types::gateway_types::GatewayPolicy;
+use tracing::{info, warn};
+
+use crate::config::GatewayPolicyFileConfig;
+
+#[cfg(test)]
+#[path = "gateway_policy_test.rs"]
+mod gateway_policy_test;
+
+/// Holds the gateway policy in effect. Replacing the policy is atomic: each transaction is
+/// admitted according to a single version of the policy.
+pub(crate) struct ActiveGatewayPolicy {
+    policy: RwLock<Arc<GatewayPolicy>>,
+}
+
+impl ActiveGatewayPolicy {
+    pub fn new(policy: GatewayPolicy) -> Self {
+        Self { policy: RwLock::new(Arc::new(policy)) }
+    }
+
+    pub fn current(&self) -> Arc<GatewayPolicy> {
+        self.policy.read().expect("Gateway policy lock should not be poisoned.").clone()
+    }
+
+    /// Replaces the policy in effect, logging the change for auditing. `source` describes where
+    /// the new policy came from.
+    pub fn replace(&self, policy: GatewayPolicy, source: &str) {
+        let mut active_policy =
+            self.policy.write().expect("Gateway policy lock should not be poisoned.");
+        if **active_policy == policy {
+            return;
+        }
+        info!(
+            "Replacing the gateway policy from {source}. Previous policy: {:?}, new policy: {:?}.",
+            active_policy, policy
+        );
+        *active_policy = Arc::new(policy);
+    }
+}
+
+/// Replaces the active policy whenever the contents of the policy file change. If the file cannot
+/// be read or parsed, the active policy is kept.
+pub(crate) async fn watch_policy_file(
+    active_policy: Arc<ActiveGatewayPolicy>,
+    config: GatewayPolicyFileConfig,
+) {
+    let source = config.path.display().to_string();
+    let mut poll_interval = tokio::time::interval(config.poll_interval);
+    // Contents are compared to the previous read, so that each change is handled once.
+    let mut last_contents = None;
+    loop {
+        poll_interval.tick().await;
+        let contents = tokio::fs::read(&config.path).await.map_err(|err| err.to_string());
+        if last_contents.as_ref() == Some(&contents) {
+            continue;
+        }
+
+        let policy = contents.clone().and_then(|contents| {
+            serde_json::from_slice::<GatewayPolicy>(&contents).map_err(|err| err.to_string())
+        });
+        match policy {
+            Ok(policy) => active_policy.replace(policy, &source),
+            Err(err) => {
+                warn!("Keeping the active gateway policy, failed to load {source}: {err}");
+            }
+        }
+        last_contents = Some(contents);
+    }
+}
@@ -0,0 +1,59 @@
+use std::sync::Arc;
+use std::time::Duration;
+
+use apollo_gateway_types::gateway_types::GatewayPolicy;
+use starknet_api::contract_address;
+use tempfile::TempDir;
+
+use crate::config::GatewayPolicyFileConfig;
+use crate::gateway_policy::{watch_policy_file, ActiveGatewayPolicy};
+
+const POLL_INTERVAL: Duration = Duration::from_millis(10);
+
+fn policy(block_declare: bool) -> GatewayPolicy {
+    GatewayPolicy {
+        block_declare,
+        authorized_declarer_accounts: None,
+        denied_sender_accounts: vec![contract_address!("0x1")],
+        min_gas_price_percentage: 80,
+    }
+}
+
+async fn wait_for_policy(active_policy: &ActiveGatewayPolicy, expected_policy: &GatewayPolicy) {
+    tokio::time::timeout(Duration::from_secs(5), async {
+        while *active_policy.current() != *expected_policy {
+            tokio::time::sleep(POLL_INTERVAL).await;
+        }
+    })
+    .await
+    .expect("The active policy should be replaced.");
+}
+
+#[tokio::test]
+async fn policy_file_changes_replace_the_active_policy() {
+    let dir = TempDir::new().unwrap();
+    let path = dir.path().join("policy.json");
+    let initial_policy = policy(false);
+    let active_policy = Arc::new(ActiveGatewayPolicy::new(initial_policy.clone()));
+    let config =
+        GatewayPolicyFileConfig { enabled: true, path: path.clone(), poll_interval: POLL_INTERVAL };
+    let watcher = tokio::spawn(watch_policy_file(active_policy.clone(), config));
+
+    // A missing file keeps the initial policy.
+    tokio::time::sleep(3 * POLL_INTERVAL).await;
+    assert_eq!(*active_policy.current(), initial_policy);
+
+    let blocking_policy = policy(true);
+    std::fs::write(&path, serde_json::to_vec(&blocking_policy).unwrap()).unwrap();
+    wait_for_policy(&active_policy, &blocking_policy).await;
+
+    // An invalid file keeps the active policy.
+    std::fs::write(&path, "{\"block_declare\": false}").unwrap();
+    tokio::time::sleep(3 * POLL_INTERVAL).await;
+    assert_eq!(*active_policy.current(), blocking_policy);
+
+    std::fs::write(&path, serde_json::to_vec(&initial_policy).unwrap()).unwrap();
+    wait_for_policy(&active_policy, &initial_policy).await;
+
+    watcher.abort();
+}
@@ -15,6 +15,7 @@ use apollo_gateway_types::gateway_types::{
     DeclareGatewayOutput,
     DeployAccountGatewayOutput,
     GatewayOutput,
+    GatewayPolicy,
     InvokeGatewayOutput,
 };
 use apollo_mempool_types::communication::{
@@ -70,9 +71,13 @@ use strum::VariantNames;
 use tempfile::TempDir;
 
 use crate::config::{
+    DuplicateSubmissionCacheConfig,
     GatewayConfig,
+    GatewayPolicyFileConfig,
+    StateCacheConfig,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
+    ValidationWorkerPoolConfig,
 };
 use crate::errors::GatewayResult;
 use crate::gateway::{Gateway, ProcessTxBlockingTask};
@@ -81,6 +86,8 @@ use crate::metrics::{
     GatewayMetricHandle,
     SourceLabelValue,
     GATEWAY_ADD_TX_LATENCY,
+    GATEWAY_DUPLICATE_SUBMISSION_CACHE_HITS,
+    GATEWAY_DUPLICATE_SUBMISSION_CACHE_MISSES,
     GATEWAY_TRANSACTIONS_FAILED,
     GATEWAY_TRANSACTIONS_RECEIVED,
     GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL,
@@ -113,6 +120,10 @@ fn config() -> GatewayConfig {
         chain_info: ChainInfo::create_for_testing(),
         block_declare: false,
         authorized_declarer_accounts: None,
+        duplicate_submission_cache_config: DuplicateSubmissionCacheConfig::default(),
+        state_cache_config: StateCacheConfig::default(),
+        validation_worker_pool_config: ValidationWorkerPoolConfig::default(),
+        policy_file_config: GatewayPolicyFileConfig::default(),
     }
 }
 
@@ -347,6 +358,7 @@ fn process_tx_task(
     ProcessTxBlockingTask {
         stateless_tx_validator: Arc::new(StatelessTransactionValidator {
             config: StatelessTransactionValidatorConfig::default(),
+            is_paymaster_enabled: chain_info.is_paymaster_enabled,
         }),
         stateful_tx_validator_factory: Arc::new(mock_validator_factory),
         state_reader_factory: Arc::new(MockStateReaderFactory::new()),
@@ -379,6 +391,10 @@ fn process_tx_task(
     Err(MempoolClientError::MempoolError(MempoolError::NonceTooLarge(Nonce::default()))),
     StarknetErrorCode::UnknownErrorCode("StarknetErrorCode.NONCE_TOO_LARGE".to_string())
 )]
+#[case::tx_exceeding_account_tx_limit(
+    Err(MempoolClientError::MempoolError(MempoolError::AccountTxLimitExceeded { address: ContractAddress::default(), max_txs: 1 })),
+    StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::TransactionLimitExceeded)
+)]
 #[tokio::test]
 async fn test_add_tx_negative(
     mut mock_dependencies: MockDependencies,
@@ -423,6 +439,87 @@ async fn test_add_tx_positive(
     );
     check_positive_add_tx_result(tx_args, result.unwrap());
 }
+
+#[rstest]
+#[tokio::test]
+async fn test_add_tx_resubmission_returns_cached_verdict(
+    mut mock_dependencies: MockDependencies,
+    #[values(invoke_args(), deploy_account_args(), declare_args())] tx_args: impl TestingTxArgs,
+) {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let _recorder_guard = metrics::set_default_local_recorder(&recorder);
+    mock_dependencies.config.duplicate_submission_cache_config.enabled = true;
+    // The mempool expects the transaction only once.
+    setup_mock_state(&mut mock_dependencies, &tx_args, Ok(())).await;
+
+    let input_tx = tx_args.get_rpc_tx();
+    let gateway = mock_dependencies.gateway();
+    let result = gateway.add_tx(input_tx.clone(), p2p_message_metadata()).await;
+    let resubmission_result = gateway.add_tx(input_tx, p2p_message_metadata()).await;
+
+    assert_eq!(resubmission_result, result);
+    let metrics = recorder.handle().render();
+    assert_eq!(
+        GATEWAY_DUPLICATE_SUBMISSION_CACHE_MISSES.parse_numeric_metric::<u64>(&metrics),
+        Some(1)
+    );
+    assert_eq!(
+        GATEWAY_DUPLICATE_SUBMISSION_CACHE_HITS.parse_numeric_metric::<u64>(&metrics),
+        Some(1)
+    );
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_add_tx_resubmission_after_mempool_error_is_processed_again(
+    mut mock_dependencies: MockDependencies,
+) {
+    mock_dependencies.config.duplicate_submission_cache_config.enabled = true;
+    let tx_args = invoke_args();
+    let mempool_error = MempoolClientError::MempoolError(MempoolError::DuplicateNonce {
+        address: ContractAddress::default(),
+        nonce: Nonce::default(),
+    });
+    setup_mock_state(&mut mock_dependencies, &tx_args, Err(mempool_error)).await;
+    // The mempool accepts the resubmission, e.g., once the conflicting transaction was removed.
+    setup_mock_state(&mut mock_dependencies, &tx_args, Ok(())).await;
+
+    let input_tx = tx_args.get_rpc_tx();
+    let gateway = mock_dependencies.gateway();
+    assert!(gateway.add_tx(input_tx.clone(), p2p_message_metadata()).await.is_err());
+    let resubmission_result = gateway.add_tx(input_tx, p2p_message_metadata()).await;
+
+    check_positive_add_tx_result(tx_args, resubmission_result.unwrap());
+}
+
+#[rstest]
+#[tokio::test]
+async fn test_validate_tx_positive(
+    mut mock_dependencies: MockDependencies,
+    #[values(invoke_args(), deploy_account_args(), declare_args())] tx_args: impl TestingTxArgs,
+) {
+    let input_tx = tx_args.get_rpc_tx();
+    let expected_internal_tx = tx_args.get_internal_tx();
+    setup_class_manager_client_mock(
+        &mut mock_dependencies.mock_class_manager_client,
+        input_tx.clone(),
+    );
+    fund_account(
+        &mock_dependencies.config.chain_info,
+        expected_internal_tx.contract_address(),
+        VALID_ACCOUNT_BALANCE,
+        &mut mock_dependencies.state_reader_factory.state_reader.blockifier_state_reader,
+    );
+
+    // No mempool expectations are set; the transaction must not be added.
+    let gateway = mock_dependencies.gateway();
+    let output = gateway.validate_tx(input_tx).await.unwrap();
+
+    assert_eq!(output.transaction_hash, expected_internal_tx.tx_hash());
+    let validation_resources = output.validation_resources.expect("`__validate__` should run.");
+    assert!(validation_resources.n_steps > 0 || validation_resources.gas_consumed > 0);
+}
+
 // Gateway spec errors tests.
 // TODO(Arni): Add tests for all the error cases. Check the response (use `into_response` on the
 // result of `add_tx`).
@@ -550,6 +647,32 @@ async fn test_unauthorized_declare_config(
     assert_eq!(gateway_output_code_error, expected_code_error);
 }
 
+#[rstest]
+#[tokio::test]
+async fn test_policy_replaced_at_runtime(mock_dependencies: MockDependencies) {
+    let gateway = mock_dependencies.gateway();
+    let invoke_tx = invoke_args().get_rpc_tx();
+    let policy = GatewayPolicy {
+        block_declare: true,
+        denied_sender_accounts: vec![invoke_tx.calculate_sender_address().unwrap()],
+        ..gateway.get_policy()
+    };
+
+    gateway.policy.replace(policy.clone(), "test");
+    assert_eq!(gateway.get_policy(), policy);
+
+    let result = gateway.add_tx(invoke_tx, None).await;
+    let expected_code =
+        StarknetErrorCode::UnknownErrorCode("StarknetErrorCode.BLOCKED_SENDER_ADDRESS".to_string());
+    assert_eq!(result.unwrap_err().code, expected_code);
+
+    let result = gateway.add_tx(declare_tx(), None).await;
+    let expected_code = StarknetErrorCode::UnknownErrorCode(
+        "StarknetErrorCode.BLOCKED_TRANSACTION_TYPE".to_string(),
+    );
+    assert_eq!(result.unwrap_err().code, expected_code);
+}
+
 #[rstest]
 #[case::two_addresses(
     Some(vec![
@@ -1,13 +1,16 @@
 pub mod communication;
 mod compiler_version;
 pub mod config;
+mod duplicate_submission_cache;
 pub mod errors;
 pub mod gateway;
+mod gateway_policy;
 pub mod metrics;
 pub mod rpc_objects;
 pub mod rpc_state_reader;
 #[cfg(test)]
 mod rpc_state_reader_test;
+mod state_cache;
 pub mod state_reader;
 #[cfg(any(feature = "testing", test))]
 pub mod state_reader_test_utils;
@@ -18,3 +21,4 @@ mod sync_state_reader;
 mod sync_state_reader_test;
 #[cfg(test)]
 mod test_utils;
+mod validation_worker_pool;
@@ -24,6 +24,14 @@ define_metrics!(
         LabeledMetricCounter { GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL, "gateway_transactions_sent_to_mempool", "Counter of transactions sent to the mempool", init = 0 , labels = TRANSACTION_TYPE_AND_SOURCE_LABELS},
         MetricHistogram { GATEWAY_ADD_TX_LATENCY, "gateway_add_tx_latency", "Latency of gateway add_tx function in secs" },
         MetricHistogram { GATEWAY_VALIDATE_TX_LATENCY, "gateway_validate_tx_latency", "Latency of gateway validate function in secs" },
+        MetricCounter { GATEWAY_DUPLICATE_SUBMISSION_CACHE_HITS, "gateway_duplicate_submission_cache_hits", "Counter of submitted transactions answered by a cached verdict", init = 0 },
+        MetricCounter { GATEWAY_DUPLICATE_SUBMISSION_CACHE_MISSES, "gateway_duplicate_submission_cache_misses", "Counter of submitted transactions with no cached verdict", init = 0 },
+        MetricCounter { GATEWAY_STATE_CACHE_HITS, "gateway_state_cache_hits", "Counter of state reads of stateful validation served by the block state cache", init = 0 },
+        MetricCounter { GATEWAY_STATE_CACHE_MISSES, "gateway_state_cache_misses", "Counter of state reads of stateful validation not served by the block state cache", init = 0 },
+        MetricGauge { GATEWAY_STATE_CACHE_SIZE, "gateway_state_cache_size", "The number of values held by the block state caches" },
+        MetricHistogram { GATEWAY_VALIDATION_QUEUE_WAIT_TIME, "gateway_validation_queue_wait_time_secs", "Time transactions waited for a free validation worker in secs" },
+        MetricGauge { GATEWAY_VALIDATION_ACTIVE_WORKERS, "gateway_validation_active_workers", "The number of validation workers processing transactions" },
+        MetricCounter { GATEWAY_VALIDATION_REJECTIONS, "gateway_validation_rejections", "Counter of transactions rejected as the validation queue is full", init = 0 },
     },
     Infra => {
         LabeledMetricHistogram {
@@ -125,4 +133,12 @@ pub(crate) fn register_metrics() {
     GATEWAY_TRANSACTIONS_SENT_TO_MEMPOOL.register();
     GATEWAY_ADD_TX_LATENCY.register();
     GATEWAY_VALIDATE_TX_LATENCY.register();
+    GATEWAY_DUPLICATE_SUBMISSION_CACHE_HITS.register();
+    GATEWAY_DUPLICATE_SUBMISSION_CACHE_MISSES.register();
+    GATEWAY_STATE_CACHE_HITS.register();
+    GATEWAY_STATE_CACHE_MISSES.register();
+    GATEWAY_STATE_CACHE_SIZE.register();
+    GATEWAY_VALIDATION_QUEUE_WAIT_TIME.register();
+    GATEWAY_VALIDATION_ACTIVE_WORKERS.register();
+    GATEWAY_VALIDATION_REJECTIONS.register();
 }
@@ -0,0 +1,205 @@
+use std::collections::HashMap;
+use std::hash::Hash;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex, MutexGuard, OnceLock, RwLock};
+
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::state_api::StateResult;
+use starknet_api::block::{BlockHash, BlockInfo, BlockNumber};
+use starknet_api::core::{ClassHash, ContractAddress, Nonce};
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+use crate::config::StateCacheConfig;
+use crate::metrics::{
+    GATEWAY_STATE_CACHE_HITS,
+    GATEWAY_STATE_CACHE_MISSES,
+    GATEWAY_STATE_CACHE_SIZE,
+};
+
+/// Holds the state cache of the latest block, shared by all the state readers of that block.
+/// The cache is replaced by an empty one once a different block is observed as the latest block.
+/// Blocks are told apart by their hash, so that a block which replaced a reverted one at the same
+/// height does not share its cache.
+pub(crate) struct LatestBlockStateCache {
+    config: StateCacheConfig,
+    cache: Mutex<Option<Arc<BlockStateCache>>>,
+}
+
+impl LatestBlockStateCache {
+    pub fn new(config: StateCacheConfig) -> Self {
+        Self { config, cache: Mutex::new(None) }
+    }
+
+    /// Returns the state cache of the given latest block, discarding the cache of the previous one.
+    pub fn observe_latest_block(
+        &self,
+        block_number: BlockNumber,
+        block_hash: BlockHash,
+    ) -> Arc<BlockStateCache> {
+        let mut cache = self.lock();
+        match cache.as_ref() {
+            Some(block_cache)
+                if block_cache.block_number == block_number
+                    && block_cache.block_hash == block_hash =>
+            {
+                block_cache.clone()
+            }
+            _ => {
+                let block_cache =
+                    Arc::new(BlockStateCache::new(block_number, block_hash, self.config.clone()));
+                *cache = Some(block_cache.clone());
+                block_cache
+            }
+        }
+    }
+
+    fn lock(&self) -> MutexGuard<'_, Option<Arc<BlockStateCache>>> {
+        self.cache.lock().expect("Latest block state cache lock should not be poisoned.")
+    }
+}
+
+/// A concurrent cache of the values read from the state of a single block.
+/// Only successful reads are cached, and the estimated size of the cached values is bounded by the
+/// config; once full, further values are read through without being cached.
+pub(crate) struct BlockStateCache {
+    block_number: BlockNumber,
+    block_hash: BlockHash,
+    config: StateCacheConfig,
+    block_info: OnceLock<BlockInfo>,
+    storage: RwLock<HashMap<(ContractAddress, StorageKey), Felt>>,
+    nonces: RwLock<HashMap<ContractAddress, Nonce>>,
+    class_hashes: RwLock<HashMap<ContractAddress, ClassHash>>,
+    // Undeclared classes are cached as `None`.
+    compiled_classes: RwLock<HashMap<ClassHash, Option<RunnableCompiledClass>>>,
+    // The number of cached values, excluding the block info.
+    n_values: AtomicUsize,
+    // The estimated size of the cached values, in bytes.
+    size_in_bytes: AtomicUsize,
+}
+
+impl BlockStateCache {
+    pub fn new(block_number: BlockNumber, block_hash: BlockHash, config: StateCacheConfig) -> Self {
+        Self {
+            block_number,
+            block_hash,
+            config,
+            block_info: OnceLock::new(),
+            storage: RwLock::default(),
+            nonces: RwLock::default(),
+            class_hashes: RwLock::default(),
+            compiled_classes: RwLock::default(),
+            n_values: AtomicUsize::new(0),
+            size_in_bytes: AtomicUsize::new(0),
+        }
+    }
+
+    pub fn get_block_info(
+        &self,
+        fetch: impl FnOnce() -> StateResult<BlockInfo>,
+    ) -> StateResult<BlockInfo> {
+        if let Some(block_info) = self.block_info.get() {
+            GATEWAY_STATE_CACHE_HITS.increment(1);
+            return Ok(block_info.clone());
+        }
+
+        GATEWAY_STATE_CACHE_MISSES.increment(1);
+        let block_info = fetch()?;
+        if self.block_info.set(block_info.clone()).is_ok() {
+            GATEWAY_STATE_CACHE_SIZE.increment(1);
+        }
+        Ok(block_info)
+    }
+
+    pub fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+        fetch: impl FnOnce() -> StateResult<Felt>,
+    ) -> StateResult<Felt> {
+        self.get_or_fetch(&self.storage, (contract_address, key), fetch, |_| 0)
+    }
+
+    pub fn get_nonce_at(
+        &self,
+        contract_address: ContractAddress,
+        fetch: impl FnOnce() -> StateResult<Nonce>,
+    ) -> StateResult<Nonce> {
+        self.get_or_fetch(&self.nonces, contract_address, fetch, |_| 0)
+    }
+
+    pub fn get_class_hash_at(
+        &self,
+        contract_address: ContractAddress,
+        fetch: impl FnOnce() -> StateResult<ClassHash>,
+    ) -> StateResult<ClassHash> {
+        self.get_or_fetch(&self.class_hashes, contract_address, fetch, |_| 0)
+    }
+
+    /// Returns the compiled class of the given class hash, or `None` if it is not declared.
+    pub fn get_compiled_class(
+        &self,
+        class_hash: ClassHash,
+        fetch: impl FnOnce() -> StateResult<Option<RunnableCompiledClass>>,
+    ) -> StateResult<Option<RunnableCompiledClass>> {
+        // The size of a compiled class is dominated by its bytecode.
+        self.get_or_fetch(&self.compiled_classes, class_hash, fetch, |compiled_class| {
+            compiled_class
+                .as_ref()
+                .map_or(0, |compiled_class| compiled_class.bytecode_length() * size_of::<Felt>())
+        })
+    }
+
+    /// Returns the cached value of the key, or fetches it and caches it if the cache has room for
+    /// it. The estimated size of a value is the size of its entry, plus its given heap size.
+    fn get_or_fetch<K: Eq + Hash, V: Clone>(
+        &self,
+        values: &RwLock<HashMap<K, V>>,
+        key: K,
+        fetch: impl FnOnce() -> StateResult<V>,
+        heap_size: impl FnOnce(&V) -> usize,
+    ) -> StateResult<V> {
+        if let Some(value) =
+            values.read().expect("Block state cache lock should not be poisoned.").get(&key)
+        {
+            GATEWAY_STATE_CACHE_HITS.increment(1);
+            return Ok(value.clone());
+        }
+
+        GATEWAY_STATE_CACHE_MISSES.increment(1);
+        let value = fetch()?;
+        let size = size_of::<(K, V)>() + heap_size(&value);
+        // Reserve room before inserting, so that concurrent insertions do not exceed the capacity.
+        let is_reserved = self
+            .size_in_bytes
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current_size| {
+                current_size
+                    .checked_add(size)
+                    .filter(|&new_size| new_size <= self.config.max_size_in_bytes)
+            })
+            .is_ok();
+        if is_reserved {
+            let mut values =
+                values.write().expect("Block state cache lock should not be poisoned.");
+            if values.insert(key, value.clone()).is_some() {
+                // A concurrent read already cached the value.
+                self.size_in_bytes.fetch_sub(size, Ordering::Relaxed);
+            } else {
+                self.n_values.fetch_add(1, Ordering::Relaxed);
+                GATEWAY_STATE_CACHE_SIZE.increment(1);
+            }
+        }
+        Ok(value)
+    }
+}
+
+impl Drop for BlockStateCache {
+    fn drop(&mut self) {
+        let n_block_info = usize::from(self.block_info.get().is_some());
+        let n_values = n_block_info + self.n_values.load(Ordering::Relaxed);
+        GATEWAY_STATE_CACHE_SIZE.decrement(
+            u32::try_from(n_values).expect("The number of cached values should fit in u32."),
+        );
+    }
+}
@@ -1,9 +1,12 @@
+use std::sync::Arc;
+
 use apollo_gateway_types::deprecated_gateway_error::{
     KnownStarknetErrorCode,
     StarknetError,
     StarknetErrorCode,
 };
 use apollo_gateway_types::errors::GatewaySpecError;
+use apollo_gateway_types::gateway_types::ValidationResources;
 use apollo_mempool_types::communication::SharedMempoolClient;
 use apollo_proc_macros::sequencer_latency_histogram;
 use blockifier::blockifier::stateful_validator::{
@@ -13,6 +16,7 @@ use blockifier::blockifier::stateful_validator::{
 use blockifier::blockifier_versioned_constants::VersionedConstants;
 use blockifier::bouncer::BouncerConfig;
 use blockifier::context::{BlockContext, ChainInfo};
+use blockifier::execution::call_info::CallInfo;
 use blockifier::state::cached_state::CachedState;
 use blockifier::transaction::account_transaction::{AccountTransaction, ExecutionFlags};
 use blockifier::transaction::transactions::enforce_fee;
@@ -29,6 +33,7 @@ use tracing::{debug, error};
 
 use crate::config::StatefulTransactionValidatorConfig;
 use crate::errors::{mempool_client_err_to_deprecated_gw_err, StatefulTransactionValidatorResult};
+use crate::gateway_policy::ActiveGatewayPolicy;
 use crate::metrics::GATEWAY_VALIDATE_TX_LATENCY;
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 
@@ -48,6 +53,7 @@ pub trait StatefulTransactionValidatorFactoryTrait: Send + Sync {
 }
 pub struct StatefulTransactionValidatorFactory {
     pub config: StatefulTransactionValidatorConfig,
+    pub(crate) policy: Arc<ActiveGatewayPolicy>,
 }
 
 impl StatefulTransactionValidatorFactoryTrait for StatefulTransactionValidatorFactory {
@@ -82,21 +88,25 @@ impl StatefulTransactionValidatorFactoryTrait for StatefulTransactionValidatorFa
         let blockifier_stateful_tx_validator =
             BlockifierStatefulValidator::create(state, block_context);
 
-        Ok(Box::new(StatefulTransactionValidator {
-            config: self.config.clone(),
-            blockifier_stateful_tx_validator,
-        }))
+        // The minimal gas price percentage is set by the gateway policy, which may change at
+        // runtime.
+        let config = StatefulTransactionValidatorConfig {
+            min_gas_price_percentage: self.policy.current().min_gas_price_percentage,
+            ..self.config.clone()
+        };
+        Ok(Box::new(StatefulTransactionValidator { config, blockifier_stateful_tx_validator }))
     }
 }
 
 #[cfg_attr(test, mockall::automock)]
 pub trait StatefulTransactionValidatorTrait {
+    /// Returns the account nonce, and the resources consumed by `__validate__` if it was run.
     fn extract_state_nonce_and_run_validations(
         &mut self,
         executable_tx: &ExecutableTransaction,
         mempool_client: SharedMempoolClient,
         runtime: tokio::runtime::Handle,
-    ) -> StatefulTransactionValidatorResult<Nonce>;
+    ) -> StatefulTransactionValidatorResult<(Nonce, Option<ValidationResources>)>;
 }
 
 pub struct StatefulTransactionValidator<B: BlockifierStatefulValidatorTrait> {
@@ -112,7 +122,7 @@ impl<B: BlockifierStatefulValidatorTrait> StatefulTransactionValidatorTrait
         executable_tx: &ExecutableTransaction,
         mempool_client: SharedMempoolClient,
         runtime: tokio::runtime::Handle,
-    ) -> StatefulTransactionValidatorResult<Nonce> {
+    ) -> StatefulTransactionValidatorResult<(Nonce, Option<ValidationResources>)> {
         let address = executable_tx.contract_address();
         let account_nonce =
             self.blockifier_stateful_tx_validator.get_nonce(address).map_err(|e| {
@@ -120,8 +130,13 @@ impl<B: BlockifierStatefulValidatorTrait> StatefulTransactionValidatorTrait
                 // TODO(yair): Fix this. Need to map the errors better.
                 StarknetError::internal(&e.to_string())
             })?;
-        self.run_transaction_validations(executable_tx, account_nonce, mempool_client, runtime)?;
-        Ok(account_nonce)
+        let validation_resources = self.run_transaction_validations(
+            executable_tx,
+            account_nonce,
+            mempool_client,
+            runtime,
+        )?;
+        Ok((account_nonce, validation_resources))
     }
 }
 
@@ -132,10 +147,9 @@ impl<B: BlockifierStatefulValidatorTrait> StatefulTransactionValidator<B> {
         account_nonce: Nonce,
         mempool_client: SharedMempoolClient,
         runtime: tokio::runtime::Handle,
-    ) -> StatefulTransactionValidatorResult<()> {
+    ) -> StatefulTransactionValidatorResult<Option<ValidationResources>> {
         self.validate_state_preconditions(executable_tx, account_nonce)?;
-        self.run_validate_entry_point(executable_tx, account_nonce, mempool_client, runtime)?;
-        Ok(())
+        self.run_validate_entry_point(executable_tx, account_nonce, mempool_client, runtime)
     }
 
     fn validate_state_preconditions(
@@ -199,7 +213,7 @@ impl<B: BlockifierStatefulValidatorTrait> StatefulTransactionValidator<B> {
         account_nonce: Nonce,
         mempool_client: SharedMempoolClient,
         runtime: tokio::runtime::Handle,
-    ) -> StatefulTransactionValidatorResult<()> {
+    ) -> StatefulTransactionValidatorResult<Option<ValidationResources>> {
         let skip_validate =
             skip_stateful_validations(executable_tx, account_nonce, mempool_client, runtime)?;
         let only_query = false;
@@ -209,11 +223,14 @@ impl<B: BlockifierStatefulValidatorTrait> StatefulTransactionValidator<B> {
             ExecutionFlags { only_query, charge_fee, validate: !skip_validate, strict_nonce_check };
 
         let account_tx = AccountTransaction { tx: executable_tx.clone(), execution_flags };
-        self.blockifier_stateful_tx_validator.validate(account_tx).map_err(|e| StarknetError {
-            code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
-            message: e.to_string(),
-        })?;
-        Ok(())
+        let validate_call_info = self
+            .blockifier_stateful_tx_validator
+            .validate(account_tx)
+            .map_err(|e| StarknetError {
+                code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+                message: e.to_string(),
+            })?;
+        Ok(validate_call_info.as_ref().map(validation_resources))
     }
 
     fn is_valid_nonce(&self, executable_tx: &ExecutableTransaction, account_nonce: Nonce) -> bool {
@@ -266,6 +283,20 @@ impl<B: BlockifierStatefulValidatorTrait> StatefulTransactionValidator<B> {
     }
 }
 
+fn validation_resources(validate_call_info: &CallInfo) -> ValidationResources {
+    let resources = &validate_call_info.resources;
+    ValidationResources {
+        n_steps: resources.n_steps,
+        n_memory_holes: resources.n_memory_holes,
+        builtin_instance_counter: resources
+            .builtin_instance_counter
+            .iter()
+            .map(|(builtin, count)| (builtin.to_str_with_suffix().to_string(), *count))
+            .collect(),
+        gas_consumed: validate_call_info.execution.gas_consumed,
+    }
+}
+
 /// Check if validation of an invoke transaction should be skipped due to deploy_account not being
 /// processed yet. This feature is used to improve UX for users sending deploy_account + invoke at
 /// once.
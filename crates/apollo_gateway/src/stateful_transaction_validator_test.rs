@@ -5,12 +5,14 @@ use apollo_gateway_types::deprecated_gateway_error::{
     StarknetError,
     StarknetErrorCode,
 };
+use apollo_gateway_types::gateway_types::ValidationResources;
 use apollo_mempool_types::communication::MockMempoolClient;
 use blockifier::blockifier::stateful_validator::{
     MockStatefulValidatorTrait as MockBlockifierStatefulValidatorTrait,
     StatefulValidatorError as BlockifierStatefulValidatorError,
 };
 use blockifier::context::ChainInfo;
+use blockifier::execution::call_info::CallInfo;
 use blockifier::test_utils::contracts::FeatureContractTrait;
 use blockifier::transaction::errors::{TransactionFeeError, TransactionPreValidationError};
 use blockifier::transaction::test_utils::calculate_class_info_for_testing;
@@ -40,7 +42,8 @@ use starknet_api::transaction::fields::{
 };
 use starknet_api::{declare_tx_args, deploy_account_tx_args, invoke_tx_args, nonce};
 
-use crate::config::StatefulTransactionValidatorConfig;
+use crate::config::{GatewayConfig, StatefulTransactionValidatorConfig};
+use crate::gateway_policy::ActiveGatewayPolicy;
 use crate::state_reader::{MockStateReaderFactory, StateReaderFactory};
 use crate::state_reader_test_utils::local_test_state_reader_factory;
 use crate::stateful_transaction_validator::{
@@ -62,7 +65,7 @@ async fn test_extract_state_nonce_and_run_validations(
     let account_nonce = nonce!(0);
 
     let expected_result = if expect_ok {
-        Ok(account_nonce)
+        Ok(Some(CallInfo::default()))
     } else {
         Err(BlockifierStatefulValidatorError::TransactionPreValidationError(
             TransactionPreValidationError::TransactionFeeError(Box::new(
@@ -77,7 +80,7 @@ async fn test_extract_state_nonce_and_run_validations(
     };
     let expected_result_as_stateful_transaction_validator_result = expected_result
         .as_ref()
-        .map(|validate_result| *validate_result)
+        .map(|_validate_call_info| (account_nonce, Some(ValidationResources::default())))
         .map_err(|blockifier_error| StarknetError {
             code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
             message: format!("{blockifier_error}"),
@@ -85,7 +88,7 @@ async fn test_extract_state_nonce_and_run_validations(
 
     let mut mock_blockifier_validator = MockBlockifierStatefulValidatorTrait::new();
     mock_blockifier_validator.expect_get_nonce().return_once(move |_| Ok(account_nonce));
-    mock_blockifier_validator.expect_validate().return_once(|_| expected_result.map(|_| ()));
+    mock_blockifier_validator.expect_validate().return_once(|_| expected_result);
     mock_blockifier_validator.expect_block_info().return_const(BlockInfo::default());
 
     let mut mock_mempool_client = MockMempoolClient::new();
@@ -117,6 +120,7 @@ async fn test_extract_state_nonce_and_run_validations(
 fn test_instantiate_validator() {
     let stateful_validator_factory = StatefulTransactionValidatorFactory {
         config: StatefulTransactionValidatorConfig::default(),
+        policy: Arc::new(ActiveGatewayPolicy::new(GatewayConfig::default().initial_policy())),
     };
     let state_reader_factory =
         local_test_state_reader_factory(CairoVersion::Cairo1(RunnableCairo1::Casm), false);
@@ -195,7 +199,7 @@ async fn test_skip_validate(
     mock_blockifier_validator
         .expect_validate()
         .withf(move |tx| tx.execution_flags.validate == should_validate)
-        .returning(|_| Ok(()));
+        .returning(|_| Ok(None));
     mock_blockifier_validator.expect_block_info().return_const(BlockInfo::default());
 
     let mut mock_mempool_client = MockMempoolClient::new();
@@ -228,7 +232,7 @@ async fn test_skip_validate(
     100_u128.try_into().unwrap(),
     100,
     100_u128.into(),
-    Ok(())
+    Ok(None)
 )]
 #[case::tx_gas_price_below_threshold_fail(
     100_u128.try_into().unwrap(),
@@ -245,13 +249,13 @@ async fn test_skip_validate(
     100_u128.try_into().unwrap(),
     50,
     50_u128.into(),
-    Ok(())
+    Ok(None)
 )]
 #[case::tx_gas_price_above_threshold_with_factor_pass(
     100_u128.try_into().unwrap(),
     50,
     51_u128.into(),
-    Ok(())
+    Ok(None)
 )]
 #[case::tx_gas_price_below_threshold_with_factor_fail(
     100_u128.try_into().unwrap(),
@@ -268,7 +272,7 @@ async fn test_skip_validate(
     100_u128.try_into().unwrap(),
     0,
     0_u128.into(),
-    Ok(()),
+    Ok(None),
 )]
 #[case::tx_gas_price_zero_fails_when_percentage_nonzero_fail(
     100_u128.try_into().unwrap(),
@@ -286,7 +290,7 @@ async fn validate_resource_bounds(
     #[case] prev_l2_gas_price: NonzeroGasPrice,
     #[case] min_gas_price_percentage: u8,
     #[case] tx_gas_price_per_unit: GasPrice,
-    #[case] expected_result: Result<(), StarknetError>,
+    #[case] expected_result: Result<Option<ValidationResources>, StarknetError>,
 ) {
     let account_nonce = nonce!(0);
     let resource_bounds = ValidResourceBounds::AllResources(AllResourceBounds {
@@ -297,7 +301,7 @@ async fn validate_resource_bounds(
 
     let mut mock_blockifier_validator = MockBlockifierStatefulValidatorTrait::new();
     mock_blockifier_validator.expect_get_nonce().return_once(move |_| Ok(account_nonce));
-    mock_blockifier_validator.expect_validate().return_once(|_| Ok(()));
+    mock_blockifier_validator.expect_validate().return_once(|_| Ok(None));
     mock_blockifier_validator.expect_block_info().return_const(BlockInfo {
         gas_prices: GasPrices {
             strk_gas_prices: GasPriceVector {
@@ -332,8 +336,8 @@ async fn validate_resource_bounds(
 }
 
 #[rstest]
-#[case::nonce_equal_to_account_nonce(0, nonce!(1), nonce!(1), Ok(()))] // Nonce is equal to account nonce.
-#[case::nonce_in_allowed_range(10, nonce!(1), nonce!(11), Ok(()))]
+#[case::nonce_equal_to_account_nonce(0, nonce!(1), nonce!(1), Ok(None))] // Nonce is equal to account nonce.
+#[case::nonce_in_allowed_range(10, nonce!(1), nonce!(11), Ok(None))]
 #[case::nonce_beyond_allowed_gap(
     10,
     nonce!(1),
@@ -351,11 +355,11 @@ async fn test_is_valid_nonce(
     #[case] max_allowed_nonce_gap: u32,
     #[case] account_nonce: Nonce,
     #[case] tx_nonce: Nonce,
-    #[case] expected_result_code: Result<(), StarknetErrorCode>,
+    #[case] expected_result_code: Result<Option<ValidationResources>, StarknetErrorCode>,
 ) {
     let mut mock_blockifier_validator = MockBlockifierStatefulValidatorTrait::new();
     mock_blockifier_validator.expect_get_nonce().return_once(move |_| Ok(account_nonce));
-    mock_blockifier_validator.expect_validate().return_once(|_| Ok(()));
+    mock_blockifier_validator.expect_validate().return_once(|_| Ok(None));
     mock_blockifier_validator.expect_block_info().return_const(BlockInfo::default());
 
     let mut stateful_validator = StatefulTransactionValidator {
@@ -383,7 +387,7 @@ async fn test_is_valid_nonce(
 }
 
 #[rstest]
-#[case::nonce_equal_to_account_nonce(0, Ok(()))]
+#[case::nonce_equal_to_account_nonce(0, Ok(None))]
 #[case::nonce_greater_then_account_nonce(
     1,
     Err(StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::InvalidTransactionNonce))
@@ -392,13 +396,13 @@ async fn test_is_valid_nonce(
 #[tokio::test]
 async fn test_reject_future_declares(
     #[case] account_nonce_diff: i32,
-    #[case] expected_result_code: Result<(), StarknetErrorCode>,
+    #[case] expected_result_code: Result<Option<ValidationResources>, StarknetErrorCode>,
 ) {
     let account_nonce = 10;
 
     let mut mock_blockifier_validator = MockBlockifierStatefulValidatorTrait::new();
     mock_blockifier_validator.expect_get_nonce().return_once(move |_| Ok(nonce!(account_nonce)));
-    mock_blockifier_validator.expect_validate().return_once(|_| Ok(()));
+    mock_blockifier_validator.expect_validate().return_once(|_| Ok(None));
     mock_blockifier_validator.expect_block_info().return_const(BlockInfo::default());
 
     let executable_tx = executable_declare_tx(
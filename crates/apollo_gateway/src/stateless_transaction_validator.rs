@@ -21,6 +21,7 @@ mod stateless_transaction_validator_test;
 #[derive(Clone)]
 pub struct StatelessTransactionValidator {
     pub config: StatelessTransactionValidatorConfig,
+    pub is_paymaster_enabled: bool,
 }
 
 impl StatelessTransactionValidator {
@@ -31,7 +32,7 @@ impl StatelessTransactionValidator {
 
         Self::validate_contract_address(tx)?;
         Self::validate_empty_account_deployment_data(tx)?;
-        Self::validate_empty_paymaster_data(tx)?;
+        self.validate_paymaster_data(tx)?;
         self.validate_resource_bounds(tx)?;
         self.validate_tx_size(tx)?;
         self.validate_nonce_data_availability_mode(tx)?;
@@ -98,11 +99,18 @@ impl StatelessTransactionValidator {
         }
     }
 
-    /// The Starknet OS enforces that the paymaster data is empty. We add this validation here in
-    /// the gateway to prevent transactions from failing the OS.
-    fn validate_empty_paymaster_data(
+    /// Unless paymasters are enabled on the chain, the paymaster data must be empty: the Starknet
+    /// OS runs the validation of a paymaster for any transaction with paymaster data, while the
+    /// execution ignores it. We add this validation here in the gateway to prevent transactions
+    /// from failing the OS.
+    fn validate_paymaster_data(
+        &self,
         tx: &RpcTransaction,
     ) -> StatelessTransactionValidatorResult<()> {
+        if self.is_paymaster_enabled {
+            return Ok(());
+        }
+
         let paymaster_data = match tx {
             RpcTransaction::DeployAccount(RpcDeployAccountTransaction::V3(tx)) => {
                 &tx.paymaster_data
@@ -120,7 +120,7 @@ fn test_positive_flow(
     #[values(TransactionType::Declare, TransactionType::DeployAccount, TransactionType::Invoke)]
     tx_type: TransactionType,
 ) {
-    let tx_validator = StatelessTransactionValidator { config };
+    let tx_validator = StatelessTransactionValidator { config, is_paymaster_enabled: false };
 
     let tx = rpc_tx_for_testing(tx_type, rpc_tx_args);
 
@@ -159,8 +159,10 @@ fn test_invalid_resource_bounds(
     #[values(TransactionType::Declare, TransactionType::DeployAccount, TransactionType::Invoke)]
     tx_type: TransactionType,
 ) {
-    let tx_validator =
-        StatelessTransactionValidator { config: DEFAULT_VALIDATOR_CONFIG.to_owned() };
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG.to_owned(),
+        is_paymaster_enabled: false,
+    };
 
     let tx = rpc_tx_for_testing(tx_type, rpc_tx_args);
 
@@ -252,8 +254,10 @@ fn test_invalid_tx(
     #[case] expected_error: StatelessTransactionValidatorError,
     #[case] tx_types: Vec<TransactionType>,
 ) {
-    let tx_validator =
-        StatelessTransactionValidator { config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone() };
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+        is_paymaster_enabled: false,
+    };
     for tx_type in tx_types {
         let tx = rpc_tx_for_testing(tx_type, rpc_tx_args.clone());
 
@@ -261,6 +265,51 @@ fn test_invalid_tx(
     }
 }
 
+#[rstest]
+fn test_paymaster_data_with_paymaster_enabled(
+    #[values(TransactionType::Declare, TransactionType::DeployAccount, TransactionType::Invoke)]
+    tx_type: TransactionType,
+) {
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+        is_paymaster_enabled: true,
+    };
+    let rpc_tx_args = RpcTransactionArgs {
+        paymaster_data: PaymasterData(vec![felt!(1_u128)]),
+        ..Default::default()
+    };
+
+    let tx = rpc_tx_for_testing(tx_type, rpc_tx_args);
+
+    assert_matches!(tx_validator.validate(&tx), Ok(()));
+}
+
+#[rstest]
+fn test_account_deployment_data_with_paymaster_enabled(
+    #[values(TransactionType::Declare, TransactionType::Invoke)] tx_type: TransactionType,
+) {
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+        is_paymaster_enabled: true,
+    };
+    let rpc_tx_args = RpcTransactionArgs {
+        account_deployment_data: AccountDeploymentData(vec![felt!(1_u128)]),
+        ..Default::default()
+    };
+
+    // The OS does not support deployer data, with or without a paymaster.
+    for paymaster_data in [PaymasterData::default(), PaymasterData(vec![felt!(1_u128)])] {
+        let rpc_tx_args = RpcTransactionArgs { paymaster_data, ..rpc_tx_args.clone() };
+        let tx = rpc_tx_for_testing(tx_type, rpc_tx_args);
+        assert_eq!(
+            tx_validator.validate(&tx).unwrap_err(),
+            StatelessTransactionValidatorError::NonEmptyField {
+                field_name: "account_deployment_data".to_string()
+            }
+        );
+    }
+}
+
 #[rstest]
 #[case::sierra_program_length_zero(
     vec![],
@@ -335,8 +384,10 @@ fn test_declare_sierra_version_failure(
     #[case] sierra_program: Vec<Felt>,
     #[case] expected_error: StatelessTransactionValidatorError,
 ) {
-    let tx_validator =
-        StatelessTransactionValidator { config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone() };
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+        is_paymaster_enabled: false,
+    };
 
     let contract_class = SierraContractClass { sierra_program, ..Default::default() };
     let tx = rpc_declare_tx(declare_tx_args!(), contract_class);
@@ -355,8 +406,10 @@ fn test_declare_sierra_version_failure(
 ))]
 #[case::max_sierra_version(create_sierra_program(&MAX_SIERRA_VERSION))]
 fn test_declare_sierra_version_sucsses(#[case] sierra_program: Vec<Felt>) {
-    let tx_validator =
-        StatelessTransactionValidator { config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone() };
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+        is_paymaster_enabled: false,
+    };
 
     let contract_class = SierraContractClass { sierra_program, ..Default::default() };
     let tx = rpc_declare_tx(declare_tx_args!(), contract_class);
@@ -372,6 +425,7 @@ fn test_declare_contract_class_size_too_long() {
             max_contract_class_object_size: config_max_contract_class_object_size,
             ..*DEFAULT_VALIDATOR_CONFIG_FOR_TESTING
         },
+        is_paymaster_enabled: false,
     };
     let contract_class = SierraContractClass {
         sierra_program: create_sierra_program(&MIN_SIERRA_VERSION),
@@ -399,6 +453,7 @@ fn test_declare_contract_bytecode_size_too_long() {
             max_contract_bytecode_size: sierra_program.len() - 1,
             ..*DEFAULT_VALIDATOR_CONFIG_FOR_TESTING
         },
+        is_paymaster_enabled: false,
     };
 
     let tx = rpc_declare_tx(
@@ -456,8 +511,10 @@ fn test_declare_entry_points_not_sorted_by_selector(
     #[case] entry_points: Vec<EntryPoint>,
     #[case] expected: StatelessTransactionValidatorResult<()>,
 ) {
-    let tx_validator =
-        StatelessTransactionValidator { config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone() };
+    let tx_validator = StatelessTransactionValidator {
+        config: DEFAULT_VALIDATOR_CONFIG_FOR_TESTING.clone(),
+        is_paymaster_enabled: false,
+    };
 
     let contract_class = SierraContractClass {
         sierra_program: create_sierra_program(&MIN_SIERRA_VERSION),
@@ -1,3 +1,5 @@
+use std::sync::Arc;
+
 use apollo_class_manager_types::SharedClassManagerClient;
 use apollo_state_sync_types::communication::{
     SharedStateSyncClient,
@@ -16,6 +18,7 @@ use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::state::StorageKey;
 use starknet_types_core::felt::Felt;
 
+use crate::state_cache::{BlockStateCache, LatestBlockStateCache};
 use crate::state_reader::{MempoolStateReader, StateReaderFactory};
 
 pub(crate) struct SyncStateReader {
@@ -23,6 +26,8 @@ pub(crate) struct SyncStateReader {
     state_sync_client: SharedStateSyncClient,
     class_manager_client: SharedClassManagerClient,
     runtime: tokio::runtime::Handle,
+    // Shared with the other state readers of the block, if set.
+    state_cache: Option<Arc<BlockStateCache>>,
 }
 
 impl SyncStateReader {
@@ -32,12 +37,15 @@ impl SyncStateReader {
         block_number: BlockNumber,
         runtime: tokio::runtime::Handle,
     ) -> Self {
-        Self { block_number, state_sync_client, class_manager_client, runtime }
+        Self { block_number, state_sync_client, class_manager_client, runtime, state_cache: None }
     }
-}
 
-impl MempoolStateReader for SyncStateReader {
-    fn get_block_info(&self) -> StateResult<BlockInfo> {
+    pub fn with_state_cache(mut self, state_cache: Arc<BlockStateCache>) -> Self {
+        self.state_cache = Some(state_cache);
+        self
+    }
+
+    fn fetch_block_info(&self) -> StateResult<BlockInfo> {
         let block = block_on(self.state_sync_client.get_block(self.block_number))
             .map_err(|e| StateError::StateReadError(e.to_string()))?;
 
@@ -66,10 +74,8 @@ impl MempoolStateReader for SyncStateReader {
 
         Ok(block_info)
     }
-}
 
-impl BlockifierStateReader for SyncStateReader {
-    fn get_storage_at(
+    fn fetch_storage_at(
         &self,
         contract_address: ContractAddress,
         key: StorageKey,
@@ -89,7 +95,7 @@ impl BlockifierStateReader for SyncStateReader {
         }
     }
 
-    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+    fn fetch_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
         let res = self
             .runtime
             .block_on(self.state_sync_client.get_nonce_at(self.block_number, contract_address));
@@ -103,14 +109,18 @@ impl BlockifierStateReader for SyncStateReader {
         }
     }
 
-    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+    /// Returns the compiled class of the given class hash, or `None` if it is not declared.
+    fn fetch_compiled_class(
+        &self,
+        class_hash: ClassHash,
+    ) -> StateResult<Option<RunnableCompiledClass>> {
         let is_class_declared = self
             .runtime
             .block_on(self.state_sync_client.is_class_declared_at(self.block_number, class_hash))
             .map_err(|e| StateError::StateReadError(e.to_string()))?;
 
         if !is_class_declared {
-            return Err(StateError::UndeclaredClassHash(class_hash));
+            return Ok(None);
         }
 
         let contract_class = self
@@ -122,17 +132,18 @@ impl BlockifierStateReader for SyncStateReader {
                  was declared",
             );
 
-        match contract_class {
+        let compiled_class = match contract_class {
             ContractClass::V1(casm_contract_class) => {
-                Ok(RunnableCompiledClass::V1(casm_contract_class.try_into()?))
+                RunnableCompiledClass::V1(casm_contract_class.try_into()?)
             }
             ContractClass::V0(deprecated_contract_class) => {
-                Ok(RunnableCompiledClass::V0(deprecated_contract_class.try_into()?))
+                RunnableCompiledClass::V0(deprecated_contract_class.try_into()?)
             }
-        }
+        };
+        Ok(Some(compiled_class))
     }
 
-    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+    fn fetch_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
         let res = self.runtime.block_on(
             self.state_sync_client.get_class_hash_at(self.block_number, contract_address),
         );
@@ -145,6 +156,54 @@ impl BlockifierStateReader for SyncStateReader {
             Err(e) => Err(StateError::StateReadError(e.to_string())),
         }
     }
+}
+
+impl MempoolStateReader for SyncStateReader {
+    fn get_block_info(&self) -> StateResult<BlockInfo> {
+        match &self.state_cache {
+            Some(state_cache) => state_cache.get_block_info(|| self.fetch_block_info()),
+            None => self.fetch_block_info(),
+        }
+    }
+}
+
+impl BlockifierStateReader for SyncStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        let fetch = || self.fetch_storage_at(contract_address, key);
+        match &self.state_cache {
+            Some(state_cache) => state_cache.get_storage_at(contract_address, key, fetch),
+            None => fetch(),
+        }
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        let fetch = || self.fetch_nonce_at(contract_address);
+        match &self.state_cache {
+            Some(state_cache) => state_cache.get_nonce_at(contract_address, fetch),
+            None => fetch(),
+        }
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        let fetch = || self.fetch_compiled_class(class_hash);
+        let compiled_class = match &self.state_cache {
+            Some(state_cache) => state_cache.get_compiled_class(class_hash, fetch),
+            None => fetch(),
+        }?;
+        compiled_class.ok_or(StateError::UndeclaredClassHash(class_hash))
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        let fetch = || self.fetch_class_hash_at(contract_address);
+        match &self.state_cache {
+            Some(state_cache) => state_cache.get_class_hash_at(contract_address, fetch),
+            None => fetch(),
+        }
+    }
 
     fn get_compiled_class_hash(&self, _class_hash: ClassHash) -> StateResult<CompiledClassHash> {
         todo!()
@@ -155,6 +214,7 @@ pub struct SyncStateReaderFactory {
     pub shared_state_sync_client: SharedStateSyncClient,
     pub class_manager_client: SharedClassManagerClient,
     pub runtime: tokio::runtime::Handle,
+    pub(crate) state_cache: LatestBlockStateCache,
 }
 
 impl StateReaderFactory for SyncStateReaderFactory {
@@ -166,15 +226,25 @@ impl StateReaderFactory for SyncStateReaderFactory {
             .block_on(self.shared_state_sync_client.get_latest_block_number())?
             .ok_or(StateSyncClientError::StateSyncError(StateSyncError::EmptyState))?;
 
-        Ok(Box::new(SyncStateReader::from_number(
-            self.shared_state_sync_client.clone(),
-            self.class_manager_client.clone(),
-            latest_block_number,
-            self.runtime.clone(),
-        )))
+        let latest_block_hash = self
+            .runtime
+            .block_on(self.shared_state_sync_client.get_block_hash(latest_block_number))?;
+        let state_cache =
+            self.state_cache.observe_latest_block(latest_block_number, latest_block_hash);
+
+        Ok(Box::new(
+            SyncStateReader::from_number(
+                self.shared_state_sync_client.clone(),
+                self.class_manager_client.clone(),
+                latest_block_number,
+                self.runtime.clone(),
+            )
+            .with_state_cache(state_cache),
+        ))
     }
 
     fn get_state_reader(&self, block_number: BlockNumber) -> Box<dyn MempoolStateReader> {
+        // The hash of the block is unknown, so the state cache of the latest block is not shared.
         Box::new(SyncStateReader::from_number(
             self.shared_state_sync_client.clone(),
             self.class_manager_client.clone(),
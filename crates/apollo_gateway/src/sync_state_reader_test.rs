@@ -1,4 +1,5 @@
-use std::sync::Arc;
+use std::mem::size_of;
+use std::sync::{Arc, Mutex};
 
 use apollo_class_manager_types::{
     ClassManagerClientResult,
@@ -16,6 +17,7 @@ use lazy_static::lazy_static;
 use mockall::predicate;
 use rstest::rstest;
 use starknet_api::block::{
+    BlockHash,
     BlockHeaderWithoutHash,
     BlockInfo,
     BlockNumber,
@@ -26,12 +28,15 @@ use starknet_api::block::{
     NonzeroGasPrice,
 };
 use starknet_api::contract_class::{ContractClass, SierraVersion};
-use starknet_api::core::{ClassHash, SequencerContractAddress};
+use starknet_api::core::{ClassHash, ContractAddress, SequencerContractAddress};
 use starknet_api::data_availability::L1DataAvailabilityMode;
 use starknet_api::{class_hash, contract_address, felt, nonce, storage_key};
 
-use crate::state_reader::MempoolStateReader;
-use crate::sync_state_reader::SyncStateReader;
+use crate::config::StateCacheConfig;
+use crate::state_cache::LatestBlockStateCache;
+use crate::state_reader::{MempoolStateReader, StateReaderFactory};
+use crate::sync_state_reader::{SyncStateReader, SyncStateReaderFactory};
+
 #[tokio::test]
 async fn test_get_block_info() {
     let mut mock_state_sync_client = MockStateSyncClient::new();
@@ -288,3 +293,98 @@ async fn test_get_compiled_class_panics_when_class_exists_in_sync_but_not_in_cla
     )
     .await;
 }
+
+fn sync_state_reader_factory(
+    mock_state_sync_client: MockStateSyncClient,
+    state_cache_config: StateCacheConfig,
+) -> SyncStateReaderFactory {
+    SyncStateReaderFactory {
+        shared_state_sync_client: Arc::new(mock_state_sync_client),
+        class_manager_client: Arc::new(MockClassManagerClient::new()),
+        runtime: tokio::runtime::Handle::current(),
+        state_cache: LatestBlockStateCache::new(state_cache_config),
+    }
+}
+
+#[tokio::test]
+async fn test_readers_of_the_latest_block_share_state_cache() {
+    let mut mock_state_sync_client = MockStateSyncClient::new();
+    let latest_block = Arc::new(Mutex::new((BlockNumber(1), BlockHash(felt!("0x1")))));
+    let contract_address = contract_address!("0x2");
+    let expected_result = nonce!(0x3);
+
+    let synced_block = latest_block.clone();
+    mock_state_sync_client
+        .expect_get_latest_block_number()
+        .returning(move || Ok(Some(synced_block.lock().unwrap().0)));
+    let synced_block = latest_block.clone();
+    mock_state_sync_client
+        .expect_get_block_hash()
+        .returning(move |_| Ok(synced_block.lock().unwrap().1));
+    // The nonce is read once per block: twice at height 1, as its block is replaced.
+    for (block_number, n_reads) in [(BlockNumber(1), 2), (BlockNumber(2), 1)] {
+        mock_state_sync_client
+            .expect_get_nonce_at()
+            .times(n_reads)
+            .with(predicate::eq(block_number), predicate::eq(contract_address))
+            .returning(move |_, _| Ok(expected_result));
+    }
+
+    let state_reader_factory =
+        sync_state_reader_factory(mock_state_sync_client, StateCacheConfig::default());
+    tokio::task::spawn_blocking(move || {
+        let read_nonce_from_latest_block = || {
+            let state_reader = state_reader_factory.get_state_reader_from_latest_block().unwrap();
+            assert_eq!(state_reader.get_nonce_at(contract_address).unwrap(), expected_result);
+        };
+        read_nonce_from_latest_block();
+        read_nonce_from_latest_block();
+
+        // The latest block was reverted, and a different block was synced at the same height.
+        *latest_block.lock().unwrap() = (BlockNumber(1), BlockHash(felt!("0x2")));
+        read_nonce_from_latest_block();
+
+        // A new block was synced.
+        *latest_block.lock().unwrap() = (BlockNumber(2), BlockHash(felt!("0x3")));
+        read_nonce_from_latest_block();
+    })
+    .await
+    .unwrap();
+}
+
+#[tokio::test]
+async fn test_state_cache_is_bounded() {
+    let mut mock_state_sync_client = MockStateSyncClient::new();
+    let cached_contract_address = contract_address!("0x2");
+    let uncached_contract_address = contract_address!("0x3");
+    let expected_result = class_hash!("0x4");
+
+    mock_state_sync_client.expect_get_latest_block_number().returning(|| Ok(Some(BlockNumber(1))));
+    mock_state_sync_client.expect_get_block_hash().returning(|_| Ok(BlockHash::default()));
+    mock_state_sync_client
+        .expect_get_class_hash_at()
+        .times(1)
+        .with(predicate::eq(BlockNumber(1)), predicate::eq(cached_contract_address))
+        .returning(move |_, _| Ok(expected_result));
+    mock_state_sync_client
+        .expect_get_class_hash_at()
+        .times(2)
+        .with(predicate::eq(BlockNumber(1)), predicate::eq(uncached_contract_address))
+        .returning(move |_, _| Ok(expected_result));
+
+    // Room for a single class hash.
+    let state_cache_config =
+        StateCacheConfig { max_size_in_bytes: size_of::<(ContractAddress, ClassHash)>() };
+    let state_reader_factory =
+        sync_state_reader_factory(mock_state_sync_client, state_cache_config);
+    tokio::task::spawn_blocking(move || {
+        let state_reader = state_reader_factory.get_state_reader_from_latest_block().unwrap();
+        for contract_address in
+            [cached_contract_address, uncached_contract_address].into_iter().cycle().take(4)
+        {
+            assert_eq!(state_reader.get_class_hash_at(contract_address).unwrap(), expected_result);
+        }
+    })
+    .await
+    .unwrap();
+}
@@ -0,0 +1,104 @@
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+use std::time::Instant;
+
+use apollo_gateway_types::deprecated_gateway_error::StarknetError;
+use tokio::sync::{OwnedSemaphorePermit, Semaphore};
+use tracing::{error, warn};
+
+use crate::config::ValidationWorkerPoolConfig;
+use crate::errors::GatewayResult;
+use crate::metrics::{
+    GATEWAY_VALIDATION_ACTIVE_WORKERS,
+    GATEWAY_VALIDATION_QUEUE_WAIT_TIME,
+    GATEWAY_VALIDATION_REJECTIONS,
+};
+
+#[cfg(test)]
+#[path = "validation_worker_pool_test.rs"]
+mod validation_worker_pool_test;
+
+/// Runs the blocking processing of transactions on a bounded number of workers.
+/// Transactions wait in a bounded queue for a free worker; once the queue is full, further
+/// transactions are rejected immediately, as the node is overloaded.
+pub(crate) struct ValidationWorkerPool {
+    workers: Arc<Semaphore>,
+    max_queue_depth: usize,
+    queue_depth: AtomicUsize,
+}
+
+impl ValidationWorkerPool {
+    pub fn new(config: &ValidationWorkerPoolConfig) -> Self {
+        Self {
+            workers: Arc::new(Semaphore::new(config.n_workers)),
+            max_queue_depth: config.max_queue_depth,
+            queue_depth: AtomicUsize::new(0),
+        }
+    }
+
+    /// Runs the task in a blocking thread once a worker is free.
+    pub async fn run<T: Send + 'static>(
+        &self,
+        task: impl FnOnce() -> GatewayResult<T> + Send + 'static,
+    ) -> GatewayResult<T> {
+        let worker = self.acquire_worker().await?;
+
+        // The worker is released when the task is done, even if this future is dropped.
+        tokio::task::spawn_blocking(move || {
+            GATEWAY_VALIDATION_ACTIVE_WORKERS.increment(1);
+            let result = task();
+            GATEWAY_VALIDATION_ACTIVE_WORKERS.decrement(1);
+            drop(worker);
+            result
+        })
+        .await
+        .map_err(|join_err| {
+            error!("Failed to process tx: {}", join_err);
+            StarknetError::internal(&join_err.to_string())
+        })?
+    }
+
+    async fn acquire_worker(&self) -> GatewayResult<OwnedSemaphorePermit> {
+        let queued_at = Instant::now();
+        let worker = match self.workers.clone().try_acquire_owned() {
+            Ok(worker) => worker,
+            Err(_) => {
+                let _queue_slot = QueueSlot::reserve(&self.queue_depth, self.max_queue_depth)
+                    .ok_or_else(|| {
+                        warn!("Rejecting a transaction, the validation queue is full.");
+                        GATEWAY_VALIDATION_REJECTIONS.increment(1);
+                        StarknetError::node_overloaded()
+                    })?;
+                self.workers
+                    .clone()
+                    .acquire_owned()
+                    .await
+                    .expect("The validation workers semaphore should not be closed.")
+            }
+        };
+        GATEWAY_VALIDATION_QUEUE_WAIT_TIME.record(queued_at.elapsed().as_secs_f64());
+        Ok(worker)
+    }
+}
+
+/// A place in the queue of transactions waiting for a worker, released when dropped.
+struct QueueSlot<'a> {
+    queue_depth: &'a AtomicUsize,
+}
+
+impl<'a> QueueSlot<'a> {
+    fn reserve(queue_depth: &'a AtomicUsize, max_queue_depth: usize) -> Option<Self> {
+        queue_depth
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |depth| {
+                (depth < max_queue_depth).then_some(depth + 1)
+            })
+            .ok()?;
+        Some(Self { queue_depth })
+    }
+}
+
+impl Drop for QueueSlot<'_> {
+    fn drop(&mut self) {
+        self.queue_depth.fetch_sub(1, Ordering::Relaxed);
+    }
+}
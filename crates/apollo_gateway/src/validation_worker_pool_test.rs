@@ -0,0 +1,56 @@
+use std::sync::atomic::Ordering;
+use std::sync::{mpsc, Arc};
+
+use apollo_gateway_types::deprecated_gateway_error::StarknetError;
+use tokio::sync::oneshot;
+
+use crate::config::ValidationWorkerPoolConfig;
+use crate::validation_worker_pool::ValidationWorkerPool;
+
+fn validation_worker_pool(n_workers: usize, max_queue_depth: usize) -> Arc<ValidationWorkerPool> {
+    Arc::new(ValidationWorkerPool::new(&ValidationWorkerPoolConfig { n_workers, max_queue_depth }))
+}
+
+#[tokio::test]
+async fn free_workers_run_tasks_without_queueing() {
+    let pool = validation_worker_pool(1, 0);
+
+    assert_eq!(pool.run(|| Ok(1)).await, Ok(1));
+    assert_eq!(pool.run(|| Ok(2)).await, Ok(2));
+}
+
+#[tokio::test]
+async fn tasks_beyond_the_queue_depth_are_rejected() {
+    let pool = validation_worker_pool(1, 1);
+
+    // Occupy the only worker until released.
+    let (started_sender, started_receiver) = oneshot::channel();
+    let (release_sender, release_receiver) = mpsc::channel();
+    let running_task = tokio::spawn({
+        let pool = pool.clone();
+        async move {
+            pool.run(move || {
+                started_sender.send(()).unwrap();
+                release_receiver.recv().unwrap();
+                Ok(1)
+            })
+            .await
+        }
+    });
+    started_receiver.await.unwrap();
+
+    let queued_task = tokio::spawn({
+        let pool = pool.clone();
+        async move { pool.run(|| Ok(2)).await }
+    });
+    while pool.queue_depth.load(Ordering::Relaxed) == 0 {
+        tokio::task::yield_now().await;
+    }
+
+    assert_eq!(pool.run(|| Ok(3)).await, Err(StarknetError::node_overloaded()));
+
+    release_sender.send(()).unwrap();
+    assert_eq!(running_task.await.unwrap(), Ok(1));
+    assert_eq!(queued_task.await.unwrap(), Ok(2));
+    assert_eq!(pool.queue_depth.load(Ordering::Relaxed), 0);
+}
@@ -8,12 +8,19 @@ use async_trait::async_trait;
 #[cfg(any(feature = "testing", test))]
 use mockall::automock;
 use serde::{Deserialize, Serialize};
+use starknet_api::rpc_transaction::RpcTransaction;
 use strum::EnumVariantNames;
 use strum_macros::{AsRefStr, EnumDiscriminants, EnumIter, IntoStaticStr};
 use thiserror::Error;
 
 use crate::errors::GatewayError;
-use crate::gateway_types::{GatewayInput, GatewayOutput, GatewayResult};
+use crate::gateway_types::{
+    GatewayInput,
+    GatewayOutput,
+    GatewayPolicy,
+    GatewayResult,
+    ValidateTransactionOutput,
+};
 
 pub type LocalGatewayClient = LocalComponentClient<GatewayRequest, GatewayResponse>;
 pub type RemoteGatewayClient = RemoteComponentClient<GatewayRequest, GatewayResponse>;
@@ -28,6 +35,13 @@ use tracing::{error, instrument};
 #[async_trait]
 pub trait GatewayClient: Send + Sync {
     async fn add_tx(&self, gateway_input: GatewayInput) -> GatewayClientResult<GatewayOutput>;
+    /// Runs all the validations of `add_tx` without adding the transaction to the mempool.
+    async fn validate_tx(
+        &self,
+        rpc_tx: RpcTransaction,
+    ) -> GatewayClientResult<ValidateTransactionOutput>;
+    /// Returns the admission policy currently applied by the gateway.
+    async fn get_policy(&self) -> GatewayClientResult<GatewayPolicy>;
 }
 
 #[derive(Serialize, Deserialize, Clone, AsRefStr, EnumDiscriminants)]
@@ -38,6 +52,8 @@ pub trait GatewayClient: Send + Sync {
 )]
 pub enum GatewayRequest {
     AddTransaction(GatewayInput),
+    ValidateTransaction(RpcTransaction),
+    GetPolicy,
 }
 impl_debug_for_infra_requests_and_responses!(GatewayRequest);
 impl_labeled_request!(GatewayRequest, GatewayRequestLabelValue);
@@ -46,6 +62,8 @@ impl PrioritizedRequest for GatewayRequest {}
 #[derive(Clone, Serialize, Deserialize, AsRefStr)]
 pub enum GatewayResponse {
     AddTransaction(GatewayResult<GatewayOutput>),
+    ValidateTransaction(GatewayResult<ValidateTransactionOutput>),
+    GetPolicy(GatewayResult<GatewayPolicy>),
 }
 impl_debug_for_infra_requests_and_responses!(GatewayResponse);
 
@@ -73,4 +91,31 @@ where
             Direct
         )
     }
+
+    #[instrument(skip(self))]
+    async fn validate_tx(
+        &self,
+        rpc_tx: RpcTransaction,
+    ) -> GatewayClientResult<ValidateTransactionOutput> {
+        let request = GatewayRequest::ValidateTransaction(rpc_tx);
+        handle_all_response_variants!(
+            GatewayResponse,
+            ValidateTransaction,
+            GatewayClientError,
+            GatewayError,
+            Direct
+        )
+    }
+
+    #[instrument(skip(self))]
+    async fn get_policy(&self) -> GatewayClientResult<GatewayPolicy> {
+        let request = GatewayRequest::GetPolicy;
+        handle_all_response_variants!(
+            GatewayResponse,
+            GetPolicy,
+            GatewayClientError,
+            GatewayError,
+            Direct
+        )
+    }
 }
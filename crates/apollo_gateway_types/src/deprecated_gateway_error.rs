@@ -78,6 +78,23 @@ impl StarknetError {
     fn internal_error_code() -> StarknetErrorCode {
         StarknetErrorCode::UnknownErrorCode("StarknetErrorCode.InternalError".to_string())
     }
+
+    /// An error returned when the node has no capacity to process the request; the request may
+    /// succeed if retried later.
+    pub fn node_overloaded() -> Self {
+        Self {
+            code: Self::node_overloaded_error_code(),
+            message: "The node is overloaded, please try again later.".to_string(),
+        }
+    }
+
+    pub fn is_node_overloaded(&self) -> bool {
+        self.code == Self::node_overloaded_error_code()
+    }
+
+    fn node_overloaded_error_code() -> StarknetErrorCode {
+        StarknetErrorCode::UnknownErrorCode("StarknetErrorCode.NODE_OVERLOADED".to_string())
+    }
 }
 
 impl std::fmt::Display for StarknetError {
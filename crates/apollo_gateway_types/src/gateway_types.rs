@@ -1,3 +1,5 @@
+use std::collections::BTreeMap;
+
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
 use serde::{Deserialize, Serialize};
 use starknet_api::core::{ClassHash, ContractAddress};
@@ -73,4 +75,51 @@ impl InvokeGatewayOutput {
     }
 }
 
+/// The verdict of a validate-only run: the transaction would have been accepted by the gateway.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct ValidateTransactionOutput {
+    pub transaction_hash: TransactionHash,
+    /// `None` if `__validate__` was skipped, i.e., for an invoke that follows a deploy account
+    /// that is not yet applied to the state.
+    pub validation_resources: Option<ValidationResources>,
+}
+
+/// The execution resources consumed by the `__validate__` entry point, including inner calls.
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct ValidationResources {
+    pub n_steps: usize,
+    pub n_memory_holes: usize,
+    pub builtin_instance_counter: BTreeMap<String, usize>,
+    pub gas_consumed: u64,
+}
+
+/// The admission policy applied by the gateway to incoming transactions. Unlike the rest of the
+/// gateway config, the policy can be replaced while the node is running.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct GatewayPolicy {
+    /// If true, all declare transactions are rejected.
+    pub block_declare: bool,
+    /// If set, only these accounts can declare new contracts.
+    #[serde(default)]
+    pub authorized_declarer_accounts: Option<Vec<ContractAddress>>,
+    /// Transactions sent by these accounts are rejected.
+    #[serde(default)]
+    pub denied_sender_accounts: Vec<ContractAddress>,
+    /// Minimum gas price as percentage of threshold to accept transactions.
+    pub min_gas_price_percentage: u8,
+}
+
+impl GatewayPolicy {
+    pub fn is_authorized_declarer(&self, declarer_address: &ContractAddress) -> bool {
+        match &self.authorized_declarer_accounts {
+            Some(allowed_accounts) => allowed_accounts.contains(declarer_address),
+            None => true,
+        }
+    }
+
+    pub fn is_denied_sender(&self, sender_address: &ContractAddress) -> bool {
+        self.denied_sender_accounts.contains(sender_address)
+    }
+}
+
 pub type GatewayResult<T> = Result<T, GatewayError>;
@@ -1,7 +1,7 @@
 use std::collections::BTreeMap;
 use std::net::{IpAddr, Ipv4Addr};
 
-use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::dumping::{prepend_sub_config_name, ser_param, SerializeConfig};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -13,19 +13,133 @@ pub const HTTP_SERVER_PORT: u16 = 8080;
 pub struct HttpServerConfig {
     pub ip: IpAddr,
     pub port: u16,
+    #[validate]
+    pub rate_limit_config: RateLimitConfig,
+    #[validate]
+    pub batch_config: AddTransactionBatchConfig,
 }
 
 impl SerializeConfig for HttpServerConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param("ip", &self.ip.to_string(), "The http server ip.", ParamPrivacyInput::Public),
             ser_param("port", &self.port, "The http server port.", ParamPrivacyInput::Public),
-        ])
+        ]);
+        dump.append(&mut prepend_sub_config_name(
+            self.rate_limit_config.dump(),
+            "rate_limit_config",
+        ));
+        dump.append(&mut prepend_sub_config_name(self.batch_config.dump(), "batch_config"));
+        dump
     }
 }
 
 impl Default for HttpServerConfig {
     fn default() -> Self {
-        Self { ip: IpAddr::from(Ipv4Addr::UNSPECIFIED), port: HTTP_SERVER_PORT }
+        Self {
+            ip: IpAddr::from(Ipv4Addr::UNSPECIFIED),
+            port: HTTP_SERVER_PORT,
+            rate_limit_config: RateLimitConfig::default(),
+            batch_config: AddTransactionBatchConfig::default(),
+        }
+    }
+}
+
+/// Limits on batches of transactions submitted in a single request.
+#[derive(Clone, Copy, Debug, Serialize, Deserialize, Validate, PartialEq)]
+pub struct AddTransactionBatchConfig {
+    // The maximal number of transactions in a batch.
+    pub max_size: usize,
+    // The maximal number of transactions of a batch that are sent to the gateway concurrently.
+    #[validate(range(min = 1))]
+    pub max_concurrent_txs: usize,
+}
+
+impl Default for AddTransactionBatchConfig {
+    fn default() -> Self {
+        Self { max_size: 100, max_concurrent_txs: 10 }
+    }
+}
+
+impl SerializeConfig for AddTransactionBatchConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "max_size",
+                &self.max_size,
+                "The maximal number of transactions in a batch.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_concurrent_txs",
+                &self.max_concurrent_txs,
+                "The maximal number of transactions of a batch that are sent to the gateway \
+                 concurrently.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+/// Limits on the rate of add transaction requests, applied independently per client IP, per
+/// client region (as reported by the `X-Client-Region` header) and per sender address.
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct RateLimitConfig {
+    #[validate]
+    pub per_client_ip: TokenBucketConfig,
+    #[validate]
+    pub per_client_region: TokenBucketConfig,
+    #[validate]
+    pub per_sender_address: TokenBucketConfig,
+}
+
+impl SerializeConfig for RateLimitConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut dump = BTreeMap::new();
+        dump.append(&mut prepend_sub_config_name(self.per_client_ip.dump(), "per_client_ip"));
+        dump.append(&mut prepend_sub_config_name(
+            self.per_client_region.dump(),
+            "per_client_region",
+        ));
+        dump.append(&mut prepend_sub_config_name(
+            self.per_sender_address.dump(),
+            "per_sender_address",
+        ));
+        dump
+    }
+}
+
+/// A token bucket: each request consumes a token, and tokens are refilled at a constant rate up
+/// to the bucket's capacity.
+#[derive(Clone, Copy, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct TokenBucketConfig {
+    // The maximal number of requests accepted in a burst. 0 disables the limit.
+    pub max_burst: u32,
+    // The number of requests accepted per second, once a burst is exhausted.
+    pub refill_rate_per_second: u32,
+}
+
+impl TokenBucketConfig {
+    pub fn is_enabled(&self) -> bool {
+        self.max_burst > 0
+    }
+}
+
+impl SerializeConfig for TokenBucketConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "max_burst",
+                &self.max_burst,
+                "The maximal number of requests accepted in a burst. 0 disables the limit.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "refill_rate_per_second",
+                &self.refill_rate_per_second,
+                "The number of requests accepted per second, once a burst is exhausted.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
     }
 }
@@ -5,6 +5,8 @@ use apollo_gateway_types::deprecated_gateway_error::{
     StarknetErrorCode,
 };
 use apollo_gateway_types::errors::GatewayError;
+use apollo_mempool_types::communication::MempoolClientError;
+use apollo_mempool_types::errors::MempoolError;
 use axum::response::{IntoResponse, Response};
 use hyper::StatusCode;
 use regex::Regex;
@@ -12,6 +14,8 @@ use starknet_api::compression_utils::CompressionError;
 use thiserror::Error;
 use tracing::{debug, error};
 
+use crate::rate_limiter::RateLimiterKind;
+
 /// Errors originating from `[`HttpServer::run`]` command.
 #[derive(Debug, Error)]
 pub enum HttpServerRunError {
@@ -28,21 +32,57 @@ pub enum HttpServerError {
     DeserializationError(#[from] serde_json::Error),
     #[error(transparent)]
     DecompressionError(#[from] CompressionError),
+    #[error(transparent)]
+    MempoolClientError(#[from] Box<MempoolClientError>),
+    #[error("The mempool is not accessible from this node.")]
+    MempoolUnavailable,
+    #[error("Too many requests from the same {0}.")]
+    RateLimited(RateLimiterKind),
+    #[error("Batch of {size} transactions exceeds the maximal batch size of {max_size}.")]
+    BatchTooLarge { size: usize, max_size: usize },
 }
 
 impl IntoResponse for HttpServerError {
     fn into_response(self) -> Response {
+        let (response_code, deprecated_gateway_error) = self.into_starknet_error();
+        let response_body = serialize_error(&deprecated_gateway_error);
+        (response_code, response_body).into_response()
+    }
+}
+
+impl HttpServerError {
+    /// Returns the response status code and the Starknet error describing this error to clients.
+    /// Note: the error message is not sanitized.
+    pub(crate) fn into_starknet_error(self) -> (StatusCode, StarknetError) {
         match self {
-            HttpServerError::GatewayClientError(e) => gw_client_err_into_response(*e),
-            HttpServerError::DeserializationError(e) => serde_error_into_response(e),
-            HttpServerError::DecompressionError(e) => compression_error_into_response(e),
+            HttpServerError::GatewayClientError(e) => gw_client_err_into_starknet_error(*e),
+            HttpServerError::DeserializationError(e) => serde_error_into_starknet_error(e),
+            HttpServerError::DecompressionError(e) => compression_error_into_starknet_error(e),
+            HttpServerError::MempoolClientError(e) => mempool_client_err_into_starknet_error(*e),
+            HttpServerError::MempoolUnavailable => (
+                StatusCode::SERVICE_UNAVAILABLE,
+                StarknetError::internal("The mempool is not accessible from this node."),
+            ),
+            HttpServerError::RateLimited(kind) => rate_limited_into_starknet_error(kind),
+            HttpServerError::BatchTooLarge { size, max_size } => (
+                StatusCode::BAD_REQUEST,
+                StarknetError {
+                    code: StarknetErrorCode::KnownErrorCode(
+                        KnownStarknetErrorCode::MalformedRequest,
+                    ),
+                    message: format!(
+                        "Batch of {size} transactions exceeds the maximal batch size of \
+                         {max_size}."
+                    ),
+                },
+            ),
         }
     }
 }
 
-fn compression_error_into_response(err: CompressionError) -> Response {
+fn compression_error_into_starknet_error(err: CompressionError) -> (StatusCode, StarknetError) {
     debug!("Failed to decompress the transaction: {}", err);
-    let (response_code, deprecated_gateway_error) = (
+    (
         StatusCode::BAD_REQUEST,
         StarknetError {
             code: StarknetErrorCode::UnknownErrorCode(
@@ -50,30 +90,33 @@ fn compression_error_into_response(err: CompressionError) -> Response {
             ),
             message: "Invalid compressed program.".to_string(),
         },
-    );
-    let response_body = serialize_error(&deprecated_gateway_error);
-    (response_code, response_body).into_response()
+    )
 }
 
-fn serde_error_into_response(err: serde_json::Error) -> Response {
+fn serde_error_into_starknet_error(err: serde_json::Error) -> (StatusCode, StarknetError) {
     debug!("Failed to deserialize transaction: {}", err);
-    let (response_code, deprecated_gateway_error) = (
+    (
         StatusCode::BAD_REQUEST,
         StarknetError {
             code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::MalformedRequest),
             message: err.to_string(),
         },
-    );
-    let response_body = serialize_error(&deprecated_gateway_error);
-    (response_code, response_body).into_response()
+    )
 }
 
-fn gw_client_err_into_response(err: GatewayClientError) -> Response {
-    let (response_code, deprecated_gateway_error) = match err {
+fn gw_client_err_into_starknet_error(err: GatewayClientError) -> (StatusCode, StarknetError) {
+    match err {
         GatewayClientError::ClientError(e) => {
             error!("Encountered a ClientError: {}", e);
             (StatusCode::INTERNAL_SERVER_ERROR, StarknetError::internal("Internal error"))
         }
+        GatewayClientError::GatewayError(GatewayError::DeprecatedGatewayError {
+            source,
+            p2p_message_metadata: _,
+        }) if source.is_node_overloaded() => {
+            debug!("Rejected a request as the gateway is overloaded.");
+            (StatusCode::SERVICE_UNAVAILABLE, source)
+        }
         GatewayClientError::GatewayError(GatewayError::DeprecatedGatewayError {
             source,
             p2p_message_metadata: _,
@@ -82,16 +125,50 @@ fn gw_client_err_into_response(err: GatewayClientError) -> Response {
             // added to the error response.
             (StatusCode::BAD_REQUEST, source)
         }
-    };
+    }
+}
 
-    let response_body = serialize_error(&deprecated_gateway_error);
+fn rate_limited_into_starknet_error(kind: RateLimiterKind) -> (StatusCode, StarknetError) {
+    debug!("Rejected a request due to the {} rate limit.", kind);
+    (
+        StatusCode::TOO_MANY_REQUESTS,
+        StarknetError {
+            code: StarknetErrorCode::KnownErrorCode(
+                KnownStarknetErrorCode::TransactionLimitExceeded,
+            ),
+            message: format!("Too many requests from the same {kind}, please try again later."),
+        },
+    )
+}
 
-    (response_code, response_body).into_response()
+fn mempool_client_err_into_starknet_error(err: MempoolClientError) -> (StatusCode, StarknetError) {
+    match err {
+        MempoolClientError::MempoolError(MempoolError::TransactionNotFound { tx_hash }) => (
+            StatusCode::NOT_FOUND,
+            StarknetError {
+                code: StarknetErrorCode::UnknownErrorCode(
+                    "StarknetErrorCode.TRANSACTION_NOT_FOUND".to_string(),
+                ),
+                message: format!("Transaction with hash {tx_hash} is not in the mempool."),
+            },
+        ),
+        err => {
+            error!("Encountered an error while querying the mempool: {}", err);
+            (StatusCode::INTERNAL_SERVER_ERROR, StarknetError::internal("Internal error"))
+        }
+    }
 }
 
 /// Serializes a `StarknetError` into an HTTP response, encode the error message
 /// to defend potential Cross-Site risks.
 fn serialize_error(error: &StarknetError) -> Response {
+    serde_json::to_vec(&sanitize_error(error))
+        .expect("Expecting a serializable StarknetError.")
+        .into_response()
+}
+
+/// Encodes the error message of a `StarknetError` to defend potential Cross-Site risks.
+pub(crate) fn sanitize_error(error: &StarknetError) -> StarknetError {
     let quote_re = Regex::new(r#"[\"`]"#).unwrap(); // " and ` => ' (single quote)
     let sanitize_re = Regex::new(r#"[^a-zA-Z0-9 :.,\[\]\(\)\{\}'_]"#).unwrap(); // All other non-alphanumeric characters except [:.,[](){}]_ => ' ' (space)
 
@@ -99,9 +176,5 @@ fn serialize_error(error: &StarknetError) -> Response {
     message = quote_re.replace_all(&message, "'").to_string();
     message = sanitize_re.replace_all(&message, " ").to_string();
 
-    let sanitized_error = StarknetError { code: error.code.clone(), message };
-
-    serde_json::to_vec(&sanitized_error)
-        .expect("Expecting a serializable StarknetError.")
-        .into_response()
+    StarknetError { code: error.code.clone(), message }
 }
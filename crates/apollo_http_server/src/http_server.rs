@@ -1,6 +1,7 @@
 use std::clone::Clone;
 use std::net::SocketAddr;
 use std::string::String;
+use std::sync::Arc;
 
 use apollo_gateway_types::communication::{GatewayClientError, SharedGatewayClient};
 use apollo_gateway_types::deprecated_gateway_error::{
@@ -12,25 +13,31 @@ use apollo_gateway_types::errors::GatewayError;
 use apollo_gateway_types::gateway_types::{
     GatewayInput,
     GatewayOutput,
+    ValidateTransactionOutput,
     SUPPORTED_TRANSACTION_VERSIONS,
 };
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_infra_utils::type_name::short_type_name;
+use apollo_mempool_types::communication::SharedMempoolClient;
+use apollo_mempool_types::mempool_types::MempoolTransactionStatus;
 use apollo_proc_macros::sequencer_latency_histogram;
-use axum::extract::State;
+use axum::extract::{ConnectInfo, Query, State};
 use axum::http::HeaderMap;
 use axum::routing::{get, post};
 use axum::{async_trait, Json, Router};
 use blockifier_reexecution::state_reader::serde_utils::deserialize_transaction_json_to_starknet_api_tx;
+use futures::StreamExt;
 use serde::de::Error;
-use starknet_api::rpc_transaction::RpcTransaction;
+use serde::{Deserialize, Serialize};
+use starknet_api::rpc_transaction::{InternalRpcTransaction, RpcTransaction};
 use starknet_api::serde_utils::bytes_from_hex_str;
 use starknet_api::transaction::fields::ValidResourceBounds;
+use starknet_api::transaction::TransactionHash;
 use tracing::{debug, info, instrument};
 
-use crate::config::HttpServerConfig;
+use crate::config::{AddTransactionBatchConfig, HttpServerConfig};
 use crate::deprecated_gateway_transaction::DeprecatedGatewayTransactionV3;
-use crate::errors::{HttpServerError, HttpServerRunError};
+use crate::errors::{sanitize_error, HttpServerError, HttpServerRunError};
 use crate::metrics::{
     init_metrics,
     ADDED_TRANSACTIONS_DEPRECATED_ERROR,
@@ -40,6 +47,7 @@ use crate::metrics::{
     ADDED_TRANSACTIONS_TOTAL,
     HTTP_SERVER_ADD_TX_LATENCY,
 };
+use crate::rate_limiter::RateLimiters;
 
 #[cfg(test)]
 #[path = "http_server_test.rs"]
@@ -57,11 +65,20 @@ pub struct HttpServer {
 #[derive(Clone)]
 pub struct AppState {
     pub gateway_client: SharedGatewayClient,
+    pub mempool_client: Option<SharedMempoolClient>,
+    pub rate_limiters: Arc<RateLimiters>,
+    pub batch_config: AddTransactionBatchConfig,
 }
 
 impl HttpServer {
-    pub fn new(config: HttpServerConfig, gateway_client: SharedGatewayClient) -> Self {
-        let app_state = AppState { gateway_client };
+    pub fn new(
+        config: HttpServerConfig,
+        gateway_client: SharedGatewayClient,
+        mempool_client: Option<SharedMempoolClient>,
+    ) -> Self {
+        let rate_limiters = Arc::new(RateLimiters::new(&config.rate_limit_config));
+        let batch_config = config.batch_config;
+        let app_state = AppState { gateway_client, mempool_client, rate_limiters, batch_config };
         HttpServer { config, app_state }
     }
 
@@ -69,13 +86,16 @@ impl HttpServer {
         init_metrics();
 
         // Parses the bind address from HttpServerConfig, returning an error for invalid addresses.
-        let HttpServerConfig { ip, port } = self.config;
+        let HttpServerConfig { ip, port, .. } = self.config;
         let addr = SocketAddr::new(ip, port);
         let app = self.app();
         info!("HttpServer running using socket: {}", addr);
 
         // Create a server that runs forever.
-        Ok(axum::Server::bind(&addr).serve(app.into_make_service()).await?)
+        // The connection info provides the client IP, used for rate limiting.
+        Ok(axum::Server::bind(&addr)
+            .serve(app.into_make_service_with_connect_info::<SocketAddr>())
+            .await?)
     }
 
     // TODO(Yael): consider supporting both formats in the same endpoint if possible.
@@ -87,6 +107,19 @@ impl HttpServer {
             // Rest api endpoint
             .route("/gateway/add_transaction", post(add_tx))
             .with_state(self.app_state.clone())
+            // Batch endpoints, in both formats; a result is returned per transaction.
+            .route("/gateway/add_rpc_transaction_batch", post(add_rpc_tx_batch))
+            .with_state(self.app_state.clone())
+            .route("/gateway/add_transaction_batch", post(add_tx_batch))
+            .with_state(self.app_state.clone())
+            // Runs the gateway validations without adding the transaction.
+            .route("/gateway/validate_rpc_transaction", post(validate_rpc_tx))
+            .with_state(self.app_state.clone())
+            // Transactions that were received but not yet included in a block, and their status.
+            .route("/gateway/get_transaction", get(get_tx))
+            .with_state(self.app_state.clone())
+            .route("/gateway/get_transaction_status", get(get_tx_status))
+            .with_state(self.app_state.clone())
             // TODO(shahak): Remove this once we fix the centralized simulator to not use is_alive
             // and is_ready.
             .route(
@@ -105,36 +138,206 @@ impl HttpServer {
 #[instrument(skip(app_state))]
 async fn add_rpc_tx(
     State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     headers: HeaderMap,
     Json(tx): Json<RpcTransaction>,
 ) -> HttpServerResult<Json<GatewayOutput>> {
     debug!("ADD_TX_START: Http server received a new transaction.");
     ADDED_TRANSACTIONS_TOTAL.increment(1);
-    add_tx_inner(app_state, headers, tx).await
+    check_client_rate_limits(&app_state, connect_info, &headers)?;
+    add_tx_inner(app_state, &headers, tx).await.map(Json)
 }
 
 #[instrument(skip(app_state))]
 #[sequencer_latency_histogram(HTTP_SERVER_ADD_TX_LATENCY, true)]
 async fn add_tx(
     State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
     headers: HeaderMap,
     tx: String,
 ) -> HttpServerResult<Json<GatewayOutput>> {
     ADDED_TRANSACTIONS_TOTAL.increment(1);
     debug!("ADD_TX_START: Http server received a new transaction.");
-    validate_supported_tx_version(&tx).inspect_err(|e| {
+    check_client_rate_limits(&app_state, connect_info, &headers)?;
+    let rpc_tx = parse_deprecated_tx(&tx)?;
+
+    add_tx_inner(app_state, &headers, rpc_tx).await.map(Json)
+}
+
+/// The result of adding a single transaction of a batch.
+#[derive(Clone, Debug, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AddTransactionBatchItemResult {
+    TransactionHash(TransactionHash),
+    Error(StarknetError),
+}
+
+impl From<HttpServerResult<GatewayOutput>> for AddTransactionBatchItemResult {
+    fn from(add_tx_result: HttpServerResult<GatewayOutput>) -> Self {
+        match add_tx_result {
+            Ok(gateway_output) => Self::TransactionHash(gateway_output.transaction_hash()),
+            Err(err) => {
+                let (_response_code, deprecated_gateway_error) = err.into_starknet_error();
+                Self::Error(sanitize_error(&deprecated_gateway_error))
+            }
+        }
+    }
+}
+
+#[instrument(skip(app_state, txs))]
+async fn add_rpc_tx_batch(
+    State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(txs): Json<Vec<serde_json::Value>>,
+) -> HttpServerResult<Json<Vec<AddTransactionBatchItemResult>>> {
+    debug!("ADD_TX_BATCH_START: Http server received a batch of {} transactions.", txs.len());
+    add_tx_batch_inner(app_state, connect_info, headers, txs, |tx| {
+        serde_json::from_value(tx).map_err(HttpServerError::from).inspect_err(|e| {
+            debug!("Error while parsing transaction: {}", e);
+            increment_failure_metrics(e);
+        })
+    })
+    .await
+}
+
+#[instrument(skip(app_state, txs))]
+async fn add_tx_batch(
+    State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    txs: String,
+) -> HttpServerResult<Json<Vec<AddTransactionBatchItemResult>>> {
+    let txs: Vec<serde_json::Value> = serde_json::from_str(&txs)?;
+    debug!("ADD_TX_BATCH_START: Http server received a batch of {} transactions.", txs.len());
+    add_tx_batch_inner(app_state, connect_info, headers, txs, |tx| {
+        parse_deprecated_tx(&tx.to_string())
+    })
+    .await
+}
+
+/// Adds the transactions of a batch concurrently, as if each was submitted on its own, and
+/// returns their results in the order of the batch.
+async fn add_tx_batch_inner(
+    app_state: AppState,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    txs: Vec<serde_json::Value>,
+    parse_tx: fn(serde_json::Value) -> HttpServerResult<RpcTransaction>,
+) -> HttpServerResult<Json<Vec<AddTransactionBatchItemResult>>> {
+    let AddTransactionBatchConfig { max_size, max_concurrent_txs } = app_state.batch_config;
+    if txs.len() > max_size {
+        return Err(HttpServerError::BatchTooLarge { size: txs.len(), max_size });
+    }
+
+    let headers = &headers;
+    let results: Vec<AddTransactionBatchItemResult> = futures::stream::iter(txs)
+        .map(|tx| {
+            let app_state = app_state.clone();
+            async move {
+                ADDED_TRANSACTIONS_TOTAL.increment(1);
+                let add_tx_result = async {
+                    check_client_rate_limits(&app_state, connect_info, headers)?;
+                    let rpc_tx = parse_tx(tx)?;
+                    add_tx_inner(app_state, headers, rpc_tx).await
+                }
+                .await;
+                AddTransactionBatchItemResult::from(add_tx_result)
+            }
+        })
+        .buffered(max_concurrent_txs)
+        .collect()
+        .await;
+
+    Ok(Json(results))
+}
+
+#[instrument(skip(app_state))]
+async fn validate_rpc_tx(
+    State(app_state): State<AppState>,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: HeaderMap,
+    Json(tx): Json<RpcTransaction>,
+) -> HttpServerResult<Json<ValidateTransactionOutput>> {
+    debug!("Http server received a transaction to validate.");
+    let client_ip = connect_info.map(|ConnectInfo(socket)| socket.ip());
+    app_state
+        .rate_limiters
+        .check_client(client_ip, client_region(&headers))
+        .map_err(HttpServerError::RateLimited)?;
+    // Wrap the gateway client interaction with a tokio::spawn as it is NOT cancel-safe.
+    let validate_tx_output =
+        tokio::spawn(async move { app_state.gateway_client.validate_tx(tx).await })
+            .await
+            .expect("Should be able to get validate_tx result")
+            .map_err(|e| {
+                debug!("Error while validating transaction: {}", e);
+                HttpServerError::from(Box::new(e))
+            })?;
+
+    Ok(Json(validate_tx_output))
+}
+
+#[derive(Debug, Deserialize)]
+struct TransactionHashQuery {
+    #[serde(rename = "transactionHash")]
+    transaction_hash: TransactionHash,
+}
+
+#[instrument(skip(app_state))]
+async fn get_tx(
+    State(app_state): State<AppState>,
+    Query(query): Query<TransactionHashQuery>,
+) -> HttpServerResult<Json<InternalRpcTransaction>> {
+    let mempool_client = app_state.mempool_client.ok_or(HttpServerError::MempoolUnavailable)?;
+    // Wrap the mempool client interaction with a tokio::spawn as it is NOT cancel-safe.
+    let tx = tokio::spawn(async move { mempool_client.get_tx(query.transaction_hash).await })
+        .await
+        .expect("Should be able to get get_tx result")
+        .map_err(|e| {
+            debug!("Error while getting transaction: {}", e);
+            HttpServerError::from(Box::new(e))
+        })?;
+
+    Ok(Json(tx))
+}
+
+#[instrument(skip(app_state))]
+async fn get_tx_status(
+    State(app_state): State<AppState>,
+    Query(query): Query<TransactionHashQuery>,
+) -> HttpServerResult<Json<MempoolTransactionStatus>> {
+    let mempool_client = app_state.mempool_client.ok_or(HttpServerError::MempoolUnavailable)?;
+    // Wrap the mempool client interaction with a tokio::spawn as it is NOT cancel-safe.
+    // Even if the current task is cancelled, e.g., when a request is dropped while still being
+    // processed, the inner task will continue to run.
+    let tx_status =
+        tokio::spawn(async move { mempool_client.get_tx_status(query.transaction_hash).await })
+            .await
+            .expect("Should be able to get get_tx_status result")
+            .map_err(|e| {
+                debug!("Error while getting transaction status: {}", e);
+                HttpServerError::from(Box::new(e))
+            })?;
+
+    Ok(Json(tx_status))
+}
+
+/// Parses a transaction of the deprecated gateway format into an RPC transaction.
+fn parse_deprecated_tx(tx: &str) -> HttpServerResult<RpcTransaction> {
+    validate_supported_tx_version(tx).inspect_err(|e| {
         debug!("Error while validating transaction version: {}", e);
         increment_failure_metrics(e);
     })?;
-    let tx: DeprecatedGatewayTransactionV3 = serde_json::from_str(&tx).inspect_err(|e| {
-        debug!("Error while parsing transaction: {}", e);
-        check_supported_resource_bounds_and_increment_metrics(&tx);
-    })?;
-    let rpc_tx = tx.try_into().inspect_err(|e| {
+    let deprecated_tx: DeprecatedGatewayTransactionV3 =
+        serde_json::from_str(tx).inspect_err(|e| {
+            debug!("Error while parsing transaction: {}", e);
+            check_supported_resource_bounds_and_increment_metrics(tx);
+        })?;
+    let rpc_tx = deprecated_tx.try_into().inspect_err(|e| {
         debug!("Error while converting deprecated gateway transaction into RPC transaction: {}", e);
     })?;
-
-    add_tx_inner(app_state, headers, rpc_tx).await
+    Ok(rpc_tx)
 }
 
 fn validate_supported_tx_version(tx: &str) -> HttpServerResult<()> {
@@ -182,11 +385,41 @@ fn check_supported_resource_bounds_and_increment_metrics(tx: &str) {
     ADDED_TRANSACTIONS_FAILURE.increment(1);
 }
 
+fn client_region(headers: &HeaderMap) -> Option<&str> {
+    headers.get(CLIENT_REGION_HEADER).and_then(|region| region.to_str().ok())
+}
+
+fn check_client_rate_limits(
+    app_state: &AppState,
+    connect_info: Option<ConnectInfo<SocketAddr>>,
+    headers: &HeaderMap,
+) -> HttpServerResult<()> {
+    let client_ip = connect_info.map(|ConnectInfo(socket)| socket.ip());
+    app_state
+        .rate_limiters
+        .check_client(client_ip, client_region(headers))
+        .map_err(HttpServerError::RateLimited)
+        .inspect_err(increment_failure_metrics)
+}
+
+fn check_sender_rate_limit(app_state: &AppState, tx: &RpcTransaction) -> HttpServerResult<()> {
+    // A sender address that cannot be calculated is rejected by the gateway.
+    let Ok(sender_address) = tx.calculate_sender_address() else {
+        return Ok(());
+    };
+    app_state
+        .rate_limiters
+        .check_sender(sender_address)
+        .map_err(HttpServerError::RateLimited)
+        .inspect_err(increment_failure_metrics)
+}
+
 async fn add_tx_inner(
     app_state: AppState,
-    headers: HeaderMap,
+    headers: &HeaderMap,
     tx: RpcTransaction,
-) -> HttpServerResult<Json<GatewayOutput>> {
+) -> HttpServerResult<GatewayOutput> {
+    check_sender_rate_limit(&app_state, &tx)?;
     let gateway_input: GatewayInput = GatewayInput { rpc_tx: tx, message_metadata: None };
     // Wrap the gateway client interaction with a tokio::spawn as it is NOT cancel-safe.
     // Even if the current task is cancelled, e.g., when a request is dropped while still being
@@ -200,10 +433,9 @@ async fn add_tx_inner(
                 HttpServerError::from(Box::new(e))
             });
 
-    let region =
-        headers.get(CLIENT_REGION_HEADER).and_then(|region| region.to_str().ok()).unwrap_or("N/A");
+    let region = client_region(headers).unwrap_or("N/A");
     record_added_transactions(&add_tx_result, region);
-    Ok(Json(add_tx_result?))
+    add_tx_result
 }
 
 fn record_added_transactions(add_tx_result: &HttpServerResult<GatewayOutput>, region: &str) {
@@ -224,8 +456,9 @@ fn record_added_transactions(add_tx_result: &HttpServerResult<GatewayOutput>, re
 pub fn create_http_server(
     config: HttpServerConfig,
     gateway_client: SharedGatewayClient,
+    mempool_client: Option<SharedMempoolClient>,
 ) -> HttpServer {
-    HttpServer::new(config, gateway_client)
+    HttpServer::new(config, gateway_client, mempool_client)
 }
 
 #[async_trait]
@@ -10,27 +10,41 @@ use apollo_gateway_types::gateway_types::{
     DeployAccountGatewayOutput,
     GatewayOutput,
     InvokeGatewayOutput,
+    ValidateTransactionOutput,
+    ValidationResources,
 };
 use apollo_infra::component_client::ClientError;
+use apollo_mempool_types::communication::{MempoolClientError, MockMempoolClient};
+use apollo_mempool_types::errors::MempoolError;
+use apollo_mempool_types::mempool_types::{MempoolTransactionStatus, TransactionEligibility};
 use axum::body::{Bytes, HttpBody};
 use axum::response::{IntoResponse, Response};
 use axum::Json;
 use hyper::StatusCode;
+use mempool_test_utils::starknet_api_test_utils::deploy_account_tx;
+use metrics_exporter_prometheus::PrometheusBuilder;
 use rstest::rstest;
 use serde_json::Value;
+use starknet_api::rpc_transaction::{InternalRpcTransaction, RpcTransaction};
+use starknet_api::test_utils::invoke::internal_invoke_tx;
 use starknet_api::test_utils::read_json_file;
 use starknet_api::transaction::TransactionHash;
-use starknet_api::{class_hash, contract_address, tx_hash};
+use starknet_api::{class_hash, contract_address, invoke_tx_args, tx_hash};
 use starknet_types_core::felt::Felt;
 use tracing_test::traced_test;
 
+use crate::config::{AddTransactionBatchConfig, RateLimitConfig, TokenBucketConfig};
 use crate::errors::HttpServerError;
-use crate::http_server::CLIENT_REGION_HEADER;
+use crate::http_server::{AddTransactionBatchItemResult, CLIENT_REGION_HEADER};
+use crate::metrics::{LABEL_NAME_RATE_LIMITER, RATE_LIMITED_REQUESTS};
+use crate::rate_limiter::RateLimiterKind;
 use crate::test_utils::{
     add_tx_http_client,
     deprecated_gateway_declare_tx,
     deprecated_gateway_deploy_account_tx,
     deprecated_gateway_invoke_tx,
+    mempool_http_client,
+    rate_limited_add_tx_http_client,
     rpc_invoke_tx,
     GatewayTransaction,
     TransactionSerialization,
@@ -45,6 +59,9 @@ const DEPRECATED_GATEWAY_DEPLOY_ACCOUNT_TX_RESPONSE_JSON_PATH: &str =
 
 const EXPECTED_TX_HASH: TransactionHash = TransactionHash(Felt::ONE);
 
+const ONE_REQUEST_LIMIT: TokenBucketConfig =
+    TokenBucketConfig { max_burst: 1, refill_rate_per_second: 0 };
+
 // The http_server is oblivious to the GateWayOutput type, so we always return invoke.
 pub fn default_gateway_output() -> GatewayOutput {
     GatewayOutput::Invoke(InvokeGatewayOutput::new(EXPECTED_TX_HASH))
@@ -106,6 +123,23 @@ async fn error_into_response() {
     );
 }
 
+#[tokio::test]
+async fn node_overloaded_error_into_response() {
+    let expected_error = StarknetError::node_overloaded();
+    let error = HttpServerError::GatewayClientError(Box::new(GatewayClientError::GatewayError(
+        GatewayError::DeprecatedGatewayError {
+            source: expected_error.clone(),
+            p2p_message_metadata: None,
+        },
+    )));
+    let response = error.into_response();
+
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = to_bytes(response).await;
+    let error: StarknetError = serde_json::from_slice(&body).unwrap();
+    assert_eq!(error.code, expected_error.code);
+}
+
 #[traced_test]
 #[rstest]
 #[case::add_deprecated_gateway_tx(0, deprecated_gateway_invoke_tx())]
@@ -310,3 +344,225 @@ async fn sanitizing_error_message() {
         starknet_error.message
     );
 }
+
+#[tokio::test]
+async fn get_tx_status() {
+    let tx_status = MempoolTransactionStatus {
+        tx_hash: EXPECTED_TX_HASH,
+        submission_time: 1_700_000_000,
+        eligibility: TransactionEligibility::Ready { queue_position: 3 },
+    };
+    let unknown_tx_hash = tx_hash!(2);
+
+    let mut mock_mempool_client = MockMempoolClient::new();
+    mock_mempool_client
+        .expect_get_tx_status()
+        .withf(|tx_hash| *tx_hash == EXPECTED_TX_HASH)
+        .times(1)
+        .return_const(Ok(tx_status.clone()));
+    mock_mempool_client
+        .expect_get_tx_status()
+        .withf(move |tx_hash| *tx_hash == unknown_tx_hash)
+        .times(1)
+        .return_const(Err(MempoolClientError::MempoolError(MempoolError::TransactionNotFound {
+            tx_hash: unknown_tx_hash,
+        })));
+
+    let http_client = mempool_http_client(mock_mempool_client, 17).await;
+
+    let response = http_client.get_tx_status(EXPECTED_TX_HASH).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response_tx_status: MempoolTransactionStatus =
+        serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(response_tx_status, tx_status);
+
+    let response = http_client.get_tx_status(unknown_tx_hash).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn get_tx_status_without_mempool_client() {
+    let http_client = add_tx_http_client(MockGatewayClient::new(), 18).await;
+
+    let response = http_client.get_tx_status(EXPECTED_TX_HASH).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let response = http_client.get_tx(EXPECTED_TX_HASH).await;
+    assert_eq!(response.status(), StatusCode::SERVICE_UNAVAILABLE);
+}
+
+#[tokio::test]
+async fn get_tx() {
+    let tx_hash = EXPECTED_TX_HASH;
+    let tx = internal_invoke_tx(invoke_tx_args!(tx_hash));
+    let unknown_tx_hash = tx_hash!(2);
+
+    let mut mock_mempool_client = MockMempoolClient::new();
+    mock_mempool_client
+        .expect_get_tx()
+        .withf(move |requested_tx_hash| *requested_tx_hash == tx_hash)
+        .times(1)
+        .return_const(Ok(tx.clone()));
+    mock_mempool_client
+        .expect_get_tx()
+        .withf(move |requested_tx_hash| *requested_tx_hash == unknown_tx_hash)
+        .times(1)
+        .return_const(Err(MempoolClientError::MempoolError(MempoolError::TransactionNotFound {
+            tx_hash: unknown_tx_hash,
+        })));
+
+    let http_client = mempool_http_client(mock_mempool_client, 26).await;
+
+    let response = http_client.get_tx(tx_hash).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let response_tx: InternalRpcTransaction =
+        serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(response_tx, tx);
+
+    let response = http_client.get_tx(unknown_tx_hash).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[rstest]
+#[case::per_client_ip(
+    19,
+    RateLimitConfig { per_client_ip: ONE_REQUEST_LIMIT, ..Default::default() },
+    RateLimiterKind::ClientIp
+)]
+#[case::per_client_region(
+    20,
+    RateLimitConfig { per_client_region: ONE_REQUEST_LIMIT, ..Default::default() },
+    RateLimiterKind::ClientRegion
+)]
+#[case::per_sender_address(
+    21,
+    RateLimitConfig { per_sender_address: ONE_REQUEST_LIMIT, ..Default::default() },
+    RateLimiterKind::SenderAddress
+)]
+#[tokio::test]
+async fn rate_limited_add_tx(
+    #[case] index: u16,
+    #[case] rate_limit_config: RateLimitConfig,
+    #[case] expected_rate_limiter: RateLimiterKind,
+) {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let _recorder_guard = metrics::set_default_local_recorder(&recorder);
+
+    // Only the first request reaches the gateway.
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client.expect_add_tx().times(1).return_const(Ok(default_gateway_output()));
+    let http_client =
+        rate_limited_add_tx_http_client(mock_gateway_client, rate_limit_config, index).await;
+
+    let region_header = [(CLIENT_REGION_HEADER, "test")];
+    let response = http_client.add_tx_with_headers(rpc_invoke_tx(), region_header).await;
+    assert_eq!(response.status(), StatusCode::OK);
+
+    let response = http_client.add_tx_with_headers(rpc_invoke_tx(), region_header).await;
+    assert_eq!(response.status(), StatusCode::TOO_MANY_REQUESTS);
+    let error: StarknetError = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(
+        error.code,
+        StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::TransactionLimitExceeded)
+    );
+
+    let metrics = recorder.handle().render();
+    RATE_LIMITED_REQUESTS.assert_eq::<usize>(
+        &metrics,
+        1,
+        &[(LABEL_NAME_RATE_LIMITER, expected_rate_limiter.into())],
+    );
+}
+
+#[rstest]
+#[case::add_deprecated_gateway_txs(
+    22,
+    vec![deprecated_gateway_invoke_tx(), deprecated_gateway_deploy_account_tx()]
+)]
+#[case::add_rpc_txs(23, vec![rpc_invoke_tx(), deploy_account_tx()])]
+#[tokio::test]
+async fn add_tx_batch(#[case] index: u16, #[case] txs: Vec<impl GatewayTransaction>) {
+    // The invoke transaction is added, and the deploy account transaction is rejected.
+    let expected_error = StarknetError {
+        code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+        message: "Arbitrary".to_string(),
+    };
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client
+        .expect_add_tx()
+        .withf(|input| matches!(input.rpc_tx, RpcTransaction::Invoke(_)))
+        .times(1)
+        .return_const(Ok(default_gateway_output()));
+    mock_gateway_client
+        .expect_add_tx()
+        .withf(|input| matches!(input.rpc_tx, RpcTransaction::DeployAccount(_)))
+        .times(1)
+        .return_const(Err(GatewayClientError::GatewayError(
+            GatewayError::DeprecatedGatewayError {
+                source: expected_error.clone(),
+                p2p_message_metadata: None,
+            },
+        )));
+    let http_client = add_tx_http_client(mock_gateway_client, index).await;
+
+    let response = http_client.add_tx_batch(&txs).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let results: Vec<AddTransactionBatchItemResult> =
+        serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(
+        results,
+        vec![
+            AddTransactionBatchItemResult::TransactionHash(EXPECTED_TX_HASH),
+            AddTransactionBatchItemResult::Error(expected_error),
+        ]
+    );
+}
+
+#[tokio::test]
+async fn add_tx_batch_exceeds_max_size() {
+    let http_client = add_tx_http_client(MockGatewayClient::new(), 24).await;
+
+    let txs = vec![rpc_invoke_tx(); AddTransactionBatchConfig::default().max_size + 1];
+    let response = http_client.add_tx_batch(&txs).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+}
+
+#[tokio::test]
+async fn validate_rpc_tx() {
+    let expected_output = ValidateTransactionOutput {
+        transaction_hash: EXPECTED_TX_HASH,
+        validation_resources: Some(ValidationResources { n_steps: 100, ..Default::default() }),
+    };
+    let expected_error = StarknetError {
+        code: StarknetErrorCode::KnownErrorCode(KnownStarknetErrorCode::ValidateFailure),
+        message: "Arbitrary".to_string(),
+    };
+    // Transactions are only validated; none of them is added.
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client
+        .expect_validate_tx()
+        .withf(|tx| matches!(tx, RpcTransaction::Invoke(_)))
+        .times(1)
+        .return_const(Ok(expected_output.clone()));
+    mock_gateway_client
+        .expect_validate_tx()
+        .withf(|tx| matches!(tx, RpcTransaction::DeployAccount(_)))
+        .times(1)
+        .return_const(Err(GatewayClientError::GatewayError(
+            GatewayError::DeprecatedGatewayError {
+                source: expected_error.clone(),
+                p2p_message_metadata: None,
+            },
+        )));
+    let http_client = add_tx_http_client(mock_gateway_client, 25).await;
+
+    let response = http_client.validate_tx(&rpc_invoke_tx()).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let output: ValidateTransactionOutput =
+        serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(output, expected_output);
+
+    let response = http_client.validate_tx(&deploy_account_tx()).await;
+    assert_eq!(response.status(), StatusCode::BAD_REQUEST);
+    let error: StarknetError = serde_json::from_str(&response.text().await.unwrap()).unwrap();
+    assert_eq!(error.code, expected_error.code);
+}
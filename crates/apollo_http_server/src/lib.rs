@@ -4,5 +4,6 @@ mod deprecated_gateway_transaction;
 pub mod errors;
 pub mod http_server;
 pub mod metrics;
+pub mod rate_limiter;
 #[cfg(any(feature = "testing", test))]
 pub mod test_utils;
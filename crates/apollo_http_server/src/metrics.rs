@@ -1,6 +1,9 @@
-use apollo_metrics::define_metrics;
+use apollo_metrics::{define_metrics, generate_permutation_labels};
+use strum::VariantNames;
 use tracing::info;
 
+use crate::rate_limiter::RateLimiterKind;
+
 #[cfg(test)]
 #[path = "metrics_test.rs"]
 pub mod metrics_test;
@@ -14,9 +17,17 @@ define_metrics!(
         MetricCounter { ADDED_TRANSACTIONS_INTERNAL_ERROR, "http_server_added_transactions_internal_error", "Number of faulty added transactions failing on internal error", init = 0 },
         MetricCounter { ADDED_TRANSACTIONS_DEPRECATED_ERROR, "http_server_added_transactions_deprecated_error", "Number of faulty added transactions failing on deprecated error", init = 0 },
         MetricHistogram { HTTP_SERVER_ADD_TX_LATENCY, "http_server_add_tx_latency", "Latency of HTTP add_tx endpoint in secs" },
+        LabeledMetricCounter { RATE_LIMITED_REQUESTS, "http_server_rate_limited_requests", "Number of add transaction requests rejected by a rate limiter, per limiter", init = 0, labels = RATE_LIMITER_LABELS },
     },
 );
 
+pub const LABEL_NAME_RATE_LIMITER: &str = "rate_limiter";
+
+generate_permutation_labels! {
+    RATE_LIMITER_LABELS,
+    (LABEL_NAME_RATE_LIMITER, RateLimiterKind),
+}
+
 pub(crate) fn init_metrics() {
     info!("Initializing HTTP Server metrics");
     ADDED_TRANSACTIONS_TOTAL.register();
@@ -25,4 +36,5 @@ pub(crate) fn init_metrics() {
     ADDED_TRANSACTIONS_INTERNAL_ERROR.register();
     ADDED_TRANSACTIONS_DEPRECATED_ERROR.register();
     HTTP_SERVER_ADD_TX_LATENCY.register();
+    RATE_LIMITED_REQUESTS.register();
 }
@@ -0,0 +1,184 @@
+use std::collections::{HashMap, VecDeque};
+use std::hash::Hash;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::Instant;
+
+use starknet_api::core::ContractAddress;
+use strum_macros::{Display, EnumIter, EnumVariantNames, IntoStaticStr};
+
+use crate::config::{RateLimitConfig, TokenBucketConfig};
+use crate::metrics::{LABEL_NAME_RATE_LIMITER, RATE_LIMITED_REQUESTS};
+
+#[cfg(test)]
+#[path = "rate_limiter_test.rs"]
+pub mod rate_limiter_test;
+
+/// The key by which a rate limiter tells requests apart.
+#[derive(Clone, Copy, Debug, Display, EnumIter, EnumVariantNames, IntoStaticStr, PartialEq)]
+#[strum(serialize_all = "snake_case")]
+pub enum RateLimiterKind {
+    ClientIp,
+    ClientRegion,
+    SenderAddress,
+}
+
+/// The rate limiters applied to add transaction requests.
+pub struct RateLimiters {
+    client_ip: RateLimiter<IpAddr>,
+    client_region: RateLimiter<String>,
+    sender_address: RateLimiter<ContractAddress>,
+}
+
+impl RateLimiters {
+    pub fn new(config: &RateLimitConfig) -> Self {
+        Self {
+            client_ip: RateLimiter::new(RateLimiterKind::ClientIp, config.per_client_ip),
+            client_region: RateLimiter::new(
+                RateLimiterKind::ClientRegion,
+                config.per_client_region,
+            ),
+            sender_address: RateLimiter::new(
+                RateLimiterKind::SenderAddress,
+                config.per_sender_address,
+            ),
+        }
+    }
+
+    /// Consumes a token of the request's client IP and region, if known. Returns the kind of the
+    /// first limiter that rejected the request, if any.
+    pub fn check_client(
+        &self,
+        client_ip: Option<IpAddr>,
+        client_region: Option<&str>,
+    ) -> Result<(), RateLimiterKind> {
+        let now = Instant::now();
+        if let Some(client_ip) = client_ip {
+            self.client_ip.try_acquire(client_ip, now)?;
+        }
+        if let Some(client_region) = client_region {
+            self.client_region.try_acquire(client_region.to_owned(), now)?;
+        }
+        Ok(())
+    }
+
+    /// Consumes a token of the transaction's sender. Returns the limiter's kind if it rejected the
+    /// request.
+    pub fn check_sender(&self, sender_address: ContractAddress) -> Result<(), RateLimiterKind> {
+        self.sender_address.try_acquire(sender_address, Instant::now())
+    }
+}
+
+/// A token-bucket rate limiter, holding a separate bucket per key.
+pub struct RateLimiter<K> {
+    kind: RateLimiterKind,
+    config: TokenBucketConfig,
+    buckets: Mutex<Buckets<K>>,
+}
+
+impl<K: Clone + Eq + Hash> RateLimiter<K> {
+    pub fn new(kind: RateLimiterKind, config: TokenBucketConfig) -> Self {
+        Self { kind, config, buckets: Mutex::new(Buckets::default()) }
+    }
+
+    /// Consumes a token of the given key's bucket, or rejects the request if the bucket is empty.
+    pub fn try_acquire(&self, key: K, now: Instant) -> Result<(), RateLimiterKind> {
+        if !self.config.is_enabled() {
+            return Ok(());
+        }
+
+        let mut buckets = self.buckets.lock().expect("Rate limiter lock should not be poisoned.");
+        buckets.discard_full_buckets(&self.config, now);
+        if buckets.get_or_insert(key, &self.config, now).try_consume(&self.config, now) {
+            return Ok(());
+        }
+
+        RATE_LIMITED_REQUESTS.increment(1, &[(LABEL_NAME_RATE_LIMITER, self.kind.into())]);
+        Err(self.kind)
+    }
+
+    #[cfg(test)]
+    fn n_tracked_keys(&self) -> usize {
+        self.buckets.lock().expect("Rate limiter lock should not be poisoned.").by_key.len()
+    }
+}
+
+// The buckets of a rate limiter. Buckets refilled to their capacity are discarded, as they are
+// indistinguishable from new ones.
+struct Buckets<K> {
+    by_key: HashMap<K, TokenBucket>,
+    // Every tracked key, with the time it was last queued at, oldest first. A key's bucket may
+    // have been used since it was queued, in which case it is queued again once it reaches the
+    // front.
+    queue: VecDeque<(Instant, K)>,
+}
+
+impl<K> Default for Buckets<K> {
+    fn default() -> Self {
+        Self { by_key: HashMap::new(), queue: VecDeque::new() }
+    }
+}
+
+impl<K: Clone + Eq + Hash> Buckets<K> {
+    fn get_or_insert(
+        &mut self,
+        key: K,
+        config: &TokenBucketConfig,
+        now: Instant,
+    ) -> &mut TokenBucket {
+        if !self.by_key.contains_key(&key) {
+            self.queue.push_back((now, key.clone()));
+        }
+        self.by_key.entry(key).or_insert_with(|| TokenBucket::new(config, now))
+    }
+
+    // Pops keys from the front of the queue until reaching a bucket which is not full and was not
+    // used since it was queued. Each key is popped at most once per use of its bucket, so this
+    // takes amortized constant time.
+    fn discard_full_buckets(&mut self, config: &TokenBucketConfig, now: Instant) {
+        while let Some((queued_at, key)) = self.queue.pop_front() {
+            let bucket = self.by_key.get(&key).expect("Queued keys should have a bucket.");
+            if bucket.is_full(config, now) {
+                self.by_key.remove(&key);
+            } else if bucket.last_refill > queued_at {
+                self.queue.push_back((bucket.last_refill, key));
+            } else {
+                self.queue.push_front((queued_at, key));
+                return;
+            }
+        }
+    }
+}
+
+struct TokenBucket {
+    tokens: f64,
+    last_refill: Instant,
+}
+
+impl TokenBucket {
+    fn new(config: &TokenBucketConfig, now: Instant) -> Self {
+        Self { tokens: f64::from(config.max_burst), last_refill: now }
+    }
+
+    fn refill(&mut self, config: &TokenBucketConfig, now: Instant) {
+        let elapsed_secs = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens = (self.tokens + elapsed_secs * f64::from(config.refill_rate_per_second))
+            .min(f64::from(config.max_burst));
+        self.last_refill = now;
+    }
+
+    fn try_consume(&mut self, config: &TokenBucketConfig, now: Instant) -> bool {
+        self.refill(config, now);
+        if self.tokens < 1.0 {
+            return false;
+        }
+        self.tokens -= 1.0;
+        true
+    }
+
+    fn is_full(&self, config: &TokenBucketConfig, now: Instant) -> bool {
+        let elapsed_secs = now.saturating_duration_since(self.last_refill).as_secs_f64();
+        self.tokens + elapsed_secs * f64::from(config.refill_rate_per_second)
+            >= f64::from(config.max_burst)
+    }
+}
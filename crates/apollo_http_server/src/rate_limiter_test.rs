@@ -0,0 +1,70 @@
+use std::time::{Duration, Instant};
+
+use crate::config::TokenBucketConfig;
+use crate::rate_limiter::{RateLimiter, RateLimiterKind};
+
+const KIND: RateLimiterKind = RateLimiterKind::ClientIp;
+
+#[test]
+fn burst_is_limited_and_refilled() {
+    let config = TokenBucketConfig { max_burst: 2, refill_rate_per_second: 1 };
+    let rate_limiter = RateLimiter::new(KIND, config);
+    let start = Instant::now();
+
+    assert_eq!(rate_limiter.try_acquire("key", start), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("key", start), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("key", start), Err(KIND));
+
+    // Half a token is not enough.
+    let half_second_later = start + Duration::from_millis(500);
+    assert_eq!(rate_limiter.try_acquire("key", half_second_later), Err(KIND));
+
+    let second_later = start + Duration::from_secs(1);
+    assert_eq!(rate_limiter.try_acquire("key", second_later), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("key", second_later), Err(KIND));
+
+    // Refilling does not exceed the burst size.
+    let minute_later = start + Duration::from_secs(60);
+    assert_eq!(rate_limiter.try_acquire("key", minute_later), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("key", minute_later), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("key", minute_later), Err(KIND));
+}
+
+#[test]
+fn keys_are_limited_independently() {
+    let config = TokenBucketConfig { max_burst: 1, refill_rate_per_second: 0 };
+    let rate_limiter = RateLimiter::new(KIND, config);
+    let now = Instant::now();
+
+    assert_eq!(rate_limiter.try_acquire("key", now), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("key", now), Err(KIND));
+    assert_eq!(rate_limiter.try_acquire("other_key", now), Ok(()));
+}
+
+#[test]
+fn disabled_limiter_accepts_all_requests() {
+    let rate_limiter = RateLimiter::new(KIND, TokenBucketConfig::default());
+    let now = Instant::now();
+
+    for _ in 0..10 {
+        assert_eq!(rate_limiter.try_acquire("key", now), Ok(()));
+    }
+}
+
+#[test]
+fn refilled_buckets_are_discarded() {
+    let config = TokenBucketConfig { max_burst: 1, refill_rate_per_second: 1 };
+    let rate_limiter = RateLimiter::new(KIND, config);
+    let start = Instant::now();
+    let half_second_later = start + Duration::from_millis(500);
+    let second_later = start + Duration::from_secs(1);
+
+    assert_eq!(rate_limiter.try_acquire("idle_key", start), Ok(()));
+    assert_eq!(rate_limiter.try_acquire("busy_key", half_second_later), Ok(()));
+    assert_eq!(rate_limiter.n_tracked_keys(), 2);
+
+    // Only the bucket of the idle key was refilled to its capacity, so only it is discarded.
+    assert_eq!(rate_limiter.try_acquire("new_key", second_later), Ok(()));
+    assert_eq!(rate_limiter.n_tracked_keys(), 2);
+    assert_eq!(rate_limiter.try_acquire("busy_key", second_later), Err(KIND));
+}
@@ -4,6 +4,7 @@ use std::sync::Arc;
 use apollo_gateway_types::communication::MockGatewayClient;
 use apollo_gateway_types::gateway_types::GatewayOutput;
 use apollo_infra_utils::test_utils::{AvailablePorts, TestIdentifier};
+use apollo_mempool_types::communication::{MockMempoolClient, SharedMempoolClient};
 use axum::body::Body;
 use blockifier_test_utils::cairo_versions::CairoVersion;
 use hyper::StatusCode;
@@ -13,7 +14,7 @@ use serde::Serialize;
 use starknet_api::rpc_transaction::RpcTransaction;
 use starknet_api::transaction::TransactionHash;
 
-use crate::config::HttpServerConfig;
+use crate::config::{HttpServerConfig, RateLimitConfig};
 use crate::deprecated_gateway_transaction::DeprecatedGatewayTransactionV3;
 use crate::http_server::HttpServer;
 
@@ -55,6 +56,24 @@ impl HttpTestClient {
         response.text().await.unwrap()
     }
 
+    pub async fn get_tx(&self, tx_hash: TransactionHash) -> Response {
+        self.client
+            .get(format!("http://{}/gateway/get_transaction", self.socket))
+            .query(&[("transactionHash", tx_hash.to_string())])
+            .send()
+            .await
+            .unwrap()
+    }
+
+    pub async fn get_tx_status(&self, tx_hash: TransactionHash) -> Response {
+        self.client
+            .get(format!("http://{}/gateway/get_transaction_status", self.socket))
+            .query(&[("transactionHash", tx_hash.to_string())])
+            .send()
+            .await
+            .unwrap()
+    }
+
     // Prefer using assert_add_tx_success or other higher level methods of this client, to ensure
     // tests are boilerplate and implementation-detail free.
     pub async fn add_tx(&self, tx: impl GatewayTransaction) -> Response {
@@ -81,23 +100,46 @@ impl HttpTestClient {
             .await
             .unwrap()
     }
+
+    pub async fn validate_tx(&self, tx: &RpcTransaction) -> Response {
+        self.client
+            .post(format!("http://{}/gateway/validate_rpc_transaction", self.socket))
+            .header("content-type", tx.content_type())
+            .body(Body::from(serde_json::to_string(tx).unwrap()))
+            .send()
+            .await
+            .unwrap()
+    }
+
+    /// Sends the transactions in a single request to the batch endpoint of their format.
+    pub async fn add_tx_batch<T: GatewayTransaction>(&self, txs: &[T]) -> Response {
+        let first_tx = txs.first().expect("Batch should not be empty.");
+        self.client
+            .post(format!("http://{}/gateway/{}_batch", self.socket, first_tx.endpoint()))
+            .header("content-type", first_tx.content_type())
+            .body(Body::from(serde_json::to_string(txs).unwrap()))
+            .send()
+            .await
+            .unwrap()
+    }
 }
 
 pub fn create_http_server_config(socket: SocketAddr) -> HttpServerConfig {
-    HttpServerConfig { ip: socket.ip(), port: socket.port() }
+    HttpServerConfig { ip: socket.ip(), port: socket.port(), ..Default::default() }
 }
 
 /// Creates an HTTP server and an HttpTestClient that can interact with it.
 pub async fn http_client_server_setup(
     mock_gateway_client: MockGatewayClient,
+    mempool_client: Option<SharedMempoolClient>,
     http_server_config: HttpServerConfig,
 ) -> HttpTestClient {
     // Create and run the server.
     let mut http_server =
-        HttpServer::new(http_server_config.clone(), Arc::new(mock_gateway_client));
+        HttpServer::new(http_server_config.clone(), Arc::new(mock_gateway_client), mempool_client);
     tokio::spawn(async move { http_server.run().await });
 
-    let HttpServerConfig { ip, port } = http_server_config;
+    let HttpServerConfig { ip, port, .. } = http_server_config;
     let add_tx_http_client = HttpTestClient::new(SocketAddr::from((ip, port)));
 
     // Ensure the server starts running.
@@ -149,11 +191,37 @@ pub async fn add_tx_http_client(
     mock_gateway_client: MockGatewayClient,
     port_index: u16,
 ) -> HttpTestClient {
+    http_client_server_setup(mock_gateway_client, None, unit_test_http_server_config(port_index))
+        .await
+}
+
+pub async fn mempool_http_client(
+    mock_mempool_client: MockMempoolClient,
+    port_index: u16,
+) -> HttpTestClient {
+    http_client_server_setup(
+        MockGatewayClient::new(),
+        Some(Arc::new(mock_mempool_client)),
+        unit_test_http_server_config(port_index),
+    )
+    .await
+}
+
+pub async fn rate_limited_add_tx_http_client(
+    mock_gateway_client: MockGatewayClient,
+    rate_limit_config: RateLimitConfig,
+    port_index: u16,
+) -> HttpTestClient {
+    let http_server_config =
+        HttpServerConfig { rate_limit_config, ..unit_test_http_server_config(port_index) };
+    http_client_server_setup(mock_gateway_client, None, http_server_config).await
+}
+
+fn unit_test_http_server_config(port_index: u16) -> HttpServerConfig {
     let ip = IpAddr::from(Ipv4Addr::LOCALHOST);
     let mut available_ports =
         AvailablePorts::new(TestIdentifier::HttpServerUnitTests.into(), port_index);
-    let http_server_config = HttpServerConfig { ip, port: available_ports.get_next_port() };
-    http_client_server_setup(mock_gateway_client, http_server_config).await
+    HttpServerConfig { ip, port: available_ports.get_next_port(), ..Default::default() }
 }
 
 pub fn rpc_invoke_tx() -> RpcTransaction {
@@ -970,7 +970,7 @@ async fn get_sequencer_setup_configs(
             .http_server_config
             .as_ref()
             .expect("Http server config should be set for this executable.");
-        let HttpServerConfig { ip, port } = http_server_config;
+        let HttpServerConfig { ip, port, .. } = http_server_config;
         let add_tx_http_client = HttpTestClient::new(SocketAddr::new(*ip, *port));
 
         nodes.push(NodeSetup::new(
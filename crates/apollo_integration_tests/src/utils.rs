@@ -13,15 +13,19 @@ use apollo_class_manager::config::{
 };
 use apollo_compile_to_casm::config::SierraCompilationConfig;
 use apollo_config::converters::UrlAndHeaders;
-use apollo_consensus::config::{ConsensusConfig, TimeoutsConfig};
+use apollo_consensus::config::{ConsensusConfig, EvidenceConfig, TimeoutsConfig};
 use apollo_consensus::types::ValidatorId;
 use apollo_consensus_manager::config::ConsensusManagerConfig;
 use apollo_consensus_orchestrator::cende::{CendeConfig, RECORDER_WRITE_BLOB_PATH};
 use apollo_consensus_orchestrator::config::ContextConfig;
 use apollo_gateway::config::{
+    DuplicateSubmissionCacheConfig,
     GatewayConfig,
+    GatewayPolicyFileConfig,
+    StateCacheConfig,
     StatefulTransactionValidatorConfig,
     StatelessTransactionValidatorConfig,
+    ValidationWorkerPoolConfig,
 };
 use apollo_http_server::test_utils::create_http_server_config;
 use apollo_infra_utils::test_utils::AvailablePorts;
@@ -42,7 +46,13 @@ use apollo_node::config::component_execution_config::ExpectedComponentConfig;
 use apollo_node::config::definitions::ConfigPointersMap;
 use apollo_node::config::monitoring::MonitoringConfig;
 use apollo_node::config::node_config::{SequencerNodeConfig, CONFIG_POINTERS};
+use apollo_protobuf::consensus::DEFAULT_VALIDATOR_ID;
 use apollo_rpc::RpcConfig;
+use apollo_signature_manager::config::SignatureManagerConfig;
+use apollo_signature_manager::signature_manager::{private_key_for_testing, LocalKeyStore};
+use apollo_staking::committee_provider::Staker;
+use apollo_staking::staking_contract::ConfiguredStaker;
+use apollo_staking::staking_manager::StakingManagerConfig;
 use apollo_state_sync::config::StateSyncConfig;
 use apollo_storage::StorageConfig;
 use axum::extract::Query;
@@ -64,6 +74,7 @@ use starknet_api::block::BlockNumber;
 use starknet_api::core::{ChainId, ContractAddress};
 use starknet_api::execution_resources::GasAmount;
 use starknet_api::rpc_transaction::RpcTransaction;
+use starknet_api::staking::StakingWeight;
 use starknet_api::transaction::fields::ContractAddressSalt;
 use starknet_api::transaction::{L1HandlerTransaction, TransactionHash, TransactionHasher};
 use starknet_types_core::felt::Felt;
@@ -74,6 +85,10 @@ use url::Url;
 use crate::anvil_base_layer::AnvilBaseLayer;
 use crate::state_reader::StorageTestConfig;
 
+#[cfg(test)]
+#[path = "utils_test.rs"]
+mod utils_test;
+
 pub const ACCOUNT_ID_0: AccountId = 0;
 pub const ACCOUNT_ID_1: AccountId = 1;
 pub const NEW_ACCOUNT_SALT: ContractAddressSalt = ContractAddressSalt(Felt::THREE);
@@ -82,6 +97,8 @@ pub const UNDEPLOYED_ACCOUNT_ID: AccountId = 2;
 // with the set [TimeoutsConfig] .
 pub const TPS: u64 = 3;
 pub const N_TXS_IN_FIRST_BLOCK: usize = 2;
+// Short enough for the committee to change a few times during a flow test.
+pub const FLOW_TEST_EPOCH_LENGTH: u64 = 2;
 
 pub type CreateRpcTxsFn = fn(&mut MultiAccountTransactionGenerator) -> Vec<RpcTransaction>;
 pub type CreateL1ToL2MessagesArgsFn =
@@ -293,6 +310,7 @@ pub fn create_node_config(
     let sierra_compiler_config =
         wrap_if_component_config_expected!(sierra_compiler, sierra_compiler_config);
     let state_sync_config = wrap_if_component_config_expected!(state_sync, state_sync_config);
+    let signature_manager_config = create_signature_manager_config(validator_id);
 
     let sequencer_node_config = SequencerNodeConfig {
         base_layer_config,
@@ -312,6 +330,7 @@ pub fn create_node_config(
         monitoring_endpoint_config,
         monitoring_config,
         sierra_compiler_config,
+        signature_manager_config,
         state_sync_config,
     };
 
@@ -332,6 +351,7 @@ pub(crate) fn create_consensus_manager_configs_from_network_configs(
     timeouts.proposal_timeout *= 3;
 
     let num_validators = u64::try_from(n_composed_nodes).unwrap();
+    let stakers = create_flow_test_stakers(num_validators);
 
     network_configs
         .into_iter()
@@ -343,10 +363,13 @@ pub(crate) fn create_consensus_manager_configs_from_network_configs(
                 // TODO(Matan, Dan): Set the right amount
                 startup_delay: Duration::from_secs(15),
                 timeouts: timeouts.clone(),
+                // The nodes of a test share the file system, so they keep the evidence in memory.
+                evidence: EvidenceConfig { persist: false, ..Default::default() },
                 ..Default::default()
             },
             context_config: ContextConfig {
                 num_validators,
+                stakers: stakers.clone(),
                 chain_id: chain_id.clone(),
                 builder_address: ContractAddress::from(4_u128),
                 ..Default::default()
@@ -355,12 +378,39 @@ pub(crate) fn create_consensus_manager_configs_from_network_configs(
                 skip_write_height: Some(BlockNumber(1)),
                 ..Default::default()
             },
+            staking_manager_config: StakingManagerConfig {
+                epoch_length: FLOW_TEST_EPOCH_LENGTH,
+                ..Default::default()
+            },
             assume_no_malicious_validators: true,
             ..Default::default()
         })
         .collect()
 }
 
+// Runs the flow through epoch transitions: the validators are equally weighted in the first epoch,
+// and weighted by their index from the second one on. The i-th validator signs with the i-th
+// testing key.
+pub(crate) fn create_flow_test_stakers(num_validators: u64) -> Vec<ConfiguredStaker> {
+    let initial_stakers: Vec<_> = (0..num_validators)
+        .map(|i| ConfiguredStaker {
+            start_epoch: 0,
+            staker: Staker {
+                address: ContractAddress::from(DEFAULT_VALIDATOR_ID + i),
+                weight: StakingWeight(1),
+                public_key: LocalKeyStore::new(private_key_for_testing(i)).public_key.0,
+            },
+        })
+        .collect();
+    let mut stakers = initial_stakers.clone();
+    stakers.extend(initial_stakers.into_iter().zip(1..).map(|(mut configured_staker, weight)| {
+        configured_staker.start_epoch = 1;
+        configured_staker.staker.weight = StakingWeight(weight);
+        configured_staker
+    }));
+    stakers
+}
+
 // Creates a local recorder server that always returns a success status.
 pub fn spawn_success_recorder(socket_address: SocketAddr) -> JoinHandle<()> {
     tokio::spawn(async move {
@@ -615,6 +665,10 @@ pub fn create_gateway_config(
         chain_info,
         block_declare: false,
         authorized_declarer_accounts: None,
+        duplicate_submission_cache_config: DuplicateSubmissionCacheConfig::default(),
+        state_cache_config: StateCacheConfig::default(),
+        validation_worker_pool_config: ValidationWorkerPoolConfig::default(),
+        policy_file_config: GatewayPolicyFileConfig::default(),
     }
 }
 
@@ -676,6 +730,14 @@ pub fn set_validator_id(
     validator_id
 }
 
+// The node of the i-th validator signs with the i-th testing key, which its default staker is
+// registered with.
+fn create_signature_manager_config(validator_id: ValidatorId) -> SignatureManagerConfig {
+    let validator_index =
+        u64::try_from(Felt::from(validator_id) - Felt::from(DEFAULT_VALIDATOR_ID)).unwrap();
+    SignatureManagerConfig { private_key: private_key_for_testing(validator_index) }
+}
+
 pub fn create_state_sync_configs(
     state_sync_storage_config: StorageConfig,
     ports: Vec<u16>,
@@ -0,0 +1,41 @@
+use std::sync::Arc;
+
+use apollo_staking::committee_provider::CommitteeProvider;
+use apollo_staking::staking_contract::StaticStakingContract;
+use apollo_staking::staking_manager::{StakingManager, StakingManagerConfig};
+use apollo_staking::utils::BlockPseudorandomGenerator;
+use apollo_state_sync_types::communication::MockStateSyncClient;
+use starknet_api::block::BlockNumber;
+
+use crate::flow_test_setup::NUM_OF_SEQUENCERS;
+use crate::utils::{create_flow_test_stakers, FLOW_TEST_EPOCH_LENGTH};
+
+// The number of rounds whose proposers are compared.
+const N_ROUNDS: u32 = 5;
+
+#[tokio::test]
+async fn flow_test_committee_changes_across_epochs() {
+    let num_validators = u64::try_from(NUM_OF_SEQUENCERS).unwrap();
+    // The flow test heights are below the proposer prediction window, so the proposers don't depend
+    // on past block hashes and the state sync client isn't used.
+    let staking_manager = StakingManager::new(
+        Arc::new(StaticStakingContract::new(create_flow_test_stakers(num_validators))),
+        Box::new(BlockPseudorandomGenerator),
+        Arc::new(MockStateSyncClient::new()),
+        StakingManagerConfig { epoch_length: FLOW_TEST_EPOCH_LENGTH, ..Default::default() },
+    );
+
+    let last_height_of_first_epoch = BlockNumber(FLOW_TEST_EPOCH_LENGTH - 1);
+    let first_height_of_second_epoch = BlockNumber(FLOW_TEST_EPOCH_LENGTH);
+    let first_committee = staking_manager.get_committee(last_height_of_first_epoch).await.unwrap();
+    let second_committee =
+        staking_manager.get_committee(first_height_of_second_epoch).await.unwrap();
+    assert_ne!(first_committee, second_committee);
+
+    let proposers = |height| {
+        (0..N_ROUNDS)
+            .map(|round| staking_manager.get_proposer(height, round).unwrap())
+            .collect::<Vec<_>>()
+    };
+    assert_ne!(proposers(last_height_of_first_epoch), proposers(first_height_of_second_epoch));
+}
@@ -59,9 +59,6 @@ pub async fn end_to_end_flow(
     let sequencers = [&mock_running_system.sequencer_0, &mock_running_system.sequencer_1];
     // We use only the first sequencer's gateway to test that the mempools are syncing.
     let sequencer_to_add_txs = *sequencers.first().unwrap();
-    let mut expected_proposer_iter = sequencers.iter().cycle();
-    // We start at height 1, so we need to skip the proposer of the initial height.
-    expected_proposer_iter.next().unwrap();
     let chain_id = mock_running_system.chain_id().clone();
     let mut send_rpc_tx_fn = |tx| sequencer_to_add_txs.assert_add_tx_success(tx);
 
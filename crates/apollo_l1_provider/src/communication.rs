@@ -33,6 +33,9 @@ impl ComponentRequestHandler<L1ProviderRequest, L1ProviderResponse> for L1Provid
                     height,
                 ))
             }
+            L1ProviderRequest::RevertBlock { height, tx_hashes } => {
+                L1ProviderResponse::RevertBlock(self.revert_block(height, &tx_hashes))
+            }
             L1ProviderRequest::GetTransactions { n_txs, height } => {
                 L1ProviderResponse::GetTransactions(self.get_txs(n_txs, height))
             }
@@ -168,6 +168,42 @@ impl L1Provider {
         Ok(())
     }
 
+    /// Reverts the latest committed block: its L1 handler transactions, given by `tx_hashes`,
+    /// return to their state before it was committed, so that they can be included in a later
+    /// block.
+    #[instrument(skip(self, tx_hashes), err)]
+    pub fn revert_block(
+        &mut self,
+        height: BlockNumber,
+        tx_hashes: &[TransactionHash],
+    ) -> L1ProviderResult<()> {
+        info!("Reverting block at height {height} in the L1 provider.");
+        if self.state.uninitialized() {
+            return Err(L1ProviderError::Uninitialized);
+        }
+
+        if self.is_historical_height(height) {
+            debug!(
+                "Skipping revert block for historical height: {height}, the provider started at \
+                 height {}",
+                self.start_height
+            );
+            return Ok(());
+        }
+
+        // Only the latest committed block can be reverted.
+        if height.unchecked_next() != self.current_height {
+            return Err(L1ProviderError::UnexpectedHeight {
+                expected_height: self.current_height.prev().unwrap_or_default(),
+                got: height,
+            });
+        }
+
+        self.tx_manager.revert_txs(tx_hashes);
+        self.current_height = height;
+        Ok(())
+    }
+
     #[instrument(skip_all, err)]
     pub fn add_events(&mut self, events: Vec<Event>) -> L1ProviderResult<()> {
         if self.state.uninitialized() {
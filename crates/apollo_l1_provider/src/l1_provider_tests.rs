@@ -534,6 +534,56 @@ fn commit_block_twice_panics() {
     l1_provider.commit_block([tx_hash!(1)].into(), [].into(), BlockNumber(0)).unwrap();
 }
 
+#[test]
+fn revert_block_returns_txs_to_pending() {
+    let mut l1_provider = setup_rejected_transactions();
+
+    // Test.
+    l1_provider.revert_block(BlockNumber(0), &[tx_hash!(1), tx_hash!(2)]).unwrap();
+
+    // Both the committed and the rejected transactions are pending again.
+    let expected_l1_provider = L1ProviderContentBuilder::new()
+        .with_txs([l1_handler(3), l1_handler(1), l1_handler(2)])
+        .with_height(BlockNumber(0))
+        .build();
+    expected_l1_provider.assert_eq(&l1_provider);
+}
+
+#[test]
+fn reverted_tx_can_be_proposed_again() {
+    // Setup.
+    let mut l1_provider = L1ProviderContentBuilder::new()
+        .with_txs([l1_handler(1)])
+        .with_height(BlockNumber(5))
+        .with_state(ProviderState::Propose)
+        .build_into_l1_provider();
+    assert_eq!(l1_provider.get_txs(1, BlockNumber(5)).unwrap(), [l1_handler(1)]);
+    commit_block_no_rejected(&mut l1_provider, &[tx_hash!(1)], BlockNumber(5));
+
+    // Test.
+    l1_provider.revert_block(BlockNumber(5), &[tx_hash!(1)]).unwrap();
+
+    l1_provider.start_block(BlockNumber(5), ProposeSession).unwrap();
+    assert_eq!(l1_provider.get_txs(1, BlockNumber(5)).unwrap(), [l1_handler(1)]);
+}
+
+#[test]
+fn revert_block_only_reverts_latest_block() {
+    // Setup.
+    let mut l1_provider =
+        L1ProviderContentBuilder::new().with_height(BlockNumber(5)).build_into_l1_provider();
+    commit_block_no_rejected(&mut l1_provider, &[], BlockNumber(5));
+
+    // Test.
+    assert_eq!(
+        l1_provider.revert_block(BlockNumber(4), &[]),
+        Err(L1ProviderError::UnexpectedHeight {
+            expected_height: BlockNumber(5),
+            got: BlockNumber(4)
+        })
+    );
+}
+
 #[test]
 fn add_tx_identical_timestamp_both_stored() {
     // Setup.
@@ -580,6 +580,14 @@ impl L1ProviderClient for FakeL1ProviderClient {
         Ok(())
     }
 
+    async fn revert_block(
+        &self,
+        _height: BlockNumber,
+        _tx_hashes: Vec<TransactionHash>,
+    ) -> L1ProviderClientResult<()> {
+        todo!()
+    }
+
     async fn validate(
         &self,
         _tx_hash: TransactionHash,
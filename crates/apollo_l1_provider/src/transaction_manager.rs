@@ -151,6 +151,16 @@ impl TransactionManager {
         }
     }
 
+    /// Returns the transactions of a reverted block to their state before it was committed.
+    pub fn revert_txs(&mut self, tx_hashes: &[TransactionHash]) {
+        self.rollback_staging();
+
+        for &tx_hash in tx_hashes {
+            // Transactions consumed on L1 since their block was committed may have been removed.
+            self.with_record(tx_hash, |record| record.mark_reverted());
+        }
+    }
+
     /// Adds a transaction to the transaction manager, return true if the transaction was
     /// successfully added. If the transaction is occupied or already had its hash stored as
     /// committed, it will not be added, and false will be returned.
@@ -261,8 +271,8 @@ impl TransactionManager {
 
             let tx_hash = hash;
             if record.is_proposable() {
-                // Assumption: txs will only be added to the index once, on arrival, so this
-                // preserves arrival order.
+                // Assumption: txs are only added to the index on arrival, or when the block that
+                // included them is reverted, so this preserves arrival order up to reverts.
                 let tx_hashes = self.proposable_index.entry(scrape_timestamp).or_default();
                 if !tx_hashes.contains(&tx_hash) {
                     tx_hashes.push(tx_hash);
@@ -71,6 +71,20 @@ impl TransactionRecord {
         self.rejected = true;
     }
 
+    /// Undoes the inclusion of the transaction in a block that was reverted, as committed or
+    /// rejected.
+    pub fn mark_reverted(&mut self) {
+        if matches!(self.state, TransactionState::Committed | TransactionState::Rejected) {
+            self.state = if self.cancellation_requested_at.is_some() {
+                TransactionState::CancellationStartedOnL2
+            } else {
+                TransactionState::Pending
+            };
+        }
+        self.committed = false;
+        self.rejected = false;
+    }
+
     /// Mark a cancellation request for this transaction.
     /// Returns the existing cancellation timestamp if one exists, `None` if this is the first
     /// request.
@@ -74,6 +74,10 @@ pub enum L1ProviderRequest {
         height: BlockNumber,
     },
     Initialize(Vec<Event>),
+    RevertBlock {
+        height: BlockNumber,
+        tx_hashes: Vec<TransactionHash>,
+    },
     StartBlock {
         state: SessionState,
         height: BlockNumber,
@@ -94,6 +98,7 @@ pub enum L1ProviderResponse {
     CommitBlock(L1ProviderResult<()>),
     GetTransactions(L1ProviderResult<Vec<L1HandlerTransaction>>),
     Initialize(L1ProviderResult<()>),
+    RevertBlock(L1ProviderResult<()>),
     StartBlock(L1ProviderResult<()>),
     Validate(L1ProviderResult<ValidationStatus>),
     GetL1ProviderSnapshot(L1ProviderResult<L1ProviderSnapshot>),
@@ -130,6 +135,12 @@ pub trait L1ProviderClient: Send + Sync {
         height: BlockNumber,
     ) -> L1ProviderClientResult<()>;
 
+    async fn revert_block(
+        &self,
+        height: BlockNumber,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> L1ProviderClientResult<()>;
+
     async fn add_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()>;
     async fn initialize(&self, events: Vec<Event>) -> L1ProviderClientResult<()>;
     async fn get_l1_provider_snapshot(&self) -> L1ProviderClientResult<L1ProviderSnapshot>;
@@ -204,6 +215,21 @@ where
         )
     }
 
+    async fn revert_block(
+        &self,
+        height: BlockNumber,
+        tx_hashes: Vec<TransactionHash>,
+    ) -> L1ProviderClientResult<()> {
+        let request = L1ProviderRequest::RevertBlock { height, tx_hashes };
+        handle_all_response_variants!(
+            L1ProviderResponse,
+            RevertBlock,
+            L1ProviderClientError,
+            L1ProviderError,
+            Direct
+        )
+    }
+
     #[instrument(skip(self))]
     async fn add_events(&self, events: Vec<Event>) -> L1ProviderClientResult<()> {
         let request = L1ProviderRequest::AddEvents(events);
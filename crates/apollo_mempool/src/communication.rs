@@ -1,3 +1,4 @@
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use apollo_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
@@ -9,18 +10,31 @@ use apollo_mempool_types::communication::{
     MempoolResponse,
 };
 use apollo_mempool_types::errors::MempoolError;
-use apollo_mempool_types::mempool_types::{CommitBlockArgs, MempoolResult, MempoolSnapshot};
+use apollo_mempool_types::mempool_types::{
+    CommitBlockArgs,
+    DroppedTransaction,
+    DroppedTransactionsFilter,
+    MempoolEvent,
+    MempoolResult,
+    MempoolSnapshot,
+    MempoolTransactionStatus,
+    RevertBlockArgs,
+};
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
+use apollo_state_sync_types::communication::{SharedStateSyncClient, StateSyncClientError};
+use apollo_state_sync_types::errors::StateSyncError;
 use apollo_time::time::DefaultClock;
 use async_trait::async_trait;
 use starknet_api::block::GasPrice;
-use starknet_api::core::ContractAddress;
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::rpc_transaction::InternalRpcTransaction;
-use tracing::warn;
+use starknet_api::transaction::TransactionHash;
+use tracing::{info, warn};
 
 use crate::config::MempoolConfig;
 use crate::mempool::Mempool;
 use crate::metrics::register_metrics;
+use crate::persistence::{load_journal, MempoolJournal, PersistedMempool};
 
 pub type LocalMempoolServer =
     LocalComponentServer<MempoolCommunicationWrapper, MempoolRequest, MempoolResponse>;
@@ -29,10 +43,12 @@ pub type RemoteMempoolServer = RemoteComponentServer<MempoolRequest, MempoolResp
 pub fn create_mempool(
     config: MempoolConfig,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    state_sync_client: SharedStateSyncClient,
 ) -> MempoolCommunicationWrapper {
     MempoolCommunicationWrapper::new(
         Mempool::new(config, Arc::new(DefaultClock)),
         mempool_p2p_propagator_client,
+        state_sync_client,
     )
 }
 
@@ -40,14 +56,17 @@ pub fn create_mempool(
 pub struct MempoolCommunicationWrapper {
     mempool: Mempool,
     mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+    // Used to fetch the latest nonces of the accounts restored from the journal.
+    state_sync_client: SharedStateSyncClient,
 }
 
 impl MempoolCommunicationWrapper {
     pub fn new(
         mempool: Mempool,
         mempool_p2p_propagator_client: SharedMempoolP2pPropagatorClient,
+        state_sync_client: SharedStateSyncClient,
     ) -> Self {
-        MempoolCommunicationWrapper { mempool, mempool_p2p_propagator_client }
+        MempoolCommunicationWrapper { mempool, mempool_p2p_propagator_client, state_sync_client }
     }
 
     async fn send_tx_to_p2p(
@@ -91,10 +110,88 @@ impl MempoolCommunicationWrapper {
         Ok(())
     }
 
+    fn revert_block(&mut self, args: RevertBlockArgs) -> MempoolResult<()> {
+        self.mempool.revert_block(args);
+        Ok(())
+    }
+
+    /// Restores the mempool content from its journal and journals all subsequent changes, if
+    /// persistence is enabled. Failures are logged and otherwise ignored, as persistence is
+    /// best-effort.
+    async fn start_persistence(&mut self) {
+        let persistence_config = self.mempool.config().persistence_config.clone();
+        if !persistence_config.enabled {
+            return;
+        }
+
+        match load_journal(&persistence_config.path) {
+            Ok(Some(content)) => {
+                let latest_nonces = self.latest_nonces(&content).await;
+                self.mempool.restore(content, latest_nonces)
+            }
+            Ok(None) => info!("No mempool journal found at {:?}.", persistence_config.path),
+            Err(err) => {
+                warn!(
+                    "Failed to load the mempool journal from {:?}: {err}",
+                    persistence_config.path
+                )
+            }
+        }
+
+        match MempoolJournal::start(
+            persistence_config.path.clone(),
+            &self.mempool.persisted_content(),
+        ) {
+            Ok(journal) => self.mempool.set_journal(journal),
+            Err(err) => {
+                warn!("Failed to start the mempool journal at {:?}: {err}", persistence_config.path)
+            }
+        }
+    }
+
+    /// Returns the nonces of the accounts of the given content at the latest synced block, so that
+    /// transactions included in blocks committed while the node was down are not restored.
+    /// Accounts whose nonce cannot be fetched are omitted, and are restored with their persisted
+    /// nonces.
+    async fn latest_nonces(&self, content: &PersistedMempool) -> HashMap<ContractAddress, Nonce> {
+        let mut latest_nonces = HashMap::new();
+        let latest_block_number = match self.state_sync_client.get_latest_block_number().await {
+            Ok(Some(latest_block_number)) => latest_block_number,
+            Ok(None) => return latest_nonces,
+            Err(err) => {
+                warn!("Failed to get the latest block number for restoring the mempool: {err}");
+                return latest_nonces;
+            }
+        };
+
+        for persisted_tx in &content.transactions {
+            let address = persisted_tx.args.account_state.address;
+            if latest_nonces.contains_key(&address) {
+                continue;
+            }
+            match self.state_sync_client.get_nonce_at(latest_block_number, address).await {
+                Ok(nonce) => {
+                    latest_nonces.insert(address, nonce);
+                }
+                // The account is not deployed yet.
+                Err(StateSyncClientError::StateSyncError(StateSyncError::ContractNotFound(_))) => {}
+                Err(err) => {
+                    warn!("Failed to get the nonce of {address} at {latest_block_number}: {err}")
+                }
+            }
+        }
+
+        latest_nonces
+    }
+
     fn get_txs(&mut self, n_txs: usize) -> MempoolResult<Vec<InternalRpcTransaction>> {
         self.mempool.get_txs(n_txs)
     }
 
+    fn peek_txs(&self, n_txs: usize) -> MempoolResult<Vec<InternalRpcTransaction>> {
+        Ok(self.mempool.peek_txs(n_txs))
+    }
+
     fn account_tx_in_pool_or_recent_block(
         &self,
         account_address: ContractAddress,
@@ -110,6 +207,25 @@ impl MempoolCommunicationWrapper {
     fn mempool_snapshot(&self) -> MempoolResult<MempoolSnapshot> {
         self.mempool.mempool_snapshot()
     }
+
+    fn get_tx(&self, tx_hash: TransactionHash) -> MempoolResult<InternalRpcTransaction> {
+        self.mempool.get_tx(tx_hash)
+    }
+
+    fn get_tx_status(&self, tx_hash: TransactionHash) -> MempoolResult<MempoolTransactionStatus> {
+        self.mempool.tx_status(tx_hash)
+    }
+
+    fn get_mempool_events(&self, from_sequence_number: u64) -> MempoolResult<Vec<MempoolEvent>> {
+        Ok(self.mempool.events_from(from_sequence_number))
+    }
+
+    fn get_dropped_txs(
+        &self,
+        filter: DroppedTransactionsFilter,
+    ) -> MempoolResult<Vec<DroppedTransaction>> {
+        Ok(self.mempool.dropped_txs(&filter))
+    }
 }
 
 #[async_trait]
@@ -122,9 +238,15 @@ impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommuni
             MempoolRequest::CommitBlock(args) => {
                 MempoolResponse::CommitBlock(self.commit_block(args))
             }
+            MempoolRequest::RevertBlock(args) => {
+                MempoolResponse::RevertBlock(self.revert_block(args))
+            }
             MempoolRequest::GetTransactions(n_txs) => {
                 MempoolResponse::GetTransactions(self.get_txs(n_txs))
             }
+            MempoolRequest::PeekTransactions(n_txs) => {
+                MempoolResponse::PeekTransactions(self.peek_txs(n_txs))
+            }
             MempoolRequest::AccountTxInPoolOrRecentBlock(account_address) => {
                 MempoolResponse::AccountTxInPoolOrRecentBlock(
                     self.account_tx_in_pool_or_recent_block(account_address),
@@ -136,6 +258,18 @@ impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommuni
             MempoolRequest::GetMempoolSnapshot() => {
                 MempoolResponse::GetMempoolSnapshot(self.mempool_snapshot())
             }
+            MempoolRequest::GetTransaction(tx_hash) => {
+                MempoolResponse::GetTransaction(self.get_tx(tx_hash))
+            }
+            MempoolRequest::GetTransactionStatus(tx_hash) => {
+                MempoolResponse::GetTransactionStatus(self.get_tx_status(tx_hash))
+            }
+            MempoolRequest::GetMempoolEvents(from_sequence_number) => {
+                MempoolResponse::GetMempoolEvents(self.get_mempool_events(from_sequence_number))
+            }
+            MempoolRequest::GetDroppedTransactions(filter) => {
+                MempoolResponse::GetDroppedTransactions(self.get_dropped_txs(filter))
+            }
         }
     }
 }
@@ -144,5 +278,6 @@ impl ComponentRequestHandler<MempoolRequest, MempoolResponse> for MempoolCommuni
 impl ComponentStarter for MempoolCommunicationWrapper {
     async fn start(&mut self) {
         register_metrics();
+        self.start_persistence().await;
     }
 }
@@ -1,8 +1,14 @@
 use std::collections::BTreeMap;
+use std::path::PathBuf;
 use std::time::Duration;
 
 use apollo_config::converters::deserialize_seconds_to_duration;
-use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::dumping::{
+    prepend_sub_config_name,
+    ser_optional_param,
+    ser_param,
+    SerializeConfig,
+};
 use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use serde::{Deserialize, Serialize};
 use validator::Validate;
@@ -29,6 +35,21 @@ pub struct MempoolConfig {
     pub committed_nonce_retention_block_count: usize,
     // The maximum size of the mempool, in bytes.
     pub capacity_in_bytes: u64,
+    // The maximum number of transactions a single account can have in the mempool; unlimited if
+    // `None`.
+    pub max_txs_per_account: Option<usize>,
+    // The maximum distance between a transaction's nonce and its account's committed nonce;
+    // unlimited if `None`.
+    pub max_nonce_distance: Option<u64>,
+    // The maximum share of the mempool capacity a single account can occupy, in percent.
+    pub max_account_capacity_percentage: u8, // E.g., 10 for 10% of `capacity_in_bytes`.
+    // The policy by which transactions in the priority queue are ordered for sequencing.
+    pub ordering_policy: TransactionOrderingPolicy,
+    // The number of most recent dropped transactions to keep records of; 0 disables the records.
+    pub dropped_txs_capacity: usize,
+    pub persistence_config: MempoolPersistenceConfig,
+    #[validate]
+    pub event_bus_config: MempoolEventBusConfig,
 }
 
 impl Default for MempoolConfig {
@@ -41,13 +62,20 @@ impl Default for MempoolConfig {
             declare_delay: Duration::from_secs(1),
             committed_nonce_retention_block_count: 100,
             capacity_in_bytes: 1 << 30, // 1GB.
+            max_txs_per_account: None,
+            max_nonce_distance: None,
+            max_account_capacity_percentage: 100,
+            ordering_policy: TransactionOrderingPolicy::default(),
+            dropped_txs_capacity: 10000,
+            persistence_config: MempoolPersistenceConfig::default(),
+            event_bus_config: MempoolEventBusConfig::default(),
         }
     }
 }
 
 impl SerializeConfig for MempoolConfig {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        BTreeMap::from_iter([
+        let mut dump = BTreeMap::from_iter([
             ser_param(
                 "enable_fee_escalation",
                 &self.enable_fee_escalation,
@@ -93,6 +121,133 @@ impl SerializeConfig for MempoolConfig {
                 "Maximum size of the mempool, in bytes.",
                 ParamPrivacyInput::Public,
             ),
+            ser_param(
+                "max_account_capacity_percentage",
+                &self.max_account_capacity_percentage,
+                "Maximum share of the mempool capacity a single account can occupy, in percent.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "ordering_policy",
+                &self.ordering_policy,
+                "The policy by which ready transactions are ordered for sequencing; one of: tip, \
+                 fifo, effective_priority_fee, account_round_robin.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "dropped_txs_capacity",
+                &self.dropped_txs_capacity,
+                "Number of most recently dropped transactions to keep records of, for querying \
+                 why they were dropped. 0 disables the records.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.extend(ser_optional_param(
+            &self.max_txs_per_account,
+            200,
+            "max_txs_per_account",
+            "Maximum number of transactions a single account can have in the mempool.",
+            ParamPrivacyInput::Public,
+        ));
+        dump.extend(ser_optional_param(
+            &self.max_nonce_distance,
+            200,
+            "max_nonce_distance",
+            "Maximum distance between a transaction's nonce and its account's committed nonce.",
+            ParamPrivacyInput::Public,
+        ));
+        dump.append(&mut prepend_sub_config_name(
+            self.persistence_config.dump(),
+            "persistence_config",
+        ));
+        dump.append(&mut prepend_sub_config_name(self.event_bus_config.dump(), "event_bus_config"));
+        dump
+    }
+}
+
+/// The order in which transactions that are ready for sequencing are returned by the mempool.
+#[derive(Clone, Copy, Debug, Default, Deserialize, Eq, PartialEq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum TransactionOrderingPolicy {
+    /// Highest tip first.
+    #[default]
+    Tip,
+    /// First queued, first sequenced.
+    Fifo,
+    /// Highest tip plus L2 gas price headroom, i.e., the amount by which the max L2 gas price
+    /// bound exceeds the current gas price threshold.
+    EffectivePriorityFee,
+    /// Accounts take turns: an account is served again only after all other queued accounts were
+    /// served the same number of times in the current block. Ties are broken by tip.
+    AccountRoundRobin,
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
+pub struct MempoolPersistenceConfig {
+    // If true, changes to the mempool content are journaled to disk and replayed on startup.
+    pub enabled: bool,
+    // Path of the journal file.
+    pub path: PathBuf,
+}
+
+impl Default for MempoolPersistenceConfig {
+    fn default() -> Self {
+        MempoolPersistenceConfig { enabled: false, path: "/data/mempool/journal".into() }
+    }
+}
+
+impl SerializeConfig for MempoolPersistenceConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, changes to the mempool content are journaled to disk and replayed on \
+                 startup.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "path",
+                &self.path,
+                "Path of the mempool journal file.",
+                ParamPrivacyInput::Public,
+            ),
+        ])
+    }
+}
+
+#[derive(Debug, Deserialize, Serialize, Clone, PartialEq, Validate)]
+pub struct MempoolEventBusConfig {
+    // If true, the mempool publishes the lifecycle events of its transactions.
+    pub enabled: bool,
+    // The maximum number of events retained for consumers that did not yet receive them.
+    #[validate(range(min = 1))]
+    pub capacity: usize,
+}
+
+impl Default for MempoolEventBusConfig {
+    fn default() -> Self {
+        MempoolEventBusConfig { enabled: false, capacity: 10000 }
+    }
+}
+
+impl SerializeConfig for MempoolEventBusConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([
+            ser_param(
+                "enabled",
+                &self.enabled,
+                "If true, the mempool publishes the lifecycle events of its transactions. If \
+                 false, no events are published nor retained.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "capacity",
+                &self.capacity,
+                "Maximum number of mempool events retained for consumers that did not yet receive \
+                 them.",
+                ParamPrivacyInput::Public,
+            ),
         ])
     }
 }
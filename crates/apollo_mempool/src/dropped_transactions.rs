@@ -0,0 +1,53 @@
+use std::collections::VecDeque;
+
+use apollo_mempool_types::mempool_types::{
+    DroppedTransaction,
+    DroppedTransactionsFilter,
+    TransactionDropReason,
+};
+use apollo_time::time::DateTime;
+
+use crate::mempool::TransactionReference;
+
+/// A bounded log of the most recent transactions that were removed from the mempool without being
+/// committed; once full, the oldest records are discarded.
+pub struct DroppedTransactionLog {
+    records: VecDeque<DroppedTransaction>,
+    capacity: usize,
+}
+
+impl DroppedTransactionLog {
+    pub fn new(capacity: usize) -> Self {
+        DroppedTransactionLog { records: VecDeque::with_capacity(capacity), capacity }
+    }
+
+    pub fn record(
+        &mut self,
+        time: DateTime,
+        tx_reference: &TransactionReference,
+        reason: TransactionDropReason,
+    ) {
+        if self.capacity == 0 {
+            return;
+        }
+        if self.records.len() == self.capacity {
+            self.records.pop_front();
+        }
+
+        self.records.push_back(DroppedTransaction {
+            tx_hash: tx_reference.tx_hash,
+            address: tx_reference.address,
+            nonce: tx_reference.nonce,
+            reason,
+            timestamp: time
+                .timestamp_millis()
+                .try_into()
+                .expect("Time should not precede the epoch."),
+        });
+    }
+
+    /// Returns the records that match the given filter, oldest first.
+    pub fn query(&self, filter: &DroppedTransactionsFilter) -> Vec<DroppedTransaction> {
+        self.records.iter().filter(|record| filter.matches(record)).cloned().collect()
+    }
+}
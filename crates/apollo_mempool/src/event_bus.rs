@@ -0,0 +1,80 @@
+use std::collections::VecDeque;
+
+use apollo_mempool_types::mempool_types::{MempoolEvent, MempoolEventKind};
+use apollo_time::time::DateTime;
+use tokio::sync::broadcast;
+
+use crate::mempool::TransactionReference;
+
+#[cfg(test)]
+#[path = "event_bus_test.rs"]
+pub mod event_bus_test;
+
+/// Publishes the lifecycle events of mempool transactions.
+/// In-process consumers subscribe to a bounded broadcast channel; remote consumers poll the most
+/// recent events, which are retained in a backlog of the same bound.
+pub struct MempoolEventBus {
+    sender: broadcast::Sender<MempoolEvent>,
+    backlog: VecDeque<MempoolEvent>,
+    capacity: usize,
+    next_sequence_number: u64,
+}
+
+impl MempoolEventBus {
+    /// Panics: if `capacity` is zero.
+    pub fn new(capacity: usize) -> Self {
+        let (sender, _receiver) = broadcast::channel(capacity);
+        MempoolEventBus {
+            sender,
+            backlog: VecDeque::with_capacity(capacity),
+            capacity,
+            next_sequence_number: 0,
+        }
+    }
+
+    /// Returns a receiver of all events published from now on. A receiver that falls more than
+    /// `capacity` events behind skips the oldest ones (see `broadcast::error::RecvError::Lagged`).
+    pub fn subscribe(&self) -> broadcast::Receiver<MempoolEvent> {
+        self.sender.subscribe()
+    }
+
+    pub fn publish(
+        &mut self,
+        time: DateTime,
+        tx_reference: &TransactionReference,
+        kind: MempoolEventKind,
+    ) {
+        let event = MempoolEvent {
+            sequence_number: self.next_sequence_number,
+            timestamp: time
+                .timestamp_millis()
+                .try_into()
+                .expect("Time should not precede the epoch."),
+            tx_hash: tx_reference.tx_hash,
+            address: tx_reference.address,
+            nonce: tx_reference.nonce,
+            kind,
+        };
+        self.next_sequence_number += 1;
+
+        if self.backlog.len() == self.capacity {
+            self.backlog.pop_front();
+        }
+        self.backlog.push_back(event.clone());
+
+        // Sending only fails if there are no subscribers, in which case the event is only kept in
+        // the backlog.
+        let _ = self.sender.send(event);
+    }
+
+    /// Returns the retained events whose sequence number is at least the given one, oldest first.
+    pub fn events_from(&self, sequence_number: u64) -> Vec<MempoolEvent> {
+        let Some(oldest_event) = self.backlog.front() else {
+            return Vec::new();
+        };
+
+        let n_skipped = sequence_number.saturating_sub(oldest_event.sequence_number);
+        let n_skipped = usize::try_from(n_skipped).unwrap_or(usize::MAX);
+        self.backlog.iter().skip(n_skipped).cloned().collect()
+    }
+}
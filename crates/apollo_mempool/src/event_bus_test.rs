@@ -0,0 +1,62 @@
+use apollo_mempool_types::mempool_types::{MempoolEvent, MempoolEventKind};
+use apollo_time::time::DateTime;
+use starknet_api::block::GasPrice;
+use starknet_api::transaction::fields::Tip;
+use starknet_api::{contract_address, nonce, tx_hash};
+
+use crate::event_bus::MempoolEventBus;
+use crate::mempool::TransactionReference;
+
+fn tx_reference(tx_hash: u64) -> TransactionReference {
+    TransactionReference {
+        address: contract_address!("0x0"),
+        nonce: nonce!(tx_hash),
+        tx_hash: tx_hash!(tx_hash),
+        tip: Tip::default(),
+        max_l2_gas_price: GasPrice::default(),
+    }
+}
+
+fn publish_added(event_bus: &mut MempoolEventBus, tx_hashes: impl IntoIterator<Item = u64>) {
+    for tx_hash in tx_hashes {
+        event_bus.publish(
+            DateTime::from_timestamp_millis(1_000).unwrap(),
+            &tx_reference(tx_hash),
+            MempoolEventKind::Added,
+        );
+    }
+}
+
+fn sequence_numbers(events: Vec<MempoolEvent>) -> Vec<u64> {
+    events.into_iter().map(|event| event.sequence_number).collect()
+}
+
+#[test]
+fn events_from_returns_retained_events() {
+    let mut event_bus = MempoolEventBus::new(3);
+    publish_added(&mut event_bus, 0..5);
+
+    // Only the last 3 events are retained.
+    assert_eq!(sequence_numbers(event_bus.events_from(0)), vec![2, 3, 4]);
+    assert_eq!(sequence_numbers(event_bus.events_from(3)), vec![3, 4]);
+    assert_eq!(sequence_numbers(event_bus.events_from(5)), Vec::<u64>::new());
+
+    let event = &event_bus.events_from(4)[0];
+    assert_eq!(event.tx_hash, tx_hash!(4));
+    assert_eq!(event.nonce, nonce!(4));
+    assert_eq!(event.timestamp, 1_000);
+}
+
+#[test]
+fn subscribers_receive_published_events() {
+    let mut event_bus = MempoolEventBus::new(3);
+    publish_added(&mut event_bus, [0]);
+
+    let mut receiver = event_bus.subscribe();
+    publish_added(&mut event_bus, [1]);
+
+    // Events published before subscribing are not received.
+    let event = receiver.try_recv().unwrap();
+    assert_eq!(event.sequence_number, 1);
+    assert!(receiver.try_recv().is_err());
+}
@@ -1,7 +1,10 @@
 pub mod communication;
 pub mod config;
+pub(crate) mod dropped_transactions;
+pub mod event_bus;
 pub mod mempool;
 pub mod metrics;
+pub mod persistence;
 pub(crate) mod suspended_transaction_pool;
 pub(crate) mod transaction_pool;
 pub(crate) mod transaction_queue;
@@ -6,25 +6,37 @@ use apollo_mempool_types::mempool_types::{
     AccountState,
     AddTransactionArgs,
     CommitBlockArgs,
+    DroppedTransaction,
+    DroppedTransactionsFilter,
+    MempoolEvent,
+    MempoolEventKind,
     MempoolResult,
     MempoolSnapshot,
     MempoolStateSnapshot,
+    MempoolTransactionStatus,
+    RevertBlockArgs,
+    TransactionDropReason,
+    TransactionEligibility,
 };
 use apollo_time::time::{Clock, DateTime};
 use indexmap::IndexSet;
 use rand::{thread_rng, Rng};
 use starknet_api::block::GasPrice;
 use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::hash::StarkHash;
 use starknet_api::rpc_transaction::{InternalRpcTransaction, InternalRpcTransactionWithoutTxHash};
 use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
 use tracing::{debug, info, instrument, trace};
 
 use crate::config::MempoolConfig;
+use crate::dropped_transactions::DroppedTransactionLog;
+use crate::event_bus::MempoolEventBus;
 use crate::metrics::{
     metric_count_committed_txs,
     metric_count_expired_txs,
     metric_count_rejected_txs,
+    metric_count_restore_results,
     metric_set_get_txs_size,
     MempoolMetricHandle,
     MEMPOOL_DELAYED_DECLARES_SIZE,
@@ -34,6 +46,7 @@ use crate::metrics::{
     MEMPOOL_PRIORITY_QUEUE_SIZE,
     MEMPOOL_TOTAL_SIZE_BYTES,
 };
+use crate::persistence::{JournalRecord, MempoolJournal, PersistedMempool, PersistedTransaction};
 use crate::transaction_pool::TransactionPool;
 use crate::transaction_queue::TransactionQueue;
 use crate::utils::try_increment_nonce;
@@ -100,6 +113,12 @@ impl MempoolState {
             .unwrap_or(incoming_account_nonce)
     }
 
+    /// Returns the last committed nonce for the address. If no value is found for address,
+    /// incoming_account_nonce is returned.
+    fn committed_nonce(&self, address: ContractAddress, incoming_account_nonce: Nonce) -> Nonce {
+        self.committed.get(&address).copied().unwrap_or(incoming_account_nonce)
+    }
+
     fn contains_account(&self, address: ContractAddress) -> bool {
         self.staged.contains_key(&address) || self.committed.contains_key(&address)
     }
@@ -127,8 +146,25 @@ impl MempoolState {
             .copied()
             .collect();
 
-        self.committed.extend(address_to_nonce.clone());
         self.staged.clear();
+        self.record_committed_nonces(address_to_nonce);
+
+        addresses_to_rewind
+    }
+
+    /// Rolls back the committed nonces to the given ones, which precede a reverted block, and
+    /// returns the addresses that were staged, as their proposals are aborted with the revert.
+    fn revert(&mut self, address_to_nonce: AddressToNonce) -> Vec<ContractAddress> {
+        let staged_addresses = self.staged.drain().map(|(address, _)| address).collect();
+        // The rolled back nonces are recorded as a new commit, so that they are retained for the
+        // same number of blocks as committed nonces.
+        self.record_committed_nonces(address_to_nonce);
+
+        staged_addresses
+    }
+
+    fn record_committed_nonces(&mut self, address_to_nonce: AddressToNonce) {
+        self.committed.extend(address_to_nonce.clone());
 
         // Add the commit event to the history.
         // If an old event has been removed (due to history size limit), delete the associated
@@ -143,8 +179,6 @@ impl MempoolState {
                 self.committed.remove(&address);
             }
         }
-
-        addresses_to_rewind
     }
 
     fn validate_incoming_tx(
@@ -223,6 +257,20 @@ impl AddTransactionQueue {
         })
     }
 
+    fn account_txs(
+        &self,
+        contract_address: ContractAddress,
+    ) -> impl Iterator<Item = &InternalRpcTransaction> {
+        self.elements
+            .iter()
+            .map(|(_, tx_args)| &tx_args.tx)
+            .filter(move |tx| tx.contract_address() == contract_address)
+    }
+
+    fn get(&self, tx_hash: TransactionHash) -> Option<&(DateTime, AddTransactionArgs)> {
+        self.elements.iter().find(|(_, args)| args.tx.tx_hash == tx_hash)
+    }
+
     fn len(&self) -> usize {
         self.elements.len()
     }
@@ -245,6 +293,12 @@ pub struct Mempool {
     // therefore candidates for eviction.
     accounts_with_gap: AccountsWithGap,
     state: MempoolState,
+    // Publishes transaction lifecycle events; `None` if disabled.
+    event_bus: Option<MempoolEventBus>,
+    // Recent transactions that were removed without being committed, and why.
+    dropped_txs: DroppedTransactionLog,
+    // Persists the changes to the mempool content; `None` if persistence is disabled.
+    journal: Option<MempoolJournal>,
     clock: Arc<dyn Clock>,
 }
 
@@ -254,13 +308,28 @@ impl Mempool {
             config: config.clone(),
             delayed_declares: AddTransactionQueue::new(),
             tx_pool: TransactionPool::new(clock.clone()),
-            tx_queue: TransactionQueue::default(),
+            tx_queue: TransactionQueue::with_ordering_policy(config.ordering_policy),
             accounts_with_gap: AccountsWithGap::new(),
             state: MempoolState::new(config.committed_nonce_retention_block_count),
+            event_bus: config
+                .event_bus_config
+                .enabled
+                .then(|| MempoolEventBus::new(config.event_bus_config.capacity)),
+            dropped_txs: DroppedTransactionLog::new(config.dropped_txs_capacity),
+            journal: None,
             clock,
         }
     }
 
+    pub fn config(&self) -> &MempoolConfig {
+        &self.config
+    }
+
+    /// Persists all subsequent changes to the mempool content to the given journal.
+    pub fn set_journal(&mut self, journal: MempoolJournal) {
+        self.journal = Some(journal);
+    }
+
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter(&self) -> impl Iterator<Item = &TransactionReference> {
@@ -291,6 +360,7 @@ impl Mempool {
         // Update the mempool state with the given transactions' nonces.
         for tx_reference in &eligible_tx_references {
             self.state.stage(tx_reference)?;
+            self.publish_event(tx_reference, MempoolEventKind::Staged);
         }
 
         let n_returned_txs = eligible_tx_references.len();
@@ -317,6 +387,32 @@ impl Mempool {
             .collect())
     }
 
+    /// Returns up to `n_txs` transactions that are ready for sequencing, without staging them.
+    /// Unlike [Self::get_txs], the transactions of an account are returned together, in nonce
+    /// order, following its queued transaction; the accounts are ordered by the priority of their
+    /// queued transactions.
+    pub fn peek_txs(&self, n_txs: usize) -> Vec<InternalRpcTransaction> {
+        let mut txs = Vec::with_capacity(n_txs);
+        for queued_tx_reference in self.tx_queue.iter_over_ready_txs() {
+            let mut next_tx_reference = Some(*queued_tx_reference);
+            while let Some(tx_reference) = next_tx_reference {
+                if txs.len() == n_txs {
+                    return txs;
+                }
+                txs.push(
+                    self.tx_pool
+                        .get_by_tx_hash(tx_reference.tx_hash)
+                        .expect("Transaction hash from queue must appear in pool.")
+                        .clone(),
+                );
+                let account_state =
+                    AccountState { address: tx_reference.address, nonce: tx_reference.nonce };
+                next_tx_reference = self.tx_pool.get_next_eligible_tx(account_state).ok().flatten();
+            }
+        }
+        txs
+    }
+
     /// Adds a new transaction to the mempool.
     #[instrument(
         skip(self, args),
@@ -339,6 +435,8 @@ impl Mempool {
 
         let tx_reference = TransactionReference::new(&args.tx);
         self.validate_incoming_tx(tx_reference, args.account_state.nonce)?;
+        self.validate_account_limits(&args.tx, args.account_state.nonce)
+            .inspect_err(|err| metric_handle.transaction_rejected(err))?;
         self.handle_fee_escalation(&args.tx)?;
 
         if self.exceeds_capacity(&args.tx) {
@@ -346,6 +444,13 @@ impl Mempool {
         }
 
         metric_handle.transaction_inserted();
+        self.publish_event(&tx_reference, MempoolEventKind::Added);
+        if let Some(journal) = &self.journal {
+            journal.record(JournalRecord::Added(PersistedTransaction::new(
+                args.clone(),
+                self.clock.now(),
+            )));
+        }
 
         // May override a removed queued nonce with the received account nonce or the account's
         // state nonce.
@@ -366,7 +471,11 @@ impl Mempool {
     }
 
     fn insert_to_tx_queue(&mut self, tx_reference: TransactionReference) {
-        self.tx_queue.insert(tx_reference, self.config.validate_resource_bounds);
+        let arrival_order = self
+            .tx_pool
+            .get_arrival_order(tx_reference.tx_hash)
+            .expect("Queued transactions should be in the pool.");
+        self.tx_queue.insert(tx_reference, arrival_order, self.config.validate_resource_bounds);
     }
 
     fn add_tx_inner(&mut self, args: AddTransactionArgs) {
@@ -380,6 +489,15 @@ impl Mempool {
             .insert(tx)
             .expect("Duplicate transactions should cause an error during the validation stage.");
 
+        self.enqueue_if_next_nonce(tx_reference, account_state);
+    }
+
+    /// Inserts the transaction to the queue if its nonce is the account's next nonce.
+    fn enqueue_if_next_nonce(
+        &mut self,
+        tx_reference: TransactionReference,
+        account_state: AccountState,
+    ) {
         let AccountState { address, nonce: incoming_account_nonce } = account_state;
         let account_nonce = self.state.resolve_nonce(address, incoming_account_nonce);
         if tx_reference.nonce == account_nonce {
@@ -409,6 +527,7 @@ impl Mempool {
     #[instrument(skip(self, args))]
     pub fn commit_block(&mut self, args: CommitBlockArgs) {
         let CommitBlockArgs { address_to_nonce, rejected_tx_hashes } = args;
+        self.journal_committed_nonces(&address_to_nonce);
         debug!(
             "Committing block with {} addresses and {} rejected tx to the mempool.",
             address_to_nonce.len(),
@@ -431,8 +550,11 @@ impl Mempool {
             }
 
             // Remove from pool.
-            let n_removed_txs = self.tx_pool.remove_up_to_nonce_when_committed(address, next_nonce);
-            metric_count_committed_txs(n_removed_txs);
+            let removed_txs = self.tx_pool.remove_up_to_nonce_when_committed(address, next_nonce);
+            metric_count_committed_txs(removed_txs.len());
+            for tx_reference in &removed_txs {
+                self.publish_event(tx_reference, MempoolEventKind::Committed);
+            }
 
             // Maybe close nonce gap.
             if self.tx_queue.get_nonce(address).is_none() {
@@ -458,6 +580,9 @@ impl Mempool {
 
         debug!("Aligned mempool to committed nonces.");
 
+        // A new block starts; all accounts get a fresh turn.
+        self.tx_queue.reset_account_turns();
+
         // Remove rejected transactions from the mempool.
         if !rejected_tx_hashes.is_empty() {
             debug!("Removed rejected transactions from mempool: {:?}", rejected_tx_hashes);
@@ -467,6 +592,10 @@ impl Mempool {
         for tx_hash in rejected_tx_hashes {
             if let Ok(tx) = self.tx_pool.remove(tx_hash) {
                 self.tx_queue.remove(tx.contract_address());
+                self.record_dropped_tx(
+                    &TransactionReference::new(&tx),
+                    TransactionDropReason::Rejected,
+                );
                 account_nonce_updates
                     .entry(tx.contract_address())
                     .and_modify(|nonce| *nonce = (*nonce).min(tx.nonce()))
@@ -486,6 +615,75 @@ impl Mempool {
         self.update_accounts_with_gap(account_nonce_updates);
     }
 
+    /// Rolls back the mempool to the state preceding a reverted block: committed nonces are set to
+    /// the given ones, the transactions committed in the block are returned to the mempool, staged
+    /// transactions are returned to the queue, and accounts' queued transactions are aligned to
+    /// their rolled back nonces.
+    #[instrument(skip(self, args))]
+    pub fn revert_block(&mut self, args: RevertBlockArgs) {
+        let RevertBlockArgs { address_to_nonce, reverted_txs } = args;
+        debug!(
+            "Reverting block with {} addresses and {} transactions in the mempool.",
+            address_to_nonce.len(),
+            reverted_txs.len()
+        );
+        self.journal_committed_nonces(&address_to_nonce);
+
+        for tx in reverted_txs {
+            let address = tx.contract_address();
+            let Some(&account_nonce) = address_to_nonce.get(&address) else {
+                continue;
+            };
+            let tx_reference = TransactionReference::new(&tx);
+            // The nonce may have been reused since the transaction was committed.
+            if tx_reference.nonce < account_nonce
+                || self.tx_pool.get_by_address_and_nonce(address, tx_reference.nonce).is_some()
+            {
+                continue;
+            }
+
+            let account_state = AccountState { address, nonce: account_nonce };
+            self.publish_event(&tx_reference, MempoolEventKind::Added);
+            if let Some(journal) = &self.journal {
+                journal.record(JournalRecord::Added(PersistedTransaction::new(
+                    AddTransactionArgs { tx: tx.clone(), account_state },
+                    self.clock.now(),
+                )));
+            }
+            // Queued below, once the account nonces are rolled back.
+            self.tx_pool.insert(tx).expect("Reverted transactions should not be in the pool.");
+        }
+
+        let reverted_addresses: Vec<_> = address_to_nonce.keys().copied().collect();
+        let staged_addresses = self.state.revert(address_to_nonce);
+
+        let mut account_nonce_updates = AddressToNonce::new();
+        for address in reverted_addresses.into_iter().chain(staged_addresses) {
+            // Staged accounts that were not reverted resume from their lowest nonce, as in
+            // `commit_block`.
+            let Some(account_nonce) = self
+                .state
+                .committed
+                .get(&address)
+                .copied()
+                .or_else(|| self.tx_pool.get_lowest_nonce(address))
+            else {
+                continue;
+            };
+
+            self.tx_queue.remove(address);
+            if let Some(tx_reference) =
+                self.tx_pool.get_by_address_and_nonce(address, account_nonce)
+            {
+                self.insert_to_tx_queue(tx_reference);
+            }
+            account_nonce_updates.insert(address, account_nonce);
+        }
+
+        self.update_state_metrics();
+        self.update_accounts_with_gap(account_nonce_updates);
+    }
+
     pub fn account_tx_in_pool_or_recent_block(&self, account_address: ContractAddress) -> bool {
         self.state.contains_account(account_address)
             || self.tx_pool.contains_account(account_address)
@@ -517,6 +715,64 @@ impl Mempool {
         Ok(())
     }
 
+    /// Validates that admitting the given transaction keeps its account within the anti-spam
+    /// limits. A transaction replacing a pooled one (fee escalation) does not add to the account's
+    /// transaction count, and only the difference in size is accounted for.
+    fn validate_account_limits(
+        &self,
+        tx: &InternalRpcTransaction,
+        incoming_account_nonce: Nonce,
+    ) -> MempoolResult<()> {
+        let address = tx.contract_address();
+        let tx_nonce = tx.nonce();
+
+        if let Some(max_nonce_distance) = self.config.max_nonce_distance {
+            let account_nonce = self.state.committed_nonce(address, incoming_account_nonce);
+            if tx_nonce > Nonce(account_nonce.0 + StarkHash::from(max_nonce_distance)) {
+                return Err(MempoolError::NonceDistanceExceeded {
+                    address,
+                    tx_nonce,
+                    account_nonce,
+                    max_nonce_distance,
+                });
+            }
+        }
+
+        let account_txs: Vec<&InternalRpcTransaction> = self
+            .tx_pool
+            .account_txs_sorted_by_nonce(address)
+            .map(|tx_reference| {
+                self.tx_pool
+                    .get_by_tx_hash(tx_reference.tx_hash)
+                    .expect("Transaction from the account index must appear in pool.")
+            })
+            .chain(self.delayed_declares.account_txs(address))
+            .collect();
+        let replaced_tx = account_txs.iter().find(|account_tx| account_tx.nonce() == tx_nonce);
+
+        if let Some(max_txs) = self.config.max_txs_per_account {
+            if replaced_tx.is_none() && account_txs.len() >= max_txs {
+                return Err(MempoolError::AccountTxLimitExceeded { address, max_txs });
+            }
+        }
+
+        // A full share is covered by the global capacity check, which may also evict transactions.
+        if self.config.max_account_capacity_percentage >= 100 {
+            return Ok(());
+        }
+        let account_size_in_bytes = account_txs.iter().map(|tx| tx.total_bytes()).sum::<u64>()
+            - replaced_tx.map_or(0, |tx| tx.total_bytes())
+            + tx.total_bytes();
+        let max_account_size_in_bytes = u128::from(self.config.capacity_in_bytes)
+            * u128::from(self.config.max_account_capacity_percentage)
+            / 100;
+        if u128::from(account_size_in_bytes) > max_account_size_in_bytes {
+            return Err(MempoolError::AccountCapacityExceeded { address });
+        }
+
+        Ok(())
+    }
+
     fn validate_commitment(&self, address: ContractAddress, next_nonce: Nonce) {
         self.state.validate_commitment(address, next_nonce);
     }
@@ -580,6 +836,10 @@ impl Mempool {
         self.tx_pool
             .remove(existing_tx_reference.tx_hash)
             .expect("Transaction hash from pool must exist.");
+        self.record_dropped_tx(
+            &existing_tx_reference,
+            TransactionDropReason::Replaced { replacement_tx_hash: incoming_tx_reference.tx_hash },
+        );
 
         Ok(())
     }
@@ -620,6 +880,9 @@ impl Mempool {
         let removed_txs =
             self.tx_pool.remove_txs_older_than(self.config.transaction_ttl, &self.state.staged);
         let queued_txs = self.tx_queue.remove_txs(&removed_txs);
+        for tx_reference in &removed_txs {
+            self.record_dropped_tx(tx_reference, TransactionDropReason::Expired);
+        }
 
         metric_count_expired_txs(removed_txs.len());
         self.update_state_metrics();
@@ -655,6 +918,7 @@ impl Mempool {
                 self.tx_pool
                     .remove(tx.tx_hash)
                     .expect("Transaction hash from queue must appear in pool.");
+                self.record_dropped_tx(&tx, TransactionDropReason::Expired);
                 (tx.address, self.state.resolve_nonce(tx.address, tx.nonce))
             })
             .collect();
@@ -676,6 +940,222 @@ impl Mempool {
         })
     }
 
+    /// Returns the transaction with the given hash, including delayed declares.
+    pub fn get_tx(&self, tx_hash: TransactionHash) -> MempoolResult<InternalRpcTransaction> {
+        if let Some((_, args)) = self.delayed_declares.get(tx_hash) {
+            return Ok(args.tx.clone());
+        }
+
+        self.tx_pool.get_by_tx_hash(tx_hash).cloned()
+    }
+
+    /// Returns a receiver of the transaction lifecycle events published from now on, if events are
+    /// enabled.
+    pub fn subscribe_to_events(&self) -> Option<tokio::sync::broadcast::Receiver<MempoolEvent>> {
+        self.event_bus.as_ref().map(MempoolEventBus::subscribe)
+    }
+
+    /// Returns the retained transaction lifecycle events whose sequence number is at least the
+    /// given one; empty if events are disabled.
+    pub fn events_from(&self, sequence_number: u64) -> Vec<MempoolEvent> {
+        self.event_bus
+            .as_ref()
+            .map(|event_bus| event_bus.events_from(sequence_number))
+            .unwrap_or_default()
+    }
+
+    fn publish_event(&mut self, tx_reference: &TransactionReference, kind: MempoolEventKind) {
+        if let Some(journal) = &self.journal {
+            match kind {
+                MempoolEventKind::Added | MempoolEventKind::Staged => {}
+                // All other events remove the transaction from the mempool.
+                _ => journal.record(JournalRecord::Removed(tx_reference.tx_hash)),
+            }
+        }
+        if let Some(event_bus) = &mut self.event_bus {
+            event_bus.publish(self.clock.now(), tx_reference, kind);
+        }
+    }
+
+    fn journal_committed_nonces(&self, address_to_nonce: &AddressToNonce) {
+        if let Some(journal) = &self.journal {
+            journal.record(JournalRecord::CommittedNonces(address_to_nonce.clone()));
+        }
+    }
+
+    /// Returns the records of recently dropped transactions that match the given filter, oldest
+    /// first.
+    pub fn dropped_txs(&self, filter: &DroppedTransactionsFilter) -> Vec<DroppedTransaction> {
+        self.dropped_txs.query(filter)
+    }
+
+    /// Records that the given transaction was removed from the mempool without being committed.
+    fn record_dropped_tx(
+        &mut self,
+        tx_reference: &TransactionReference,
+        reason: TransactionDropReason,
+    ) {
+        self.dropped_txs.record(self.clock.now(), tx_reference, reason.clone());
+        self.publish_event(tx_reference, reason.into());
+    }
+
+    /// Returns the status of the transaction with the given hash: when it was received and whether
+    /// it can currently be sequenced.
+    pub fn tx_status(&self, tx_hash: TransactionHash) -> MempoolResult<MempoolTransactionStatus> {
+        let (submission_time, eligibility) = match self.delayed_declares.get(tx_hash) {
+            Some((submission_time, args)) => {
+                let pool_position = self.pool_position(&TransactionReference::new(&args.tx));
+                (*submission_time, TransactionEligibility::DeclareDelay { pool_position })
+            }
+            None => {
+                let tx_reference = TransactionReference::new(self.tx_pool.get_by_tx_hash(tx_hash)?);
+                (self.tx_pool.get_submission_time(tx_hash)?, self.tx_eligibility(&tx_reference))
+            }
+        };
+
+        Ok(MempoolTransactionStatus {
+            tx_hash,
+            submission_time: submission_time
+                .timestamp()
+                .try_into()
+                .expect("Submission time should not precede the unix epoch."),
+            eligibility,
+        })
+    }
+
+    fn tx_eligibility(&self, tx_reference: &TransactionReference) -> TransactionEligibility {
+        if let Some(eligibility) = self.tx_queue.queued_tx_eligibility(tx_reference) {
+            return eligibility;
+        }
+
+        let TransactionReference { address, nonce, .. } = *tx_reference;
+        if self.state.staged.get(&address).is_some_and(|&next_nonce| nonce < next_nonce) {
+            TransactionEligibility::Staged
+        } else if self.accounts_with_gap.contains(&address) {
+            TransactionEligibility::NonceGap { pool_position: self.pool_position(tx_reference) }
+        } else {
+            TransactionEligibility::AwaitingPreviousNonce {
+                pool_position: self.pool_position(tx_reference),
+            }
+        }
+    }
+
+    /// Returns the number of pooled transactions of the same account with lower nonces than the
+    /// given transaction.
+    fn pool_position(&self, tx_reference: &TransactionReference) -> usize {
+        self.tx_pool
+            .account_txs_sorted_by_nonce(tx_reference.address)
+            .take_while(|pooled_tx| pooled_tx.nonce < tx_reference.nonce)
+            .count()
+    }
+
+    /// Returns the mempool content in a form that can be stored on disk and later restored with
+    /// `restore`.
+    pub fn persisted_content(&self) -> PersistedMempool {
+        let pooled_txs = self.tx_pool.chronological_txs_hashes().into_iter().map(|tx_hash| {
+            let tx = self
+                .tx_pool
+                .get_by_tx_hash(tx_hash)
+                .expect("Transaction hash from pool must exist.")
+                .clone();
+            let submission_time =
+                self.tx_pool.get_submission_time(tx_hash).expect("Transaction must be in pool.");
+            let account_state = AccountState {
+                address: tx.contract_address(),
+                nonce: self.persisted_account_nonce(tx.contract_address()),
+            };
+            PersistedTransaction::new(AddTransactionArgs { tx, account_state }, submission_time)
+        });
+        let delayed_declares =
+            self.delayed_declares.elements.iter().map(|(submission_time, args)| {
+                PersistedTransaction::new(args.clone(), *submission_time)
+            });
+
+        let mut transactions: Vec<_> = pooled_txs.chain(delayed_declares).collect();
+        transactions.sort_by_key(|tx| tx.submission_time());
+
+        PersistedMempool { transactions, mempool_state: self.state.state_snapshot() }
+    }
+
+    /// Returns the account nonce to store alongside the transactions of the given account.
+    /// Staged nonces are not restored, since the proposal that staged them does not survive a
+    /// restart; for such accounts the lowest pooled nonce is the account nonce.
+    /// Accounts with a nonce gap and no committed nonce are stored with the default nonce, which
+    /// keeps their transactions out of the queue until the gap is closed.
+    fn persisted_account_nonce(&self, address: ContractAddress) -> Nonce {
+        if self.state.staged.contains_key(&address) {
+            return self
+                .tx_pool
+                .get_lowest_nonce(address)
+                .expect("Staged account must have transactions in the pool.");
+        }
+
+        self.tx_queue
+            .get_nonce(address)
+            .or_else(|| self.state.committed.get(&address).copied())
+            .unwrap_or_default()
+    }
+
+    /// Re-inserts previously persisted content into an empty mempool.
+    /// Transactions are re-validated against the committed nonces, where the given latest nonces
+    /// take precedence over the persisted ones, as blocks may have been committed since the content
+    /// was persisted. Transactions whose TTL has passed, that are no longer valid, or that do not
+    /// fit in the mempool are dropped.
+    pub fn restore(
+        &mut self,
+        persisted_mempool: PersistedMempool,
+        latest_nonces: HashMap<ContractAddress, Nonce>,
+    ) {
+        let PersistedMempool { transactions, mempool_state } = persisted_mempool;
+        let mut committed_nonces = mempool_state.committed;
+        committed_nonces.extend(latest_nonces);
+        self.state.commit(committed_nonces);
+
+        let submission_cutoff_time = self.clock.now() - self.config.transaction_ttl;
+        let mut account_nonce_updates = AddressToNonce::new();
+        let (mut n_restored_txs, mut n_dropped_txs) = (0, 0);
+        for persisted_tx in transactions {
+            let submission_time = persisted_tx.submission_time();
+            let args = persisted_tx.args;
+            let tx_reference = TransactionReference::new(&args.tx);
+
+            let is_valid = submission_time >= submission_cutoff_time
+                && self.validate_incoming_tx(tx_reference, args.account_state.nonce).is_ok()
+                && self.validate_no_delayed_declare_front_run(tx_reference).is_ok()
+                && self
+                    .tx_pool
+                    .get_by_address_and_nonce(tx_reference.address, tx_reference.nonce)
+                    .is_none()
+                && !self.exceeds_capacity(&args.tx);
+            if !is_valid {
+                debug!("Dropped {tx_reference} while restoring the mempool.");
+                n_dropped_txs += 1;
+                continue;
+            }
+
+            account_nonce_updates.insert(
+                args.account_state.address,
+                self.state.resolve_nonce(args.account_state.address, args.account_state.nonce),
+            );
+            if let InternalRpcTransactionWithoutTxHash::Declare(_) = &args.tx.tx {
+                self.delayed_declares.push_back(submission_time, args);
+            } else {
+                let AddTransactionArgs { tx, account_state } = args;
+                self.tx_pool
+                    .insert_with_submission_time(tx, submission_time)
+                    .expect("Duplicate transactions should have been dropped.");
+                self.enqueue_if_next_nonce(tx_reference, account_state);
+            }
+            n_restored_txs += 1;
+        }
+        self.add_ready_declares();
+
+        info!("Restored {n_restored_txs} transactions, dropped {n_dropped_txs} transactions.");
+        metric_count_restore_results(n_restored_txs, n_dropped_txs);
+        self.update_state_metrics();
+        self.update_accounts_with_gap(account_nonce_updates);
+    }
+
     fn size_in_bytes(&self) -> u64 {
         self.tx_pool.size_in_bytes() + self.delayed_declares.size_in_bytes()
     }
@@ -736,6 +1216,7 @@ impl Mempool {
                     .expect("Transaction must exist in the pool.");
                 total_space_freed += tx.total_bytes();
                 MEMPOOL_EVICTIONS_COUNT.increment(1);
+                self.record_dropped_tx(tx_ref, TransactionDropReason::Evicted);
                 if total_space_freed >= required_space {
                     break;
                 }
@@ -3,32 +3,51 @@ use std::sync::Arc;
 use std::time::Duration;
 
 use apollo_infra::component_client::ClientError;
+use apollo_infra::component_definitions::{ComponentRequestHandler, ComponentStarter};
 use apollo_mempool_p2p_types::communication::{
     MempoolP2pPropagatorClientError,
     MockMempoolP2pPropagatorClient,
 };
-use apollo_mempool_types::communication::AddTransactionArgsWrapper;
+use apollo_mempool_types::communication::{
+    AddTransactionArgsWrapper,
+    MempoolRequest,
+    MempoolResponse,
+};
 use apollo_mempool_types::errors::MempoolError;
-use apollo_mempool_types::mempool_types::{AccountState, AddTransactionArgs};
+use apollo_mempool_types::mempool_types::{
+    AccountState,
+    AddTransactionArgs,
+    DroppedTransactionsFilter,
+    MempoolEventKind,
+    RevertBlockArgs,
+    TransactionDropReason,
+    TransactionEligibility,
+};
 use apollo_metrics::metrics::HistogramValue;
 use apollo_network_types::network_types::BroadcastedMessageMetadata;
+use apollo_state_sync_types::communication::{MockStateSyncClient, StateSyncClientError};
+use apollo_state_sync_types::errors::StateSyncError;
 use apollo_test_utils::{get_rng, GetTestInstance};
 use apollo_time::test_utils::FakeClock;
+use apollo_time::time::Clock;
 use mempool_test_utils::starknet_api_test_utils::test_valid_resource_bounds;
 use metrics_exporter_prometheus::PrometheusBuilder;
 use mockall::predicate::eq;
 use pretty_assertions::assert_eq;
 use rstest::{fixture, rstest};
-use starknet_api::block::GasPrice;
+use starknet_api::block::{BlockNumber, GasPrice};
 use starknet_api::rpc_transaction::InternalRpcTransaction;
 use starknet_api::test_utils::declare::{internal_rpc_declare_tx, DeclareTxArgs};
 use starknet_api::test_utils::invoke::internal_invoke_tx;
 use starknet_api::transaction::fields::TransactionSignature;
 use starknet_api::transaction::TransactionHash;
 use starknet_api::{contract_address, declare_tx_args, felt, invoke_tx_args, nonce, tx_hash};
+use tempfile::tempdir;
 
 use super::AddTransactionQueue;
 use crate::communication::MempoolCommunicationWrapper;
+use crate::config::{MempoolEventBusConfig, MempoolPersistenceConfig, TransactionOrderingPolicy};
+use crate::dropped_transactions::DroppedTransactionLog;
 use crate::mempool::{
     AccountsWithGap,
     Mempool,
@@ -37,7 +56,14 @@ use crate::mempool::{
     MempoolState,
     TransactionReference,
 };
-use crate::metrics::register_metrics;
+use crate::metrics::{
+    register_metrics,
+    DropReason,
+    LABEL_NAME_DROP_REASON,
+    MEMPOOL_TRANSACTIONS_DROPPED,
+    MEMPOOL_TRANSACTIONS_RESTORED,
+};
+use crate::persistence::{load_journal, MempoolJournal};
 use crate::test_utils::{
     add_tx,
     add_tx_expect_error,
@@ -147,6 +173,9 @@ impl MempoolTestContentBuilder {
             ),
             accounts_with_gap: AccountsWithGap::new(),
             state: MempoolState::new(self.config.committed_nonce_retention_block_count),
+            event_bus: None,
+            dropped_txs: DroppedTransactionLog::new(self.config.dropped_txs_capacity),
+            journal: None,
             clock: Arc::new(FakeClock::default()),
         }
     }
@@ -406,6 +435,38 @@ fn test_get_txs_with_nonce_gap() {
     expected_mempool_content.assert_eq(&mempool.content());
 }
 
+// `peek_txs` tests.
+
+#[rstest]
+fn test_peek_txs_does_not_stage_txs() {
+    // Setup.
+    let tx_address_0_nonce_0 = tx!(tx_hash: 1, address: "0x0", tx_nonce: 0, tip: 20);
+    let tx_address_0_nonce_1 = tx!(tx_hash: 2, address: "0x0", tx_nonce: 1, tip: 20);
+    let tx_address_1_nonce_0 = tx!(tx_hash: 3, address: "0x1", tx_nonce: 0, tip: 30);
+
+    let queue_txs = [&tx_address_0_nonce_0, &tx_address_1_nonce_0].map(TransactionReference::new);
+    let pool_txs =
+        [&tx_address_0_nonce_0, &tx_address_0_nonce_1, &tx_address_1_nonce_0].map(|tx| tx.clone());
+    let mut mempool = MempoolTestContentBuilder::new()
+        .with_pool(pool_txs)
+        .with_priority_queue(queue_txs)
+        .build_full_mempool();
+
+    // Test and assert: an account's transactions are returned together.
+    assert_eq!(
+        mempool.peek_txs(3),
+        [tx_address_1_nonce_0.clone(), tx_address_0_nonce_0.clone(), tx_address_0_nonce_1.clone()]
+    );
+    assert_eq!(mempool.peek_txs(2), [tx_address_1_nonce_0.clone(), tx_address_0_nonce_0.clone()]);
+
+    // Assert: the peeked transactions are still returned by `get_txs`.
+    get_txs_and_assert_expected(
+        &mut mempool,
+        3,
+        &[tx_address_1_nonce_0, tx_address_0_nonce_0, tx_address_0_nonce_1],
+    );
+}
+
 // `add_tx` tests.
 
 #[rstest]
@@ -646,6 +707,132 @@ fn add_tx_exceeds_capacity() {
     add_tx_expect_error(&mut mempool, &input_declare, MempoolError::MempoolFull);
 }
 
+/// Adds two transactions of the same account, and asserts that a third one is rejected by one of
+/// the account limits set in the given config.
+fn assert_third_account_tx_rejected(
+    config: MempoolConfig,
+    expected_error: MempoolError,
+    expected_drop_reason: DropReason,
+) {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let _recorder_guard = metrics::set_default_local_recorder(&recorder);
+    register_metrics();
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+
+    for input in [
+        add_tx_input!(tx_hash: 0, address: "0x0", tx_nonce: 0, account_nonce: 0),
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 1, account_nonce: 0),
+    ] {
+        add_tx(&mut mempool, &input);
+    }
+
+    let input_tx = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 2, account_nonce: 0);
+    add_tx_expect_error(&mut mempool, &input_tx, expected_error);
+
+    // Other accounts are not affected.
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0));
+
+    MEMPOOL_TRANSACTIONS_DROPPED.assert_eq(
+        &recorder.handle().render(),
+        1,
+        &[(LABEL_NAME_DROP_REASON, expected_drop_reason.into())],
+    );
+}
+
+#[rstest]
+fn add_tx_exceeds_account_tx_limit() {
+    assert_third_account_tx_rejected(
+        MempoolConfig { max_txs_per_account: Some(2), ..Default::default() },
+        MempoolError::AccountTxLimitExceeded { address: contract_address!("0x0"), max_txs: 2 },
+        DropReason::AccountTxLimitExceeded,
+    );
+}
+
+#[rstest]
+fn add_tx_exceeds_nonce_distance() {
+    assert_third_account_tx_rejected(
+        MempoolConfig { max_nonce_distance: Some(1), ..Default::default() },
+        MempoolError::NonceDistanceExceeded {
+            address: contract_address!("0x0"),
+            tx_nonce: nonce!(2),
+            account_nonce: nonce!(0),
+            max_nonce_distance: 1,
+        },
+        DropReason::NonceDistanceExceeded,
+    );
+}
+
+#[rstest]
+fn add_tx_exceeds_account_capacity() {
+    // All transactions added by the test are of the same size.
+    let tx_size =
+        add_tx_input!(tx_hash: 0, address: "0x0", tx_nonce: 0, account_nonce: 0).tx.total_bytes();
+    assert_third_account_tx_rejected(
+        MempoolConfig {
+            capacity_in_bytes: 10 * tx_size,
+            max_account_capacity_percentage: 20,
+            ..Default::default()
+        },
+        MempoolError::AccountCapacityExceeded { address: contract_address!("0x0") },
+        DropReason::AccountCapacityExceeded,
+    );
+}
+
+#[rstest]
+fn account_limits_are_disabled_by_default() {
+    let mut mempool = Mempool::new(MempoolConfig::default(), Arc::new(FakeClock::default()));
+
+    // Far more transactions, with far larger nonce gaps, than any sensible limit.
+    for tx_nonce in (0..1000).step_by(4) {
+        add_tx(
+            &mut mempool,
+            &add_tx_input!(tx_hash: tx_nonce, address: "0x0", tx_nonce: tx_nonce, account_nonce: 0),
+        );
+    }
+    assert_eq!(mempool.tx_pool.account_txs_sorted_by_nonce(contract_address!("0x0")).count(), 250);
+}
+
+#[rstest]
+fn nonce_distance_is_measured_from_committed_nonce() {
+    let mut mempool = Mempool::new(
+        MempoolConfig { max_nonce_distance: Some(2), ..Default::default() },
+        Arc::new(FakeClock::default()),
+    );
+    commit_block(&mut mempool, [("0x0", 3)], []);
+
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 5, account_nonce: 0));
+    add_tx_expect_error(
+        &mut mempool,
+        &add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 6, account_nonce: 0),
+        MempoolError::NonceDistanceExceeded {
+            address: contract_address!("0x0"),
+            tx_nonce: nonce!(6),
+            account_nonce: nonce!(3),
+            max_nonce_distance: 2,
+        },
+    );
+}
+
+#[rstest]
+fn fee_escalation_is_not_limited_by_account_tx_limit() {
+    let mut mempool = Mempool::new(
+        MempoolConfig {
+            max_txs_per_account: Some(1),
+            enable_fee_escalation: true,
+            fee_escalation_percentage: 10,
+            ..Default::default()
+        },
+        Arc::new(FakeClock::default()),
+    );
+    let input_tx =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, tip: 100, max_l2_gas_price: 100);
+    add_tx(&mut mempool, &input_tx);
+
+    let replacement_tx =
+        add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 0, tip: 110, max_l2_gas_price: 110);
+    add_tx(&mut mempool, &replacement_tx);
+}
+
 // `commit_block` tests.
 
 #[rstest]
@@ -944,8 +1131,11 @@ async fn test_new_tx_sent_to_p2p(mempool: Mempool) {
         .times(1)
         .with(eq(tx_args.tx))
         .returning(|_| Ok(()));
-    let mut mempool_wrapper =
-        MempoolCommunicationWrapper::new(mempool, Arc::new(mock_mempool_p2p_propagator_client));
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(mock_mempool_p2p_propagator_client),
+        Arc::new(MockStateSyncClient::new()),
+    );
 
     mempool_wrapper.add_tx(propagateor_args).await.unwrap();
 }
@@ -968,8 +1158,11 @@ async fn test_propagated_tx_sent_to_p2p(mempool: Mempool) {
         .with(eq(expected_message_metadata.clone()))
         .returning(|_| Ok(()));
 
-    let mut mempool_wrapper =
-        MempoolCommunicationWrapper::new(mempool, Arc::new(mock_mempool_p2p_propagator_client));
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(mock_mempool_p2p_propagator_client),
+        Arc::new(MockStateSyncClient::new()),
+    );
 
     mempool_wrapper.add_tx(propagated_args).await.unwrap();
 }
@@ -1241,6 +1434,11 @@ fn metrics_correctness() {
         txs_dropped_expired: 1,
         txs_dropped_failed_add_tx_checks: 1,
         txs_dropped_rejected: 1,
+        txs_dropped_failed_restore: 0,
+        txs_dropped_account_tx_limit: 0,
+        txs_dropped_nonce_distance: 0,
+        txs_dropped_account_capacity: 0,
+        txs_restored: 0,
         pool_size: 4,
         priority_queue_size: 3,
         pending_queue_size: 1,
@@ -1402,6 +1600,94 @@ fn committed_account_nonce_cleanup() {
     add_tx(&mut mempool, &input_tx);
 }
 
+#[rstest]
+fn revert_block_returns_committed_and_staged_txs(mut mempool: Mempool) {
+    // Setup: commit a block including the transaction of 0x0, while a transaction of 0x1 is staged
+    // for the next block.
+    let committed_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &committed_tx);
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&committed_tx.tx));
+    commit_block(&mut mempool, [("0x0", 1)], []);
+
+    let staged_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &staged_tx);
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&staged_tx.tx));
+
+    // Test: revert the committed block.
+    mempool.revert_block(RevertBlockArgs {
+        address_to_nonce: HashMap::from([(contract_address!("0x0"), nonce!(0))]),
+        reverted_txs: vec![committed_tx.tx.clone()],
+    });
+
+    // Assert: both the committed and the staged transactions are back in the queue.
+    let expected_queue_txs = [&staged_tx.tx, &committed_tx.tx].map(TransactionReference::new);
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([committed_tx.tx, staged_tx.tx])
+        .with_priority_queue(expected_queue_txs)
+        .build();
+    expected_mempool_content.assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn reverted_tx_can_be_proposed_again(mut mempool: Mempool) {
+    let tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &tx);
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&tx.tx));
+    commit_block(&mut mempool, [("0x0", 1)], []);
+    get_txs_and_assert_expected(&mut mempool, 1, &[]);
+
+    mempool.revert_block(RevertBlockArgs {
+        address_to_nonce: HashMap::from([(contract_address!("0x0"), nonce!(0))]),
+        reverted_txs: vec![tx.tx.clone()],
+    });
+
+    // The reverted transaction is proposed again, and committed in the new block.
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&tx.tx));
+    commit_block(&mut mempool, [("0x0", 1)], []);
+    MempoolTestContentBuilder::new().with_pool([]).build().assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn revert_block_returns_txs_unknown_to_the_mempool(mut mempool: Mempool) {
+    // Setup: commit a block proposed by another node, whose transaction was never added to this
+    // mempool.
+    let tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    commit_block(&mut mempool, [("0x0", 1)], []);
+
+    // Test: revert the committed block.
+    mempool.revert_block(RevertBlockArgs {
+        address_to_nonce: HashMap::from([(contract_address!("0x0"), nonce!(0))]),
+        reverted_txs: vec![tx.tx.clone()],
+    });
+
+    // Assert: the transaction of the reverted block is added to the mempool.
+    MempoolTestContentBuilder::new()
+        .with_pool([tx.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&tx.tx)])
+        .build()
+        .assert_eq(&mempool.content());
+}
+
+#[rstest]
+fn rewound_tx_keeps_its_fifo_position() {
+    let config =
+        MempoolConfig { ordering_policy: TransactionOrderingPolicy::Fifo, ..Default::default() };
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+    // Tips are increasing, so that only the FIFO policy sequences the first transaction first.
+    let [first_tx, second_tx, third_tx] = [(1, "0x0", 10), (2, "0x1", 20), (3, "0x2", 30)]
+        .map(|(tx_hash, address, tip)| add_tx_input!(tx_hash: tx_hash, address: address, tip: tip));
+    add_tx(&mut mempool, &first_tx);
+    add_tx(&mut mempool, &second_tx);
+    get_txs_and_assert_expected(&mut mempool, 1, std::slice::from_ref(&first_tx.tx));
+    add_tx(&mut mempool, &third_tx);
+
+    // Test: the block is committed without the proposed transaction, which is rewound.
+    commit_block(&mut mempool, [], []);
+
+    // Assert: the rewound transaction is still sequenced before later arrivals.
+    get_txs_and_assert_expected(&mut mempool, 3, &[first_tx.tx, second_tx.tx, third_tx.tx]);
+}
+
 #[rstest]
 fn test_get_mempool_snapshot() {
     // Create a mempool with a fake clock.
@@ -1425,6 +1711,370 @@ fn test_get_mempool_snapshot() {
     assert_eq!(mempool_snapshot.transactions, expected_chronological_hashes);
 }
 
+#[rstest]
+fn test_get_tx_status() {
+    // Setup.
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(MempoolConfig::default(), fake_clock.clone());
+    mempool.update_gas_price(GasPrice(100));
+
+    let staged_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 30, max_l2_gas_price: 100);
+    let first_ready_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 20, max_l2_gas_price: 100);
+    let second_ready_tx = add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let awaiting_tx = add_tx_input!(tx_hash: 4, address: "0x2", tx_nonce: 1, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let pending_tx = add_tx_input!(tx_hash: 5, address: "0x3", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 99);
+    let tx_with_gap = add_tx_input!(tx_hash: 6, address: "0x4", tx_nonce: 2, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let second_tx_with_gap = add_tx_input!(tx_hash: 9, address: "0x4", tx_nonce: 3, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let declare = declare_add_tx_input(
+        declare_tx_args!(resource_bounds: test_valid_resource_bounds(), sender_address: contract_address!("0x5"), tx_hash: tx_hash!(7)),
+    );
+    for input in [
+        &staged_tx,
+        &first_ready_tx,
+        &second_ready_tx,
+        &awaiting_tx,
+        &pending_tx,
+        &tx_with_gap,
+        &second_tx_with_gap,
+    ] {
+        add_tx(&mut mempool, input);
+    }
+    fake_clock.advance(Duration::from_secs(1));
+    add_tx(&mut mempool, &declare);
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![staged_tx.tx.clone()]);
+
+    // Test and assert.
+    let expected_eligibilities = [
+        (&staged_tx, TransactionEligibility::Staged),
+        (&first_ready_tx, TransactionEligibility::Ready { queue_position: 0 }),
+        (&second_ready_tx, TransactionEligibility::Ready { queue_position: 1 }),
+        (&awaiting_tx, TransactionEligibility::AwaitingPreviousNonce { pool_position: 1 }),
+        (
+            &pending_tx,
+            TransactionEligibility::GasPriceBelowThreshold {
+                queue_position: 0,
+                gas_price_threshold: GasPrice(100),
+            },
+        ),
+        (&tx_with_gap, TransactionEligibility::NonceGap { pool_position: 0 }),
+        (&second_tx_with_gap, TransactionEligibility::NonceGap { pool_position: 1 }),
+        (&declare, TransactionEligibility::DeclareDelay { pool_position: 0 }),
+    ];
+    for (input, expected_eligibility) in expected_eligibilities {
+        let tx_hash = input.tx.tx_hash;
+        let status = mempool.tx_status(tx_hash).unwrap();
+        assert_eq!(status.eligibility, expected_eligibility, "Unexpected status of {tx_hash}.");
+        assert_eq!(mempool.get_tx(tx_hash).unwrap(), input.tx);
+    }
+
+    let declare_status = mempool.tx_status(declare.tx.tx_hash).unwrap();
+    let staged_tx_status = mempool.tx_status(staged_tx.tx.tx_hash).unwrap();
+    assert_eq!(
+        declare_status.submission_time,
+        u64::try_from(fake_clock.now().timestamp()).unwrap()
+    );
+    assert_eq!(declare_status.submission_time - staged_tx_status.submission_time, 1);
+
+    let unknown_tx_hash = tx_hash!(8);
+    let expected_error = MempoolError::TransactionNotFound { tx_hash: unknown_tx_hash };
+    assert_eq!(mempool.tx_status(unknown_tx_hash), Err(expected_error.clone()));
+    assert_eq!(mempool.get_tx(unknown_tx_hash), Err(expected_error));
+}
+
+#[rstest]
+fn test_mempool_events() {
+    // Setup.
+    let fake_clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig {
+        event_bus_config: MempoolEventBusConfig { enabled: true, ..Default::default() },
+        ..Default::default()
+    };
+    let transaction_ttl = config.transaction_ttl;
+    let mut mempool = Mempool::new(config, fake_clock.clone());
+    let mut receiver = mempool.subscribe_to_events().unwrap();
+
+    let tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let replacement_tx = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 20, max_l2_gas_price: 200);
+    let rejected_tx = add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let expired_tx = add_tx_input!(tx_hash: 4, address: "0x2", tx_nonce: 1, account_nonce: 0);
+    let late_tx = add_tx_input!(tx_hash: 5, address: "0x3", tx_nonce: 0, account_nonce: 0);
+
+    // Test.
+    for input in [&tx, &replacement_tx, &rejected_tx, &expired_tx] {
+        add_tx(&mut mempool, input);
+    }
+    mempool.get_txs(2).unwrap();
+    commit_block(&mut mempool, [("0x0", 1)], [tx_hash!(3)]);
+    fake_clock.advance(transaction_ttl + Duration::from_secs(1));
+    add_tx(&mut mempool, &late_tx);
+
+    // Assert.
+    let expected_events = [
+        (1, MempoolEventKind::Added),
+        (1, MempoolEventKind::Replaced { replacement_tx_hash: tx_hash!(2) }),
+        (2, MempoolEventKind::Added),
+        (3, MempoolEventKind::Added),
+        (4, MempoolEventKind::Added),
+        (2, MempoolEventKind::Staged),
+        (3, MempoolEventKind::Staged),
+        (2, MempoolEventKind::Committed),
+        (3, MempoolEventKind::Rejected),
+        (4, MempoolEventKind::Expired),
+        (5, MempoolEventKind::Added),
+    ]
+    .map(|(tx_hash, kind)| (tx_hash!(tx_hash), kind));
+
+    let events = mempool.events_from(0);
+    let event_kinds: Vec<_> =
+        events.iter().map(|event| (event.tx_hash, event.kind.clone())).collect();
+    assert_eq!(event_kinds, expected_events);
+    for (sequence_number, event) in events.iter().enumerate() {
+        assert_eq!(event.sequence_number, u64::try_from(sequence_number).unwrap());
+        assert_eq!(receiver.try_recv().unwrap(), *event);
+    }
+    assert_eq!(mempool.events_from(10), events[10..]);
+}
+
+#[rstest]
+#[case::all_records(DroppedTransactionsFilter::default(), vec![1, 3, 4])]
+#[case::by_tx_hash(DroppedTransactionsFilter { tx_hash: Some(tx_hash!(3)), address: None }, vec![3])]
+#[case::by_address(
+    DroppedTransactionsFilter { tx_hash: None, address: Some(contract_address!("0x2")) },
+    vec![4]
+)]
+#[case::no_match(DroppedTransactionsFilter { tx_hash: Some(tx_hash!(2)), address: None }, vec![])]
+fn test_dropped_txs(
+    #[case] filter: DroppedTransactionsFilter,
+    #[case] expected_tx_hashes: Vec<u8>,
+) {
+    // Setup.
+    let fake_clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig::default();
+    let transaction_ttl = config.transaction_ttl;
+    let mut mempool = Mempool::new(config, fake_clock.clone());
+
+    let tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 10, max_l2_gas_price: 100);
+    let replacement_tx = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 20, max_l2_gas_price: 200);
+    let rejected_tx = add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    let expired_tx = add_tx_input!(tx_hash: 4, address: "0x2", tx_nonce: 1, account_nonce: 0);
+
+    // Test.
+    for input in [&tx, &replacement_tx, &rejected_tx, &expired_tx] {
+        add_tx(&mut mempool, input);
+    }
+    mempool.get_txs(2).unwrap();
+    commit_block(&mut mempool, [("0x0", 1)], [tx_hash!(3)]);
+    fake_clock.advance(transaction_ttl + Duration::from_secs(1));
+    let expiration_time = u64::try_from(fake_clock.now().timestamp_millis()).unwrap();
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 5, address: "0x3", tx_nonce: 0, account_nonce: 0));
+
+    // Assert.
+    let dropped_txs = mempool.dropped_txs(&filter);
+    let dropped_tx_hashes: Vec<_> = dropped_txs.iter().map(|record| record.tx_hash).collect();
+    let expected_tx_hashes: Vec<_> =
+        expected_tx_hashes.into_iter().map(|tx_hash| tx_hash!(tx_hash)).collect();
+    assert_eq!(dropped_tx_hashes, expected_tx_hashes);
+
+    for record in dropped_txs {
+        let expected_reason = match record.tx_hash {
+            tx_hash if tx_hash == tx.tx.tx_hash => {
+                TransactionDropReason::Replaced { replacement_tx_hash: tx_hash!(2) }
+            }
+            tx_hash if tx_hash == rejected_tx.tx.tx_hash => TransactionDropReason::Rejected,
+            _ => {
+                assert_eq!(record.timestamp, expiration_time);
+                TransactionDropReason::Expired
+            }
+        };
+        assert_eq!(record.reason, expected_reason);
+    }
+}
+
+#[rstest]
+fn dropped_txs_are_bounded() {
+    let config = MempoolConfig { dropped_txs_capacity: 1, ..Default::default() };
+    let mut mempool = Mempool::new(config, Arc::new(FakeClock::default()));
+    for (tx_hash, tip, max_l2_gas_price) in [(1, 10, 100), (2, 20, 200), (3, 30, 300)] {
+        let input = add_tx_input!(tx_hash: tx_hash, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: tip, max_l2_gas_price: max_l2_gas_price);
+        add_tx(&mut mempool, &input);
+    }
+
+    // Only the latest replacement is kept.
+    let dropped_txs = mempool.dropped_txs(&DroppedTransactionsFilter::default());
+    assert_eq!(dropped_txs.len(), 1);
+    assert_eq!(
+        dropped_txs[0].reason,
+        TransactionDropReason::Replaced { replacement_tx_hash: tx_hash!(3) }
+    );
+}
+
+#[rstest]
+fn mempool_events_are_disabled_by_default() {
+    let mut mempool = Mempool::new(MempoolConfig::default(), Arc::new(FakeClock::default()));
+    add_tx(&mut mempool, &add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0));
+
+    assert!(mempool.subscribe_to_events().is_none());
+    assert!(mempool.events_from(0).is_empty());
+}
+
+#[rstest]
+fn restore_mempool_from_persisted_content() {
+    let fake_clock = Arc::new(FakeClock::default());
+    let mut mempool = Mempool::new(MempoolConfig::default(), fake_clock.clone());
+
+    let staged_tx =
+        add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0, tip: 100);
+    let queued_tx = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let tx_with_gap = add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 2, account_nonce: 0);
+    for input in [&staged_tx, &queued_tx, &tx_with_gap] {
+        add_tx(&mut mempool, input);
+        fake_clock.advance(Duration::from_secs(1));
+    }
+    assert_eq!(mempool.get_txs(1).unwrap(), vec![staged_tx.tx.clone()]);
+
+    // Test.
+    let persisted_content = mempool.persisted_content();
+    let mut restored_mempool = Mempool::new(MempoolConfig::default(), fake_clock.clone());
+    restored_mempool.restore(persisted_content, HashMap::new());
+
+    // Staged nonces are not restored, so the staged transaction is eligible again.
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([&staged_tx, &queued_tx, &tx_with_gap].map(|input| input.tx.clone()))
+        .with_priority_queue([TransactionReference::new(&staged_tx.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&restored_mempool.content());
+    assert!(restored_mempool.accounts_with_gap().contains(&contract_address!("0x1")));
+    for input in [&staged_tx, &queued_tx, &tx_with_gap] {
+        assert_eq!(
+            restored_mempool.tx_pool.get_submission_time(input.tx.tx_hash),
+            mempool.tx_pool.get_submission_time(input.tx.tx_hash)
+        );
+    }
+}
+
+#[rstest]
+fn restore_mempool_drops_expired_and_stale_txs() {
+    let recorder = PrometheusBuilder::new().build_recorder();
+    let _recorder_guard = metrics::set_default_local_recorder(&recorder);
+    register_metrics();
+
+    let fake_clock = Arc::new(FakeClock::default());
+    let config = MempoolConfig { transaction_ttl: Duration::from_secs(60), ..Default::default() };
+    let mut mempool = Mempool::new(config.clone(), fake_clock.clone());
+
+    let expired_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &expired_tx);
+    fake_clock.advance(config.transaction_ttl / 2);
+    let stale_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    let valid_tx = add_tx_input!(tx_hash: 3, address: "0x2", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &stale_tx);
+    add_tx(&mut mempool, &valid_tx);
+
+    // Simulate a block committed while the node was down, and advance the clock beyond the TTL of
+    // the first transaction.
+    let persisted_content = mempool.persisted_content();
+    let latest_nonces = HashMap::from([(contract_address!("0x1"), nonce!(1))]);
+    fake_clock.advance(config.transaction_ttl / 2 + Duration::from_secs(5));
+
+    // Test.
+    let mut restored_mempool = Mempool::new(config, fake_clock.clone());
+    restored_mempool.restore(persisted_content, latest_nonces);
+
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([valid_tx.tx.clone()])
+        .with_priority_queue([TransactionReference::new(&valid_tx.tx)])
+        .build();
+    expected_mempool_content.assert_eq(&restored_mempool.content());
+
+    let metrics = recorder.handle().render();
+    MEMPOOL_TRANSACTIONS_RESTORED.assert_eq(&metrics, 1);
+    MEMPOOL_TRANSACTIONS_DROPPED.assert_eq(
+        &metrics,
+        2,
+        &[(LABEL_NAME_DROP_REASON, DropReason::FailedRestore.into())],
+    );
+}
+
+#[rstest]
+fn journal_restores_txs_added_since_last_commit() {
+    let fake_clock = Arc::new(FakeClock::default());
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal");
+    let mut mempool = Mempool::new(MempoolConfig::default(), fake_clock.clone());
+    mempool.set_journal(MempoolJournal::start(path.clone(), &mempool.persisted_content()).unwrap());
+
+    let committed_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let pending_tx = add_tx_input!(tx_hash: 2, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    add_tx(&mut mempool, &committed_tx);
+    add_tx(&mut mempool, &pending_tx);
+    commit_block(&mut mempool, [("0x0", 1)], []);
+    // Added after the last commit.
+    let new_tx = add_tx_input!(tx_hash: 3, address: "0x0", tx_nonce: 1, account_nonce: 1);
+    add_tx(&mut mempool, &new_tx);
+    // Simulate a restart; dropping the mempool waits for the journal to be written.
+    drop(mempool);
+
+    // Test.
+    let mut restored_mempool = Mempool::new(MempoolConfig::default(), fake_clock);
+    restored_mempool.restore(load_journal(&path).unwrap().unwrap(), HashMap::new());
+
+    let expected_mempool_content = MempoolTestContentBuilder::new()
+        .with_pool([pending_tx.tx.clone(), new_tx.tx.clone()])
+        .with_priority_queue([
+            TransactionReference::new(&new_tx.tx),
+            TransactionReference::new(&pending_tx.tx),
+        ])
+        .build();
+    expected_mempool_content.assert_eq(&restored_mempool.content());
+}
+
+#[tokio::test]
+async fn restored_txs_are_validated_against_latest_nonces() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal");
+    let mut mempool = Mempool::new(MempoolConfig::default(), Arc::new(FakeClock::default()));
+    mempool.set_journal(MempoolJournal::start(path.clone(), &mempool.persisted_content()).unwrap());
+    let included_tx = add_tx_input!(tx_hash: 1, address: "0x0", tx_nonce: 0, account_nonce: 0);
+    let pending_tx = add_tx_input!(tx_hash: 2, address: "0x0", tx_nonce: 1, account_nonce: 0);
+    let undeployed_account_tx =
+        add_tx_input!(tx_hash: 3, address: "0x1", tx_nonce: 0, account_nonce: 0);
+    for input in [&included_tx, &pending_tx, &undeployed_account_tx] {
+        add_tx(&mut mempool, input);
+    }
+    drop(mempool);
+
+    // Simulate a block including the first transaction, committed while the node was down.
+    let mut mock_state_sync_client = MockStateSyncClient::new();
+    mock_state_sync_client.expect_get_latest_block_number().returning(|| Ok(Some(BlockNumber(1))));
+    mock_state_sync_client.expect_get_nonce_at().returning(|_, address| {
+        if address == contract_address!("0x0") {
+            Ok(nonce!(1))
+        } else {
+            Err(StateSyncClientError::StateSyncError(StateSyncError::ContractNotFound(address)))
+        }
+    });
+    let config = MempoolConfig {
+        persistence_config: MempoolPersistenceConfig { enabled: true, path },
+        ..Default::default()
+    };
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        Mempool::new(config, Arc::new(FakeClock::default())),
+        Arc::new(MockMempoolP2pPropagatorClient::new()),
+        Arc::new(mock_state_sync_client),
+    );
+
+    // Test.
+    mempool_wrapper.start().await;
+
+    let MempoolResponse::GetTransactions(Ok(mut txs)) =
+        mempool_wrapper.handle_request(MempoolRequest::GetTransactions(3)).await
+    else {
+        panic!("Expected the restored transactions.");
+    };
+    txs.sort_by_key(|tx| tx.tx_hash);
+    assert_eq!(txs, vec![pending_tx.tx, undeployed_account_tx.tx]);
+}
+
 #[rstest]
 #[tokio::test]
 async fn add_tx_tolerates_p2p_propagation_error(mempool: Mempool) {
@@ -1439,7 +2089,11 @@ async fn add_tx_tolerates_p2p_propagation_error(mempool: Mempool) {
             "".to_string(),
         )))
     });
-    let mut mempool_wrapper = MempoolCommunicationWrapper::new(mempool, Arc::new(mock_p2p));
+    let mut mempool_wrapper = MempoolCommunicationWrapper::new(
+        mempool,
+        Arc::new(mock_p2p),
+        Arc::new(MockStateSyncClient::new()),
+    );
 
     let result = mempool_wrapper.add_tx(tx_args_wrapper).await;
 
@@ -1613,11 +2267,9 @@ fn declare_tx_closes_a_gap() {
         nonce: nonce!(0)
     ));
     add_tx(&mut mempool, &delayed_declare_tx_closes_a_gap);
-    assert!(
-        !mempool
-            .accounts_with_gap()
-            .contains(&delayed_declare_tx_closes_a_gap.tx.contract_address())
-    );
+    assert!(!mempool
+        .accounts_with_gap()
+        .contains(&delayed_declare_tx_closes_a_gap.tx.contract_address()));
 }
 
 #[rstest]
@@ -1,3 +1,4 @@
+use apollo_mempool_types::errors::MempoolError;
 use apollo_mempool_types::mempool_types::MEMPOOL_REQUEST_LABELS;
 use apollo_metrics::{define_metrics, generate_permutation_labels};
 use starknet_api::rpc_transaction::{
@@ -11,6 +12,7 @@ define_metrics!(
     Mempool => {
         MetricCounter { MEMPOOL_TRANSACTIONS_COMMITTED, "mempool_txs_committed", "The number of transactions that were committed to block", init = 0 },
         MetricCounter { MEMPOOL_EVICTIONS_COUNT, "mempool_evictions_count", "The number of transactions evicted due to capacity", init = 0 },
+        MetricCounter { MEMPOOL_TRANSACTIONS_RESTORED, "mempool_txs_restored", "The number of transactions restored from the persisted mempool snapshot on startup", init = 0 },
         LabeledMetricCounter { MEMPOOL_TRANSACTIONS_RECEIVED, "mempool_transactions_received", "Counter of transactions received by the mempool", init = 0, labels = INTERNAL_RPC_TRANSACTION_LABELS },
         LabeledMetricCounter { MEMPOOL_TRANSACTIONS_DROPPED, "mempool_transactions_dropped", "Counter of transactions dropped from the mempool", init = 0, labels = DROP_REASON_LABELS },
         MetricGauge { MEMPOOL_POOL_SIZE, "mempool_pool_size", "The number of the transactions in the mempool's transaction pool" },
@@ -80,17 +82,37 @@ pub(crate) enum DropReason {
     FailedAddTxChecks,
     Expired,
     Rejected,
+    FailedRestore,
+    AccountTxLimitExceeded,
+    NonceDistanceExceeded,
+    AccountCapacityExceeded,
+}
+
+impl From<&MempoolError> for DropReason {
+    fn from(error: &MempoolError) -> Self {
+        match error {
+            MempoolError::AccountTxLimitExceeded { .. } => DropReason::AccountTxLimitExceeded,
+            MempoolError::NonceDistanceExceeded { .. } => DropReason::NonceDistanceExceeded,
+            MempoolError::AccountCapacityExceeded { .. } => DropReason::AccountCapacityExceeded,
+            _ => DropReason::FailedAddTxChecks,
+        }
+    }
 }
 
 pub(crate) struct MempoolMetricHandle {
     tx_type: InternalRpcTransactionLabelValue,
     tx_status: TransactionStatus,
+    drop_reason: DropReason,
 }
 
 impl MempoolMetricHandle {
     pub fn new(tx: &InternalRpcTransactionWithoutTxHash) -> Self {
         let tx_type = InternalRpcTransactionLabelValue::from(tx);
-        Self { tx_type, tx_status: TransactionStatus::Dropped }
+        Self {
+            tx_type,
+            tx_status: TransactionStatus::Dropped,
+            drop_reason: DropReason::FailedAddTxChecks,
+        }
     }
 
     fn label(&self) -> Vec<(&'static str, &'static str)> {
@@ -104,13 +126,17 @@ impl MempoolMetricHandle {
     pub fn transaction_inserted(&mut self) {
         self.tx_status = TransactionStatus::AddedToMempool;
     }
+
+    pub fn transaction_rejected(&mut self, error: &MempoolError) {
+        self.drop_reason = error.into();
+    }
 }
 
 impl Drop for MempoolMetricHandle {
     fn drop(&mut self) {
         match self.tx_status {
             TransactionStatus::Dropped => MEMPOOL_TRANSACTIONS_DROPPED
-                .increment(1, &[(LABEL_NAME_DROP_REASON, DropReason::FailedAddTxChecks.into())]),
+                .increment(1, &[(LABEL_NAME_DROP_REASON, (&self.drop_reason).into())]),
             TransactionStatus::AddedToMempool => {}
         }
     }
@@ -130,6 +156,15 @@ pub(crate) fn metric_count_rejected_txs(n_txs: usize) {
     );
 }
 
+pub(crate) fn metric_count_restore_results(n_restored_txs: usize, n_dropped_txs: usize) {
+    MEMPOOL_TRANSACTIONS_RESTORED
+        .increment(n_restored_txs.try_into().expect("The number of restored_txs should fit u64"));
+    MEMPOOL_TRANSACTIONS_DROPPED.increment(
+        n_dropped_txs.try_into().expect("The number of dropped_txs should fit u64"),
+        &[(LABEL_NAME_DROP_REASON, DropReason::FailedRestore.into())],
+    );
+}
+
 pub(crate) fn metric_count_committed_txs(committed_txs: usize) {
     MEMPOOL_TRANSACTIONS_COMMITTED
         .increment(committed_txs.try_into().expect("The number of committed_txs should fit u64"));
@@ -145,6 +180,7 @@ pub(crate) fn register_metrics() {
     MEMPOOL_TRANSACTIONS_RECEIVED.register();
     MEMPOOL_TRANSACTIONS_DROPPED.register();
     MEMPOOL_EVICTIONS_COUNT.register();
+    MEMPOOL_TRANSACTIONS_RESTORED.register();
     // Register Gauges.
     MEMPOOL_POOL_SIZE.register();
     MEMPOOL_PRIORITY_QUEUE_SIZE.register();
@@ -0,0 +1,241 @@
+//! Persistence of the mempool content as an append-only journal.
+//!
+//! Every change to the mempool content is appended to the journal as a [`JournalRecord`] by a
+//! background thread, so that the mempool's event loop never waits for disk I/O. On startup the
+//! journal is replayed to recover the content, and it is periodically compacted to hold only the
+//! transactions that are still in the mempool.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{channel, Receiver, Sender};
+use std::thread::{self, JoinHandle};
+
+use apollo_mempool_types::mempool_types::{AddTransactionArgs, MempoolStateSnapshot};
+use apollo_time::time::DateTime;
+use indexmap::IndexMap;
+use serde::{Deserialize, Serialize};
+use starknet_api::core::{ContractAddress, Nonce};
+use starknet_api::transaction::TransactionHash;
+use tracing::warn;
+
+#[cfg(test)]
+#[path = "persistence_test.rs"]
+pub mod persistence_test;
+
+// The journal is not compacted before it holds at least this many records, so that a small
+// mempool is not rewritten on every change.
+const MIN_RECORDS_BEFORE_COMPACTION: usize = 10_000;
+
+/// A transaction held by the mempool, together with the data required to re-insert it on startup.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PersistedTransaction {
+    pub args: AddTransactionArgs,
+    // Nanoseconds since the unix epoch; kept as an integer to keep the format stable.
+    submission_time_nanos: i64,
+}
+
+impl PersistedTransaction {
+    pub fn new(args: AddTransactionArgs, submission_time: DateTime) -> Self {
+        let submission_time_nanos = submission_time
+            .timestamp_nanos_opt()
+            .expect("Submission time should be representable in nanoseconds.");
+        PersistedTransaction { args, submission_time_nanos }
+    }
+
+    pub fn submission_time(&self) -> DateTime {
+        DateTime::from_timestamp_nanos(self.submission_time_nanos)
+    }
+}
+
+/// The mempool content recovered from the journal.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct PersistedMempool {
+    // Sorted by submission time, oldest first.
+    pub transactions: Vec<PersistedTransaction>,
+    pub mempool_state: MempoolStateSnapshot,
+}
+
+/// A single change to the mempool content.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub enum JournalRecord {
+    /// A transaction was added to the mempool.
+    Added(PersistedTransaction),
+    /// A transaction was removed from the mempool, for any reason.
+    Removed(TransactionHash),
+    /// The committed nonces of the given accounts were set, by a committed or a reverted block.
+    CommittedNonces(HashMap<ContractAddress, Nonce>),
+}
+
+/// Appends records to the journal file from a background thread.
+/// Dropping the journal waits for the records recorded so far to be written.
+#[derive(Debug)]
+pub struct MempoolJournal {
+    sender: Option<Sender<JournalRecord>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl MempoolJournal {
+    /// Starts a new journal at the given path, holding the given content. Any existing journal at
+    /// the path is replaced.
+    pub fn start(path: PathBuf, content: &PersistedMempool) -> io::Result<Self> {
+        if let Some(parent) = path.parent() {
+            fs::create_dir_all(parent)?;
+        }
+        let n_records = content.transactions.len();
+        let file = write_journal(&path, compacted_records(content))?;
+
+        let (sender, receiver) = channel();
+        let writer = thread::Builder::new()
+            .name("mempool_journal".to_string())
+            .spawn(move || run_writer(path, file, n_records, receiver))?;
+        Ok(MempoolJournal { sender: Some(sender), writer: Some(writer) })
+    }
+
+    /// Queues the record to be appended to the journal.
+    pub fn record(&self, record: JournalRecord) {
+        let sender =
+            self.sender.as_ref().expect("Sender should exist until the journal is dropped.");
+        if sender.send(record).is_err() {
+            warn!("The mempool journal writer has stopped; the record is not persisted.");
+        }
+    }
+}
+
+impl Drop for MempoolJournal {
+    fn drop(&mut self) {
+        // Closing the channel stops the writer once it has written the queued records.
+        self.sender.take();
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                warn!("The mempool journal writer panicked.");
+            }
+        }
+    }
+}
+
+/// Replays the journal at the given path. Returns `None` if no journal exists.
+/// A trailing partial record, left by a crash mid-write, is ignored.
+pub fn load_journal(path: &Path) -> io::Result<Option<PersistedMempool>> {
+    let file = match File::open(path) {
+        Ok(file) => file,
+        Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(None),
+        Err(err) => return Err(err),
+    };
+
+    let mut transactions = IndexMap::<TransactionHash, PersistedTransaction>::new();
+    let mut committed = HashMap::new();
+    let mut reader = BufReader::new(file);
+    let mut line = Vec::new();
+    while reader.read_until(b'\n', &mut line)? != 0 {
+        if line.last() != Some(&b'\n') {
+            warn!("Ignoring a partially written mempool journal record.");
+            break;
+        }
+        match serde_json::from_slice(&line)? {
+            JournalRecord::Added(tx) => {
+                transactions.insert(tx.args.tx.tx_hash, tx);
+            }
+            JournalRecord::Removed(tx_hash) => {
+                transactions.shift_remove(&tx_hash);
+            }
+            JournalRecord::CommittedNonces(address_to_nonce) => committed.extend(address_to_nonce),
+        }
+        line.clear();
+    }
+
+    let mut transactions: Vec<_> = transactions.into_values().collect();
+    transactions.sort_by_key(|tx| tx.submission_time());
+    Ok(Some(PersistedMempool {
+        transactions,
+        mempool_state: MempoolStateSnapshot { committed, staged: HashMap::new() },
+    }))
+}
+
+/// Returns the records which recreate the given content. Only the committed nonces of accounts
+/// with transactions in the mempool are kept, since the others are not needed to restore it.
+fn compacted_records(content: &PersistedMempool) -> impl Iterator<Item = JournalRecord> + '_ {
+    let addresses: HashSet<_> =
+        content.transactions.iter().map(|tx| tx.args.account_state.address).collect();
+    let committed = content
+        .mempool_state
+        .committed
+        .iter()
+        .filter(|(address, _)| addresses.contains(address))
+        .map(|(&address, &nonce)| (address, nonce))
+        .collect();
+
+    std::iter::once(JournalRecord::CommittedNonces(committed))
+        .chain(content.transactions.iter().cloned().map(JournalRecord::Added))
+}
+
+/// Writes the records to a new journal at the given path, and returns the journal opened for
+/// appending. The records are first written to a temporary file which is then renamed, so that a
+/// crash mid-write never leaves a truncated journal behind.
+fn write_journal(
+    path: &Path,
+    records: impl IntoIterator<Item = JournalRecord>,
+) -> io::Result<File> {
+    let tmp_path = path.with_extension("tmp");
+    append_records(&mut File::create(&tmp_path)?, records)?;
+    fs::rename(tmp_path, path)?;
+
+    OpenOptions::new().append(true).open(path)
+}
+
+fn append_records(
+    file: &mut File,
+    records: impl IntoIterator<Item = JournalRecord>,
+) -> io::Result<()> {
+    let mut writer = BufWriter::new(&mut *file);
+    for record in records {
+        serde_json::to_writer(&mut writer, &record)?;
+        writer.write_all(b"\n")?;
+    }
+    writer.flush()?;
+    drop(writer);
+
+    file.sync_data()
+}
+
+// Appends the received records until the channel is closed. Records received together are written
+// with a single sync. The journal is compacted once the records appended since the last compaction
+// outnumber the records it held after it.
+fn run_writer(
+    path: PathBuf,
+    mut file: File,
+    mut n_compacted_records: usize,
+    receiver: Receiver<JournalRecord>,
+) {
+    let mut n_appended_records = 0;
+    while let Ok(record) = receiver.recv() {
+        let records: Vec<_> = std::iter::once(record).chain(receiver.try_iter()).collect();
+        n_appended_records += records.len();
+        if let Err(err) = append_records(&mut file, records) {
+            warn!("Failed to append to the mempool journal at {path:?}: {err}");
+        }
+
+        if n_appended_records < n_compacted_records.max(MIN_RECORDS_BEFORE_COMPACTION) {
+            continue;
+        }
+        match compact(&path) {
+            Ok((compacted_file, n_records)) => {
+                file = compacted_file;
+                n_compacted_records = n_records;
+                n_appended_records = 0;
+            }
+            Err(err) => warn!("Failed to compact the mempool journal at {path:?}: {err}"),
+        }
+    }
+}
+
+fn compact(path: &Path) -> io::Result<(File, usize)> {
+    let content = load_journal(path)?.unwrap_or_else(|| PersistedMempool {
+        transactions: Vec::new(),
+        mempool_state: MempoolStateSnapshot { committed: HashMap::new(), staged: HashMap::new() },
+    });
+    let file = write_journal(path, compacted_records(&content))?;
+
+    Ok((file, content.transactions.len()))
+}
@@ -0,0 +1,139 @@
+use std::collections::HashMap;
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+
+use apollo_mempool_types::mempool_types::MempoolStateSnapshot;
+use apollo_time::time::DateTime;
+use pretty_assertions::assert_eq;
+use starknet_api::{contract_address, nonce, tx_hash};
+use tempfile::tempdir;
+
+use crate::add_tx_input;
+use crate::persistence::{
+    compact,
+    load_journal,
+    JournalRecord,
+    MempoolJournal,
+    PersistedMempool,
+    PersistedTransaction,
+};
+
+fn persisted_tx(tx_hash: u64, address: &str, submission_time_secs: i64) -> PersistedTransaction {
+    let args = add_tx_input!(tx_hash: tx_hash, address: address, tx_nonce: 0, account_nonce: 0);
+    PersistedTransaction::new(args, DateTime::from_timestamp(submission_time_secs, 0).unwrap())
+}
+
+fn empty_content() -> PersistedMempool {
+    PersistedMempool {
+        transactions: vec![],
+        mempool_state: MempoolStateSnapshot { committed: HashMap::new(), staged: HashMap::new() },
+    }
+}
+
+#[test]
+fn load_journal_returns_none_when_missing() {
+    let dir = tempdir().unwrap();
+    assert_eq!(load_journal(&dir.path().join("journal")).unwrap(), None);
+}
+
+#[test]
+fn journal_replays_recorded_changes() {
+    let dir = tempdir().unwrap();
+    // Use a nested path to verify that missing directories are created.
+    let path = dir.path().join("mempool").join("journal");
+    let [removed_tx, kept_tx] = [persisted_tx(1, "0x0", 1), persisted_tx(2, "0x1", 2)];
+
+    let journal = MempoolJournal::start(path.clone(), &empty_content()).unwrap();
+    journal.record(JournalRecord::Added(removed_tx.clone()));
+    journal.record(JournalRecord::Added(kept_tx.clone()));
+    journal.record(JournalRecord::CommittedNonces(HashMap::from([(
+        contract_address!("0x0"),
+        nonce!(1),
+    )])));
+    journal.record(JournalRecord::Removed(removed_tx.args.tx.tx_hash));
+    // Waits for the records to be written.
+    drop(journal);
+
+    let expected_content = PersistedMempool {
+        transactions: vec![kept_tx],
+        mempool_state: MempoolStateSnapshot {
+            committed: HashMap::from([(contract_address!("0x0"), nonce!(1))]),
+            staged: HashMap::new(),
+        },
+    };
+    assert_eq!(load_journal(&path).unwrap().unwrap(), expected_content);
+}
+
+#[test]
+fn journal_starts_from_compacted_content() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal");
+    fs::write(&path, "stale journal content\n").unwrap();
+
+    let tx = persisted_tx(1, "0x0", 1);
+    let content = PersistedMempool {
+        transactions: vec![tx.clone()],
+        mempool_state: MempoolStateSnapshot {
+            committed: HashMap::from([
+                (contract_address!("0x0"), nonce!(0)),
+                (contract_address!("0x1"), nonce!(3)),
+            ]),
+            staged: HashMap::new(),
+        },
+    };
+    drop(MempoolJournal::start(path.clone(), &content).unwrap());
+
+    // Nonces of accounts without transactions are not needed to restore the mempool.
+    let expected_content = PersistedMempool {
+        transactions: vec![tx],
+        mempool_state: MempoolStateSnapshot {
+            committed: HashMap::from([(contract_address!("0x0"), nonce!(0))]),
+            staged: HashMap::new(),
+        },
+    };
+    assert_eq!(load_journal(&path).unwrap().unwrap(), expected_content);
+    assert!(!path.with_extension("tmp").exists());
+}
+
+#[test]
+fn partially_written_record_is_ignored() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal");
+    let tx = persisted_tx(1, "0x0", 1);
+    let journal = MempoolJournal::start(path.clone(), &empty_content()).unwrap();
+    journal.record(JournalRecord::Added(tx.clone()));
+    drop(journal);
+
+    // Simulate a crash in the middle of writing a record.
+    OpenOptions::new().append(true).open(&path).unwrap().write_all(b"{\"Removed\":").unwrap();
+
+    assert_eq!(load_journal(&path).unwrap().unwrap().transactions, vec![tx]);
+}
+
+#[test]
+fn compaction_keeps_only_live_content() {
+    let dir = tempdir().unwrap();
+    let path = dir.path().join("journal");
+    let txs: Vec<_> = (0..10).map(|i| persisted_tx(i, "0x0", i.try_into().unwrap())).collect();
+
+    let journal = MempoolJournal::start(path.clone(), &empty_content()).unwrap();
+    for tx in &txs {
+        journal.record(JournalRecord::Added(tx.clone()));
+    }
+    for tx in &txs[..8] {
+        journal.record(JournalRecord::Removed(tx.args.tx.tx_hash));
+    }
+    drop(journal);
+    let content = load_journal(&path).unwrap().unwrap();
+
+    let (_file, n_records) = compact(&path).unwrap();
+
+    assert_eq!(n_records, 2);
+    // The committed nonces record and the live transactions.
+    assert_eq!(fs::read_to_string(&path).unwrap().lines().count(), 3);
+    assert_eq!(load_journal(&path).unwrap().unwrap(), content);
+    assert_eq!(
+        content.transactions.iter().map(|tx| tx.args.tx.tx_hash).collect::<Vec<_>>(),
+        vec![tx_hash!(8), tx_hash!(9)]
+    );
+}
@@ -24,6 +24,7 @@ use crate::metrics::{
     MEMPOOL_TRANSACTIONS_COMMITTED,
     MEMPOOL_TRANSACTIONS_DROPPED,
     MEMPOOL_TRANSACTIONS_RECEIVED,
+    MEMPOOL_TRANSACTIONS_RESTORED,
     TRANSACTION_TIME_SPENT_IN_MEMPOOL,
     TRANSACTION_TIME_SPENT_UNTIL_COMMITTED,
 };
@@ -290,6 +291,11 @@ pub struct MempoolMetrics {
     pub txs_dropped_expired: u64,
     pub txs_dropped_failed_add_tx_checks: u64,
     pub txs_dropped_rejected: u64,
+    pub txs_dropped_failed_restore: u64,
+    pub txs_dropped_account_tx_limit: u64,
+    pub txs_dropped_nonce_distance: u64,
+    pub txs_dropped_account_capacity: u64,
+    pub txs_restored: u64,
     pub pool_size: u64,
     pub priority_queue_size: u64,
     pub pending_queue_size: u64,
@@ -336,6 +342,27 @@ impl MempoolMetrics {
             self.txs_dropped_rejected,
             &[(LABEL_NAME_DROP_REASON, DropReason::Rejected.into())],
         );
+        MEMPOOL_TRANSACTIONS_DROPPED.assert_eq(
+            metrics,
+            self.txs_dropped_failed_restore,
+            &[(LABEL_NAME_DROP_REASON, DropReason::FailedRestore.into())],
+        );
+        MEMPOOL_TRANSACTIONS_DROPPED.assert_eq(
+            metrics,
+            self.txs_dropped_account_tx_limit,
+            &[(LABEL_NAME_DROP_REASON, DropReason::AccountTxLimitExceeded.into())],
+        );
+        MEMPOOL_TRANSACTIONS_DROPPED.assert_eq(
+            metrics,
+            self.txs_dropped_nonce_distance,
+            &[(LABEL_NAME_DROP_REASON, DropReason::NonceDistanceExceeded.into())],
+        );
+        MEMPOOL_TRANSACTIONS_DROPPED.assert_eq(
+            metrics,
+            self.txs_dropped_account_capacity,
+            &[(LABEL_NAME_DROP_REASON, DropReason::AccountCapacityExceeded.into())],
+        );
+        MEMPOOL_TRANSACTIONS_RESTORED.assert_eq(metrics, self.txs_restored);
         MEMPOOL_POOL_SIZE.assert_eq(metrics, self.pool_size);
         MEMPOOL_PRIORITY_QUEUE_SIZE.assert_eq(metrics, self.priority_queue_size);
         MEMPOOL_PENDING_QUEUE_SIZE.assert_eq(metrics, self.pending_queue_size);
@@ -54,6 +54,17 @@ impl TransactionPool {
     }
 
     pub fn insert(&mut self, tx: InternalRpcTransaction) -> MempoolResult<()> {
+        let submission_time = self.txs_by_submission_time.clock.now();
+        self.insert_with_submission_time(tx, submission_time)
+    }
+
+    /// Inserts a transaction with an explicit submission time, e.g., when restoring transactions
+    /// that were received before a restart.
+    pub fn insert_with_submission_time(
+        &mut self,
+        tx: InternalRpcTransaction,
+        submission_time: DateTime,
+    ) -> MempoolResult<()> {
         let tx_reference = TransactionReference::new(&tx);
         let tx_hash = tx_reference.tx_hash;
         let tx_size = tx.total_bytes();
@@ -76,7 +87,8 @@ impl TransactionPool {
         };
 
         // Insert to timed mapping.
-        let unexpected_existing_tx = self.txs_by_submission_time.insert(tx_reference);
+        let unexpected_existing_tx =
+            self.txs_by_submission_time.insert(tx_reference, submission_time);
         if unexpected_existing_tx.is_some() {
             panic!(
                 "Transaction pool consistency error: transaction with hash {tx_hash} does not
@@ -111,7 +123,7 @@ impl TransactionPool {
         &mut self,
         address: ContractAddress,
         nonce: Nonce,
-    ) -> usize {
+    ) -> Vec<TransactionReference> {
         let removed_txs = self.txs_by_account.remove_up_to_nonce(address, nonce);
 
         for tx_ref in &removed_txs {
@@ -128,7 +140,7 @@ impl TransactionPool {
         self.remove_from_main_mapping(&removed_txs);
         self.remove_from_timed_mapping(&removed_txs);
 
-        removed_txs.len()
+        removed_txs
     }
 
     pub fn remove_txs_older_than(
@@ -187,6 +199,17 @@ impl TransactionPool {
             .ok_or(MempoolError::TransactionNotFound { tx_hash })
     }
 
+    /// Returns the order in which the transaction arrived at the pool, relative to the other
+    /// transactions; it is kept as long as the transaction stays in the pool, e.g., when it is
+    /// re-queued after its proposal was rewound.
+    pub fn get_arrival_order(&self, tx_hash: TransactionHash) -> MempoolResult<u64> {
+        self.txs_by_submission_time
+            .hash_to_submission_id
+            .get(&tx_hash)
+            .map(|submission_id| submission_id.arrival_order)
+            .ok_or(MempoolError::TransactionNotFound { tx_hash })
+    }
+
     pub fn get_lowest_nonce(&self, address: ContractAddress) -> Option<Nonce> {
         self.account_txs_sorted_by_nonce(address).next().map(|tx_ref| tx_ref.nonce)
     }
@@ -329,6 +352,8 @@ impl PoolSize {
 #[derive(Clone, Debug, PartialEq, Eq)]
 struct SubmissionID {
     submission_time: DateTime,
+    // Breaks ties between transactions submitted at the same time.
+    arrival_order: u64,
     tx_hash: TransactionHash,
 }
 
@@ -339,6 +364,7 @@ impl Ord for SubmissionID {
     fn cmp(&self, other: &Self) -> Ordering {
         self.submission_time
             .cmp(&other.submission_time)
+            .then_with(|| self.arrival_order.cmp(&other.arrival_order))
             .reverse()
             .then_with(|| self.tx_hash.cmp(&other.tx_hash))
     }
@@ -353,6 +379,8 @@ impl PartialOrd for SubmissionID {
 struct TimedTransactionMap {
     txs_by_submission_time: BTreeMap<SubmissionID, TransactionReference>,
     hash_to_submission_id: HashMap<TransactionHash, SubmissionID>,
+    // Number of transactions inserted so far; used to assign arrival orders.
+    n_inserted_txs: u64,
     clock: Arc<dyn Clock>,
 }
 
@@ -361,14 +389,24 @@ impl TimedTransactionMap {
         TimedTransactionMap {
             txs_by_submission_time: BTreeMap::new(),
             hash_to_submission_id: HashMap::new(),
+            n_inserted_txs: 0,
             clock,
         }
     }
 
     /// If a transaction with the same transaction hash already exists in the mapping, the previous
     /// submission ID is returned.
-    fn insert(&mut self, tx: TransactionReference) -> Option<SubmissionID> {
-        let submission_id = SubmissionID { submission_time: self.clock.now(), tx_hash: tx.tx_hash };
+    fn insert(
+        &mut self,
+        tx: TransactionReference,
+        submission_time: DateTime,
+    ) -> Option<SubmissionID> {
+        let submission_id = SubmissionID {
+            submission_time,
+            arrival_order: self.n_inserted_txs,
+            tx_hash: tx.tx_hash,
+        };
+        self.n_inserted_txs += 1;
         self.txs_by_submission_time.insert(submission_id.clone(), tx);
         self.hash_to_submission_id.insert(tx.tx_hash, submission_id)
     }
@@ -393,6 +431,9 @@ impl TimedTransactionMap {
     ) -> Vec<TransactionReference> {
         let split_off_value = SubmissionID {
             submission_time: self.clock.now() - duration,
+            // Orders the split value before all transactions submitted exactly at the split time,
+            // so that they are removed as well.
+            arrival_order: u64::MAX,
             tx_hash: Default::default(),
         };
         let old_txs = self.txs_by_submission_time.split_off(&split_off_value);
@@ -1,41 +1,64 @@
 use std::cmp::Ordering;
 use std::collections::{BTreeSet, HashMap};
+use std::mem;
+use std::ops::Bound::{Excluded, Unbounded};
 
-use apollo_mempool_types::mempool_types::TransactionQueueSnapshot;
+use apollo_mempool_types::mempool_types::{TransactionEligibility, TransactionQueueSnapshot};
 use starknet_api::block::GasPrice;
 use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::transaction::fields::Tip;
 use starknet_api::transaction::TransactionHash;
 
+use crate::config::TransactionOrderingPolicy;
 use crate::mempool::TransactionReference;
 
 #[cfg(test)]
 #[path = "transaction_queue_test_utils.rs"]
 pub mod transaction_queue_test_utils;
 
+#[cfg(test)]
+#[path = "transaction_queue_test.rs"]
+pub mod transaction_queue_test;
+
 // A queue holding the transaction that with nonces that match account nonces.
 // Note: the derived comparison functionality considers the order guaranteed by the data structures
 // used.
 #[derive(Debug, Default)]
 pub struct TransactionQueue {
     gas_price_threshold: GasPrice,
-    // Transactions with gas price above gas price threshold (sorted by tip).
+    ordering_policy: TransactionOrderingPolicy,
+    // Transactions with gas price above gas price threshold (sorted by the ordering policy).
     priority_queue: BTreeSet<PriorityTransaction>,
     // Transactions with gas price below gas price threshold (sorted by price).
     pending_queue: BTreeSet<PendingTransaction>,
     // Set of account addresses for efficient existence checks.
-    address_to_tx: HashMap<ContractAddress, TransactionReference>,
+    address_to_tx: HashMap<ContractAddress, QueuedTransaction>,
+    // Number of transactions popped per account in the current block; used by the round-robin
+    // policy.
+    account_turns: HashMap<ContractAddress, u64>,
 }
 
 impl TransactionQueue {
+    pub fn with_ordering_policy(ordering_policy: TransactionOrderingPolicy) -> Self {
+        TransactionQueue { ordering_policy, ..Default::default() }
+    }
+
     /// Adds a transaction to the mempool, ensuring unique keys.
     /// Panics: if given a duplicate tx.
     /// If `validate_resource_bounds` is false, the transaction is added to the priority queue,
     /// regardless of it's L2 gas price bound.
-    pub fn insert(&mut self, tx_reference: TransactionReference, validate_resource_bounds: bool) {
-        assert_eq!(
-            self.address_to_tx.insert(tx_reference.address, tx_reference),
-            None,
+    /// `arrival_order` is the order in which the transaction arrived at the mempool; the FIFO
+    /// policy sequences earlier arrivals first, also when a transaction is re-queued.
+    pub fn insert(
+        &mut self,
+        tx_reference: TransactionReference,
+        arrival_order: u64,
+        validate_resource_bounds: bool,
+    ) {
+        let mut queued_tx = QueuedTransaction { tx_reference, arrival_order, priority: 0 };
+        queued_tx.priority = self.priority(&queued_tx);
+        assert!(
+            self.address_to_tx.insert(tx_reference.address, queued_tx).is_none(),
             "Only a single transaction from the same contract class can be in the mempool at a \
              time."
         );
@@ -43,9 +66,9 @@ impl TransactionQueue {
         let to_pending_queue =
             validate_resource_bounds && tx_reference.max_l2_gas_price < self.gas_price_threshold;
         let new_tx_successfully_inserted = if to_pending_queue {
-            self.pending_queue.insert(tx_reference.into())
+            self.pending_queue.insert(queued_tx.into())
         } else {
-            self.priority_queue.insert(tx_reference.into())
+            self.priority_queue.insert(queued_tx.into())
         };
         assert!(
             new_tx_successfully_inserted,
@@ -63,10 +86,14 @@ impl TransactionQueue {
 
     // TODO(gilad): remove collect, if returning an iterator is possible.
     pub fn pop_ready_chunk(&mut self, n_txs: usize) -> Vec<TransactionReference> {
-        let txs: Vec<TransactionReference> =
-            (0..n_txs).filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.0)).collect();
+        let txs: Vec<TransactionReference> = (0..n_txs)
+            .filter_map(|_| self.priority_queue.pop_last().map(|tx| tx.tx_reference))
+            .collect();
         for tx in &txs {
             self.address_to_tx.remove(&tx.address);
+            if self.ordering_policy == TransactionOrderingPolicy::AccountRoundRobin {
+                *self.account_turns.entry(tx.address).or_default() += 1;
+            }
         }
 
         txs
@@ -75,7 +102,7 @@ impl TransactionQueue {
     /// Returns an iterator of the current eligible transactions for sequencing, ordered by their
     /// priority.
     pub fn iter_over_ready_txs(&self) -> impl Iterator<Item = &TransactionReference> {
-        self.priority_queue.iter().rev().map(|tx| &tx.0)
+        self.priority_queue.iter().rev().map(|tx| &tx.0.tx_reference)
     }
 
     pub fn get_nonce(&self, address: ContractAddress) -> Option<Nonce> {
@@ -85,12 +112,12 @@ impl TransactionQueue {
     /// Removes the transaction of the given account address from the queue.
     /// This is well-defined, since there is at most one transaction per address in the queue.
     pub fn remove(&mut self, address: ContractAddress) -> bool {
-        let Some(tx_reference) = self.address_to_tx.remove(&address) else {
+        let Some(queued_tx) = self.address_to_tx.remove(&address) else {
             return false;
         };
 
-        self.priority_queue.remove(&tx_reference.into())
-            || self.pending_queue.remove(&tx_reference.into())
+        self.priority_queue.remove(&queued_tx.into())
+            || self.pending_queue.remove(&queued_tx.into())
     }
 
     /// Removes the given transactions from the queue.
@@ -107,6 +134,33 @@ impl TransactionQueue {
         removed_txs
     }
 
+    /// Returns the eligibility of the given transaction if it is queued, including the number of
+    /// transactions ahead of it in its queue.
+    pub fn queued_tx_eligibility(
+        &self,
+        tx_reference: &TransactionReference,
+    ) -> Option<TransactionEligibility> {
+        let queued_tx = *self.address_to_tx.get(&tx_reference.address)?;
+        if queued_tx.tx_hash != tx_reference.tx_hash {
+            return None;
+        }
+
+        // Both queues are popped from their end, so the transactions ahead are the greater ones.
+        let priority_tx = PriorityTransaction(queued_tx);
+        if self.priority_queue.contains(&priority_tx) {
+            let queue_position =
+                self.priority_queue.range((Excluded(priority_tx), Unbounded)).count();
+            return Some(TransactionEligibility::Ready { queue_position });
+        }
+
+        let pending_tx = PendingTransaction(queued_tx);
+        let queue_position = self.pending_queue.range((Excluded(pending_tx), Unbounded)).count();
+        Some(TransactionEligibility::GasPriceBelowThreshold {
+            queue_position,
+            gas_price_threshold: self.gas_price_threshold,
+        })
+    }
+
     pub fn has_ready_txs(&self) -> bool {
         !self.priority_queue.is_empty()
     }
@@ -122,12 +176,16 @@ impl TransactionQueue {
     }
 
     fn promote_txs_to_priority(&mut self, threshold: GasPrice) {
-        let tmp_split_tx = PendingTransaction(TransactionReference {
-            max_l2_gas_price: threshold,
-            address: ContractAddress::default(),
-            nonce: Nonce::default(),
-            tx_hash: TransactionHash::default(),
-            tip: Tip::default(),
+        let tmp_split_tx = PendingTransaction(QueuedTransaction {
+            tx_reference: TransactionReference {
+                max_l2_gas_price: threshold,
+                address: ContractAddress::default(),
+                nonce: Nonce::default(),
+                tx_hash: TransactionHash::default(),
+                tip: Tip::default(),
+            },
+            arrival_order: 0,
+            priority: 0,
         });
 
         // Split off the pending queue at the given transaction higher than the threshold.
@@ -135,7 +193,7 @@ impl TransactionQueue {
 
         // Insert all transactions from the split point into the priority queue, skip
         // `tmp_split_tx`.
-        // Note: extend will reorder transactions by `Tip` during insertion, despite them being
+        // Note: extend will reorder transactions by priority during insertion, despite them being
         // initially ordered by fee.
         self.priority_queue.extend(txs_over_threshold.map(|tx| PriorityTransaction::from(tx.0)));
     }
@@ -156,9 +214,57 @@ impl TransactionQueue {
         self.pending_queue.extend(txs_to_remove.iter().map(|tx| PendingTransaction::from(tx.0)));
     }
 
+    /// Starts a new round of the round-robin ordering policy, forgetting which accounts were
+    /// already served; a no-op under other policies.
+    pub fn reset_account_turns(&mut self) {
+        if self.account_turns.is_empty() {
+            return;
+        }
+        self.account_turns.clear();
+
+        // Priorities depend on the account turns, so the queues are rebuilt.
+        let priority_queue = mem::take(&mut self.priority_queue);
+        for tx in priority_queue {
+            let queued_tx = self.reprioritize(tx.0);
+            self.priority_queue.insert(queued_tx.into());
+        }
+        let pending_queue = mem::take(&mut self.pending_queue);
+        for tx in pending_queue {
+            let queued_tx = self.reprioritize(tx.0);
+            self.pending_queue.insert(queued_tx.into());
+        }
+    }
+
+    fn reprioritize(&mut self, queued_tx: QueuedTransaction) -> QueuedTransaction {
+        let queued_tx = QueuedTransaction { priority: self.priority(&queued_tx), ..queued_tx };
+        self.address_to_tx.insert(queued_tx.address, queued_tx);
+        queued_tx
+    }
+
+    /// Returns the priority of the given transaction under the queue's ordering policy; greater
+    /// priorities are sequenced first.
+    fn priority(&self, queued_tx: &QueuedTransaction) -> u128 {
+        let tx_reference = &queued_tx.tx_reference;
+        match self.ordering_policy {
+            TransactionOrderingPolicy::Tip => tx_reference.tip.0.into(),
+            TransactionOrderingPolicy::Fifo => u128::MAX - u128::from(queued_tx.arrival_order),
+            // Equivalent to ordering by tip plus the headroom over the gas price threshold, as the
+            // threshold is shared by all transactions.
+            TransactionOrderingPolicy::EffectivePriorityFee => {
+                u128::from(tx_reference.tip.0).saturating_add(tx_reference.max_l2_gas_price.0)
+            }
+            // Fewer turns first, then higher tip.
+            TransactionOrderingPolicy::AccountRoundRobin => {
+                let turns =
+                    self.account_turns.get(&tx_reference.address).copied().unwrap_or_default();
+                (u128::from(u64::MAX - turns) << 64) | u128::from(tx_reference.tip.0)
+            }
+        }
+    }
+
     pub fn queue_snapshot(&self) -> TransactionQueueSnapshot {
-        let priority_queue = self.priority_queue.iter().map(|tx| tx.0.tx_hash).collect();
-        let pending_queue = self.pending_queue.iter().map(|tx| tx.0.tx_hash).collect();
+        let priority_queue = self.priority_queue.iter().map(|tx| tx.tx_hash).collect();
+        let pending_queue = self.pending_queue.iter().map(|tx| tx.tx_hash).collect();
 
         TransactionQueueSnapshot {
             gas_price_threshold: self.gas_price_threshold,
@@ -168,9 +274,18 @@ impl TransactionQueue {
     }
 }
 
+/// A queued transaction reference, along with its priority under the queue's ordering policy.
+#[derive(Clone, Copy, Debug, derive_more::Deref)]
+struct QueuedTransaction {
+    #[deref]
+    tx_reference: TransactionReference,
+    arrival_order: u64,
+    priority: u128,
+}
+
 /// Encapsulates a transaction reference to assess its order (i.e., gas price).
 #[derive(Clone, Copy, Debug, derive_more::Deref, derive_more::From)]
-struct PendingTransaction(pub TransactionReference);
+struct PendingTransaction(pub QueuedTransaction);
 
 /// Compare transactions based only on their gas price, using the Eq trait. It ensures that
 /// two gas price are either exactly equal or not.
@@ -200,13 +315,13 @@ impl PartialOrd for PendingTransaction {
 }
 
 /// This struct behaves similarly to `PendingTransaction`, encapsulating a transaction reference
-/// to assess its order (i.e., tip); see its documentation for more details.
+/// to assess its order (i.e., priority); see its documentation for more details.
 #[derive(Clone, Copy, Debug, derive_more::Deref, derive_more::From)]
-struct PriorityTransaction(pub TransactionReference);
+struct PriorityTransaction(pub QueuedTransaction);
 
 impl PartialEq for PriorityTransaction {
     fn eq(&self, other: &PriorityTransaction) -> bool {
-        self.tip == other.tip && self.tx_hash == other.tx_hash
+        self.priority == other.priority && self.tx_hash == other.tx_hash
     }
 }
 
@@ -214,7 +329,7 @@ impl Eq for PriorityTransaction {}
 
 impl Ord for PriorityTransaction {
     fn cmp(&self, other: &Self) -> Ordering {
-        self.tip.cmp(&other.tip).then_with(|| self.tx_hash.cmp(&other.tx_hash))
+        self.priority.cmp(&other.priority).then_with(|| self.tx_hash.cmp(&other.tx_hash))
     }
 }
 
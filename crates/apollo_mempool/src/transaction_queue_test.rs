@@ -0,0 +1,111 @@
+use rstest::rstest;
+use starknet_api::block::GasPrice;
+use starknet_api::transaction::fields::Tip;
+use starknet_api::transaction::TransactionHash;
+use starknet_api::{contract_address, nonce, tx_hash};
+
+use crate::config::TransactionOrderingPolicy;
+use crate::mempool::TransactionReference;
+use crate::transaction_queue::TransactionQueue;
+
+fn tx_reference(
+    tx_hash: u64,
+    address: &str,
+    nonce: u8,
+    tip: u64,
+    max_l2_gas_price: u128,
+) -> TransactionReference {
+    TransactionReference {
+        address: contract_address!(address),
+        nonce: nonce!(nonce),
+        tx_hash: tx_hash!(tx_hash),
+        tip: Tip(tip),
+        max_l2_gas_price: GasPrice(max_l2_gas_price),
+    }
+}
+
+fn tx_queue_with(
+    ordering_policy: TransactionOrderingPolicy,
+    txs: &[TransactionReference],
+) -> TransactionQueue {
+    let mut tx_queue = TransactionQueue::with_ordering_policy(ordering_policy);
+    for (arrival_order, tx) in (0..).zip(txs) {
+        tx_queue.insert(*tx, arrival_order, true);
+    }
+    tx_queue
+}
+
+#[track_caller]
+fn assert_popped(tx_queue: &mut TransactionQueue, expected_tx_hashes: &[u64]) {
+    let popped_tx_hashes: Vec<TransactionHash> = tx_queue
+        .pop_ready_chunk(expected_tx_hashes.len())
+        .into_iter()
+        .map(|tx| tx.tx_hash)
+        .collect();
+    let expected_tx_hashes: Vec<TransactionHash> =
+        expected_tx_hashes.iter().map(|&tx_hash| tx_hash!(tx_hash)).collect();
+    assert_eq!(popped_tx_hashes, expected_tx_hashes);
+}
+
+#[rstest]
+#[case::tip(TransactionOrderingPolicy::Tip, vec![2, 3, 1])]
+#[case::fifo(TransactionOrderingPolicy::Fifo, vec![1, 2, 3])]
+#[case::effective_priority_fee(TransactionOrderingPolicy::EffectivePriorityFee, vec![3, 2, 1])]
+#[case::account_round_robin(TransactionOrderingPolicy::AccountRoundRobin, vec![2, 3, 1])]
+fn ordering_policy_determines_order(
+    #[case] ordering_policy: TransactionOrderingPolicy,
+    #[case] expected_tx_hashes: Vec<u64>,
+) {
+    let txs = [
+        tx_reference(1, "0x0", 0, 10, 100),
+        tx_reference(2, "0x1", 0, 30, 100),
+        tx_reference(3, "0x2", 0, 20, 200),
+    ];
+    let mut tx_queue = tx_queue_with(ordering_policy, &txs);
+
+    assert_popped(&mut tx_queue, &expected_tx_hashes);
+}
+
+#[test]
+fn fifo_order_is_kept_across_gas_price_threshold_updates() {
+    let txs = [tx_reference(1, "0x0", 0, 0, 50), tx_reference(2, "0x1", 0, 100, 100)];
+    let mut tx_queue = tx_queue_with(TransactionOrderingPolicy::Fifo, &txs);
+
+    tx_queue.update_gas_price_threshold(GasPrice(75));
+    tx_queue.update_gas_price_threshold(GasPrice(0));
+
+    assert_popped(&mut tx_queue, &[1, 2]);
+}
+
+#[test]
+fn fifo_order_is_kept_when_a_transaction_is_requeued() {
+    let txs = [tx_reference(1, "0x0", 0, 0, 100), tx_reference(2, "0x1", 0, 0, 100)];
+    let mut tx_queue = tx_queue_with(TransactionOrderingPolicy::Fifo, &txs);
+    assert_popped(&mut tx_queue, &[1]);
+
+    // The proposal is rewound, and a later transaction arrives before the first is re-queued.
+    tx_queue.insert(tx_reference(3, "0x2", 0, 0, 100), 2, true);
+    tx_queue.insert(txs[0], 0, true);
+
+    assert_popped(&mut tx_queue, &[1, 2, 3]);
+}
+
+#[test]
+fn account_round_robin_serves_each_account_once_per_round() {
+    // Account 0x0 pays the highest tip, but may not take consecutive turns.
+    let txs = [tx_reference(1, "0x0", 0, 100, 100), tx_reference(2, "0x1", 0, 10, 100)];
+    let mut tx_queue = tx_queue_with(TransactionOrderingPolicy::AccountRoundRobin, &txs);
+
+    assert_popped(&mut tx_queue, &[1]);
+    tx_queue.insert(tx_reference(3, "0x0", 1, 100, 100), 2, true);
+    assert_popped(&mut tx_queue, &[2]);
+    tx_queue.insert(tx_reference(4, "0x1", 1, 10, 100), 3, true);
+
+    // Both accounts were served once; the tip breaks the tie.
+    assert_popped(&mut tx_queue, &[3]);
+
+    // Account 0x0 was served twice, but a new round forgets previous turns.
+    tx_queue.insert(tx_reference(5, "0x0", 2, 100, 100), 4, true);
+    tx_queue.reset_account_turns();
+    assert_popped(&mut tx_queue, &[5, 4]);
+}
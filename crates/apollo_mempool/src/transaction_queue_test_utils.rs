@@ -1,9 +1,9 @@
-use std::collections::HashMap;
+use std::collections::HashSet;
 
 use starknet_api::block::GasPrice;
 
 use crate::mempool::TransactionReference;
-use crate::transaction_queue::{PendingTransaction, PriorityTransaction, TransactionQueue};
+use crate::transaction_queue::{PendingTransaction, QueuedTransaction, TransactionQueue};
 
 impl TransactionQueue {
     pub fn new(
@@ -11,25 +11,34 @@ impl TransactionQueue {
         pending_queue: Vec<TransactionReference>,
         gas_price_threshold: GasPrice,
     ) -> Self {
-        // Build address to nonce mapping, check queues are mutually exclusive in addresses.
+        // Check queues are mutually exclusive in addresses.
         let tx_references = pending_queue.iter().chain(priority_queue.iter());
-        let mut address_to_tx = HashMap::new();
+        let mut addresses = HashSet::new();
         for tx_ref in tx_references {
             let address = tx_ref.address;
-            if address_to_tx.insert(address, *tx_ref).is_some() {
+            if !addresses.insert(address) {
                 panic!("Duplicate address: {address}; queues must be mutually exclusive.");
             }
         }
 
-        TransactionQueue {
-            priority_queue: priority_queue.into_iter().map(PriorityTransaction).collect(),
-            pending_queue: pending_queue.into_iter().map(PendingTransaction).collect(),
-            address_to_tx,
-            gas_price_threshold,
+        let mut tx_queue = TransactionQueue { gas_price_threshold, ..Default::default() };
+        // Transactions are given arrival orders in the order they are listed.
+        let mut arrival_orders = 0..;
+        for (tx_ref, arrival_order) in priority_queue.into_iter().zip(&mut arrival_orders) {
+            tx_queue.insert(tx_ref, arrival_order, false);
         }
+        for (tx_ref, arrival_order) in pending_queue.into_iter().zip(&mut arrival_orders) {
+            let mut queued_tx =
+                QueuedTransaction { tx_reference: tx_ref, arrival_order, priority: 0 };
+            queued_tx.priority = tx_queue.priority(&queued_tx);
+            tx_queue.address_to_tx.insert(tx_ref.address, queued_tx);
+            tx_queue.pending_queue.insert(PendingTransaction(queued_tx));
+        }
+
+        tx_queue
     }
 
     pub fn pending_txs(&self) -> Vec<TransactionReference> {
-        self.pending_queue.iter().rev().map(|tx| tx.0).collect()
+        self.pending_queue.iter().rev().map(|tx| tx.tx_reference).collect()
     }
 }
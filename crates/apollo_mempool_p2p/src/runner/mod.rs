@@ -76,20 +76,29 @@ impl ComponentStarter for MempoolP2pRunner {
                         Err(gateway_client_error) => {
                             // TODO(shahak): Analyze the error to see if it's the tx's fault or an
                             // internal error. Widen GatewayError's variants if necessary.
-                            if let GatewayClientError::GatewayError(
-                                GatewayError::DeprecatedGatewayError{p2p_message_metadata: Some(p2p_message_metadata), ..}
-                            ) = gateway_client_error {
-                                warn!(
-                                    "Gateway rejected transaction we received from another peer. Reporting peer."
-                                );
-                                if let Err(e) = self.broadcast_topic_client.report_peer(p2p_message_metadata.clone()).await {
-                                    warn!("Failed to report peer: {:?}", e);
+                            match gateway_client_error {
+                                GatewayClientError::GatewayError(
+                                    GatewayError::DeprecatedGatewayError{source, ..}
+                                ) if source.is_node_overloaded() => {
+                                    // The transaction was not validated, so the peer is not at fault.
+                                    warn!("Gateway is overloaded, dropped a transaction we received from another peer.");
+                                }
+                                GatewayClientError::GatewayError(
+                                    GatewayError::DeprecatedGatewayError{p2p_message_metadata: Some(p2p_message_metadata), ..}
+                                ) => {
+                                    warn!(
+                                        "Gateway rejected transaction we received from another peer. Reporting peer."
+                                    );
+                                    if let Err(e) = self.broadcast_topic_client.report_peer(p2p_message_metadata.clone()).await {
+                                        warn!("Failed to report peer: {:?}", e);
+                                    }
+                                }
+                                gateway_client_error => {
+                                    warn!(
+                                        "Failed sending transaction to gateway. {:?}",
+                                        gateway_client_error
+                                    );
                                 }
-                            } else {
-                                warn!(
-                                    "Failed sending transaction to gateway. {:?}",
-                                    gateway_client_error
-                                );
                             }
                         }
                     }
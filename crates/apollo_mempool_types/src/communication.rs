@@ -17,12 +17,22 @@ use serde::{Deserialize, Serialize};
 use starknet_api::block::GasPrice;
 use starknet_api::core::ContractAddress;
 use starknet_api::rpc_transaction::InternalRpcTransaction;
+use starknet_api::transaction::TransactionHash;
 use strum::EnumVariantNames;
 use strum_macros::{AsRefStr, EnumDiscriminants, EnumIter, IntoStaticStr};
 use thiserror::Error;
 
 use crate::errors::MempoolError;
-use crate::mempool_types::{AddTransactionArgs, CommitBlockArgs, MempoolSnapshot};
+use crate::mempool_types::{
+    AddTransactionArgs,
+    CommitBlockArgs,
+    DroppedTransaction,
+    DroppedTransactionsFilter,
+    MempoolEvent,
+    MempoolSnapshot,
+    MempoolTransactionStatus,
+    RevertBlockArgs,
+};
 
 pub type LocalMempoolClient = LocalComponentClient<MempoolRequest, MempoolResponse>;
 pub type RemoteMempoolClient = RemoteComponentClient<MempoolRequest, MempoolResponse>;
@@ -46,13 +56,33 @@ pub trait MempoolClient: Send + Sync {
     // TODO(AlonH): Rename tx to transaction
     async fn add_tx(&self, args: AddTransactionArgsWrapper) -> MempoolClientResult<()>;
     async fn commit_block(&self, args: CommitBlockArgs) -> MempoolClientResult<()>;
+    async fn revert_block(&self, args: RevertBlockArgs) -> MempoolClientResult<()>;
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<InternalRpcTransaction>>;
+    /// Returns up to `n_txs` transactions that are ready for sequencing without staging them, so
+    /// they are still returned by `get_txs`.
+    async fn peek_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<InternalRpcTransaction>>;
     async fn account_tx_in_pool_or_recent_block(
         &self,
         contract_address: ContractAddress,
     ) -> MempoolClientResult<bool>;
     async fn update_gas_price(&self, gas_price: GasPrice) -> MempoolClientResult<()>;
     async fn get_mempool_snapshot(&self) -> MempoolClientResult<MempoolSnapshot>;
+    async fn get_tx(&self, tx_hash: TransactionHash)
+        -> MempoolClientResult<InternalRpcTransaction>;
+    async fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolClientResult<MempoolTransactionStatus>;
+    /// Returns the retained mempool events whose sequence number is at least the given one.
+    async fn get_mempool_events(
+        &self,
+        from_sequence_number: u64,
+    ) -> MempoolClientResult<Vec<MempoolEvent>>;
+    /// Returns the retained records of dropped transactions that match the filter, oldest first.
+    async fn get_dropped_txs(
+        &self,
+        filter: DroppedTransactionsFilter,
+    ) -> MempoolClientResult<Vec<DroppedTransaction>>;
 }
 
 #[derive(Serialize, Deserialize, Clone, AsRefStr, EnumDiscriminants)]
@@ -64,24 +94,35 @@ pub trait MempoolClient: Send + Sync {
 pub enum MempoolRequest {
     AddTransaction(AddTransactionArgsWrapper),
     CommitBlock(CommitBlockArgs),
+    RevertBlock(RevertBlockArgs),
     GetTransactions(usize),
+    PeekTransactions(usize),
     AccountTxInPoolOrRecentBlock(ContractAddress),
     // TODO(yair): Rename to `StartBlock` and add cleanup of staged txs.
     UpdateGasPrice(GasPrice),
     GetMempoolSnapshot(),
+    GetTransaction(TransactionHash),
+    GetTransactionStatus(TransactionHash),
+    GetMempoolEvents(u64),
+    GetDroppedTransactions(DroppedTransactionsFilter),
 }
 impl_debug_for_infra_requests_and_responses!(MempoolRequest);
 impl_labeled_request!(MempoolRequest, MempoolRequestLabelValue);
 impl PrioritizedRequest for MempoolRequest {
     fn priority(&self) -> RequestPriority {
         match self {
-            MempoolRequest::CommitBlock(_) | MempoolRequest::GetTransactions(_) => {
-                RequestPriority::High
-            }
+            MempoolRequest::CommitBlock(_)
+            | MempoolRequest::RevertBlock(_)
+            | MempoolRequest::GetTransactions(_) => RequestPriority::High,
             MempoolRequest::AddTransaction(_)
+            | MempoolRequest::PeekTransactions(_)
             | MempoolRequest::AccountTxInPoolOrRecentBlock(_)
             | MempoolRequest::UpdateGasPrice(_)
-            | MempoolRequest::GetMempoolSnapshot() => RequestPriority::Normal,
+            | MempoolRequest::GetMempoolSnapshot()
+            | MempoolRequest::GetTransaction(_)
+            | MempoolRequest::GetTransactionStatus(_)
+            | MempoolRequest::GetMempoolEvents(_)
+            | MempoolRequest::GetDroppedTransactions(_) => RequestPriority::Normal,
         }
     }
 }
@@ -90,10 +131,16 @@ impl PrioritizedRequest for MempoolRequest {
 pub enum MempoolResponse {
     AddTransaction(MempoolResult<()>),
     CommitBlock(MempoolResult<()>),
+    RevertBlock(MempoolResult<()>),
     GetTransactions(MempoolResult<Vec<InternalRpcTransaction>>),
+    PeekTransactions(MempoolResult<Vec<InternalRpcTransaction>>),
     AccountTxInPoolOrRecentBlock(MempoolResult<bool>),
     UpdateGasPrice(MempoolResult<()>),
     GetMempoolSnapshot(MempoolResult<MempoolSnapshot>),
+    GetTransaction(MempoolResult<InternalRpcTransaction>),
+    GetTransactionStatus(MempoolResult<MempoolTransactionStatus>),
+    GetMempoolEvents(MempoolResult<Vec<MempoolEvent>>),
+    GetDroppedTransactions(MempoolResult<Vec<DroppedTransaction>>),
 }
 impl_debug_for_infra_requests_and_responses!(MempoolResponse);
 
@@ -132,6 +179,17 @@ where
         )
     }
 
+    async fn revert_block(&self, args: RevertBlockArgs) -> MempoolClientResult<()> {
+        let request = MempoolRequest::RevertBlock(args);
+        handle_all_response_variants!(
+            MempoolResponse,
+            RevertBlock,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
     async fn get_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<InternalRpcTransaction>> {
         let request = MempoolRequest::GetTransactions(n_txs);
         handle_all_response_variants!(
@@ -143,6 +201,17 @@ where
         )
     }
 
+    async fn peek_txs(&self, n_txs: usize) -> MempoolClientResult<Vec<InternalRpcTransaction>> {
+        let request = MempoolRequest::PeekTransactions(n_txs);
+        handle_all_response_variants!(
+            MempoolResponse,
+            PeekTransactions,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
     async fn account_tx_in_pool_or_recent_block(
         &self,
         account_address: ContractAddress,
@@ -178,4 +247,60 @@ where
             Direct
         )
     }
+
+    async fn get_tx(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolClientResult<InternalRpcTransaction> {
+        let request = MempoolRequest::GetTransaction(tx_hash);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetTransaction,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
+    async fn get_tx_status(
+        &self,
+        tx_hash: TransactionHash,
+    ) -> MempoolClientResult<MempoolTransactionStatus> {
+        let request = MempoolRequest::GetTransactionStatus(tx_hash);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetTransactionStatus,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
+    async fn get_mempool_events(
+        &self,
+        from_sequence_number: u64,
+    ) -> MempoolClientResult<Vec<MempoolEvent>> {
+        let request = MempoolRequest::GetMempoolEvents(from_sequence_number);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetMempoolEvents,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
+
+    async fn get_dropped_txs(
+        &self,
+        filter: DroppedTransactionsFilter,
+    ) -> MempoolClientResult<Vec<DroppedTransaction>> {
+        let request = MempoolRequest::GetDroppedTransactions(filter);
+        handle_all_response_variants!(
+            MempoolResponse,
+            GetDroppedTransactions,
+            MempoolClientError,
+            MempoolError,
+            Direct
+        )
+    }
 }
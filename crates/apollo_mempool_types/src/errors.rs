@@ -19,4 +19,18 @@ pub enum MempoolError {
     TransactionNotFound { tx_hash: TransactionHash },
     #[error("Transaction rejected: mempool capacity exceeded.")]
     MempoolFull,
+    #[error("Account {address} exceeded the limit of {max_txs} transactions in the mempool.")]
+    AccountTxLimitExceeded { address: ContractAddress, max_txs: usize },
+    #[error(
+        "Transaction nonce is too far ahead of the account nonce. Account nonce: {account_nonce}, \
+         got: {tx_nonce}, maximal distance: {max_nonce_distance}."
+    )]
+    NonceDistanceExceeded {
+        address: ContractAddress,
+        tx_nonce: Nonce,
+        account_nonce: Nonce,
+        max_nonce_distance: u64,
+    },
+    #[error("Account {address} exceeded its share of the mempool capacity.")]
+    AccountCapacityExceeded { address: ContractAddress },
 }
@@ -38,6 +38,15 @@ pub struct CommitBlockArgs {
     pub rejected_tx_hashes: IndexSet<TransactionHash>,
 }
 
+#[derive(Clone, Debug, Default, PartialEq, Serialize, Deserialize)]
+pub struct RevertBlockArgs {
+    /// The nonces, as of right before the reverted block, of the accounts whose nonce the block
+    /// changed.
+    pub address_to_nonce: HashMap<ContractAddress, Nonce>,
+    /// The account transactions included in the reverted block, returned to the mempool.
+    pub reverted_txs: Vec<InternalRpcTransaction>,
+}
+
 pub type MempoolResult<T> = Result<T, MempoolError>;
 
 #[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
@@ -61,6 +70,117 @@ pub struct MempoolStateSnapshot {
     pub staged: HashMap<ContractAddress, Nonce>,
 }
 
+/// The status of a transaction that was received by the mempool and was not yet committed.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+pub struct MempoolTransactionStatus {
+    pub tx_hash: TransactionHash,
+    /// Unix timestamp (in seconds) of the transaction's arrival to the mempool.
+    pub submission_time: u64,
+    pub eligibility: TransactionEligibility,
+}
+
+/// Whether a transaction can currently be sequenced, and if not, why.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionEligibility {
+    /// Queued for sequencing; `queue_position` is the number of transactions ahead of it.
+    Ready { queue_position: usize },
+    /// Given to the batcher for the block currently being built.
+    Staged,
+    /// Queued, but its max L2 gas price is below the current threshold; `queue_position` is the
+    /// number of pending transactions ahead of it.
+    GasPriceBelowThreshold { queue_position: usize, gas_price_threshold: GasPrice },
+    /// Waiting for transactions of the same account with lower nonces to be sequenced;
+    /// `pool_position` is the number of such transactions in the pool.
+    AwaitingPreviousNonce { pool_position: usize },
+    /// The account nonce is lower than the lowest nonce the mempool holds for the account;
+    /// `pool_position` is the number of transactions of the account with lower nonces in the pool.
+    NonceGap { pool_position: usize },
+    /// A declare transaction that is held back until the declare delay passes; `pool_position` is
+    /// the number of transactions of the account with lower nonces in the pool.
+    DeclareDelay { pool_position: usize },
+}
+
+/// A lifecycle event of a single mempool transaction.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MempoolEvent {
+    /// Strictly increasing across events; a skipped value means the consumer missed events.
+    pub sequence_number: u64,
+    /// Unix timestamp (in milliseconds) of the event.
+    pub timestamp: u64,
+    pub tx_hash: TransactionHash,
+    pub address: ContractAddress,
+    pub nonce: Nonce,
+    pub kind: MempoolEventKind,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum MempoolEventKind {
+    /// Accepted by the mempool.
+    Added,
+    /// Removed in favor of a transaction with the same nonce and higher fees.
+    Replaced { replacement_tx_hash: TransactionHash },
+    /// Removed to make space for incoming transactions.
+    Evicted,
+    /// Removed since its time-to-live has passed.
+    Expired,
+    /// Given to the batcher for the block currently being built.
+    Staged,
+    /// Included in a committed block.
+    Committed,
+    /// Rejected by the batcher while building a block.
+    Rejected,
+}
+
+/// A record of a transaction that was removed from the mempool without being committed.
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DroppedTransaction {
+    pub tx_hash: TransactionHash,
+    pub address: ContractAddress,
+    pub nonce: Nonce,
+    pub reason: TransactionDropReason,
+    /// Unix timestamp (in milliseconds) of the removal.
+    pub timestamp: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TransactionDropReason {
+    /// Its time-to-live has passed.
+    Expired,
+    /// Evicted to make space for incoming transactions.
+    Evicted,
+    /// Replaced by a transaction with the same nonce and higher fees.
+    Replaced { replacement_tx_hash: TransactionHash },
+    /// Rejected by the batcher while building a block.
+    Rejected,
+}
+
+impl From<TransactionDropReason> for MempoolEventKind {
+    fn from(reason: TransactionDropReason) -> Self {
+        match reason {
+            TransactionDropReason::Expired => MempoolEventKind::Expired,
+            TransactionDropReason::Evicted => MempoolEventKind::Evicted,
+            TransactionDropReason::Replaced { replacement_tx_hash } => {
+                MempoolEventKind::Replaced { replacement_tx_hash }
+            }
+            TransactionDropReason::Rejected => MempoolEventKind::Rejected,
+        }
+    }
+}
+
+/// Selects dropped transaction records; unset fields match all records.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct DroppedTransactionsFilter {
+    pub tx_hash: Option<TransactionHash>,
+    pub address: Option<ContractAddress>,
+}
+
+impl DroppedTransactionsFilter {
+    pub fn matches(&self, dropped_tx: &DroppedTransaction) -> bool {
+        self.tx_hash.is_none_or(|tx_hash| tx_hash == dropped_tx.tx_hash)
+            && self.address.is_none_or(|address| address == dropped_tx.address)
+    }
+}
+
 generate_permutation_labels! {
     MEMPOOL_REQUEST_LABELS,
     (LABEL_NAME_REQUEST_VARIANT, MempoolRequestLabelValue),
@@ -1,17 +1,43 @@
 use std::net::SocketAddr;
+use std::time::Duration;
 
+use apollo_batcher_types::batcher_types::{
+    ExecutionProfile,
+    ExecutionProfilesFilter,
+    PreconfirmedTransactionStatus,
+};
+use apollo_batcher_types::communication::{
+    BatcherClientError,
+    SharedBatcherClient,
+    SharedPreconfirmedBlockSource,
+};
+use apollo_batcher_types::errors::BatcherError;
+use apollo_gateway_types::communication::SharedGatewayClient;
+use apollo_gateway_types::gateway_types::GatewayPolicy;
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_infra_utils::type_name::short_type_name;
 use apollo_l1_provider_types::{L1ProviderSnapshot, SharedL1ProviderClient};
 use apollo_mempool_types::communication::SharedMempoolClient;
-use apollo_mempool_types::mempool_types::MempoolSnapshot;
+use apollo_mempool_types::mempool_types::{
+    DroppedTransaction,
+    DroppedTransactionsFilter,
+    MempoolSnapshot,
+};
 use apollo_metrics::metrics::COLLECT_SEQUENCER_PROFILING_METRICS;
+use apollo_protobuf::consensus::{EvidenceFilter, SharedEvidenceSource};
+use axum::extract::Query;
 use axum::http::StatusCode;
+use axum::response::sse::{Event, KeepAlive, Sse};
 use axum::response::{IntoResponse, Response};
 use axum::routing::get;
 use axum::{async_trait, Json, Router, Server};
+use futures::stream::BoxStream;
+use futures::{stream, StreamExt};
 use hyper::Error;
 use metrics_exporter_prometheus::{PrometheusBuilder, PrometheusHandle};
+use serde::Deserialize;
+use serde_json::Value;
+use starknet_api::transaction::TransactionHash;
 use tracing::{error, info, instrument};
 
 use crate::config::MonitoringEndpointConfig;
@@ -26,25 +52,47 @@ pub(crate) const READY: &str = "ready";
 pub(crate) const VERSION: &str = "nodeVersion";
 pub(crate) const METRICS: &str = "metrics";
 pub(crate) const MEMPOOL_SNAPSHOT: &str = "mempoolSnapshot";
+pub(crate) const MEMPOOL_EVENTS: &str = "mempoolEvents";
+pub(crate) const DROPPED_TRANSACTIONS: &str = "droppedTransactions";
 pub(crate) const L1_PROVIDER_SNAPSHOT: &str = "l1ProviderSnapshot";
+pub(crate) const GATEWAY_POLICY: &str = "gatewayPolicy";
+pub(crate) const EXECUTION_PROFILES: &str = "executionProfiles";
+pub(crate) const PRE_CONFIRMED_BLOCKS: &str = "preConfirmedBlocks";
+pub(crate) const PRE_CONFIRMED_TRANSACTION_STATUS: &str = "preConfirmedTransactionStatus";
+pub(crate) const CONSENSUS_EVIDENCE: &str = "consensusEvidence";
+
+// How often the mempool is polled for new events while streaming them.
+const MEMPOOL_EVENTS_POLL_INTERVAL: Duration = Duration::from_millis(100);
+// How often a remote batcher is polled for its latest pre-confirmed block while streaming them.
+const PRE_CONFIRMED_BLOCKS_POLL_INTERVAL: Duration = Duration::from_millis(100);
 
 const HISTOGRAM_BUCKETS: &[f64] =
     &[0.001, 0.0025, 0.005, 0.01, 0.025, 0.05, 0.1, 0.25, 0.5, 1.0, 2.5, 5.0, 10.0, 25.0, 50.0];
 
+/// The components whose state the monitoring endpoint serves. The routes of an absent component
+/// fail.
+#[derive(Clone, Default)]
+pub struct MonitoringEndpointSources {
+    pub mempool_client: Option<SharedMempoolClient>,
+    pub l1_provider_client: Option<SharedL1ProviderClient>,
+    pub gateway_client: Option<SharedGatewayClient>,
+    pub batcher_client: Option<SharedBatcherClient>,
+    pub pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
+    pub evidence_source: Option<SharedEvidenceSource>,
+}
+
 pub struct MonitoringEndpoint {
     config: MonitoringEndpointConfig,
     version: &'static str,
     prometheus_handle: Option<PrometheusHandle>,
-    mempool_client: Option<SharedMempoolClient>,
-    l1_provider_client: Option<SharedL1ProviderClient>,
+    sources: MonitoringEndpointSources,
 }
 
 impl MonitoringEndpoint {
     pub fn new(
         config: MonitoringEndpointConfig,
         version: &'static str,
-        mempool_client: Option<SharedMempoolClient>,
-        l1_provider_client: Option<SharedL1ProviderClient>,
+        sources: MonitoringEndpointSources,
     ) -> Self {
         // TODO(Tsabary): consider error handling
         let prometheus_handle = if config.collect_metrics {
@@ -64,13 +112,7 @@ impl MonitoringEndpoint {
         } else {
             None
         };
-        MonitoringEndpoint {
-            config,
-            version,
-            prometheus_handle,
-            mempool_client,
-            l1_provider_client,
-        }
+        MonitoringEndpoint { config, version, prometheus_handle, sources }
     }
 
     #[instrument(
@@ -93,8 +135,18 @@ impl MonitoringEndpoint {
     fn app(&self) -> Router {
         let version = self.version.to_string();
         let prometheus_handle = self.prometheus_handle.clone();
-        let mempool_client = self.mempool_client.clone();
-        let l1_provider_client = self.l1_provider_client.clone();
+        let sources = &self.sources;
+        let mempool_client = sources.mempool_client.clone();
+        let mempool_events_client = sources.mempool_client.clone();
+        let dropped_txs_client = sources.mempool_client.clone();
+        let l1_provider_client = sources.l1_provider_client.clone();
+        let gateway_client = sources.gateway_client.clone();
+        let batcher_client = sources.batcher_client.clone();
+        let pre_confirmed_blocks_source = sources.pre_confirmed_block_source.clone();
+        let pre_confirmed_blocks_client = sources.batcher_client.clone();
+        let pre_confirmed_tx_status_source = sources.pre_confirmed_block_source.clone();
+        let pre_confirmed_tx_status_client = sources.batcher_client.clone();
+        let evidence_source = sources.evidence_source.clone();
 
         Router::new()
             .route(
@@ -117,20 +169,63 @@ impl MonitoringEndpoint {
                 format!("/{MONITORING_PREFIX}/{MEMPOOL_SNAPSHOT}").as_str(),
                 get(move || mempool_snapshot(mempool_client)),
             )
+            .route(
+                format!("/{MONITORING_PREFIX}/{MEMPOOL_EVENTS}").as_str(),
+                get(move |query: Query<MempoolEventsQuery>| {
+                    mempool_events(mempool_events_client, query)
+                }),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{DROPPED_TRANSACTIONS}").as_str(),
+                get(move |filter: Query<DroppedTransactionsFilter>| {
+                    dropped_txs(dropped_txs_client, filter)
+                }),
+            )
             .route(
                 format!("/{MONITORING_PREFIX}/{L1_PROVIDER_SNAPSHOT}").as_str(),
                 get(move || get_l1_provider_snapshot(l1_provider_client)),
             )
+            .route(
+                format!("/{MONITORING_PREFIX}/{GATEWAY_POLICY}").as_str(),
+                get(move || gateway_policy(gateway_client)),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{EXECUTION_PROFILES}").as_str(),
+                get(move |filter: Query<ExecutionProfilesFilter>| {
+                    execution_profiles(batcher_client, filter)
+                }),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{PRE_CONFIRMED_BLOCKS}").as_str(),
+                get(move || {
+                    pre_confirmed_blocks(pre_confirmed_blocks_source, pre_confirmed_blocks_client)
+                }),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{PRE_CONFIRMED_TRANSACTION_STATUS}").as_str(),
+                get(move |query: Query<PreconfirmedTransactionStatusQuery>| {
+                    pre_confirmed_transaction_status(
+                        pre_confirmed_tx_status_source,
+                        pre_confirmed_tx_status_client,
+                        query,
+                    )
+                }),
+            )
+            .route(
+                format!("/{MONITORING_PREFIX}/{CONSENSUS_EVIDENCE}").as_str(),
+                get(move |filter: Query<EvidenceFilter>| {
+                    consensus_evidence(evidence_source, filter)
+                }),
+            )
     }
 }
 
 pub fn create_monitoring_endpoint(
     config: MonitoringEndpointConfig,
     version: &'static str,
-    mempool_client: Option<SharedMempoolClient>,
-    l1_provider_client: Option<SharedL1ProviderClient>,
+    sources: MonitoringEndpointSources,
 ) -> MonitoringEndpoint {
-    MonitoringEndpoint::new(config, version, mempool_client, l1_provider_client)
+    MonitoringEndpoint::new(config, version, sources)
 }
 
 #[async_trait]
@@ -180,6 +275,80 @@ async fn mempool_snapshot(
     }
 }
 
+#[derive(Debug, Deserialize)]
+struct MempoolEventsQuery {
+    // The sequence number of the first event to stream; older events that are still retained by
+    // the mempool are streamed first.
+    #[serde(default)]
+    from: u64,
+}
+
+// Streams mempool events as server-sent events, with the event sequence number as the event id.
+// The stream ends if the mempool cannot be reached; clients resume from the last received id.
+#[instrument(level = "debug", skip(mempool_client))]
+async fn mempool_events(
+    mempool_client: Option<SharedMempoolClient>,
+    Query(query): Query<MempoolEventsQuery>,
+) -> Response {
+    let Some(client) = mempool_client else {
+        return StatusCode::METHOD_NOT_ALLOWED.into_response();
+    };
+
+    let event_batches = stream::unfold((client, query.from), |(client, from)| async move {
+        loop {
+            // Wrap the mempool client interaction with a tokio::spawn as it is NOT cancel-safe,
+            // see `mempool_snapshot`.
+            let spawned_client = client.clone();
+            let mempool_events_result =
+                tokio::spawn(async move { spawned_client.get_mempool_events(from).await })
+                    .await
+                    .expect("Should be able to get mempool events result");
+
+            match mempool_events_result {
+                Ok(events) => match events.last() {
+                    Some(last_event) => {
+                        let next_from = last_event.sequence_number + 1;
+                        return Some((events, (client, next_from)));
+                    }
+                    None => tokio::time::sleep(MEMPOOL_EVENTS_POLL_INTERVAL).await,
+                },
+                Err(err) => {
+                    error!("Failed to get mempool events: {:?}", err);
+                    return None;
+                }
+            }
+        }
+    });
+    let events = event_batches
+        .flat_map(stream::iter)
+        .map(|event| Event::default().id(event.sequence_number.to_string()).json_data(event));
+
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
+
+// Returns the records of recently dropped mempool transactions that match the `tx_hash` and
+// `address` query parameters, if given.
+#[instrument(level = "debug", skip(mempool_client))]
+async fn dropped_txs(
+    mempool_client: Option<SharedMempoolClient>,
+    Query(filter): Query<DroppedTransactionsFilter>,
+) -> Result<Json<Vec<DroppedTransaction>>, StatusCode> {
+    let Some(client) = mempool_client else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+
+    // Wrap the mempool client interaction with a tokio::spawn as it is NOT cancel-safe, see
+    // `mempool_snapshot`.
+    let dropped_txs_result = tokio::spawn(async move { client.get_dropped_txs(filter).await })
+        .await
+        .expect("Should be able to get dropped transactions result");
+
+    dropped_txs_result.map(Json).map_err(|err| {
+        error!("Failed to get dropped transactions: {:?}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
 // Returns L1 provider snapshot
 #[instrument(level = "debug", skip(l1_provider_client))]
 async fn get_l1_provider_snapshot(
@@ -206,3 +375,148 @@ async fn get_l1_provider_snapshot(
         None => Err(StatusCode::METHOD_NOT_ALLOWED),
     }
 }
+
+// Returns the admission policy currently applied by the gateway.
+#[instrument(level = "debug", skip(gateway_client))]
+async fn gateway_policy(
+    gateway_client: Option<SharedGatewayClient>,
+) -> Result<Json<GatewayPolicy>, StatusCode> {
+    let Some(client) = gateway_client else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+
+    // Wrap the gateway client interaction with a tokio::spawn as it is NOT cancel-safe, see
+    // `mempool_snapshot`.
+    let gateway_policy_result = tokio::spawn(async move { client.get_policy().await })
+        .await
+        .expect("Should be able to get gateway policy result");
+
+    gateway_policy_result.map(Json).map_err(|err| {
+        error!("Failed to get gateway policy: {:?}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+// Returns the execution profiles of the recent proposals that match the `height` and
+// `proposal_id` query parameters, if given.
+#[instrument(level = "debug", skip(batcher_client))]
+async fn execution_profiles(
+    batcher_client: Option<SharedBatcherClient>,
+    Query(filter): Query<ExecutionProfilesFilter>,
+) -> Result<Json<Vec<ExecutionProfile>>, StatusCode> {
+    let Some(client) = batcher_client else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+
+    // Wrap the batcher client interaction with a tokio::spawn as it is NOT cancel-safe, see
+    // `mempool_snapshot`.
+    let execution_profiles_result =
+        tokio::spawn(async move { client.get_execution_profiles(filter).await })
+            .await
+            .expect("Should be able to get execution profiles result");
+
+    execution_profiles_result.map(Json).map_err(|err| {
+        error!("Failed to get execution profiles: {:?}", err);
+        StatusCode::INTERNAL_SERVER_ERROR
+    })
+}
+
+// Streams the batcher's pre-confirmed blocks as server-sent events, starting with the latest one.
+// Available only if the batcher publishes its pre-confirmed blocks in-process. If the batcher runs
+// in another node, it is polled through its client, and the stream ends if it cannot be reached.
+#[instrument(level = "debug", skip(pre_confirmed_block_source, batcher_client))]
+async fn pre_confirmed_blocks(
+    pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
+    batcher_client: Option<SharedBatcherClient>,
+) -> Response {
+    let blocks = match (pre_confirmed_block_source, batcher_client) {
+        (Some(source), _) => source.subscribe(),
+        (None, Some(client)) => poll_pre_confirmed_blocks(client),
+        (None, None) => return StatusCode::METHOD_NOT_ALLOWED.into_response(),
+    };
+
+    let events = blocks.map(|block| Event::default().json_data(block));
+    Sse::new(events).keep_alive(KeepAlive::default()).into_response()
+}
+
+// Yields the latest pre-confirmed block of the batcher whenever it changes.
+fn poll_pre_confirmed_blocks(client: SharedBatcherClient) -> BoxStream<'static, Value> {
+    stream::unfold((client, None), |(client, last_block)| async move {
+        loop {
+            // Wrap the batcher client interaction with a tokio::spawn as it is NOT cancel-safe,
+            // see `mempool_snapshot`.
+            let spawned_client = client.clone();
+            let latest_block_result =
+                tokio::spawn(async move { spawned_client.get_latest_preconfirmed_block().await })
+                    .await
+                    .expect("Should be able to get latest pre-confirmed block result");
+
+            match latest_block_result {
+                Ok(Some(block)) if last_block.as_ref() != Some(&block) => {
+                    return Some((block.clone(), (client, Some(block))));
+                }
+                Ok(_) => tokio::time::sleep(PRE_CONFIRMED_BLOCKS_POLL_INTERVAL).await,
+                Err(err) => {
+                    error!("Failed to get the latest pre-confirmed block: {:?}", err);
+                    return None;
+                }
+            }
+        }
+    })
+    .boxed()
+}
+
+#[derive(Debug, Deserialize)]
+struct PreconfirmedTransactionStatusQuery {
+    tx_hash: TransactionHash,
+}
+
+// Returns the status of the transaction in the batcher's latest pre-confirmed block, or 404 if it
+// is not there. If the batcher runs in another node, it is queried through its client.
+#[instrument(level = "debug", skip(pre_confirmed_block_source, batcher_client))]
+async fn pre_confirmed_transaction_status(
+    pre_confirmed_block_source: Option<SharedPreconfirmedBlockSource>,
+    batcher_client: Option<SharedBatcherClient>,
+    Query(query): Query<PreconfirmedTransactionStatusQuery>,
+) -> Result<Json<PreconfirmedTransactionStatus>, StatusCode> {
+    if let Some(source) = pre_confirmed_block_source {
+        return source.transaction_status(query.tx_hash).map(Json).ok_or(StatusCode::NOT_FOUND);
+    }
+    let Some(client) = batcher_client else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+
+    // Wrap the batcher client interaction with a tokio::spawn as it is NOT cancel-safe, see
+    // `mempool_snapshot`.
+    let status_result =
+        tokio::spawn(
+            async move { client.get_preconfirmed_transaction_status(query.tx_hash).await },
+        )
+        .await
+        .expect("Should be able to get pre-confirmed transaction status result");
+
+    match status_result {
+        Ok(status) => status.map(Json).ok_or(StatusCode::NOT_FOUND),
+        Err(BatcherClientError::BatcherError(BatcherError::PreconfirmedBlocksNotInProcess)) => {
+            Err(StatusCode::METHOD_NOT_ALLOWED)
+        }
+        Err(err) => {
+            error!("Failed to get pre-confirmed transaction status: {:?}", err);
+            Err(StatusCode::INTERNAL_SERVER_ERROR)
+        }
+    }
+}
+
+// Returns the evidence of the equivocations detected by consensus that match the `height` and
+// `offender` query parameters, if given. Available only if consensus runs in this node.
+#[instrument(level = "debug", skip(evidence_source))]
+async fn consensus_evidence(
+    evidence_source: Option<SharedEvidenceSource>,
+    Query(filter): Query<EvidenceFilter>,
+) -> Result<Json<Vec<Value>>, StatusCode> {
+    let Some(evidence_source) = evidence_source else {
+        return Err(StatusCode::METHOD_NOT_ALLOWED);
+    };
+
+    Ok(Json(evidence_source.get_evidence(&filter)))
+}
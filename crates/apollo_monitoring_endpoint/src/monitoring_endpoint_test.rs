@@ -2,24 +2,49 @@ use std::collections::HashMap;
 use std::net::IpAddr;
 use std::sync::Arc;
 
+use apollo_batcher_types::batcher_types::{
+    BlockCloseReason,
+    BouncerDimension,
+    ExecutionProfile,
+    ExecutionProfilesFilter,
+    PreconfirmedTransactionStatus,
+    ProposalId,
+};
+use apollo_batcher_types::communication::{
+    BatcherClientError,
+    MockBatcherClient,
+    MockPreconfirmedBlockSource,
+};
+use apollo_batcher_types::errors::BatcherError;
+use apollo_gateway_types::communication::MockGatewayClient;
+use apollo_gateway_types::gateway_types::GatewayPolicy;
+use apollo_infra::component_client::ClientError;
 use apollo_l1_provider_types::{L1ProviderSnapshot, MockL1ProviderClient};
-use apollo_mempool_types::communication::MockMempoolClient;
+use apollo_mempool_types::communication::{MempoolClientError, MockMempoolClient};
 use apollo_mempool_types::mempool_types::{
+    DroppedTransaction,
+    DroppedTransactionsFilter,
+    MempoolEvent,
+    MempoolEventKind,
     MempoolSnapshot,
     MempoolStateSnapshot,
+    TransactionDropReason,
     TransactionQueueSnapshot,
 };
+use apollo_protobuf::consensus::{EvidenceFilter, EvidenceSource};
 use axum::http::StatusCode;
 use axum::response::Response;
 use axum::Router;
+use blockifier::bouncer::BouncerWeights;
+use futures::{stream, StreamExt};
 use hyper::body::to_bytes;
 use hyper::Client;
 use metrics::{counter, describe_counter};
 use pretty_assertions::assert_eq;
-use serde_json::{from_slice, to_value, Value};
+use serde_json::{from_slice, json, to_value, Value};
 use starknet_api::block::{BlockNumber, GasPrice};
 use starknet_api::core::{ContractAddress, Nonce};
-use starknet_api::{nonce, tx_hash};
+use starknet_api::{contract_address, nonce, tx_hash};
 use tokio::spawn;
 use tokio::task::yield_now;
 use tower::ServiceExt;
@@ -29,10 +54,18 @@ use crate::config::{MONITORING_ENDPOINT_DEFAULT_IP, MONITORING_ENDPOINT_DEFAULT_
 use crate::monitoring_endpoint::{
     create_monitoring_endpoint,
     MonitoringEndpoint,
+    MonitoringEndpointSources,
     ALIVE,
+    CONSENSUS_EVIDENCE,
+    DROPPED_TRANSACTIONS,
+    EXECUTION_PROFILES,
+    GATEWAY_POLICY,
     L1_PROVIDER_SNAPSHOT,
+    MEMPOOL_EVENTS,
     MEMPOOL_SNAPSHOT,
     METRICS,
+    PRE_CONFIRMED_BLOCKS,
+    PRE_CONFIRMED_TRANSACTION_STATUS,
     READY,
     VERSION,
 };
@@ -51,7 +84,7 @@ const CONFIG_WITHOUT_METRICS: MonitoringEndpointConfig = MonitoringEndpointConfi
 
 fn setup_monitoring_endpoint(config: Option<MonitoringEndpointConfig>) -> MonitoringEndpoint {
     let config = config.unwrap_or(CONFIG_WITHOUT_METRICS);
-    create_monitoring_endpoint(config, TEST_VERSION, None, None)
+    create_monitoring_endpoint(config, TEST_VERSION, MonitoringEndpointSources::default())
 }
 
 async fn request_app(app: Router, method: &str) -> Response {
@@ -132,8 +165,10 @@ fn setup_monitoring_endpoint_with_mempool_client() -> MonitoringEndpoint {
     create_monitoring_endpoint(
         CONFIG_WITHOUT_METRICS,
         TEST_VERSION,
-        Some(shared_mock_mempool_client),
-        None,
+        MonitoringEndpointSources {
+            mempool_client: Some(shared_mock_mempool_client),
+            ..Default::default()
+        },
     )
 }
 
@@ -184,6 +219,109 @@ async fn mempool_not_present() {
     assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 }
 
+fn expected_mempool_events() -> Vec<MempoolEvent> {
+    let event = |sequence_number: u64, kind| MempoolEvent {
+        sequence_number,
+        timestamp: 1_000 + sequence_number,
+        tx_hash: tx_hash!(1),
+        address: contract_address!("0x1"),
+        nonce: nonce!(0),
+        kind,
+    };
+    vec![event(1, MempoolEventKind::Added), event(2, MempoolEventKind::Staged)]
+}
+
+#[tokio::test]
+async fn mempool_events() {
+    let mut mock_mempool_client = MockMempoolClient::new();
+    // Serve the events once, then fail, which ends the stream.
+    mock_mempool_client.expect_get_mempool_events().returning(|from| match from {
+        1 => Ok(expected_mempool_events()),
+        _ => Err(MempoolClientError::ClientError(ClientError::CommunicationFailure(
+            "Mempool is down.".to_string(),
+        ))),
+    });
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            mempool_client: Some(Arc::new(mock_mempool_client)),
+            ..Default::default()
+        },
+    )
+    .app();
+
+    let response = request_app(app, &format!("{MEMPOOL_EVENTS}?from=1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+    let received_ids: Vec<&str> =
+        body.lines().filter_map(|line| line.strip_prefix("id:")).collect();
+    let received_events: Vec<MempoolEvent> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| serde_json::from_str(data).expect("Failed to parse JSON string"))
+        .collect();
+    assert_eq!(received_ids, vec!["1", "2"]);
+    assert_eq!(received_events, expected_mempool_events());
+}
+
+#[tokio::test]
+async fn mempool_events_without_mempool() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, MEMPOOL_EVENTS).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+fn expected_dropped_txs() -> Vec<DroppedTransaction> {
+    vec![DroppedTransaction {
+        tx_hash: tx_hash!(1),
+        address: contract_address!("0x1"),
+        nonce: nonce!(0),
+        reason: TransactionDropReason::Replaced { replacement_tx_hash: tx_hash!(2) },
+        timestamp: 1_000,
+    }]
+}
+
+#[tokio::test]
+async fn dropped_txs() {
+    let mut mock_mempool_client = MockMempoolClient::new();
+    let expected_filter = DroppedTransactionsFilter {
+        tx_hash: Some(tx_hash!(1)),
+        address: Some(contract_address!("0x1")),
+    };
+    mock_mempool_client
+        .expect_get_dropped_txs()
+        .withf(move |filter| *filter == expected_filter)
+        .returning(|_| Ok(expected_dropped_txs()));
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            mempool_client: Some(Arc::new(mock_mempool_client)),
+            ..Default::default()
+        },
+    )
+    .app();
+
+    let response =
+        request_app(app, &format!("{DROPPED_TRANSACTIONS}?tx_hash=0x1&address=0x1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+
+    let received_dropped_txs: Vec<DroppedTransaction> =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_dropped_txs, expected_dropped_txs());
+}
+
+#[tokio::test]
+async fn dropped_txs_without_mempool() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, DROPPED_TRANSACTIONS).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
 fn setup_monitoring_endpoint_with_l1_provider_client() -> MonitoringEndpoint {
     let mut l1_provider_client = MockL1ProviderClient::new();
     l1_provider_client
@@ -194,8 +332,10 @@ fn setup_monitoring_endpoint_with_l1_provider_client() -> MonitoringEndpoint {
     create_monitoring_endpoint(
         CONFIG_WITHOUT_METRICS,
         TEST_VERSION,
-        None,
-        Some(shared_mock_l1_provider_client),
+        MonitoringEndpointSources {
+            l1_provider_client: Some(shared_mock_l1_provider_client),
+            ..Default::default()
+        },
     )
 }
 
@@ -244,3 +384,302 @@ async fn l1_provider_not_present() {
     let response = request_app(app, L1_PROVIDER_SNAPSHOT).await;
     assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
 }
+
+fn expected_gateway_policy() -> GatewayPolicy {
+    GatewayPolicy {
+        block_declare: true,
+        authorized_declarer_accounts: Some(vec![contract_address!("0x1")]),
+        denied_sender_accounts: vec![contract_address!("0x2")],
+        min_gas_price_percentage: 80,
+    }
+}
+
+#[tokio::test]
+async fn gateway_policy() {
+    let mut mock_gateway_client = MockGatewayClient::new();
+    mock_gateway_client.expect_get_policy().returning(|| Ok(expected_gateway_policy()));
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            gateway_client: Some(Arc::new(mock_gateway_client)),
+            ..Default::default()
+        },
+    )
+    .app();
+
+    let response = request_app(app, GATEWAY_POLICY).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+
+    let received_policy: GatewayPolicy =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_policy, expected_gateway_policy());
+}
+
+#[tokio::test]
+async fn gateway_policy_without_gateway() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, GATEWAY_POLICY).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+fn expected_execution_profiles() -> Vec<ExecutionProfile> {
+    vec![ExecutionProfile {
+        proposal_id: ProposalId(1),
+        height: BlockNumber(2),
+        is_validator: false,
+        close_reason: BlockCloseReason::BlockFull {
+            exceeded_dimensions: vec![BouncerDimension::SierraGas],
+        },
+        bouncer_weights: BouncerWeights::empty(),
+        block_capacity: BouncerWeights::max(),
+        n_rejected_txs: 0,
+        txs: vec![],
+    }]
+}
+
+#[tokio::test]
+async fn execution_profiles() {
+    let mut mock_batcher_client = MockBatcherClient::new();
+    mock_batcher_client
+        .expect_get_execution_profiles()
+        .withf(|filter| {
+            *filter
+                == ExecutionProfilesFilter {
+                    height: Some(BlockNumber(2)),
+                    proposal_id: Some(ProposalId(1)),
+                }
+        })
+        .returning(|_| Ok(expected_execution_profiles()));
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            batcher_client: Some(Arc::new(mock_batcher_client)),
+            ..Default::default()
+        },
+    )
+    .app();
+
+    let response = request_app(app, &format!("{EXECUTION_PROFILES}?height=2&proposal_id=1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+
+    let received_profiles: Vec<ExecutionProfile> =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_profiles, expected_execution_profiles());
+}
+
+#[tokio::test]
+async fn execution_profiles_without_batcher() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, EXECUTION_PROFILES).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+fn expected_pre_confirmed_blocks() -> Vec<Value> {
+    vec![
+        json!({"block_number": 2, "round": 0, "write_iteration": 0}),
+        json!({"block_number": 2, "round": 0, "write_iteration": 1}),
+    ]
+}
+
+fn setup_monitoring_endpoint_with_pre_confirmed_block_source() -> MonitoringEndpoint {
+    let mut mock_pre_confirmed_block_source = MockPreconfirmedBlockSource::new();
+    // The stream ends after the given blocks.
+    mock_pre_confirmed_block_source
+        .expect_subscribe()
+        .returning(|| stream::iter(expected_pre_confirmed_blocks()).boxed());
+    mock_pre_confirmed_block_source.expect_transaction_status().returning(|tx_hash| {
+        (tx_hash == tx_hash!(1)).then_some(PreconfirmedTransactionStatus::PreConfirmed)
+    });
+    create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            pre_confirmed_block_source: Some(Arc::new(mock_pre_confirmed_block_source)),
+            ..Default::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn pre_confirmed_blocks() {
+    let app = setup_monitoring_endpoint_with_pre_confirmed_block_source().app();
+
+    let response = request_app(app, PRE_CONFIRMED_BLOCKS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+    let received_blocks: Vec<Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| serde_json::from_str(data).expect("Failed to parse JSON string"))
+        .collect();
+    assert_eq!(received_blocks, expected_pre_confirmed_blocks());
+}
+
+#[tokio::test]
+async fn pre_confirmed_transaction_status() {
+    let app = setup_monitoring_endpoint_with_pre_confirmed_block_source().app();
+
+    let response =
+        request_app(app.clone(), &format!("{PRE_CONFIRMED_TRANSACTION_STATUS}?tx_hash=0x1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let received_status: PreconfirmedTransactionStatus =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_status, PreconfirmedTransactionStatus::PreConfirmed);
+
+    let response =
+        request_app(app, &format!("{PRE_CONFIRMED_TRANSACTION_STATUS}?tx_hash=0x2")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+fn setup_monitoring_endpoint_with_remote_batcher() -> MonitoringEndpoint {
+    let mut mock_batcher_client = MockBatcherClient::new();
+    // A block is only streamed once even if it is polled again, and a failure ends the stream.
+    let expected_blocks = expected_pre_confirmed_blocks();
+    let mut latest_blocks =
+        vec![expected_blocks[0].clone(), expected_blocks[0].clone(), expected_blocks[1].clone()]
+            .into_iter();
+    mock_batcher_client.expect_get_latest_preconfirmed_block().returning(move || {
+        latest_blocks.next().map(Some).ok_or(BatcherClientError::ClientError(
+            ClientError::CommunicationFailure("Batcher is down.".to_string()),
+        ))
+    });
+    mock_batcher_client.expect_get_preconfirmed_transaction_status().returning(|tx_hash| {
+        Ok((tx_hash == tx_hash!(1)).then_some(PreconfirmedTransactionStatus::PreConfirmed))
+    });
+    create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            batcher_client: Some(Arc::new(mock_batcher_client)),
+            ..Default::default()
+        },
+    )
+}
+
+#[tokio::test]
+async fn pre_confirmed_blocks_from_remote_batcher() {
+    let app = setup_monitoring_endpoint_with_remote_batcher().app();
+
+    let response = request_app(app, PRE_CONFIRMED_BLOCKS).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let body = String::from_utf8(body_bytes.to_vec()).unwrap();
+
+    let received_blocks: Vec<Value> = body
+        .lines()
+        .filter_map(|line| line.strip_prefix("data:"))
+        .map(|data| serde_json::from_str(data).expect("Failed to parse JSON string"))
+        .collect();
+    assert_eq!(received_blocks, expected_pre_confirmed_blocks());
+}
+
+#[tokio::test]
+async fn pre_confirmed_transaction_status_from_remote_batcher() {
+    let app = setup_monitoring_endpoint_with_remote_batcher().app();
+
+    let response =
+        request_app(app.clone(), &format!("{PRE_CONFIRMED_TRANSACTION_STATUS}?tx_hash=0x1")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let received_status: PreconfirmedTransactionStatus =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_status, PreconfirmedTransactionStatus::PreConfirmed);
+
+    let response =
+        request_app(app, &format!("{PRE_CONFIRMED_TRANSACTION_STATUS}?tx_hash=0x2")).await;
+    assert_eq!(response.status(), StatusCode::NOT_FOUND);
+}
+
+#[tokio::test]
+async fn pre_confirmed_transaction_status_from_batcher_without_feed() {
+    let mut mock_batcher_client = MockBatcherClient::new();
+    mock_batcher_client.expect_get_preconfirmed_transaction_status().returning(|_| {
+        Err(BatcherClientError::BatcherError(BatcherError::PreconfirmedBlocksNotInProcess))
+    });
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            batcher_client: Some(Arc::new(mock_batcher_client)),
+            ..Default::default()
+        },
+    )
+    .app();
+
+    let response =
+        request_app(app, &format!("{PRE_CONFIRMED_TRANSACTION_STATUS}?tx_hash=0x1")).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+#[tokio::test]
+async fn pre_confirmed_blocks_without_feed() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app.clone(), PRE_CONFIRMED_BLOCKS).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+    let response =
+        request_app(app, &format!("{PRE_CONFIRMED_TRANSACTION_STATUS}?tx_hash=0x1")).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
+
+// Serves evidence of equivocations by the given offenders at the given heights.
+struct TestEvidenceSource(Vec<(BlockNumber, ContractAddress)>);
+
+impl EvidenceSource for TestEvidenceSource {
+    fn get_evidence(&self, filter: &EvidenceFilter) -> Vec<Value> {
+        self.0
+            .iter()
+            .filter(|(height, offender)| filter.matches(*height, *offender))
+            .map(|(height, offender)| json!({"height": height, "offender": offender}))
+            .collect()
+    }
+}
+
+#[tokio::test]
+async fn consensus_evidence() {
+    let evidence = vec![
+        (BlockNumber(1), contract_address!("0x1")),
+        (BlockNumber(1), contract_address!("0x2")),
+        (BlockNumber(2), contract_address!("0x1")),
+    ];
+    let expected_evidence: Vec<Value> = evidence
+        .iter()
+        .map(|(height, offender)| json!({"height": height, "offender": offender}))
+        .collect();
+    let app = create_monitoring_endpoint(
+        CONFIG_WITHOUT_METRICS,
+        TEST_VERSION,
+        MonitoringEndpointSources {
+            evidence_source: Some(Arc::new(TestEvidenceSource(evidence))),
+            ..Default::default()
+        },
+    )
+    .app();
+
+    let response = request_app(app.clone(), CONSENSUS_EVIDENCE).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let received_evidence: Vec<Value> =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_evidence, expected_evidence);
+
+    let response = request_app(app, &format!("{CONSENSUS_EVIDENCE}?height=1&offender=0x2")).await;
+    assert_eq!(response.status(), StatusCode::OK);
+    let body_bytes = to_bytes(response.into_body()).await.unwrap();
+    let received_evidence: Vec<Value> =
+        from_slice(&body_bytes).expect("Failed to parse JSON string");
+    assert_eq!(received_evidence, expected_evidence[1..2]);
+}
+
+#[tokio::test]
+async fn consensus_evidence_without_consensus() {
+    let app = setup_monitoring_endpoint(None).app();
+    let response = request_app(app, CONSENSUS_EVIDENCE).await;
+    assert_eq!(response.status(), StatusCode::METHOD_NOT_ALLOWED);
+}
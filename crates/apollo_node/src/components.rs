@@ -1,5 +1,7 @@
+use std::sync::Arc;
+
 use apollo_batcher::batcher::{create_batcher, Batcher};
-use apollo_batcher::pre_confirmed_cende_client::PreconfirmedCendeClient;
+use apollo_batcher::pre_confirmed_cende_client::create_pre_confirmed_cende_client;
 use apollo_class_manager::class_manager::create_class_manager;
 use apollo_class_manager::ClassManager;
 use apollo_compile_to_casm::{create_sierra_compiler, SierraCompiler};
@@ -19,7 +21,9 @@ use apollo_mempool_p2p::runner::MempoolP2pRunner;
 use apollo_monitoring_endpoint::monitoring_endpoint::{
     create_monitoring_endpoint,
     MonitoringEndpoint,
+    MonitoringEndpointSources,
 };
+use apollo_protobuf::consensus::SharedEvidenceSource;
 use apollo_signature_manager::{create_signature_manager, SignatureManager};
 use apollo_state_sync::runner::StateSyncRunner;
 use apollo_state_sync::{create_state_sync_and_runner, StateSync};
@@ -65,7 +69,7 @@ pub async fn create_node_components(
     // instead of cloning them and retaining ownership. Alternatively, consider passing references
     // to the components instead.
     info!("Creating node components.");
-    let batcher = match config.components.batcher.execution_mode {
+    let (batcher, pre_confirmed_block_source) = match config.components.batcher.execution_mode {
         ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
             let batcher_config =
@@ -78,20 +82,23 @@ pub async fn create_node_components(
             let class_manager_client = clients
                 .get_class_manager_shared_client()
                 .expect("Class Manager Client should be available");
-            let pre_confirmed_cende_client = std::sync::Arc::new(PreconfirmedCendeClient::new(
-                batcher_config.pre_confirmed_cende_config.clone(),
-            ));
-            Some(create_batcher(
+            let (pre_confirmed_cende_client, pre_confirmed_block_source) =
+                create_pre_confirmed_cende_client(
+                    batcher_config.pre_confirmed_cende_config.clone(),
+                );
+            let batcher = create_batcher(
                 batcher_config.clone(),
                 mempool_client,
                 l1_provider_client,
                 class_manager_client,
                 pre_confirmed_cende_client,
-            ))
+                pre_confirmed_block_source.clone(),
+            );
+            (Some(batcher), pre_confirmed_block_source)
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
             // TODO(tsabary): assert config is not set.
-            None
+            (None, None)
         }
     };
 
@@ -114,7 +121,7 @@ pub async fn create_node_components(
     let signature_manager = match config.components.signature_manager.execution_mode {
         ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
         | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => {
-            Some(create_signature_manager())
+            Some(create_signature_manager(&config.signature_manager_config))
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => None,
     };
@@ -183,7 +190,9 @@ pub async fn create_node_components(
             let gateway_client =
                 clients.get_gateway_shared_client().expect("Gateway Client should be available");
 
-            Some(create_http_server(http_server_config.clone(), gateway_client))
+            let mempool_client = clients.get_mempool_shared_client();
+
+            Some(create_http_server(http_server_config.clone(), gateway_client, mempool_client))
         }
         ActiveComponentExecutionMode::Disabled => {
             // TODO(tsabary): assert config is not set.
@@ -228,7 +237,14 @@ pub async fn create_node_components(
             let mempool_p2p_propagator_client = clients
                 .get_mempool_p2p_propagator_shared_client()
                 .expect("Propagator Client should be available");
-            let mempool = create_mempool(mempool_config.clone(), mempool_p2p_propagator_client);
+            let state_sync_client = clients
+                .get_state_sync_shared_client()
+                .expect("State Sync Client should be available");
+            let mempool = create_mempool(
+                mempool_config.clone(),
+                mempool_p2p_propagator_client,
+                state_sync_client,
+            );
             Some(mempool)
         }
         ReactiveComponentExecutionMode::Disabled | ReactiveComponentExecutionMode::Remote => {
@@ -266,11 +282,43 @@ pub async fn create_node_components(
                 | ReactiveComponentExecutionMode::Remote => None,
             };
 
+            let gateway_client = match config.components.gateway.execution_mode {
+                ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+                | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => Some(
+                    clients
+                        .get_gateway_shared_client()
+                        .expect("Gateway Client should be available"),
+                ),
+                ReactiveComponentExecutionMode::Disabled
+                | ReactiveComponentExecutionMode::Remote => None,
+            };
+
+            let batcher_client = match config.components.batcher.execution_mode {
+                ReactiveComponentExecutionMode::LocalExecutionWithRemoteDisabled
+                | ReactiveComponentExecutionMode::LocalExecutionWithRemoteEnabled => Some(
+                    clients
+                        .get_batcher_shared_client()
+                        .expect("Batcher Client should be available"),
+                ),
+                ReactiveComponentExecutionMode::Disabled
+                | ReactiveComponentExecutionMode::Remote => None,
+            };
+
+            let evidence_source = consensus_manager.as_ref().map(|consensus_manager| {
+                Arc::new(consensus_manager.evidence_store().clone()) as SharedEvidenceSource
+            });
+
             Some(create_monitoring_endpoint(
                 monitoring_endpoint_config.clone(),
                 VERSION_FULL,
-                mempool_client,
-                l1_provider_client,
+                MonitoringEndpointSources {
+                    mempool_client,
+                    l1_provider_client,
+                    gateway_client,
+                    batcher_client,
+                    pre_confirmed_block_source,
+                    evidence_source,
+                },
             ))
         }
         ActiveComponentExecutionMode::Disabled => {
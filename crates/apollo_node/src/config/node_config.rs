@@ -33,6 +33,7 @@ use apollo_mempool::config::MempoolConfig;
 use apollo_mempool_p2p::config::MempoolP2pConfig;
 use apollo_monitoring_endpoint::config::MonitoringEndpointConfig;
 use apollo_reverts::RevertConfig;
+use apollo_signature_manager::config::SignatureManagerConfig;
 use apollo_state_sync::config::StateSyncConfig;
 use clap::Command;
 use papyrus_base_layer::ethereum_base_layer_contract::EthereumBaseLayerConfig;
@@ -67,6 +68,7 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
                 "batcher_config.storage.db_config.chain_id",
                 "consensus_manager_config.context_config.chain_id",
                 "consensus_manager_config.network_config.chain_id",
+                "consensus_manager_config.staking_manager_config.chain_info.chain_id",
                 "gateway_config.chain_info.chain_id",
                 "l1_scraper_config.chain_id",
                 "l1_gas_price_scraper_config.chain_id",
@@ -85,6 +87,8 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
             set_pointing_param_paths(&[
                 "batcher_config.block_builder_config.chain_info.fee_token_addresses.\
                  eth_fee_token_address",
+                "consensus_manager_config.staking_manager_config.chain_info.fee_token_addresses.\
+                 eth_fee_token_address",
                 "gateway_config.chain_info.fee_token_addresses.eth_fee_token_address",
                 "state_sync_config.rpc_config.execution_config.eth_fee_contract_address",
             ]),
@@ -109,6 +113,8 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
             set_pointing_param_paths(&[
                 "batcher_config.block_builder_config.chain_info.fee_token_addresses.\
                  strk_fee_token_address",
+                "consensus_manager_config.staking_manager_config.chain_info.fee_token_addresses.\
+                 strk_fee_token_address",
                 "gateway_config.chain_info.fee_token_addresses.strk_fee_token_address",
                 "state_sync_config.rpc_config.execution_config.strk_fee_contract_address",
             ]),
@@ -152,6 +158,7 @@ pub static CONFIG_POINTERS: LazyLock<ConfigPointers> = LazyLock::new(|| {
         &VersionedConstantsOverrides::default(),
         set_pointing_param_paths(&[
             "batcher_config.block_builder_config.versioned_constants_overrides",
+            "consensus_manager_config.staking_manager_config.versioned_constants_overrides",
             "gateway_config.stateful_tx_validator_config.versioned_constants_overrides",
         ]),
     );
@@ -213,6 +220,8 @@ pub struct SequencerNodeConfig {
     #[validate]
     pub sierra_compiler_config: Option<SierraCompilationConfig>,
     #[validate]
+    pub signature_manager_config: SignatureManagerConfig,
+    #[validate]
     pub state_sync_config: Option<StateSyncConfig>,
 }
 
@@ -244,6 +253,10 @@ impl SerializeConfig for SequencerNodeConfig {
             ser_optional_sub_config(&self.l1_provider_config, "l1_provider_config"),
             ser_optional_sub_config(&self.l1_scraper_config, "l1_scraper_config"),
             ser_optional_sub_config(&self.sierra_compiler_config, "sierra_compiler_config"),
+            prepend_sub_config_name(
+                self.signature_manager_config.dump(),
+                "signature_manager_config",
+            ),
             ser_optional_sub_config(&self.state_sync_config, "state_sync_config"),
         ];
 
@@ -273,6 +286,7 @@ impl Default for SequencerNodeConfig {
             mempool_p2p_config: Some(MempoolP2pConfig::default()),
             monitoring_endpoint_config: Some(MonitoringEndpointConfig::default()),
             sierra_compiler_config: Some(SierraCompilationConfig::default()),
+            signature_manager_config: SignatureManagerConfig::default(),
             state_sync_config: Some(StateSyncConfig::default()),
         }
     }
@@ -3,13 +3,16 @@
 mod consensus_test;
 
 use std::fmt::Display;
+use std::sync::Arc;
 
 use bytes::{Buf, BufMut};
 use prost::DecodeError;
 use serde::{Deserialize, Serialize};
+use serde_json::Value;
 use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::consensus_transaction::ConsensusTransaction;
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 
 use crate::converters::ProtobufConversionError;
@@ -34,6 +37,8 @@ pub struct Vote {
     pub round: u32,
     pub block_hash: Option<BlockHash>,
     pub voter: ContractAddress,
+    /// The signature of the voter over the other fields of the vote.
+    pub signature: Signature,
 }
 
 #[derive(Debug, Clone, Hash, Eq, PartialEq)]
@@ -50,7 +55,7 @@ pub struct StreamMessage<T: IntoFromProto, StreamId: IntoFromProto + Clone> {
 }
 
 /// This message must be sent first when proposing a new block.
-#[derive(Clone, Copy, Debug, PartialEq)]
+#[derive(Clone, Copy, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ProposalInit {
     /// The height of the consensus (block number).
     pub height: BlockNumber,
@@ -60,6 +65,8 @@ pub struct ProposalInit {
     pub valid_round: Option<u32>,
     /// Address of the one who proposed the block.
     pub proposer: ContractAddress,
+    /// The signature of the proposer over the other fields of the init.
+    pub signature: Signature,
 }
 
 /// This struct differs from `BlockInfo` in `starknet_api` because we send L1 gas prices in ETH and
@@ -90,6 +97,7 @@ impl Default for ProposalInit {
             round: Default::default(),
             valid_round: Default::default(),
             proposer: ContractAddress::from(DEFAULT_VALIDATOR_ID),
+            signature: Default::default(),
         }
     }
 }
@@ -102,11 +110,13 @@ pub struct TransactionBatch {
 }
 
 /// The proposal is done when receiving this fin message, which contains the block hash.
-#[derive(Debug, Clone, PartialEq)]
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct ProposalFin {
     /// The block hash of the proposed block.
-    /// TODO(Matan): Consider changing the content ID to a signature.
     pub proposal_commitment: BlockHash,
+    /// The signature of the proposer over the commitment, together with the height and round of
+    /// the proposal.
+    pub signature: Signature,
 }
 
 /// A part of the proposal.
@@ -202,3 +212,28 @@ impl std::fmt::Display for HeightAndRound {
         write!(f, "(height={}, round={})", self.0, self.1)
     }
 }
+
+/// Selects equivocation evidence; unset fields match all evidence.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceFilter {
+    /// The height of the equivocation.
+    pub height: Option<BlockNumber>,
+    /// The validator which equivocated.
+    pub offender: Option<ContractAddress>,
+}
+
+impl EvidenceFilter {
+    /// Whether the evidence of an equivocation by `offender` at `height` is selected by the filter.
+    pub fn matches(&self, height: BlockNumber, offender: ContractAddress) -> bool {
+        self.height.is_none_or(|filter_height| filter_height == height)
+            && self.offender.is_none_or(|filter_offender| filter_offender == offender)
+    }
+}
+
+pub type SharedEvidenceSource = Arc<dyn EvidenceSource>;
+
+/// Serves the evidence of the equivocations detected by consensus, e.g. to the monitoring endpoint.
+pub trait EvidenceSource: Send + Sync {
+    /// Returns the evidence selected by the filter as JSON, in the order it was detected.
+    fn get_evidence(&self, filter: &EvidenceFilter) -> Vec<Value>;
+}
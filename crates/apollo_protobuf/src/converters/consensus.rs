@@ -7,6 +7,7 @@ use std::convert::{TryFrom, TryInto};
 use prost::Message;
 use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::consensus_transaction::ConsensusTransaction;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::hash::StarkHash;
 
 use super::common::{
@@ -60,8 +61,9 @@ impl TryFrom<protobuf::Vote> for Vote {
         let block_hash: Option<BlockHash> =
             value.block_hash.map(|block_hash| block_hash.try_into()).transpose()?.map(BlockHash);
         let voter = value.voter.ok_or(missing("voter"))?.try_into()?;
+        let signature = value.signature.ok_or(missing("signature"))?.try_into()?;
 
-        Ok(Vote { vote_type, height, round, block_hash, voter })
+        Ok(Vote { vote_type, height, round, block_hash, voter, signature })
     }
 }
 
@@ -78,12 +80,30 @@ impl From<Vote> for protobuf::Vote {
             round: value.round,
             block_hash: value.block_hash.map(|hash| hash.0.into()),
             voter: Some(value.voter.into()),
+            signature: Some(value.signature.into()),
         }
     }
 }
 
 auto_impl_into_and_try_from_vec_u8!(Vote, protobuf::Vote);
 
+impl TryFrom<protobuf::ConsensusSignature> for Signature {
+    type Error = ProtobufConversionError;
+
+    fn try_from(value: protobuf::ConsensusSignature) -> Result<Self, Self::Error> {
+        Ok(Signature {
+            r: value.r.ok_or(missing("ConsensusSignature::r"))?.try_into()?,
+            s: value.s.ok_or(missing("ConsensusSignature::s"))?.try_into()?,
+        })
+    }
+}
+
+impl From<Signature> for protobuf::ConsensusSignature {
+    fn from(value: Signature) -> Self {
+        Self { r: Some(value.r.into()), s: Some(value.s.into()) }
+    }
+}
+
 impl<T, StreamId> TryFrom<protobuf::StreamMessage> for StreamMessage<T, StreamId>
 where
     T: IntoFromProto,
@@ -173,7 +193,8 @@ impl TryFrom<protobuf::ProposalInit> for ProposalInit {
         let round = value.round;
         let valid_round = value.valid_round;
         let proposer = value.proposer.ok_or(missing("proposer"))?.try_into()?;
-        Ok(ProposalInit { height: BlockNumber(height), round, valid_round, proposer })
+        let signature = value.signature.ok_or(missing("signature"))?.try_into()?;
+        Ok(ProposalInit { height: BlockNumber(height), round, valid_round, proposer, signature })
     }
 }
 
@@ -184,6 +205,7 @@ impl From<ProposalInit> for protobuf::ProposalInit {
             round: value.round,
             valid_round: value.valid_round,
             proposer: Some(value.proposer.into()),
+            signature: Some(value.signature.into()),
         }
     }
 }
@@ -261,13 +283,17 @@ impl TryFrom<protobuf::ProposalFin> for ProposalFin {
         let proposal_commitment: StarkHash =
             value.proposal_commitment.ok_or(missing("proposal_commitment"))?.try_into()?;
         let proposal_commitment = BlockHash(proposal_commitment);
-        Ok(ProposalFin { proposal_commitment })
+        let signature = value.signature.ok_or(missing("signature"))?.try_into()?;
+        Ok(ProposalFin { proposal_commitment, signature })
     }
 }
 
 impl From<ProposalFin> for protobuf::ProposalFin {
     fn from(value: ProposalFin) -> Self {
-        protobuf::ProposalFin { proposal_commitment: Some(value.proposal_commitment.0.into()) }
+        protobuf::ProposalFin {
+            proposal_commitment: Some(value.proposal_commitment.0.into()),
+            signature: Some(value.signature.into()),
+        }
     }
 }
 
@@ -6,6 +6,7 @@ use rand::Rng;
 use starknet_api::block::{BlockHash, BlockNumber, GasPrice};
 use starknet_api::consensus_transaction::ConsensusTransaction;
 use starknet_api::core::ContractAddress;
+use starknet_api::crypto::utils::Signature;
 use starknet_api::data_availability::L1DataAvailabilityMode;
 
 use super::ProtobufConversionError;
@@ -28,6 +29,7 @@ auto_impl_get_test_instance! {
         pub round: u32,
         pub block_hash: Option<BlockHash>,
         pub voter: ContractAddress,
+        pub signature: Signature,
     }
     pub enum VoteType {
         Prevote = 0,
@@ -38,9 +40,11 @@ auto_impl_get_test_instance! {
         pub round: u32,
         pub valid_round: Option<u32>,
         pub proposer: ContractAddress,
+        pub signature: Signature,
     }
     pub struct ProposalFin {
         pub proposal_commitment: BlockHash,
+        pub signature: Signature,
     }
     pub struct TransactionBatch {
         pub transactions: Vec<ConsensusTransaction>,
@@ -371,6 +371,9 @@ pub struct Vote {
     pub block_hash: ::core::option::Option<Hash>,
     #[prost(message, optional, tag = "6")]
     pub voter: ::core::option::Option<Address>,
+    /// The signature of the voter over the other fields of the vote.
+    #[prost(message, optional, tag = "7")]
+    pub signature: ::core::option::Option<ConsensusSignature>,
 }
 /// Nested message and enum types in `Vote`.
 pub mod vote {
@@ -443,6 +446,9 @@ pub struct ProposalInit {
     pub valid_round: ::core::option::Option<u32>,
     #[prost(message, optional, tag = "4")]
     pub proposer: ::core::option::Option<Address>,
+    /// The signature of the proposer over the other fields of the init.
+    #[prost(message, optional, tag = "5")]
+    pub signature: ::core::option::Option<ConsensusSignature>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -476,6 +482,10 @@ pub struct ProposalFin {
     /// Identifies a Starknet block based on the content streamed in the proposal.
     #[prost(message, optional, tag = "1")]
     pub proposal_commitment: ::core::option::Option<Hash>,
+    /// The signature of the proposer over the commitment, together with the height and round of
+    /// the proposal.
+    #[prost(message, optional, tag = "2")]
+    pub signature: ::core::option::Option<ConsensusSignature>,
 }
 /// Network format:
 /// 1. First message is ProposalInit
@@ -395,6 +395,7 @@ fn create_block_context(
             eth_fee_token_address: execution_config.eth_fee_contract_address,
         },
         is_l3: false,
+        is_paymaster_enabled: false,
     };
     let starknet_version = storage_reader
         .begin_ro_txn()?
@@ -30,6 +30,15 @@ impl ComponentRequestHandler<SignatureManagerRequest, SignatureManagerResponse>
                     self.sign_precommit_vote(block_hash).await,
                 )
             }
+            SignatureManagerRequest::SignVote(vote) => {
+                SignatureManagerResponse::SignVote(self.sign_vote(&vote).await)
+            }
+            SignatureManagerRequest::SignProposalInit(init) => {
+                SignatureManagerResponse::SignProposalInit(self.sign_proposal_init(&init).await)
+            }
+            SignatureManagerRequest::SignProposalFin(init, fin) => {
+                SignatureManagerResponse::SignProposalFin(self.sign_proposal_fin(&init, &fin).await)
+            }
         }
     }
 }
@@ -0,0 +1,25 @@
+use std::collections::BTreeMap;
+
+use apollo_config::dumping::{ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
+use serde::{Deserialize, Serialize};
+use starknet_api::crypto::utils::PrivateKey;
+use validator::Validate;
+
+#[derive(Clone, Debug, Default, Serialize, Deserialize, Validate, PartialEq)]
+pub struct SignatureManagerConfig {
+    /// The private key which this node signs its consensus messages with. Must be set; its public
+    /// key is the one registered for this node's validator.
+    pub private_key: PrivateKey,
+}
+
+impl SerializeConfig for SignatureManagerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        BTreeMap::from_iter([ser_param(
+            "private_key",
+            &self.private_key,
+            "The private key which this node signs its consensus messages with.",
+            ParamPrivacyInput::Private,
+        )])
+    }
+}
@@ -1,4 +1,5 @@
 pub mod communication;
+pub mod config;
 pub mod metrics;
 pub mod signature_manager;
 
@@ -6,21 +7,17 @@ use std::ops::Deref;
 
 use apollo_infra::component_definitions::ComponentStarter;
 use async_trait::async_trait;
+use starknet_api::crypto::utils::PrivateKey;
 
+use crate::config::SignatureManagerConfig;
 use crate::signature_manager::{LocalKeyStore, SignatureManager as GenericSignatureManager};
 
 #[derive(Clone, Debug)]
 pub struct LocalKeyStoreSignatureManager(pub GenericSignatureManager<LocalKeyStore>);
 
 impl LocalKeyStoreSignatureManager {
-    pub fn new() -> Self {
-        Self(GenericSignatureManager::new(LocalKeyStore::new_for_testing()))
-    }
-}
-
-impl Default for LocalKeyStoreSignatureManager {
-    fn default() -> Self {
-        Self::new()
+    pub fn new(private_key: PrivateKey) -> Self {
+        Self(GenericSignatureManager::new(LocalKeyStore::new(private_key)))
     }
 }
 
@@ -34,10 +31,15 @@ impl Deref for LocalKeyStoreSignatureManager {
 
 pub use LocalKeyStoreSignatureManager as SignatureManager;
 
-// TODO(Elin): understand how key store would look in production and better define the way the
-// signature manager is created.
-pub fn create_signature_manager() -> SignatureManager {
-    SignatureManager::new()
+// TODO(Elin): consider loading the key from a secret key store instead of the config.
+pub fn create_signature_manager(config: &SignatureManagerConfig) -> SignatureManager {
+    // A zero key has no public key, and would sign messages no validator can be verified by.
+    assert_ne!(
+        config.private_key,
+        PrivateKey::default(),
+        "signature_manager_config.private_key must be set."
+    );
+    SignatureManager::new(config.private_key)
 }
 
 #[async_trait]
@@ -2,6 +2,7 @@ use std::ops::Deref;
 
 use apollo_infra::component_definitions::ComponentStarter;
 use apollo_network_types::network_types::PeerId;
+use apollo_protobuf::consensus::{ProposalFin, ProposalInit, Vote, VoteType};
 use apollo_signature_manager_types::{
     KeyStore,
     KeyStoreResult,
@@ -25,6 +26,9 @@ pub mod signature_manager_test;
 // Message domain separators.
 pub(crate) const INIT_PEER_ID: &[u8] = b"INIT_PEER_ID";
 pub(crate) const PRECOMMIT_VOTE: &[u8] = b"PRECOMMIT_VOTE";
+pub(crate) const VOTE: &[u8] = b"VOTE";
+pub(crate) const PROPOSAL_INIT: &[u8] = b"PROPOSAL_INIT";
+pub(crate) const PROPOSAL_FIN: &[u8] = b"PROPOSAL_FIN";
 
 pub type SignatureVerificationResult<T> = Result<T, SignatureVerificationError>;
 
@@ -67,6 +71,28 @@ impl<KS: KeyStore> SignatureManager<KS> {
         self.sign(message_digest).await
     }
 
+    pub async fn sign_vote(&self, vote: &Vote) -> SignatureManagerResult<RawSignature> {
+        let message_digest = build_vote_message_digest(vote);
+        self.sign(message_digest).await
+    }
+
+    pub async fn sign_proposal_init(
+        &self,
+        init: &ProposalInit,
+    ) -> SignatureManagerResult<RawSignature> {
+        let message_digest = build_proposal_init_message_digest(init);
+        self.sign(message_digest).await
+    }
+
+    pub async fn sign_proposal_fin(
+        &self,
+        init: &ProposalInit,
+        fin: &ProposalFin,
+    ) -> SignatureManagerResult<RawSignature> {
+        let message_digest = build_proposal_fin_message_digest(init, fin);
+        self.sign(message_digest).await
+    }
+
     async fn sign(&self, message_digest: MessageDigest) -> SignatureManagerResult<RawSignature> {
         let private_key = self.keystore.get_key().await?;
         let signature = ecdsa_sign(&private_key, &message_digest)
@@ -87,12 +113,12 @@ pub struct LocalKeyStore {
 }
 
 impl LocalKeyStore {
-    fn _new(private_key: PrivateKey) -> Self {
+    pub fn new(private_key: PrivateKey) -> Self {
         let public_key = PublicKey(get_public_key(&private_key));
         Self { private_key, public_key }
     }
 
-    pub(crate) const fn new_for_testing() -> Self {
+    pub const fn new_for_testing() -> Self {
         // Created using `cairo-lang`.
         const PRIVATE_KEY: PrivateKey = PrivateKey(Felt::from_hex_unchecked(
             "0x608bf2cdb1ad4138e72d2f82b8c5db9fa182d1883868ae582ed373429b7a133",
@@ -105,6 +131,12 @@ impl LocalKeyStore {
     }
 }
 
+/// Returns the private key of the `index`-th node in tests; the 0-th is the key of
+/// [`LocalKeyStore::new_for_testing`].
+pub fn private_key_for_testing(index: u64) -> PrivateKey {
+    PrivateKey(LocalKeyStore::new_for_testing().private_key.0 + Felt::from(index))
+}
+
 #[async_trait]
 impl KeyStore for LocalKeyStore {
     async fn get_key(&self) -> KeyStoreResult<PrivateKey> {
@@ -134,6 +166,55 @@ fn build_precommit_vote_message_digest(block_hash: BlockHash) -> MessageDigest {
     MessageDigest(blake2s_to_felt(&message))
 }
 
+// The signed fields of consensus messages are serialized in a fixed order, prefixed by the domain
+// separator of the message. Optional fields are prefixed by a flag, so that an absent value is
+// distinguishable from any present one.
+fn build_vote_message_digest(vote: &Vote) -> MessageDigest {
+    let mut message = VOTE.to_vec();
+    message.push(match vote.vote_type {
+        VoteType::Prevote => 0,
+        VoteType::Precommit => 1,
+    });
+    message.extend_from_slice(&vote.height.to_be_bytes());
+    message.extend_from_slice(&vote.round.to_be_bytes());
+    extend_with_optional(&mut message, vote.block_hash.map(|block_hash| block_hash.to_bytes_be()));
+    message.extend_from_slice(&Felt::from(vote.voter).to_bytes_be());
+
+    MessageDigest(blake2s_to_felt(&message))
+}
+
+fn build_proposal_init_message_digest(init: &ProposalInit) -> MessageDigest {
+    let mut message = PROPOSAL_INIT.to_vec();
+    extend_with_proposal_init(&mut message, init);
+
+    MessageDigest(blake2s_to_felt(&message))
+}
+
+fn build_proposal_fin_message_digest(init: &ProposalInit, fin: &ProposalFin) -> MessageDigest {
+    let mut message = PROPOSAL_FIN.to_vec();
+    extend_with_proposal_init(&mut message, init);
+    message.extend_from_slice(&fin.proposal_commitment.to_bytes_be());
+
+    MessageDigest(blake2s_to_felt(&message))
+}
+
+fn extend_with_proposal_init(message: &mut Vec<u8>, init: &ProposalInit) {
+    message.extend_from_slice(&init.height.0.to_be_bytes());
+    message.extend_from_slice(&init.round.to_be_bytes());
+    extend_with_optional(message, init.valid_round.map(u32::to_be_bytes));
+    message.extend_from_slice(&Felt::from(init.proposer).to_bytes_be());
+}
+
+fn extend_with_optional<const N: usize>(message: &mut Vec<u8>, value: Option<[u8; N]>) {
+    match value {
+        Some(value) => {
+            message.push(1);
+            message.extend_from_slice(&value);
+        }
+        None => message.push(0),
+    }
+}
+
 fn verify_signature(
     message_digest: MessageDigest,
     signature: RawSignature,
@@ -174,3 +255,28 @@ pub fn verify_precommit_vote_signature(
     let message_digest = build_precommit_vote_message_digest(block_hash);
     verify_signature(message_digest, signature, public_key)
 }
+
+pub fn verify_vote_signature(
+    vote: &Vote,
+    public_key: PublicKey,
+) -> SignatureVerificationResult<bool> {
+    let message_digest = build_vote_message_digest(vote);
+    verify_signature(message_digest, vote.signature.into(), public_key)
+}
+
+pub fn verify_proposal_init_signature(
+    init: &ProposalInit,
+    public_key: PublicKey,
+) -> SignatureVerificationResult<bool> {
+    let message_digest = build_proposal_init_message_digest(init);
+    verify_signature(message_digest, init.signature.into(), public_key)
+}
+
+pub fn verify_proposal_fin_signature(
+    init: &ProposalInit,
+    fin: &ProposalFin,
+    public_key: PublicKey,
+) -> SignatureVerificationResult<bool> {
+    let message_digest = build_proposal_fin_message_digest(init, fin);
+    verify_signature(message_digest, fin.signature.into(), public_key)
+}
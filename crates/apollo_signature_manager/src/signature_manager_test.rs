@@ -1,16 +1,21 @@
 use apollo_network_types::network_types::PeerId;
+use apollo_protobuf::consensus::{ProposalFin, ProposalInit, Vote, VoteType};
 use hex::FromHex;
 use pretty_assertions::assert_eq;
 use rstest::rstest;
-use starknet_api::block::BlockHash;
-use starknet_api::core::Nonce;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_api::core::{ContractAddress, Nonce};
 use starknet_api::{felt, nonce};
 use starknet_core::crypto::Signature;
 use starknet_core::types::Felt;
 
 use crate::signature_manager::{
+    private_key_for_testing,
     verify_identity,
     verify_precommit_vote_signature,
+    verify_proposal_fin_signature,
+    verify_proposal_init_signature,
+    verify_vote_signature,
     LocalKeyStore,
     SignatureManager,
 };
@@ -24,6 +29,15 @@ const ALICE_IDENTITY_SIGNATURE: Signature = Signature {
     ),
 };
 
+const ALICE_VOTE_SIGNATURE: Signature = Signature {
+    r: Felt::from_hex_unchecked(
+        "0x33aa92953a85c7c0f08c7c4db68ca2a80787b37304b9e09dda2cf3ef0a06428",
+    ),
+    s: Felt::from_hex_unchecked(
+        "0x56dae90e02911b7782502f5663b8e97e8189d26235a1429da094d9b704157b6",
+    ),
+};
+
 const ALICE_PRECOMMIT_SIGNATURE: Signature = Signature {
     r: Felt::from_hex_unchecked("0xe16ecc38c135735e8aed7ffdb150ebb956a93ec19ac53e8295cdbd04d552b2"),
     s: Felt::from_hex_unchecked(
@@ -113,3 +127,101 @@ async fn test_sign_precommit_vote() {
         true
     );
 }
+
+fn vote() -> Vote {
+    Vote {
+        vote_type: VoteType::Prevote,
+        height: 1,
+        round: 2,
+        block_hash: Some(BlockHash(felt!("0x1234"))),
+        voter: ContractAddress::from(100_u64),
+        signature: Default::default(),
+    }
+}
+
+fn proposal_init() -> ProposalInit {
+    ProposalInit {
+        height: BlockNumber(1),
+        round: 2,
+        valid_round: Some(1),
+        proposer: ContractAddress::from(100_u64),
+        signature: Default::default(),
+    }
+}
+
+#[tokio::test]
+async fn test_sign_vote() {
+    let key_store = LocalKeyStore::new_for_testing();
+    let signature_manager = SignatureManager::new(key_store);
+
+    let mut vote = vote();
+    let signature = signature_manager.sign_vote(&vote).await;
+
+    assert_eq!(signature, Ok(ALICE_VOTE_SIGNATURE.into()));
+
+    // Test alignment with verification function.
+    vote.signature = signature.unwrap().try_into().unwrap();
+    assert_eq!(verify_vote_signature(&vote, key_store.public_key).unwrap(), true);
+}
+
+#[rstest]
+#[case::vote_type(Vote { vote_type: VoteType::Precommit, ..vote() })]
+#[case::round(Vote { round: 3, ..vote() })]
+#[case::nil(Vote { block_hash: None, ..vote() })]
+#[case::zero_block_hash(Vote { block_hash: Some(BlockHash(felt!("0x0"))), ..vote() })]
+#[case::voter(Vote { voter: ContractAddress::from(101_u64), ..vote() })]
+#[tokio::test]
+async fn test_vote_signature_does_not_match_another_vote(#[case] mut other_vote: Vote) {
+    let key_store = LocalKeyStore::new_for_testing();
+    let signature_manager = SignatureManager::new(key_store);
+
+    let signature = signature_manager.sign_vote(&vote()).await.unwrap();
+
+    other_vote.signature = signature.try_into().unwrap();
+    assert_eq!(verify_vote_signature(&other_vote, key_store.public_key).unwrap(), false);
+}
+
+#[tokio::test]
+async fn test_sign_proposal_init() {
+    let key_store = LocalKeyStore::new_for_testing();
+    let signature_manager = SignatureManager::new(key_store);
+
+    let mut init = proposal_init();
+    let signature = signature_manager.sign_proposal_init(&init).await.unwrap();
+    init.signature = signature.try_into().unwrap();
+
+    assert_eq!(verify_proposal_init_signature(&init, key_store.public_key).unwrap(), true);
+    let other_init = ProposalInit { valid_round: None, ..init };
+    assert_eq!(verify_proposal_init_signature(&other_init, key_store.public_key).unwrap(), false);
+}
+
+#[tokio::test]
+async fn test_sign_proposal_fin() {
+    let key_store = LocalKeyStore::new_for_testing();
+    let signature_manager = SignatureManager::new(key_store);
+
+    let init = proposal_init();
+    let mut fin = ProposalFin {
+        proposal_commitment: BlockHash(felt!("0x1234")),
+        signature: Default::default(),
+    };
+    let signature = signature_manager.sign_proposal_fin(&init, &fin).await.unwrap();
+    fin.signature = signature.try_into().unwrap();
+
+    assert_eq!(verify_proposal_fin_signature(&init, &fin, key_store.public_key).unwrap(), true);
+    // The fin can't be replayed in a proposal of another round.
+    let other_init = ProposalInit { round: 3, ..init };
+    assert_eq!(
+        verify_proposal_fin_signature(&other_init, &fin, key_store.public_key).unwrap(),
+        false
+    );
+}
+
+#[test]
+fn private_keys_for_testing() {
+    let first = LocalKeyStore::new(private_key_for_testing(0));
+    assert_eq!(first.public_key, LocalKeyStore::new_for_testing().public_key);
+
+    let second = LocalKeyStore::new(private_key_for_testing(1));
+    assert_ne!(second.public_key, first.public_key);
+}
@@ -7,6 +7,7 @@ use apollo_infra::{impl_debug_for_infra_requests_and_responses, impl_labeled_req
 use apollo_metrics::generate_permutation_labels;
 use apollo_network_types::network_types::PeerId;
 use apollo_proc_macros::handle_all_response_variants;
+use apollo_protobuf::consensus::{ProposalFin, ProposalInit, Vote};
 use async_trait::async_trait;
 #[cfg(any(feature = "testing", test))]
 use mockall::automock;
@@ -59,6 +60,23 @@ pub trait SignatureManagerClient: Send + Sync {
         &self,
         block_hash: BlockHash,
     ) -> SignatureManagerClientResult<RawSignature>;
+
+    /// Signs a consensus vote. The signature of the vote itself is ignored.
+    async fn sign_vote(&self, vote: Vote) -> SignatureManagerClientResult<RawSignature>;
+
+    /// Signs the init of a consensus proposal. The signature of the init itself is ignored.
+    async fn sign_proposal_init(
+        &self,
+        init: ProposalInit,
+    ) -> SignatureManagerClientResult<RawSignature>;
+
+    /// Signs the fin of a consensus proposal. The signature covers the height, round and proposer
+    /// of `init` as well, so that a fin cannot be replayed in another proposal.
+    async fn sign_proposal_fin(
+        &self,
+        init: ProposalInit,
+        fin: ProposalFin,
+    ) -> SignatureManagerClientResult<RawSignature>;
 }
 
 #[derive(Clone, Debug, Error, Eq, PartialEq, Serialize, Deserialize)]
@@ -90,6 +108,9 @@ pub enum SignatureManagerClientError {
 pub enum SignatureManagerRequest {
     Identify(PeerId, Nonce),
     SignPrecommitVote(BlockHash),
+    SignVote(Vote),
+    SignProposalInit(ProposalInit),
+    SignProposalFin(ProposalInit, ProposalFin),
 }
 impl_debug_for_infra_requests_and_responses!(SignatureManagerRequest);
 impl_labeled_request!(SignatureManagerRequest, SignatureManagerRequestLabelValue);
@@ -103,6 +124,9 @@ generate_permutation_labels! {
 pub enum SignatureManagerResponse {
     Identify(SignatureManagerResult<RawSignature>),
     SignPrecommitVote(SignatureManagerResult<RawSignature>),
+    SignVote(SignatureManagerResult<RawSignature>),
+    SignProposalInit(SignatureManagerResult<RawSignature>),
+    SignProposalFin(SignatureManagerResult<RawSignature>),
 }
 impl_debug_for_infra_requests_and_responses!(SignatureManagerResponse);
 
@@ -140,4 +164,44 @@ where
             Direct
         )
     }
+
+    async fn sign_vote(&self, vote: Vote) -> SignatureManagerClientResult<RawSignature> {
+        let request = SignatureManagerRequest::SignVote(vote);
+        handle_all_response_variants!(
+            SignatureManagerResponse,
+            SignVote,
+            SignatureManagerClientError,
+            SignatureManagerError,
+            Direct
+        )
+    }
+
+    async fn sign_proposal_init(
+        &self,
+        init: ProposalInit,
+    ) -> SignatureManagerClientResult<RawSignature> {
+        let request = SignatureManagerRequest::SignProposalInit(init);
+        handle_all_response_variants!(
+            SignatureManagerResponse,
+            SignProposalInit,
+            SignatureManagerClientError,
+            SignatureManagerError,
+            Direct
+        )
+    }
+
+    async fn sign_proposal_fin(
+        &self,
+        init: ProposalInit,
+        fin: ProposalFin,
+    ) -> SignatureManagerClientResult<RawSignature> {
+        let request = SignatureManagerRequest::SignProposalFin(init, fin);
+        handle_all_response_variants!(
+            SignatureManagerResponse,
+            SignProposalFin,
+            SignatureManagerClientError,
+            SignatureManagerError,
+            Direct
+        )
+    }
 }
@@ -1,11 +1,14 @@
 use std::sync::Arc;
 
 use apollo_consensus::types::Round;
-use apollo_state_sync_types::communication::{SharedStateSyncClient, StateSyncClientError};
+use apollo_state_sync_types::communication::StateSyncClientError;
 use async_trait::async_trait;
 use blockifier::context::BlockContext;
 use blockifier::execution::errors::EntryPointExecutionError;
+use blockifier::state::errors::StateError;
 use blockifier::state::state_api::StateReader;
+#[cfg(any(feature = "testing", test))]
+use mockall::automock;
 use starknet_api::block::BlockNumber;
 use starknet_api::core::ContractAddress;
 use starknet_api::staking::StakingWeight;
@@ -16,8 +19,7 @@ use crate::contract_types::RetdataDeserializationError;
 
 pub type Committee = Vec<Staker>;
 
-#[cfg_attr(test, derive(Clone))]
-#[derive(Debug, PartialEq, Eq)]
+#[derive(Clone, Debug, PartialEq, Eq)]
 pub struct Staker {
     // A contract address of the staker, to which rewards are sent.
     pub address: ContractAddress,
@@ -35,9 +37,13 @@ pub enum CommitteeProviderError {
     #[error(transparent)]
     RetdataDeserializationError(#[from] RetdataDeserializationError),
     #[error(transparent)]
+    StateError(#[from] StateError),
+    #[error(transparent)]
     StateSyncClientError(#[from] StateSyncClientError),
     #[error("Committee is empty.")]
     EmptyCommittee,
+    #[error("The committee of height {0} was not fetched.")]
+    MissingCommittee(BlockNumber),
 }
 
 pub type CommitteeProviderResult<T> = Result<T, CommitteeProviderError>;
@@ -46,31 +52,31 @@ pub type CommitteeProviderResult<T> = Result<T, CommitteeProviderError>;
 pub struct ExecutionContext<S: StateReader> {
     pub state_reader: S,
     pub block_context: Arc<BlockContext>,
-    pub state_sync_client: SharedStateSyncClient,
 }
 
 /// Trait for managing committee operations including fetching and selecting committee members
 /// and proposers for consensus.
 /// The committee is a subset of nodes (proposer and validators) that are selected to participate in
 /// the consensus at a given epoch, responsible for proposing blocks and voting on them.
+#[cfg_attr(any(feature = "testing", test), automock)]
 #[async_trait]
-pub trait CommitteeProvider {
-    /// Returns a list of the committee members at the given epoch.
-    /// The state's most recent block should be provided in the execution_context.
-    fn get_committee<S: StateReader>(
-        &mut self,
-        epoch: u64,
-        execution_context: ExecutionContext<S>,
-    ) -> CommitteeProviderResult<Arc<Committee>>;
+pub trait CommitteeProvider: Send + Sync {
+    /// Returns a list of the committee members at the epoch of the given height.
+    ///
+    /// Also prepares the proposer selection of the height, so it must be called for a height
+    /// before `get_proposer`.
+    async fn get_committee(&self, height: BlockNumber) -> CommitteeProviderResult<Arc<Committee>>;
 
     /// Returns the address of the proposer for the specified height and round.
     ///
     /// The proposer is deterministically selected for a given height and round, from the committee
-    /// corresponding to the epoch associated with that height.
-    async fn get_proposer<S: StateReader + Send>(
-        &mut self,
+    /// corresponding to the epoch associated with that height. Consensus selects proposers
+    /// synchronously, so this only uses data fetched by `get_committee` for the height.
+    fn get_proposer(
+        &self,
         height: BlockNumber,
         round: Round,
-        execution_context: ExecutionContext<S>,
     ) -> CommitteeProviderResult<ContractAddress>;
 }
+
+pub type SharedCommitteeProvider = Arc<dyn CommitteeProvider>;
@@ -7,7 +7,6 @@ use thiserror::Error;
 use crate::committee_provider::Staker;
 
 pub(crate) const GET_STAKERS_ENTRY_POINT: &str = "get_stakers";
-pub(crate) const EPOCH_LENGTH: u64 = 100; // Number of heights in an epoch.
 
 // Represents a Cairo1 `Array` containing elements that can be deserialized to `T`.
 // `T` must implement `TryFrom<[Felt; N]>`, where `N` is the size of `T`'s Cairo equivalent.
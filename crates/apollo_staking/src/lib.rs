@@ -1,5 +1,6 @@
 pub mod committee_provider;
 pub mod contract_types;
+pub mod staking_contract;
 pub mod staking_manager;
-
-mod utils;
+pub mod sync_state_reader;
+pub mod utils;
@@ -0,0 +1,148 @@
+use std::collections::BTreeMap;
+use std::fmt::Display;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use async_trait::async_trait;
+use blockifier::execution::entry_point::call_view_entry_point;
+use blockifier::state::state_api::StateReader;
+#[cfg(test)]
+use mockall::automock;
+use serde::{Serialize, Serializer};
+use starknet_api::core::ContractAddress;
+use starknet_api::staking::StakingWeight;
+use starknet_api::transaction::fields::Calldata;
+use starknet_types_core::felt::Felt;
+use thiserror::Error;
+
+use crate::committee_provider::{CommitteeProviderResult, ExecutionContext, Staker};
+use crate::contract_types::GET_STAKERS_ENTRY_POINT;
+use crate::staking_manager::StakerSet;
+
+#[cfg(test)]
+#[path = "staking_contract_test.rs"]
+mod staking_contract_test;
+
+/// The source of the stakers at each epoch, from which the committees are selected.
+#[cfg_attr(test, automock)]
+#[async_trait]
+pub trait StakingContract: Send + Sync {
+    /// Returns the stakers at the given epoch.
+    async fn get_stakers(&self, epoch: u64) -> CommitteeProviderResult<StakerSet>;
+}
+
+/// Reads the stakers by calling the staking contract's view entry point, over the state returned
+/// by `execution_context_fn`.
+///
+/// Both run on a blocking thread, so the state may be read synchronously.
+pub struct ExecutionStakingContract<F> {
+    staking_contract_address: ContractAddress,
+    execution_context_fn: Arc<F>,
+}
+
+impl<F> ExecutionStakingContract<F> {
+    pub fn new(staking_contract_address: ContractAddress, execution_context_fn: F) -> Self {
+        Self { staking_contract_address, execution_context_fn: Arc::new(execution_context_fn) }
+    }
+}
+
+#[async_trait]
+impl<S, F> StakingContract for ExecutionStakingContract<F>
+where
+    S: StateReader,
+    F: Fn() -> CommitteeProviderResult<ExecutionContext<S>> + Send + Sync + 'static,
+{
+    async fn get_stakers(&self, epoch: u64) -> CommitteeProviderResult<StakerSet> {
+        let execution_context_fn = Arc::clone(&self.execution_context_fn);
+        let staking_contract_address = self.staking_contract_address;
+        let retdata = tokio::task::spawn_blocking(move || {
+            let execution_context = execution_context_fn()?;
+            let call_info = call_view_entry_point(
+                execution_context.state_reader,
+                execution_context.block_context,
+                staking_contract_address,
+                GET_STAKERS_ENTRY_POINT,
+                Calldata(vec![Felt::from(epoch)].into()),
+            )?;
+            CommitteeProviderResult::Ok(call_info.execution.retdata)
+        })
+        .await
+        .expect("Reading the stakers from the staking contract panicked.")?;
+
+        Ok(Staker::from_retdata_many(retdata)?)
+    }
+}
+
+/// A staker which is set by config rather than read from the chain.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ConfiguredStaker {
+    // The first epoch at which the staker is active.
+    pub start_epoch: u64,
+    pub staker: Staker,
+}
+
+#[derive(Debug, Error, PartialEq, Eq)]
+#[error("Invalid staker '{0}', expected 'start_epoch:address:weight:public_key'.")]
+pub struct ConfiguredStakerParseError(String);
+
+impl FromStr for ConfiguredStaker {
+    type Err = ConfiguredStakerParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let parse_error = || ConfiguredStakerParseError(s.to_string());
+        let [start_epoch, address, weight, public_key] =
+            <[&str; 4]>::try_from(s.split(':').collect::<Vec<_>>()).map_err(|_| parse_error())?;
+
+        let start_epoch = start_epoch.parse().map_err(|_| parse_error())?;
+        let address = Felt::from_hex(address)
+            .ok()
+            .and_then(|address| ContractAddress::try_from(address).ok())
+            .ok_or_else(parse_error)?;
+        let weight = StakingWeight(weight.parse().map_err(|_| parse_error())?);
+        let public_key = Felt::from_hex(public_key).map_err(|_| parse_error())?;
+
+        Ok(Self { start_epoch, staker: Staker { address, weight, public_key } })
+    }
+}
+
+impl Display for ConfiguredStaker {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let Self { start_epoch, staker: Staker { address, weight, public_key } } = self;
+        write!(f, "{start_epoch}:{:#x}:{}:{public_key:#x}", Felt::from(*address), weight.0)
+    }
+}
+
+impl Serialize for ConfiguredStaker {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_str(self)
+    }
+}
+
+/// A staking contract whose stakers are set by config. The stakers at an epoch are the ones with
+/// the latest `start_epoch` which isn't after it, so each distinct `start_epoch` replaces the whole
+/// staker set.
+pub struct StaticStakingContract {
+    stakers_by_start_epoch: BTreeMap<u64, StakerSet>,
+}
+
+impl StaticStakingContract {
+    pub fn new(stakers: Vec<ConfiguredStaker>) -> Self {
+        let mut stakers_by_start_epoch = BTreeMap::<u64, StakerSet>::new();
+        for ConfiguredStaker { start_epoch, staker } in stakers {
+            stakers_by_start_epoch.entry(start_epoch).or_default().push(staker);
+        }
+        Self { stakers_by_start_epoch }
+    }
+}
+
+#[async_trait]
+impl StakingContract for StaticStakingContract {
+    async fn get_stakers(&self, epoch: u64) -> CommitteeProviderResult<StakerSet> {
+        Ok(self
+            .stakers_by_start_epoch
+            .range(..=epoch)
+            .next_back()
+            .map(|(_, stakers)| stakers.clone())
+            .unwrap_or_default())
+    }
+}
@@ -0,0 +1,71 @@
+use assert_matches::assert_matches;
+use rstest::rstest;
+use starknet_api::core::{ContractAddress, PatriciaKey};
+use starknet_api::staking::StakingWeight;
+use starknet_types_core::felt::Felt;
+
+use crate::committee_provider::Staker;
+use crate::staking_contract::{
+    ConfiguredStaker,
+    ConfiguredStakerParseError,
+    StakingContract,
+    StaticStakingContract,
+};
+
+const STAKER_1: Staker = Staker {
+    address: ContractAddress(PatriciaKey::from_hex_unchecked("0x1")),
+    weight: StakingWeight(1000),
+    public_key: Felt::ONE,
+};
+const STAKER_2: Staker = Staker {
+    address: ContractAddress(PatriciaKey::from_hex_unchecked("0x2")),
+    weight: StakingWeight(2000),
+    public_key: Felt::TWO,
+};
+
+fn configured(start_epoch: u64, staker: Staker) -> ConfiguredStaker {
+    ConfiguredStaker { start_epoch, staker }
+}
+
+#[rstest]
+#[case::before_first_start_epoch(0, vec![])]
+#[case::first_start_epoch(1, vec![STAKER_1, STAKER_2])]
+#[case::between_start_epochs(2, vec![STAKER_1, STAKER_2])]
+#[case::second_start_epoch(3, vec![STAKER_2])]
+#[case::after_last_start_epoch(10, vec![STAKER_2])]
+#[tokio::test]
+async fn static_staking_contract_stakers_by_epoch(
+    #[case] epoch: u64,
+    #[case] expected_stakers: Vec<Staker>,
+) {
+    let staking_contract = StaticStakingContract::new(vec![
+        configured(1, STAKER_1),
+        configured(3, STAKER_2),
+        configured(1, STAKER_2),
+    ]);
+
+    assert_eq!(staking_contract.get_stakers(epoch).await.unwrap(), expected_stakers);
+}
+
+#[test]
+fn configured_staker_string_round_trip() {
+    let configured_staker = configured(5, STAKER_2);
+
+    let as_string = configured_staker.to_string();
+    assert_eq!(as_string, "5:0x2:2000:0x2");
+    assert_eq!(as_string.parse::<ConfiguredStaker>().unwrap(), configured_staker);
+}
+
+#[rstest]
+#[case::missing_field("0:0x1:1")]
+#[case::extra_field("0:0x1:1:0x1:0")]
+#[case::invalid_start_epoch("x:0x1:1:0x1")]
+#[case::invalid_address("0:address:1:0x1")]
+#[case::invalid_weight("0:0x1:-1:0x1")]
+#[case::invalid_public_key("0:0x1:1:key")]
+fn configured_staker_parse_failure(#[case] configured_staker: &str) {
+    assert_matches!(
+        configured_staker.parse::<ConfiguredStaker>(),
+        Err(ConfiguredStakerParseError(_))
+    );
+}
@@ -1,25 +1,27 @@
 use std::collections::BTreeMap;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
+use apollo_config::dumping::{prepend_sub_config_name, ser_param, SerializeConfig};
+use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_consensus::types::Round;
 use apollo_state_sync_types::communication::SharedStateSyncClient;
 use async_trait::async_trait;
-use blockifier::execution::entry_point::call_view_entry_point;
-use blockifier::state::state_api::StateReader;
+use blockifier::abi::constants::STORED_BLOCK_HASH_BUFFER;
+use blockifier::blockifier_versioned_constants::VersionedConstantsOverrides;
+use blockifier::context::ChainInfo;
+use serde::{Deserialize, Serialize};
 use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::ContractAddress;
-use starknet_api::transaction::fields::Calldata;
-use starknet_types_core::felt::Felt;
+use validator::{Validate, ValidationError};
 
 use crate::committee_provider::{
     Committee,
     CommitteeProvider,
     CommitteeProviderError,
     CommitteeProviderResult,
-    ExecutionContext,
     Staker,
 };
-use crate::contract_types::{EPOCH_LENGTH, GET_STAKERS_ENTRY_POINT};
+use crate::staking_contract::StakingContract;
 use crate::utils::BlockRandomGenerator;
 
 pub type StakerSet = Vec<Staker>;
@@ -28,10 +30,14 @@ pub type StakerSet = Vec<Staker>;
 #[path = "staking_manager_test.rs"]
 mod staking_manager_test;
 
-// TODO(Dafna): implement SerializeConfig and Validate for this struct. Specifically, the Validate
-// should check that proposer_prediction_window_in_heights >= STORED_BLOCK_HASH_BUFFER.
+// The number of recent heights for which the proposer selection data is kept.
+const MAX_CACHED_HEIGHTS: usize = 10;
+
+#[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Validate)]
 pub struct StakingManagerConfig {
-    pub staking_contract_address: ContractAddress,
+    // The number of heights in an epoch. The committee is fixed within an epoch.
+    #[validate(range(min = 1))]
+    pub epoch_length: u64,
     pub max_cached_epochs: usize,
 
     // The desired number of committee members to select from the available stakers.
@@ -41,7 +47,81 @@ pub struct StakingManagerConfig {
     // Defines how many heights in advance the proposer can be predicted.
     // While the exact identity may depend on staker prediction constraints,
     // the proposer selection logic becomes deterministic at this offset.
+    #[validate(custom = "validate_proposer_prediction_window")]
     pub proposer_prediction_window_in_heights: u64,
+
+    // The address of the staking contract, from which the stakers are read.
+    pub staking_contract_address: ContractAddress,
+    // The chain and the versioned constants overrides to call the staking contract with.
+    pub chain_info: ChainInfo,
+    pub versioned_constants_overrides: VersionedConstantsOverrides,
+}
+
+impl SerializeConfig for StakingManagerConfig {
+    fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
+        let mut dump = BTreeMap::from_iter([
+            ser_param(
+                "epoch_length",
+                &self.epoch_length,
+                "The number of heights in an epoch. The committee is fixed within an epoch.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "max_cached_epochs",
+                &self.max_cached_epochs,
+                "The maximum number of epochs whose committee is cached.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "committee_size",
+                &self.committee_size,
+                "The desired number of committee members to select from the available stakers.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "proposer_prediction_window_in_heights",
+                &self.proposer_prediction_window_in_heights,
+                "Defines how many heights in advance the proposer can be predicted.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "staking_contract_address",
+                &self.staking_contract_address,
+                "The address of the staking contract, from which the stakers are read.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
+        dump.append(&mut prepend_sub_config_name(self.chain_info.dump(), "chain_info"));
+        dump.append(&mut prepend_sub_config_name(
+            self.versioned_constants_overrides.dump(),
+            "versioned_constants_overrides",
+        ));
+        dump
+    }
+}
+
+impl Default for StakingManagerConfig {
+    fn default() -> Self {
+        Self {
+            epoch_length: 100,
+            max_cached_epochs: 10,
+            committee_size: 100,
+            proposer_prediction_window_in_heights: STORED_BLOCK_HASH_BUFFER,
+            staking_contract_address: ContractAddress::default(),
+            chain_info: ChainInfo::default(),
+            versioned_constants_overrides: VersionedConstantsOverrides::default(),
+        }
+    }
+}
+
+// The hash of the randomness source block must be retrievable from the state.
+fn validate_proposer_prediction_window(window: u64) -> Result<(), ValidationError> {
+    if window < STORED_BLOCK_HASH_BUFFER {
+        return Err(ValidationError::new(
+            "proposer_prediction_window_in_heights must be at least STORED_BLOCK_HASH_BUFFER",
+        ));
+    }
+    Ok(())
 }
 
 struct CommitteeData {
@@ -50,34 +130,46 @@ struct CommitteeData {
     total_weight: u128,
 }
 
-// Holds committee data for the highest known epochs, limited in size by `capacity``.
-struct CommitteeDataCache {
-    // The maximum number of epochs to cache.
+// The data needed to select the proposers of a height.
+#[derive(Clone)]
+struct HeightData {
+    committee_data: Arc<CommitteeData>,
+    randomness_block_hash: Option<BlockHash>,
+}
+
+// Holds the values of the highest known keys, limited in size by `capacity`.
+struct BoundedCache<K, V> {
+    // The maximum number of keys to cache.
     capacity: usize,
-    // A map of epoch to the epoch's data.
-    cache: BTreeMap<u64, Arc<CommitteeData>>,
+    cache: BTreeMap<K, V>,
 }
 
 // Responsible for fetching and storing the committee at a given epoch.
 // The committee is a subset of nodes (proposer and validators) that are selected to participate in
 // the consensus at a given epoch, responsible for proposing blocks and voting on them.
 pub struct StakingManager {
-    committee_data_cache: CommitteeDataCache,
+    staking_contract: Arc<dyn StakingContract>,
     random_generator: Box<dyn BlockRandomGenerator>,
+    state_sync_client: SharedStateSyncClient,
     config: StakingManagerConfig,
+    // A map of epoch to the epoch's data.
+    committee_data_cache: Mutex<BoundedCache<u64, Arc<CommitteeData>>>,
+    // A map of height to the data of its proposer selection, which must be available
+    // synchronously.
+    height_data_cache: Mutex<BoundedCache<BlockNumber, HeightData>>,
 }
 
-impl CommitteeDataCache {
+impl<K: Ord, V> BoundedCache<K, V> {
     pub fn new(capacity: usize) -> Self {
         Self { capacity, cache: BTreeMap::new() }
     }
 
-    pub fn get(&self, epoch: u64) -> Option<&Arc<CommitteeData>> {
-        self.cache.get(&epoch)
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache.get(key)
     }
 
-    pub fn insert(&mut self, epoch: u64, data: Arc<CommitteeData>) {
-        self.cache.insert(epoch, data);
+    pub fn insert(&mut self, key: K, value: V) {
+        self.cache.insert(key, value);
         if self.cache.len() > self.capacity {
             self.cache.pop_first();
         }
@@ -86,53 +178,68 @@ impl CommitteeDataCache {
 
 impl StakingManager {
     pub fn new(
+        staking_contract: Arc<dyn StakingContract>,
         random_generator: Box<dyn BlockRandomGenerator>,
+        state_sync_client: SharedStateSyncClient,
         config: StakingManagerConfig,
     ) -> Self {
         Self {
-            committee_data_cache: CommitteeDataCache::new(config.max_cached_epochs),
+            committee_data_cache: Mutex::new(BoundedCache::new(config.max_cached_epochs)),
+            height_data_cache: Mutex::new(BoundedCache::new(MAX_CACHED_HEIGHTS)),
+            staking_contract,
             random_generator,
+            state_sync_client,
             config,
         }
     }
 
+    fn epoch(&self, height: BlockNumber) -> u64 {
+        height.0 / self.config.epoch_length
+    }
+
+    // Returns the proposer selection data for the given height.
+    // If the data is not cached, it is fetched and cached.
+    async fn height_data(&self, height: BlockNumber) -> CommitteeProviderResult<HeightData> {
+        if let Some(height_data) = self.height_data_cache.lock().unwrap().get(&height) {
+            return Ok(height_data.clone());
+        }
+
+        // Try to get the hash of the block used for proposer selection randomness.
+        let randomness_block_hash = self.proposer_randomness_block_hash(height).await?;
+        // Get the committee for the epoch this height belongs to.
+        let committee_data = self.committee_data_at_epoch(self.epoch(height)).await?;
+
+        let height_data = HeightData { committee_data, randomness_block_hash };
+        self.height_data_cache.lock().unwrap().insert(height, height_data.clone());
+        Ok(height_data)
+    }
+
     // Returns the committee data for the given epoch.
-    // If the data is not cached, it is fetched from the state and cached.
-    fn committee_data_at_epoch<S: StateReader>(
-        &mut self,
+    // If the data is not cached, it is fetched from the staking contract and cached.
+    async fn committee_data_at_epoch(
+        &self,
         epoch: u64,
-        execution_context: ExecutionContext<S>,
     ) -> CommitteeProviderResult<Arc<CommitteeData>> {
         // Attempt to read from cache.
-        if let Some(committee_data) = self.committee_data_cache.get(epoch) {
+        if let Some(committee_data) = self.committee_data_cache.lock().unwrap().get(&epoch) {
             return Ok(committee_data.clone());
         }
 
-        // Otherwise, build the committee from state, and cache the result.
-        let committee_data =
-            Arc::new(self.fetch_and_build_committee_data(epoch, execution_context)?);
-        self.committee_data_cache.insert(epoch, committee_data.clone());
+        // Otherwise, build the committee from the staking contract, and cache the result.
+        let committee_data = Arc::new(self.fetch_and_build_committee_data(epoch).await?);
+        self.committee_data_cache.lock().unwrap().insert(epoch, committee_data.clone());
 
         Ok(committee_data)
     }
 
-    // Queries the state to fetch stakers for the given epoch and builds the full committee data.
+    // Fetches the stakers for the given epoch and builds the full committee data.
     // This includes selecting the committee and preparing cumulative weights for proposer
     // selection.
-    fn fetch_and_build_committee_data<S: StateReader>(
+    async fn fetch_and_build_committee_data(
         &self,
         epoch: u64,
-        execution_context: ExecutionContext<S>,
     ) -> CommitteeProviderResult<CommitteeData> {
-        let call_info = call_view_entry_point(
-            execution_context.state_reader,
-            execution_context.block_context,
-            self.config.staking_contract_address,
-            GET_STAKERS_ENTRY_POINT,
-            Calldata(vec![Felt::from(epoch)].into()),
-        )?;
-
-        let stakers = Staker::from_retdata_many(call_info.execution.retdata)?;
+        let stakers = self.staking_contract.get_stakers(epoch).await?;
         let committee_members = self.select_committee(stakers);
 
         // Prepare the data needed for proposer selection.
@@ -166,7 +273,6 @@ impl StakingManager {
     async fn proposer_randomness_block_hash(
         &self,
         current_block_number: BlockNumber,
-        state_sync_client: SharedStateSyncClient,
     ) -> CommitteeProviderResult<Option<BlockHash>> {
         let randomness_source_block =
             current_block_number.0.checked_sub(self.config.proposer_prediction_window_in_heights);
@@ -177,7 +283,7 @@ impl StakingManager {
             }
             Some(block_number) => {
                 let block_hash =
-                    state_sync_client.get_block_hash(BlockNumber(block_number)).await?;
+                    self.state_sync_client.get_block_hash(BlockNumber(block_number)).await?;
                 Ok(Some(block_hash))
             }
         }
@@ -225,38 +331,36 @@ impl StakingManager {
 
 #[async_trait]
 impl CommitteeProvider for StakingManager {
-    fn get_committee<S: StateReader>(
-        &mut self,
-        epoch: u64,
-        execution_context: ExecutionContext<S>,
-    ) -> CommitteeProviderResult<Arc<Committee>> {
-        let committee_data = self.committee_data_at_epoch(epoch, execution_context)?;
-        Ok(committee_data.committee_members.clone())
+    async fn get_committee(&self, height: BlockNumber) -> CommitteeProviderResult<Arc<Committee>> {
+        let height_data = self.height_data(height).await?;
+        Ok(height_data.committee_data.committee_members.clone())
     }
 
     // Returns the address of the proposer for the specified height and round.
     // The proposer is chosen from the committee corresponding to the epoch of the given height.
     // Selection is based on a deterministic random number derived from the height, round,
     // and the hash of a past block — offset by `config.proposer_prediction_window`.
-    async fn get_proposer<S: StateReader + Send>(
-        &mut self,
+    fn get_proposer(
+        &self,
         height: BlockNumber,
         round: Round,
-        execution_context: ExecutionContext<S>,
     ) -> CommitteeProviderResult<ContractAddress> {
-        // Try to get the hash of the block used for proposer selection randomness.
-        let block_hash = self
-            .proposer_randomness_block_hash(height, execution_context.state_sync_client.clone())
-            .await?;
-
-        // Get the committee for the epoch this height belongs to.
-        let epoch = height.0 / EPOCH_LENGTH; // TODO(Dafna): export to a utility function.
-        let committee_data = self.committee_data_at_epoch(epoch, execution_context)?;
+        let HeightData { committee_data, randomness_block_hash } = self
+            .height_data_cache
+            .lock()
+            .unwrap()
+            .get(&height)
+            .cloned()
+            .ok_or(CommitteeProviderError::MissingCommittee(height))?;
 
         // Generate a pseudorandom value in the range [0, total_weight) based on the height, round,
         // and block hash.
-        let random_value =
-            self.random_generator.generate(height, round, block_hash, committee_data.total_weight);
+        let random_value = self.random_generator.generate(
+            height,
+            round,
+            randomness_block_hash,
+            committee_data.total_weight,
+        );
 
         // Select a proposer from the committee using the generated random.
         let proposer = self.choose_proposer(&committee_data, random_value)?;
@@ -1,5 +1,5 @@
 use std::convert::TryFrom;
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 
 use apollo_state_sync_types::communication::MockStateSyncClient;
 use assert_matches::assert_matches;
@@ -19,7 +19,7 @@ use blockifier_test_utils::cairo_versions::{CairoVersion, RunnableCairo1};
 use blockifier_test_utils::calldata::create_calldata;
 use blockifier_test_utils::contracts::FeatureContract;
 use rstest::{fixture, rstest};
-use starknet_api::block::BlockNumber;
+use starknet_api::block::{BlockHash, BlockNumber};
 use starknet_api::core::{ContractAddress, PatriciaKey, CONTRACT_ADDRESS_DOMAIN_SIZE};
 use starknet_api::staking::StakingWeight;
 use starknet_api::{contract_address, invoke_tx_args};
@@ -33,6 +33,7 @@ use crate::committee_provider::{
     Staker,
 };
 use crate::contract_types::RetdataDeserializationError;
+use crate::staking_contract::{ExecutionStakingContract, MockStakingContract};
 use crate::staking_manager::{StakerSet, StakingManager, StakingManagerConfig};
 use crate::utils::MockBlockRandomGenerator;
 
@@ -64,6 +65,7 @@ const STAKER_4: Staker = Staker {
 
 type Context = Arc<BlockContext>;
 type State = CachedState<DictStateReader>;
+type SharedState = Arc<Mutex<State>>;
 
 #[fixture]
 fn block_context() -> Context {
@@ -71,7 +73,7 @@ fn block_context() -> Context {
 }
 
 #[fixture]
-fn state(block_context: Context) -> State {
+fn state(block_context: Context) -> SharedState {
     // Prepare the storage with a mock staking contract, and a dummy account as a staker.
     let state = test_state(
         block_context.chain_info(),
@@ -79,20 +81,51 @@ fn state(block_context: Context) -> State {
         &[(STAKING_CONTRACT, 1), (ACCOUNT_CONTRACT, 1)],
     );
 
-    state
+    Arc::new(Mutex::new(state))
 }
 
 #[fixture]
 fn default_config() -> StakingManagerConfig {
     StakingManagerConfig {
-        staking_contract_address: STAKING_CONTRACT.get_instance_address(0),
+        epoch_length: 100,
         max_cached_epochs: 10,
         committee_size: 10,
         proposer_prediction_window_in_heights: 10,
+        staking_contract_address: STAKING_CONTRACT.get_instance_address(0),
+        ..Default::default()
     }
 }
 
-fn set_stakers(state: &mut State, block_context: &Context, stakers: &[Staker]) {
+// Creates a staking manager which reads the stakers from the mock staking contract in `state`.
+fn staking_manager(
+    state: &SharedState,
+    block_context: &Context,
+    random_generator: MockBlockRandomGenerator,
+    config: StakingManagerConfig,
+) -> StakingManager {
+    let state = state.clone();
+    let block_context = block_context.clone();
+    let staking_contract =
+        ExecutionStakingContract::new(STAKING_CONTRACT.get_instance_address(0), move || {
+            Ok(ExecutionContext {
+                state_reader: state.lock().unwrap().clone(),
+                block_context: block_context.clone(),
+            })
+        });
+
+    let mut state_sync_client = MockStateSyncClient::new();
+    state_sync_client.expect_get_block_hash().returning(|_| Ok(BlockHash::default()));
+
+    StakingManager::new(
+        Arc::new(staking_contract),
+        Box::new(random_generator),
+        Arc::new(state_sync_client),
+        config,
+    )
+}
+
+fn set_stakers(state: &SharedState, block_context: &Context, stakers: &[Staker]) {
+    let state = &mut *state.lock().unwrap();
     let mut stakers_as_felts: Vec<Felt> = stakers.iter().flat_map(<Vec<Felt>>::from).collect();
     stakers_as_felts.insert(0, Felt::from(stakers.len()));
 
@@ -119,72 +152,101 @@ fn set_stakers(state: &mut State, block_context: &Context, stakers: &[Staker]) {
 #[case::multiple_stakers_equal_to_committee_size(vec![STAKER_1, STAKER_2, STAKER_3], vec![STAKER_3, STAKER_2, STAKER_1])]
 #[case::multiple_stakers_greater_than_committee_size(vec![STAKER_1, STAKER_2, STAKER_3, STAKER_4], vec![STAKER_4, STAKER_3, STAKER_2])]
 #[case::multiple_stakers_equal_weights(vec![STAKER_1, STAKER_2, STAKER_3, Staker { address: ContractAddress(PatriciaKey::from_hex_unchecked("0x0")), .. STAKER_1 }], vec![STAKER_3, STAKER_2, STAKER_1])]
-fn get_committee_success(
+#[tokio::test]
+async fn get_committee_success(
     default_config: StakingManagerConfig,
-    mut state: State,
+    state: SharedState,
     block_context: Context,
     #[case] stakers: StakerSet,
     #[case] expected_committee: Committee,
 ) {
-    set_stakers(&mut state, &block_context, &stakers);
+    set_stakers(&state, &block_context, &stakers);
 
-    let mut committee_manager = StakingManager::new(
-        Box::new(MockBlockRandomGenerator::new()),
+    let committee_manager = staking_manager(
+        &state,
+        &block_context,
+        MockBlockRandomGenerator::new(),
         StakingManagerConfig { committee_size: 3, ..default_config },
     );
 
-    let context = ExecutionContext {
-        state_reader: state.clone(),
-        block_context: block_context.clone(),
-        state_sync_client: Arc::new(MockStateSyncClient::new()),
-    };
-    let committee = committee_manager.get_committee(1, context).unwrap();
+    let committee = committee_manager.get_committee(BlockNumber(100)).await.unwrap();
 
     assert_eq!(*committee, expected_committee);
 }
 
 #[rstest]
-fn get_committee_cache(
+#[tokio::test]
+async fn get_committee_cache(
     default_config: StakingManagerConfig,
-    mut state: State,
+    state: SharedState,
     block_context: Context,
 ) {
-    let mut committee_manager = StakingManager::new(
-        Box::new(MockBlockRandomGenerator::new()),
+    let committee_manager = staking_manager(
+        &state,
+        &block_context,
+        MockBlockRandomGenerator::new(),
         StakingManagerConfig { max_cached_epochs: 1, ..default_config },
     );
 
     // Case 1: Get committee for epoch 1. Cache miss – STAKER_1 fetched from contract.
-    set_stakers(&mut state, &block_context, vec![STAKER_1].as_slice());
-    let context = ExecutionContext {
-        state_reader: state.clone(),
-        block_context: block_context.clone(),
-        state_sync_client: Arc::new(MockStateSyncClient::new()),
-    };
-    let committee = committee_manager.get_committee(1, context).unwrap();
+    set_stakers(&state, &block_context, vec![STAKER_1].as_slice());
+    let committee = committee_manager.get_committee(BlockNumber(100)).await.unwrap();
     assert_eq!(*committee, vec![STAKER_1]);
 
-    // Case 2: Query epoch 1 again. Cache hit – STAKER_1 returned from cache despite contract
-    // change.
-    set_stakers(&mut state, &block_context, vec![STAKER_2].as_slice());
-    let context = ExecutionContext {
-        state_reader: state.clone(),
-        block_context: block_context.clone(),
-        state_sync_client: Arc::new(MockStateSyncClient::new()),
-    };
-    let committee = committee_manager.get_committee(1, context.clone()).unwrap();
+    // Case 2: Query another height of epoch 1. Cache hit – STAKER_1 returned from cache despite
+    // contract change.
+    set_stakers(&state, &block_context, vec![STAKER_2].as_slice());
+    let committee = committee_manager.get_committee(BlockNumber(101)).await.unwrap();
     assert_eq!(*committee, vec![STAKER_1]);
 
     // Case 3: Get committee for epoch 2. Cache miss – STAKER_2 fetched from updated contract state.
-    let committee = committee_manager.get_committee(2, context.clone()).unwrap();
+    let committee = committee_manager.get_committee(BlockNumber(200)).await.unwrap();
     assert_eq!(*committee, vec![STAKER_2]);
 
-    // Case 4: Query epoch 1 again. Cache miss due to the cache being full - STAKER_2 now fetched
-    // from contract.
-    let committee = committee_manager.get_committee(1, context).unwrap();
+    // Case 4: Query a new height of epoch 1. Cache miss due to the cache being full - STAKER_2 now
+    // fetched from contract.
+    let committee = committee_manager.get_committee(BlockNumber(102)).await.unwrap();
     assert_eq!(*committee, vec![STAKER_2]);
 }
 
+#[rstest]
+#[tokio::test]
+async fn get_committee_epoch_transition(default_config: StakingManagerConfig) {
+    let mut staking_contract = MockStakingContract::new();
+    staking_contract
+        .expect_get_stakers()
+        .withf(|epoch| *epoch == 0)
+        .returning(|_| Ok(vec![STAKER_1]));
+    staking_contract
+        .expect_get_stakers()
+        .withf(|epoch| *epoch == 1)
+        .returning(|_| Ok(vec![STAKER_2]));
+    let mut state_sync_client = MockStateSyncClient::new();
+    state_sync_client.expect_get_block_hash().returning(|_| Ok(BlockHash::default()));
+    let mut random_generator = MockBlockRandomGenerator::new();
+    random_generator.expect_generate().returning(|_, _, _, _| 0);
+
+    let committee_manager = StakingManager::new(
+        Arc::new(staking_contract),
+        Box::new(random_generator),
+        Arc::new(state_sync_client),
+        StakingManagerConfig { epoch_length: 3, ..default_config },
+    );
+
+    // The last height of epoch 0.
+    let committee = committee_manager.get_committee(BlockNumber(2)).await.unwrap();
+    assert_eq!(*committee, vec![STAKER_1]);
+    assert_eq!(committee_manager.get_proposer(BlockNumber(2), 0).unwrap(), STAKER_1.address);
+
+    // The first height of epoch 1.
+    let committee = committee_manager.get_committee(BlockNumber(3)).await.unwrap();
+    assert_eq!(*committee, vec![STAKER_2]);
+    assert_eq!(committee_manager.get_proposer(BlockNumber(3), 0).unwrap(), STAKER_2.address);
+
+    // Heights which were already fetched keep their proposers.
+    assert_eq!(committee_manager.get_proposer(BlockNumber(2), 1).unwrap(), STAKER_1.address);
+}
+
 #[rstest]
 #[case(9999, STAKER_1)]
 #[case(9000, STAKER_1)]
@@ -197,7 +259,7 @@ fn get_committee_cache(
 #[tokio::test]
 async fn get_proposer_success(
     default_config: StakingManagerConfig,
-    mut state: State,
+    state: SharedState,
     block_context: Context,
     #[case] random_value: u128,
     #[case] expected_proposer: Staker,
@@ -209,19 +271,16 @@ async fn get_proposer_success(
     // - [7000–8999] → STAKER_2
     // - [9000–9999] → STAKER_1
 
-    set_stakers(&mut state, &block_context, &vec![STAKER_1, STAKER_2, STAKER_3, STAKER_4]);
+    set_stakers(&state, &block_context, &vec![STAKER_1, STAKER_2, STAKER_3, STAKER_4]);
 
     let mut random_generator = MockBlockRandomGenerator::new();
     random_generator.expect_generate().returning(move |_, _, _, _| random_value);
 
-    let mut committee_manager = StakingManager::new(Box::new(random_generator), default_config);
+    let committee_manager =
+        staking_manager(&state, &block_context, random_generator, default_config);
 
-    let context = ExecutionContext {
-        state_reader: state.clone(),
-        block_context: block_context.clone(),
-        state_sync_client: Arc::new(MockStateSyncClient::new()),
-    };
-    let proposer = committee_manager.get_proposer(BlockNumber(1), 0, context).await.unwrap();
+    committee_manager.get_committee(BlockNumber(1)).await.unwrap();
+    let proposer = committee_manager.get_proposer(BlockNumber(1), 0).unwrap();
 
     assert_eq!(proposer, expected_proposer.address);
 }
@@ -230,50 +289,57 @@ async fn get_proposer_success(
 #[tokio::test]
 async fn get_proposer_empty_committee(
     default_config: StakingManagerConfig,
-    state: State,
+    state: SharedState,
     block_context: Context,
 ) {
     let mut random_generator = MockBlockRandomGenerator::new();
     random_generator.expect_generate().returning(move |_, _, _, _| 0);
 
-    let mut committee_manager = StakingManager::new(
-        Box::new(random_generator),
+    let committee_manager = staking_manager(
+        &state,
+        &block_context,
+        random_generator,
         StakingManagerConfig { committee_size: 0, ..default_config },
     );
 
-    let context = ExecutionContext {
-        state_reader: state.clone(),
-        block_context: block_context.clone(),
-        state_sync_client: Arc::new(MockStateSyncClient::new()),
-    };
-    let err = committee_manager.get_proposer(BlockNumber(1), 0, context).await.unwrap_err();
+    committee_manager.get_committee(BlockNumber(1)).await.unwrap();
+    let err = committee_manager.get_proposer(BlockNumber(1), 0).unwrap_err();
     assert_matches!(err, CommitteeProviderError::EmptyCommittee);
 }
 
+#[rstest]
+fn get_proposer_missing_committee(
+    default_config: StakingManagerConfig,
+    state: SharedState,
+    block_context: Context,
+) {
+    let committee_manager =
+        staking_manager(&state, &block_context, MockBlockRandomGenerator::new(), default_config);
+
+    let err = committee_manager.get_proposer(BlockNumber(1), 0).unwrap_err();
+    assert_matches!(err, CommitteeProviderError::MissingCommittee(BlockNumber(1)));
+}
+
 #[rstest]
 #[tokio::test]
 #[should_panic]
 async fn get_proposer_random_value_exceeds_total_weight(
     default_config: StakingManagerConfig,
-    mut state: State,
+    state: SharedState,
     block_context: Context,
 ) {
     // Stakers with total weight 10000.
-    set_stakers(&mut state, &block_context, &vec![STAKER_1, STAKER_2, STAKER_3, STAKER_4]);
+    set_stakers(&state, &block_context, &vec![STAKER_1, STAKER_2, STAKER_3, STAKER_4]);
 
     // Random value is out of range. Valid range is [0, 10000).
     let mut random_generator = MockBlockRandomGenerator::new();
     random_generator.expect_generate().returning(move |_, _, _, _| 10000);
 
-    let mut committee_manager = StakingManager::new(Box::new(random_generator), default_config);
-
-    let context = ExecutionContext {
-        state_reader: state.clone(),
-        block_context: block_context.clone(),
-        state_sync_client: Arc::new(MockStateSyncClient::new()),
-    };
+    let committee_manager =
+        staking_manager(&state, &block_context, random_generator, default_config);
 
-    let _ = committee_manager.get_proposer(BlockNumber(1), 0, context).await;
+    committee_manager.get_committee(BlockNumber(1)).await.unwrap();
+    let _ = committee_manager.get_proposer(BlockNumber(1), 0);
 }
 
 // --- TryFrom tests for Staker and ArrayRetdata ---
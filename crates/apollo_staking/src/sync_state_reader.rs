@@ -0,0 +1,144 @@
+use apollo_class_manager_types::SharedClassManagerClient;
+use apollo_state_sync_types::communication::{
+    SharedStateSyncClient,
+    StateSyncClientError,
+    StateSyncClientResult,
+};
+use apollo_state_sync_types::errors::StateSyncError;
+use blockifier::execution::contract_class::RunnableCompiledClass;
+use blockifier::state::errors::StateError;
+use blockifier::state::state_api::{StateReader, StateResult};
+use starknet_api::block::{BlockInfo, BlockNumber, GasPriceVector, GasPrices};
+use starknet_api::contract_class::ContractClass;
+use starknet_api::core::{ClassHash, CompiledClassHash, ContractAddress, Nonce};
+use starknet_api::data_availability::L1DataAvailabilityMode;
+use starknet_api::state::StorageKey;
+use starknet_types_core::felt::Felt;
+
+/// Reads the state of a synced block from the state sync, and its classes from the class manager.
+///
+/// The reads block on `runtime`, so the reader must be used from a blocking thread.
+pub struct SyncStateReader {
+    block_number: BlockNumber,
+    state_sync_client: SharedStateSyncClient,
+    class_manager_client: SharedClassManagerClient,
+    runtime: tokio::runtime::Handle,
+}
+
+impl SyncStateReader {
+    /// Returns a reader of the state of the latest synced block.
+    pub fn from_latest_block(
+        state_sync_client: SharedStateSyncClient,
+        class_manager_client: SharedClassManagerClient,
+        runtime: tokio::runtime::Handle,
+    ) -> StateSyncClientResult<Self> {
+        let block_number = runtime
+            .block_on(state_sync_client.get_latest_block_number())?
+            .ok_or(StateSyncClientError::StateSyncError(StateSyncError::EmptyState))?;
+        Ok(Self { block_number, state_sync_client, class_manager_client, runtime })
+    }
+
+    pub fn get_block_info(&self) -> StateResult<BlockInfo> {
+        let block = self
+            .runtime
+            .block_on(self.state_sync_client.get_block(self.block_number))
+            .map_err(|e| StateError::StateReadError(e.to_string()))?;
+
+        let block_header = block.block_header_without_hash;
+        Ok(BlockInfo {
+            block_number: block_header.block_number,
+            block_timestamp: block_header.timestamp,
+            sequencer_address: block_header.sequencer.0,
+            gas_prices: GasPrices {
+                eth_gas_prices: GasPriceVector {
+                    l1_gas_price: block_header.l1_gas_price.price_in_wei.try_into()?,
+                    l1_data_gas_price: block_header.l1_data_gas_price.price_in_wei.try_into()?,
+                    l2_gas_price: block_header.l2_gas_price.price_in_wei.try_into()?,
+                },
+                strk_gas_prices: GasPriceVector {
+                    l1_gas_price: block_header.l1_gas_price.price_in_fri.try_into()?,
+                    l1_data_gas_price: block_header.l1_data_gas_price.price_in_fri.try_into()?,
+                    l2_gas_price: block_header.l2_gas_price.price_in_fri.try_into()?,
+                },
+            },
+            use_kzg_da: match block_header.l1_da_mode {
+                L1DataAvailabilityMode::Blob => true,
+                L1DataAvailabilityMode::Calldata => false,
+            },
+        })
+    }
+}
+
+// Maps a missing contract to the default value, as an undeployed contract reads.
+fn default_if_contract_not_found<T: Default>(result: StateSyncClientResult<T>) -> StateResult<T> {
+    match result {
+        Ok(value) => Ok(value),
+        Err(StateSyncClientError::StateSyncError(StateSyncError::ContractNotFound(_))) => {
+            Ok(T::default())
+        }
+        Err(e) => Err(StateError::StateReadError(e.to_string())),
+    }
+}
+
+impl StateReader for SyncStateReader {
+    fn get_storage_at(
+        &self,
+        contract_address: ContractAddress,
+        key: StorageKey,
+    ) -> StateResult<Felt> {
+        default_if_contract_not_found(self.runtime.block_on(self.state_sync_client.get_storage_at(
+            self.block_number,
+            contract_address,
+            key,
+        )))
+    }
+
+    fn get_nonce_at(&self, contract_address: ContractAddress) -> StateResult<Nonce> {
+        default_if_contract_not_found(
+            self.runtime
+                .block_on(self.state_sync_client.get_nonce_at(self.block_number, contract_address)),
+        )
+    }
+
+    fn get_compiled_class(&self, class_hash: ClassHash) -> StateResult<RunnableCompiledClass> {
+        let is_class_declared = self
+            .runtime
+            .block_on(self.state_sync_client.is_class_declared_at(self.block_number, class_hash))
+            .map_err(|e| StateError::StateReadError(e.to_string()))?;
+        if !is_class_declared {
+            return Err(StateError::UndeclaredClassHash(class_hash));
+        }
+
+        let contract_class = self
+            .runtime
+            .block_on(self.class_manager_client.get_executable(class_hash))
+            .map_err(|e| StateError::StateReadError(e.to_string()))?
+            .ok_or_else(|| {
+                StateError::StateReadError(format!(
+                    "Class with hash {class_hash:?} is declared but missing from the class \
+                     manager."
+                ))
+            })?;
+        Ok(match contract_class {
+            ContractClass::V1(casm_contract_class) => {
+                RunnableCompiledClass::V1(casm_contract_class.try_into()?)
+            }
+            ContractClass::V0(deprecated_contract_class) => {
+                RunnableCompiledClass::V0(deprecated_contract_class.try_into()?)
+            }
+        })
+    }
+
+    fn get_class_hash_at(&self, contract_address: ContractAddress) -> StateResult<ClassHash> {
+        default_if_contract_not_found(self.runtime.block_on(
+            self.state_sync_client.get_class_hash_at(self.block_number, contract_address),
+        ))
+    }
+
+    fn get_compiled_class_hash(&self, class_hash: ClassHash) -> StateResult<CompiledClassHash> {
+        // Only view calls are executed, which don't declare classes.
+        Err(StateError::StateReadError(format!(
+            "The compiled class hash of {class_hash:?} isn't readable from the state sync."
+        )))
+    }
+}
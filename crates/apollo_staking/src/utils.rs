@@ -2,6 +2,12 @@ use apollo_consensus::types::Round;
 #[cfg(test)]
 use mockall::automock;
 use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+use starknet_types_core::hash::{Poseidon, StarkHash};
+
+#[cfg(test)]
+#[path = "utils_test.rs"]
+mod utils_test;
 
 #[cfg_attr(test, automock)]
 pub trait BlockRandomGenerator: Send + Sync {
@@ -14,17 +20,27 @@ pub trait BlockRandomGenerator: Send + Sync {
     ) -> u128;
 }
 
-#[allow(dead_code)]
+/// Derives the random value from the Poseidon hash of the height, round and block hash, so that all
+/// nodes agree on it.
 pub struct BlockPseudorandomGenerator;
 
 impl BlockRandomGenerator for BlockPseudorandomGenerator {
     fn generate(
         &self,
-        _height: BlockNumber,
-        _round: Round,
-        _block_hash: Option<BlockHash>,
-        _range: u128,
+        height: BlockNumber,
+        round: Round,
+        block_hash: Option<BlockHash>,
+        range: u128,
     ) -> u128 {
-        todo!()
+        let hash = Poseidon::hash_array(&[
+            Felt::from(height.0),
+            Felt::from(round),
+            block_hash.map(|block_hash| block_hash.0).unwrap_or_default(),
+        ]);
+        // Take the low 128 bits of the hash. The modulo bias is negligible for realistic ranges.
+        let low_bytes: [u8; 16] =
+            hash.to_bytes_le()[..16].try_into().expect("A Felt has 32 bytes.");
+        // An empty range has no valid value; returning 0 leaves handling it to the caller.
+        u128::from_le_bytes(low_bytes).checked_rem(range).unwrap_or_default()
     }
 }
@@ -0,0 +1,32 @@
+use rstest::rstest;
+use starknet_api::block::{BlockHash, BlockNumber};
+use starknet_types_core::felt::Felt;
+
+use crate::utils::{BlockPseudorandomGenerator, BlockRandomGenerator};
+
+#[rstest]
+#[case::no_block_hash(None)]
+#[case::with_block_hash(Some(BlockHash(Felt::from(7_u8))))]
+fn pseudorandom_generator_is_deterministic_and_in_range(#[case] block_hash: Option<BlockHash>) {
+    const RANGE: u128 = 1000;
+    let generator = BlockPseudorandomGenerator;
+
+    let random = generator.generate(BlockNumber(1), 0, block_hash, RANGE);
+    assert!(random < RANGE);
+    assert_eq!(random, generator.generate(BlockNumber(1), 0, block_hash, RANGE));
+}
+
+#[test]
+fn pseudorandom_generator_depends_on_round() {
+    let generator = BlockPseudorandomGenerator;
+    let randoms: Vec<u128> =
+        (0..10).map(|round| generator.generate(BlockNumber(1), round, None, u128::MAX)).collect();
+
+    // With a full range, distinct rounds practically never collide.
+    assert!(randoms.iter().skip(1).all(|random| *random != randoms[0]));
+}
+
+#[test]
+fn pseudorandom_generator_empty_range() {
+    assert_eq!(BlockPseudorandomGenerator.generate(BlockNumber(1), 0, None, 0), 0);
+}
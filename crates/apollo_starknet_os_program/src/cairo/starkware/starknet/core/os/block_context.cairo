@@ -44,6 +44,8 @@ struct BlockContext {
     block_info_for_validate: BlockInfo*,
     // StarknetOsConfig instance.
     starknet_os_config: StarknetOsConfig,
+    // Whether transactions may name a paymaster (in their paymaster data) to pay their fee.
+    is_paymaster_enabled: felt,
     // A function pointer to the 'execute_syscalls' function.
     execute_syscalls_ptr: felt*,
     // A function pointer to the 'execute_deprecated_syscalls' function.
@@ -90,6 +92,7 @@ func get_block_context{range_check_ptr}(
             fee_token_address=nondet %{ os_hints_config.starknet_os_config.fee_token_address %},
             public_key_hash=public_key_hash,
         ),
+        is_paymaster_enabled=nondet %{ os_hints_config.starknet_os_config.is_paymaster_enabled %},
         execute_syscalls_ptr=execute_syscalls_ptr,
         execute_deprecated_syscalls_ptr=execute_deprecated_syscalls_ptr,
     );
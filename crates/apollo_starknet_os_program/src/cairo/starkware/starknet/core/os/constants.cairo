@@ -46,6 +46,11 @@ const VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR = (
     0x36fcbf06cd96843058359e1a75928beacfac10727dab22a3972f0af8aa92895
 );
 
+// get_selector_from_name('__validate_paymaster__').
+const VALIDATE_PAYMASTER_ENTRY_POINT_SELECTOR = (
+    0x1f7bf216232848ddeab0be9070e7506898fcbc3bccb2906d5e35576559251b5
+);
+
 // get_selector_from_name('transfer').
 const TRANSFER_ENTRY_POINT_SELECTOR = (
     0x83afd3f4caedc6eebf44246fe54e38c95e3179a5ec9ea81740eca5b482d12e
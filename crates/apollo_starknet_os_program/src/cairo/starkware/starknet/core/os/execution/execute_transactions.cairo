@@ -52,6 +52,7 @@ from starkware.starknet.core.os.constants import (
     VALIDATE_DECLARE_ENTRY_POINT_SELECTOR,
     VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR,
     VALIDATE_MAX_SIERRA_GAS,
+    VALIDATE_PAYMASTER_ENTRY_POINT_SELECTOR,
     VALIDATED,
 )
 from starkware.starknet.core.os.contract_address.contract_address import get_contract_address
@@ -305,9 +306,74 @@ func compute_max_possible_fee(tx_info: TxInfo*) -> felt {
         l1_data_gas_bounds.max_price_per_unit;
 }
 
+// Runs the "__validate_paymaster__" entry point of the transaction's paymaster, if it has one and
+// paymasters are enabled, which approves paying the transaction's fee. The paymaster is the first
+// element of the paymaster data, and it is called by the account with the transaction hash, the
+// maximal fee and the paymaster data.
+//
+// Arguments:
+// block_context - a global context that is fixed throughout the block.
+// tx_execution_context - The execution context of the account's validation.
+func run_validate_paymaster{
+    range_check_ptr,
+    remaining_gas: felt,
+    builtin_ptrs: BuiltinPointers*,
+    contract_state_changes: DictAccess*,
+    contract_class_changes: DictAccess*,
+    outputs: OsCarriedOutputs*,
+}(block_context: BlockContext*, tx_execution_context: ExecutionContext*) {
+    alloc_locals;
+    if (block_context.is_paymaster_enabled == 0) {
+        return ();
+    }
+    local tx_info: TxInfo* = tx_execution_context.execution_info.tx_info;
+    local paymaster_data_size = tx_info.paymaster_data_end - tx_info.paymaster_data_start;
+    if (paymaster_data_size == 0) {
+        return ();
+    }
+
+    let (local calldata: felt*) = alloc();
+    let max_fee = compute_max_possible_fee(tx_info=tx_info);
+    assert calldata[0] = tx_info.transaction_hash;
+    assert calldata[1] = max_fee;
+    assert calldata[2] = paymaster_data_size;
+    memcpy(dst=&calldata[3], src=tx_info.paymaster_data_start, len=paymaster_data_size);
+
+    local paymaster_address = tx_info.paymaster_data_start[0];
+    let (state_entry: StateEntry*) = dict_read{dict_ptr=contract_state_changes}(
+        key=paymaster_address
+    );
+    local validate_paymaster_execution_context: ExecutionContext* = new ExecutionContext(
+        entry_point_type=ENTRY_POINT_TYPE_EXTERNAL,
+        class_hash=state_entry.class_hash,
+        calldata_size=paymaster_data_size + 3,
+        calldata=calldata,
+        execution_info=new ExecutionInfo(
+            block_info=block_context.block_info_for_validate,
+            tx_info=tx_info,
+            caller_address=tx_info.account_contract_address,
+            contract_address=paymaster_address,
+            selector=VALIDATE_PAYMASTER_ENTRY_POINT_SELECTOR,
+        ),
+        deprecated_tx_info=tx_execution_context.deprecated_tx_info,
+    );
+
+    // The "__validate_paymaster__" function should not revert.
+    let (retdata_size, retdata, is_deprecated) = non_reverting_select_execute_entry_point_func(
+        block_context=block_context, execution_context=validate_paymaster_execution_context
+    );
+    if (is_deprecated == 0) {
+        assert retdata_size = 1;
+        assert retdata[0] = VALIDATED;
+    }
+
+    return ();
+}
+
 // Charges a fee from the user.
 // If max_fee is not 0, validates that the selector matches the entry point of an account contract
-// and executes an ERC20 transfer on the behalf of that account contract.
+// and executes an ERC20 transfer on the behalf of that account contract, or of the transaction's
+// paymaster if it has one and paymasters are enabled.
 //
 // Arguments:
 // block_context - a global context that is fixed throughout the block.
@@ -328,6 +394,15 @@ func charge_fee{
         return ();
     }
 
+    // The paymaster, if there is one, is the first element of the paymaster data.
+    local paymaster_data_size = tx_info.paymaster_data_end - tx_info.paymaster_data_start;
+    local fee_payer_address;
+    if (block_context.is_paymaster_enabled * paymaster_data_size == 0) {
+        assert fee_payer_address = tx_info.account_contract_address;
+    } else {
+        assert fee_payer_address = tx_info.paymaster_data_start[0];
+    }
+
     local calldata: TransferCallData = TransferCallData(
         recipient=block_context.block_info_for_execute.sequencer_address,
         amount=Uint256(low=nondet %{ execution_helper.tx_execution_info.actual_fee %}, high=0),
@@ -352,7 +427,7 @@ func charge_fee{
         execution_info=new ExecutionInfo(
             block_info=block_context.block_info_for_execute,
             tx_info=tx_info,
-            caller_address=tx_info.account_contract_address,
+            caller_address=fee_payer_address,
             contract_address=fee_token_address,
             selector=TRANSFER_ENTRY_POINT_SELECTOR,
         ),
@@ -485,6 +560,7 @@ func execute_invoke_function_transaction{
             execution_context=tx_execution_context,
             account_deployment_data_size=account_deployment_data_size,
             account_deployment_data=account_deployment_data,
+            is_paymaster_enabled=block_context.is_paymaster_enabled,
         );
     }
     update_poseidon_in_builtin_ptrs(poseidon_ptr=poseidon_ptr);
@@ -518,6 +594,9 @@ func execute_invoke_function_transaction{
         cap_remaining_gas(max_gas=VALIDATE_MAX_SIERRA_GAS);
         let pre_validate_gas = remaining_gas;
         run_validate(block_context=block_context, tx_execution_context=tx_execution_context);
+        run_validate_paymaster(
+            block_context=block_context, tx_execution_context=tx_execution_context
+        );
     }
     let validate_gas_consumed = pre_validate_gas - remaining_gas;
     tempvar remaining_gas = initial_user_gas_bound - validate_gas_consumed;
@@ -798,6 +877,7 @@ func execute_deploy_account_transaction{
             common_fields=common_tx_fields,
             calldata_size=validate_deploy_calldata_size,
             calldata=validate_deploy_calldata,
+            is_paymaster_enabled=block_context.is_paymaster_enabled,
         );
     }
     update_poseidon_in_builtin_ptrs(poseidon_ptr=poseidon_ptr);
@@ -874,6 +954,11 @@ func execute_deploy_account_transaction{
         assert retdata_size = 1;
         assert retdata[0] = VALIDATED;
     }
+    with remaining_gas {
+        run_validate_paymaster(
+            block_context=block_context, tx_execution_context=validate_deploy_execution_context
+        );
+    }
 
     // Charge fee.
     charge_fee(block_context=block_context, tx_execution_context=validate_deploy_execution_context);
@@ -925,6 +1010,7 @@ func execute_declare_transaction{
             compiled_class_hash=compiled_class_hash,
             account_deployment_data_size=account_deployment_data_size,
             account_deployment_data=account_deployment_data,
+            is_paymaster_enabled=block_context.is_paymaster_enabled,
         );
         %{
             assert ids.transaction_hash == tx.hash_value, (
@@ -1015,6 +1101,11 @@ func execute_declare_transaction{
         assert retdata_size = 1;
         assert retdata[0] = VALIDATED;
     }
+    with remaining_gas {
+        run_validate_paymaster(
+            block_context=block_context, tx_execution_context=validate_declare_execution_context
+        );
+    }
 
     // Declare the class hash.
     // Note that prev_value=0 enforces that a class may be declared only once.
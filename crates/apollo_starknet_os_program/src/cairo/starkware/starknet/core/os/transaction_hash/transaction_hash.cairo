@@ -148,11 +148,13 @@ func hash_fee_fields{range_check_ptr, poseidon_ptr: PoseidonBuiltin*}(
 
 func hash_tx_common_fields{
     range_check_ptr, poseidon_ptr: PoseidonBuiltin*, hash_state: PoseidonHashState
-}(common_fields: CommonTxFields*) {
+}(common_fields: CommonTxFields*, is_paymaster_enabled: felt) {
     alloc_locals;
 
-    // TODO(Noa, 01/01/2026): remove the following `assert` once paymaster is supported.
-    assert common_fields.paymaster_data_length = 0;
+    // Paymaster data is only supported on chains where paymasters are enabled.
+    if (is_paymaster_enabled == 0) {
+        assert common_fields.paymaster_data_length = 0;
+    }
 
     let fee_fields_hash = hash_fee_fields(
         tip=common_fields.tip,
@@ -188,6 +190,7 @@ func compute_invoke_transaction_hash{range_check_ptr, poseidon_ptr: PoseidonBuil
     execution_context: ExecutionContext*,
     account_deployment_data_size: felt,
     account_deployment_data: felt*,
+    is_paymaster_enabled: felt,
 ) -> felt {
     alloc_locals;
 
@@ -199,7 +202,9 @@ func compute_invoke_transaction_hash{range_check_ptr, poseidon_ptr: PoseidonBuil
 
     let hash_state: PoseidonHashState = poseidon_hash_init();
     with hash_state {
-        hash_tx_common_fields(common_fields=common_fields);
+        hash_tx_common_fields(
+            common_fields=common_fields, is_paymaster_enabled=is_paymaster_enabled
+        );
         poseidon_hash_update_with_nested_hash(
             data_ptr=account_deployment_data, data_length=account_deployment_data_size
         );
@@ -235,7 +240,7 @@ func compute_l1_handler_transaction_hash{pedersen_ptr: HashBuiltin*}(
 
 // See comment above `compute_invoke_transaction_hash()`.
 func compute_deploy_account_transaction_hash{range_check_ptr, poseidon_ptr: PoseidonBuiltin*}(
-    common_fields: CommonTxFields*, calldata_size: felt, calldata: felt*
+    common_fields: CommonTxFields*, calldata_size: felt, calldata: felt*, is_paymaster_enabled: felt
 ) -> felt {
     alloc_locals;
 
@@ -245,7 +250,9 @@ func compute_deploy_account_transaction_hash{range_check_ptr, poseidon_ptr: Pose
 
     let hash_state: PoseidonHashState = poseidon_hash_init();
     with hash_state {
-        hash_tx_common_fields(common_fields=common_fields);
+        hash_tx_common_fields(
+            common_fields=common_fields, is_paymaster_enabled=is_paymaster_enabled
+        );
         // Hash and add the constructor calldata to the hash state.
         poseidon_hash_update_with_nested_hash(data_ptr=&calldata[2], data_length=calldata_size - 2);
         // Add the class hash and the contract address salt to the hash state.
@@ -263,6 +270,7 @@ func compute_declare_transaction_hash{range_check_ptr, poseidon_ptr: PoseidonBui
     compiled_class_hash: felt,
     account_deployment_data_size: felt,
     account_deployment_data: felt*,
+    is_paymaster_enabled: felt,
 ) -> felt {
     alloc_locals;
 
@@ -274,7 +282,9 @@ func compute_declare_transaction_hash{range_check_ptr, poseidon_ptr: PoseidonBui
 
     let hash_state: PoseidonHashState = poseidon_hash_init();
     with hash_state {
-        hash_tx_common_fields(common_fields=common_fields);
+        hash_tx_common_fields(
+            common_fields=common_fields, is_paymaster_enabled=is_paymaster_enabled
+        );
         poseidon_hash_update_with_nested_hash(
             data_ptr=account_deployment_data, data_length=account_deployment_data_size
         );
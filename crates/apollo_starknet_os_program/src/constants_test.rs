@@ -4,7 +4,9 @@ use apollo_infra_utils::cairo0_compiler::cairo0_format;
 use apollo_infra_utils::compile_time_cargo_manifest_dir;
 use blockifier::blockifier_versioned_constants::{OsConstants, VersionedConstants};
 use blockifier::execution::syscalls::vm_syscall_utils::SyscallSelector;
+use starknet_api::abi::abi_utils::selector_from_name;
 use starknet_api::core::{ClassHash, ContractAddress, EntryPointSelector};
+use starknet_api::transaction::constants::VALIDATE_PAYMASTER_ENTRY_POINT_NAME;
 use starknet_types_core::felt::Felt;
 
 const CONSTANTS_CONTENTS: &str = include_str!("cairo/starkware/starknet/core/os/constants.cairo");
@@ -84,6 +86,8 @@ fn generate_constants_file() -> String {
             selector_to_hex(&os_constants.validate_declare_entry_point_selector),
         VALIDATE_DEPLOY_ENTRY_POINT_SELECTOR =
             selector_to_hex(&os_constants.validate_deploy_entry_point_selector),
+        VALIDATE_PAYMASTER_ENTRY_POINT_SELECTOR =
+            selector_to_hex(&selector_from_name(VALIDATE_PAYMASTER_ENTRY_POINT_NAME)),
         TRANSFER_ENTRY_POINT_SELECTOR =
             selector_to_hex(&os_constants.transfer_entry_point_selector),
         DEFAULT_ENTRY_POINT_SELECTOR = selector_to_hex(&os_constants.default_entry_point_selector),
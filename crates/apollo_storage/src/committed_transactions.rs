@@ -0,0 +1,84 @@
+//! Interface for handling the transactions of the blocks committed by the batcher, which are kept
+//! so that they can be returned to the mempool and the L1 provider if their block is reverted.
+//! This is a table separate from Papyrus storage; scope and version do not apply on it.
+//! Use carefully, only within batcher code, which is responsible for maintaining this table.
+//!
+//! Import [`CommittedTransactionsStorageReader`] and [`CommittedTransactionsStorageWriter`] to read
+//! and write data related to committed transactions using a [`StorageTxn`].
+
+use serde::{Deserialize, Serialize};
+use starknet_api::block::BlockNumber;
+use starknet_api::rpc_transaction::InternalRpcTransaction;
+use starknet_api::transaction::TransactionHash;
+
+use crate::db::table_types::Table;
+use crate::db::{TransactionKind, RW};
+use crate::{StorageResult, StorageTxn};
+
+#[cfg(test)]
+#[path = "committed_transactions_test.rs"]
+mod committed_transactions_test;
+
+/// The transactions of a block committed by the batcher.
+#[derive(Clone, Debug, Default, Eq, PartialEq, Serialize, Deserialize)]
+pub struct CommittedBlockTransactions {
+    /// The account transactions included in the block. Empty for blocks received from state sync,
+    /// which carry the transaction hashes only.
+    pub account_transactions: Vec<InternalRpcTransaction>,
+    /// The hashes of the L1 handler transactions consumed by the block.
+    pub l1_handler_transaction_hashes: Vec<TransactionHash>,
+}
+
+/// Interface for reading the transactions of committed blocks.
+pub trait CommittedTransactionsStorageReader {
+    /// Returns the transactions of the given block.
+    /// Returns `None` if the block's transactions are not stored.
+    fn get_committed_block_transactions(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<CommittedBlockTransactions>>;
+}
+
+/// Interface for writing the transactions of committed blocks.
+pub trait CommittedTransactionsStorageWriter
+where
+    Self: Sized,
+{
+    /// Stores the transactions of the given block, replacing any previously stored ones.
+    fn set_committed_block_transactions(
+        self,
+        block_number: BlockNumber,
+        transactions: &CommittedBlockTransactions,
+    ) -> StorageResult<Self>;
+
+    /// Removes the transactions of the given block, if stored.
+    fn delete_committed_block_transactions(self, block_number: BlockNumber) -> StorageResult<Self>;
+}
+
+impl<Mode: TransactionKind> CommittedTransactionsStorageReader for StorageTxn<'_, Mode> {
+    fn get_committed_block_transactions(
+        &self,
+        block_number: BlockNumber,
+    ) -> StorageResult<Option<CommittedBlockTransactions>> {
+        let table = self.open_table(&self.tables.committed_block_transactions)?;
+        Ok(table.get(&self.txn, &block_number)?)
+    }
+}
+
+impl CommittedTransactionsStorageWriter for StorageTxn<'_, RW> {
+    fn set_committed_block_transactions(
+        self,
+        block_number: BlockNumber,
+        transactions: &CommittedBlockTransactions,
+    ) -> StorageResult<Self> {
+        let table = self.open_table(&self.tables.committed_block_transactions)?;
+        table.upsert(&self.txn, &block_number, transactions)?;
+        Ok(self)
+    }
+
+    fn delete_committed_block_transactions(self, block_number: BlockNumber) -> StorageResult<Self> {
+        let table = self.open_table(&self.tables.committed_block_transactions)?;
+        table.delete(&self.txn, &block_number)?;
+        Ok(self)
+    }
+}
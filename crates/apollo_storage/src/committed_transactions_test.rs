@@ -0,0 +1,51 @@
+use starknet_api::block::BlockNumber;
+use starknet_api::test_utils::invoke::internal_invoke_tx;
+use starknet_api::{invoke_tx_args, tx_hash};
+
+use crate::committed_transactions::{
+    CommittedBlockTransactions,
+    CommittedTransactionsStorageReader,
+    CommittedTransactionsStorageWriter,
+};
+use crate::test_utils::get_test_storage;
+
+#[test]
+fn committed_transactions_storage() {
+    let ((reader, mut writer), _temp_dir) = get_test_storage();
+    let block_number = BlockNumber(5);
+
+    // Non-existent entry.
+    let transactions =
+        reader.begin_ro_txn().unwrap().get_committed_block_transactions(block_number).unwrap();
+    assert_eq!(transactions, None);
+
+    // Insert an entry.
+    let expected_transactions = CommittedBlockTransactions {
+        account_transactions: vec![internal_invoke_tx(invoke_tx_args!(tx_hash: tx_hash!(1)))],
+        l1_handler_transaction_hashes: vec![tx_hash!(2)],
+    };
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .set_committed_block_transactions(block_number, &expected_transactions)
+        .unwrap()
+        .commit()
+        .unwrap();
+
+    // Read the inserted entry.
+    let transactions =
+        reader.begin_ro_txn().unwrap().get_committed_block_transactions(block_number).unwrap();
+    assert_eq!(transactions, Some(expected_transactions));
+
+    // Delete the entry.
+    writer
+        .begin_rw_txn()
+        .unwrap()
+        .delete_committed_block_transactions(block_number)
+        .unwrap()
+        .commit()
+        .unwrap();
+    let transactions =
+        reader.begin_ro_txn().unwrap().get_committed_block_transactions(block_number).unwrap();
+    assert_eq!(transactions, None);
+}
@@ -80,6 +80,7 @@ pub mod body;
 pub mod class;
 pub mod class_hash;
 pub mod class_manager;
+pub mod committed_transactions;
 pub mod compiled_class;
 #[cfg(feature = "document_calls")]
 pub mod document_calls;
@@ -112,6 +113,7 @@ use apollo_config::{ParamPath, ParamPrivacyInput, SerializedParam};
 use apollo_proc_macros::latency_histogram;
 use body::events::EventIndex;
 use cairo_lang_starknet_classes::casm_contract_class::CasmContractClass;
+use committed_transactions::CommittedBlockTransactions;
 use db::db_stats::{DbTableStats, DbWholeStats};
 use db::serialization::{Key, NoVersionValueWrapper, ValueSerde, VersionZeroWrapper};
 use db::table_types::{CommonPrefix, NoValue, Table, TableType};
@@ -203,6 +205,10 @@ pub fn open_storage(
         compiled_class_hash: db_writer.create_common_prefix_table("compiled_class_hash")?,
         stateless_compiled_class_hash_v2: db_writer
             .create_simple_table("stateless_compiled_class_hash_v2")?,
+
+        // Transactions of committed blocks.
+        committed_block_transactions: db_writer
+            .create_simple_table("committed_block_transactions")?,
     });
     let (file_writers, file_readers) = open_storage_files(
         &storage_config.db_config,
@@ -555,7 +561,10 @@ struct_field_names! {
 
         // Compiled class hashes.
         compiled_class_hash: TableIdentifier<(ClassHash, BlockNumber), VersionZeroWrapper<CompiledClassHash>, CommonPrefix>,
-        stateless_compiled_class_hash_v2: TableIdentifier<ClassHash, NoVersionValueWrapper<CompiledClassHash>, SimpleTable>
+        stateless_compiled_class_hash_v2: TableIdentifier<ClassHash, NoVersionValueWrapper<CompiledClassHash>, SimpleTable>,
+
+        // Transactions of committed blocks.
+        committed_block_transactions: TableIdentifier<BlockNumber, NoVersionValueWrapper<CommittedBlockTransactions>, SimpleTable>
     }
 }
 
@@ -128,6 +128,7 @@ use tracing::warn;
 
 use crate::body::events::EventIndex;
 use crate::body::TransactionIndex;
+use crate::committed_transactions::CommittedBlockTransactions;
 use crate::compression_utils::{
     compress,
     decompress,
@@ -745,6 +746,20 @@ impl StorageSerde for serde_json::Value {
     }
 }
 
+// Committed block transactions are kept only to return them on a revert, so their serialization is
+// not optimized.
+impl StorageSerde for CommittedBlockTransactions {
+    fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
+        let bytes = serde_json::to_vec(self)?;
+        bytes.serialize_into(res)
+    }
+
+    fn deserialize_from(bytes: &mut impl std::io::Read) -> Option<Self> {
+        let buf = Vec::deserialize_from(bytes)?;
+        serde_json::from_slice(buf.as_slice()).ok()
+    }
+}
+
 impl StorageSerde for String {
     fn serialize_into(&self, res: &mut impl std::io::Write) -> Result<(), StorageSerdeError> {
         (self.as_bytes().to_vec()).serialize_into(res)
@@ -45,14 +45,20 @@ pub type StatefulValidatorResult<T> = Result<T, StatefulValidatorError>;
 #[cfg_attr(any(test, feature = "mocks"), mockall::automock)]
 pub trait StatefulValidatorTrait {
     #[allow(clippy::result_large_err)]
-    fn validate(&mut self, account_tx: AccountTransaction) -> StatefulValidatorResult<()>;
+    fn validate(
+        &mut self,
+        account_tx: AccountTransaction,
+    ) -> StatefulValidatorResult<Option<CallInfo>>;
     fn block_info(&self) -> &BlockInfo;
     #[allow(clippy::result_large_err)]
     fn get_nonce(&mut self, account_address: ContractAddress) -> StatefulValidatorResult<Nonce>;
 }
 
 impl<S: StateReader> StatefulValidatorTrait for StatefulValidator<S> {
-    fn validate(&mut self, account_tx: AccountTransaction) -> StatefulValidatorResult<()> {
+    fn validate(
+        &mut self,
+        account_tx: AccountTransaction,
+    ) -> StatefulValidatorResult<Option<CallInfo>> {
         self.perform_validations(account_tx)
     }
 
@@ -77,7 +83,11 @@ impl<S: StateReader> StatefulValidator<S> {
         Self { tx_executor }
     }
 
-    pub fn perform_validations(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<()> {
+    /// Returns the call info of the `__validate__` entry point, or `None` if it was not run.
+    pub fn perform_validations(
+        &mut self,
+        tx: AccountTransaction,
+    ) -> StatefulValidatorResult<Option<CallInfo>> {
         // Deploy account transaction should be fully executed, since the constructor must run
         // before `__validate_deploy__`. The execution already includes all necessary validations,
         // so they are skipped here.
@@ -90,16 +100,16 @@ impl<S: StateReader> StatefulValidator<S> {
         let tx_context = Arc::new(self.tx_executor.block_context.to_tx_context(&tx));
         tx.perform_pre_validation_stage(self.state(), &tx_context)?;
         if !tx.execution_flags.validate {
-            return Ok(());
+            return Ok(None);
         }
 
         // `__validate__` call.
-        let (_optional_call_info, actual_cost) = self.validate(&tx, tx_context.clone())?;
+        let (optional_call_info, actual_cost) = self.validate(&tx, tx_context.clone())?;
 
         // Post validations.
         PostValidationReport::verify(&tx_context, &actual_cost, tx.execution_flags.charge_fee)?;
 
-        Ok(())
+        Ok(optional_call_info)
     }
 
     pub fn block_info(&self) -> &BlockInfo {
@@ -110,9 +120,10 @@ impl<S: StateReader> StatefulValidator<S> {
         self.tx_executor.block_state.as_mut().expect(BLOCK_STATE_ACCESS_ERR)
     }
 
-    fn execute(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<()> {
-        self.tx_executor.execute(&Transaction::Account(tx))?;
-        Ok(())
+    fn execute(&mut self, tx: AccountTransaction) -> StatefulValidatorResult<Option<CallInfo>> {
+        let (tx_execution_info, _state_diff) =
+            self.tx_executor.execute(&Transaction::Account(tx))?;
+        Ok(tx_execution_info.validate_call_info)
     }
 
     fn validate(
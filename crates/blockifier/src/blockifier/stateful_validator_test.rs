@@ -121,5 +121,5 @@ fn test_tx_validator_conditional_validate(
     let mut stateful_validator = StatefulValidator::create(state, block_context);
     // The transaction validations should be skipped and the function should return Ok.
     let result = stateful_validator.perform_validations(tx);
-    assert_matches!(result, Ok(()));
+    assert_matches!(result, Ok(None));
 }
@@ -58,6 +58,10 @@ pub struct BlockExecutionSummary {
     pub state_diff: CommitmentStateDiff,
     pub compressed_state_diff: Option<CommitmentStateDiff>,
     pub bouncer_weights: BouncerWeights,
+    // The weights of each successfully executed transaction, in execution order.
+    pub tx_bouncer_weights: Vec<BouncerWeights>,
+    // The weights of the first transaction that did not fit in the block, if any.
+    pub rejected_tx_bouncer_weights: Option<BouncerWeights>,
     pub casm_hash_computation_data_sierra_gas: CasmHashComputationData,
     pub casm_hash_computation_data_proving_gas: CasmHashComputationData,
     pub compiled_class_hashes_for_migration: CompiledClassHashesForMigration,
@@ -274,6 +278,7 @@ pub(crate) fn finalize_block<S: StateReader>(
         mem::take(bouncer.get_mut_casm_hash_computation_data_sierra_gas());
     let casm_hash_computation_data_proving_gas =
         mem::take(bouncer.get_mut_casm_hash_computation_data_proving_gas());
+    let tx_bouncer_weights = mem::take(bouncer.get_mut_tx_bouncer_weights());
 
     assert_eq!(
         casm_hash_computation_data_sierra_gas
@@ -290,6 +295,8 @@ pub(crate) fn finalize_block<S: StateReader>(
         state_diff: state_diff.into(),
         compressed_state_diff,
         bouncer_weights: *bouncer.get_bouncer_weights(),
+        tx_bouncer_weights,
+        rejected_tx_bouncer_weights: bouncer.get_rejected_tx_bouncer_weights(),
         casm_hash_computation_data_sierra_gas,
         casm_hash_computation_data_proving_gas,
         compiled_class_hashes_for_migration: class_hashes_to_migrate.into_values().collect(),
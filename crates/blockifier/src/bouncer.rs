@@ -444,6 +444,10 @@ pub struct Bouncer {
     pub state_changes_keys: StateChangesKeys,
     pub bouncer_config: BouncerConfig,
     accumulated_weights: TxWeights,
+    // The weights of the transactions added to the block, in the order they were added.
+    tx_bouncer_weights: Vec<BouncerWeights>,
+    // The weights of the first transaction that did not fit in the block, if any.
+    rejected_tx_bouncer_weights: Option<BouncerWeights>,
 }
 
 impl Bouncer {
@@ -457,6 +461,8 @@ impl Bouncer {
             state_changes_keys: StateChangesKeys::default(),
             bouncer_config: BouncerConfig::empty(),
             accumulated_weights: TxWeights::empty(),
+            tx_bouncer_weights: Vec::new(),
+            rejected_tx_bouncer_weights: None,
         }
     }
 
@@ -464,6 +470,14 @@ impl Bouncer {
         &self.accumulated_weights.bouncer_weights
     }
 
+    pub fn get_mut_tx_bouncer_weights(&mut self) -> &mut Vec<BouncerWeights> {
+        &mut self.tx_bouncer_weights
+    }
+
+    pub fn get_rejected_tx_bouncer_weights(&self) -> Option<BouncerWeights> {
+        self.rejected_tx_bouncer_weights
+    }
+
     pub fn get_mut_casm_hash_computation_data_sierra_gas(
         &mut self,
     ) -> &mut CasmHashComputationData {
@@ -542,6 +556,7 @@ impl Bouncer {
                 tx_weights.bouncer_weights,
                 self.get_bouncer_weights()
             );
+            self.rejected_tx_bouncer_weights.get_or_insert(tx_bouncer_weights);
             Err(TransactionExecutorError::BlockFull)?
         }
 
@@ -566,6 +581,7 @@ impl Bouncer {
             .bouncer_weights
             .checked_add(tx_weights.bouncer_weights)
             .expect(&err_msg);
+        self.tx_bouncer_weights.push(tx_weights.bouncer_weights);
         self.accumulated_weights
             .casm_hash_computation_data_sierra_gas
             .extend(tx_weights.casm_hash_computation_data_sierra_gas);
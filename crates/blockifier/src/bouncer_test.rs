@@ -145,7 +145,9 @@ fn test_block_weights_has_room_n_txs(
     casm_hash_computation_data_proving_gas: CasmHashComputationData::empty(),
     // TODO(Meshi): Change to relevant test case when the migration is implemented.
     class_hashes_to_migrate: HashMap::default(),
-}
+},
+    tx_bouncer_weights: Vec::new(),
+    rejected_tx_bouncer_weights: None,
 })]
 fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
     // TODO(Aviv): Use expect! to avoid magic numbers.
@@ -206,6 +208,7 @@ fn test_bouncer_update(#[case] initial_bouncer: Bouncer) {
         .accumulated_weights
         .casm_hash_computation_data_proving_gas
         .extend(casm_hash_computation_data_proving_gas.clone());
+    expected_bouncer.tx_bouncer_weights.push(weights_to_update);
 
     assert_eq!(updated_bouncer, expected_bouncer);
 }
@@ -387,6 +390,11 @@ fn test_bouncer_try_update_n_txs(
     );
 
     assert_matches!(result, Err(TransactionExecutorError::BlockFull));
+    assert_eq!(bouncer.tx_bouncer_weights.len(), 1);
+    assert_matches!(
+        bouncer.get_rejected_tx_bouncer_weights(),
+        Some(BouncerWeights { n_txs: 1, .. })
+    );
 }
 
 /// This test verifies that `get_tx_weights` returns a reasonable casm hash computation data.
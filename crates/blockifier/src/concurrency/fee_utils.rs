@@ -30,8 +30,8 @@ pub fn complete_fee_transfer_flow(
     state: &mut impl UpdatableState,
     tx: &Transaction,
 ) {
-    if tx_context.is_sequencer_the_sender() {
-        // When the sequencer is the sender, we use the sequential (full) fee transfer.
+    if tx_context.is_sequencer_the_fee_payer() {
+        // When the sequencer is the fee payer, we use the sequential (full) fee transfer.
         return;
     }
 
@@ -57,7 +57,7 @@ pub fn complete_fee_transfer_flow(
             tx_execution_info.receipt.fee,
             &tx_context.block_context,
             sequencer_balance,
-            tx_context.tx_info.sender_address(),
+            tx_context.fee_payer_address(),
             state_diff,
         );
     } else {
@@ -108,12 +108,12 @@ pub fn add_fee_to_sequencer_balance(
     actual_fee: Fee,
     block_context: &BlockContext,
     sequencer_balance: (Felt, Felt),
-    sender_address: ContractAddress,
+    fee_payer_address: ContractAddress,
     state_diff: &mut StateMaps,
 ) {
     assert_ne!(
-        sender_address, block_context.block_info.sequencer_address,
-        "The sender cannot be the sequencer."
+        fee_payer_address, block_context.block_info.sequencer_address,
+        "The fee payer cannot be the sequencer."
     );
     let (low, high) = sequencer_balance;
     let sequencer_balance_low_as_u128 =
@@ -14,6 +14,7 @@ use starknet_api::transaction::fields::{
     Tip,
     ValidResourceBounds,
 };
+use starknet_types_core::felt::Felt;
 
 use crate::blockifier_versioned_constants::VersionedConstants;
 use crate::bouncer::BouncerConfig;
@@ -36,8 +37,30 @@ impl TransactionContext {
     pub fn fee_token_address(&self) -> ContractAddress {
         self.block_context.chain_info.fee_token_address(&self.tx_info.fee_type())
     }
-    pub fn is_sequencer_the_sender(&self) -> bool {
-        self.tx_info.sender_address() == self.block_context.block_info.sequencer_address
+    /// Returns the first element of the paymaster data, if paymasters are enabled on this chain
+    /// and the transaction names one. The element is not necessarily a valid contract address.
+    pub fn paymaster_data_head(&self) -> Option<Felt> {
+        if !self.block_context.chain_info.is_paymaster_enabled {
+            return None;
+        }
+        match &self.tx_info {
+            TransactionInfo::Current(CurrentTransactionInfo { paymaster_data, .. }) => {
+                paymaster_data.0.first().copied()
+            }
+            TransactionInfo::Deprecated(_) => None,
+        }
+    }
+    /// Returns the paymaster of the transaction, if it has a valid one.
+    pub fn paymaster_address(&self) -> Option<ContractAddress> {
+        self.paymaster_data_head().and_then(|head| ContractAddress::try_from(head).ok())
+    }
+    /// Returns the account charged for the transaction fee: the paymaster if there is one, and
+    /// the sender otherwise.
+    pub fn fee_payer_address(&self) -> ContractAddress {
+        self.paymaster_address().unwrap_or_else(|| self.tx_info.sender_address())
+    }
+    pub fn is_sequencer_the_fee_payer(&self) -> bool {
+        self.fee_payer_address() == self.block_context.block_info.sequencer_address
     }
     pub fn get_gas_vector_computation_mode(&self) -> GasVectorComputationMode {
         self.tx_info.gas_mode()
@@ -202,6 +225,11 @@ pub struct ChainInfo {
     pub fee_token_addresses: FeeTokenAddresses,
     #[serde(default)]
     pub is_l3: bool,
+    /// Whether transactions may name a paymaster (the first element of their paymaster data) to
+    /// validate them and pay their fee. Blocks containing such transactions must be proven with
+    /// the same flag in the OS chain info.
+    #[serde(default)]
+    pub is_paymaster_enabled: bool,
 }
 
 impl ChainInfo {
@@ -220,18 +248,28 @@ impl Default for ChainInfo {
             chain_id: ChainId::Other("0x0".to_string()),
             fee_token_addresses: FeeTokenAddresses::default(),
             is_l3: false,
+            is_paymaster_enabled: false,
         }
     }
 }
 
 impl SerializeConfig for ChainInfo {
     fn dump(&self) -> BTreeMap<ParamPath, SerializedParam> {
-        let members = BTreeMap::from_iter([ser_param(
-            "chain_id",
-            &self.chain_id,
-            "The chain ID of the StarkNet chain.",
-            ParamPrivacyInput::Public,
-        )]);
+        let members = BTreeMap::from_iter([
+            ser_param(
+                "chain_id",
+                &self.chain_id,
+                "The chain ID of the StarkNet chain.",
+                ParamPrivacyInput::Public,
+            ),
+            ser_param(
+                "is_paymaster_enabled",
+                &self.is_paymaster_enabled,
+                "Whether transactions may be validated and paid for by a paymaster. Must match \
+                 the flag the OS proves the blocks with.",
+                ParamPrivacyInput::Public,
+            ),
+        ]);
 
         vec![
             members,
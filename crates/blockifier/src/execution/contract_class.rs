@@ -245,6 +245,15 @@ impl RunnableCompiledClass {
         }
     }
 
+    pub fn bytecode_length(&self) -> usize {
+        match self {
+            Self::V0(class) => class.bytecode_length(),
+            Self::V1(class) => class.bytecode_length(),
+            #[cfg(feature = "cairo_native")]
+            Self::V1Native(class) => class.casm().bytecode_length(),
+        }
+    }
+
     pub fn estimate_casm_hash_computation_resources(&self) -> ExecutionResources {
         match self {
             Self::V0(class) => class.estimate_casm_hash_computation_resources(),
@@ -120,15 +120,15 @@ pub fn get_fee_by_gas_vector(
     gas_vector.cost(block_info.gas_prices.gas_price_vector(fee_type), tip)
 }
 
-/// Returns the current fee balance and a boolean indicating whether the balance covers the fee.
+/// Returns the current fee balance of the fee payer and a boolean indicating whether the balance
+/// covers the fee.
 pub fn get_balance_and_if_covers_fee(
     state: &mut dyn StateReader,
     tx_context: &TransactionContext,
     fee: Fee,
 ) -> TransactionFeeResult<(Felt, Felt, bool)> {
-    let tx_info = &tx_context.tx_info;
-    let (balance_low, balance_high) =
-        state.get_fee_token_balance(tx_info.sender_address(), tx_context.fee_token_address())?;
+    let (balance_low, balance_high) = state
+        .get_fee_token_balance(tx_context.fee_payer_address(), tx_context.fee_token_address())?;
     Ok((
         balance_low,
         balance_high,
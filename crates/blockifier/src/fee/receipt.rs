@@ -174,7 +174,8 @@ impl TransactionReceipt {
             signature_length: account_tx.signature_length(),
             code_size: account_tx.declare_code_size(),
             state_changes,
-            sender_address: Some(tx_context.tx_info.sender_address()),
+            // The fee transfer updates the balance of the fee payer.
+            sender_address: Some(tx_context.fee_payer_address()),
             l1_handler_payload_size: None,
             execution_summary_without_fee_transfer,
             tx_type: account_tx.tx_type(),
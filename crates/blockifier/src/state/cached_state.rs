@@ -698,7 +698,7 @@ impl StateChangesKeys {
         let actual_fee = tx_result.receipt.fee.0;
         let sequencer_address = tx_context.block_context.block_info.sequencer_address;
         if concurrency_mode
-            && !tx_context.is_sequencer_the_sender()
+            && !tx_context.is_sequencer_the_fee_payer()
             && tx_result.fee_transfer_call_info.is_some()
             && actual_fee > 0
         {
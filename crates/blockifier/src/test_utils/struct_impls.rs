@@ -185,6 +185,7 @@ impl ChainInfo {
                 strk_fee_token_address: contract_address!(TEST_ERC20_CONTRACT_ADDRESS2),
             },
             is_l3: false,
+            is_paymaster_enabled: false,
         }
     }
 }
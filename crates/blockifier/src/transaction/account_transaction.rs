@@ -56,6 +56,7 @@ use crate::transaction::errors::{
     TransactionPreValidationError,
 };
 use crate::transaction::objects::{
+    CurrentTransactionInfo,
     HasRelatedFeeType,
     RevertError,
     TransactionExecutionInfo,
@@ -255,6 +256,14 @@ impl AccountTransaction {
         let tx_info = &tx_context.tx_info;
         Self::handle_nonce(state, tx_info, self.execution_flags.strict_nonce_check)?;
 
+        if let Some(paymaster_data_head) = tx_context.paymaster_data_head() {
+            if tx_context.paymaster_address().is_none() {
+                return Err(TransactionPreValidationError::InvalidPaymasterAddress {
+                    paymaster_data_head,
+                });
+            }
+        }
+
         if self.execution_flags.charge_fee {
             self.check_fee_bounds(tx_context)?;
 
@@ -431,7 +440,8 @@ impl AccountTransaction {
 
         Self::assert_actual_fee_in_bounds(&tx_context, actual_fee);
 
-        let fee_transfer_call_info = if concurrency_mode && !tx_context.is_sequencer_the_sender() {
+        let fee_transfer_call_info = if concurrency_mode && !tx_context.is_sequencer_the_fee_payer()
+        {
             Self::concurrency_execute_fee_transfer(state, tx_context, actual_fee)?
         } else {
             Self::execute_fee_transfer(state, tx_context, actual_fee)?
@@ -450,7 +460,7 @@ impl AccountTransaction {
         // The most significant 128 bits of the amount transferred.
         let msb_amount = Felt::ZERO;
 
-        let TransactionContext { block_context, tx_info } = tx_context.as_ref();
+        let block_context = &tx_context.block_context;
         let storage_address = tx_context.fee_token_address();
         // The fee contains the cost of running this transfer, and the token contract is
         // well known to the sequencer, so there is no need to limit its run.
@@ -467,7 +477,7 @@ impl AccountTransaction {
                 msb_amount
             ],
             storage_address,
-            caller_address: tx_info.sender_address(),
+            caller_address: tx_context.fee_payer_address(),
             call_type: CallType::Call,
 
             initial_gas: remaining_gas_for_fee_transfer,
@@ -921,7 +931,7 @@ impl ValidatableTransaction for AccountTransaction {
         };
 
         // Note that we allow a revert here and we handle it bellow to get a better error message.
-        let validate_call_info = validate_call
+        let mut validate_call_info = validate_call
             .execute(state, &mut context, remaining_validation_gas)
             .map_err(|error| TransactionExecutionError::ValidateTransactionError {
                 error: Box::new(error),
@@ -929,38 +939,119 @@ impl ValidatableTransaction for AccountTransaction {
                 storage_address,
                 selector: validate_selector,
             })?;
-
-        // Validate return data.
-        let compiled_class = state.get_compiled_class(class_hash)?;
-        if is_cairo1(&compiled_class) {
-            // The account contract class is a Cairo 1.0 contract; the `validate` entry point should
-            // return `VALID`.
-            let expected_retdata = retdata![*constants::VALIDATE_RETDATA];
-
-            if validate_call_info.execution.failed {
-                return Err(TransactionExecutionError::PanicInValidate {
-                    panic_reason: extract_trailing_cairo1_revert_trace(
-                        &validate_call_info,
-                        Cairo1RevertHeader::Validation,
-                    ),
-                });
-            }
-
-            if validate_call_info.execution.retdata != expected_retdata {
-                return Err(TransactionExecutionError::InvalidValidateReturnData {
-                    actual: validate_call_info.execution.retdata,
-                });
-            }
-        } else if validate_call_info.execution.failed {
-            return Err(TransactionExecutionError::ValidateCairo0Error(
-                validate_call_info.execution.retdata,
-            ));
+        check_validate_call_result(state, class_hash, &validate_call_info)?;
+
+        let tx_context = Arc::clone(&context.tx_context);
+        if let (Some(paymaster_address), TransactionInfo::Current(current_tx_info)) =
+            (tx_context.paymaster_address(), &tx_context.tx_info)
+        {
+            let paymaster_call_info = Self::validate_paymaster(
+                state,
+                &mut context,
+                remaining_validation_gas,
+                paymaster_address,
+                current_tx_info,
+            )?;
+            // The paymaster validation is accounted for as if the account had called the
+            // paymaster at the end of its validation. The OS runs it as a separate entry point
+            // right after the account's validation, which matches the order of the calls.
+            validate_call_info.resources += &paymaster_call_info.resources;
+            validate_call_info.execution.gas_consumed += paymaster_call_info.execution.gas_consumed;
+            validate_call_info.inner_calls.push(paymaster_call_info);
         }
+
         remaining_gas.subtract_used_gas(&validate_call_info);
         Ok(Some(validate_call_info))
     }
 }
 
+impl AccountTransaction {
+    /// Runs the validation of the paymaster of the transaction, which approves paying its fee.
+    /// The paymaster is called by the account, with the transaction hash, the maximal fee the
+    /// transaction may be charged and the paymaster data as calldata.
+    fn validate_paymaster(
+        state: &mut dyn State,
+        context: &mut EntryPointExecutionContext,
+        remaining_validation_gas: &mut u64,
+        paymaster_address: ContractAddress,
+        tx_info: &CurrentTransactionInfo,
+    ) -> TransactionExecutionResult<CallInfo> {
+        // The maximal fee is computed with the signed tip, as the OS does.
+        let max_fee = tx_info.resource_bounds.max_possible_fee(tx_info.tip);
+        let paymaster_data = &tx_info.paymaster_data.0;
+        let calldata = Calldata(Arc::new(
+            [
+                tx_info.common_fields.transaction_hash.0,
+                max_fee.0.into(),
+                paymaster_data.len().into(),
+            ]
+            .into_iter()
+            .chain(paymaster_data.iter().copied())
+            .collect(),
+        ));
+        let sender_address = tx_info.common_fields.sender_address;
+
+        let class_hash = state.get_class_hash_at(paymaster_address)?;
+        let selector = selector_from_name(constants::VALIDATE_PAYMASTER_ENTRY_POINT_NAME);
+        let validate_paymaster_call = CallEntryPoint {
+            entry_point_type: EntryPointType::External,
+            entry_point_selector: selector,
+            calldata,
+            class_hash: None,
+            code_address: None,
+            storage_address: paymaster_address,
+            caller_address: sender_address,
+            call_type: CallType::Call,
+            initial_gas: *remaining_validation_gas,
+        };
+
+        let paymaster_call_info = validate_paymaster_call
+            .execute(state, context, remaining_validation_gas)
+            .map_err(|error| TransactionExecutionError::ValidateTransactionError {
+                error: Box::new(error),
+                class_hash,
+                storage_address: paymaster_address,
+                selector,
+            })?;
+        check_validate_call_result(state, class_hash, &paymaster_call_info)?;
+        Ok(paymaster_call_info)
+    }
+}
+
+/// Verifies that a validation call, of the given class, approved the transaction.
+fn check_validate_call_result(
+    state: &mut dyn State,
+    class_hash: ClassHash,
+    validate_call_info: &CallInfo,
+) -> TransactionExecutionResult<()> {
+    let compiled_class = state.get_compiled_class(class_hash)?;
+    if is_cairo1(&compiled_class) {
+        // The contract class is a Cairo 1.0 contract; the `validate` entry point should return
+        // `VALID`.
+        let expected_retdata = retdata![*constants::VALIDATE_RETDATA];
+
+        if validate_call_info.execution.failed {
+            return Err(TransactionExecutionError::PanicInValidate {
+                panic_reason: extract_trailing_cairo1_revert_trace(
+                    validate_call_info,
+                    Cairo1RevertHeader::Validation,
+                ),
+            });
+        }
+
+        if validate_call_info.execution.retdata != expected_retdata {
+            return Err(TransactionExecutionError::InvalidValidateReturnData {
+                actual: validate_call_info.execution.retdata.clone(),
+            });
+        }
+    } else if validate_call_info.execution.failed {
+        return Err(TransactionExecutionError::ValidateCairo0Error(
+            validate_call_info.execution.retdata.clone(),
+        ));
+    }
+    Ok(())
+}
+
 pub fn is_cairo1(compiled_class: &RunnableCompiledClass) -> bool {
     match compiled_class {
         RunnableCompiledClass::V0(_) => false,
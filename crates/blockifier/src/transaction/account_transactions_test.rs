@@ -18,6 +18,7 @@ use starknet_api::abi::abi_utils::{
     selector_from_name,
 };
 use starknet_api::block::{FeeType, GasPrice};
+use starknet_api::contract_class::EntryPointType;
 use starknet_api::core::{
     calculate_contract_address,
     ClassHash,
@@ -26,6 +27,7 @@ use starknet_api::core::{
     Nonce,
     PatriciaKey,
 };
+use starknet_api::deprecated_contract_class::EntryPointV0;
 use starknet_api::executable_transaction::{
     AccountTransaction as ApiExecutableTransaction,
     DeclareTransaction as ApiExecutableDeclareTransaction,
@@ -46,15 +48,21 @@ use starknet_api::test_utils::{
     DEFAULT_STRK_L2_GAS_PRICE,
     MAX_FEE,
 };
-use starknet_api::transaction::constants::TRANSFER_ENTRY_POINT_NAME;
+use starknet_api::transaction::constants::{
+    TRANSFER_ENTRY_POINT_NAME,
+    VALIDATE_ENTRY_POINT_NAME,
+    VALIDATE_PAYMASTER_ENTRY_POINT_NAME,
+};
 use starknet_api::transaction::fields::{
     AllResourceBounds,
     Calldata,
     ContractAddressSalt,
     Fee,
     GasVectorComputationMode,
+    PaymasterData,
     Resource,
     ResourceBounds,
+    Tip,
     ValidResourceBounds,
 };
 use starknet_api::transaction::{
@@ -74,12 +82,13 @@ use starknet_api::{
     invoke_tx_args,
     nonce,
     storage_key,
+    tx_hash,
 };
 use starknet_types_core::felt::Felt;
 
-use crate::context::{BlockContext, TransactionContext};
+use crate::context::{BlockContext, ChainInfo, TransactionContext};
 use crate::execution::call_info::CallInfo;
-use crate::execution::contract_class::TrackedResource;
+use crate::execution::contract_class::{CompiledClassV0, RunnableCompiledClass, TrackedResource};
 use crate::execution::entry_point::{EntryPointExecutionContext, SierraGasRevertTracker};
 use crate::execution::syscalls::hint_processor::ENTRYPOINT_NOT_FOUND_ERROR;
 use crate::execution::syscalls::vm_syscall_utils::SyscallSelector;
@@ -2163,3 +2172,186 @@ fn test_missing_validate_entrypoint_rejects(
         if ret == retdata![Felt::from_hex(ENTRYPOINT_NOT_FOUND_ERROR).unwrap()]
     );
 }
+
+/// Returns a state with an account and a funded test contract, which serves as a paymaster, along
+/// with their addresses.
+fn paymaster_test_state(
+    chain_info: &ChainInfo,
+) -> (CachedState<DictStateReader>, ContractAddress, ContractAddress) {
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let mut state = test_state(chain_info, BALANCE, &[(account, 1u16), (test_contract, 1u16)]);
+    let paymaster_address = test_contract.get_instance_address(0_u16);
+    fund_account(chain_info, paymaster_address, BALANCE, &mut state.state);
+    (state, account.get_instance_address(0_u16), paymaster_address)
+}
+
+#[rstest]
+fn test_paymaster_data_ignored_when_paymaster_disabled(
+    block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    let chain_info = &block_context.chain_info;
+    assert!(!chain_info.is_paymaster_enabled);
+    let fee_token_address = chain_info.fee_token_address(&FeeType::Strk);
+    let (mut state, account_address, paymaster_address) = paymaster_test_state(chain_info);
+    let tx_args = invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_trivial_calldata(paymaster_address),
+        resource_bounds: default_all_resource_bounds,
+        paymaster_data: PaymasterData(vec![*paymaster_address.0.key()]),
+    };
+
+    let tx_execution_info = run_invoke_tx(&mut state, &block_context, tx_args).unwrap();
+    assert!(!tx_execution_info.is_reverted());
+
+    // The sender pays the fee.
+    assert_eq!(
+        state.get_fee_token_balance(account_address, fee_token_address).unwrap(),
+        (felt!(BALANCE.0 - tx_execution_info.receipt.fee.0), felt!(0_u8))
+    );
+    assert_eq!(
+        state.get_fee_token_balance(paymaster_address, fee_token_address).unwrap(),
+        (felt!(BALANCE.0), felt!(0_u8))
+    );
+}
+
+#[rstest]
+fn test_paymaster_without_validate_entrypoint_rejects(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    block_context.chain_info.is_paymaster_enabled = true;
+    let chain_info = &block_context.chain_info;
+    let fee_token_address = chain_info.fee_token_address(&FeeType::Strk);
+    let (mut state, account_address, paymaster_address) = paymaster_test_state(chain_info);
+    let tx_args = invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_trivial_calldata(paymaster_address),
+        resource_bounds: default_all_resource_bounds,
+        paymaster_data: PaymasterData(vec![*paymaster_address.0.key()]),
+    };
+
+    let error = run_invoke_tx(&mut state, &block_context, tx_args).unwrap_err();
+    assert_matches!(
+        error,
+        TransactionExecutionError::ValidateCairo0Error(ret)
+        if ret == retdata![Felt::from_hex(ENTRYPOINT_NOT_FOUND_ERROR).unwrap()]
+    );
+    assert_eq!(
+        state.get_fee_token_balance(paymaster_address, fee_token_address).unwrap(),
+        (felt!(BALANCE.0), felt!(0_u8))
+    );
+}
+
+#[rstest]
+fn test_invalid_paymaster_address_rejects(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    block_context.chain_info.is_paymaster_enabled = true;
+    let (mut state, account_address, paymaster_address) =
+        paymaster_test_state(&block_context.chain_info);
+    let tx_args = invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_trivial_calldata(paymaster_address),
+        resource_bounds: default_all_resource_bounds,
+        paymaster_data: PaymasterData(vec![Felt::MAX]),
+    };
+
+    let error = run_invoke_tx(&mut state, &block_context, tx_args).unwrap_err();
+    assert_matches!(
+        error,
+        TransactionExecutionError::TransactionPreValidationError(boxed_error)
+        if matches!(
+            *boxed_error,
+            TransactionPreValidationError::InvalidPaymasterAddress { paymaster_data_head }
+            if paymaster_data_head == Felt::MAX
+        )
+    );
+}
+
+/// Returns a paymaster class which approves every transaction. `__validate_paymaster__` has the
+/// same signature as an account's `__validate__`, so the class is the dummy account's, with
+/// `__validate_paymaster__` mapped to its `__validate__` entry point.
+fn dummy_paymaster_class() -> RunnableCompiledClass {
+    let RunnableCompiledClass::V0(account_class) =
+        FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0).get_runnable_class()
+    else {
+        panic!("The dummy account should be a Cairo 0 class.");
+    };
+    let mut paymaster_class = (*account_class.0).clone();
+    let external_entry_points =
+        paymaster_class.entry_points_by_type.get_mut(&EntryPointType::External).unwrap();
+    let validate_selector = selector_from_name(VALIDATE_ENTRY_POINT_NAME);
+    let validate_offset =
+        external_entry_points.iter().find(|ep| ep.selector == validate_selector).unwrap().offset;
+    external_entry_points.push(EntryPointV0 {
+        selector: selector_from_name(VALIDATE_PAYMASTER_ENTRY_POINT_NAME),
+        offset: validate_offset,
+    });
+    RunnableCompiledClass::V0(CompiledClassV0(Arc::new(paymaster_class)))
+}
+
+#[rstest]
+fn test_paymaster_validates_and_pays_fee(
+    mut block_context: BlockContext,
+    default_all_resource_bounds: ValidResourceBounds,
+) {
+    block_context.chain_info.is_paymaster_enabled = true;
+    let chain_info = &block_context.chain_info;
+    let fee_token_address = chain_info.fee_token_address(&FeeType::Strk);
+    let account = FeatureContract::AccountWithoutValidations(CairoVersion::Cairo0);
+    let test_contract = FeatureContract::TestContract(CairoVersion::Cairo0);
+    let mut state = test_state(chain_info, BALANCE, &[(account, 1u16), (test_contract, 1u16)]);
+    let account_address = account.get_instance_address(0_u16);
+    let paymaster_address = contract_address!("0x7a11e7");
+    let paymaster_class_hash = class_hash!("0x7a11e7");
+    state.state.address_to_class_hash.insert(paymaster_address, paymaster_class_hash);
+    state.state.class_hash_to_class.insert(paymaster_class_hash, dummy_paymaster_class());
+    fund_account(chain_info, paymaster_address, BALANCE, &mut state.state);
+
+    let tx_hash = tx_hash!(0x7a5);
+    let paymaster_data = vec![*paymaster_address.0.key(), felt!(7_u8)];
+    let tx_args = invoke_tx_args! {
+        sender_address: account_address,
+        calldata: create_trivial_calldata(test_contract.get_instance_address(0_u16)),
+        resource_bounds: default_all_resource_bounds,
+        paymaster_data: PaymasterData(paymaster_data.clone()),
+        tx_hash,
+    };
+    let tx_execution_info = run_invoke_tx(&mut state, &block_context, tx_args).unwrap();
+    assert!(!tx_execution_info.is_reverted());
+
+    // The account calls the paymaster at the end of its validation.
+    let validate_call_info = tx_execution_info.validate_call_info.unwrap();
+    let paymaster_call = &validate_call_info.inner_calls.last().unwrap().call;
+    let max_fee = default_all_resource_bounds.max_possible_fee(Tip::default());
+    let expected_calldata: Vec<_> = [tx_hash.0, max_fee.0.into(), paymaster_data.len().into()]
+        .into_iter()
+        .chain(paymaster_data)
+        .collect();
+    assert_eq!(paymaster_call.storage_address, paymaster_address);
+    assert_eq!(paymaster_call.caller_address, account_address);
+    assert_eq!(
+        paymaster_call.entry_point_selector,
+        selector_from_name(VALIDATE_PAYMASTER_ENTRY_POINT_NAME)
+    );
+    assert_eq!(paymaster_call.calldata.0.as_slice(), expected_calldata.as_slice());
+
+    // The paymaster pays the fee.
+    let fee = tx_execution_info.receipt.fee;
+    assert_ne!(fee, Fee(0));
+    assert_eq!(
+        tx_execution_info.fee_transfer_call_info.unwrap().call.caller_address,
+        paymaster_address
+    );
+    assert_eq!(
+        state.get_fee_token_balance(paymaster_address, fee_token_address).unwrap(),
+        (felt!(BALANCE.0 - fee.0), felt!(0_u8))
+    );
+    assert_eq!(
+        state.get_fee_token_balance(account_address, fee_token_address).unwrap(),
+        (felt!(BALANCE.0), felt!(0_u8))
+    );
+}
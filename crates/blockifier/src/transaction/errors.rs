@@ -6,7 +6,7 @@ use starknet_api::execution_resources::GasAmount;
 use starknet_api::transaction::fields::{AllResourceBounds, Fee, Resource};
 use starknet_api::transaction::TransactionVersion;
 use starknet_api::StarknetApiError;
-use starknet_types_core::felt::FromStrError;
+use starknet_types_core::felt::{Felt, FromStrError};
 use thiserror::Error;
 
 use crate::bouncer::BouncerWeights;
@@ -140,6 +140,8 @@ pub enum TransactionPreValidationError {
          {:#064x}; got: {:#064x}.", ***address, **account_nonce, **incoming_tx_nonce
     )]
     InvalidNonce { address: ContractAddress, account_nonce: Nonce, incoming_tx_nonce: Nonce },
+    #[error("Invalid paymaster address: {paymaster_data_head:#064x}.")]
+    InvalidPaymasterAddress { paymaster_data_head: Felt },
     #[error(transparent)]
     StateError(#[from] StateError),
     #[error(transparent)]
@@ -71,6 +71,7 @@ pub fn get_chain_info(chain_id: &ChainId) -> ChainInfo {
         chain_id: chain_id.clone(),
         fee_token_addresses: get_fee_token_addresses(chain_id),
         is_l3: false,
+        is_paymaster_enabled: false,
     }
 }
 
@@ -1,2 +1,4 @@
-#[derive(Debug, PartialEq, Eq, Copy, Clone, Ord, PartialOrd)]
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, PartialEq, Eq, Copy, Clone, Ord, PartialOrd, Serialize, Deserialize)]
 pub struct StakingWeight(pub u128);
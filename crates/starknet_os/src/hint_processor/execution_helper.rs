@@ -112,17 +112,30 @@ pub struct TransactionExecutionInfoReference<'a> {
     pub tx_execution_info: &'a CentralTransactionExecutionInfo,
     call_info_iter: CallInfoIter<'a>,
     pub call_info_tracker: Option<CallInfoTracker<'a>>,
+    // The validation of the transaction's paymaster. It is recorded as the last inner call of the
+    // account's validation, but the OS runs it as a separate entry point right after it.
+    paymaster_call_info: Option<&'a CallInfo>,
 }
 
 impl<'a> TransactionExecutionInfoReference<'a> {
     pub fn new(
         tx_execution_info: &'a CentralTransactionExecutionInfo,
         tx_type: TransactionType,
+        has_paymaster: bool,
     ) -> Self {
+        let paymaster_call_info = if has_paymaster {
+            tx_execution_info
+                .validate_call_info
+                .as_ref()
+                .and_then(|validate_call_info| validate_call_info.inner_calls.last())
+        } else {
+            None
+        };
         Self {
             tx_execution_info,
             call_info_iter: tx_execution_info.call_info_iter(tx_type),
             call_info_tracker: None,
+            paymaster_call_info,
         }
     }
 
@@ -140,8 +153,18 @@ impl<'a> TransactionExecutionInfoReference<'a> {
             .call_info_iter
             .next()
             .ok_or(ExecutionHelperError::EndOfIterator { item_type: "call_info".to_string() })?;
-        self.call_info_tracker =
-            Some(CallInfoTracker::new(next_call_info, execution_info_ptr, deprecated_tx_info_ptr));
+        let is_validate_call = self
+            .tx_execution_info
+            .validate_call_info
+            .as_ref()
+            .is_some_and(|validate_call_info| std::ptr::eq(validate_call_info, next_call_info));
+        let detached_inner_call = if is_validate_call { self.paymaster_call_info } else { None };
+        self.call_info_tracker = Some(CallInfoTracker::new(
+            next_call_info,
+            detached_inner_call,
+            execution_info_ptr,
+            deprecated_tx_info_ptr,
+        ));
         Ok(())
     }
 
@@ -172,7 +195,11 @@ impl<'a> TransactionExecutionIter<'a> {
         Self { tx_execution_info_iter: tx_execution_infos.iter(), tx_execution_info_ref: None }
     }
 
-    pub fn start_tx(&mut self, tx_type: TransactionType) -> Result<(), ExecutionHelperError> {
+    pub fn start_tx(
+        &mut self,
+        tx_type: TransactionType,
+        has_paymaster: bool,
+    ) -> Result<(), ExecutionHelperError> {
         if self.tx_execution_info_ref.is_some() {
             return Err(ExecutionHelperError::ContextOverwrite {
                 context: "transaction execution info".to_string(),
@@ -182,8 +209,11 @@ impl<'a> TransactionExecutionIter<'a> {
             .tx_execution_info_iter
             .next()
             .ok_or(ExecutionHelperError::MissingTxExecutionInfo)?;
-        self.tx_execution_info_ref =
-            Some(TransactionExecutionInfoReference::new(next_tx_execution_info, tx_type));
+        self.tx_execution_info_ref = Some(TransactionExecutionInfoReference::new(
+            next_tx_execution_info,
+            tx_type,
+            has_paymaster,
+        ));
         Ok(())
     }
 
@@ -211,7 +241,7 @@ impl<'a> TransactionExecutionIter<'a> {
         // The transaction type determines in which order the inner calls were executed.
         // Since the transaction is skipped, the transaction's type doesn't matter.
         let dummy_tx_type = TransactionType::Declare;
-        self.start_tx(dummy_tx_type)?;
+        self.start_tx(dummy_tx_type, false)?;
         self.end_tx()?;
         Ok(())
     }
@@ -219,6 +249,8 @@ impl<'a> TransactionExecutionIter<'a> {
 
 pub struct CallInfoTracker<'a> {
     pub call_info: &'a CallInfo,
+    // The last inner call of the call, if the OS runs it separately rather than from the call.
+    pub detached_inner_call: Option<&'a CallInfo>,
     pub deployed_contracts_iterator: Box<dyn Iterator<Item = ContractAddress> + 'a>,
     pub inner_calls_iterator: Iter<'a, CallInfo>,
     pub execute_code_read_iterator: Iter<'a, Felt>,
@@ -241,19 +273,23 @@ macro_rules! next_iterator_method {
 impl<'a> CallInfoTracker<'a> {
     pub fn new(
         call_info: &'a CallInfo,
+        detached_inner_call: Option<&'a CallInfo>,
         execution_info_ptr: Relocatable,
         deprecated_tx_info_ptr: Relocatable,
     ) -> Self {
+        let n_inner_calls =
+            call_info.inner_calls.len() - usize::from(detached_inner_call.is_some());
+        let inner_calls = &call_info.inner_calls[..n_inner_calls];
         Self {
             call_info,
+            detached_inner_call,
             deployed_contracts_iterator: Box::new(
-                call_info
-                    .inner_calls
+                inner_calls
                     .iter()
                     .filter(|inner| inner.call.entry_point_type == EntryPointType::Constructor)
                     .map(|inner| inner.call.storage_address),
             ),
-            inner_calls_iterator: call_info.inner_calls.iter(),
+            inner_calls_iterator: inner_calls.iter(),
             execute_code_read_iterator: call_info.storage_access_tracker.storage_read_values.iter(),
             execute_code_class_hash_read_iterator: call_info
                 .storage_access_tracker
@@ -268,6 +304,13 @@ impl<'a> CallInfoTracker<'a> {
         }
     }
 
+    /// Returns the gas consumed by the call, excluding the detached inner call.
+    pub fn gas_consumed(&self) -> u64 {
+        let detached_gas_consumed =
+            self.detached_inner_call.map_or(0, |inner_call| inner_call.execution.gas_consumed);
+        self.call_info.execution.gas_consumed - detached_gas_consumed
+    }
+
     pub fn verify_exhausted_iterators(&mut self) -> Result<(), ExecutionHelperError> {
         let mut unexhausteds_iterators = Vec::new();
 
@@ -35,6 +35,7 @@ use crate::hints::hint_implementation::block_context::{
     chain_id,
     fee_token_address,
     get_block_mapping,
+    is_paymaster_enabled,
     sequencer_address,
     write_use_kzg_da_to_memory,
 };
@@ -1085,6 +1086,12 @@ define_hint_enum!(
         fee_token_address,
         "memory[ap] = to_felt_or_relocatable(os_hints_config.starknet_os_config.fee_token_address)"
     ),
+    (
+        IsPaymasterEnabled,
+        is_paymaster_enabled,
+        "memory[ap] = \
+         to_felt_or_relocatable(os_hints_config.starknet_os_config.is_paymaster_enabled)"
+    ),
     (
         SequencerAddress,
         sequencer_address,
@@ -46,6 +46,14 @@ pub(crate) fn fee_token_address<S: StateReader>(
     Ok(insert_value_into_ap(vm, strk_fee_token_address.0.key())?)
 }
 
+pub(crate) fn is_paymaster_enabled<S: StateReader>(
+    hint_processor: &mut SnosHintProcessor<'_, S>,
+    HintArgs { vm, .. }: HintArgs<'_>,
+) -> OsHintResult {
+    let is_paymaster_enabled = hint_processor.os_hints_config.chain_info.is_paymaster_enabled;
+    Ok(insert_value_into_ap(vm, Felt::from(is_paymaster_enabled))?)
+}
+
 pub(crate) fn sequencer_address<S: StateReader>(
     hint_processor: &mut SnosHintProcessor<'_, S>,
     HintArgs { vm, .. }: HintArgs<'_>,
@@ -8,7 +8,7 @@ use cairo_vm::hint_processor::builtin_hint_processor::hint_utils::{
 use cairo_vm::hint_processor::hint_processor_utils::felt_to_usize;
 use cairo_vm::types::relocatable::{MaybeRelocatable, Relocatable};
 use num_traits::ToPrimitive;
-use starknet_api::executable_transaction::AccountTransaction;
+use starknet_api::executable_transaction::{AccountTransaction, Transaction};
 use starknet_types_core::felt::Felt;
 
 use crate::hint_processor::snos_hint_processor::SnosHintProcessor;
@@ -152,8 +152,17 @@ pub(crate) fn start_tx<S: StateReader>(
     hint_processor: &mut SnosHintProcessor<'_, S>,
     HintArgs { .. }: HintArgs<'_>,
 ) -> OsHintResult {
-    let tx_type = hint_processor.get_current_execution_helper()?.tx_tracker.get_tx()?.tx_type();
-    hint_processor.get_mut_current_execution_helper()?.tx_execution_iter.start_tx(tx_type)?;
+    let tx = hint_processor.get_current_execution_helper()?.tx_tracker.get_tx()?;
+    let tx_type = tx.tx_type();
+    // The OS runs the validation of a paymaster for every transaction with paymaster data.
+    let has_paymaster = match tx {
+        Transaction::Account(account_tx) => !account_tx.paymaster_data().0.is_empty(),
+        Transaction::L1Handler(_) => false,
+    };
+    hint_processor
+        .get_mut_current_execution_helper()?
+        .tx_execution_iter
+        .start_tx(tx_type, has_paymaster)?;
     Ok(())
 }
 
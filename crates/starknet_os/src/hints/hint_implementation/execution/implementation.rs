@@ -589,14 +589,13 @@ pub(crate) fn check_execution<S: StateReader>(
         )?)?;
         let actual_gas = remaining_gas - *gas_builtin;
 
-        let call_info = current_execution_helper
+        let call_info_tracker = current_execution_helper
             .tx_execution_iter
             .get_tx_execution_info_ref()?
-            .get_call_info_tracker()?
-            .call_info;
-        let mut predicted = Felt::from(call_info.execution.gas_consumed);
+            .get_call_info_tracker()?;
+        let mut predicted = Felt::from(call_info_tracker.gas_consumed());
 
-        match call_info.tracked_resource {
+        match call_info_tracker.call_info.tracked_resource {
             TrackedResource::SierraGas => {
                 let initial_budget = Const::EntryPointInitialBudget.fetch(constants)?;
                 predicted -= initial_budget;
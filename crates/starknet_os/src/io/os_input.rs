@@ -73,6 +73,9 @@ pub struct OsChainInfo {
     #[cfg_attr(feature = "deserialize", serde(deserialize_with = "deserialize_chain_id_from_hex"))]
     pub chain_id: ChainId,
     pub strk_fee_token_address: ContractAddress,
+    // Not part of the OS config hash.
+    #[cfg_attr(feature = "deserialize", serde(default))]
+    pub is_paymaster_enabled: bool,
 }
 
 impl Default for OsChainInfo {
@@ -80,6 +83,7 @@ impl Default for OsChainInfo {
         OsChainInfo {
             chain_id: ChainId::Other("0x0".to_string()),
             strk_fee_token_address: ContractAddress::default(),
+            is_paymaster_enabled: false,
         }
     }
 }
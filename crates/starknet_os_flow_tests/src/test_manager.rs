@@ -301,6 +301,7 @@ impl<S: FlowTestState> TestManager<S> {
         OsChainInfo {
             chain_id: first_chain_info.chain_id.clone(),
             strk_fee_token_address: first_chain_info.fee_token_addresses.strk_fee_token_address,
+            is_paymaster_enabled: first_chain_info.is_paymaster_enabled,
         }
     }
 